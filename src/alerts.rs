@@ -0,0 +1,406 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use chrono::Local;
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use log::{info, warn};
+use crate::known_folders;
+use crate::notifications::NotificationSource;
+use crate::{toast, DashboardCard, DevDashboard};
+
+/// Between repeated notifications while a rule keeps firing
+const RENOTIFY_AFTER: Duration = Duration::from_secs(30 * 60);
+/// Format version of exported rule packs
+const RULE_PACK_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum AlertMetric {
+    CpuUsage,
+    CpuTemperature,
+    MemoryUsage,
+    DiskFree,                       // Gigabytes free on one drive, or the fullest drive
+    GpuUsage,
+    GpuTemperature,
+}
+
+impl AlertMetric {
+    const ALL: [AlertMetric; 6] = [
+        AlertMetric::CpuUsage,
+        AlertMetric::CpuTemperature,
+        AlertMetric::MemoryUsage,
+        AlertMetric::DiskFree,
+        AlertMetric::GpuUsage,
+        AlertMetric::GpuTemperature,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            AlertMetric::CpuUsage => "CPU usage",
+            AlertMetric::CpuTemperature => "CPU temperature",
+            AlertMetric::MemoryUsage => "Memory usage",
+            AlertMetric::DiskFree => "Free disk space",
+            AlertMetric::GpuUsage => "GPU usage",
+            AlertMetric::GpuTemperature => "GPU temperature",
+        }
+    }
+
+    fn unit(&self) -> &'static str {
+        match self {
+            AlertMetric::CpuUsage | AlertMetric::MemoryUsage | AlertMetric::GpuUsage => "%",
+            AlertMetric::CpuTemperature | AlertMetric::GpuTemperature => "°C",
+            AlertMetric::DiskFree => " GB",
+        }
+    }
+
+    /// The card that shows the badge while a rule on this reading fires
+    fn card(&self) -> DashboardCard {
+        match self {
+            AlertMetric::CpuUsage | AlertMetric::CpuTemperature => DashboardCard::Cpu,
+            AlertMetric::MemoryUsage => DashboardCard::Memory,
+            AlertMetric::DiskFree => DashboardCard::Storage,
+            AlertMetric::GpuUsage | AlertMetric::GpuTemperature => DashboardCard::Gpu,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Above,
+    Below,
+}
+
+impl Comparison {
+    fn symbol(&self) -> &'static str {
+        match self {
+            Comparison::Above => ">",
+            Comparison::Below => "<",
+        }
+    }
+
+    fn holds(&self, value: f32, threshold: f32) -> bool {
+        match self {
+            Comparison::Above => value > threshold,
+            Comparison::Below => value < threshold,
+        }
+    }
+}
+
+/// Fires when the reading stays past the threshold for the duration, e.g. CPU usage > 90% for 60 s
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct AlertRule {
+    pub enabled: bool,
+    pub metric: AlertMetric,
+    pub drive: String,              // Drive letter for free disk space; empty for whichever drive is lowest
+    pub comparison: Comparison,
+    pub threshold: f32,
+    pub seconds: u32,               // How long the condition has to hold; 0 fires on the first reading
+}
+
+impl Default for AlertRule {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            metric: AlertMetric::CpuUsage,
+            drive: String::new(),
+            comparison: Comparison::Above,
+            threshold: 90.0,
+            seconds: 60,
+        }
+    }
+}
+
+impl AlertRule {
+    fn describe(&self) -> String {
+        let subject = match (self.metric, self.drive.trim()) {
+            (AlertMetric::DiskFree, "") => "Free space on a drive".to_string(),
+            (AlertMetric::DiskFree, drive) => format!("Free space on {}:", drive.trim_end_matches(':').to_uppercase()),
+            (metric, _) => metric.label().to_string(),
+        };
+        let mut text = format!("{} {} {}{}", subject, self.comparison.symbol(), self.threshold, self.metric.unit());
+        if self.seconds > 0 {
+            text.push_str(&format!(" for {} s", self.seconds));
+        }
+        text
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct AlertSettings {
+    pub enabled: bool,
+    pub notify: bool,               // Windows notification when a rule fires, besides the card badge
+    pub rules: Vec<AlertRule>,
+}
+
+impl Default for AlertSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            notify: true,
+            rules: vec![
+                AlertRule::default(),
+                AlertRule { metric: AlertMetric::DiskFree, comparison: Comparison::Below, threshold: 10.0, seconds: 0, ..Default::default() },
+                AlertRule { metric: AlertMetric::GpuTemperature, threshold: 85.0, seconds: 30, ..Default::default() },
+            ],
+        }
+    }
+}
+
+/// Alert rules on their own, for sharing a standard set across machines without the rest of the settings
+#[derive(Serialize, Deserialize)]
+struct RulePack {
+    version: u32,
+    rules: Vec<AlertRule>,
+}
+
+/// Reads a rule pack, refusing one from a newer version whose rules may not mean the same here
+fn read_rule_pack(path: &Path) -> Result<Vec<AlertRule>, String> {
+    let contents = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let pack: RulePack = serde_json::from_str(&contents).map_err(|e| format!("not an alert rule pack: {}", e))?;
+    if pack.version > RULE_PACK_VERSION {
+        return Err(format!("made by a newer Dev Dashboard (version {})", pack.version));
+    }
+    Ok(pack.rules)
+}
+
+/// How long a rule's condition has held, and whether it has fired
+#[derive(Default, Clone)]
+struct RuleState {
+    since: Option<Instant>,
+    firing: Option<String>,         // Reading when it fired, for the badge's tooltip
+    notified: Option<Instant>,
+}
+
+/// Rule states in settings order, and the rule pack import form
+#[derive(Default)]
+pub struct Alerts {
+    states: Vec<RuleState>,
+    import_path: String,
+    replace_on_import: bool,            // Replace the current rules instead of adding to them
+    status: Option<(String, bool)>,     // Outcome of the last export or import, and whether it failed
+}
+
+impl DevDashboard {
+    /// The rule's reading, or None when it isn't available on this machine right now
+    fn alert_reading(&self, rule: &AlertRule) -> Option<f32> {
+        let history = &self.tray.history;
+        let gpu = self.gpu_info.as_ref();
+        match rule.metric {
+            AlertMetric::CpuUsage => history.cpu.back().copied(),
+            AlertMetric::CpuTemperature => self.throttle_monitor.status.and_then(|status| status.temperature),
+            AlertMetric::MemoryUsage => history.memory.back().copied(),
+            AlertMetric::GpuUsage => gpu.and_then(|gpu| gpu.utilization),
+            AlertMetric::GpuTemperature => gpu.and_then(|gpu| gpu.temperature).map(|temperature| temperature as f32),
+            AlertMetric::DiskFree => {
+                let drive = rule.drive.trim().trim_end_matches(':').to_lowercase();
+                self.volume_monitor.volumes().iter()
+                    .filter(|volume| drive.is_empty() || volume.mount.trim_end_matches(['\\', ':']).to_lowercase() == drive)
+                    .filter_map(|volume| volume.space.map(|(_, free)| free as f32 / 1_000_000_000.0))
+                    .min_by(|a, b| a.total_cmp(b))
+            }
+        }
+    }
+
+    /// Evaluates the rules against the latest readings; called once a second
+    pub(crate) fn update_alerts(&mut self) {
+        let settings = &self.settings.alerts;
+        if !settings.enabled || self.monitoring_paused() {
+            self.alerts.states.clear();
+            return;
+        }
+        let readings: Vec<Option<f32>> = settings.rules.iter()
+            .map(|rule| if rule.enabled { self.alert_reading(rule) } else { None })
+            .collect();
+        self.alerts.states.resize_with(readings.len(), RuleState::default);
+
+        let mut fired = Vec::new();
+        for ((rule, reading), state) in self.settings.alerts.rules.iter().zip(readings).zip(&mut self.alerts.states) {
+            let Some(value) = reading.filter(|value| rule.comparison.holds(*value, rule.threshold)) else {
+                if state.firing.take().is_some() {
+                    info!("Alert cleared: {}", rule.describe());
+                }
+                state.since = None;
+                continue;
+            };
+            let since = *state.since.get_or_insert_with(Instant::now);
+            if since.elapsed() < Duration::from_secs(rule.seconds as u64) {
+                continue;
+            }
+            let reading = format!("{:.1}{}", value, rule.metric.unit());
+            if state.firing.is_none() {
+                warn!("Alert: {} (now {})", rule.describe(), reading);
+                self.daily_summaries.record_alert(&rule.describe());
+                self.notifications.push(NotificationSource::Alert, &rule.describe(), &format!("Now {}", reading));
+            }
+            state.firing = Some(reading.clone());
+            if !state.notified.is_some_and(|time| time.elapsed() < RENOTIFY_AFTER) {
+                state.notified = Some(Instant::now());
+                fired.push((rule.describe(), reading));
+            }
+        }
+        if self.settings.alerts.notify {
+            for (rule, reading) in fired {
+                toast::show_toast(&rule, &format!("Now {}", reading));
+            }
+        }
+    }
+
+    /// Descriptions of the rules firing for a card, with their readings
+    fn firing_alerts(&self, card: DashboardCard) -> Vec<String> {
+        self.settings.alerts.rules.iter()
+            .zip(&self.alerts.states)
+            .filter(|(rule, _)| rule.metric.card() == card)
+            .filter_map(|(rule, state)| state.firing.as_ref().map(|reading| format!("{} (now {})", rule.describe(), reading)))
+            .collect()
+    }
+
+    /// Red badge left of the pop-out button while any of the card's rules fire
+    pub(crate) fn show_alert_badge(&self, ui: &mut egui::Ui, card_rect: egui::Rect, card: DashboardCard) {
+        let firing = self.firing_alerts(card);
+        if firing.is_empty() {
+            return;
+        }
+        let rect = egui::Rect::from_min_size(card_rect.right_top() + egui::vec2(-45.0, 1.0), egui::vec2(26.0, 16.0));
+        let text = RichText::new(format!("⚠ {}", firing.len())).size(11.0).color(Color32::from_rgb(220, 50, 50));
+        ui.put(rect, egui::Label::new(text)).on_hover_text(firing.join("\n"));
+    }
+
+    /// Writes the rules to Documents\Dev Dashboard and shows the file in Explorer
+    fn export_alert_rules(&mut self) {
+        let pack = RulePack { version: RULE_PACK_VERSION, rules: self.settings.alerts.rules.clone() };
+        let folder = known_folders::documents().join("Dev Dashboard");
+        let path = folder.join(format!("alert-rules-{}.json", Local::now().format("%Y%m%d-%H%M%S")));
+        let result = serde_json::to_string_pretty(&pack)
+            .map_err(std::io::Error::from)
+            .and_then(|json| std::fs::create_dir_all(&folder).and_then(|_| std::fs::write(&path, json)));
+        self.alerts.status = Some(match result {
+            Ok(()) => {
+                info!("Exported {} alert rules to {:?}", pack.rules.len(), path);
+                let _ = std::process::Command::new("explorer").arg(format!("/select,{}", path.display())).spawn();
+                (format!("Exported {} rules", pack.rules.len()), false)
+            }
+            Err(e) => {
+                warn!("Could not export alert rules: {}", e);
+                (format!("Could not export: {}", e), true)
+            }
+        });
+    }
+
+    /// Adds the rules from a pack, skipping ones already set up, or replaces the current rules with them
+    fn import_alert_rules(&mut self) {
+        let path = PathBuf::from(self.alerts.import_path.trim().trim_matches('"'));
+        let rules = match read_rule_pack(&path) {
+            Ok(rules) => rules,
+            Err(e) => {
+                warn!("Could not import alert rules from {:?}: {}", path, e);
+                self.alerts.status = Some((format!("Could not import: {}", e), true));
+                return;
+            }
+        };
+        let settings = &mut self.settings.alerts;
+        let message = if self.alerts.replace_on_import {
+            let message = format!("Replaced the rules with {} from the pack", rules.len());
+            settings.rules = rules;
+            message
+        } else {
+            let total = rules.len();
+            let new: Vec<AlertRule> = rules.into_iter()
+                .filter(|rule| !settings.rules.iter().any(|existing| existing.describe() == rule.describe()))
+                .collect();
+            let message = format!("Added {} of {} rules; the others were already set up", new.len(), total);
+            settings.rules.extend(new);
+            message
+        };
+        info!("Imported alert rules from {:?}: {}", path, message);
+        // States follow the rules by position
+        self.alerts.states.clear();
+        self.alerts.status = Some((message, false));
+        self.save_settings();
+    }
+
+    pub(crate) fn show_alert_settings(&mut self, ui: &mut egui::Ui) {
+        ui.label("Alerts:");
+        let settings = &mut self.settings.alerts;
+        let mut changed = ui.checkbox(&mut settings.enabled, "Alert when a reading stays past a threshold")
+            .on_hover_text("Firing rules show a badge on their card")
+            .changed();
+        let mut remove = None;
+        ui.add_enabled_ui(settings.enabled, |ui| {
+            changed |= ui.checkbox(&mut settings.notify, "Show a Windows notification").changed();
+            for (index, rule) in settings.rules.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    changed |= ui.checkbox(&mut rule.enabled, "").changed();
+                    egui::ComboBox::from_id_source(("alert_metric", index))
+                        .selected_text(rule.metric.label())
+                        .show_ui(ui, |ui| {
+                            for metric in AlertMetric::ALL {
+                                if ui.selectable_value(&mut rule.metric, metric, metric.label()).changed() {
+                                    changed = true;
+                                    rule.comparison = if metric == AlertMetric::DiskFree { Comparison::Below } else { Comparison::Above };
+                                }
+                            }
+                        });
+                    if rule.metric == AlertMetric::DiskFree {
+                        changed |= ui.add(egui::TextEdit::singleline(&mut rule.drive).hint_text("Any").desired_width(32.0))
+                            .on_hover_text("Drive letter, or empty for whichever drive has the least space")
+                            .changed();
+                    }
+                    egui::ComboBox::from_id_source(("alert_comparison", index))
+                        .width(36.0)
+                        .selected_text(rule.comparison.symbol())
+                        .show_ui(ui, |ui| {
+                            for comparison in [Comparison::Above, Comparison::Below] {
+                                changed |= ui.selectable_value(&mut rule.comparison, comparison, comparison.symbol()).changed();
+                            }
+                        });
+                    changed |= ui.add(egui::DragValue::new(&mut rule.threshold).clamp_range(0.0..=10_000.0).suffix(rule.metric.unit())).changed();
+                    ui.label("for");
+                    changed |= ui.add(egui::DragValue::new(&mut rule.seconds).clamp_range(0..=3600).suffix(" s")).changed();
+                    if ui.small_button("✖").on_hover_text("Remove this rule").clicked() {
+                        remove = Some(index);
+                    }
+                });
+            }
+            if ui.button("Add Rule").clicked() {
+                settings.rules.push(AlertRule::default());
+                changed = true;
+            }
+        });
+        if let Some(index) = remove {
+            settings.rules.remove(index);
+            // States follow the rules by position
+            self.alerts.states.clear();
+            changed = true;
+        }
+        if changed {
+            self.save_settings();
+        }
+
+        ui.add_space(4.0);
+        let mut export = false;
+        let mut import = false;
+        ui.horizontal(|ui| {
+            export = ui.button("Export Rules")
+                .on_hover_text("Saves the rules as a JSON rule pack in Documents\\Dev Dashboard, to share with a team")
+                .clicked();
+            ui.add(egui::TextEdit::singleline(&mut self.alerts.import_path)
+                .hint_text(r"\\server\team\alert-rules.json")
+                .desired_width(200.0));
+            let path_given = !self.alerts.import_path.trim().is_empty();
+            import = ui.add_enabled(path_given, egui::Button::new("Import Rules")).clicked();
+            ui.checkbox(&mut self.alerts.replace_on_import, "Replace current rules");
+        });
+        if let Some((message, failed)) = &self.alerts.status {
+            let color = if *failed { Color32::from_rgb(220, 50, 50) } else { Color32::from_rgb(107, 114, 128) };
+            ui.label(RichText::new(message).color(color));
+        }
+        if export {
+            self.export_alert_rules();
+        }
+        if import {
+            self.import_alert_rules();
+        }
+    }
+}
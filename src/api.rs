@@ -0,0 +1,356 @@
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+use std::sync::{Arc, Mutex};
+use axum::extract::State;
+use axum::http::{header, HeaderMap, StatusCode, Uri};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::Local;
+use eframe::egui;
+use egui::{Color32, RichText};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Serialize, Deserialize};
+use serde_json::{json, Value};
+use sysinfo::{PidExt, ProcessExt, SystemExt};
+use tokio::sync::oneshot;
+use log::{info, warn};
+use crate::DevDashboard;
+
+/// Off by default; while on, anyone who has the token can read the snapshot
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ApiSettings {
+    pub enabled: bool,
+    pub port: u16,
+    pub lan: bool,                  // Listen on every interface instead of only this PC
+    pub token: String,              // Required as a bearer token or ?token= on every request
+}
+
+impl Default for ApiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 8765,
+            lan: false,
+            token: String::new(),
+        }
+    }
+}
+
+impl ApiSettings {
+    fn address(&self) -> SocketAddr {
+        let ip = if self.lan { Ipv4Addr::UNSPECIFIED } else { Ipv4Addr::LOCALHOST };
+        SocketAddr::new(IpAddr::V4(ip), self.port)
+    }
+}
+
+/// What the endpoints serve, rebuilt once a second while the server runs
+#[derive(Default)]
+struct ApiSnapshot {
+    metrics: Value,
+    processes: Value,
+    apps: Value,
+}
+
+#[derive(Clone)]
+struct ApiState {
+    token: Arc<String>,
+    snapshot: Arc<Mutex<ApiSnapshot>>,
+}
+
+impl ApiState {
+    fn is_authorized(&self, headers: &HeaderMap, uri: &Uri) -> bool {
+        let bearer = headers.get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        // Browsers on a phone cannot set headers, so the token can also come in the query
+        let query = uri.query().unwrap_or_default().split('&').find_map(|pair| pair.strip_prefix("token="));
+        // An empty token would let `?token=` through
+        !self.token.is_empty() && bearer.or(query) == Some(self.token.as_str())
+    }
+
+    fn respond(&self, headers: &HeaderMap, uri: &Uri, section: fn(&ApiSnapshot) -> &Value) -> Response {
+        if !self.is_authorized(headers, uri) {
+            return (StatusCode::UNAUTHORIZED, Json(json!({ "error": "Missing or wrong token" }))).into_response();
+        }
+        let snapshot = self.snapshot.lock().unwrap();
+        Json(section(&snapshot).clone()).into_response()
+    }
+}
+
+async fn metrics(State(state): State<ApiState>, headers: HeaderMap, uri: Uri) -> Response {
+    state.respond(&headers, &uri, |snapshot| &snapshot.metrics)
+}
+
+async fn processes(State(state): State<ApiState>, headers: HeaderMap, uri: Uri) -> Response {
+    state.respond(&headers, &uri, |snapshot| &snapshot.processes)
+}
+
+async fn apps(State(state): State<ApiState>, headers: HeaderMap, uri: Uri) -> Response {
+    state.respond(&headers, &uri, |snapshot| &snapshot.apps)
+}
+
+fn new_token() -> String {
+    let mut bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// This PC's address on the network the default route uses; connecting a UDP socket sends nothing
+fn lan_address() -> Option<IpAddr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("1.1.1.1:80").ok()?;
+    Some(socket.local_addr().ok()?.ip())
+}
+
+/// The JSON server on the dashboard's tokio runtime
+#[derive(Default)]
+pub struct ApiServer {
+    snapshot: Arc<Mutex<ApiSnapshot>>,
+    shutdown: Option<oneshot::Sender<()>>,
+    listening: Option<SocketAddr>,
+    error: Option<String>,
+}
+
+impl ApiServer {
+    pub fn is_running(&self) -> bool {
+        self.listening.is_some()
+    }
+
+    fn stop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+        if let Some(address) = self.listening.take() {
+            info!("REST API on {} stopped", address);
+        }
+    }
+}
+
+impl DevDashboard {
+    /// Starts, stops or moves the server to match the settings
+    pub(crate) fn update_api_server(&mut self) {
+        let settings = &self.settings.api;
+        let wanted = settings.enabled.then(|| settings.address());
+        if self.api_server.listening == wanted || (wanted.is_some() && self.api_server.error.is_some()) {
+            return;
+        }
+        self.api_server.stop();
+        let Some(address) = wanted else { return };
+        // settings.json may have been edited by hand or restored from a setup script without the token
+        if self.settings.api.token.is_empty() {
+            self.settings.api.token = new_token();
+            self.save_settings();
+        }
+        let settings = &self.settings.api;
+
+        let listener = std::net::TcpListener::bind(address)
+            .and_then(|listener| listener.set_nonblocking(true).map(|_| listener));
+        let listener = match listener {
+            Ok(listener) => listener,
+            Err(e) => {
+                warn!("REST API could not listen on {}: {}", address, e);
+                self.api_server.error = Some(format!("Could not listen on port {}: {}", address.port(), e));
+                return;
+            }
+        };
+        let state = ApiState { token: Arc::new(settings.token.clone()), snapshot: self.api_server.snapshot.clone() };
+        let app = Router::new()
+            .route("/api/metrics", get(metrics))
+            .route("/api/processes", get(processes))
+            .route("/api/apps", get(apps))
+            .with_state(state);
+        let (shutdown, stopped) = oneshot::channel::<()>();
+        self.runtime().spawn(async move {
+            let server = match axum::Server::from_tcp(listener) {
+                Ok(builder) => builder.serve(app.into_make_service()),
+                Err(e) => {
+                    warn!("REST API could not start: {}", e);
+                    return;
+                }
+            };
+            if let Err(e) = server.with_graceful_shutdown(async { stopped.await.ok(); }).await {
+                warn!("REST API stopped: {}", e);
+            }
+        });
+        info!("REST API listening on {}", address);
+        self.api_server.shutdown = Some(shutdown);
+        self.api_server.listening = Some(address);
+        self.api_server.error = None;
+    }
+
+    /// Rebuilds what the endpoints serve from the latest readings
+    pub(crate) fn update_api_snapshot(&mut self) {
+        if !self.api_server.is_running() {
+            return;
+        }
+        let history = &self.tray.history;
+        let total_memory = self.sys.total_memory();
+        let used_memory = total_memory.saturating_sub(self.sys.available_memory());
+        let gpu = self.gpu_info.as_ref().map(|gpu| json!({
+            "name": gpu.name,
+            "utilization_percent": gpu.utilization,
+            "temperature_c": gpu.temperature,
+            "memory_used_bytes": gpu.memory_used,
+            "memory_total_bytes": gpu.memory_total,
+            "graphics_clock_mhz": gpu.graphics_clock,
+            "memory_clock_mhz": gpu.memory_clock,
+        }));
+        let mut network: Vec<Value> = self.network_stats.iter()
+            .filter(|(name, _)| DevDashboard::is_physical_interface(name))
+            .map(|(name, stats)| json!({
+                "interface": name,
+                "received_bytes_per_second": stats.received_speed,
+                "sent_bytes_per_second": stats.sent_speed,
+                "received_since_boot_bytes": stats.total_received,
+                "sent_since_boot_bytes": stats.total_sent,
+            }))
+            .collect();
+        network.sort_by(|a, b| a["interface"].as_str().cmp(&b["interface"].as_str()));
+        let drives: Vec<Value> = self.volume_monitor.volumes().iter()
+            .filter_map(|volume| volume.space.map(|(total, free)| json!({
+                "mount": volume.mount,
+                "total_bytes": total,
+                "free_bytes": free,
+                "online": volume.online,
+            })))
+            .collect();
+        let metrics = json!({
+            "time": Local::now().to_rfc3339(),
+            "uptime_seconds": self.sys.uptime(),
+            "monitoring_paused": self.monitoring_paused(),
+            "cpu": {
+                "usage_percent": history.cpu.back(),
+                "temperature_c": self.throttle_monitor.status.and_then(|status| status.temperature),
+            },
+            "memory": {
+                "usage_percent": history.memory.back(),
+                "used_bytes": used_memory,
+                "total_bytes": total_memory,
+            },
+            "gpu": gpu,
+            "network": network,
+            "drives": drives,
+        });
+
+        let mut processes: Vec<(f32, Value)> = self.sys.processes().iter()
+            .map(|(pid, process)| (process.cpu_usage(), json!({
+                "pid": pid.as_u32(),
+                "name": process.name(),
+                "cpu_percent": process.cpu_usage(),
+                "memory_bytes": process.memory(),
+            })))
+            .collect();
+        processes.sort_by(|a, b| b.0.total_cmp(&a.0));
+        let apps: Vec<Value> = self.ninite_apps.iter()
+            .map(|app| json!({
+                "name": app.name,
+                "category": app.category,
+                "installed": app.installed,
+                "ninite_id": app.ninite_id,
+                "winget_id": app.info.winget_id,
+            }))
+            .collect();
+
+        let mut snapshot = self.api_server.snapshot.lock().unwrap();
+        snapshot.metrics = metrics;
+        snapshot.processes = Value::Array(processes.into_iter().map(|(_, process)| process).collect());
+        snapshot.apps = Value::Array(apps);
+    }
+
+    pub(crate) fn show_api_settings(&mut self, ui: &mut egui::Ui) {
+        ui.label("REST API:");
+        let settings = &mut self.settings.api;
+        let mut changed = ui.checkbox(&mut settings.enabled, "Serve metrics, processes and apps as JSON")
+            .on_hover_text("GET /api/metrics, /api/processes and /api/apps, for scripts and other devices")
+            .changed();
+        ui.add_enabled_ui(settings.enabled, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Port:");
+                changed |= ui.add(egui::DragValue::new(&mut settings.port).clamp_range(1024..=65535)).changed();
+            });
+            changed |= ui.checkbox(&mut settings.lan, "Allow other devices on the network")
+                .on_hover_text("Windows asks once whether to let the dashboard through the firewall")
+                .changed();
+            ui.horizontal(|ui| {
+                ui.label("Token:");
+                ui.label(RichText::new(&settings.token).monospace());
+                if ui.small_button("New token").on_hover_text("Clients using the old token stop working").clicked() {
+                    settings.token = new_token();
+                    changed = true;
+                }
+            });
+        });
+        if settings.enabled && settings.token.is_empty() {
+            settings.token = new_token();
+            changed = true;
+        }
+
+        if let Some(address) = self.api_server.listening {
+            let host = if settings.lan {
+                lan_address().map(|ip| ip.to_string()).unwrap_or_else(whoami::devicename)
+            } else {
+                Ipv4Addr::LOCALHOST.to_string()
+            };
+            let url = format!("http://{}:{}/api/metrics?token={}", host, address.port(), settings.token);
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(&url).color(Color32::from_rgb(22, 163, 74)));
+                if ui.small_button("📋").on_hover_text("Copy the URL").clicked() {
+                    ui.output_mut(|output| output.copied_text = url.clone());
+                }
+            });
+        }
+        if let Some(error) = &self.api_server.error {
+            ui.colored_label(Color32::from_rgb(220, 50, 50), error);
+        }
+        if changed {
+            // A changed token or port needs a new listener
+            self.api_server.stop();
+            self.api_server.error = None;
+            self.save_settings();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn state(token: &str) -> ApiState {
+        ApiState { token: Arc::new(token.to_string()), snapshot: Arc::default() }
+    }
+
+    fn bearer(token: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_str(&format!("Bearer {}", token)).unwrap());
+        headers
+    }
+
+    #[test]
+    fn is_authorized_accepts_the_token_as_header_or_query() {
+        let state = state("s3cret");
+        let metrics: Uri = "/api/metrics".parse().unwrap();
+        assert!(state.is_authorized(&bearer("s3cret"), &metrics));
+        assert!(state.is_authorized(&HeaderMap::new(), &"/api/metrics?token=s3cret".parse().unwrap()));
+        assert!(state.is_authorized(&HeaderMap::new(), &"/api/metrics?pretty=1&token=s3cret".parse().unwrap()));
+    }
+
+    #[test]
+    fn is_authorized_rejects_missing_or_wrong_tokens() {
+        let state = state("s3cret");
+        let metrics: Uri = "/api/metrics".parse().unwrap();
+        assert!(!state.is_authorized(&HeaderMap::new(), &metrics));
+        assert!(!state.is_authorized(&bearer("wrong"), &metrics));
+        assert!(!state.is_authorized(&HeaderMap::new(), &"/api/metrics?token=s3cre".parse().unwrap()));
+        assert!(!state.is_authorized(&HeaderMap::new(), &"/api/metrics?token=".parse().unwrap()));
+    }
+
+    #[test]
+    fn is_authorized_rejects_everything_without_a_token() {
+        let state = state("");
+        assert!(!state.is_authorized(&HeaderMap::new(), &"/api/metrics?token=".parse().unwrap()));
+        assert!(!state.is_authorized(&HeaderMap::new(), &"/api/metrics".parse().unwrap()));
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::Duration;
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use tokio::process::Command as TokioCommand;
+use log::{debug, warn};
+use crate::{DevDashboard, NiniteApp, CREATE_NO_WINDOW};
+
+const WINGET_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// What an app is, shown before it is installed; catalog entries can replace it
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct AppInfo {
+    pub description: String,
+    pub homepage: String,
+    pub license: String,
+    pub winget_id: String,          // Package the latest version and release notes are read from
+    pub choco_id: String,           // Chocolatey package, for installs from a Chocolatey repository
+}
+
+/// Built-in details: (Ninite ID, description, homepage, license, winget ID, Chocolatey ID)
+const BUILTIN: &[(&str, &str, &str, &str, &str, &str)] = &[
+    ("chrome", "Google's web browser", "https://www.google.com/chrome/", "Freeware", "Google.Chrome", "googlechrome"),
+    ("firefox", "Mozilla's open-source web browser", "https://www.mozilla.org/firefox/", "MPL-2.0", "Mozilla.Firefox", "firefox"),
+    ("edge", "Microsoft's Chromium-based web browser", "https://www.microsoft.com/edge", "Freeware", "Microsoft.Edge", "microsoft-edge"),
+    ("zoom", "Video meetings and chat", "https://zoom.us", "Freeware", "Zoom.Zoom", "zoom"),
+    ("discord", "Voice, video and text chat", "https://discord.com", "Freeware", "Discord.Discord", "discord"),
+    ("vlc", "Media player for nearly every audio and video format", "https://www.videolan.org/vlc/", "GPL-2.0", "VideoLAN.VLC", "vlc"),
+    ("audacity", "Audio recorder and editor", "https://www.audacityteam.org", "GPL-3.0", "Audacity.Audacity", "audacity"),
+    ("blender", "3D modelling, animation and rendering", "https://www.blender.org", "GPL-3.0", "BlenderFoundation.Blender", "blender"),
+    ("paintdotnet", "Image and photo editor", "https://www.getpaint.net", "Freeware", "dotPDN.PaintDotNet", "paint.net"),
+    ("gimp", "Image editor with layers, masks and plug-ins", "https://www.gimp.org", "GPL-3.0", "GIMP.GIMP", "gimp"),
+    ("libreoffice", "Office suite: documents, spreadsheets and presentations", "https://www.libreoffice.org", "MPL-2.0", "TheDocumentFoundation.LibreOffice", "libreoffice-fresh"),
+    ("python", "Python 3 interpreter and pip", "https://www.python.org", "PSF-2.0", "Python.Python.3.12", "python3"),
+    ("filezilla", "FTP, FTPS and SFTP client", "https://filezilla-project.org", "GPL-2.0", "TimKosse.FileZilla.Client", "filezilla"),
+    ("notepadplusplus", "Source code and text editor", "https://notepad-plus-plus.org", "GPL-3.0", "Notepad++.Notepad++", "notepadplusplus"),
+    ("winscp", "SFTP, SCP and FTP client with a two-pane file manager", "https://winscp.net", "GPL-3.0", "WinSCP.WinSCP", "winscp"),
+    ("putty", "SSH and telnet client", "https://www.chiark.greenend.org.uk/~sgtatham/putty/", "MIT", "PuTTY.PuTTY", "putty"),
+    ("vscode", "Code editor with extensions, debugging and Git", "https://code.visualstudio.com", "Microsoft Software License", "Microsoft.VisualStudioCode", "vscode"),
+    ("evernote", "Notes, to-dos and web clippings synced across devices", "https://evernote.com", "Proprietary", "Evernote.Evernote", "evernote"),
+    ("googleearth", "3D globe, maps and imagery", "https://www.google.com/earth/", "Freeware", "Google.EarthPro", "googleearthpro"),
+    ("7zip", "File archiver with a high compression ratio", "https://www.7-zip.org", "LGPL-2.1", "7zip.7zip", "7zip"),
+    ("winrar", "Archiver for RAR, ZIP and other formats", "https://www.win-rar.com", "Shareware", "RARLab.WinRAR", "winrar"),
+    ("qbittorrent", "BitTorrent client", "https://www.qbittorrent.org", "GPL-2.0", "qBittorrent.qBittorrent", "qbittorrent"),
+];
+
+pub fn builtin(ninite_id: &str) -> AppInfo {
+    BUILTIN.iter()
+        .find(|(id, ..)| *id == ninite_id)
+        .map(|&(_, description, homepage, license, winget_id, choco_id)| AppInfo {
+            description: description.to_string(),
+            homepage: homepage.to_string(),
+            license: license.to_string(),
+            winget_id: winget_id.to_string(),
+            choco_id: choco_id.to_string(),
+        })
+        .unwrap_or_default()
+}
+
+/// The current release as winget describes it
+#[derive(Clone, Default)]
+pub struct ReleaseDetails {
+    version: String,
+    release_notes: String,
+    release_notes_url: String,
+}
+
+/// Fields of a `winget show` listing; continuation lines are indented under an empty "Release Notes:"
+fn parse_show(output: &str) -> ReleaseDetails {
+    let mut details = ReleaseDetails::default();
+    let mut in_notes = false;
+    for line in output.lines().map(|line| line.rsplit('\r').next().unwrap_or(line)) {
+        if in_notes {
+            if line.starts_with(' ') || line.trim().is_empty() {
+                details.release_notes.push_str(line.trim());
+                details.release_notes.push('\n');
+                continue;
+            }
+            in_notes = false;
+        }
+        let Some((key, value)) = line.split_once(':') else { continue };
+        let value = value.trim();
+        match key.trim() {
+            "Version" => details.version = value.to_string(),
+            "Release Notes" => {
+                details.release_notes = value.to_string();
+                in_notes = value.is_empty();
+            }
+            "Release Notes Url" => details.release_notes_url = value.to_string(),
+            _ => {}
+        }
+    }
+    details.release_notes = details.release_notes.trim().to_string();
+    details
+}
+
+async fn winget_show(winget_id: String, source_args: Vec<String>) -> Result<ReleaseDetails, String> {
+    let child = TokioCommand::new("winget")
+        .args(["show", "--id", &winget_id, "--exact", "--accept-source-agreements", "--disable-interactivity"])
+        .args(&source_args)
+        .creation_flags(CREATE_NO_WINDOW)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Could not run winget: {}", e))?;
+    let output = tokio::time::timeout(WINGET_TIMEOUT, child.wait_with_output()).await
+        .map_err(|_| "winget did not answer in time".to_string())?
+        .map_err(|e| e.to_string())?;
+    let details = parse_show(&String::from_utf8_lossy(&output.stdout));
+    if details.version.is_empty() {
+        return Err("winget has no listing for this app".to_string());
+    }
+    Ok(details)
+}
+
+/// Release details fetched from winget, kept for the session
+#[derive(Default)]
+pub struct AppInfoCache {
+    details: HashMap<String, Result<ReleaseDetails, String>>,  // By winget ID
+    pending: HashMap<String, Receiver<Result<ReleaseDetails, String>>>,
+    wanted: Vec<String>,            // Opened in a popover but not requested yet
+}
+
+impl AppInfoCache {
+    /// "ℹ" button next to an app that opens its info popover
+    pub fn show_button(&mut self, ui: &mut egui::Ui, app: &NiniteApp) {
+        let info = &app.info;
+        if info.description.is_empty() && info.homepage.is_empty() && info.winget_id.is_empty() {
+            return;
+        }
+        let response = ui.small_button("ℹ").on_hover_text("About this app");
+        let popup_id = ui.make_persistent_id(("app_info", &app.ninite_id));
+        if response.clicked() {
+            ui.memory_mut(|memory| memory.toggle_popup(popup_id));
+            let id = &info.winget_id;
+            if !id.is_empty() && !self.details.contains_key(id) && !self.pending.contains_key(id) && !self.wanted.contains(id) {
+                self.wanted.push(id.clone());
+            }
+        }
+
+        let details = self.details.get(&info.winget_id);
+        let loading = self.pending.contains_key(&info.winget_id) || self.wanted.contains(&info.winget_id);
+        egui::popup::popup_below_widget(ui, popup_id, &response, |ui| {
+            ui.set_width(320.0);
+            ui.label(RichText::new(&app.name).strong());
+            if !info.description.is_empty() {
+                ui.label(&info.description);
+            }
+            if !info.license.is_empty() {
+                ui.label(RichText::new(format!("License: {}", info.license)).color(Color32::from_rgb(156, 163, 175)));
+            }
+            if !info.homepage.is_empty() {
+                ui.hyperlink_to("Homepage", &info.homepage);
+            }
+            if info.winget_id.is_empty() {
+                return;
+            }
+            ui.separator();
+            match details {
+                Some(Ok(details)) => {
+                    ui.label(format!("Latest version: {}", details.version));
+                    if !details.release_notes.is_empty() {
+                        egui::ScrollArea::vertical().id_source(("app_release_notes", &app.ninite_id)).max_height(160.0).show(ui, |ui| {
+                            ui.label(RichText::new(&details.release_notes).small());
+                        });
+                    }
+                    if !details.release_notes_url.is_empty() {
+                        ui.hyperlink_to("Release notes", &details.release_notes_url);
+                    }
+                }
+                Some(Err(e)) => {
+                    ui.label(RichText::new(e).color(Color32::from_rgb(107, 114, 128)));
+                }
+                None if loading => {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Asking winget for the latest release...");
+                    });
+                }
+                None => {}
+            }
+        });
+    }
+}
+
+impl DevDashboard {
+    /// Starts winget lookups for popovers that were opened and collects the answers
+    pub(crate) fn update_app_info(&mut self) {
+        let source_args = self.settings.package_sources.winget_source_args();
+        let cache = &mut self.app_info;
+        cache.pending.retain(|id, receiver| match receiver.try_recv() {
+            Ok(result) => {
+                if let Err(e) = &result {
+                    debug!("No release details for {}: {}", id, e);
+                }
+                cache.details.insert(id.clone(), result);
+                false
+            }
+            Err(std::sync::mpsc::TryRecvError::Empty) => true,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                warn!("winget lookup for {} stopped without an answer", id);
+                false
+            }
+        });
+
+        for id in std::mem::take(&mut self.app_info.wanted) {
+            let source_args = source_args.clone();
+            let (sender, receiver) = channel();
+            self.app_info.pending.insert(id.clone(), receiver);
+            self.runtime().spawn(async move {
+                sender.send(winget_show(id, source_args).await).ok();
+            });
+        }
+    }
+}
@@ -0,0 +1,45 @@
+/// Words installers add to a DisplayName that say nothing about which app it is
+const NOISE_WORDS: [&str; 10] = ["x64", "x86", "amd64", "arm64", "win64", "win32", "64bit", "32bit", "bit", "bits"];
+
+/// Accented Latin letters folded to their base letter, so "Explorateur" and "Éxplorateur" compare equal
+fn fold(c: char) -> char {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+        'ç' => 'c',
+        'è' | 'é' | 'ê' | 'ë' => 'e',
+        'ì' | 'í' | 'î' | 'ï' => 'i',
+        'ñ' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' => 'o',
+        'ù' | 'ú' | 'û' | 'ü' => 'u',
+        'ý' | 'ÿ' => 'y',
+        _ => c,
+    }
+}
+
+/// Reduces an app or DisplayName to its words for comparing across languages and installer conventions.
+/// Parenthesized parts are dropped, since that is where installers put the architecture and language,
+/// e.g. "Mozilla Firefox (x64 de)", "7-Zip 23.01 (64 bits)" or "Notepad++ (64-Bit x64)".
+pub fn normalize(name: &str) -> String {
+    let mut words = String::new();
+    let mut depth = 0usize;
+    for c in name.chars().flat_map(char::to_lowercase).map(fold) {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            _ if depth > 0 => {}
+            // Keeps "notepad++" and "c#" apart from "notepad" and "c"
+            _ if c.is_alphanumeric() || c == '+' || c == '#' => words.push(c),
+            _ => words.push(' '),
+        }
+    }
+    words.split_whitespace()
+        .filter(|word| !NOISE_WORDS.contains(word))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether a DisplayName names the app, matching whole words so "Git" doesn't match "GitHub Desktop"
+pub fn matches(display_name: &str, app_name: &str) -> bool {
+    let app = normalize(app_name);
+    !app.is_empty() && format!(" {} ", normalize(display_name)).contains(&format!(" {} ", app))
+}
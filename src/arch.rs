@@ -0,0 +1,124 @@
+use std::sync::OnceLock;
+use serde::{Serialize, Deserialize};
+use windows::Win32::System::SystemInformation::{
+    IMAGE_FILE_MACHINE, IMAGE_FILE_MACHINE_AMD64, IMAGE_FILE_MACHINE_ARM64, IMAGE_FILE_MACHINE_I386,
+};
+use windows::Win32::System::Threading::{GetCurrentProcess, IsWow64Process2};
+use winreg::enums::HKEY_LOCAL_MACHINE;
+use winreg::RegKey;
+use log::info;
+use crate::NiniteApp;
+
+/// First Windows 11 build; Windows 10 on ARM can only emulate 32-bit x86 apps
+const X64_EMULATION_BUILD: u32 = 22000;
+
+/// Processor architecture of Windows or of an app build
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Arch {
+    X64,
+    Arm64,
+    X86,
+}
+
+impl Arch {
+    pub fn label(self) -> &'static str {
+        match self {
+            Arch::X64 => "x64",
+            Arch::Arm64 => "ARM64",
+            Arch::X86 => "32-bit x86",
+        }
+    }
+}
+
+/// Native builds by Ninite ID; catalog entries can override them
+const BUILDS: &[(&str, &[Arch])] = &[
+    ("chrome", &[Arch::X64, Arch::Arm64, Arch::X86]),
+    ("firefox", &[Arch::X64, Arch::Arm64, Arch::X86]),
+    ("edge", &[Arch::X64, Arch::Arm64, Arch::X86]),
+    ("zoom", &[Arch::X64, Arch::Arm64, Arch::X86]),
+    ("discord", &[Arch::X64]),
+    ("vlc", &[Arch::X64, Arch::X86]),
+    ("audacity", &[Arch::X64, Arch::X86]),
+    ("blender", &[Arch::X64, Arch::Arm64]),
+    ("paintdotnet", &[Arch::X64, Arch::Arm64]),
+    ("gimp", &[Arch::X64, Arch::Arm64]),
+    ("libreoffice", &[Arch::X64, Arch::Arm64, Arch::X86]),
+    ("python", &[Arch::X64, Arch::Arm64, Arch::X86]),
+    ("filezilla", &[Arch::X64, Arch::X86]),
+    ("notepadplusplus", &[Arch::X64, Arch::Arm64, Arch::X86]),
+    ("winscp", &[Arch::X86]),
+    ("putty", &[Arch::X64, Arch::Arm64, Arch::X86]),
+    ("vscode", &[Arch::X64, Arch::Arm64]),
+    ("evernote", &[Arch::X64]),
+    ("googleearth", &[Arch::X64]),
+    ("7zip", &[Arch::X64, Arch::Arm64, Arch::X86]),
+    ("winrar", &[Arch::X64, Arch::X86]),
+    ("qbittorrent", &[Arch::X64]),
+];
+
+/// Native builds of an app; empty when unknown, which is treated as runs everywhere
+pub fn builds(ninite_id: &str) -> Vec<Arch> {
+    BUILDS.iter()
+        .find(|(id, _)| *id == ninite_id)
+        .map(|(_, builds)| builds.to_vec())
+        .unwrap_or_default()
+}
+
+/// The architecture Windows itself runs on, even when this process is emulated
+pub fn os_arch() -> Arch {
+    static ARCH: OnceLock<Arch> = OnceLock::new();
+    *ARCH.get_or_init(|| {
+        let mut process = IMAGE_FILE_MACHINE::default();
+        let mut native = IMAGE_FILE_MACHINE::default();
+        let ok = unsafe { IsWow64Process2(GetCurrentProcess(), &mut process, Some(&mut native)) }.as_bool();
+        let arch = match native {
+            IMAGE_FILE_MACHINE_ARM64 => Arch::Arm64,
+            IMAGE_FILE_MACHINE_I386 => Arch::X86,
+            IMAGE_FILE_MACHINE_AMD64 => Arch::X64,
+            _ if !ok && cfg!(target_arch = "x86") => Arch::X86,
+            _ => Arch::X64,
+        };
+        info!("Windows architecture: {}", arch.label());
+        arch
+    })
+}
+
+fn emulates_x64() -> bool {
+    static EMULATES: OnceLock<bool> = OnceLock::new();
+    *EMULATES.get_or_init(|| RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey(r"SOFTWARE\Microsoft\Windows NT\CurrentVersion")
+        .and_then(|key| key.get_value::<String, _>("CurrentBuildNumber"))
+        .ok()
+        .and_then(|build| build.parse::<u32>().ok())
+        .is_some_and(|build| build >= X64_EMULATION_BUILD))
+}
+
+/// How an app would run on this PC
+#[derive(Clone, Copy, PartialEq)]
+pub enum Support {
+    Native,
+    /// Runs as a 32-bit build on 64-bit Windows
+    Bits32,
+    /// Runs through ARM64 emulation of the given build
+    Emulated(Arch),
+    Unsupported,
+}
+
+impl NiniteApp {
+    pub(crate) fn support(&self) -> Support {
+        if self.builds.is_empty() {
+            return Support::Native;
+        }
+        let os = os_arch();
+        if self.builds.contains(&os) {
+            return Support::Native;
+        }
+        match os {
+            Arch::X64 if self.builds.contains(&Arch::X86) => Support::Bits32,
+            Arch::Arm64 if self.builds.contains(&Arch::X64) && emulates_x64() => Support::Emulated(Arch::X64),
+            // x86-only tools are skipped on ARM64: they run slowly under emulation and often ship x86 drivers or shell extensions
+            _ => Support::Unsupported,
+        }
+    }
+}
@@ -0,0 +1,352 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant, SystemTime};
+use chrono::{DateTime, Local};
+use eframe::egui;
+use egui::RichText;
+use serde::{Serialize, Deserialize};
+use log::{info, warn};
+use crate::notifications::NotificationSource;
+use crate::{toast, DevDashboard};
+
+/// How often backup locations are re-scanned
+const CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+/// Limits on how much of a folder is walked to find its newest file
+const MAX_DEPTH: usize = 8;
+const MAX_ENTRIES: usize = 20_000;
+/// Result key for File History
+const FILE_HISTORY: &str = "File History";
+
+/// A folder that should have been modified within `max_age_hours`
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct BackupFolderRule {
+    pub path: String,
+    pub max_age_hours: u32,
+}
+
+/// Persisted backup monitoring configuration
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct BackupSettings {
+    pub file_history: bool,
+    pub file_history_max_hours: u32,
+    pub folders: Vec<BackupFolderRule>,
+}
+
+impl Default for BackupSettings {
+    fn default() -> Self {
+        Self {
+            file_history: false,
+            file_history_max_hours: 24,
+            folders: Vec::new(),
+        }
+    }
+}
+
+impl BackupSettings {
+    pub fn is_configured(&self) -> bool {
+        self.file_history || !self.folders.is_empty()
+    }
+
+    /// (name, path, max age) for every monitored location
+    fn targets(&self) -> Vec<(String, PathBuf, u32)> {
+        let mut targets = Vec::new();
+        if self.file_history {
+            if let Some(path) = file_history_config_dir() {
+                targets.push((FILE_HISTORY.to_string(), path, self.file_history_max_hours));
+            }
+        }
+        for rule in &self.folders {
+            targets.push((rule.path.clone(), PathBuf::from(&rule.path), rule.max_age_hours));
+        }
+        targets
+    }
+}
+
+/// Result of the latest scan of one location
+#[derive(Clone)]
+pub struct BackupStatus {
+    pub last_success: Option<SystemTime>,
+    pub error: Option<String>,
+}
+
+/// Background backup checker state
+#[derive(Default)]
+pub struct BackupMonitor {
+    results: HashMap<String, BackupStatus>,
+    stale: HashSet<String>,     // Locations already reported as stale
+    last_check: Option<Instant>,
+    receiver: Option<Receiver<(String, BackupStatus)>>,
+    pending: usize,
+    new_path: String,           // Text fields in Settings
+    new_hours: u32,
+}
+
+/// File History updates its catalog and configuration here after every run
+fn file_history_config_dir() -> Option<PathBuf> {
+    let local_app_data = std::env::var_os("LOCALAPPDATA")?;
+    Some(Path::new(&local_app_data).join(r"Microsoft\Windows\FileHistory\Configuration"))
+}
+
+/// Newest modification time of anything under `path`, bounded by depth and entry count
+fn newest_modification(path: &Path, depth: usize, budget: &mut usize) -> Option<SystemTime> {
+    let metadata = std::fs::metadata(path).ok()?;
+    let mut newest = metadata.modified().ok();
+    if !metadata.is_dir() || depth >= MAX_DEPTH {
+        return newest;
+    }
+
+    for entry in std::fs::read_dir(path).ok()?.flatten() {
+        if *budget == 0 {
+            break;
+        }
+        *budget -= 1;
+        newest = newest.max(newest_modification(&entry.path(), depth + 1, budget));
+    }
+    newest
+}
+
+fn check_location(name: &str, path: &Path) -> BackupStatus {
+    if !path.exists() {
+        let error = if name == FILE_HISTORY {
+            "File History is not set up".to_string()
+        } else {
+            "Folder not found".to_string()
+        };
+        return BackupStatus { last_success: None, error: Some(error) };
+    }
+    let mut budget = MAX_ENTRIES;
+    BackupStatus {
+        last_success: newest_modification(path, 0, &mut budget),
+        error: None,
+    }
+}
+
+/// Formats how long ago a time was, e.g. "3h ago"
+fn format_age(age: Duration) -> String {
+    let hours = age.as_secs() / 3600;
+    if hours >= 48 {
+        format!("{}d ago", hours / 24)
+    } else if hours > 0 {
+        format!("{}h ago", hours)
+    } else {
+        format!("{}m ago", age.as_secs() / 60)
+    }
+}
+
+impl BackupMonitor {
+    fn is_checking(&self) -> bool {
+        self.pending > 0
+    }
+
+    fn start_check(&mut self, settings: &BackupSettings) {
+        let targets = settings.targets();
+        if self.is_checking() || targets.is_empty() {
+            return;
+        }
+
+        let (sender, receiver) = channel();
+        self.receiver = Some(receiver);
+        self.pending = targets.len();
+        self.last_check = Some(Instant::now());
+
+        std::thread::spawn(move || {
+            for (name, path, _) in targets {
+                let status = check_location(&name, &path);
+                if sender.send((name, status)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Collects finished scans and schedules the next run.
+    /// Returns the locations that just went stale with their window in hours, so each is reported once.
+    pub fn update(&mut self, settings: &BackupSettings) -> Vec<(String, u32)> {
+        let mut newly_stale = Vec::new();
+        if let Some(receiver) = &self.receiver {
+            while let Ok((name, status)) = receiver.try_recv() {
+                self.results.insert(name, status);
+                self.pending = self.pending.saturating_sub(1);
+            }
+            if self.pending == 0 {
+                self.receiver = None;
+                for (name, _, max_hours) in settings.targets() {
+                    if is_stale(self.results.get(&name), max_hours) {
+                        if self.stale.insert(name.clone()) {
+                            warn!("Backup is stale: {} (expected within {}h)", name, max_hours);
+                            newly_stale.push((name, max_hours));
+                        }
+                    } else {
+                        self.stale.remove(&name);
+                    }
+                }
+            }
+        }
+
+        let due = match self.last_check {
+            Some(last) => last.elapsed() >= CHECK_INTERVAL,
+            None => true,
+        };
+        if due {
+            self.start_check(settings);
+        }
+        newly_stale
+    }
+}
+
+/// A location is stale when it errored or hasn't changed within its window
+fn is_stale(status: Option<&BackupStatus>, max_hours: u32) -> bool {
+    match status {
+        Some(BackupStatus { error: Some(_), .. }) => true,
+        Some(BackupStatus { last_success: Some(time), .. }) => {
+            time.elapsed().unwrap_or_default() > Duration::from_secs(max_hours as u64 * 3600)
+        }
+        Some(BackupStatus { last_success: None, .. }) => true,
+        None => false,
+    }
+}
+
+impl DevDashboard {
+    /// Runs the backup checks and raises a toast and a notification when a backup goes stale
+    pub(crate) fn update_backups(&mut self) {
+        for (name, max_hours) in self.backup_monitor.update(&self.settings.backup) {
+            let title = format!("Backup overdue: {}", name);
+            let body = format!("No successful backup within {} hours", max_hours);
+            toast::show_toast(&title, &body);
+            self.notifications.push(NotificationSource::Alert, &title, &body);
+        }
+    }
+
+    /// Displays the backup status card
+    /// Shows the last successful run of each backup and flags stale ones
+    pub(crate) fn show_backup_card(&mut self, ui: &mut egui::Ui) {
+        let targets = self.settings.backup.targets();
+        let monitor = &mut self.backup_monitor;
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Backups").strong().heading());
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if monitor.is_checking() {
+                    ui.spinner();
+                } else if ui.small_button("⟳").on_hover_text("Check now").clicked() {
+                    monitor.last_check = None;
+                }
+            });
+        });
+        ui.add_space(8.0);
+
+        for (name, _, max_hours) in &targets {
+            ui.horizontal(|ui| {
+                ui.label(name);
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    match monitor.results.get(name) {
+                        Some(BackupStatus { error: Some(error), .. }) => {
+                            ui.colored_label(egui::Color32::from_rgb(220, 50, 50), "Error")
+                                .on_hover_text(error);
+                        }
+                        Some(BackupStatus { last_success: Some(time), .. }) => {
+                            let color = if is_stale(monitor.results.get(name), *max_hours) {
+                                egui::Color32::from_rgb(220, 50, 50)
+                            } else {
+                                egui::Color32::from_rgb(22, 163, 74)
+                            };
+                            let local: DateTime<Local> = (*time).into();
+                            ui.colored_label(color, format_age(time.elapsed().unwrap_or_default()))
+                                .on_hover_text(format!(
+                                    "Last change {}\nExpected within {}h",
+                                    local.format("%Y-%m-%d %H:%M"),
+                                    max_hours
+                                ));
+                        }
+                        Some(_) => {
+                            ui.colored_label(egui::Color32::from_rgb(220, 50, 50), "Never");
+                        }
+                        None => {
+                            ui.label("Checking...");
+                        }
+                    }
+                });
+            });
+        }
+    }
+
+    /// Last successful run per location, for copying the card
+    pub(crate) fn backup_card_values(&self) -> Vec<(String, String)> {
+        self.settings.backup.targets()
+            .into_iter()
+            .map(|(name, _, max_hours)| {
+                let value = match self.backup_monitor.results.get(&name) {
+                    Some(BackupStatus { error: Some(error), .. }) => format!("Error: {}", error),
+                    Some(BackupStatus { last_success: Some(time), .. }) => {
+                        let local: DateTime<Local> = (*time).into();
+                        let stale = if is_stale(self.backup_monitor.results.get(&name), max_hours) { ", overdue" } else { "" };
+                        format!("{} ago ({}){}", format_age(time.elapsed().unwrap_or_default()), local.format("%Y-%m-%d %H:%M"), stale)
+                    }
+                    Some(_) => "Never".to_string(),
+                    None => "Checking".to_string(),
+                };
+                (name, value)
+            })
+            .collect()
+    }
+
+    /// Settings section for backup monitoring
+    pub(crate) fn show_backup_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+
+        ui.label("Backup Monitoring:");
+        ui.horizontal(|ui| {
+            changed |= ui.checkbox(&mut self.settings.backup.file_history, "Windows File History").changed();
+            if self.settings.backup.file_history {
+                ui.label("within (hours):");
+                changed |= ui.add(egui::DragValue::new(&mut self.settings.backup.file_history_max_hours)
+                    .clamp_range(1..=24 * 30)).changed();
+            }
+        });
+
+        let mut removed = None;
+        for (index, rule) in self.settings.backup.folders.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(format!("{} (within {}h)", rule.path, rule.max_age_hours));
+                if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                    removed = Some(index);
+                }
+            });
+        }
+        if let Some(index) = removed {
+            let rule = self.settings.backup.folders.remove(index);
+            self.backup_monitor.results.remove(&rule.path);
+            self.backup_monitor.stale.remove(&rule.path);
+            changed = true;
+        }
+
+        let monitor = &mut self.backup_monitor;
+        if monitor.new_hours == 0 {
+            monitor.new_hours = 24;
+        }
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut monitor.new_path)
+                .hint_text(r"D:\Backups\Projects")
+                .desired_width(180.0))
+                .on_hover_text("Flag this folder when nothing in it changed within the given hours");
+            ui.add(egui::DragValue::new(&mut monitor.new_hours).clamp_range(1..=24 * 30).suffix("h"));
+            if ui.button("Add").clicked() {
+                let path = monitor.new_path.trim().to_string();
+                if !path.is_empty() && !self.settings.backup.folders.iter().any(|rule| rule.path == path) {
+                    info!("Monitoring backup folder {}", path);
+                    self.settings.backup.folders.push(BackupFolderRule { path, max_age_hours: monitor.new_hours });
+                    changed = true;
+                }
+                monitor.new_path.clear();
+            }
+        });
+
+        if changed {
+            // Rescan with the new configuration right away
+            self.backup_monitor.last_check = None;
+            self.save_settings();
+        }
+    }
+}
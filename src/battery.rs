@@ -0,0 +1,364 @@
+use std::os::windows::process::CommandExt;
+use std::process::Command;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+use eframe::egui;
+use egui::RichText;
+use log::{info, warn};
+use crate::card_export::CardHistory;
+use crate::{DevDashboard, CREATE_NO_WINDOW};
+
+/// How often the battery report is regenerated
+const REPORT_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Capacity of one battery as reported by `powercfg /batteryreport`
+#[derive(Clone)]
+pub struct BatteryInfo {
+    pub name: String,
+    pub design_capacity: u64,       // mWh
+    pub full_charge_capacity: u64,  // mWh
+    pub cycle_count: Option<u64>,
+}
+
+impl BatteryInfo {
+    pub fn health_percent(&self) -> f32 {
+        if self.design_capacity == 0 {
+            return 0.0;
+        }
+        self.full_charge_capacity as f32 / self.design_capacity as f32 * 100.0
+    }
+}
+
+/// One point of the capacity history, as a percentage of design capacity
+#[derive(Clone)]
+pub struct CapacityEntry {
+    pub date: String,               // YYYY-MM-DD
+    pub health_percent: f32,
+}
+
+#[derive(Clone, Default)]
+pub struct BatteryReport {
+    pub batteries: Vec<BatteryInfo>,
+    pub history: Vec<CapacityEntry>,
+}
+
+impl BatteryReport {
+    /// Change in health per year between the first and last history entries
+    pub fn degradation_per_year(&self) -> Option<f32> {
+        let first = self.history.first()?;
+        let last = self.history.last()?;
+        let parse = |date: &str| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok();
+        let days = (parse(&last.date)? - parse(&first.date)?).num_days();
+        if days < 30 {
+            return None;
+        }
+        Some((last.health_percent - first.health_percent) / days as f32 * 365.0)
+    }
+}
+
+#[derive(Default)]
+pub struct BatteryHealth {
+    pub report: Option<Result<BatteryReport, String>>,
+    last_run: Option<Instant>,
+    receiver: Option<Receiver<Result<BatteryReport, String>>>,
+}
+
+impl BatteryHealth {
+    /// Whether the machine has a battery, once the first report has finished
+    pub fn has_battery(&self) -> bool {
+        matches!(&self.report, Some(Ok(report)) if !report.batteries.is_empty())
+    }
+
+    fn start_report(&mut self) {
+        if self.receiver.is_some() {
+            return;
+        }
+        let (sender, receiver) = channel();
+        self.receiver = Some(receiver);
+        self.last_run = Some(Instant::now());
+        std::thread::spawn(move || {
+            sender.send(generate_report()).ok();
+        });
+    }
+
+    /// Collects the finished report and regenerates it on an interval
+    pub fn update(&mut self) {
+        if let Some(receiver) = &self.receiver {
+            if let Ok(report) = receiver.try_recv() {
+                match &report {
+                    Ok(report) => info!("Battery report: {} batteries, {} history entries", report.batteries.len(), report.history.len()),
+                    Err(e) => warn!("Battery report failed: {}", e),
+                }
+                self.report = Some(report);
+                self.receiver = None;
+            }
+        }
+
+        let due = match self.last_run {
+            Some(last) => last.elapsed() >= REPORT_INTERVAL,
+            None => true,
+        };
+        if due {
+            self.start_report();
+        }
+    }
+
+    /// Full-charge capacity as a share of design capacity, per day from the battery report
+    pub fn export_history(&self) -> CardHistory {
+        let mut history = CardHistory::new(vec!["date", "health_percent"]);
+        if let Some(Ok(report)) = &self.report {
+            for entry in &report.history {
+                history.rows.push(vec![entry.date.clone(), format!("{:.1}", entry.health_percent)]);
+            }
+        }
+        history
+    }
+}
+
+/// Text content of the first `<tag>` inside `xml`
+fn tag_text<'a>(xml: &'a str, tag: &str) -> Option<&'a str> {
+    let open = format!("<{}>", tag);
+    let start = xml.find(&open)? + open.len();
+    let end = xml[start..].find(&format!("</{}>", tag))? + start;
+    Some(xml[start..end].trim())
+}
+
+/// Value of `name="..."` within a single element
+fn attribute<'a>(element: &'a str, name: &str) -> Option<&'a str> {
+    let key = format!(" {}=\"", name);
+    let start = element.find(&key)? + key.len();
+    let end = element[start..].find('"')? + start;
+    Some(&element[start..end])
+}
+
+fn parse_report(xml: &str) -> BatteryReport {
+    let mut report = BatteryReport::default();
+
+    let mut rest = tag_text(xml, "Batteries").unwrap_or_default();
+    while let Some(start) = rest.find("<Battery>") {
+        let after = &rest[start..];
+        let Some(end) = after.find("</Battery>") else { break };
+        let battery = &after[..end];
+        rest = &after[end..];
+
+        let design_capacity = tag_text(battery, "DesignCapacity").and_then(|v| v.parse().ok()).unwrap_or(0);
+        let full_charge_capacity = tag_text(battery, "FullChargeCapacity").and_then(|v| v.parse().ok()).unwrap_or(0);
+        if design_capacity == 0 {
+            continue;
+        }
+        let name = [tag_text(battery, "Manufacturer"), tag_text(battery, "Id")]
+            .iter()
+            .flatten()
+            .copied()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        report.batteries.push(BatteryInfo {
+            name: if name.is_empty() { "Battery".to_string() } else { name },
+            design_capacity,
+            full_charge_capacity,
+            cycle_count: tag_text(battery, "CycleCount").and_then(|v| v.parse().ok()).filter(|count| *count > 0),
+        });
+    }
+
+    let history = tag_text(xml, "History").unwrap_or_default();
+    for element in history.split("<HistoryEntry").skip(1) {
+        let element = element.split('>').next().unwrap_or_default();
+        let design: f32 = attribute(element, "DesignCapacity").and_then(|v| v.parse().ok()).unwrap_or(0.0);
+        let full: f32 = attribute(element, "FullChargeCapacity").and_then(|v| v.parse().ok()).unwrap_or(0.0);
+        let Some(date) = attribute(element, "LocalStartDate").or_else(|| attribute(element, "StartDate")) else { continue };
+        if design <= 0.0 || full <= 0.0 {
+            continue;
+        }
+        report.history.push(CapacityEntry {
+            date: date.get(..10).unwrap_or(date).to_string(),
+            health_percent: full / design * 100.0,
+        });
+    }
+
+    report
+}
+
+/// Runs `powercfg /batteryreport` in XML mode and parses the result
+fn generate_report() -> Result<BatteryReport, String> {
+    let path = std::env::temp_dir().join("dev_dashboard_battery_report.xml");
+    let output = Command::new("powercfg")
+        .args(["/batteryreport", "/xml", "/output"])
+        .arg(&path)
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_err(|e| format!("Could not run powercfg: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stdout).trim().to_string());
+    }
+
+    let bytes = std::fs::read(&path).map_err(|e| format!("Could not read battery report: {}", e))?;
+    let _ = std::fs::remove_file(&path);
+    // Depending on the Windows build the report is written as UTF-16 or UTF-8
+    let xml = match bytes.strip_prefix(&[0xFF, 0xFE]) {
+        Some(utf16) => {
+            let units: Vec<u16> = utf16.chunks_exact(2).map(|pair| u16::from_le_bytes([pair[0], pair[1]])).collect();
+            String::from_utf16_lossy(&units)
+        }
+        None => String::from_utf8_lossy(&bytes).into_owned(),
+    };
+    Ok(parse_report(&xml))
+}
+
+fn health_color(percent: f32) -> egui::Color32 {
+    if percent >= 80.0 {
+        egui::Color32::from_rgb(22, 163, 74)
+    } else if percent >= 60.0 {
+        egui::Color32::from_rgb(202, 138, 4)
+    } else {
+        egui::Color32::from_rgb(220, 50, 50)
+    }
+}
+
+impl DevDashboard {
+    /// Displays the battery health card
+    /// Shows design vs full-charge capacity, cycle count, and the capacity trend
+    pub(crate) fn show_battery_card(&self, ui: &mut egui::Ui) {
+        self.show_card(ui, "Battery Health", |ui| {
+            let Some(Ok(report)) = &self.battery_health.report else { return };
+
+            for battery in &report.batteries {
+                let health = battery.health_percent();
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(&battery.name).strong());
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.colored_label(health_color(health), format!("{:.0}%", health));
+                    });
+                });
+                ui.label(format!(
+                    "Capacity: {:.1} / {:.1} Wh",
+                    battery.full_charge_capacity as f32 / 1000.0,
+                    battery.design_capacity as f32 / 1000.0
+                ));
+                if let Some(cycles) = battery.cycle_count {
+                    ui.label(format!("Cycle count: {}", cycles));
+                }
+                ui.add_space(4.0);
+            }
+
+            if report.history.len() >= 2 {
+                let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width().min(260.0), 60.0), egui::Sense::hover());
+                let painter = ui.painter();
+                painter.rect_filled(rect, 4.0, egui::Color32::from_rgb(17, 24, 39));
+
+                let max = report.history.iter().map(|entry| entry.health_percent).fold(100.0, f32::max);
+                let min = report.history.iter().map(|entry| entry.health_percent).fold(max, f32::min).min(max - 1.0);
+                let step = rect.width() / (report.history.len() - 1) as f32;
+                let points: Vec<egui::Pos2> = report.history
+                    .iter()
+                    .enumerate()
+                    .map(|(index, entry)| {
+                        let y = (entry.health_percent - min) / (max - min);
+                        egui::pos2(rect.left() + index as f32 * step, rect.bottom() - y * rect.height())
+                    })
+                    .collect();
+                painter.add(egui::Shape::line(points, egui::Stroke::new(1.5, egui::Color32::from_rgb(88, 165, 237))));
+
+                let first = &report.history[0];
+                let last = &report.history[report.history.len() - 1];
+                response.on_hover_text(format!(
+                    "{}: {:.0}%\n{}: {:.0}%",
+                    first.date, first.health_percent, last.date, last.health_percent
+                ));
+
+                if let Some(per_year) = report.degradation_per_year() {
+                    ui.label(format!("Trend: {:+.1}% per year", per_year));
+                }
+            }
+        });
+    }
+
+    /// Capacity and cycles per battery, for copying the card
+    pub(crate) fn battery_card_values(&self) -> Vec<(String, String)> {
+        let Some(Ok(report)) = &self.battery_health.report else { return Vec::new() };
+        let mut values = Vec::new();
+        for battery in &report.batteries {
+            let mut value = format!(
+                "{:.0}% health, {:.1} / {:.1} Wh",
+                battery.health_percent(),
+                battery.full_charge_capacity as f32 / 1000.0,
+                battery.design_capacity as f32 / 1000.0
+            );
+            if let Some(cycles) = battery.cycle_count {
+                value.push_str(&format!(", {} cycles", cycles));
+            }
+            values.push((battery.name.clone(), value));
+        }
+        if let Some(per_year) = report.degradation_per_year() {
+            values.push(("Trend".to_string(), format!("{:+.1}% per year", per_year)));
+        }
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORT: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<BatteryReport xmlns="http://schemas.microsoft.com/battery/2012">
+  <Batteries>
+    <Battery>
+      <Id>DELL 1VX1H</Id>
+      <Manufacturer>SMP</Manufacturer>
+      <DesignCapacity>54000</DesignCapacity>
+      <FullChargeCapacity>45900</FullChargeCapacity>
+      <CycleCount>312</CycleCount>
+    </Battery>
+    <Battery>
+      <Id></Id>
+      <Manufacturer></Manufacturer>
+      <DesignCapacity>42000</DesignCapacity>
+      <FullChargeCapacity>42000</FullChargeCapacity>
+      <CycleCount>0</CycleCount>
+    </Battery>
+    <Battery>
+      <Id>Placeholder</Id>
+      <DesignCapacity>0</DesignCapacity>
+    </Battery>
+  </Batteries>
+  <History>
+    <HistoryEntry LocalStartDate="2023-06-01T00:00:00" StartDate="2023-05-31T22:00:00" DesignCapacity="54000" FullChargeCapacity="54000" />
+    <HistoryEntry StartDate="2024-06-01T00:00:00" DesignCapacity="54000" FullChargeCapacity="48600" />
+    <HistoryEntry LocalStartDate="2024-07-01T00:00:00" DesignCapacity="0" FullChargeCapacity="0" />
+  </History>
+</BatteryReport>"#;
+
+    #[test]
+    fn parse_report_reads_batteries() {
+        let report = parse_report(REPORT);
+        assert_eq!(report.batteries.len(), 2);
+        let first = &report.batteries[0];
+        assert_eq!(first.name, "SMP DELL 1VX1H");
+        assert_eq!(first.design_capacity, 54000);
+        assert_eq!(first.full_charge_capacity, 45900);
+        assert_eq!(first.cycle_count, Some(312));
+        assert!((first.health_percent() - 85.0).abs() < 0.01);
+        let second = &report.batteries[1];
+        assert_eq!(second.name, "Battery");
+        assert_eq!(second.cycle_count, None);
+    }
+
+    #[test]
+    fn parse_report_reads_history() {
+        let report = parse_report(REPORT);
+        let dates: Vec<&str> = report.history.iter().map(|entry| entry.date.as_str()).collect();
+        assert_eq!(dates, ["2023-06-01", "2024-06-01"]);
+        assert!((report.history[0].health_percent - 100.0).abs() < 0.01);
+        assert!((report.history[1].health_percent - 90.0).abs() < 0.01);
+        let per_year = report.degradation_per_year().unwrap();
+        assert!((per_year - -9.97).abs() < 0.05, "{}", per_year);
+    }
+
+    #[test]
+    fn parse_report_without_batteries() {
+        let report = parse_report("<BatteryReport><Batteries></Batteries></BatteryReport>");
+        assert!(report.batteries.is_empty());
+        assert!(report.history.is_empty());
+    }
+}
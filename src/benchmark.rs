@@ -0,0 +1,316 @@
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::os::windows::fs::OpenOptionsExt;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::{Duration, Instant};
+use chrono::Local;
+use eframe::egui;
+use egui::RichText;
+use rand::Rng;
+use serde::{Serialize, Deserialize};
+use sha2::{Digest, Sha256};
+use log::{info, warn};
+use crate::{storage, DevDashboard};
+
+/// Past runs are stored next to settings.json
+const HISTORY_FILE: &str = "benchmarks.json";
+/// Duration of each timed test
+const TEST_DURATION: Duration = Duration::from_secs(3);
+const CPU_BLOCK: usize = 1024 * 1024;
+const MEMORY_BUFFER: usize = 256 * 1024 * 1024;
+const DISK_FILE_SIZE: usize = 256 * 1024 * 1024;
+const DISK_CHUNK: usize = 1024 * 1024;
+const RANDOM_BLOCK: usize = 4096;
+/// Bypass the file cache so disk tests measure the drive rather than RAM
+const FILE_FLAG_NO_BUFFERING: u32 = 0x2000_0000;
+const FILE_FLAG_WRITE_THROUGH: u32 = 0x8000_0000;
+
+/// Scores from one benchmark run
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BenchmarkResult {
+    pub timestamp: String,
+    pub note: String,           // e.g. "after driver update"
+    pub cpu_mb_s: f64,          // Multi-threaded SHA-256 throughput
+    pub memory_gb_s: f64,       // Buffer copy bandwidth
+    pub disk_write_mb_s: f64,   // Sequential write
+    pub disk_read_mb_s: f64,    // Sequential read
+    pub disk_random_iops: f64,  // 4K random reads
+}
+
+enum BenchmarkMessage {
+    Stage(&'static str),
+    Finished(Result<BenchmarkResult, String>),
+}
+
+/// State of the Benchmark tool
+#[derive(Default)]
+pub struct Benchmark {
+    history: Vec<BenchmarkResult>,
+    receiver: Option<Receiver<BenchmarkMessage>>,
+    stage: Option<&'static str>,
+    error: Option<String>,
+    note: String,
+}
+
+impl Benchmark {
+    pub fn load() -> Self {
+        let history = storage::load_json(HISTORY_FILE);
+        Self { history, ..Default::default() }
+    }
+
+    fn save(&self) {
+        storage::save_json(HISTORY_FILE, &self.history);
+    }
+
+    fn is_running(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    fn start(&mut self) {
+        info!("Starting benchmark");
+        let (sender, receiver) = channel();
+        self.receiver = Some(receiver);
+        self.error = None;
+        let note = self.note.trim().to_string();
+        std::thread::spawn(move || {
+            let result = run_benchmark(&sender, note);
+            sender.send(BenchmarkMessage::Finished(result)).ok();
+        });
+    }
+
+    fn poll(&mut self) {
+        let Some(receiver) = &self.receiver else { return };
+        while let Ok(message) = receiver.try_recv() {
+            match message {
+                BenchmarkMessage::Stage(stage) => self.stage = Some(stage),
+                BenchmarkMessage::Finished(result) => {
+                    self.receiver = None;
+                    self.stage = None;
+                    match result {
+                        Ok(result) => {
+                            info!("Benchmark finished: CPU {:.0} MB/s, memory {:.1} GB/s", result.cpu_mb_s, result.memory_gb_s);
+                            self.history.push(result);
+                            self.note.clear();
+                            self.save();
+                        }
+                        Err(e) => {
+                            warn!("Benchmark failed: {}", e);
+                            self.error = Some(e);
+                        }
+                    }
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Hashes 1 MB blocks on every core for the test duration
+fn cpu_test() -> f64 {
+    let threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    let start = Instant::now();
+    let handles: Vec<_> = (0..threads)
+        .map(|seed| std::thread::spawn(move || {
+            let block = vec![seed as u8; CPU_BLOCK];
+            let mut blocks = 0u64;
+            let mut hasher = Sha256::new();
+            while start.elapsed() < TEST_DURATION {
+                hasher.update(&block);
+                blocks += 1;
+            }
+            std::hint::black_box(hasher.finalize());
+            blocks
+        }))
+        .collect();
+    let blocks: u64 = handles.into_iter().filter_map(|handle| handle.join().ok()).sum();
+    blocks as f64 * CPU_BLOCK as f64 / (1024.0 * 1024.0) / start.elapsed().as_secs_f64()
+}
+
+/// Copies a buffer much larger than the CPU caches back and forth
+fn memory_test() -> f64 {
+    let source = vec![1u8; MEMORY_BUFFER];
+    let mut target = vec![0u8; MEMORY_BUFFER];
+    let start = Instant::now();
+    let mut copies = 0u64;
+    while start.elapsed() < TEST_DURATION {
+        target.copy_from_slice(&source);
+        std::hint::black_box(&target);
+        copies += 1;
+    }
+    // Each copy reads and writes the buffer once
+    (copies * 2 * MEMORY_BUFFER as u64) as f64 / (1024.0 * 1024.0 * 1024.0) / start.elapsed().as_secs_f64()
+}
+
+/// A buffer whose start is aligned for unbuffered I/O
+fn aligned_buffer(storage: &mut Vec<u8>, size: usize) -> &mut [u8] {
+    storage.resize(size + RANDOM_BLOCK, 0);
+    let offset = storage.as_ptr().align_offset(RANDOM_BLOCK);
+    &mut storage[offset..offset + size]
+}
+
+/// Sequential write, sequential read and 4K random read on a temporary file
+fn disk_test(sender: &Sender<BenchmarkMessage>) -> Result<(f64, f64, f64), String> {
+    let path = std::env::temp_dir().join("dev_dashboard_benchmark.tmp");
+    let megabytes = DISK_FILE_SIZE as f64 / (1024.0 * 1024.0);
+
+    let result = (|| {
+        sender.send(BenchmarkMessage::Stage("Disk sequential write")).ok();
+        let chunk = vec![0xA5u8; DISK_CHUNK];
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .custom_flags(FILE_FLAG_WRITE_THROUGH)
+            .open(&path)
+            .map_err(|e| format!("Could not create test file: {}", e))?;
+        let start = Instant::now();
+        for _ in 0..DISK_FILE_SIZE / DISK_CHUNK {
+            file.write_all(&chunk).map_err(|e| e.to_string())?;
+        }
+        file.sync_all().map_err(|e| e.to_string())?;
+        let write = megabytes / start.elapsed().as_secs_f64();
+        drop(file);
+
+        sender.send(BenchmarkMessage::Stage("Disk sequential read")).ok();
+        let mut file = OpenOptions::new()
+            .read(true)
+            .custom_flags(FILE_FLAG_NO_BUFFERING)
+            .open(&path)
+            .map_err(|e| e.to_string())?;
+        let mut storage = Vec::new();
+        let buffer = aligned_buffer(&mut storage, DISK_CHUNK);
+        let start = Instant::now();
+        for _ in 0..DISK_FILE_SIZE / DISK_CHUNK {
+            file.read_exact(buffer).map_err(|e| e.to_string())?;
+        }
+        let read = megabytes / start.elapsed().as_secs_f64();
+
+        sender.send(BenchmarkMessage::Stage("Disk random read")).ok();
+        let buffer = &mut buffer[..RANDOM_BLOCK];
+        let blocks = (DISK_FILE_SIZE / RANDOM_BLOCK) as u64;
+        let mut rng = rand::thread_rng();
+        let start = Instant::now();
+        let mut reads = 0u64;
+        while start.elapsed() < TEST_DURATION {
+            let offset = rng.gen_range(0..blocks) * RANDOM_BLOCK as u64;
+            file.seek(SeekFrom::Start(offset)).map_err(|e| e.to_string())?;
+            file.read_exact(buffer).map_err(|e| e.to_string())?;
+            reads += 1;
+        }
+        let iops = reads as f64 / start.elapsed().as_secs_f64();
+
+        Ok((write, read, iops))
+    })();
+
+    let _ = std::fs::remove_file(&path);
+    result
+}
+
+fn run_benchmark(sender: &Sender<BenchmarkMessage>, note: String) -> Result<BenchmarkResult, String> {
+    sender.send(BenchmarkMessage::Stage("CPU (multi-thread hash)")).ok();
+    let cpu_mb_s = cpu_test();
+    sender.send(BenchmarkMessage::Stage("Memory bandwidth")).ok();
+    let memory_gb_s = memory_test();
+    let (disk_write_mb_s, disk_read_mb_s, disk_random_iops) = disk_test(sender)?;
+
+    Ok(BenchmarkResult {
+        timestamp: Local::now().format("%Y-%m-%d %H:%M").to_string(),
+        note,
+        cpu_mb_s,
+        memory_gb_s,
+        disk_write_mb_s,
+        disk_read_mb_s,
+        disk_random_iops,
+    })
+}
+
+/// Score with its change relative to the previous run
+fn score_label(ui: &mut egui::Ui, value: f64, previous: Option<f64>, format: impl Fn(f64) -> String) {
+    ui.horizontal(|ui| {
+        ui.label(format(value));
+        if let Some(previous) = previous.filter(|previous| *previous > 0.0) {
+            let change = (value - previous) / previous * 100.0;
+            if change.abs() >= 1.0 {
+                let color = if change > 0.0 {
+                    egui::Color32::from_rgb(22, 163, 74)
+                } else {
+                    egui::Color32::from_rgb(220, 38, 38)
+                };
+                ui.colored_label(color, format!("{:+.0}%", change));
+            }
+        }
+    });
+}
+
+impl DevDashboard {
+    /// Benchmark tool page
+    /// Runs CPU, memory and disk tests and lists past results for comparison
+    pub(crate) fn show_benchmark_tool(&mut self, ui: &mut egui::Ui) {
+        let benchmark = &mut self.benchmark;
+        benchmark.poll();
+
+        ui.heading("Quick Benchmark");
+        ui.add_space(8.0);
+
+        ui.horizontal(|ui| {
+            ui.label("Note:");
+            ui.add(egui::TextEdit::singleline(&mut benchmark.note)
+                .hint_text("e.g. after GPU driver 551.23")
+                .desired_width(220.0));
+            if ui.add_enabled(!benchmark.is_running(), egui::Button::new("Run Benchmark")).clicked() {
+                benchmark.start();
+            }
+            if let Some(stage) = benchmark.stage {
+                ui.spinner();
+                ui.label(format!("{}...", stage));
+            }
+        });
+        ui.label(RichText::new("Takes about 15 seconds and writes a 256 MB temporary file. Close heavy apps for consistent results.")
+            .small()
+            .color(egui::Color32::from_rgb(156, 163, 175)));
+        if let Some(error) = &benchmark.error {
+            ui.colored_label(egui::Color32::from_rgb(220, 50, 50), format!("Benchmark failed: {}", error));
+        }
+        ui.add_space(12.0);
+
+        if benchmark.history.is_empty() {
+            ui.label("No results yet.");
+            return;
+        }
+
+        let mut removed = None;
+        egui::ScrollArea::vertical().id_source("benchmark_scroll").show(ui, |ui| {
+            egui::Grid::new("benchmark_grid")
+                .striped(true)
+                .spacing([16.0, 6.0])
+                .show(ui, |ui| {
+                    for header in ["Date", "Note", "CPU", "Memory", "Seq. Write", "Seq. Read", "4K Random", ""] {
+                        ui.label(RichText::new(header).strong());
+                    }
+                    ui.end_row();
+
+                    // Newest first, each compared with the run before it
+                    for index in (0..benchmark.history.len()).rev() {
+                        let result = &benchmark.history[index];
+                        let previous = index.checked_sub(1).map(|i| &benchmark.history[i]);
+                        ui.label(&result.timestamp);
+                        ui.label(&result.note);
+                        score_label(ui, result.cpu_mb_s, previous.map(|p| p.cpu_mb_s), |v| format!("{:.0} MB/s", v));
+                        score_label(ui, result.memory_gb_s, previous.map(|p| p.memory_gb_s), |v| format!("{:.1} GB/s", v));
+                        score_label(ui, result.disk_write_mb_s, previous.map(|p| p.disk_write_mb_s), |v| format!("{:.0} MB/s", v));
+                        score_label(ui, result.disk_read_mb_s, previous.map(|p| p.disk_read_mb_s), |v| format!("{:.0} MB/s", v));
+                        score_label(ui, result.disk_random_iops, previous.map(|p| p.disk_random_iops), |v| format!("{:.0} IOPS", v));
+                        if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                            removed = Some(index);
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+
+        if let Some(index) = removed {
+            benchmark.history.remove(index);
+            benchmark.save();
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use std::time::{Duration, Instant};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use sysinfo::{ProcessExt, SystemExt};
+use windows::Win32::System::Power::{
+    SetThreadExecutionState, ES_CONTINUOUS, ES_DISPLAY_REQUIRED, ES_SYSTEM_REQUIRED, EXECUTION_STATE,
+};
+use log::{info, warn};
+use crate::notifications::NotificationSource;
+use crate::{toast, DevDashboard, InstallerState};
+
+/// Preset lengths offered in the menu
+const DURATIONS: [(&str, u64); 3] = [("1 hour", 60), ("2 hours", 120), ("4 hours", 240)];
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct CaffeineSettings {
+    pub keep_display_on: bool,  // Otherwise only sleep is blocked and the screen may still turn off
+}
+
+impl Default for CaffeineSettings {
+    fn default() -> Self {
+        Self {
+            keep_display_on: true,
+        }
+    }
+}
+
+/// What ends a keep-awake session
+#[derive(Clone, PartialEq)]
+enum Until {
+    TurnedOff,
+    Time(Instant),
+    ProcessExits(String),       // Every process with this executable name has exited
+    InstallerDone,              // The Tools tab download and install has finished
+}
+
+impl Until {
+    fn describe(&self) -> String {
+        match self {
+            Until::TurnedOff => "until turned off".to_string(),
+            Until::Time(end) => {
+                let minutes = end.saturating_duration_since(Instant::now()).as_secs() / 60 + 1;
+                format!("for {}h {:02}m", minutes / 60, minutes % 60)
+            }
+            Until::ProcessExits(name) => format!("until {} exits", name),
+            Until::InstallerDone => "until the installer finishes".to_string(),
+        }
+    }
+}
+
+/// Sleep blocker state; the execution state belongs to the UI thread, which sets and clears it
+#[derive(Default)]
+pub struct Caffeine {
+    until: Option<Until>,               // None while sleep is allowed
+    applied: Option<bool>,              // Whether the display flag was included in the applied state
+    process: String,                    // Text field in the menu
+    error: Option<String>,
+}
+
+impl Caffeine {
+    pub fn is_active(&self) -> bool {
+        self.until.is_some()
+    }
+
+    /// Processes need refreshing while waiting for one to exit
+    pub fn watches_process(&self) -> bool {
+        matches!(self.until, Some(Until::ProcessExits(_)))
+    }
+}
+
+/// ES_CONTINUOUS keeps the flags until the next call from the same thread
+fn set_execution_state(flags: EXECUTION_STATE) -> bool {
+    unsafe { SetThreadExecutionState(flags) }.0 != 0
+}
+
+impl DevDashboard {
+    fn start_caffeine(&mut self, until: Until) {
+        info!("Keeping the PC awake {}", until.describe());
+        self.caffeine.until = Some(until);
+        self.caffeine.error = None;
+        self.apply_caffeine();
+    }
+
+    fn stop_caffeine(&mut self, reason: Option<String>) {
+        self.caffeine.until = None;
+        self.apply_caffeine();
+        if let Some(reason) = reason {
+            info!("Allowing sleep again: {}", reason);
+            let title = "Sleep allowed again";
+            toast::show_toast(title, &reason);
+            self.notifications.push(NotificationSource::Watcher, title, &reason);
+        }
+    }
+
+    /// Sets or clears the execution state when the session or the display option changed
+    fn apply_caffeine(&mut self) {
+        let wanted = self.caffeine.until.as_ref().map(|_| self.settings.caffeine.keep_display_on);
+        if wanted == self.caffeine.applied {
+            return;
+        }
+        let flags = match wanted {
+            Some(true) => ES_CONTINUOUS | ES_SYSTEM_REQUIRED | ES_DISPLAY_REQUIRED,
+            Some(false) => ES_CONTINUOUS | ES_SYSTEM_REQUIRED,
+            None => ES_CONTINUOUS,
+        };
+        if !set_execution_state(flags) {
+            warn!("Could not change the sleep state");
+            self.caffeine.error = Some("Windows refused to change the sleep state".to_string());
+            self.caffeine.until = None;
+            return;
+        }
+        self.caffeine.applied = wanted;
+    }
+
+    /// Ends the session when its time is up or the watched task has finished
+    pub(crate) fn update_caffeine(&mut self) {
+        let reason = match &self.caffeine.until {
+            Some(Until::Time(end)) if Instant::now() >= *end => Some("The keep-awake time is up".to_string()),
+            Some(Until::ProcessExits(name)) => {
+                let running = self.sys.processes().values().any(|process| process.name().eq_ignore_ascii_case(name));
+                (!running).then(|| format!("{} has exited", name))
+            }
+            Some(Until::InstallerDone) => matches!(self.installer_state, InstallerState::Idle | InstallerState::Error(_))
+                .then(|| "The installer has finished".to_string()),
+            _ => None,
+        };
+        if reason.is_some() {
+            self.stop_caffeine(reason);
+        }
+    }
+
+    /// Top bar toggle with a menu of how long to stay awake
+    pub(crate) fn show_caffeine_button(&mut self, ui: &mut egui::Ui) {
+        let text = if self.caffeine.is_active() {
+            RichText::new("☕").color(Color32::from_rgb(202, 138, 4))
+        } else {
+            RichText::new("☕")
+        };
+        let hover = match &self.caffeine.until {
+            Some(until) => format!("Keeping the PC awake {}", until.describe()),
+            None => "Keep the PC awake".to_string(),
+        };
+        let mut start = None;
+        let mut stop = false;
+        let mut display_changed = false;
+        let installing = matches!(self.installer_state, InstallerState::Downloading | InstallerState::Installing);
+
+        ui.menu_button(text, |ui| {
+            let grey = Color32::from_rgb(107, 114, 128);
+            match &self.caffeine.until {
+                Some(until) => {
+                    ui.label(format!("Keeping the PC awake {}", until.describe()));
+                    if ui.button("Allow sleep").clicked() {
+                        stop = true;
+                        ui.close_menu();
+                    }
+                }
+                None => {
+                    if ui.button("Keep awake").clicked() {
+                        start = Some(Until::TurnedOff);
+                        ui.close_menu();
+                    }
+                    for (label, minutes) in DURATIONS {
+                        if ui.button(format!("For {}", label)).clicked() {
+                            start = Some(Until::Time(Instant::now() + Duration::from_secs(minutes * 60)));
+                            ui.close_menu();
+                        }
+                    }
+                    if installing && ui.button("Until the installer finishes").clicked() {
+                        start = Some(Until::InstallerDone);
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    ui.label("Until a process exits:");
+                    ui.horizontal(|ui| {
+                        ui.add(egui::TextEdit::singleline(&mut self.caffeine.process)
+                            .hint_text("msbuild.exe")
+                            .desired_width(120.0));
+                        let name = self.caffeine.process.trim().to_string();
+                        if ui.add_enabled(!name.is_empty(), egui::Button::new("Start")).clicked() {
+                            // The process list is only kept fresh while something needs it
+                            self.sys.refresh_processes();
+                            let running = self.sys.processes().values().any(|process| process.name().eq_ignore_ascii_case(&name));
+                            if running {
+                                start = Some(Until::ProcessExits(name));
+                                ui.close_menu();
+                            } else {
+                                self.caffeine.error = Some(format!("{} is not running", name));
+                            }
+                        }
+                    });
+                }
+            }
+            if let Some(error) = &self.caffeine.error {
+                ui.colored_label(Color32::from_rgb(220, 50, 50), error);
+            }
+            ui.separator();
+            display_changed = ui.checkbox(&mut self.settings.caffeine.keep_display_on, "Keep the display on").changed();
+            ui.label(RichText::new("Manual sleep and closing the lid still work").small().color(grey));
+        }).response.on_hover_text(hover);
+
+        if let Some(until) = start {
+            self.start_caffeine(until);
+        }
+        if stop {
+            info!("Allowing sleep again");
+            self.stop_caffeine(None);
+        }
+        if display_changed {
+            self.apply_caffeine();
+            self.save_settings();
+        }
+    }
+}
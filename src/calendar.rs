@@ -0,0 +1,762 @@
+use std::collections::HashSet;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday};
+use eframe::egui;
+use egui::RichText;
+use serde::{Serialize, Deserialize};
+use winreg::enums::HKEY_LOCAL_MACHINE;
+use winreg::RegKey;
+use log::{info, warn};
+use crate::DevDashboard;
+
+/// Upper bound on months stepped through for monthly/yearly rules
+const MAX_MONTH_STEPS: i64 = 12 * 100;
+
+/// Windows keeps the offset and daylight saving rules of each time zone here
+const TIME_ZONES_KEY: &str = r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Time Zones";
+
+/// IANA names used by Google Calendar and most feeds, with the matching Windows time zone
+const IANA_ZONES: &[(&str, &str)] = &[
+    ("Etc/UTC", "UTC"),
+    ("Etc/GMT", "UTC"),
+    ("GMT", "UTC"),
+    ("Europe/London", "GMT Standard Time"),
+    ("Europe/Dublin", "GMT Standard Time"),
+    ("Europe/Lisbon", "GMT Standard Time"),
+    ("Europe/Amsterdam", "W. Europe Standard Time"),
+    ("Europe/Berlin", "W. Europe Standard Time"),
+    ("Europe/Rome", "W. Europe Standard Time"),
+    ("Europe/Stockholm", "W. Europe Standard Time"),
+    ("Europe/Vienna", "W. Europe Standard Time"),
+    ("Europe/Zurich", "W. Europe Standard Time"),
+    ("Europe/Oslo", "W. Europe Standard Time"),
+    ("Europe/Paris", "Romance Standard Time"),
+    ("Europe/Brussels", "Romance Standard Time"),
+    ("Europe/Madrid", "Romance Standard Time"),
+    ("Europe/Copenhagen", "Romance Standard Time"),
+    ("Europe/Prague", "Central Europe Standard Time"),
+    ("Europe/Budapest", "Central Europe Standard Time"),
+    ("Europe/Warsaw", "Central European Standard Time"),
+    ("Europe/Athens", "GTB Standard Time"),
+    ("Europe/Bucharest", "GTB Standard Time"),
+    ("Europe/Helsinki", "FLE Standard Time"),
+    ("Europe/Kiev", "FLE Standard Time"),
+    ("Europe/Kyiv", "FLE Standard Time"),
+    ("Europe/Istanbul", "Turkey Standard Time"),
+    ("Europe/Moscow", "Russian Standard Time"),
+    ("America/New_York", "Eastern Standard Time"),
+    ("America/Toronto", "Eastern Standard Time"),
+    ("America/Detroit", "Eastern Standard Time"),
+    ("America/Chicago", "Central Standard Time"),
+    ("America/Winnipeg", "Central Standard Time"),
+    ("America/Mexico_City", "Central Standard Time (Mexico)"),
+    ("America/Denver", "Mountain Standard Time"),
+    ("America/Edmonton", "Mountain Standard Time"),
+    ("America/Phoenix", "US Mountain Standard Time"),
+    ("America/Los_Angeles", "Pacific Standard Time"),
+    ("America/Vancouver", "Pacific Standard Time"),
+    ("America/Anchorage", "Alaskan Standard Time"),
+    ("Pacific/Honolulu", "Hawaiian Standard Time"),
+    ("America/Halifax", "Atlantic Standard Time"),
+    ("America/Sao_Paulo", "E. South America Standard Time"),
+    ("America/Argentina/Buenos_Aires", "Argentina Standard Time"),
+    ("America/Bogota", "SA Pacific Standard Time"),
+    ("Asia/Dubai", "Arabian Standard Time"),
+    ("Asia/Jerusalem", "Israel Standard Time"),
+    ("Asia/Kolkata", "India Standard Time"),
+    ("Asia/Calcutta", "India Standard Time"),
+    ("Asia/Bangkok", "SE Asia Standard Time"),
+    ("Asia/Singapore", "Singapore Standard Time"),
+    ("Asia/Shanghai", "China Standard Time"),
+    ("Asia/Hong_Kong", "China Standard Time"),
+    ("Asia/Taipei", "Taipei Standard Time"),
+    ("Asia/Tokyo", "Tokyo Standard Time"),
+    ("Asia/Seoul", "Korea Standard Time"),
+    ("Australia/Perth", "W. Australia Standard Time"),
+    ("Australia/Brisbane", "E. Australia Standard Time"),
+    ("Australia/Sydney", "AUS Eastern Standard Time"),
+    ("Australia/Melbourne", "AUS Eastern Standard Time"),
+    ("Pacific/Auckland", "New Zealand Standard Time"),
+    ("Africa/Johannesburg", "South Africa Standard Time"),
+    ("Africa/Cairo", "Egypt Standard Time"),
+    ("Africa/Lagos", "W. Central Africa Standard Time"),
+];
+
+/// Persisted calendar configuration
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct CalendarSettings {
+    pub ics_source: String,       // http(s) URL or local path of an .ics feed
+    pub refresh_minutes: u32,
+}
+
+impl Default for CalendarSettings {
+    fn default() -> Self {
+        Self {
+            ics_source: String::new(),
+            refresh_minutes: 15,
+        }
+    }
+}
+
+/// A single occurrence of a calendar event
+#[derive(Clone, Debug)]
+pub struct CalendarEvent {
+    pub summary: String,
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+    pub all_day: bool,
+}
+
+/// Runtime state of the calendar integration
+#[derive(Default)]
+pub struct Calendar {
+    pub events: Vec<CalendarEvent>,   // Occurrences from today through tomorrow, sorted by start
+    pub error: Option<String>,
+    ics: String,                      // Last feed loaded, kept to rebuild the agenda after midnight
+    built_for: Option<NaiveDate>,     // Day the events were built for
+    last_fetch: Option<Instant>,
+    receiver: Option<Receiver<Result<String, String>>>,
+}
+
+impl Calendar {
+    /// The next timed event that hasn't started yet today, with minutes until it starts
+    pub fn next_meeting(&self) -> Option<(&CalendarEvent, i64)> {
+        let now = Local::now();
+        self.events
+            .iter()
+            .filter(|event| !event.all_day && event.start > now)
+            .map(|event| (event, (event.start - now).num_minutes()))
+            .next()
+    }
+
+    /// Events overlapping today
+    pub fn today(&self) -> impl Iterator<Item = &CalendarEvent> {
+        let (today_start, tomorrow_start) = today_bounds()
+            .unwrap_or_else(|| (Local::now(), Local::now()));
+        self.events.iter().filter(move |event| {
+            event.start < tomorrow_start && (event.end > today_start || event.start >= today_start)
+        })
+    }
+}
+
+/// Local midnight today and tomorrow
+fn today_bounds() -> Option<(DateTime<Local>, DateTime<Local>)> {
+    let today = Local::now().date_naive();
+    let start = Local.from_local_datetime(&today.and_hms_opt(0, 0, 0)?).earliest()?;
+    let tomorrow = Local.from_local_datetime(&today.succ_opt()?.and_hms_opt(0, 0, 0)?).earliest()?;
+    Some((start, tomorrow))
+}
+
+/// Raw VEVENT properties needed to build occurrences
+#[derive(Default)]
+struct RawEvent {
+    uid: String,
+    summary: String,
+    start: Option<(DateTime<Local>, bool)>,
+    end: Option<DateTime<Local>>,
+    rrule: Option<String>,
+    exdates: Vec<DateTime<Local>>,
+    recurrence_id: Option<DateTime<Local>>,
+    cancelled: bool,
+}
+
+/// Joins folded lines (continuations start with a space or tab)
+fn unfold(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in ics.lines() {
+        let line = line.trim_end_matches('\r');
+        if (line.starts_with(' ') || line.starts_with('\t')) && !lines.is_empty() {
+            lines.last_mut().unwrap().push_str(&line[1..]);
+        } else {
+            lines.push(line.to_string());
+        }
+    }
+    lines
+}
+
+/// Offset and daylight saving rules of a Windows time zone (the registry's TZI value)
+struct ZoneRules {
+    bias: i64,                  // Minutes added to local standard time to get UTC
+    standard_bias: i64,
+    daylight_bias: i64,
+    standard_date: [u16; 8],    // SYSTEMTIME of the switch back to standard time
+    daylight_date: [u16; 8],    // SYSTEMTIME of the switch to daylight time; month 0 when there is none
+}
+
+impl ZoneRules {
+    /// Reads the rules of a Windows time zone, or the Windows zone matching an IANA name
+    fn lookup(tzid: &str) -> Option<Self> {
+        // Some feeds prefix IANA names with a vendor path, e.g. /mozilla.org/20050126_1/Europe/Berlin
+        let name = IANA_ZONES
+            .iter()
+            .find(|(iana, _)| tzid == *iana || tzid.ends_with(&format!("/{}", iana)))
+            .map_or(tzid, |(_, windows)| windows);
+        let key = RegKey::predef(HKEY_LOCAL_MACHINE).open_subkey(format!(r"{}\{}", TIME_ZONES_KEY, name)).ok()?;
+        let tzi = key.get_raw_value("TZI").ok()?.bytes;
+        if tzi.len() < 44 {
+            return None;
+        }
+        let long = |at: usize| i32::from_le_bytes([tzi[at], tzi[at + 1], tzi[at + 2], tzi[at + 3]]) as i64;
+        let system_time = |at: usize| {
+            let mut fields = [0u16; 8];
+            for (i, field) in fields.iter_mut().enumerate() {
+                *field = u16::from_le_bytes([tzi[at + i * 2], tzi[at + i * 2 + 1]]);
+            }
+            fields
+        };
+        Some(Self {
+            bias: long(0),
+            standard_bias: long(4),
+            daylight_bias: long(8),
+            standard_date: system_time(12),
+            daylight_date: system_time(28),
+        })
+    }
+
+    /// Converts a wall-clock time in this zone to UTC
+    fn to_utc(&self, local: NaiveDateTime) -> NaiveDateTime {
+        let bias = if self.is_daylight(local) {
+            self.bias + self.daylight_bias
+        } else {
+            self.bias + self.standard_bias
+        };
+        local + chrono::Duration::minutes(bias)
+    }
+
+    fn is_daylight(&self, local: NaiveDateTime) -> bool {
+        let (Some(start), Some(end)) = (
+            transition(&self.daylight_date, local.year()),
+            transition(&self.standard_date, local.year()),
+        ) else {
+            return false;
+        };
+        if start < end {
+            local >= start && local < end
+        } else {
+            // Southern hemisphere: daylight time spans the new year
+            local >= start || local < end
+        }
+    }
+}
+
+/// When a SYSTEMTIME transition rule falls in a year. The day field is the week of the month (5 = last).
+fn transition(rule: &[u16; 8], year: i32) -> Option<NaiveDateTime> {
+    let [rule_year, month, weekday, week, hour, minute, ..] = *rule;
+    if month == 0 {
+        return None;
+    }
+    let time = chrono::NaiveTime::from_hms_opt(hour as u32, minute as u32, 0)?;
+    if rule_year != 0 {
+        // Absolute date, used by zones whose rules changed in a particular year
+        return Some(NaiveDate::from_ymd_opt(rule_year as i32, month as u32, week as u32)?.and_time(time));
+    }
+    let first = NaiveDate::from_ymd_opt(year, month as u32, 1)?;
+    let offset = (weekday as i64 - first.weekday().num_days_from_sunday() as i64).rem_euclid(7);
+    let mut date = first + chrono::Duration::days(offset + (week.max(1) as i64 - 1) * 7);
+    while date.month() != month as u32 {
+        date -= chrono::Duration::days(7);
+    }
+    Some(date.and_time(time))
+}
+
+/// The TZID parameter of a property, without quotes
+fn tzid_param(params: &str) -> Option<&str> {
+    params
+        .split(';')
+        .find_map(|param| param.strip_prefix("TZID="))
+        .map(|tzid| tzid.trim_matches('"'))
+        .filter(|tzid| !tzid.is_empty())
+}
+
+/// Parses an iCalendar date or date-time value. Returns the time and whether it is an all-day date.
+/// Times with a TZID are converted from that zone (Windows or IANA name); unknown zones are treated as local time.
+fn parse_ics_time(params: &str, value: &str) -> Option<(DateTime<Local>, bool)> {
+    let value = value.trim();
+    if (params.contains("VALUE=DATE") && !params.contains("VALUE=DATE-TIME")) || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        let local = Local.from_local_datetime(&date.and_hms_opt(0, 0, 0)?).earliest()?;
+        return Some((local, true));
+    }
+    if let Some(utc) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some((Utc.from_utc_datetime(&naive).with_timezone(&Local), false));
+    }
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    if let Some(zone) = tzid_param(params).and_then(ZoneRules::lookup) {
+        return Some((Utc.from_utc_datetime(&zone.to_utc(naive)).with_timezone(&Local), false));
+    }
+    Some((Local.from_local_datetime(&naive).earliest()?, false))
+}
+
+fn parse_events(ics: &str) -> Vec<RawEvent> {
+    let mut events = Vec::new();
+    let mut current: Option<RawEvent> = None;
+
+    for line in unfold(ics) {
+        if line == "BEGIN:VEVENT" {
+            current = Some(RawEvent::default());
+            continue;
+        }
+        if line == "END:VEVENT" {
+            if let Some(event) = current.take() {
+                events.push(event);
+            }
+            continue;
+        }
+        let Some(event) = current.as_mut() else { continue };
+        let Some((key, value)) = line.split_once(':') else { continue };
+        let (name, params) = key.split_once(';').unwrap_or((key, ""));
+
+        match name {
+            "UID" => event.uid = value.to_string(),
+            "SUMMARY" => event.summary = value.replace("\\,", ",").replace("\\;", ";").replace("\\n", " "),
+            "DTSTART" => event.start = parse_ics_time(params, value),
+            "DTEND" => event.end = parse_ics_time(params, value).map(|(time, _)| time),
+            "RRULE" => event.rrule = Some(value.to_string()),
+            "EXDATE" => event.exdates.extend(value.split(',').filter_map(|v| parse_ics_time(params, v)).map(|(t, _)| t)),
+            "RECURRENCE-ID" => event.recurrence_id = parse_ics_time(params, value).map(|(time, _)| time),
+            "STATUS" => event.cancelled = value.eq_ignore_ascii_case("CANCELLED"),
+            _ => {}
+        }
+    }
+
+    events
+}
+
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    // BYDAY entries may carry an ordinal prefix (e.g. 1MO); only the weekday is used
+    match value.trim_start_matches(|c: char| c == '-' || c == '+' || c.is_ascii_digit()) {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// Expands an RRULE into occurrence start times between `keep_from` and `window_end`.
+/// Supports FREQ (DAILY/WEEKLY/MONTHLY/YEARLY), INTERVAL, COUNT, UNTIL and weekly BYDAY.
+fn expand_rrule(start: DateTime<Local>, rule: &str, keep_from: DateTime<Local>, window_end: DateTime<Local>) -> Vec<DateTime<Local>> {
+    let mut freq = "";
+    let mut interval = 1i64;
+    let mut count: Option<usize> = None;
+    let mut until: Option<DateTime<Local>> = None;
+    let mut by_day: Vec<Weekday> = Vec::new();
+
+    for part in rule.split(';') {
+        let Some((key, value)) = part.split_once('=') else { continue };
+        match key {
+            "FREQ" => freq = value,
+            "INTERVAL" => interval = value.parse().unwrap_or(1).max(1),
+            "COUNT" => count = value.parse().ok(),
+            "UNTIL" => until = parse_ics_time("", value).map(|(time, _)| time),
+            "BYDAY" => by_day = value.split(',').filter_map(parse_weekday).collect(),
+            _ => {}
+        }
+    }
+
+    let limit = until.map_or(window_end, |until| until.min(window_end));
+    let max = count.unwrap_or(usize::MAX);
+    let mut occurrences = Vec::new();
+    let mut emitted = 0usize;
+
+    // Steps are taken in wall-clock time so occurrences keep their time of day across daylight saving changes.
+    // COUNT includes occurrences before the window, so every step is counted but only recent ones are kept.
+    let mut push = |naive: NaiveDateTime, occurrences: &mut Vec<DateTime<Local>>| -> bool {
+        // A time in the hour skipped when the clocks go forward doesn't happen and isn't counted
+        let Some(time) = Local.from_local_datetime(&naive).earliest() else {
+            return true;
+        };
+        if time > limit || emitted >= max {
+            return false;
+        }
+        emitted += 1;
+        if time >= keep_from {
+            occurrences.push(time);
+        }
+        true
+    };
+
+    let start = start.naive_local();
+    match freq {
+        "DAILY" => {
+            let mut time = start;
+            while push(time, &mut occurrences) {
+                time += chrono::Duration::days(interval);
+            }
+        }
+        "WEEKLY" if !by_day.is_empty() => {
+            // Walk week by week from the Monday of the starting week
+            let week_start = start - chrono::Duration::days(start.weekday().num_days_from_monday() as i64);
+            let mut week = 0i64;
+            'weeks: loop {
+                for offset in 0..7 {
+                    let time = week_start + chrono::Duration::days(week * 7 * interval + offset);
+                    if time < start || !by_day.contains(&time.weekday()) {
+                        continue;
+                    }
+                    if !push(time, &mut occurrences) {
+                        break 'weeks;
+                    }
+                }
+                week += 1;
+            }
+        }
+        "WEEKLY" => {
+            let mut time = start;
+            while push(time, &mut occurrences) {
+                time += chrono::Duration::days(7 * interval);
+            }
+        }
+        "MONTHLY" | "YEARLY" => {
+            let months = if freq == "YEARLY" { 12 * interval } else { interval };
+            let mut step = 0i64;
+            loop {
+                let total = start.month0() as i64 + step * months;
+                let year = start.year() + (total / 12) as i32;
+                let month = (total % 12) as u32 + 1;
+                step += 1;
+                // Skip months without this day (e.g. the 31st)
+                let Some(time) = start.with_day(1)
+                    .and_then(|t| t.with_year(year))
+                    .and_then(|t| t.with_month(month))
+                    .and_then(|t| t.with_day(start.day())) else {
+                    if step > MAX_MONTH_STEPS { break; }
+                    continue;
+                };
+                if !push(time, &mut occurrences) {
+                    break;
+                }
+            }
+        }
+        _ => {
+            push(start, &mut occurrences);
+        }
+    }
+
+    occurrences
+}
+
+/// Builds the sorted list of occurrences overlapping today and tomorrow
+fn build_occurrences(ics: &str) -> Vec<CalendarEvent> {
+    let Some((window_start, tomorrow_start)) = today_bounds() else {
+        return Vec::new();
+    };
+    let window_end = tomorrow_start + chrono::Duration::days(1);
+
+    let raw_events = parse_events(ics);
+
+    // Instances moved or cancelled individually replace their original occurrence
+    let overridden: HashSet<(String, i64)> = raw_events
+        .iter()
+        .filter_map(|event| event.recurrence_id.map(|id| (event.uid.clone(), id.timestamp())))
+        .collect();
+
+    let mut occurrences = Vec::new();
+    for event in raw_events {
+        let Some((start, all_day)) = event.start else { continue };
+        if event.cancelled {
+            continue;
+        }
+        let duration = match event.end {
+            Some(end) if end > start => end - start,
+            _ if all_day => chrono::Duration::days(1),
+            _ => chrono::Duration::zero(),
+        };
+
+        let starts = match (&event.rrule, event.recurrence_id) {
+            (Some(rule), None) => expand_rrule(start, rule, window_start - duration, window_end),
+            _ => vec![start],
+        };
+
+        for occurrence in starts {
+            let end = occurrence + duration;
+            if occurrence >= window_end || (occurrence < window_start && end <= window_start) {
+                continue;
+            }
+            if event.exdates.contains(&occurrence) {
+                continue;
+            }
+            if event.recurrence_id.is_none() && overridden.contains(&(event.uid.clone(), occurrence.timestamp())) {
+                continue;
+            }
+            occurrences.push(CalendarEvent {
+                summary: if event.summary.is_empty() { "(No title)".to_string() } else { event.summary.clone() },
+                start: occurrence,
+                end,
+                all_day,
+            });
+        }
+    }
+
+    occurrences.sort_by_key(|event| (event.start, !event.all_day));
+    occurrences
+}
+
+/// Reads the feed from disk or downloads it
+async fn load_ics(source: String) -> Result<String, String> {
+    let path = source.strip_prefix("file:///").unwrap_or(&source);
+    if source.starts_with("http://") || source.starts_with("https://") || source.starts_with("webcal://") {
+        let url = source.replacen("webcal://", "https://", 1);
+        let response = reqwest::get(&url).await.map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("Server returned: {}", response.status()));
+        }
+        response.text().await.map_err(|e| e.to_string())
+    } else {
+        std::fs::read_to_string(path).map_err(|e| e.to_string())
+    }
+}
+
+impl DevDashboard {
+    /// Refreshes the calendar feed on its interval and rebuilds today's agenda
+    pub(crate) fn update_calendar(&mut self) {
+        if let Some(receiver) = &self.calendar.receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.calendar.receiver = None;
+                match result {
+                    Ok(ics) => {
+                        self.calendar.events = build_occurrences(&ics);
+                        self.calendar.ics = ics;
+                        self.calendar.built_for = Some(Local::now().date_naive());
+                        self.calendar.error = None;
+                        info!("Loaded {} calendar events for today and tomorrow", self.calendar.events.len());
+                    }
+                    Err(e) => {
+                        warn!("Failed to load calendar: {}", e);
+                        self.calendar.error = Some(e);
+                    }
+                }
+            }
+        }
+
+        // The window is today and tomorrow, so it moves at midnight even when the feed hasn't changed
+        let today = Local::now().date_naive();
+        if !self.calendar.ics.is_empty() && self.calendar.built_for != Some(today) {
+            self.calendar.events = build_occurrences(&self.calendar.ics);
+            self.calendar.built_for = Some(today);
+        }
+
+        let source = self.settings.calendar.ics_source.trim().to_string();
+        if source.is_empty() || self.calendar.receiver.is_some() {
+            return;
+        }
+        let interval = Duration::from_secs(self.settings.calendar.refresh_minutes.max(1) as u64 * 60);
+        if matches!(self.calendar.last_fetch, Some(last) if last.elapsed() < interval) {
+            return;
+        }
+
+        let (sender, receiver) = channel();
+        self.calendar.receiver = Some(receiver);
+        self.calendar.last_fetch = Some(Instant::now());
+        self.runtime().spawn(async move {
+            sender.send(load_ics(source).await).ok();
+        });
+    }
+
+    /// Displays the agenda card
+    /// Shows today's events, with the current event highlighted
+    pub(crate) fn show_calendar_card(&self, ui: &mut egui::Ui) {
+        self.show_card(ui, "Today's Agenda", |ui| {
+            if let Some(error) = &self.calendar.error {
+                ui.colored_label(egui::Color32::from_rgb(220, 50, 50), "Could not load calendar")
+                    .on_hover_text(error);
+                return;
+            }
+
+            let now = Local::now();
+            let mut any = false;
+            for event in self.calendar.today() {
+                any = true;
+                let time = if event.all_day {
+                    "All day".to_string()
+                } else {
+                    format!("{} - {}", event.start.format("%H:%M"), event.end.format("%H:%M"))
+                };
+                let text = format!("{}  {}", time, event.summary);
+
+                if !event.all_day && event.end <= now {
+                    ui.label(RichText::new(text).color(egui::Color32::from_rgb(107, 114, 128)));
+                } else if !event.all_day && event.start <= now {
+                    ui.label(RichText::new(text).strong().color(egui::Color32::from_rgb(88, 165, 237)));
+                } else {
+                    ui.label(text);
+                }
+            }
+            if !any {
+                ui.label("No events today");
+            }
+        });
+    }
+
+    /// Today's events as time and summary, for copying the card
+    pub(crate) fn calendar_card_values(&self) -> Vec<(String, String)> {
+        if let Some(error) = &self.calendar.error {
+            return vec![("Error".to_string(), error.clone())];
+        }
+        self.calendar.today()
+            .map(|event| {
+                let time = if event.all_day {
+                    "All day".to_string()
+                } else {
+                    format!("{} - {}", event.start.format("%H:%M"), event.end.format("%H:%M"))
+                };
+                (time, event.summary.clone())
+            })
+            .collect()
+    }
+
+    /// Header countdown to the next meeting, shown once it's within the next hour
+    pub(crate) fn show_next_meeting(&self, ui: &mut egui::Ui) {
+        let Some((event, minutes)) = self.calendar.next_meeting() else { return };
+        if minutes >= 60 {
+            return;
+        }
+        let text = if minutes < 1 {
+            format!("📅 {} starting now", event.summary)
+        } else {
+            format!("📅 {} in {} min", event.summary, minutes)
+        };
+        let color = if minutes < 5 {
+            egui::Color32::from_rgb(202, 138, 4)
+        } else {
+            egui::Color32::from_rgb(88, 165, 237)
+        };
+        ui.colored_label(color, text)
+            .on_hover_text(format!("{} - {}", event.start.format("%H:%M"), event.end.format("%H:%M")));
+        ui.separator();
+    }
+
+    /// Settings section for the calendar feed
+    pub(crate) fn show_calendar_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+
+        ui.label("Calendar (.ics URL or file):");
+        let response = ui.add(egui::TextEdit::singleline(&mut self.settings.calendar.ics_source)
+            .hint_text("https://calendar.google.com/.../basic.ics")
+            .desired_width(280.0))
+            .on_hover_text("Google Calendar: Settings → Integrate calendar → Secret address in iCal format\nOutlook: Settings → Shared calendars → Publish a calendar → ICS link");
+        if response.lost_focus() {
+            changed = true;
+            self.calendar.last_fetch = None;
+            self.calendar.events.clear();
+            self.calendar.ics.clear();
+            self.calendar.error = None;
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Refresh every (minutes):");
+            changed |= ui.add(egui::DragValue::new(&mut self.settings.calendar.refresh_minutes)
+                .clamp_range(1..=240)).changed();
+        });
+
+        if changed {
+            self.save_settings();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Timelike;
+
+    fn local(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(year, month, day, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn unfold_joins_continuation_lines() {
+        let lines = unfold("BEGIN:VEVENT\r\nSUMMARY:Sprint \r\n planning\r\n\twith QA\r\nEND:VEVENT\r\n");
+        assert_eq!(lines, ["BEGIN:VEVENT", "SUMMARY:Sprint planningwith QA", "END:VEVENT"]);
+    }
+
+    #[test]
+    fn parse_ics_time_reads_dates_and_times() {
+        assert_eq!(parse_ics_time("VALUE=DATE", "20240603"), Some((local(2024, 6, 3, 0, 0), true)));
+        assert_eq!(parse_ics_time("", "20240603"), Some((local(2024, 6, 3, 0, 0), true)));
+        assert_eq!(parse_ics_time("", "20240603T093000"), Some((local(2024, 6, 3, 9, 30), false)));
+        let utc = Utc.with_ymd_and_hms(2024, 6, 3, 9, 30, 0).unwrap().with_timezone(&Local);
+        assert_eq!(parse_ics_time("", "20240603T093000Z"), Some((utc, false)));
+        assert_eq!(parse_ics_time("", "not a date"), None);
+    }
+
+    #[test]
+    fn parse_ics_time_converts_from_tzid() {
+        let utc = |hour| Utc.with_ymd_and_hms(2024, 7, 1, hour, 0, 0).unwrap().with_timezone(&Local);
+        // Berlin is UTC+2 in summer, under both its IANA and Windows names
+        assert_eq!(parse_ics_time("TZID=Europe/Berlin", "20240701T120000"), Some((utc(10), false)));
+        assert_eq!(parse_ics_time("TZID=\"W. Europe Standard Time\"", "20240701T120000"), Some((utc(10), false)));
+        assert_eq!(parse_ics_time("TZID=/mozilla.org/20050126_1/Europe/Berlin", "20240701T120000"), Some((utc(10), false)));
+        assert_eq!(parse_ics_time("TZID=America/New_York", "20240701T120000"), Some((utc(16), false)));
+        let winter = Utc.with_ymd_and_hms(2024, 1, 15, 11, 0, 0).unwrap().with_timezone(&Local);
+        assert_eq!(parse_ics_time("TZID=Europe/Berlin", "20240115T120000"), Some((winter, false)));
+    }
+
+    #[test]
+    fn transition_finds_the_nth_and_last_weekday() {
+        // Second Sunday of March at 2:00, and last Sunday of October at 3:00
+        let us_start = [0, 3, 0, 2, 2, 0, 0, 0];
+        let eu_end = [0, 10, 0, 5, 3, 0, 0, 0];
+        assert_eq!(transition(&us_start, 2024), NaiveDate::from_ymd_opt(2024, 3, 10).unwrap().and_hms_opt(2, 0, 0));
+        assert_eq!(transition(&eu_end, 2024), NaiveDate::from_ymd_opt(2024, 10, 27).unwrap().and_hms_opt(3, 0, 0));
+        assert_eq!(transition(&[0; 8], 2024), None);
+    }
+
+    #[test]
+    fn expand_rrule_daily_count() {
+        let start = local(2024, 6, 3, 9, 0);
+        let occurrences = expand_rrule(start, "FREQ=DAILY;COUNT=3", start, start + chrono::Duration::days(30));
+        assert_eq!(occurrences, [start, start + chrono::Duration::days(1), start + chrono::Duration::days(2)]);
+    }
+
+    #[test]
+    fn expand_rrule_count_includes_occurrences_before_the_window() {
+        let start = local(2024, 6, 3, 9, 0);
+        let keep_from = start + chrono::Duration::days(2);
+        let occurrences = expand_rrule(start, "FREQ=DAILY;COUNT=3", keep_from, start + chrono::Duration::days(30));
+        assert_eq!(occurrences, [keep_from]);
+    }
+
+    #[test]
+    fn expand_rrule_weekly_by_day() {
+        // Monday 3 June; Mondays and Wednesdays for two weeks
+        let start = local(2024, 6, 3, 9, 0);
+        let days = |n| start + chrono::Duration::days(n);
+        let occurrences = expand_rrule(start, "FREQ=WEEKLY;BYDAY=MO,WE", start, days(13));
+        assert_eq!(occurrences, [start, days(2), days(7), days(9)]);
+    }
+
+    #[test]
+    fn expand_rrule_until_stops_early() {
+        let start = local(2024, 6, 3, 9, 0);
+        let occurrences = expand_rrule(start, "FREQ=WEEKLY;INTERVAL=2;UNTIL=20240625T000000Z", start, local(2024, 12, 31, 0, 0));
+        assert_eq!(occurrences, [start, start + chrono::Duration::days(14)]);
+    }
+
+    #[test]
+    fn expand_rrule_keeps_the_time_of_day_across_daylight_saving_changes() {
+        // A year covers the spring and autumn changes in either hemisphere, whatever zone the tests run in
+        let start = local(2024, 1, 1, 9, 0);
+        let year_end = local(2024, 12, 31, 23, 0);
+        let daily = expand_rrule(start, "FREQ=DAILY", start, year_end);
+        assert_eq!(daily.len(), 366);
+        assert!(daily.iter().all(|time| time.hour() == 9 && time.minute() == 0));
+        let weekly = expand_rrule(start, "FREQ=WEEKLY;BYDAY=MO,TH", start, year_end);
+        assert_eq!(weekly.len(), 105);
+        assert!(weekly.iter().all(|time| time.hour() == 9));
+        assert!(weekly.contains(&local(2024, 3, 28, 9, 0)) && weekly.contains(&local(2024, 11, 4, 9, 0)));
+    }
+
+    #[test]
+    fn expand_rrule_monthly_skips_short_months() {
+        let start = local(2024, 1, 31, 9, 0);
+        let occurrences = expand_rrule(start, "FREQ=MONTHLY;COUNT=3", start, local(2025, 1, 1, 0, 0));
+        assert_eq!(occurrences, [start, local(2024, 3, 31, 9, 0), local(2024, 5, 31, 9, 0)]);
+    }
+}
@@ -0,0 +1,355 @@
+use chrono::Local;
+use eframe::egui;
+use sysinfo::{CpuExt, SystemExt};
+use log::{info, warn};
+use crate::guard::{ProcessKill, SensitiveAction};
+use crate::known_folders;
+use crate::{toast, DashboardCard, DevDashboard, NetworkStats};
+
+/// Readings over time behind a card, written out as CSV
+pub struct CardHistory {
+    pub columns: Vec<&'static str>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl CardHistory {
+    pub fn new(columns: Vec<&'static str>) -> Self {
+        Self { columns, rows: Vec::new() }
+    }
+
+    fn to_csv(&self) -> String {
+        let mut csv = self.columns.join(",");
+        csv.push_str("\r\n");
+        for row in &self.rows {
+            let fields: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+            csv.push_str(&fields.join(","));
+            csv.push_str("\r\n");
+        }
+        csv
+    }
+}
+
+/// Quotes a field that contains a separator, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn size_text(bytes: u64) -> String {
+    let (value, unit) = DevDashboard::format_bytes(bytes);
+    format!("{:.1} {}", value, unit)
+}
+
+/// "Label: value" lines under the card title, for chats and tickets
+fn values_text(title: &str, values: &[(String, String)]) -> String {
+    let mut text = format!("{} ({})\n", title, Local::now().format("%Y-%m-%d %H:%M:%S"));
+    for (label, value) in values {
+        text.push_str(&format!("{}: {}\n", label, value));
+    }
+    text
+}
+
+/// Rows keep their order and may repeat a label, so they are a list rather than an object
+fn values_json(title: &str, values: &[(String, String)]) -> String {
+    let rows: Vec<serde_json::Value> = values
+        .iter()
+        .map(|(label, value)| serde_json::json!({ "label": label, "value": value }))
+        .collect();
+    let json = serde_json::json!({
+        "card": title,
+        "time": Local::now().to_rfc3339(),
+        "values": rows,
+    });
+    serde_json::to_string_pretty(&json).unwrap_or_default()
+}
+
+impl DevDashboard {
+    fn card_title(&self, card: DashboardCard) -> String {
+        let title = match card {
+            DashboardCard::System => "System",
+            DashboardCard::Cpu => "CPU",
+            DashboardCard::Memory => "Memory",
+            DashboardCard::Storage => "Storage",
+            DashboardCard::Network => "Network",
+            DashboardCard::Gpu => "GPU",
+            DashboardCard::Certificates => "Certificates",
+            DashboardCard::Ticker => "Ticker",
+            DashboardCard::Calendar => "Today's Agenda",
+            DashboardCard::Todo => "Todo",
+            DashboardCard::Usage => "Screen Time",
+            DashboardCard::Backup => "Backups",
+            DashboardCard::Cloud => "Cloud Storage",
+            DashboardCard::Power => "Power",
+            DashboardCard::Battery => "Battery Health",
+            DashboardCard::Uptime => "Uptime History",
+            DashboardCard::NetworkShares => "Network Shares",
+            DashboardCard::Repos => "Repos",
+            DashboardCard::Stacks => "Stacks",
+            DashboardCard::Tunnels => "Tunnels",
+            DashboardCard::TopProcesses => "Top Processes",
+            DashboardCard::ProcessNetwork => "Network by Process",
+            DashboardCard::Wsl => "WSL",
+            DashboardCard::Plugin(index) => {
+                return self.plugins.plugins.get(index).map(|plugin| plugin.manifest.name.clone()).unwrap_or_default();
+            }
+            DashboardCard::Script(index) => {
+                return self.scripts.scripts.get(index).and_then(|script| script.title.clone()).unwrap_or_default();
+            }
+            DashboardCard::Wmi(index) => {
+                return self.settings.wmi_cards.cards.get(index).map(|config| config.title.clone()).unwrap_or_default();
+            }
+            DashboardCard::Command(index) => {
+                return self.settings.command_cards.cards.get(index).map(|config| config.title.clone()).unwrap_or_default();
+            }
+        };
+        title.to_string()
+    }
+
+    /// What the card currently shows, as label and value pairs
+    fn card_values(&self, card: DashboardCard) -> Vec<(String, String)> {
+        match card {
+            DashboardCard::System => self.system_card_values(),
+            DashboardCard::Cpu => self.cpu_card_values(),
+            DashboardCard::Memory => self.memory_card_values(),
+            DashboardCard::Storage => self.storage_card_values(),
+            DashboardCard::Network => self.network_card_values(),
+            DashboardCard::Gpu => self.gpu_card_values(),
+            DashboardCard::Certificates => self.certificates_card_values(),
+            DashboardCard::Ticker => self.ticker_card_values(),
+            DashboardCard::Calendar => self.calendar_card_values(),
+            DashboardCard::Todo => self.todo_card_values(),
+            DashboardCard::Usage => self.usage_card_values(),
+            DashboardCard::Backup => self.backup_card_values(),
+            DashboardCard::Cloud => self.cloud_card_values(),
+            DashboardCard::Power => self.power_card_values(),
+            DashboardCard::Battery => self.battery_card_values(),
+            DashboardCard::Uptime => self.uptime_card_values(),
+            DashboardCard::NetworkShares => self.network_shares_card_values(),
+            DashboardCard::Repos => self.repos_card_values(),
+            DashboardCard::Stacks => self.stacks_card_values(),
+            DashboardCard::Tunnels => self.tunnels_card_values(),
+            DashboardCard::TopProcesses => self.top_processes_card_values(),
+            DashboardCard::ProcessNetwork => self.process_network_card_values(),
+            DashboardCard::Wsl => self.wsl_card_values(),
+            DashboardCard::Plugin(index) => self.plugins.plugins.get(index)
+                .and_then(|plugin| plugin.content.as_ref())
+                .map(|content| content.values())
+                .unwrap_or_default(),
+            DashboardCard::Script(index) => self.scripts.scripts.get(index)
+                .map(|script| script.content.values())
+                .unwrap_or_default(),
+            DashboardCard::Wmi(index) => self.wmi_card_values(index),
+            DashboardCard::Command(index) => self.command_card_values(index),
+        }
+    }
+
+    /// Cards that keep readings over time; the rest only have their current values
+    fn card_history(&self, card: DashboardCard) -> Option<CardHistory> {
+        match card {
+            DashboardCard::Cpu => Some(self.metric_history(&["cpu_percent"], |history, index| {
+                vec![format!("{:.1}", history.cpu[index])]
+            })),
+            DashboardCard::Memory => Some(self.metric_history(&["memory_percent"], |history, index| {
+                vec![format!("{:.1}", history.memory[index])]
+            })),
+            DashboardCard::Network => Some(self.metric_history(&["received_bytes_per_sec", "sent_bytes_per_sec"], |history, index| {
+                vec![format!("{:.0}", history.received[index]), format!("{:.0}", history.sent[index])]
+            })),
+            DashboardCard::Storage => Some(self.storage_history.export_history()),
+            DashboardCard::Gpu => self.gpu_info.as_ref().map(|gpu| gpu.history.export_history()),
+            DashboardCard::Usage => Some(self.usage.export_history()),
+            DashboardCard::Power => Some(self.power_monitor.export_history()),
+            DashboardCard::Battery => Some(self.battery_health.export_history()),
+            DashboardCard::Uptime => Some(self.uptime_history.export_history()),
+            _ => None,
+        }
+    }
+
+    /// The tray's last minute of samples, which every headline card shares
+    fn metric_history(
+        &self,
+        columns: &[&'static str],
+        row: impl Fn(&crate::tray::MetricHistory, usize) -> Vec<String>,
+    ) -> CardHistory {
+        let history = &self.tray.history;
+        let mut names = vec!["time"];
+        names.extend_from_slice(columns);
+        let mut table = CardHistory::new(names);
+        for (index, time) in history.times.iter().enumerate() {
+            let mut fields = vec![time.format("%Y-%m-%d %H:%M:%S").to_string()];
+            fields.extend(row(history, index));
+            table.rows.push(fields);
+        }
+        table
+    }
+
+    fn system_card_values(&self) -> Vec<(String, String)> {
+        let uptime = self.sys.uptime();
+        vec![
+            ("OS".to_string(), format!("{} {}", self.sys.name().unwrap_or_default(), self.sys.os_version().unwrap_or_default())),
+            ("Architecture".to_string(), crate::arch::os_arch().label().to_string()),
+            ("Hostname".to_string(), self.sys.host_name().unwrap_or_default()),
+            ("Uptime".to_string(), format!("{} hours, {} minutes", uptime / 3600, uptime % 3600 / 60)),
+        ]
+    }
+
+    fn cpu_card_values(&self) -> Vec<(String, String)> {
+        let Some(cpu) = self.sys.cpus().first() else { return Vec::new() };
+        vec![
+            ("Model".to_string(), cpu.brand().to_string()),
+            ("Physical Cores".to_string(), self.sys.physical_core_count().unwrap_or(0).to_string()),
+            ("Threads".to_string(), self.sys.cpus().len().to_string()),
+            ("Speed".to_string(), format!("{:.1} GHz", cpu.frequency() as f64 / 1000.0)),
+            ("Usage".to_string(), format!("{:.1}%", self.current_cpu_usage.target)),
+        ]
+    }
+
+    fn memory_card_values(&self) -> Vec<(String, String)> {
+        let total = self.sys.total_memory();
+        let used = total.saturating_sub(self.sys.available_memory());
+        vec![
+            ("Total".to_string(), size_text(total)),
+            ("Used".to_string(), format!("{} ({:.1}%)", size_text(used), self.memory_usage.target * 100.0)),
+            ("Free".to_string(), size_text(total - used)),
+        ]
+    }
+
+    fn storage_card_values(&self) -> Vec<(String, String)> {
+        let mut values = Vec::new();
+        for volume in self.volume_monitor.volumes() {
+            let Some((total, free)) = volume.space else { continue };
+            let used = total.saturating_sub(free) as f64 / total.max(1) as f64 * 100.0;
+            let mut value = format!("{:.1}% used, {} free of {}", used, size_text(free), size_text(total));
+            if !volume.online {
+                value.push_str(" (offline, last reading)");
+            }
+            values.push((volume.mount.clone(), value));
+            let (forecast, _) = self.storage_history.forecast(&volume.mount).describe();
+            values.push((format!("{} forecast", volume.mount), forecast));
+        }
+        values
+    }
+
+    fn network_card_values(&self) -> Vec<(String, String)> {
+        let mut values = Vec::new();
+        let mut names: Vec<&String> = self.network_stats.keys().filter(|name| DevDashboard::is_physical_interface(name)).collect();
+        names.sort();
+        if self.settings.network_card.aggregate && names.len() > 1 {
+            let all: Vec<&NetworkStats> = names.iter().map(|name| &self.network_stats[*name]).collect();
+            let received: u64 = all.iter().map(|stats| stats.total_received).sum();
+            let sent: u64 = all.iter().map(|stats| stats.total_sent).sum();
+            let received_speed: f64 = all.iter().map(|stats| stats.received_speed).sum();
+            let sent_speed: f64 = all.iter().map(|stats| stats.sent_speed).sum();
+            values.push(("All interfaces received".to_string(), format!("{} since boot, {:.1} kb/s", size_text(received), received_speed / 1024.0)));
+            values.push(("All interfaces sent".to_string(), format!("{} since boot, {:.1} kb/s", size_text(sent), sent_speed / 1024.0)));
+        }
+        for name in names {
+            let stats = &self.network_stats[name];
+            values.push((format!("{} received", name), format!("{} since boot, {:.1} kb/s", size_text(stats.total_received), stats.received_speed / 1024.0)));
+            values.push((format!("{} sent", name), format!("{} since boot, {:.1} kb/s", size_text(stats.total_sent), stats.sent_speed / 1024.0)));
+            values.push((format!("{} session", name), format!("↓ {} ↑ {}", size_text(stats.session.received), size_text(stats.session.sent))));
+        }
+        values
+    }
+
+    fn gpu_card_values(&self) -> Vec<(String, String)> {
+        let Some(gpu) = &self.gpu_info else {
+            return vec![("GPU".to_string(), "No GPU detected".to_string())];
+        };
+        let mut values = vec![("Name".to_string(), gpu.name.clone())];
+        if let Some(driver) = &gpu.driver_version {
+            values.push(("Driver".to_string(), driver.clone()));
+        }
+        if let Some(utilization) = gpu.utilization {
+            values.push(("Usage".to_string(), format!("{:.1}%", utilization)));
+        }
+        if let Some(temp) = gpu.temperature {
+            values.push(("Temperature".to_string(), format!("{}°C", temp)));
+        }
+        if let (Some(total), Some(used)) = (gpu.memory_total, gpu.memory_used) {
+            values.push(("Memory".to_string(), format!("{} / {}", size_text(used), size_text(total))));
+        }
+        if let Some(power) = gpu.power_draw {
+            values.push(("Power".to_string(), format!("{:.0} W", power)));
+        }
+        if let Some(clock) = gpu.graphics_clock {
+            values.push(("Graphics clock".to_string(), format!("{} MHz", clock)));
+        }
+        if let Some(clock) = gpu.memory_clock {
+            values.push(("Memory clock".to_string(), format!("{} MHz", clock)));
+        }
+        if let Some(ecc) = gpu.compute.ecc_enabled {
+            values.push(("ECC".to_string(), if ecc { "On" } else { "Off" }.to_string()));
+        }
+        if let Some(mode) = gpu.compute.compute_mode.filter(|_| gpu.compute.is_supported()) {
+            values.push(("Compute mode".to_string(), mode.to_string()));
+        }
+        if let Some(pages) = gpu.compute.retired_pages() {
+            values.push(("Retired pages".to_string(), pages.to_string()));
+        }
+        values
+    }
+
+    fn export_card_history(&self, card: DashboardCard) {
+        let Some(history) = self.card_history(card) else { return };
+        let title = self.card_title(card);
+        if history.rows.is_empty() {
+            toast::show_toast(&format!("No {} history yet", title), "Readings are recorded while the dashboard runs");
+            return;
+        }
+        let folder = known_folders::documents().join("Dev Dashboard");
+        let name = format!("{}-{}.csv", card.key().replace(':', "-"), Local::now().format("%Y%m%d-%H%M%S"));
+        let path = folder.join(name);
+        let result = std::fs::create_dir_all(&folder).and_then(|_| std::fs::write(&path, history.to_csv()));
+        match result {
+            Ok(()) => {
+                info!("Exported {} history to {:?}", title, path);
+                let _ = std::process::Command::new("explorer").arg(format!("/select,{}", path.display())).spawn();
+            }
+            Err(e) => {
+                warn!("Could not export {} history: {}", title, e);
+                toast::show_toast(&format!("Could not export {} history", title), &e.to_string());
+            }
+        }
+    }
+
+    /// Right-click menu of a card, on the dashboard or popped out
+    pub(crate) fn show_card_menu(&mut self, response: egui::Response, card: DashboardCard) {
+        let has_history = matches!(card,
+            DashboardCard::Cpu | DashboardCard::Memory | DashboardCard::Network | DashboardCard::Storage
+            | DashboardCard::Gpu | DashboardCard::Usage | DashboardCard::Power | DashboardCard::Battery
+            | DashboardCard::Uptime);
+        let process = if card == DashboardCard::TopProcesses { self.top_process_menu_target() } else { None };
+        response.context_menu(|ui| {
+            if let Some((pid, name)) = &process {
+                if ui.button(format!("End {} (PID {})", name, pid)).clicked() {
+                    self.request_sensitive_action(SensitiveAction::KillProcess(ProcessKill::TopProcess {
+                        pid: *pid,
+                        name: name.clone(),
+                    }));
+                    ui.close_menu();
+                }
+                ui.separator();
+            }
+            if ui.button("Copy values as text").clicked() {
+                let text = values_text(&self.card_title(card), &self.card_values(card));
+                ui.output_mut(|o| o.copied_text = text);
+                ui.close_menu();
+            }
+            if ui.button("Copy values as JSON").clicked() {
+                let json = values_json(&self.card_title(card), &self.card_values(card));
+                ui.output_mut(|o| o.copied_text = json);
+                ui.close_menu();
+            }
+            if ui.add_enabled(has_history, egui::Button::new("Export history as CSV"))
+                .on_disabled_hover_text("This card only has current values")
+                .clicked() {
+                self.export_card_history(card);
+                ui.close_menu();
+            }
+        });
+    }
+}
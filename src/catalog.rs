@@ -0,0 +1,312 @@
+use std::fs::File;
+use std::io::Read;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::Duration;
+use ed25519_dalek::{Signature, VerifyingKey};
+use eframe::egui;
+use egui::{Color32, RichText};
+use reqwest::Client;
+use serde::{Serialize, Deserialize};
+use log::{debug, info, warn};
+use crate::app_info::AppInfo;
+use crate::arch::Arch;
+use crate::install_size::InstallSize;
+use crate::{storage, DevDashboard, NiniteApp};
+
+/// Release builds set these; without a key the built-in catalog is never replaced
+const CATALOG_URL: Option<&str> = option_env!("DEV_DASHBOARD_CATALOG_URL");
+const CATALOG_PUBLIC_KEY: Option<&str> = option_env!("DEV_DASHBOARD_CATALOG_KEY");   // Ed25519, 64 hex digits
+const DEFAULT_CATALOG_URL: &str = "https://raw.githubusercontent.com/Shiloh-Coffey/dev-dashboard/main/catalog/apps.json";
+const CACHE_FILE: &str = "catalog.json";
+const CACHE_SIGNATURE_FILE: &str = "catalog.sig";
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Persisted catalog update preference
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct CatalogSettings {
+    pub auto_update: bool,
+}
+
+impl Default for CatalogSettings {
+    fn default() -> Self {
+        Self { auto_update: true }
+    }
+}
+
+/// One app in the remote manifest; replaces the built-in entry with the same Ninite ID
+#[derive(Deserialize)]
+struct CatalogEntry {
+    name: String,
+    category: String,
+    ninite_id: String,
+    #[serde(default)]
+    registry_keys: Vec<String>,
+    #[serde(default)]
+    file_paths: Vec<String>,
+    #[serde(default)]
+    size: Option<InstallSize>,  // Replaces the built-in estimate when present
+    #[serde(default)]
+    builds: Option<Vec<Arch>>,  // Replaces the built-in list of native builds when present
+    #[serde(default)]
+    info: Option<AppInfo>,      // Replaces the built-in description, homepage, license and winget ID
+}
+
+/// The signed document at the catalog URL; its detached signature lives at the same URL plus ".sig"
+#[derive(Deserialize)]
+struct Manifest {
+    version: u64,       // Only newer versions than the cached one are applied
+    apps: Vec<CatalogEntry>,
+}
+
+/// A manifest whose signature checked out, with the bytes it was checked against
+struct VerifiedManifest {
+    manifest: Manifest,
+    text: String,
+    signature: String,
+}
+
+/// Version of the catalog in use and the background fetch
+#[derive(Default)]
+pub struct AppCatalog {
+    pub version: Option<u64>,   // None while the built-in catalog is in use
+    started: bool,
+    receiver: Option<Receiver<Result<VerifiedManifest, String>>>,
+    pub error: Option<String>,
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    text.trim()
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| match std::str::from_utf8(pair) {
+            Ok(pair) if pair.len() == 2 => u8::from_str_radix(pair, 16).ok(),
+            _ => None,
+        })
+        .collect()
+}
+
+fn public_key() -> Option<VerifyingKey> {
+    let bytes: [u8; 32] = decode_hex(CATALOG_PUBLIC_KEY?)?.try_into().ok()?;
+    VerifyingKey::from_bytes(&bytes).ok()
+}
+
+/// Checks the detached signature over the exact manifest bytes before parsing them
+fn verify(text: &str, signature: &str) -> Result<VerifiedManifest, String> {
+    let key = public_key().ok_or("No valid catalog signing key in this build")?;
+    verify_with(&key, text, signature)
+}
+
+fn verify_with(key: &VerifyingKey, text: &str, signature: &str) -> Result<VerifiedManifest, String> {
+    let bytes: [u8; 64] = decode_hex(signature)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or("Catalog signature is not 64 hex-encoded bytes")?;
+    key.verify_strict(text.as_bytes(), &Signature::from_bytes(&bytes))
+        .map_err(|_| "Catalog signature does not match".to_string())?;
+    let manifest: Manifest = serde_json::from_str(text).map_err(|e| format!("Invalid catalog: {}", e))?;
+    Ok(VerifiedManifest { manifest, text: text.to_string(), signature: signature.trim().to_string() })
+}
+
+async fn fetch(url: &str) -> Result<VerifiedManifest, String> {
+    let client = Client::builder().timeout(FETCH_TIMEOUT).build().map_err(|e| e.to_string())?;
+    let get = |url: String| {
+        let client = client.clone();
+        async move {
+            client.get(&url).send().await
+                .and_then(|response| response.error_for_status())
+                .map_err(|e| e.to_string())?
+                .text().await
+                .map_err(|e| e.to_string())
+        }
+    };
+    let text = get(url.to_string()).await?;
+    let signature = get(format!("{}.sig", url)).await?;
+    verify(&text, &signature)
+}
+
+fn read_file(path: &str) -> Option<String> {
+    let mut contents = String::new();
+    File::open(path).ok()?.read_to_string(&mut contents).ok()?;
+    Some(contents)
+}
+
+/// Replaces entries by Ninite ID and appends new ones; returns how many entries changed
+fn apply(apps: &mut Vec<NiniteApp>, manifest: &Manifest) -> usize {
+    let mut changed = 0;
+    for entry in &manifest.apps {
+        let app = match apps.iter_mut().find(|app| app.ninite_id == entry.ninite_id) {
+            Some(app) => app,
+            None => {
+                apps.push(NiniteApp::new(&entry.name, &entry.category, &entry.ninite_id, Vec::new(), Vec::new()));
+                apps.last_mut().unwrap()
+            }
+        };
+        if app.name != entry.name || app.category != entry.category
+            || app.registry_keys != entry.registry_keys || app.file_paths != entry.file_paths {
+            app.name = entry.name.clone();
+            app.category = entry.category.clone();
+            app.registry_keys = entry.registry_keys.clone();
+            app.file_paths = entry.file_paths.clone();
+            app.check_installation();
+            changed += 1;
+        }
+        if entry.size.is_some() {
+            app.size = entry.size;
+        }
+        if let Some(builds) = &entry.builds {
+            app.builds = builds.clone();
+        }
+        if let Some(info) = &entry.info {
+            app.info = info.clone();
+        }
+    }
+    changed
+}
+
+impl AppCatalog {
+    /// Applies the last verified remote catalog saved next to the settings
+    pub fn load_cached(apps: &mut Vec<NiniteApp>) -> Self {
+        let mut catalog = Self::default();
+        let (Some(text), Some(signature)) = (read_file(CACHE_FILE), read_file(CACHE_SIGNATURE_FILE)) else {
+            return catalog;
+        };
+        match verify(&text, &signature) {
+            Ok(verified) => {
+                apply(apps, &verified.manifest);
+                debug!("Using cached app catalog version {}", verified.manifest.version);
+                catalog.version = Some(verified.manifest.version);
+            }
+            Err(e) => warn!("Ignoring cached app catalog: {}", e),
+        }
+        catalog
+    }
+}
+
+impl DevDashboard {
+    /// Fetches the remote catalog once per run and applies it when it is newer
+    pub(crate) fn update_catalog(&mut self) {
+        if !self.catalog.started && self.settings.catalog.auto_update && CATALOG_PUBLIC_KEY.is_some() {
+            self.catalog.started = true;
+            let url = CATALOG_URL.unwrap_or(DEFAULT_CATALOG_URL).to_string();
+            debug!("Checking {} for app catalog updates", url);
+            let (sender, receiver) = channel();
+            self.catalog.receiver = Some(receiver);
+            self.runtime().spawn(async move {
+                sender.send(fetch(&url).await).ok();
+            });
+        }
+
+        let Some(receiver) = &self.catalog.receiver else { return };
+        let Ok(result) = receiver.try_recv() else { return };
+        self.catalog.receiver = None;
+        let verified = match result {
+            Ok(verified) => verified,
+            Err(e) => {
+                warn!("App catalog update failed: {}", e);
+                self.catalog.error = Some(e);
+                return;
+            }
+        };
+        // An older manifest is a replay of a superseded catalog, not an update
+        if self.catalog.version.is_some_and(|version| verified.manifest.version <= version) {
+            debug!("App catalog version {} is current", verified.manifest.version);
+            return;
+        }
+
+        let changed = apply(&mut self.app_base, &verified.manifest);
+        self.rebuild_app_list();
+        info!("Updated app catalog to version {} ({} apps changed)", verified.manifest.version, changed);
+        self.catalog.version = Some(verified.manifest.version);
+        self.catalog.error = None;
+        // Each file is replaced whole; a crash between the two leaves a pair that fails verification,
+        // so the built-in catalog is used until the next fetch
+        let saved = storage::write_atomic(CACHE_FILE, verified.text.as_bytes())
+            .and_then(|_| storage::write_atomic(CACHE_SIGNATURE_FILE, verified.signature.as_bytes()));
+        if let Err(e) = saved {
+            warn!("Could not save app catalog: {}", e);
+        }
+    }
+
+    /// Catalog source line for the installer page
+    pub(crate) fn show_catalog_status(&self, ui: &mut egui::Ui) {
+        let text = match self.catalog.version {
+            Some(version) => format!("App catalog version {} (signed update)", version),
+            None => "Built-in app catalog".to_string(),
+        };
+        let label = ui.label(RichText::new(text).small().color(Color32::from_rgb(156, 163, 175)));
+        if let Some(error) = &self.catalog.error {
+            label.on_hover_text(format!("Last update check failed: {}", error));
+        }
+    }
+
+    /// Settings section for catalog updates
+    pub(crate) fn show_catalog_settings(&mut self, ui: &mut egui::Ui) {
+        ui.label("App Catalog:");
+        let enabled = CATALOG_PUBLIC_KEY.is_some();
+        let text = "Fetch signed app detection updates on startup";
+        let response = ui.add_enabled(enabled, egui::Checkbox::new(&mut self.settings.catalog.auto_update, text));
+        if !enabled {
+            response.on_disabled_hover_text("This build has no catalog signing key");
+        } else if response.changed() {
+            self.save_settings();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    const MANIFEST: &str = r#"{"version": 3, "apps": [{"name": "Git", "category": "Developer Tools", "ninite_id": "git"}]}"#;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    fn signed(key: &SigningKey, text: &str) -> String {
+        hex(&key.sign(text.as_bytes()).to_bytes())
+    }
+
+    #[test]
+    fn decode_hex_reads_pairs() {
+        assert_eq!(decode_hex("00ff7A"), Some(vec![0x00, 0xff, 0x7a]));
+        assert_eq!(decode_hex("  0a0b\n"), Some(vec![0x0a, 0x0b]));
+        assert_eq!(decode_hex(""), Some(vec![]));
+    }
+
+    #[test]
+    fn decode_hex_rejects_bad_input() {
+        assert_eq!(decode_hex("abc"), None);
+        assert_eq!(decode_hex("zz"), None);
+        assert_eq!(decode_hex("é0"), None);
+    }
+
+    #[test]
+    fn verify_accepts_a_matching_signature() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let signature = signed(&key, MANIFEST);
+        let verified = verify_with(&key.verifying_key(), MANIFEST, &format!("{}\n", signature)).unwrap();
+        assert_eq!(verified.manifest.version, 3);
+        assert_eq!(verified.manifest.apps[0].ninite_id, "git");
+        assert_eq!(verified.signature, signature);
+    }
+
+    #[test]
+    fn verify_rejects_changed_text_or_another_key() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let signature = signed(&key, MANIFEST);
+        let changed = MANIFEST.replace("\"version\": 3", "\"version\": 4");
+        assert!(verify_with(&key.verifying_key(), &changed, &signature).is_err());
+        let other = SigningKey::from_bytes(&[8; 32]);
+        assert!(verify_with(&other.verifying_key(), MANIFEST, &signature).is_err());
+        assert!(verify_with(&key.verifying_key(), MANIFEST, &signature[..64]).is_err());
+    }
+
+    #[test]
+    fn verify_rejects_signed_invalid_json() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let text = "not json";
+        assert!(verify_with(&key.verifying_key(), text, &signed(&key, text)).is_err());
+    }
+}
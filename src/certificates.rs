@@ -0,0 +1,328 @@
+use std::collections::HashMap;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use eframe::egui;
+use egui::RichText;
+use serde::{Serialize, Deserialize};
+use log::{info, warn};
+use crate::DevDashboard;
+
+/// How often registered domains are re-checked in the background
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+/// Connection and handshake timeout per domain
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Persisted list of domains to watch
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct CertificateSettings {
+    pub domains: Vec<String>,   // host or host:port, defaults to port 443
+    pub warn_days: u32,         // Warn when a certificate expires within this many days
+}
+
+impl Default for CertificateSettings {
+    fn default() -> Self {
+        Self {
+            domains: Vec::new(),
+            warn_days: 14,
+        }
+    }
+}
+
+/// Result of the latest check for one domain
+#[derive(Clone)]
+pub struct CertificateStatus {
+    pub expires: Option<String>,    // Expiry date (YYYY-MM-DD)
+    pub days_left: Option<i64>,     // Whole days until expiry, negative once expired
+    pub subject: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Background certificate checker state
+#[derive(Default)]
+pub struct CertificateMonitor {
+    pub results: HashMap<String, CertificateStatus>,
+    last_check: Option<Instant>,
+    receiver: Option<Receiver<(String, CertificateStatus)>>,
+    pending: usize,             // Domains still being checked in the current run
+    new_domain: String,         // Text field in Settings
+}
+
+impl CertificateMonitor {
+    pub fn is_checking(&self) -> bool {
+        self.pending > 0
+    }
+
+    /// Starts checking every domain on a background thread
+    pub fn start_check(&mut self, domains: &[String]) {
+        if self.is_checking() || domains.is_empty() {
+            return;
+        }
+
+        info!("Checking TLS certificates for {} domains", domains.len());
+        let (sender, receiver) = channel();
+        self.receiver = Some(receiver);
+        self.pending = domains.len();
+        self.last_check = Some(Instant::now());
+
+        let domains = domains.to_vec();
+        std::thread::spawn(move || {
+            for domain in domains {
+                let status = check_domain(&domain);
+                if sender.send((domain, status)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Collects finished checks and schedules the next periodic run
+    pub fn update(&mut self, settings: &CertificateSettings) {
+        if let Some(receiver) = &self.receiver {
+            while let Ok((domain, status)) = receiver.try_recv() {
+                match (status.days_left, &status.error) {
+                    (_, Some(error)) => warn!("Certificate check failed for {}: {}", domain, error),
+                    (Some(days), None) if days <= settings.warn_days as i64 => {
+                        warn!("Certificate for {} expires in {} days", domain, days)
+                    }
+                    _ => {}
+                }
+                self.results.insert(domain, status);
+                self.pending = self.pending.saturating_sub(1);
+            }
+            if self.pending == 0 {
+                self.receiver = None;
+            }
+        }
+
+        let due = match self.last_check {
+            Some(last) => last.elapsed() >= CHECK_INTERVAL,
+            None => true,
+        };
+        if due {
+            self.start_check(&settings.domains);
+        }
+    }
+}
+
+/// Splits "host:port" or "[v6]:port" into its parts, defaulting to 443.
+/// IPv6 brackets are removed; a bare IPv6 address has no port.
+fn split_host_port(domain: &str) -> (&str, u16) {
+    if let Some(rest) = domain.strip_prefix('[') {
+        if let Some((host, after)) = rest.split_once(']') {
+            let port = after.strip_prefix(':').and_then(|port| port.parse().ok()).unwrap_or(443);
+            return (host, port);
+        }
+    }
+    if domain.matches(':').count() > 1 {
+        return (domain, 443);
+    }
+    if let Some((host, port)) = domain.rsplit_once(':') {
+        if let Ok(port) = port.parse() {
+            return (host, port);
+        }
+    }
+    (domain, 443)
+}
+
+/// Connects to the domain and reads the expiry of the certificate it presents.
+/// Validation is disabled so expired or misconfigured certificates can still be inspected.
+fn check_domain(domain: &str) -> CertificateStatus {
+    let failed = |error: String| CertificateStatus {
+        expires: None,
+        days_left: None,
+        subject: None,
+        error: Some(error),
+    };
+
+    let (host, port) = split_host_port(domain);
+    let address = match (host, port).to_socket_addrs().ok().and_then(|mut addrs| addrs.next()) {
+        Some(address) => address,
+        None => return failed("Could not resolve host".to_string()),
+    };
+
+    let stream = match TcpStream::connect_timeout(&address, CONNECT_TIMEOUT) {
+        Ok(stream) => stream,
+        Err(e) => return failed(format!("Connection failed: {}", e)),
+    };
+    stream.set_read_timeout(Some(CONNECT_TIMEOUT)).ok();
+    stream.set_write_timeout(Some(CONNECT_TIMEOUT)).ok();
+
+    let connector = match native_tls::TlsConnector::builder()
+        .danger_accept_invalid_certs(true)
+        .danger_accept_invalid_hostnames(true)
+        .build() {
+        Ok(connector) => connector,
+        Err(e) => return failed(format!("TLS setup failed: {}", e)),
+    };
+
+    let tls_stream = match connector.connect(host, stream) {
+        Ok(stream) => stream,
+        Err(e) => return failed(format!("TLS handshake failed: {}", e)),
+    };
+
+    let der = match tls_stream.peer_certificate() {
+        Ok(Some(certificate)) => match certificate.to_der() {
+            Ok(der) => der,
+            Err(e) => return failed(format!("Could not read certificate: {}", e)),
+        },
+        Ok(None) => return failed("Server did not present a certificate".to_string()),
+        Err(e) => return failed(format!("Could not read certificate: {}", e)),
+    };
+
+    let (_, certificate) = match x509_parser::parse_x509_certificate(&der) {
+        Ok(parsed) => parsed,
+        Err(e) => return failed(format!("Invalid certificate: {}", e)),
+    };
+
+    let not_after = certificate.validity().not_after;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs() as i64).unwrap_or(0);
+    let days_left = (not_after.timestamp() - now).div_euclid(86_400);
+
+    CertificateStatus {
+        expires: Some(not_after.to_datetime().date().to_string()),
+        days_left: Some(days_left),
+        subject: Some(certificate.subject().to_string()),
+        error: None,
+    }
+}
+
+impl DevDashboard {
+    /// Displays the certificate expiry card
+    /// Shows days until expiry for each registered domain
+    pub(crate) fn show_certificates_card(&mut self, ui: &mut egui::Ui) {
+        let warn_days = self.settings.certificates.warn_days as i64;
+        let domains = self.settings.certificates.domains.clone();
+        let monitor = &mut self.certificate_monitor;
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Certificates").strong().heading());
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if monitor.is_checking() {
+                    ui.spinner();
+                } else if ui.small_button("⟳").on_hover_text("Check now").clicked() {
+                    monitor.start_check(&domains);
+                }
+            });
+        });
+        ui.add_space(8.0);
+
+        for domain in &domains {
+            ui.horizontal(|ui| {
+                ui.label(domain);
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    match monitor.results.get(domain) {
+                        Some(CertificateStatus { error: Some(error), .. }) => {
+                            ui.colored_label(egui::Color32::from_rgb(220, 50, 50), "Error")
+                                .on_hover_text(error);
+                        }
+                        Some(CertificateStatus { days_left: Some(days), expires, subject, .. }) => {
+                            let (text, color) = if *days < 0 {
+                                ("Expired".to_string(), egui::Color32::from_rgb(220, 50, 50))
+                            } else if *days <= warn_days {
+                                (format!("⚠ {} days", days), egui::Color32::from_rgb(202, 138, 4))
+                            } else {
+                                (format!("{} days", days), egui::Color32::from_rgb(22, 163, 74))
+                            };
+                            ui.colored_label(color, text).on_hover_text(format!(
+                                "Expires {}\n{}",
+                                expires.as_deref().unwrap_or("unknown"),
+                                subject.as_deref().unwrap_or_default()
+                            ));
+                        }
+                        _ => {
+                            ui.label("Checking...");
+                        }
+                    }
+                });
+            });
+        }
+    }
+
+    /// Days left per domain, for copying the card
+    pub(crate) fn certificates_card_values(&self) -> Vec<(String, String)> {
+        self.settings.certificates.domains
+            .iter()
+            .map(|domain| {
+                let value = match self.certificate_monitor.results.get(domain) {
+                    Some(CertificateStatus { error: Some(error), .. }) => format!("Error: {}", error),
+                    Some(CertificateStatus { days_left: Some(days), expires, .. }) => {
+                        format!("{} days left, expires {}", days, expires.as_deref().unwrap_or("unknown"))
+                    }
+                    _ => "Checking".to_string(),
+                };
+                (domain.clone(), value)
+            })
+            .collect()
+    }
+
+    /// Settings section for managing monitored domains
+    pub(crate) fn show_certificate_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+
+        ui.label("Certificate Monitoring:");
+        let mut removed = None;
+        for (index, domain) in self.settings.certificates.domains.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(domain);
+                if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                    removed = Some(index);
+                }
+            });
+        }
+        if let Some(index) = removed {
+            let domain = self.settings.certificates.domains.remove(index);
+            self.certificate_monitor.results.remove(&domain);
+            changed = true;
+        }
+
+        ui.horizontal(|ui| {
+            let response = ui.add(egui::TextEdit::singleline(&mut self.certificate_monitor.new_domain)
+                .hint_text("example.com or host:8443")
+                .desired_width(180.0));
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if ui.button("Add").clicked() || submitted {
+                let domain = self.certificate_monitor.new_domain.trim().to_lowercase();
+                if !domain.is_empty() && !self.settings.certificates.domains.contains(&domain) {
+                    info!("Monitoring certificate for {}", domain);
+                    self.settings.certificates.domains.push(domain);
+                    // Check the new domain along with the others right away
+                    self.certificate_monitor.last_check = None;
+                    changed = true;
+                }
+                self.certificate_monitor.new_domain.clear();
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Warn days before expiry:");
+            changed |= ui.add(egui::DragValue::new(&mut self.settings.certificates.warn_days)
+                .clamp_range(1..=365)).changed();
+        });
+
+        if changed {
+            self.save_settings();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_host_port_defaults_to_443() {
+        assert_eq!(split_host_port("example.com"), ("example.com", 443));
+        assert_eq!(split_host_port("example.com:8443"), ("example.com", 8443));
+        assert_eq!(split_host_port("example.com:https"), ("example.com:https", 443));
+    }
+
+    #[test]
+    fn split_host_port_handles_ipv6() {
+        assert_eq!(split_host_port("[::1]:8443"), ("::1", 8443));
+        assert_eq!(split_host_port("[2001:db8::1]"), ("2001:db8::1", 443));
+        assert_eq!(split_host_port("2001:db8::1"), ("2001:db8::1", 443));
+        assert_eq!(split_host_port("::1"), ("::1", 443));
+    }
+}
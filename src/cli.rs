@@ -0,0 +1,173 @@
+use std::path::PathBuf;
+use eframe::egui;
+use log::{info, warn};
+use crate::guard::SensitiveAction;
+use crate::{DevDashboard, InstallerState, Tab, ToolPage};
+
+/// How shortcuts, scripts and deployment tools start the dashboard, e.g.
+/// `dev_dashboard.exe --profile Work --install "Chrome,Visual Studio Code" --silent`
+#[derive(Default)]
+pub struct StartupOptions {
+    pub tab: Option<String>,            // dashboard, tools, processes, security, containers or services
+    pub preset: Option<String>,         // App selection preset to select on the installer page
+    pub minimized: bool,
+    pub profile: Option<String>,        // Keeps settings and history in profiles\<name>
+    pub install: Vec<String>,           // App names or Ninite IDs to install right away
+    pub silent: bool,                   // Install in the background and exit when done
+}
+
+impl StartupOptions {
+    /// Reads the flags after the executable name; unknown flags are logged and skipped
+    /// rather than refusing to start, since the log is the only place a windowed app can report them
+    pub fn parse(args: &[String]) -> (Self, Vec<String>) {
+        let mut options = StartupOptions::default();
+        let mut problems = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            // Both `--tab tools` and `--tab=tools`
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = |flag: &str| match inline.clone().or_else(|| args.next().cloned()) {
+                Some(given) if !given.trim().is_empty() => Some(given.trim().to_string()),
+                _ => {
+                    problems.push(format!("{} needs a value", flag));
+                    None
+                }
+            };
+            match flag {
+                "--tab" => options.tab = value(flag),
+                "--preset" => options.preset = value(flag),
+                "--profile" => options.profile = value(flag),
+                "--install" => {
+                    if let Some(list) = value(flag) {
+                        options.install.extend(list.split(',').map(str::trim).filter(|name| !name.is_empty()).map(str::to_string));
+                    }
+                }
+                "--minimized" => options.minimized = true,
+                "--silent" => options.silent = true,
+                _ => problems.push(format!("Unknown argument {:?}", arg)),
+            }
+        }
+        if options.silent && options.install.is_empty() {
+            problems.push("--silent only applies together with --install".to_string());
+            options.silent = false;
+        }
+        (options, problems)
+    }
+
+    /// Folder the profile's settings and history live in, relative to the dashboard's folder;
+    /// None for a name that would point outside it
+    pub fn profile_folder(&self) -> Option<PathBuf> {
+        let name = self.profile.as_deref()?;
+        let valid = !name.contains(['\\', '/', ':', '*', '?', '"', '<', '>', '|']) && name != "." && name != "..";
+        valid.then(|| PathBuf::from("profiles").join(name))
+    }
+}
+
+fn parse_tab(name: &str) -> Option<Tab> {
+    match name.to_lowercase().as_str() {
+        "dashboard" => Some(Tab::Dashboard),
+        "tools" | "installer" => Some(Tab::Tools),
+        "processes" => Some(Tab::Processes),
+        "security" => Some(Tab::Security),
+        "containers" | "docker" => Some(Tab::Containers),
+        "services" => Some(Tab::Services),
+        _ => None,
+    }
+}
+
+/// Startup flags still to act on, and the unattended install being waited for
+#[derive(Default)]
+pub struct Startup {
+    pending: Option<StartupOptions>,
+    silent_install: bool,
+    install_started: bool,
+}
+
+impl Startup {
+    pub fn new(options: StartupOptions) -> Self {
+        Self { pending: Some(options), ..Default::default() }
+    }
+
+    /// Flags forwarded by a later start, acted on in the next frame without dropping a silent install in progress
+    pub fn queue(&mut self, options: StartupOptions) {
+        self.pending = Some(options);
+    }
+}
+
+impl DevDashboard {
+    /// Acts on the command-line flags once the first frame is up, then closes the window after a silent install
+    pub(crate) fn update_startup(&mut self, ctx: &egui::Context) {
+        if let Some(options) = self.startup.pending.take() {
+            self.apply_startup_options(ctx, options);
+        }
+        if !self.startup.silent_install {
+            return;
+        }
+        match &self.installer_state {
+            InstallerState::Downloading | InstallerState::Installing => self.startup.install_started = true,
+            InstallerState::Idle if self.startup.install_started => {
+                info!("Silent install finished, closing");
+                ctx.send_viewport_cmd_to(egui::ViewportId::ROOT, egui::ViewportCommand::Close);
+                self.startup.silent_install = false;
+            }
+            InstallerState::Error(error) => {
+                // Left open so whoever ran it can see what went wrong
+                warn!("Silent install failed: {}", error);
+                ctx.send_viewport_cmd_to(egui::ViewportId::ROOT, egui::ViewportCommand::Minimized(false));
+                self.startup.silent_install = false;
+            }
+            InstallerState::Idle => {}
+        }
+    }
+
+    fn apply_startup_options(&mut self, ctx: &egui::Context, options: StartupOptions) {
+        if let Some(name) = &options.tab {
+            match parse_tab(name) {
+                Some(tab) => self.current_tab = tab,
+                None => warn!("Unknown tab {:?} on the command line", name),
+            }
+        }
+        if let Some(name) = &options.preset {
+            if self.apply_preset(name) {
+                self.current_tab = Tab::Tools;
+                self.tool_page = ToolPage::Installer;
+            }
+        }
+
+        let mut minimized = options.minimized || options.silent;
+        if !options.install.is_empty() {
+            let mut apps = Vec::new();
+            for wanted in &options.install {
+                let found = self.ninite_apps.iter().find(|app| {
+                    app.name.eq_ignore_ascii_case(wanted) || (!app.ninite_id.is_empty() && app.ninite_id.eq_ignore_ascii_case(wanted))
+                });
+                match found {
+                    Some(app) if !apps.contains(&app.name) => apps.push(app.name.clone()),
+                    Some(_) => {}
+                    None => warn!("No app named {:?} to install", wanted),
+                }
+            }
+            if apps.is_empty() {
+                warn!("None of the apps on the command line are in the catalog, nothing to install");
+            } else {
+                info!("Installing from the command line: {:?}", apps);
+                self.selected_apps = apps;
+                self.current_tab = Tab::Tools;
+                self.tool_page = ToolPage::Installer;
+                // The action lock still applies; its prompt needs the window
+                self.request_sensitive_action(SensitiveAction::InstallApps);
+                if self.action_guard.is_pending() {
+                    minimized = false;
+                }
+                self.startup.silent_install = options.silent;
+            }
+        }
+
+        if minimized {
+            ctx.send_viewport_cmd_to(egui::ViewportId::ROOT, egui::ViewportCommand::Minimized(true));
+        }
+    }
+}
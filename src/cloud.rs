@@ -0,0 +1,372 @@
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+use eframe::egui;
+use egui::RichText;
+use reqwest::Client;
+use serde::{Serialize, Deserialize};
+use sysinfo::{ProcessExt, System, SystemExt};
+use windows::core::PCWSTR;
+use windows::Win32::Storage::FileSystem::{GetLogicalDrives, GetVolumeInformationW};
+use winreg::enums::HKEY_CURRENT_USER;
+use winreg::RegKey;
+use log::{debug, warn};
+use crate::{credentials, DevDashboard};
+
+/// How often clients and quotas are re-checked
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+/// Warn when less than this fraction of the quota is left
+const LOW_SPACE_FRACTION: f64 = 0.1;
+
+/// Persisted cloud storage configuration
+/// Tokens are optional and only used to read quotas from the provider APIs.
+/// They live in Credential Manager; settings.json from older versions may still hold them.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct CloudSettings {
+    pub enabled: bool,
+    #[serde(skip_serializing)]
+    pub onedrive_token: String,     // Microsoft Graph access token (Files.Read)
+    #[serde(skip_serializing)]
+    pub dropbox_token: String,      // Dropbox access token (account_info.read)
+}
+
+impl CloudSettings {
+    /// Reads the tokens from Credential Manager; returns true when tokens from settings.json were moved there
+    pub fn load_tokens(&mut self) -> bool {
+        credentials::migrate("onedrive_token", &mut self.onedrive_token)
+            | credentials::migrate("dropbox_token", &mut self.dropbox_token)
+    }
+
+    fn save_tokens(&self) {
+        credentials::write("onedrive_token", self.onedrive_token.trim());
+        credentials::write("dropbox_token", self.dropbox_token.trim());
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum CloudProvider {
+    OneDrive,
+    Dropbox,
+    GoogleDrive,
+}
+
+impl CloudProvider {
+    fn label(&self) -> &'static str {
+        match self {
+            CloudProvider::OneDrive => "OneDrive",
+            CloudProvider::Dropbox => "Dropbox",
+            CloudProvider::GoogleDrive => "Google Drive",
+        }
+    }
+
+    /// Sync client executable, lowercase
+    fn client_process(&self) -> &'static str {
+        match self {
+            CloudProvider::OneDrive => "onedrive.exe",
+            CloudProvider::Dropbox => "dropbox.exe",
+            CloudProvider::GoogleDrive => "googledrivefs.exe",
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Quota {
+    pub used: u64,
+    pub total: u64,
+}
+
+/// A signed-in account found on this machine
+#[derive(Clone)]
+pub struct CloudAccount {
+    pub provider: CloudProvider,
+    pub name: String,                           // Email or account type
+    pub folder: PathBuf,                        // Local sync folder or mount point
+    pub client_running: bool,
+    pub quota: Option<Result<Quota, String>>,   // None when no source for the quota is configured
+}
+
+#[derive(Default)]
+pub struct CloudStorage {
+    accounts: Vec<CloudAccount>,
+    last_refresh: Option<Instant>,
+    receiver: Option<Receiver<Vec<CloudAccount>>>,
+}
+
+/// OneDrive accounts registered by the sync client
+fn onedrive_accounts() -> Vec<CloudAccount> {
+    let Ok(accounts) = RegKey::predef(HKEY_CURRENT_USER).open_subkey(r"Software\Microsoft\OneDrive\Accounts") else {
+        return Vec::new();
+    };
+    accounts
+        .enum_keys()
+        .flatten()
+        .filter_map(|key| {
+            let account = accounts.open_subkey(&key).ok()?;
+            let folder: String = account.get_value("UserFolder").ok()?;
+            let email: String = account.get_value("UserEmail").unwrap_or(key);
+            Some(CloudAccount {
+                provider: CloudProvider::OneDrive,
+                name: email,
+                folder: PathBuf::from(folder),
+                client_running: false,
+                quota: None,
+            })
+        })
+        .collect()
+}
+
+/// Dropbox accounts listed in the client's info.json
+fn dropbox_accounts() -> Vec<CloudAccount> {
+    let info = ["LOCALAPPDATA", "APPDATA"]
+        .iter()
+        .filter_map(std::env::var_os)
+        .map(|dir| Path::new(&dir).join(r"Dropbox\info.json"))
+        .find_map(|path| std::fs::read_to_string(path).ok());
+    let Some(info) = info else { return Vec::new() };
+    let Ok(serde_json::Value::Object(info)) = serde_json::from_str(&info) else { return Vec::new() };
+
+    info.iter()
+        .filter_map(|(kind, account)| {
+            Some(CloudAccount {
+                provider: CloudProvider::Dropbox,
+                name: kind.clone(),
+                folder: PathBuf::from(account["path"].as_str()?),
+                client_running: false,
+                quota: None,
+            })
+        })
+        .collect()
+}
+
+/// Google Drive for desktop mounts a virtual drive labelled "Google Drive" whose size is the account quota
+fn google_drive_accounts() -> Vec<CloudAccount> {
+    let drives = unsafe { GetLogicalDrives() };
+    (0..26u8)
+        .filter(|index| drives & (1 << index) != 0)
+        .filter_map(|index| {
+            let root = format!("{}:\\", (b'A' + index) as char);
+            let wide_root: Vec<u16> = root.encode_utf16().chain(std::iter::once(0)).collect();
+            let mut label = [0u16; 64];
+            let ok = unsafe {
+                GetVolumeInformationW(PCWSTR(wide_root.as_ptr()), Some(&mut label), None, None, None, None)
+            };
+            let len = label.iter().position(|c| *c == 0).unwrap_or(label.len());
+            if !ok.as_bool() || String::from_utf16_lossy(&label[..len]) != "Google Drive" {
+                return None;
+            }
+            let quota = DevDashboard::get_disk_space(&root)
+                .map(|(total, free)| Quota { used: total.saturating_sub(free), total })
+                .ok_or_else(|| "Could not read drive size".to_string());
+            Some(CloudAccount {
+                provider: CloudProvider::GoogleDrive,
+                name: root.clone(),
+                folder: PathBuf::from(root),
+                client_running: false,
+                quota: Some(quota),
+            })
+        })
+        .collect()
+}
+
+/// Finds accounts for every supported client and marks which clients are running
+fn detect_accounts() -> Vec<CloudAccount> {
+    let mut accounts = onedrive_accounts();
+    accounts.extend(dropbox_accounts());
+    accounts.extend(google_drive_accounts());
+
+    let mut sys = System::new();
+    sys.refresh_processes();
+    let running: Vec<String> = sys.processes().values().map(|process| process.name().to_lowercase()).collect();
+    for account in &mut accounts {
+        account.client_running = running.iter().any(|name| name == account.provider.client_process());
+    }
+    accounts
+}
+
+async fn onedrive_quota(client: &Client, token: &str) -> Result<Quota, String> {
+    let json: serde_json::Value = client.get("https://graph.microsoft.com/v1.0/me/drive")
+        .bearer_auth(token)
+        .send().await.map_err(|e| e.to_string())?
+        .error_for_status().map_err(|e| e.to_string())?
+        .json().await.map_err(|e| e.to_string())?;
+    let quota = &json["quota"];
+    Ok(Quota {
+        used: quota["used"].as_u64().ok_or("Missing quota in response")?,
+        total: quota["total"].as_u64().ok_or("Missing quota in response")?,
+    })
+}
+
+async fn dropbox_quota(client: &Client, token: &str) -> Result<Quota, String> {
+    let json: serde_json::Value = client.post("https://api.dropboxapi.com/2/users/get_space_usage")
+        .bearer_auth(token)
+        .send().await.map_err(|e| e.to_string())?
+        .error_for_status().map_err(|e| e.to_string())?
+        .json().await.map_err(|e| e.to_string())?;
+    Ok(Quota {
+        used: json["used"].as_u64().ok_or("Missing usage in response")?,
+        total: json["allocation"]["allocated"].as_u64().ok_or("Missing allocation in response")?,
+    })
+}
+
+impl DevDashboard {
+    /// Re-detects sync clients and quotas on an interval
+    pub(crate) fn update_cloud_storage(&mut self) {
+        if let Some(receiver) = &self.cloud_storage.receiver {
+            if let Ok(accounts) = receiver.try_recv() {
+                debug!("Found {} cloud storage accounts", accounts.len());
+                self.cloud_storage.accounts = accounts;
+                self.cloud_storage.receiver = None;
+            }
+        }
+
+        let settings = &self.settings.cloud;
+        if !settings.enabled || self.cloud_storage.receiver.is_some() {
+            return;
+        }
+        if matches!(self.cloud_storage.last_refresh, Some(last) if last.elapsed() < REFRESH_INTERVAL) {
+            return;
+        }
+
+        // A token belongs to one account, so it is only used for the first account of its provider
+        let token = |value: &str| Some(value.trim().to_string()).filter(|token| !token.is_empty());
+        let mut onedrive_token = token(&settings.onedrive_token);
+        let mut dropbox_token = token(&settings.dropbox_token);
+        let (sender, receiver) = channel();
+        self.cloud_storage.receiver = Some(receiver);
+        self.cloud_storage.last_refresh = Some(Instant::now());
+
+        self.runtime().spawn(async move {
+            let mut accounts = tokio::task::spawn_blocking(detect_accounts).await.unwrap_or_default();
+            let client = Client::new();
+            for account in &mut accounts {
+                let quota = match (account.provider, &mut onedrive_token, &mut dropbox_token) {
+                    (CloudProvider::OneDrive, token @ Some(_), _) => onedrive_quota(&client, &token.take().unwrap_or_default()).await,
+                    (CloudProvider::Dropbox, _, token @ Some(_)) => dropbox_quota(&client, &token.take().unwrap_or_default()).await,
+                    _ => continue,
+                };
+                if let Err(e) = &quota {
+                    warn!("Failed to read {} quota: {}", account.provider.label(), e);
+                }
+                account.quota = Some(quota);
+            }
+            sender.send(accounts).ok();
+        });
+    }
+
+    /// Displays the cloud storage card
+    /// Shows each account's sync client status and remaining quota
+    pub(crate) fn show_cloud_card(&self, ui: &mut egui::Ui) {
+        self.show_card(ui, "Cloud Storage", |ui| {
+            if self.cloud_storage.accounts.is_empty() {
+                if self.cloud_storage.receiver.is_some() {
+                    ui.spinner();
+                } else {
+                    ui.label("No OneDrive, Dropbox or Google Drive clients found");
+                }
+                return;
+            }
+
+            for account in &self.cloud_storage.accounts {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(account.provider.label()).strong());
+                    ui.label(&account.name).on_hover_text(account.folder.display().to_string());
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if !account.folder.exists() {
+                            ui.colored_label(egui::Color32::from_rgb(220, 50, 50), "Folder missing");
+                        } else if account.client_running {
+                            ui.colored_label(egui::Color32::from_rgb(22, 163, 74), "Syncing")
+                                .on_hover_text("The sync client is running; whether it is up to date or paused is not checked");
+                        } else {
+                            ui.colored_label(egui::Color32::from_rgb(202, 138, 4), "Client not running")
+                                .on_hover_text("Files will not sync until the client is started");
+                        }
+                    });
+                });
+
+                match &account.quota {
+                    Some(Ok(quota)) if quota.total > 0 => {
+                        let fraction = quota.used as f64 / quota.total as f64;
+                        let free = quota.total.saturating_sub(quota.used);
+                        let (free_value, free_unit) = DevDashboard::format_bytes(free);
+                        let (total_value, total_unit) = DevDashboard::format_bytes(quota.total);
+                        let color = if 1.0 - fraction < LOW_SPACE_FRACTION {
+                            egui::Color32::from_rgb(220, 50, 50)
+                        } else {
+                            egui::Color32::from_rgb(88, 165, 237)
+                        };
+                        ui.add(egui::ProgressBar::new(fraction as f32)
+                            .fill(color)
+                            .text(format!("{:.1} {} free of {:.1} {}", free_value, free_unit, total_value, total_unit)));
+                    }
+                    Some(Err(error)) => {
+                        ui.colored_label(egui::Color32::from_rgb(220, 50, 50), "Quota unavailable")
+                            .on_hover_text(error);
+                    }
+                    _ => {}
+                }
+                ui.add_space(4.0);
+            }
+        });
+    }
+
+    /// Sync client state and quota per account, for copying the card
+    pub(crate) fn cloud_card_values(&self) -> Vec<(String, String)> {
+        self.cloud_storage.accounts
+            .iter()
+            .map(|account| {
+                let mut value = if !account.folder.exists() {
+                    "Folder missing".to_string()
+                } else if account.client_running {
+                    "Syncing".to_string()
+                } else {
+                    "Client not running".to_string()
+                };
+                match &account.quota {
+                    Some(Ok(quota)) if quota.total > 0 => {
+                        let (free_value, free_unit) = DevDashboard::format_bytes(quota.total.saturating_sub(quota.used));
+                        let (total_value, total_unit) = DevDashboard::format_bytes(quota.total);
+                        value.push_str(&format!(", {:.1} {} free of {:.1} {}", free_value, free_unit, total_value, total_unit));
+                    }
+                    Some(Err(error)) => value.push_str(&format!(", quota unavailable: {}", error)),
+                    _ => {}
+                }
+                (format!("{} {}", account.provider.label(), account.name), value)
+            })
+            .collect()
+    }
+
+    /// Settings section for the cloud storage card
+    pub(crate) fn show_cloud_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+
+        changed |= ui.checkbox(&mut self.settings.cloud.enabled, "Show cloud storage card").changed();
+
+        if self.settings.cloud.enabled {
+            ui.label("Optional access tokens for quota (Google Drive quota is read from its drive):");
+            ui.horizontal(|ui| {
+                ui.label("OneDrive:");
+                changed |= ui.add(egui::TextEdit::singleline(&mut self.settings.cloud.onedrive_token)
+                    .password(true)
+                    .hint_text("Microsoft Graph token")).lost_focus();
+            });
+            ui.horizontal(|ui| {
+                ui.label("Dropbox:");
+                changed |= ui.add(egui::TextEdit::singleline(&mut self.settings.cloud.dropbox_token)
+                    .password(true)
+                    .hint_text("Dropbox API token")).lost_focus();
+            });
+            ui.label(RichText::new("Tokens are kept in Windows Credential Manager. They are not refreshed: \
+                Microsoft Graph tokens expire after about an hour and short-lived Dropbox tokens after four, \
+                after which the quota shows as unavailable until a new token is pasted.")
+                .small()
+                .color(egui::Color32::from_rgb(107, 114, 128)));
+        }
+
+        if changed {
+            self.settings.cloud.save_tokens();
+            self.cloud_storage.last_refresh = None;
+            self.save_settings();
+        }
+    }
+}
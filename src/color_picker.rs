@@ -0,0 +1,196 @@
+use eframe::egui;
+use egui::{Color32, RichText};
+use windows::Win32::Foundation::POINT;
+use windows::Win32::UI::Input::KeyboardAndMouse::{GetAsyncKeyState, VK_ESCAPE, VK_LBUTTON};
+use windows::Win32::UI::WindowsAndMessaging::GetCursorPos;
+use crate::screenshot::capture_rect;
+use crate::DevDashboard;
+
+/// Pixels on each side of the cursor shown in the magnifier
+const MAGNIFIER_RADIUS: i32 = 5;
+const MAGNIFIER_CELL: f32 = 10.0;
+const MAX_RECENT: usize = 12;
+
+/// Pixels around the cursor, row-major, with the cursor pixel in the middle
+struct Sample {
+    cursor: (i32, i32),
+    pixels: Vec<Color32>,
+}
+
+impl Sample {
+    fn center(&self) -> Color32 {
+        self.pixels[self.pixels.len() / 2]
+    }
+}
+
+/// State of the Color Picker tool
+#[derive(Default)]
+pub struct ColorPicker {
+    picking: bool,
+    button_was_down: bool,      // The click on "Pick" itself must not pick a color
+    sample: Option<Sample>,
+    picked: Option<Color32>,
+    recent: Vec<Color32>,
+}
+
+fn key_down(key: i32) -> bool {
+    unsafe { GetAsyncKeyState(key) as u16 & 0x8000 != 0 }
+}
+
+fn sample_at_cursor() -> Option<Sample> {
+    let mut point = POINT::default();
+    unsafe {
+        if !GetCursorPos(&mut point).as_bool() {
+            return None;
+        }
+    }
+    let size = MAGNIFIER_RADIUS * 2 + 1;
+    let capture = capture_rect(point.x - MAGNIFIER_RADIUS, point.y - MAGNIFIER_RADIUS, size, size).ok()?;
+    let pixels = capture.pixels
+        .chunks_exact(4)
+        .map(|pixel| Color32::from_rgb(pixel[0], pixel[1], pixel[2]))
+        .collect();
+    Some(Sample { cursor: (point.x, point.y), pixels })
+}
+
+fn hex(color: Color32) -> String {
+    format!("#{:02X}{:02X}{:02X}", color.r(), color.g(), color.b())
+}
+
+fn rgb(color: Color32) -> String {
+    format!("rgb({}, {}, {})", color.r(), color.g(), color.b())
+}
+
+fn hsl(color: Color32) -> String {
+    let [r, g, b] = [color.r(), color.g(), color.b()].map(|channel| channel as f32 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let lightness = (max + min) / 2.0;
+    let delta = max - min;
+
+    let (hue, saturation) = if delta == 0.0 {
+        (0.0, 0.0)
+    } else {
+        let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+        let hue = if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        (hue, saturation)
+    };
+    format!("hsl({:.0}, {:.0}%, {:.0}%)", hue, saturation * 100.0, lightness * 100.0)
+}
+
+impl ColorPicker {
+    /// Follows the cursor while picking; a left click anywhere picks, Escape cancels
+    fn poll(&mut self) {
+        if !self.picking {
+            return;
+        }
+        if key_down(VK_ESCAPE.0 as i32) {
+            self.picking = false;
+            self.sample = None;
+            return;
+        }
+
+        self.sample = sample_at_cursor();
+        let button_down = key_down(VK_LBUTTON.0 as i32);
+        if button_down && !self.button_was_down {
+            if let Some(color) = self.sample.as_ref().map(Sample::center) {
+                self.picked = Some(color);
+                self.recent.retain(|recent| *recent != color);
+                self.recent.insert(0, color);
+                self.recent.truncate(MAX_RECENT);
+            }
+            self.picking = false;
+        }
+        self.button_was_down = button_down;
+    }
+}
+
+impl DevDashboard {
+    /// Color picker tool page
+    /// Samples any pixel on screen and shows it as hex, RGB and HSL
+    pub(crate) fn show_color_picker_tool(&mut self, ui: &mut egui::Ui) {
+        let picker = &mut self.color_picker;
+        picker.poll();
+        if picker.picking {
+            ui.ctx().request_repaint();
+        }
+
+        ui.heading("Color Picker");
+        ui.add_space(8.0);
+
+        ui.horizontal(|ui| {
+            if picker.picking {
+                ui.label(RichText::new("Click anywhere on screen to pick, Esc to cancel")
+                    .color(Color32::from_rgb(202, 138, 4)));
+            } else if ui.button("🎨 Pick color").clicked() {
+                picker.picking = true;
+                picker.button_was_down = true;
+            }
+        });
+        ui.add_space(8.0);
+
+        ui.horizontal(|ui| {
+            if let Some(sample) = picker.sample.as_ref().filter(|_| picker.picking) {
+                let size = MAGNIFIER_RADIUS * 2 + 1;
+                let (rect, _) = ui.allocate_exact_size(egui::vec2(size as f32, size as f32) * MAGNIFIER_CELL, egui::Sense::hover());
+                let painter = ui.painter();
+                for (index, color) in sample.pixels.iter().enumerate() {
+                    let (x, y) = ((index as i32 % size) as f32, (index as i32 / size) as f32);
+                    let cell = egui::Rect::from_min_size(
+                        rect.min + egui::vec2(x, y) * MAGNIFIER_CELL,
+                        egui::vec2(MAGNIFIER_CELL, MAGNIFIER_CELL),
+                    );
+                    painter.rect_filled(cell, 0.0, *color);
+                }
+                let center = egui::Rect::from_min_size(
+                    rect.min + egui::vec2(MAGNIFIER_RADIUS as f32, MAGNIFIER_RADIUS as f32) * MAGNIFIER_CELL,
+                    egui::vec2(MAGNIFIER_CELL, MAGNIFIER_CELL),
+                );
+                painter.rect_stroke(center, 0.0, egui::Stroke::new(1.0, Color32::WHITE));
+                ui.vertical(|ui| {
+                    ui.label(hex(sample.center()));
+                    ui.label(RichText::new(format!("{}, {}", sample.cursor.0, sample.cursor.1))
+                        .color(Color32::from_rgb(156, 163, 175)));
+                });
+            } else if let Some(color) = picker.picked {
+                let (rect, _) = ui.allocate_exact_size(egui::vec2(64.0, 64.0), egui::Sense::hover());
+                ui.painter().rect_filled(rect, 6.0, color);
+                ui.vertical(|ui| {
+                    for value in [hex(color), rgb(color), hsl(color)] {
+                        ui.horizontal(|ui| {
+                            ui.monospace(&value);
+                            if ui.small_button("📋").on_hover_text("Copy").clicked() {
+                                ui.output_mut(|output| output.copied_text = value.clone());
+                            }
+                        });
+                    }
+                });
+            } else {
+                ui.label(RichText::new("No color picked yet").color(Color32::from_rgb(156, 163, 175)));
+            }
+        });
+
+        if !picker.recent.is_empty() {
+            ui.add_space(12.0);
+            ui.label(RichText::new("Recent").strong());
+            ui.horizontal_wrapped(|ui| {
+                for color in picker.recent.clone() {
+                    let (rect, response) = ui.allocate_exact_size(egui::vec2(24.0, 24.0), egui::Sense::click());
+                    ui.painter().rect_filled(rect, 4.0, color);
+                    if picker.picked == Some(color) {
+                        ui.painter().rect_stroke(rect, 4.0, egui::Stroke::new(2.0, Color32::WHITE));
+                    }
+                    if response.on_hover_text(hex(color)).clicked() {
+                        picker.picked = Some(color);
+                    }
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,407 @@
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use tokio::process::{Child, Command as TokioCommand};
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{CloseHandle, HANDLE};
+use windows::Win32::System::JobObjects::{
+    AssignProcessToJobObject, CreateJobObjectW, JobObjectExtendedLimitInformation, SetInformationJobObject,
+    JOBOBJECT_EXTENDED_LIMIT_INFORMATION, JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE,
+};
+use log::warn;
+use crate::plugins::{self, CardContent, CardRow};
+use crate::{DevDashboard, CREATE_NO_WINDOW};
+
+/// Output lines (or table rows) shown per card
+const MAX_LINES: usize = 50;
+/// JSON arrays with more keys than this are cut to their first columns
+const MAX_COLUMNS: usize = 8;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    Plain,
+    Table,      // Header line followed by whitespace-aligned columns
+    Json,
+}
+
+impl OutputFormat {
+    fn label(&self) -> &'static str {
+        match self {
+            OutputFormat::Plain => "Plain text",
+            OutputFormat::Table => "Table",
+            OutputFormat::Json => "JSON",
+        }
+    }
+}
+
+/// A user-defined card showing the output of a shell command
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct CommandCardConfig {
+    pub title: String,
+    pub command: String,            // Run through cmd /C
+    pub format: OutputFormat,
+    pub interval_secs: u64,
+    pub timeout_secs: u64,
+}
+
+impl Default for CommandCardConfig {
+    fn default() -> Self {
+        Self {
+            title: "Command".to_string(),
+            command: String::new(),
+            format: OutputFormat::Plain,
+            interval_secs: 60,
+            timeout_secs: 10,
+        }
+    }
+}
+
+/// Persisted command cards
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct CommandCardSettings {
+    pub cards: Vec<CommandCardConfig>,
+}
+
+enum CommandOutput {
+    Text(String),
+    Table { headers: Vec<String>, rows: Vec<Vec<String>> },
+    Pairs(CardContent),
+}
+
+#[derive(Default)]
+struct CommandCardState {
+    last_run: Option<Instant>,
+    receiver: Option<Receiver<Result<String, String>>>,
+    output: Option<CommandOutput>,  // Last successful output, kept while later runs fail
+    error: Option<String>,
+}
+
+/// Output of each configured command card, in settings order
+#[derive(Default)]
+pub struct CommandCards {
+    states: Vec<CommandCardState>,
+}
+
+/// Job object that ends every process in it when dropped, so a command that times out
+/// doesn't leave the programs it started running
+struct ProcessTree(HANDLE);
+
+impl ProcessTree {
+    fn new() -> Result<Self, String> {
+        let job = unsafe { CreateJobObjectW(None, PCWSTR::null()) }.map_err(|e| format!("Could not create a job object: {}", e))?;
+        let tree = Self(job);
+        let mut limits = JOBOBJECT_EXTENDED_LIMIT_INFORMATION::default();
+        limits.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+        let set = unsafe {
+            SetInformationJobObject(tree.0, JobObjectExtendedLimitInformation,
+                &limits as *const _ as *const std::ffi::c_void,
+                std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32)
+        };
+        if !set.as_bool() {
+            return Err(format!("Could not set up a job object: {}", std::io::Error::last_os_error()));
+        }
+        Ok(tree)
+    }
+
+    fn add(&self, child: &Child) -> Result<(), String> {
+        let added = child.raw_handle()
+            .is_some_and(|handle| unsafe { AssignProcessToJobObject(self.0, HANDLE(handle as isize)) }.as_bool());
+        if !added {
+            return Err(format!("Could not add the command to a job object: {}", std::io::Error::last_os_error()));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for ProcessTree {
+    fn drop(&mut self) {
+        unsafe { CloseHandle(self.0) };
+    }
+}
+
+async fn run_command(command: String, timeout: Duration) -> Result<String, String> {
+    let tree = ProcessTree::new()?;
+    // cmd has its own quoting rules, so the command is passed exactly as typed
+    let child = TokioCommand::new("cmd")
+        .arg("/C")
+        .raw_arg(&command)
+        .creation_flags(CREATE_NO_WINDOW)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Could not start: {}", e))?;
+    tree.add(&child)?;
+    // Dropping the tree on a timeout ends cmd and everything it started
+    let output = tokio::time::timeout(timeout, child.wait_with_output())
+        .await
+        .map_err(|_| format!("Timed out after {}s", timeout.as_secs()))?
+        .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let code = output.status.code().map(|code| code.to_string()).unwrap_or_else(|| "?".to_string());
+        return Err(match stderr.lines().find(|line| !line.trim().is_empty()) {
+            Some(line) => format!("Exit code {}: {}", code, line.trim()),
+            None => format!("Exit code {}", code),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Splits a line into at most `count` columns; aligned output uses runs of two or more spaces
+/// as separators so single spaces inside values survive
+fn split_columns(line: &str, count: usize, wide_gaps: bool) -> Vec<String> {
+    let mut columns: Vec<String> = if wide_gaps {
+        line.split("  ").map(str::trim).filter(|column| !column.is_empty()).map(str::to_string).collect()
+    } else {
+        line.split_whitespace().map(str::to_string).collect()
+    };
+    if columns.len() > count && count > 0 {
+        let rest = columns.split_off(count - 1).join(" ");
+        columns.push(rest);
+    }
+    columns.resize(count, String::new());
+    columns
+}
+
+fn parse_table(stdout: &str) -> Result<CommandOutput, String> {
+    let mut lines = stdout.lines().filter(|line| !line.trim().is_empty());
+    let header = lines.next().ok_or("No output")?;
+    let wide_gaps = header.trim().contains("  ");
+    let headers: Vec<String> = if wide_gaps {
+        header.split("  ").map(str::trim).filter(|column| !column.is_empty()).map(str::to_string).collect()
+    } else {
+        header.split_whitespace().map(str::to_string).collect()
+    };
+    let rows = lines.take(MAX_LINES).map(|line| split_columns(line, headers.len(), wide_gaps)).collect();
+    Ok(CommandOutput::Table { headers, rows })
+}
+
+fn json_to_string(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(text) => text.clone(),
+        serde_json::Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Arrays of objects become tables, objects become label/value rows
+fn parse_json(stdout: &str) -> Result<CommandOutput, String> {
+    let value: serde_json::Value = serde_json::from_str(stdout).map_err(|e| format!("Invalid JSON: {}", e))?;
+    match &value {
+        serde_json::Value::Array(items) if items.iter().all(|item| item.is_object()) && !items.is_empty() => {
+            let mut headers: Vec<String> = Vec::new();
+            for item in items {
+                for key in item.as_object().into_iter().flat_map(|object| object.keys()) {
+                    if !headers.contains(key) && headers.len() < MAX_COLUMNS {
+                        headers.push(key.clone());
+                    }
+                }
+            }
+            let rows = items
+                .iter()
+                .take(MAX_LINES)
+                .map(|item| headers.iter().map(|key| item.get(key).map(json_to_string).unwrap_or_default()).collect())
+                .collect();
+            Ok(CommandOutput::Table { headers, rows })
+        }
+        serde_json::Value::Object(object) => Ok(CommandOutput::Pairs(CardContent {
+            rows: object
+                .iter()
+                .take(MAX_LINES)
+                .map(|(key, value)| CardRow { label: key.clone(), value: json_to_string(value), ..Default::default() })
+                .collect(),
+            error: None,
+        })),
+        other => Ok(CommandOutput::Text(serde_json::to_string_pretty(other).unwrap_or_default())),
+    }
+}
+
+fn parse_output(format: OutputFormat, stdout: &str) -> Result<CommandOutput, String> {
+    match format {
+        OutputFormat::Plain => Ok(CommandOutput::Text(stdout.lines().take(MAX_LINES).collect::<Vec<_>>().join("\n"))),
+        OutputFormat::Table => parse_table(stdout),
+        OutputFormat::Json => parse_json(stdout),
+    }
+}
+
+impl DevDashboard {
+    /// Starts commands that are due and collects finished ones
+    pub(crate) fn update_command_cards(&mut self) {
+        let count = self.settings.command_cards.cards.len();
+        self.command_cards.states.resize_with(count, CommandCardState::default);
+
+        for index in 0..count {
+            let config = self.settings.command_cards.cards[index].clone();
+            let state = &mut self.command_cards.states[index];
+            if let Some(receiver) = &state.receiver {
+                if let Ok(result) = receiver.try_recv() {
+                    match result.and_then(|stdout| parse_output(config.format, &stdout)) {
+                        Ok(output) => {
+                            state.output = Some(output);
+                            state.error = None;
+                        }
+                        Err(e) => {
+                            warn!("Command card \"{}\" failed: {}", config.title, e);
+                            state.error = Some(e);
+                        }
+                    }
+                    state.receiver = None;
+                }
+                continue;
+            }
+            let interval = Duration::from_secs(config.interval_secs.max(5));
+            if state.last_run.is_some_and(|last| last.elapsed() < interval) || config.command.trim().is_empty() {
+                continue;
+            }
+
+            let (sender, receiver) = channel();
+            state.receiver = Some(receiver);
+            state.last_run = Some(Instant::now());
+            let timeout = Duration::from_secs(config.timeout_secs.max(1));
+            self.runtime().spawn(async move {
+                sender.send(run_command(config.command, timeout).await).ok();
+            });
+        }
+    }
+
+    /// Displays one command output card
+    pub(crate) fn show_command_card(&self, ui: &mut egui::Ui, index: usize) {
+        let Some(config) = self.settings.command_cards.cards.get(index) else { return };
+        let Some(state) = self.command_cards.states.get(index) else { return };
+        self.show_card(ui, &config.title, |ui| {
+            if let Some(error) = &state.error {
+                ui.colored_label(Color32::from_rgb(220, 50, 50), format!("⚠ {}", error));
+            }
+            match &state.output {
+                Some(CommandOutput::Text(text)) => {
+                    egui::ScrollArea::both().id_source(("command_card", index)).max_height(220.0).show(ui, |ui| {
+                        ui.label(RichText::new(text).monospace());
+                    });
+                }
+                Some(CommandOutput::Table { headers, rows }) => {
+                    egui::ScrollArea::both().id_source(("command_card", index)).max_height(220.0).show(ui, |ui| {
+                        egui::Grid::new(("command_table", index)).striped(true).show(ui, |ui| {
+                            for header in headers {
+                                ui.label(RichText::new(header).strong());
+                            }
+                            ui.end_row();
+                            for row in rows {
+                                for cell in row {
+                                    ui.label(RichText::new(cell).monospace());
+                                }
+                                ui.end_row();
+                            }
+                        });
+                    });
+                }
+                Some(CommandOutput::Pairs(content)) => plugins::show_card_rows(ui, content),
+                None if state.error.is_none() => {
+                    ui.spinner();
+                }
+                None => {}
+            }
+        });
+    }
+
+    /// Last output of one command card, for copying it; table rows are keyed by their first column
+    pub(crate) fn command_card_values(&self, index: usize) -> Vec<(String, String)> {
+        let Some(state) = self.command_cards.states.get(index) else { return Vec::new() };
+        let mut values = match &state.output {
+            Some(CommandOutput::Text(text)) => vec![("Output".to_string(), text.trim_end().to_string())],
+            Some(CommandOutput::Table { headers, rows }) => rows
+                .iter()
+                .map(|row| {
+                    let rest: Vec<String> = headers.iter().zip(row).skip(1)
+                        .map(|(header, cell)| format!("{}: {}", header, cell))
+                        .collect();
+                    (row.first().cloned().unwrap_or_default(), rest.join(", "))
+                })
+                .collect(),
+            Some(CommandOutput::Pairs(content)) => content.values(),
+            None => Vec::new(),
+        };
+        if let Some(error) = &state.error {
+            values.push(("Error".to_string(), error.clone()));
+        }
+        values
+    }
+
+    /// Settings section for command output cards
+    pub(crate) fn show_command_card_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        let mut removed = None;
+
+        ui.label("Command Cards:");
+        for (index, config) in self.settings.command_cards.cards.iter_mut().enumerate() {
+            let mut rerun = false;
+            egui::CollapsingHeader::new(config.title.clone())
+                .id_source(("command_card_settings", index))
+                .show(ui, |ui| {
+                    egui::Grid::new(("command_card_grid", index)).num_columns(2).show(ui, |ui| {
+                        ui.label("Title:");
+                        changed |= ui.text_edit_singleline(&mut config.title).changed();
+                        ui.end_row();
+                        ui.label("Command:");
+                        rerun |= ui.add(egui::TextEdit::singleline(&mut config.command)
+                            .hint_text("kubectl get nodes")
+                            .font(egui::TextStyle::Monospace)).lost_focus();
+                        ui.end_row();
+                        ui.label("Output:");
+                        egui::ComboBox::from_id_source(("command_card_format", index))
+                            .selected_text(config.format.label())
+                            .show_ui(ui, |ui| {
+                                for format in [OutputFormat::Plain, OutputFormat::Table, OutputFormat::Json] {
+                                    rerun |= ui.selectable_value(&mut config.format, format, format.label()).changed();
+                                }
+                            });
+                        ui.end_row();
+                        ui.label("Refresh (s):");
+                        changed |= ui.add(egui::DragValue::new(&mut config.interval_secs).clamp_range(5..=3600)).changed();
+                        ui.end_row();
+                        ui.label("Timeout (s):");
+                        changed |= ui.add(egui::DragValue::new(&mut config.timeout_secs).clamp_range(1..=300)).changed();
+                        ui.end_row();
+                    });
+                    ui.horizontal(|ui| {
+                        rerun |= ui.button("⟳ Run now").clicked();
+                        if ui.button("✖ Remove").clicked() {
+                            removed = Some(index);
+                        }
+                    });
+                });
+            if rerun {
+                if let Some(state) = self.command_cards.states.get_mut(index) {
+                    state.last_run = None;
+                }
+                changed = true;
+            }
+        }
+        if let Some(index) = removed {
+            self.settings.command_cards.cards.remove(index);
+            if index < self.command_cards.states.len() {
+                self.command_cards.states.remove(index);
+            }
+            changed = true;
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("Add command card").clicked() {
+                self.settings.command_cards.cards.push(CommandCardConfig::default());
+                changed = true;
+            }
+            ui.label(RichText::new("Commands run through cmd.exe with your permissions")
+                .color(Color32::from_rgb(107, 114, 128)));
+        });
+
+        if changed {
+            self.save_settings();
+        }
+    }
+}
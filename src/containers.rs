@@ -0,0 +1,778 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::windows::named_pipe::ClientOptions;
+use tokio::process::Command as TokioCommand;
+use log::{info, warn};
+use crate::guard::{SensitiveAction, ServiceControl};
+use crate::{DevDashboard, CREATE_NO_WINDOW};
+
+/// Engine pipes tried in order when none is set: Docker Desktop, then a default Podman machine
+const ENGINE_PIPES: [&str; 2] = [r"\\.\pipe\docker_engine", r"\\.\pipe\podman-machine-default"];
+
+/// How often the list is refreshed while the Containers tab is visible
+const REFRESH_INTERVAL: Duration = Duration::from_secs(3);
+
+/// The pipe answers ERROR_PIPE_BUSY while another client is connecting
+const ERROR_PIPE_BUSY: i32 = 231;
+
+/// `up` may pull images first, so it gets plenty of time
+const COMPOSE_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Labels compose puts on the containers it creates
+const PROJECT_LABEL: &str = "com.docker.compose.project";
+const WORKING_DIR_LABEL: &str = "com.docker.compose.project.working_dir";
+const CONFIG_FILES_LABEL: &str = "com.docker.compose.project.config_files";
+
+/// Where a compose project was started from, so it can be brought up again after `down` removed its containers
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct ComposeProject {
+    pub working_dir: String,
+    pub config_files: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ContainerSettings {
+    pub pipe: String,                // Engine named pipe; empty tries Docker and then Podman
+    pub show_stopped: bool,
+    pub projects: BTreeMap<String, ComposeProject>,  // Compose projects seen, by name
+}
+
+impl ContainerSettings {
+    fn pipes(&self) -> Vec<String> {
+        if self.pipe.trim().is_empty() {
+            ENGINE_PIPES.iter().map(|pipe| pipe.to_string()).collect()
+        } else {
+            vec![self.pipe.trim().to_string()]
+        }
+    }
+}
+
+/// A container as listed by `GET /containers/json`
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContainerSummary {
+    id: String,
+    #[serde(default)]
+    names: Vec<String>,
+    #[serde(default)]
+    image: String,
+    #[serde(default)]
+    state: String,
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    labels: Option<HashMap<String, String>>,
+}
+
+#[derive(Clone)]
+pub struct Container {
+    pub id: String,
+    pub name: String,
+    pub image: String,
+    pub state: String,                // running, exited, paused, ...
+    pub status: String,               // Human readable, e.g. "Up 2 hours"
+    pub labels: HashMap<String, String>,
+    pub cpu: Option<f32>,             // Percent of the whole machine
+    pub memory: Option<u64>,          // Bytes in use
+    pub memory_limit: Option<u64>,
+    pub network: Option<(u64, u64)>,  // Bytes received and sent since the container started
+    pub network_rate: Option<(f64, f64)>,  // Bytes per second received and sent since the last refresh
+}
+
+impl Container {
+    pub fn is_running(&self) -> bool {
+        self.state == "running"
+    }
+
+    /// Compose project the container belongs to, from the label compose sets
+    pub fn project(&self) -> Option<&str> {
+        self.labels.get(PROJECT_LABEL).map(String::as_str)
+    }
+
+    /// Where its project's compose files are, if compose recorded it
+    fn compose_project(&self) -> Option<ComposeProject> {
+        let working_dir = self.labels.get(WORKING_DIR_LABEL)?;
+        let config_files = self.labels.get(CONFIG_FILES_LABEL)
+            .map(|files| files.split(',').map(|file| file.trim().to_string()).filter(|file| !file.is_empty()).collect())
+            .unwrap_or_default();
+        Some(ComposeProject { working_dir: working_dir.clone(), config_files })
+    }
+
+    fn state_color(&self) -> Color32 {
+        match self.state.as_str() {
+            "running" => Color32::from_rgb(22, 163, 74),
+            "paused" | "restarting" => Color32::from_rgb(202, 138, 4),
+            "dead" => Color32::from_rgb(220, 50, 50),
+            _ => Color32::from_rgb(107, 114, 128),
+        }
+    }
+}
+
+/// Usage from one stats sample
+#[derive(Default)]
+struct Usage {
+    cpu: Option<f32>,
+    memory: Option<u64>,
+    memory_limit: Option<u64>,
+    network: Option<(u64, u64)>,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum ContainerAction {
+    Start,
+    Stop,
+    Restart,
+}
+
+impl ContainerAction {
+    fn path(&self) -> &'static str {
+        match self {
+            ContainerAction::Start => "start",
+            ContainerAction::Stop => "stop",
+            ContainerAction::Restart => "restart",
+        }
+    }
+
+    pub(crate) fn label(&self) -> &'static str {
+        match self {
+            ContainerAction::Start => "Start",
+            ContainerAction::Stop => "Stop",
+            ContainerAction::Restart => "Restart",
+        }
+    }
+
+    fn done(&self) -> &'static str {
+        match self {
+            ContainerAction::Start => "Started",
+            ContainerAction::Stop => "Stopped",
+            ContainerAction::Restart => "Restarted",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum ProjectAction {
+    Up,
+    Down,
+}
+
+impl ProjectAction {
+    pub(crate) fn label(&self) -> &'static str {
+        match self {
+            ProjectAction::Up => "Up",
+            ProjectAction::Down => "Down",
+        }
+    }
+}
+
+/// A start/stop/restart or compose up/down waiting for the engine
+struct PendingAction {
+    target: String,                   // Container id, or the project name for compose actions
+    description: String,              // e.g. "Restart web-1"
+    done: String,                     // e.g. "Restarted web-1"
+}
+
+/// Sends one request over the engine pipe and returns the status code and body.
+/// HTTP/1.0 makes the engine close the connection after the reply, so the body is everything after the headers.
+async fn request(pipe: &str, method: &str, path: &str) -> Result<(u16, Vec<u8>), String> {
+    let mut attempts = 0;
+    let mut client = loop {
+        match ClientOptions::new().open(pipe) {
+            Ok(client) => break client,
+            Err(e) if e.raw_os_error() == Some(ERROR_PIPE_BUSY) && attempts < 20 => {
+                attempts += 1;
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+            Err(e) => return Err(e.to_string()),
+        }
+    };
+    let head = format!("{} {} HTTP/1.0\r\nHost: docker\r\nContent-Length: 0\r\n\r\n", method, path);
+    client.write_all(head.as_bytes()).await.map_err(|e| e.to_string())?;
+    let mut response = Vec::new();
+    client.read_to_end(&mut response).await.map_err(|e| e.to_string())?;
+
+    let split = response.windows(4).position(|window| window == b"\r\n\r\n").ok_or("Incomplete reply from the engine")?;
+    let status = String::from_utf8_lossy(&response[..split])
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .ok_or("Unreadable reply from the engine")?;
+    Ok((status, response[split + 4..].to_vec()))
+}
+
+/// The engine's own explanation of a failed request, e.g. "No such container"
+fn error_message(status: u16, body: &[u8]) -> String {
+    serde_json::from_slice::<Value>(body)
+        .ok()
+        .and_then(|value| value["message"].as_str().map(str::to_string))
+        .unwrap_or_else(|| format!("The engine answered {}", status))
+}
+
+/// CPU, memory and network from a one-shot `GET /containers/{id}/stats` sample.
+/// Linux containers report system CPU time to compare against; Windows containers only timestamps.
+fn parse_stats(stats: &Value) -> Usage {
+    let cpu_total = stats["cpu_stats"]["cpu_usage"]["total_usage"].as_f64();
+    let pre_total = stats["precpu_stats"]["cpu_usage"]["total_usage"].as_f64();
+    let cpu = match (cpu_total, pre_total) {
+        (Some(total), Some(pre)) => match (stats["cpu_stats"]["system_cpu_usage"].as_f64(), stats["precpu_stats"]["system_cpu_usage"].as_f64()) {
+            (Some(system), Some(pre_system)) if system > pre_system => Some(((total - pre) / (system - pre_system) * 100.0) as f32),
+            _ => {
+                // Windows: CPU time in 100 ns units over the wall time between the two samples, across all processors
+                let read = chrono::DateTime::parse_from_rfc3339(stats["read"].as_str().unwrap_or_default()).ok();
+                let pre_read = chrono::DateTime::parse_from_rfc3339(stats["preread"].as_str().unwrap_or_default()).ok();
+                let processors = stats["num_procs"].as_f64().filter(|count| *count > 0.0);
+                match (read, pre_read, processors) {
+                    (Some(read), Some(pre_read), Some(processors)) => {
+                        let elapsed = (read - pre_read).num_microseconds().unwrap_or(0) as f64 * 10.0;
+                        (elapsed > 0.0).then(|| ((total - pre) / (elapsed * processors) * 100.0) as f32)
+                    }
+                    _ => None,
+                }
+            }
+        },
+        _ => None,
+    };
+
+    let memory_stats = &stats["memory_stats"];
+    let memory = match memory_stats["usage"].as_u64() {
+        // Page cache counts toward usage but can be reclaimed, so it is left out like `docker stats` does
+        Some(usage) => {
+            let cache = memory_stats["stats"]["inactive_file"].as_u64()
+                .or_else(|| memory_stats["stats"]["total_inactive_file"].as_u64())
+                .unwrap_or(0);
+            Some(usage.saturating_sub(cache))
+        }
+        None => memory_stats["privateworkingset"].as_u64(),
+    };
+    let memory_limit = memory_stats["limit"].as_u64().filter(|limit| *limit > 0 && *limit < u64::MAX / 2);
+
+    // One entry per attached network; containers on the host network have none
+    let network = stats["networks"].as_object().map(|networks| {
+        networks.values().fold((0, 0), |(received, sent), network| {
+            (received + network["rx_bytes"].as_u64().unwrap_or(0), sent + network["tx_bytes"].as_u64().unwrap_or(0))
+        })
+    });
+    Usage { cpu: cpu.map(|cpu| cpu.clamp(0.0, 100.0)), memory, memory_limit, network }
+}
+
+/// Lists containers on the first pipe that answers, with usage for the running ones
+async fn fetch(pipes: Vec<String>, all: bool) -> Result<(String, Vec<Container>), String> {
+    let path = if all { "/containers/json?all=1" } else { "/containers/json" };
+    let mut last_error = None;
+    for pipe in pipes {
+        let (status, body) = match request(&pipe, "GET", path).await {
+            Ok(reply) => reply,
+            Err(e) => {
+                last_error = Some(e);
+                continue;
+            }
+        };
+        if status != 200 {
+            return Err(error_message(status, &body));
+        }
+        let summaries: Vec<ContainerSummary> = serde_json::from_slice(&body).map_err(|e| format!("Unreadable container list: {}", e))?;
+
+        // One stats call per running container; each waits for a second sample, so they run side by side
+        let samples = futures::future::join_all(summaries.iter().map(|summary| {
+            let pipe = pipe.clone();
+            let running = summary.state == "running";
+            let path = format!("/containers/{}/stats?stream=false", summary.id);
+            async move {
+                if !running {
+                    return Usage::default();
+                }
+                match request(&pipe, "GET", &path).await {
+                    Ok((200, body)) => serde_json::from_slice::<Value>(&body).map(|stats| parse_stats(&stats)).unwrap_or_default(),
+                    _ => Usage::default(),
+                }
+            }
+        })).await;
+
+        let mut containers: Vec<Container> = summaries.into_iter()
+            .zip(samples)
+            .map(|(summary, usage)| Container {
+                name: summary.names.first()
+                    .map(|name| name.trim_start_matches('/').to_string())
+                    .unwrap_or_else(|| summary.id.chars().take(12).collect()),
+                id: summary.id,
+                image: summary.image,
+                state: summary.state,
+                status: summary.status,
+                labels: summary.labels.unwrap_or_default(),
+                cpu: usage.cpu,
+                memory: usage.memory,
+                memory_limit: usage.memory_limit,
+                network: usage.network,
+                network_rate: None,
+            })
+            .collect();
+        containers.sort_by(|a, b| b.is_running().cmp(&a.is_running()).then_with(|| a.name.cmp(&b.name)));
+        return Ok((pipe, containers));
+    }
+    Err(match last_error {
+        Some(e) => format!("No container engine is running ({})", e),
+        None => "No container engine is running".to_string(),
+    })
+}
+
+/// The compose CLI of the engine in use, pointed at its pipe
+fn compose_command(pipe: &str) -> TokioCommand {
+    if pipe.contains("podman") {
+        let mut command = TokioCommand::new("podman");
+        command.arg("compose");
+        command
+    } else {
+        // npipe:////./pipe/docker_engine
+        let mut command = TokioCommand::new("docker");
+        command.args(["--host", &format!("npipe://{}", pipe.replace('\\', "/")), "compose"]);
+        command
+    }
+}
+
+/// Runs `compose up -d` or `compose down` for a project; the error is the CLI's last line of output
+async fn run_compose(pipe: String, name: String, project: ComposeProject, action: ProjectAction) -> Result<(), String> {
+    let mut command = compose_command(&pipe);
+    command.args(["--project-name", &name]);
+    for file in &project.config_files {
+        command.args(["--file", file]);
+    }
+    match action {
+        ProjectAction::Up => command.args(["up", "--detach"]),
+        ProjectAction::Down => command.arg("down"),
+    };
+    if Path::new(&project.working_dir).is_dir() {
+        command.current_dir(&project.working_dir);
+    }
+    let child = command
+        .creation_flags(CREATE_NO_WINDOW)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Could not run compose: {}", e))?;
+    let output = tokio::time::timeout(COMPOSE_TIMEOUT, child.wait_with_output()).await
+        .map_err(|_| "compose did not finish in time".to_string())?
+        .map_err(|e| e.to_string())?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Err(stderr.lines().rev().find(|line| !line.trim().is_empty()).unwrap_or("compose failed").trim().to_string())
+}
+
+async fn run_action(pipe: String, id: String, action: ContainerAction) -> Result<(), String> {
+    let (status, body) = request(&pipe, "POST", &format!("/containers/{}/{}", id, action.path())).await?;
+    match status {
+        // 304 means it was already started or stopped
+        204 | 304 => Ok(()),
+        _ => Err(error_message(status, &body)),
+    }
+}
+
+/// Containers from the local Docker or Podman engine, refreshed in the background
+#[derive(Default)]
+pub struct ContainerMonitor {
+    pub containers: Vec<Container>,
+    pub engine: Option<String>,       // Pipe that answered the last refresh
+    pub error: Option<String>,
+    last_refresh: Option<Instant>,
+    sampled: Option<Instant>,         // When the current network totals were read, for the rates
+    receiver: Option<Receiver<Result<(String, Vec<Container>), String>>>,
+    pending: Option<PendingAction>,
+    action_receiver: Option<Receiver<Result<(), String>>>,
+    message: Option<(String, bool)>,  // Outcome of the last action; true for a failure
+}
+
+impl ContainerMonitor {
+    pub fn is_refreshing(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    /// Collects finished refreshes and actions; an action triggers a refresh so the new state shows.
+    /// Returns whether a refresh came in.
+    pub fn poll(&mut self) -> bool {
+        let mut refreshed = false;
+        if let Some(result) = self.receiver.as_ref().and_then(|receiver| receiver.try_recv().ok()) {
+            refreshed = true;
+            self.receiver = None;
+            self.last_refresh = Some(Instant::now());
+            match result {
+                Ok((pipe, mut containers)) => {
+                    if self.engine.as_deref() != Some(pipe.as_str()) {
+                        info!("Container engine found on {}", pipe);
+                    }
+                    let now = Instant::now();
+                    if let Some(sampled) = self.sampled {
+                        let seconds = now.duration_since(sampled).as_secs_f64().max(0.001);
+                        for container in &mut containers {
+                            let previous = self.containers.iter()
+                                .find(|previous| previous.id == container.id)
+                                .and_then(|previous| previous.network);
+                            if let (Some((received, sent)), Some((previous_received, previous_sent))) = (container.network, previous) {
+                                container.network_rate = Some((
+                                    received.saturating_sub(previous_received) as f64 / seconds,
+                                    sent.saturating_sub(previous_sent) as f64 / seconds,
+                                ));
+                            }
+                        }
+                    }
+                    self.sampled = Some(now);
+                    self.engine = Some(pipe);
+                    self.containers = containers;
+                    self.error = None;
+                }
+                Err(e) => {
+                    self.engine = None;
+                    self.containers.clear();
+                    self.sampled = None;
+                    self.error = Some(e);
+                }
+            }
+        }
+        if let Some(result) = self.action_receiver.as_ref().and_then(|receiver| receiver.try_recv().ok()) {
+            self.action_receiver = None;
+            if let Some(pending) = self.pending.take() {
+                self.message = Some(match result {
+                    Ok(()) => (pending.done, false),
+                    Err(e) => {
+                        warn!("{} failed: {}", pending.description, e);
+                        (format!("{} failed: {}", pending.description, e), true)
+                    }
+                });
+            }
+            self.last_refresh = None;
+        }
+        refreshed
+    }
+
+    fn needs_refresh(&self) -> bool {
+        self.receiver.is_none() && match self.last_refresh {
+            Some(last) => last.elapsed() >= REFRESH_INTERVAL,
+            None => true,
+        }
+    }
+}
+
+/// A button pressed in the Containers tab, acted on once the list is drawn
+enum Request {
+    Container(String, ContainerAction),  // Container id
+    Project(String, ProjectAction),      // Project name
+    Forget(String),
+}
+
+/// "↓ 1.2 MB/s ↑ 30.0 KB/s"
+fn rate_text(rate: Option<(f64, f64)>) -> Option<String> {
+    let (received, sent) = rate?;
+    let (received, received_unit) = DevDashboard::format_bytes(received as u64);
+    let (sent, sent_unit) = DevDashboard::format_bytes(sent as u64);
+    Some(format!("↓ {:.1} {}/s ↑ {:.1} {}/s", received, received_unit, sent, sent_unit))
+}
+
+fn usage_labels(ui: &mut egui::Ui, cpu: Option<f32>, memory: Option<u64>, memory_limit: Option<u64>, rate: Option<(f64, f64)>) {
+    let grey = Color32::from_rgb(107, 114, 128);
+    match cpu {
+        Some(cpu) => ui.label(format!("{:.1}%", cpu)),
+        None => ui.label(RichText::new("-").color(grey)),
+    };
+    match memory {
+        Some(memory) => {
+            let (value, unit) = DevDashboard::format_bytes(memory);
+            let label = ui.label(format!("{:.1} {}", value, unit));
+            if let Some(limit) = memory_limit {
+                let (limit, unit) = DevDashboard::format_bytes(limit);
+                label.on_hover_text(format!("Limit {:.1} {}", limit, unit));
+            }
+        }
+        None => {
+            ui.label(RichText::new("-").color(grey));
+        }
+    }
+    match rate_text(rate) {
+        Some(text) => ui.label(text),
+        None => ui.label(RichText::new("-").color(grey)),
+    };
+}
+
+/// Sums of the containers' readings; None when none of them has one
+fn project_usage(containers: &[&Container]) -> (Option<f32>, Option<u64>, Option<(f64, f64)>) {
+    let cpu = containers.iter().filter_map(|container| container.cpu).reduce(|a, b| a + b);
+    let memory = containers.iter().filter_map(|container| container.memory).reduce(|a, b| a + b);
+    let rate = containers.iter().filter_map(|container| container.network_rate).reduce(|a, b| (a.0 + b.0, a.1 + b.1));
+    (cpu, memory, rate)
+}
+
+fn container_row(ui: &mut egui::Ui, container: &Container, indent: bool, pending: Option<&str>, requested: &mut Option<Request>) {
+    let mut hover: String = container.id.chars().take(12).collect();
+    if let Some(project) = container.project() {
+        hover.push_str(&format!("\nCompose project {}", project));
+    }
+    let name = if indent { format!("    {}", container.name) } else { container.name.clone() };
+    ui.label(name).on_hover_text(hover);
+    ui.label(&container.image);
+    ui.colored_label(container.state_color(), &container.state).on_hover_text(&container.status);
+    usage_labels(ui, container.cpu, container.memory, container.memory_limit, container.network_rate);
+    ui.horizontal(|ui| {
+        if pending == Some(container.id.as_str()) {
+            ui.spinner();
+            return;
+        }
+        let actions: &[ContainerAction] = if container.is_running() {
+            &[ContainerAction::Stop, ContainerAction::Restart]
+        } else {
+            &[ContainerAction::Start]
+        };
+        for action in actions {
+            if ui.add_enabled(pending.is_none(), egui::Button::new(action.label()).small()).clicked() {
+                *requested = Some(Request::Container(container.id.clone(), *action));
+            }
+        }
+    });
+    ui.end_row();
+}
+
+impl DevDashboard {
+    /// Refreshes the container list when it is due; called while something shows containers
+    pub(crate) fn update_containers(&mut self) {
+        if self.containers.poll() {
+            self.remember_compose_projects();
+        }
+        if !self.containers.needs_refresh() {
+            return;
+        }
+        let pipes = self.settings.containers.pipes();
+        let all = self.settings.containers.show_stopped;
+        let (sender, receiver) = channel();
+        self.containers.receiver = Some(receiver);
+        self.runtime().spawn(async move {
+            sender.send(fetch(pipes, all).await).ok();
+        });
+    }
+
+    /// Keeps where each compose project was started from, so it can still be brought up after `down`
+    fn remember_compose_projects(&mut self) {
+        let mut changed = false;
+        for container in &self.containers.containers {
+            let (Some(name), Some(project)) = (container.project(), container.compose_project()) else { continue };
+            if self.settings.containers.projects.get(name) != Some(&project) {
+                info!("Compose project {} is in {}", name, project.working_dir);
+                self.settings.containers.projects.insert(name.to_string(), project);
+                changed = true;
+            }
+        }
+        if changed {
+            self.save_settings();
+        }
+    }
+
+    pub(crate) fn start_container_action(&mut self, id: String, action: ContainerAction) {
+        let Some(pipe) = self.containers.engine.clone() else { return };
+        let name = self.containers.containers.iter()
+            .find(|container| container.id == id)
+            .map(|container| container.name.clone())
+            .unwrap_or_else(|| id.clone());
+        info!("Container {}: {}", name, action.path());
+        let (sender, receiver) = channel();
+        self.containers.pending = Some(PendingAction {
+            target: id.clone(),
+            description: format!("{} {}", action.label(), name),
+            done: format!("{} {}", action.done(), name),
+        });
+        self.containers.action_receiver = Some(receiver);
+        self.containers.message = None;
+        self.runtime().spawn(async move {
+            sender.send(run_action(pipe, id, action).await).ok();
+        });
+    }
+
+    pub(crate) fn start_project_action(&mut self, name: String, action: ProjectAction) {
+        let Some(pipe) = self.containers.engine.clone() else { return };
+        let project = self.settings.containers.projects.get(&name).cloned().unwrap_or_default();
+        info!("Compose project {}: {}", name, action.label().to_lowercase());
+        let (sender, receiver) = channel();
+        self.containers.pending = Some(PendingAction {
+            target: name.clone(),
+            description: format!("Compose {} {}", action.label().to_lowercase(), name),
+            done: format!("{} is {}", name, action.label().to_lowercase()),
+        });
+        self.containers.action_receiver = Some(receiver);
+        self.containers.message = None;
+        self.runtime().spawn(async move {
+            sender.send(run_compose(pipe, name, project, action).await).ok();
+        });
+    }
+
+    /// Displays the Containers tab
+    /// Lists containers from the local Docker or Podman engine grouped by compose project, with their usage,
+    /// start/stop/restart buttons per container and up/down per project
+    pub(crate) fn show_containers_tab(&mut self, ui: &mut egui::Ui) {
+        self.update_containers();
+        let grey = Color32::from_rgb(107, 114, 128);
+        let mut requested = None;
+        let mut settings_changed = false;
+
+        egui::Frame::none()
+            .inner_margin(egui::style::Margin::same(10.0))
+            .show(ui, |ui| {
+                ui.heading("Containers");
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    match &self.containers.engine {
+                        Some(pipe) => {
+                            let running = self.containers.containers.iter().filter(|container| container.is_running()).count();
+                            ui.label(format!("{} running", running));
+                            ui.label(RichText::new(pipe).color(grey));
+                        }
+                        None if self.containers.error.is_none() => {
+                            ui.label(RichText::new("Looking for a container engine...").color(grey));
+                        }
+                        None => {}
+                    }
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if self.containers.is_refreshing() {
+                            ui.spinner();
+                        }
+                        settings_changed |= ui.checkbox(&mut self.settings.containers.show_stopped, "Show stopped").changed();
+                    });
+                });
+                if let Some(error) = &self.containers.error {
+                    ui.colored_label(Color32::from_rgb(220, 50, 50), error);
+                    ui.label(RichText::new("Start Docker Desktop or a Podman machine, or set the engine pipe in Settings.").color(grey));
+                }
+                if let Some((message, failed)) = &self.containers.message {
+                    let color = if *failed { Color32::from_rgb(220, 50, 50) } else { Color32::from_rgb(22, 163, 74) };
+                    ui.colored_label(color, message);
+                }
+                ui.add_space(8.0);
+
+                if self.containers.engine.is_none() {
+                    return;
+                }
+
+                // Projects seen before are listed even when `down` removed all their containers
+                let mut projects: BTreeMap<&str, Vec<&Container>> = self.settings.containers.projects.keys()
+                    .map(|name| (name.as_str(), Vec::new()))
+                    .collect();
+                let mut standalone = Vec::new();
+                for container in &self.containers.containers {
+                    match container.project() {
+                        Some(project) => projects.entry(project).or_default().push(container),
+                        None => standalone.push(container),
+                    }
+                }
+                if projects.is_empty() && standalone.is_empty() {
+                    ui.label(RichText::new("No containers").color(grey));
+                    return;
+                }
+
+                let pending = self.containers.pending.as_ref().map(|pending| pending.target.as_str());
+                egui::ScrollArea::vertical().id_source("containers_scroll").show(ui, |ui| {
+                    egui::Grid::new("containers_grid")
+                        .striped(true)
+                        .spacing([16.0, 6.0])
+                        .show(ui, |ui| {
+                            ui.label(RichText::new("Name").strong());
+                            ui.label(RichText::new("Image").strong());
+                            ui.label(RichText::new("State").strong());
+                            ui.label(RichText::new("CPU").strong());
+                            ui.label(RichText::new("Memory").strong());
+                            ui.label(RichText::new("Network").strong());
+                            ui.label("");
+                            ui.end_row();
+
+                            for (name, containers) in &projects {
+                                let running = containers.iter().filter(|container| container.is_running()).count();
+                                let (state, color) = match running {
+                                    0 if containers.is_empty() => ("down", grey),
+                                    0 => ("stopped", grey),
+                                    running if running == containers.len() => ("up", Color32::from_rgb(22, 163, 74)),
+                                    _ => ("partly up", Color32::from_rgb(202, 138, 4)),
+                                };
+                                let label = ui.label(RichText::new(format!("▾ {}", name)).strong());
+                                if let Some(project) = self.settings.containers.projects.get(*name) {
+                                    label.on_hover_text(&project.working_dir);
+                                }
+                                ui.label(RichText::new(format!("{} of {} running", running, containers.len())).color(grey));
+                                ui.colored_label(color, state);
+                                let (cpu, memory, rate) = project_usage(containers);
+                                usage_labels(ui, cpu, memory, None, rate);
+                                ui.horizontal(|ui| {
+                                    if pending == Some(*name) {
+                                        ui.spinner();
+                                        return;
+                                    }
+                                    if running < containers.len() || containers.is_empty() {
+                                        let known = self.settings.containers.projects.contains_key(*name);
+                                        if ui.add_enabled(pending.is_none() && known, egui::Button::new("Up").small())
+                                            .on_hover_text("docker compose up -d")
+                                            .on_disabled_hover_text("Compose didn't record where this project's files are")
+                                            .clicked() {
+                                            requested = Some(Request::Project(name.to_string(), ProjectAction::Up));
+                                        }
+                                    }
+                                    if !containers.is_empty() {
+                                        if ui.add_enabled(pending.is_none(), egui::Button::new("Down").small())
+                                            .on_hover_text("docker compose down: stops and removes the project's containers")
+                                            .clicked() {
+                                            requested = Some(Request::Project(name.to_string(), ProjectAction::Down));
+                                        }
+                                    } else if ui.small_button("Forget").on_hover_text("Stop listing this project").clicked() {
+                                        requested = Some(Request::Forget(name.to_string()));
+                                    }
+                                });
+                                ui.end_row();
+
+                                for container in containers {
+                                    container_row(ui, container, true, pending, &mut requested);
+                                }
+                            }
+                            for container in &standalone {
+                                container_row(ui, container, false, pending, &mut requested);
+                            }
+                        });
+                });
+            });
+
+        if settings_changed {
+            self.containers.last_refresh = None;
+            self.save_settings();
+        }
+        match requested {
+            Some(Request::Container(id, action)) =>
+                self.request_sensitive_action(SensitiveAction::ControlService(ServiceControl::Container(id, action))),
+            Some(Request::Project(name, action)) =>
+                self.request_sensitive_action(SensitiveAction::ControlService(ServiceControl::Compose(name, action))),
+            Some(Request::Forget(name)) => {
+                self.settings.containers.projects.remove(&name);
+                self.save_settings();
+            }
+            None => {}
+        }
+    }
+
+    pub(crate) fn show_container_settings(&mut self, ui: &mut egui::Ui) {
+        ui.label(RichText::new("Containers").strong());
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label("Engine pipe:");
+            changed |= ui.add(egui::TextEdit::singleline(&mut self.settings.containers.pipe)
+                .hint_text(r"\\.\pipe\docker_engine")
+                .desired_width(260.0))
+                .on_hover_text("Leave empty to try Docker Desktop and then the default Podman machine")
+                .changed();
+        });
+        if changed {
+            self.containers.last_refresh = None;
+            self.save_settings();
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use eframe::egui;
+use egui::{Color32, RichText};
+use crate::DevDashboard;
+
+/// Timestamps above this are taken to be in milliseconds (year 5138 in seconds)
+const MILLISECONDS_THRESHOLD: i64 = 100_000_000_000;
+
+const BYTE_UNITS: [(&str, f64); 9] = [
+    ("B", 1.0),
+    ("KB", 1e3),
+    ("MB", 1e6),
+    ("GB", 1e9),
+    ("TB", 1e12),
+    ("KiB", 1024.0),
+    ("MiB", 1024.0 * 1024.0),
+    ("GiB", 1024.0 * 1024.0 * 1024.0),
+    ("TiB", 1024.0 * 1024.0 * 1024.0 * 1024.0),
+];
+
+/// Input fields of the Converters tool
+pub struct Converters {
+    number: String,
+    radix: u32,
+    timestamp: String,
+    date: String,
+    byte_value: f64,
+    byte_unit: usize,           // Index into BYTE_UNITS
+}
+
+impl Default for Converters {
+    fn default() -> Self {
+        Self {
+            number: String::new(),
+            radix: 10,
+            timestamp: String::new(),
+            date: String::new(),
+            byte_value: 1.0,
+            byte_unit: 7,
+        }
+    }
+}
+
+/// Parses `input` in `radix`, accepting 0x/0o/0b prefixes and digit separators
+fn parse_number(input: &str, radix: u32) -> Result<u128, String> {
+    let cleaned: String = input.chars().filter(|c| !c.is_whitespace() && *c != '_' && *c != ',').collect();
+    let lower = cleaned.to_lowercase();
+    let (digits, radix) = match lower.get(..2) {
+        Some("0x") => (&lower[2..], 16),
+        Some("0o") => (&lower[2..], 8),
+        Some("0b") => (&lower[2..], 2),
+        _ => (lower.as_str(), radix),
+    };
+    u128::from_str_radix(digits, radix).map_err(|e| e.to_string())
+}
+
+/// Inserts a separator every `group` digits from the right
+fn group_digits(digits: &str, group: usize, separator: &str) -> String {
+    let chars: Vec<char> = digits.chars().collect();
+    chars.rchunks(group)
+        .rev()
+        .map(|chunk| chunk.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Unix seconds or milliseconds to a UTC time
+fn parse_timestamp(input: &str) -> Result<DateTime<Utc>, String> {
+    let value: i64 = input.trim().parse().map_err(|_| "Not a whole number".to_string())?;
+    let time = if value.abs() >= MILLISECONDS_THRESHOLD {
+        Utc.timestamp_millis_opt(value).single()
+    } else {
+        Utc.timestamp_opt(value, 0).single()
+    };
+    time.ok_or_else(|| "Out of range".to_string())
+}
+
+/// RFC 3339, or a local `YYYY-MM-DD[ HH:MM[:SS]]` date
+fn parse_date(input: &str) -> Result<DateTime<Utc>, String> {
+    let input = input.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(input) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    let naive = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(input, format).ok())
+        .or_else(|| chrono::NaiveDate::parse_from_str(input, "%Y-%m-%d").ok().and_then(|date| date.and_hms_opt(0, 0, 0)))
+        .ok_or_else(|| "Use YYYY-MM-DD HH:MM:SS or RFC 3339".to_string())?;
+    Local.from_local_datetime(&naive)
+        .earliest()
+        .map(|time| time.with_timezone(&Utc))
+        .ok_or_else(|| "Time does not exist in the local time zone".to_string())
+}
+
+/// "3 days ago" / "in 2 hours"
+fn relative(time: DateTime<Utc>) -> String {
+    let seconds = (time - Utc::now()).num_seconds();
+    let magnitude = seconds.unsigned_abs();
+    let amount = match magnitude {
+        0..=59 => format!("{} seconds", magnitude),
+        60..=3599 => format!("{} minutes", magnitude / 60),
+        3600..=86_399 => format!("{} hours", magnitude / 3600),
+        _ => format!("{} days", magnitude / 86_400),
+    };
+    if seconds < 0 { format!("{} ago", amount) } else { format!("in {}", amount) }
+}
+
+/// Value with a copy button
+fn copy_row(ui: &mut egui::Ui, label: &str, value: String) {
+    ui.label(label);
+    ui.monospace(&value);
+    if ui.small_button("📋").on_hover_text("Copy").clicked() {
+        ui.output_mut(|output| output.copied_text = value);
+    }
+    ui.end_row();
+}
+
+fn error_label(ui: &mut egui::Ui, error: &str) {
+    ui.colored_label(Color32::from_rgb(220, 50, 50), error);
+}
+
+impl DevDashboard {
+    /// Converters tool page
+    /// Number bases, Unix timestamps and byte sizes
+    pub(crate) fn show_converters_tool(&mut self, ui: &mut egui::Ui) {
+        let converters = &mut self.converters;
+
+        egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+            ui.heading("Number Base");
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut converters.number).hint_text("255, 0xFF, 0b1111_1111").desired_width(220.0));
+                for (radix, label) in [(2, "Bin"), (8, "Oct"), (10, "Dec"), (16, "Hex")] {
+                    ui.selectable_value(&mut converters.radix, radix, label);
+                }
+            });
+            if !converters.number.trim().is_empty() {
+                match parse_number(&converters.number, converters.radix) {
+                    Ok(value) => {
+                        egui::Grid::new("converter_bases").num_columns(3).spacing([8.0, 4.0]).show(ui, |ui| {
+                            copy_row(ui, "Dec", value.to_string());
+                            copy_row(ui, "Hex", format!("0x{:X}", value));
+                            copy_row(ui, "Oct", format!("0o{:o}", value));
+                            copy_row(ui, "Bin", group_digits(&format!("{:b}", value), 4, " "));
+                        });
+                    }
+                    Err(e) => error_label(ui, &e),
+                }
+            }
+            ui.add_space(16.0);
+
+            ui.heading("Unix Timestamp");
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.label("Timestamp:");
+                ui.add(egui::TextEdit::singleline(&mut converters.timestamp).hint_text("seconds or milliseconds").desired_width(180.0));
+                if ui.button("Now").clicked() {
+                    converters.timestamp = Utc::now().timestamp().to_string();
+                }
+            });
+            if !converters.timestamp.trim().is_empty() {
+                match parse_timestamp(&converters.timestamp) {
+                    Ok(time) => {
+                        egui::Grid::new("converter_timestamp").num_columns(3).spacing([8.0, 4.0]).show(ui, |ui| {
+                            copy_row(ui, "UTC", time.to_rfc3339());
+                            copy_row(ui, "Local", time.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S %:z").to_string());
+                            copy_row(ui, "Relative", relative(time));
+                        });
+                    }
+                    Err(e) => error_label(ui, &e),
+                }
+            }
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.label("Date:");
+                ui.add(egui::TextEdit::singleline(&mut converters.date).hint_text("YYYY-MM-DD HH:MM:SS (local)").desired_width(200.0));
+            });
+            if !converters.date.trim().is_empty() {
+                match parse_date(&converters.date) {
+                    Ok(time) => {
+                        egui::Grid::new("converter_date").num_columns(3).spacing([8.0, 4.0]).show(ui, |ui| {
+                            copy_row(ui, "Seconds", time.timestamp().to_string());
+                            copy_row(ui, "Milliseconds", time.timestamp_millis().to_string());
+                        });
+                    }
+                    Err(e) => error_label(ui, &e),
+                }
+            }
+            ui.add_space(16.0);
+
+            ui.heading("Byte Size");
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut converters.byte_value).speed(1.0).clamp_range(0.0..=f64::MAX));
+                egui::ComboBox::from_id_source("converter_byte_unit")
+                    .selected_text(BYTE_UNITS[converters.byte_unit].0)
+                    .show_ui(ui, |ui| {
+                        for (index, (unit, _)) in BYTE_UNITS.iter().enumerate() {
+                            ui.selectable_value(&mut converters.byte_unit, index, *unit);
+                        }
+                    });
+            });
+            let bytes = converters.byte_value * BYTE_UNITS[converters.byte_unit].1;
+            egui::Grid::new("converter_bytes").num_columns(3).spacing([8.0, 4.0]).show(ui, |ui| {
+                for (unit, factor) in BYTE_UNITS {
+                    let value = bytes / factor;
+                    let text = if value.fract() == 0.0 && value < 1e15 {
+                        format!("{}", value as u64)
+                    } else {
+                        format!("{:.4}", value)
+                    };
+                    copy_row(ui, unit, text);
+                }
+            });
+            ui.label(RichText::new("KB/MB/GB are powers of 1000, KiB/MiB/GiB powers of 1024")
+                .color(Color32::from_rgb(156, 163, 175)));
+        });
+    }
+}
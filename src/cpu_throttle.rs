@@ -0,0 +1,229 @@
+use std::os::windows::process::CommandExt;
+use std::process::Command;
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::time::{Duration, Instant};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::Deserialize;
+use wmi::{COMLibrary, WMIConnection};
+use log::{debug, info, warn};
+use crate::sensor_recovery::BackendHealth;
+use crate::{DevDashboard, CREATE_NO_WINDOW};
+
+/// How often the processor counters are read
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+/// How often the System log is searched for firmware limit events
+const EVENT_INTERVAL: Duration = Duration::from_secs(60);
+/// Firmware limit events this recent count as ongoing
+const EVENT_WINDOW_MS: u64 = 10 * 60 * 1000;
+
+#[derive(Deserialize)]
+#[serde(rename = "Win32_PerfFormattedData_Counters_ProcessorInformation")]
+#[serde(rename_all = "PascalCase")]
+struct ProcessorInformation {
+    name: String,
+    processor_frequency: Option<u32>,               // Base clock in MHz
+    percent_processor_performance: Option<u64>,     // Effective clock as a share of base, can exceed 100
+    percent_performance_limit: Option<u32>,         // Below 100 while firmware, thermal or power limits apply
+}
+
+/// Thermal zones in tenths of a Kelvin; needs administrator rights on most machines
+#[derive(Deserialize)]
+#[serde(rename = "MSAcpi_ThermalZoneTemperature")]
+#[serde(rename_all = "PascalCase")]
+struct ThermalZone {
+    current_temperature: Option<u32>,
+    passive_trip_point: Option<u32>,
+}
+
+/// Why and how much the processor is held back
+#[derive(Clone, Copy, Default)]
+pub struct ThrottleStatus {
+    pub base_mhz: Option<u32>,
+    pub effective_mhz: Option<u32>,
+    pub performance_limit: Option<u32>,     // Percent of maximum performance allowed
+    pub thermal_passive: Option<bool>,      // A thermal zone reached its passive cooling point; None when unreadable
+    pub temperature: Option<f32>,           // Celsius, hottest thermal zone
+    pub passive_temperature: Option<f32>,   // Celsius, that zone's passive cooling point
+    pub firmware_events: Option<usize>,     // Kernel-Processor-Power event 37 in the last ten minutes
+}
+
+impl ThrottleStatus {
+    pub fn is_limited(&self) -> bool {
+        self.performance_limit.is_some_and(|limit| limit < 100)
+            || self.thermal_passive == Some(true)
+            || self.firmware_events.is_some_and(|count| count > 0)
+    }
+
+    /// Most likely cause, from the strongest evidence available
+    fn reason(&self) -> &'static str {
+        if self.thermal_passive == Some(true) {
+            "thermal throttling"
+        } else if self.firmware_events.is_some_and(|count| count > 0) {
+            "firmware limit (PROCHOT or PL1/PL2 power limit)"
+        } else {
+            "power or platform limit"
+        }
+    }
+}
+
+/// Counts firmware performance limit events; Windows logs these while PROCHOT or a package power limit is active
+fn firmware_limit_events() -> Option<usize> {
+    let query = format!(
+        "/q:*[System[Provider[@Name='Microsoft-Windows-Kernel-Processor-Power'] and EventID=37 and TimeCreated[timediff(@SystemTime) <= {}]]]",
+        EVENT_WINDOW_MS
+    );
+    let output = Command::new("wevtutil")
+        .args(["qe", "System", &query, "/f:xml", "/c:100"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).matches("</Event>").count())
+}
+
+fn run_sensor_thread(sender: Sender<ThrottleStatus>) {
+    let Ok(com) = COMLibrary::new() else {
+        warn!("Could not initialize COM for processor counters");
+        return;
+    };
+    let cimv2 = WMIConnection::new(com).ok();
+    let root_wmi = WMIConnection::with_namespace_path("root\\WMI", com).ok();
+    let mut firmware_events = None;
+    let mut last_event_check: Option<Instant> = None;
+
+    loop {
+        let mut status = ThrottleStatus::default();
+        match cimv2.as_ref().map(|wmi| wmi.query::<ProcessorInformation>()) {
+            Some(Ok(processors)) => {
+                if let Some(total) = processors.into_iter().find(|processor| processor.name == "_Total") {
+                    status.base_mhz = total.processor_frequency;
+                    status.performance_limit = total.percent_performance_limit;
+                    status.effective_mhz = total.processor_frequency
+                        .zip(total.percent_processor_performance)
+                        .map(|(base, percent)| (base as u64 * percent / 100) as u32);
+                }
+            }
+            Some(Err(e)) => debug!("Processor counter query failed: {}", e),
+            None => {}
+        }
+
+        let zones = root_wmi
+            .as_ref()
+            .and_then(|wmi| wmi.query::<ThermalZone>().ok())
+            .filter(|zones| !zones.is_empty());
+        status.thermal_passive = zones.as_ref().map(|zones| zones.iter().any(|zone| match (zone.current_temperature, zone.passive_trip_point) {
+            (Some(current), Some(passive)) => passive > 0 && current >= passive,
+            _ => false,
+        }));
+        let celsius = |tenths_kelvin: u32| tenths_kelvin as f32 / 10.0 - 273.15;
+        let hottest = zones.iter().flatten()
+            .filter(|zone| zone.current_temperature.is_some_and(|current| current > 0))
+            .max_by_key(|zone| zone.current_temperature);
+        if let Some(hottest) = hottest {
+            status.temperature = hottest.current_temperature.map(celsius);
+            status.passive_temperature = hottest.passive_trip_point.filter(|passive| *passive > 0).map(celsius);
+        }
+
+        let events_due = match last_event_check {
+            Some(last) => last.elapsed() >= EVENT_INTERVAL,
+            None => true,
+        };
+        if events_due {
+            firmware_events = firmware_limit_events();
+            last_event_check = Some(Instant::now());
+        }
+        status.firmware_events = firmware_events;
+
+        if sender.send(status).is_err() {
+            break;
+        }
+        std::thread::sleep(SAMPLE_INTERVAL);
+    }
+}
+
+/// Latest throttling indicators from the sensor thread
+#[derive(Default)]
+pub struct ThrottleMonitor {
+    receiver: Option<Receiver<ThrottleStatus>>,
+    pub status: Option<ThrottleStatus>,
+    pub health: BackendHealth,
+}
+
+impl ThrottleMonitor {
+    pub fn update(&mut self) {
+        match &self.receiver {
+            Some(receiver) => loop {
+                let status = match receiver.try_recv() {
+                    Ok(status) => status,
+                    Err(TryRecvError::Empty) => break,
+                    // The thread gives up when COM can't be initialized; it is started again after a while
+                    Err(TryRecvError::Disconnected) => {
+                        self.receiver = None;
+                        self.status = None;
+                        self.health.failed("Processor counters", "Could not initialize COM".to_string());
+                        break;
+                    }
+                };
+                self.health.succeeded("Processor counters");
+                let was_limited = self.status.is_some_and(|previous| previous.is_limited());
+                if status.is_limited() && !was_limited {
+                    info!("CPU performance limited: {} ({}% of maximum)", status.reason(),
+                        status.performance_limit.unwrap_or(100));
+                } else if !status.is_limited() && was_limited {
+                    info!("CPU performance limit lifted");
+                }
+                self.status = Some(status);
+            },
+            None if !self.health.retry_due() => {}
+            None => {
+                debug!("Starting processor throttling thread");
+                let (sender, receiver) = channel();
+                self.receiver = Some(receiver);
+                std::thread::spawn(move || run_sensor_thread(sender));
+            }
+        }
+    }
+}
+
+impl DevDashboard {
+    /// Throttling line for the CPU card
+    pub(crate) fn show_cpu_throttle_status(&self, ui: &mut egui::Ui) {
+        let Some(status) = self.throttle_monitor.status else { return };
+        let clocks = match (status.effective_mhz, status.base_mhz) {
+            (Some(effective), Some(base)) if base > 0 => format!("{:.2} GHz effective ({:.0}% of base)",
+                effective as f64 / 1000.0, effective as f64 / base as f64 * 100.0),
+            _ => String::new(),
+        };
+
+        let mut details = Vec::new();
+        if let Some(limit) = status.performance_limit {
+            details.push(format!("Performance limit: {}% of maximum", limit));
+        }
+        details.push(match status.thermal_passive {
+            Some(true) => "Thermal zone at its passive cooling point".to_string(),
+            Some(false) => "Thermal zones below their passive cooling points".to_string(),
+            None => "Thermal zones unreadable (run as administrator)".to_string(),
+        });
+        if let Some(count) = status.firmware_events {
+            details.push(format!("{} firmware limit events in the last 10 minutes", count));
+        }
+        let details = details.join("\n");
+
+        if status.is_limited() {
+            let text = match status.performance_limit.filter(|limit| *limit < 100) {
+                Some(limit) => format!("⚠ Limited to {}% by {}", limit, status.reason()),
+                None => format!("⚠ {}", status.reason()),
+            };
+            ui.label(RichText::new(text).color(Color32::from_rgb(202, 138, 4))).on_hover_text(details);
+            if !clocks.is_empty() {
+                ui.label(RichText::new(clocks).color(Color32::from_rgb(156, 163, 175)));
+            }
+        } else {
+            let text = if clocks.is_empty() { "Not throttled".to_string() } else { format!("{} · not throttled", clocks) };
+            ui.label(RichText::new(text).color(Color32::from_rgb(156, 163, 175))).on_hover_text(details);
+        }
+    }
+}
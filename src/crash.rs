@@ -0,0 +1,315 @@
+use std::backtrace::Backtrace;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::Read;
+use std::os::windows::io::AsRawHandle;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::Duration;
+use chrono::Local;
+use eframe::egui;
+use egui::{Color32, RichText};
+use reqwest::Client;
+use serde::{Serialize, Deserialize};
+use sysinfo::{System, SystemExt};
+use windows::core::HSTRING;
+use windows::Win32::Foundation::{FALSE, HANDLE, HWND};
+use windows::Win32::System::Diagnostics::Debug::{
+    MiniDumpWriteDump, SetUnhandledExceptionFilter, EXCEPTION_POINTERS, MINIDUMP_EXCEPTION_INFORMATION,
+    MiniDumpWithThreadInfo,
+};
+use windows::Win32::System::Threading::{GetCurrentProcess, GetCurrentProcessId, GetCurrentThreadId};
+use windows::Win32::UI::WindowsAndMessaging::{MessageBoxW, IDYES, MB_ICONERROR, MB_SETFOREGROUND, MB_YESNO};
+use log::{error, info, warn};
+use crate::{storage, DevDashboard};
+
+/// Release builds set this; without it reports are only kept locally
+const UPLOAD_URL: Option<&str> = option_env!("DEV_DASHBOARD_CRASH_URL");
+const CRASH_DIR: &str = "crashes";
+const LOG_FILE: &str = "dev_dashboard.log";
+/// Log lines from just before the crash that go into the report
+const LOG_TAIL_LINES: usize = 40;
+/// Suffix of reports that were already uploaded
+const UPLOADED_SUFFIX: &str = ".uploaded.txt";
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+/// Returned from the exception filter to let Windows end the process
+const EXCEPTION_EXECUTE_HANDLER: i32 = 1;
+
+/// Set once a crash is being handled so a second panic while reporting cannot loop
+static HANDLING: AtomicBool = AtomicBool::new(false);
+
+/// Persisted crash report preference
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct CrashSettings {
+    pub upload_reports: bool,   // Opt-in; only the sanitized text report is sent, never the minidump
+}
+
+/// Uploads of reports left behind by earlier crashes
+#[derive(Default)]
+pub struct CrashReporter {
+    started: bool,
+    receiver: Option<Receiver<(usize, usize)>>,     // Uploaded and failed counts
+    pub local_reports: usize,
+}
+
+/// Replaces ASCII-case-insensitive matches; byte offsets line up because only ASCII is lowered
+fn replace_ignore_case(text: &str, needle: &str, replacement: &str) -> String {
+    let haystack = text.to_ascii_lowercase();
+    let needle = needle.to_ascii_lowercase();
+    let mut result = String::with_capacity(text.len());
+    let mut last = 0;
+    for (start, _) in haystack.match_indices(&needle) {
+        if start < last {
+            continue;
+        }
+        result.push_str(&text[last..start]);
+        result.push_str(replacement);
+        last = start + needle.len();
+    }
+    result.push_str(&text[last..]);
+    result
+}
+
+/// Replaces URLs and query strings, which can carry tokens, calendar keys and private hosts
+fn strip_urls(text: &str) -> String {
+    text.split_inclusive(char::is_whitespace)
+        .map(|piece| {
+            let word = piece.trim_end();
+            let space = &piece[word.len()..];
+            if let Some(scheme) = word.find("://") {
+                // Keep what is glued to the front, such as a quote or "url="
+                let start = word[..scheme].char_indices().rev()
+                    .find(|(_, c)| !c.is_ascii_alphanumeric())
+                    .map_or(0, |(index, c)| index + c.len_utf8());
+                format!("{}<url>{}", &word[..start], space)
+            } else if let Some(query) = word.find('?').filter(|&index| word[index..].contains('=')) {
+                format!("{}?<query>{}", &word[..query], space)
+            } else {
+                piece.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Strips the profile path, account name, computer name and URLs so a report identifies nobody
+fn sanitize(text: &str) -> String {
+    let mut text = text.to_string();
+    let identifiers = [
+        (std::env::var("USERPROFILE").ok(), "%USERPROFILE%"),
+        (Some(whoami::username()), "<user>"),
+        (std::env::var("COMPUTERNAME").ok(), "<computer>"),
+    ];
+    for (value, replacement) in identifiers {
+        // Very short names would also replace unrelated text
+        if let Some(value) = value.filter(|value| value.len() >= 3) {
+            text = replace_ignore_case(&text, &value, replacement);
+        }
+    }
+    strip_urls(&text)
+}
+
+fn log_tail() -> String {
+    let mut contents = String::new();
+    if let Ok(mut file) = File::open(LOG_FILE) {
+        file.read_to_string(&mut contents).ok();
+    }
+    let lines: Vec<&str> = contents.lines().collect();
+    lines[lines.len().saturating_sub(LOG_TAIL_LINES)..].join("\n")
+}
+
+/// Path for a new report or dump, named after the crash time
+fn crash_path(stamp: &str, extension: &str) -> PathBuf {
+    Path::new(CRASH_DIR).join(format!("crash-{}.{}", stamp, extension))
+}
+
+/// Writes a dump of this process; `exception` is set when Windows reported the crash
+fn write_minidump(path: &Path, exception: Option<*const EXCEPTION_POINTERS>) -> bool {
+    let information = exception.map(|pointers| MINIDUMP_EXCEPTION_INFORMATION {
+        ThreadId: unsafe { GetCurrentThreadId() },
+        ExceptionPointers: pointers as *mut _,
+        ClientPointers: FALSE,
+    });
+    storage::write_atomic_with(path, |file| {
+        let written = unsafe {
+            MiniDumpWriteDump(
+                GetCurrentProcess(),
+                GetCurrentProcessId(),
+                HANDLE(file.as_raw_handle() as isize),
+                MiniDumpWithThreadInfo,
+                information.as_ref().map(|information| information as *const _),
+                None,
+                None,
+            )
+        };
+        if written.as_bool() { Ok(()) } else { Err(std::io::Error::last_os_error()) }
+    }).is_ok()
+}
+
+/// Writes the dump and the sanitized report, then offers to start the dashboard again
+fn report_crash(summary: &str, exception: Option<*const EXCEPTION_POINTERS>, fatal: bool) {
+    if HANDLING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    error!("Crash: {}", summary);
+    let stamp = Local::now().format("%Y%m%d-%H%M%S").to_string();
+    std::fs::create_dir_all(CRASH_DIR).ok();
+
+    let dump_path = crash_path(&stamp, "dmp");
+    let dumped = write_minidump(&dump_path, exception);
+    let system = System::new();
+    let mut report = String::new();
+    let _ = writeln!(report, "Dev Dashboard {} crash report", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(report, "Time: {}", Local::now().format("%Y-%m-%d %H:%M:%S %z"));
+    let _ = writeln!(report, "OS: {}", system.long_os_version().unwrap_or_default());
+    let _ = writeln!(report, "Minidump: {}", if dumped { "written locally, not uploaded" } else { "could not be written" });
+    let _ = writeln!(report, "\n{}", summary);
+    let _ = writeln!(report, "\nBacktrace:\n{}", Backtrace::force_capture());
+    let _ = writeln!(report, "\nLast log lines:\n{}", log_tail());
+    let report_path = crash_path(&stamp, "txt");
+    if let Err(e) = storage::write_atomic(&report_path, sanitize(&report).as_bytes()) {
+        error!("Could not write crash report: {}", e);
+    }
+    log::logger().flush();
+
+    if !fatal {
+        // A panic on a background thread under the unwinding profile leaves the dashboard running
+        HANDLING.store(false, Ordering::SeqCst);
+        return;
+    }
+    let text = format!(
+        "Dev Dashboard has crashed.\n\nA crash report was saved to {}.\n\nStart Dev Dashboard again?",
+        report_path.display()
+    );
+    let choice = unsafe {
+        MessageBoxW(HWND(0), &HSTRING::from(text), &HSTRING::from("Dev Dashboard"), MB_YESNO | MB_ICONERROR | MB_SETFOREGROUND)
+    };
+    if choice == IDYES {
+        crate::instance::release();
+        if let Ok(exe) = std::env::current_exe() {
+            Command::new(exe).args(std::env::args_os().skip(1)).spawn().ok();
+        }
+    }
+}
+
+unsafe extern "system" fn exception_filter(pointers: *const EXCEPTION_POINTERS) -> i32 {
+    let code = pointers
+        .as_ref()
+        .and_then(|pointers| pointers.ExceptionRecord.as_ref())
+        .map(|record| record.ExceptionCode.0 as u32)
+        .unwrap_or(0);
+    report_crash(&format!("Unhandled exception 0x{:08X}", code), Some(pointers), true);
+    EXCEPTION_EXECUTE_HANDLER
+}
+
+/// Reports panics and native crashes; call once logging is up
+pub fn install() {
+    std::panic::set_hook(Box::new(|info| {
+        let message = info.payload()
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Unknown panic".to_string());
+        let location = info.location().map(|location| location.to_string()).unwrap_or_default();
+        let thread = std::thread::current();
+        let thread_name = thread.name().unwrap_or("unnamed");
+        // Release builds abort on any panic; otherwise only a panic on the UI thread ends the process
+        let fatal = cfg!(panic = "abort") || thread_name == "main";
+        report_crash(&format!("Panic on thread '{}' at {}: {}", thread_name, location, message), None, fatal);
+    }));
+    unsafe { SetUnhandledExceptionFilter(Some(exception_filter)) };
+}
+
+/// Reports in the crash folder that have not been uploaded yet
+fn pending_reports() -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(CRASH_DIR) else { return Vec::new() };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+            name.ends_with(".txt") && !name.ends_with(UPLOADED_SUFFIX)
+        })
+        .collect()
+}
+
+/// Posts each report as plain text and renames it once the server accepted it
+async fn upload_reports(url: &str, reports: Vec<PathBuf>) -> (usize, usize) {
+    let Ok(client) = Client::builder().timeout(UPLOAD_TIMEOUT).build() else { return (0, reports.len()) };
+    let (mut uploaded, mut failed) = (0, 0);
+    for path in reports {
+        let Ok(report) = std::fs::read_to_string(&path) else { continue };
+        let result = client.post(url)
+            .header("Content-Type", "text/plain; charset=utf-8")
+            .body(report)
+            .send().await
+            .and_then(|response| response.error_for_status());
+        match result {
+            Ok(_) => {
+                uploaded += 1;
+                let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default().to_string();
+                std::fs::rename(&path, path.with_file_name(format!("{}{}", stem, UPLOADED_SUFFIX))).ok();
+            }
+            Err(e) => {
+                warn!("Could not upload crash report {}: {}", path.display(), e);
+                failed += 1;
+            }
+        }
+    }
+    (uploaded, failed)
+}
+
+impl DevDashboard {
+    /// Uploads reports from earlier crashes once per run when the user opted in
+    pub(crate) fn update_crash_reports(&mut self) {
+        if !self.crash_reporter.started {
+            self.crash_reporter.started = true;
+            let reports = pending_reports();
+            self.crash_reporter.local_reports = reports.len();
+            if !reports.is_empty() {
+                info!("Found {} crash reports from earlier runs", reports.len());
+            }
+            if let (true, Some(url), false) = (self.settings.crash.upload_reports, UPLOAD_URL, reports.is_empty()) {
+                let (sender, receiver) = channel();
+                self.crash_reporter.receiver = Some(receiver);
+                self.runtime().spawn(async move {
+                    sender.send(upload_reports(url, reports).await).ok();
+                });
+            }
+        }
+
+        let Some(receiver) = &self.crash_reporter.receiver else { return };
+        let Ok((uploaded, failed)) = receiver.try_recv() else { return };
+        self.crash_reporter.receiver = None;
+        self.crash_reporter.local_reports = failed;
+        info!("Uploaded {} crash reports ({} failed)", uploaded, failed);
+    }
+
+    /// Settings section for crash reports
+    pub(crate) fn show_crash_settings(&mut self, ui: &mut egui::Ui) {
+        ui.label("Crash Reports:");
+        let enabled = UPLOAD_URL.is_some();
+        let text = "Upload crash reports on the next start";
+        let response = ui.add_enabled(enabled, egui::Checkbox::new(&mut self.settings.crash.upload_reports, text));
+        if !enabled {
+            response.on_disabled_hover_text("This build has no crash report server");
+        } else if response.on_hover_text("Only the text report is sent, with your user name, profile path and computer name removed").changed() {
+            self.save_settings();
+        }
+        ui.horizontal(|ui| {
+            let count = self.crash_reporter.local_reports;
+            let text = match count {
+                0 => "No crash reports waiting".to_string(),
+                1 => "1 crash report not uploaded".to_string(),
+                _ => format!("{} crash reports not uploaded", count),
+            };
+            ui.label(RichText::new(text).color(Color32::from_rgb(156, 163, 175)));
+            if ui.button("Open folder").clicked() {
+                std::fs::create_dir_all(CRASH_DIR).ok();
+                let _ = Command::new("explorer").arg(CRASH_DIR).spawn();
+            }
+        });
+    }
+}
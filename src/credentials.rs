@@ -0,0 +1,57 @@
+use windows::core::{HSTRING, PWSTR};
+use windows::Win32::Security::Credentials::{
+    CredDeleteW, CredFree, CredReadW, CredWriteW, CREDENTIALW, CRED_PERSIST_LOCAL_MACHINE, CRED_TYPE_GENERIC,
+};
+use log::warn;
+
+/// Secrets are stored as generic credentials named "Dev Dashboard/<name>", encrypted for the signed-in user
+fn target(name: &str) -> HSTRING {
+    HSTRING::from(format!("Dev Dashboard/{}", name))
+}
+
+/// A secret saved with `write`, or None when there is none
+pub fn read(name: &str) -> Option<String> {
+    let mut credential: *mut CREDENTIALW = std::ptr::null_mut();
+    unsafe {
+        if !CredReadW(&target(name), CRED_TYPE_GENERIC.0, 0, &mut credential).as_bool() {
+            return None;
+        }
+        let blob = std::slice::from_raw_parts((*credential).CredentialBlob, (*credential).CredentialBlobSize as usize);
+        let secret = String::from_utf8_lossy(blob).into_owned();
+        CredFree(credential as *const _);
+        Some(secret)
+    }
+}
+
+/// Saves a secret in Windows Credential Manager; an empty one is deleted
+pub fn write(name: &str, secret: &str) {
+    let target = target(name);
+    if secret.is_empty() {
+        unsafe { CredDeleteW(&target, CRED_TYPE_GENERIC.0, 0) };
+        return;
+    }
+    let mut target_name: Vec<u16> = target.as_wide().iter().copied().chain(Some(0)).collect();
+    let mut blob = secret.as_bytes().to_vec();
+    let credential = CREDENTIALW {
+        Type: CRED_TYPE_GENERIC,
+        TargetName: PWSTR(target_name.as_mut_ptr()),
+        CredentialBlobSize: blob.len() as u32,
+        CredentialBlob: blob.as_mut_ptr(),
+        Persist: CRED_PERSIST_LOCAL_MACHINE,
+        ..Default::default()
+    };
+    if !unsafe { CredWriteW(&credential, 0) }.as_bool() {
+        warn!("Could not save {} in Credential Manager: {}", name, std::io::Error::last_os_error());
+    }
+}
+
+/// Moves a secret read from an older settings.json into Credential Manager, or loads the stored one.
+/// Returns true when a secret was moved, so the settings can be saved without it.
+pub fn migrate(name: &str, secret: &mut String) -> bool {
+    if secret.is_empty() {
+        *secret = read(name).unwrap_or_default();
+        return false;
+    }
+    write(name, secret);
+    true
+}
@@ -0,0 +1,116 @@
+use std::ffi::c_void;
+use std::sync::OnceLock;
+use windows::Win32::Foundation::LUID;
+use log::debug;
+
+/// D3DKMT_QUERYADAPTERINFO types for the performance data WDDM 2.4 drivers report
+const KMTQAITYPE_NODEPERFDATA: i32 = 61;
+const KMTQAITYPE_ADAPTERPERFDATA: i32 = 62;
+/// Node 0 is the 3D engine, whose clock is the one vendor tools call the core clock
+const GRAPHICS_NODE: u32 = 0;
+
+#[repr(C)]
+struct OpenAdapterFromLuid {
+    adapter_luid: LUID,
+    adapter: u32,                   // Out: kernel handle
+}
+
+#[repr(C)]
+struct CloseAdapter {
+    adapter: u32,
+}
+
+#[repr(C)]
+struct QueryAdapterInfo {
+    adapter: u32,
+    kind: i32,
+    data: *mut c_void,
+    size: u32,
+}
+
+/// D3DKMT_ADAPTER_PERFDATA
+#[repr(C)]
+#[derive(Default)]
+struct AdapterPerfData {
+    physical_adapter_index: u32,
+    memory_frequency: u64,          // Hz
+    max_memory_frequency: u64,
+    max_memory_frequency_oc: u64,
+    memory_bandwidth: u64,
+    pcie_bandwidth: u64,
+    fan_rpm: u32,
+    power: u32,                     // Tenths of a percent of the board's limit
+    temperature: u32,               // Tenths of a degree Celsius
+    power_state_override: u8,
+}
+
+/// D3DKMT_NODE_PERFDATA
+#[repr(C)]
+#[derive(Default)]
+struct NodePerfData {
+    node_ordinal: u32,
+    physical_adapter_index: u32,
+    frequency: u64,                 // Hz
+    max_frequency: u64,
+    max_frequency_oc: u64,
+    voltage: u32,
+    voltage_max: u32,
+    voltage_max_oc: u32,
+    max_transition_latency: u64,
+}
+
+type OpenAdapterFn = unsafe extern "system" fn(*mut OpenAdapterFromLuid) -> i32;
+type CloseAdapterFn = unsafe extern "system" fn(*const CloseAdapter) -> i32;
+type QueryAdapterInfoFn = unsafe extern "system" fn(*const QueryAdapterInfo) -> i32;
+
+/// The graphics kernel entry points live in gdi32.dll; the windows crate does not bind them
+fn gdi32() -> Option<&'static libloading::Library> {
+    static LIBRARY: OnceLock<Option<libloading::Library>> = OnceLock::new();
+    LIBRARY.get_or_init(|| unsafe { libloading::Library::new("gdi32.dll").ok() }).as_ref()
+}
+
+/// Temperature and clocks as the display driver reports them to Windows.
+/// This is the only vendor-neutral source for AMD and Intel GPUs; fields are None when the driver leaves them at zero.
+#[derive(Clone, Copy, Default)]
+pub struct KernelSensors {
+    pub temperature: Option<u32>,       // Celsius
+    pub graphics_clock: Option<u32>,    // MHz
+    pub memory_clock: Option<u32>,      // MHz
+}
+
+fn query<T>(query_info: QueryAdapterInfoFn, adapter: u32, kind: i32, data: &mut T) -> bool {
+    let info = QueryAdapterInfo { adapter, kind, data: data as *mut T as *mut c_void, size: std::mem::size_of::<T>() as u32 };
+    unsafe { query_info(&info) == 0 }
+}
+
+/// Reads the sensors of the adapter with this LUID; None before Windows 10 1803 or without the driver support
+pub fn read(luid: (i32, u32)) -> Option<KernelSensors> {
+    let library = gdi32()?;
+    unsafe {
+        let open = library.get::<OpenAdapterFn>(b"D3DKMTOpenAdapterFromLuid\0").ok()?;
+        let close = library.get::<CloseAdapterFn>(b"D3DKMTCloseAdapter\0").ok()?;
+        let query_info = *library.get::<QueryAdapterInfoFn>(b"D3DKMTQueryAdapterInfo\0").ok()?;
+
+        let mut handle = OpenAdapterFromLuid { adapter_luid: LUID { LowPart: luid.1, HighPart: luid.0 }, adapter: 0 };
+        if open(&mut handle) != 0 {
+            debug!("D3DKMTOpenAdapterFromLuid failed for {:?}", luid);
+            return None;
+        }
+        let mut adapter_data = AdapterPerfData::default();
+        let mut node_data = NodePerfData { node_ordinal: GRAPHICS_NODE, ..Default::default() };
+        let has_adapter = query(query_info, handle.adapter, KMTQAITYPE_ADAPTERPERFDATA, &mut adapter_data);
+        let has_node = query(query_info, handle.adapter, KMTQAITYPE_NODEPERFDATA, &mut node_data);
+        close(&CloseAdapter { adapter: handle.adapter });
+        if !has_adapter && !has_node {
+            return None;
+        }
+
+        let reported = |value: u32| (value > 0).then_some(value);
+        let megahertz = |hertz: u64| reported((hertz / 1_000_000) as u32);
+        Some(KernelSensors {
+            temperature: has_adapter.then(|| reported((adapter_data.temperature + 5) / 10)).flatten(),
+            graphics_clock: has_node.then(|| megahertz(node_data.frequency)).flatten(),
+            memory_clock: has_adapter.then(|| megahertz(adapter_data.memory_frequency)).flatten(),
+        })
+    }
+}
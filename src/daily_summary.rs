@@ -0,0 +1,197 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+use chrono::Local;
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use log::info;
+use crate::{storage, DevDashboard};
+
+/// One summary per day, next to settings.json
+const SUMMARY_FILE: &str = "summaries.json";
+const SAVE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+const RETENTION_DAYS: i64 = 90;
+
+/// Peaks and events of one day, from the hours the dashboard was running
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct DaySummary {
+    pub date: String,                       // YYYY-MM-DD
+    pub peak_cpu: f32,                      // Percent
+    pub peak_memory: f32,                   // Percent
+    pub peak_gpu: Option<f32>,              // Percent
+    pub peak_cpu_temperature: Option<f32>,  // Celsius
+    pub peak_gpu_temperature: Option<u32>,  // Celsius
+    pub received: u64,                      // Bytes, all interfaces
+    pub sent: u64,
+    pub alerts: Vec<String>,                // "14:05 GPU temperature is climbing"
+    pub watchdog_alerts: usize,
+    pub installs: Vec<String>,              // App names
+    pub seen: bool,                         // Shown on a later launch, or at the rollover
+}
+
+impl DaySummary {
+    fn new(date: String) -> Self {
+        Self { date, ..Default::default() }
+    }
+}
+
+fn today() -> String {
+    Local::now().format("%Y-%m-%d").to_string()
+}
+
+fn max_option<T: PartialOrd + Copy>(current: Option<T>, value: Option<T>) -> Option<T> {
+    match (current, value) {
+        (Some(current), Some(value)) => Some(if value > current { value } else { current }),
+        (current, value) => current.or(value),
+    }
+}
+
+/// Daily summaries and the one waiting to be shown
+#[derive(Default)]
+pub struct DailySummaries {
+    days: BTreeMap<String, DaySummary>,
+    showing: Option<String>,                // Date of the summary in the window
+    last_save: Option<Instant>,
+}
+
+impl DailySummaries {
+    /// Reads the summaries and picks the latest earlier day that was never shown
+    pub fn load() -> Self {
+        let days: BTreeMap<String, DaySummary> = storage::load_json(SUMMARY_FILE);
+        let today = today();
+        let showing = days.values().rev().find(|day| day.date < today && !day.seen).map(|day| day.date.clone());
+        Self { days, showing, last_save: None }
+    }
+
+    pub fn save(&mut self) {
+        let cutoff = (Local::now().date_naive() - chrono::Duration::days(RETENTION_DAYS)).format("%Y-%m-%d").to_string();
+        self.days.retain(|date, _| *date >= cutoff);
+        storage::save_json(SUMMARY_FILE, &self.days);
+        self.last_save = Some(Instant::now());
+    }
+
+    fn current(&mut self) -> &mut DaySummary {
+        let date = today();
+        self.days.entry(date.clone()).or_insert_with(|| DaySummary::new(date))
+    }
+
+    /// Adds an alert to today's summary
+    pub fn record_alert(&mut self, text: &str) {
+        let time = Local::now().format("%H:%M");
+        self.current().alerts.push(format!("{} {}", time, text));
+    }
+
+    pub fn record_install(&mut self, name: &str) {
+        self.current().installs.push(name.to_string());
+    }
+}
+
+fn summary_rows(summary: &DaySummary) -> Vec<(&'static str, String)> {
+    let size = |bytes: u64| {
+        let (value, unit) = DevDashboard::format_bytes(bytes);
+        format!("{:.1} {}", value, unit)
+    };
+    let mut rows = vec![
+        ("Peak CPU", format!("{:.0}%", summary.peak_cpu)),
+        ("Peak memory", format!("{:.0}%", summary.peak_memory)),
+    ];
+    if let Some(gpu) = summary.peak_gpu {
+        rows.push(("Peak GPU", format!("{:.0}%", gpu)));
+    }
+    if let Some(temperature) = summary.peak_cpu_temperature {
+        rows.push(("Hottest CPU", format!("{:.0}°C", temperature)));
+    }
+    if let Some(temperature) = summary.peak_gpu_temperature {
+        rows.push(("Hottest GPU", format!("{}°C", temperature)));
+    }
+    rows.push(("Transferred", format!("↓ {} ↑ {}", size(summary.received), size(summary.sent))));
+    rows.push(("Alerts", (summary.alerts.len() + summary.watchdog_alerts).to_string()));
+    rows.push(("Installs", if summary.installs.is_empty() { "None".to_string() } else { summary.installs.join(", ") }));
+    rows
+}
+
+impl DevDashboard {
+    /// Folds the latest readings into today's summary, and closes the day at midnight
+    pub(crate) fn update_daily_summary(&mut self) {
+        let date = today();
+        if !self.daily_summaries.days.contains_key(&date) {
+            let previous = self.daily_summaries.days.values().next_back().filter(|day| day.date < date && !day.seen);
+            if let Some(previous) = previous.map(|day| day.date.clone()) {
+                info!("Closing the summary for {}", previous);
+                self.daily_summaries.showing = Some(previous);
+            }
+            self.daily_summaries.current();
+            self.daily_summaries.save();
+        }
+
+        let history = &self.tray.history;
+        let (cpu, memory, gpu) = (history.cpu.back().copied(), history.memory.back().copied(), history.gpu.back().copied());
+        let cpu_temperature = self.throttle_monitor.status.and_then(|status| status.temperature);
+        let gpu_temperature = self.gpu_info.as_ref().and_then(|gpu| gpu.temperature);
+        let midnight = Local::now().date_naive().and_hms_opt(0, 0, 0)
+            .and_then(|time| time.and_local_timezone(Local).earliest())
+            .unwrap_or_else(Local::now);
+        let watchdog_alerts = self.watchdog.alerts_since(midnight);
+        let traffic = self.network_totals.day_total(&date);
+
+        let summary = self.daily_summaries.current();
+        summary.peak_cpu = summary.peak_cpu.max(cpu.unwrap_or_default());
+        summary.peak_memory = summary.peak_memory.max(memory.unwrap_or_default());
+        summary.peak_gpu = max_option(summary.peak_gpu, gpu);
+        summary.peak_cpu_temperature = max_option(summary.peak_cpu_temperature, cpu_temperature);
+        summary.peak_gpu_temperature = max_option(summary.peak_gpu_temperature, gpu_temperature);
+        summary.watchdog_alerts = summary.watchdog_alerts.max(watchdog_alerts);
+        summary.received = traffic.received;
+        summary.sent = traffic.sent;
+
+        let save_due = match self.daily_summaries.last_save {
+            Some(last) => last.elapsed() >= SAVE_INTERVAL,
+            None => true,
+        };
+        if save_due {
+            self.daily_summaries.save();
+        }
+    }
+
+    /// The previous day's summary, shown once on the next launch or at midnight
+    pub(crate) fn show_daily_summary_window(&mut self, ctx: &egui::Context) {
+        let Some(date) = self.daily_summaries.showing.clone() else { return };
+        let Some(summary) = self.daily_summaries.days.get(&date) else {
+            self.daily_summaries.showing = None;
+            return;
+        };
+        let mut open = true;
+        let mut dismiss = false;
+        let grey = Color32::from_rgb(156, 163, 175);
+        egui::Window::new(format!("Summary for {}", date))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("daily_summary").num_columns(2).spacing([16.0, 4.0]).show(ui, |ui| {
+                    for (label, value) in summary_rows(summary) {
+                        ui.label(RichText::new(label).color(grey));
+                        ui.label(value);
+                        ui.end_row();
+                    }
+                });
+                if !summary.alerts.is_empty() {
+                    ui.add_space(8.0);
+                    for alert in &summary.alerts {
+                        ui.label(RichText::new(alert).color(Color32::from_rgb(202, 138, 4)));
+                    }
+                }
+                ui.add_space(8.0);
+                ui.label(RichText::new("From the hours the dashboard was running").small().color(grey));
+                dismiss = ui.button("Close").clicked();
+            });
+        if dismiss || !open {
+            if let Some(summary) = self.daily_summaries.days.get_mut(&date) {
+                summary.seen = true;
+            }
+            self.daily_summaries.showing = None;
+            self.daily_summaries.save();
+        }
+    }
+}
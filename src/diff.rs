@@ -0,0 +1,265 @@
+use eframe::egui;
+use egui::{Color32, RichText};
+use similar::{capture_diff_slices, Algorithm, DiffOp};
+use crate::DevDashboard;
+
+const ADDED: Color32 = Color32::from_rgb(20, 83, 45);
+const REMOVED: Color32 = Color32::from_rgb(127, 29, 29);
+/// Unchanged lines kept around each change when collapsing
+const CONTEXT_LINES: usize = 3;
+
+#[derive(Clone, Copy, PartialEq)]
+enum DiffMode {
+    Inline,
+    SideBySide,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum WhitespaceMode {
+    Exact,
+    IgnoreTrailing,   // Leading and trailing whitespace
+    IgnoreAll,
+}
+
+/// Inputs and options of the Text Diff tool
+pub struct TextDiff {
+    left: String,
+    right: String,
+    mode: DiffMode,
+    whitespace: WhitespaceMode,
+    ignore_case: bool,
+    collapse_unchanged: bool,
+}
+
+impl Default for TextDiff {
+    fn default() -> Self {
+        Self {
+            left: String::new(),
+            right: String::new(),
+            mode: DiffMode::SideBySide,
+            whitespace: WhitespaceMode::IgnoreTrailing,
+            ignore_case: false,
+            collapse_unchanged: true,
+        }
+    }
+}
+
+/// One output line; either side is missing for pure insertions and deletions
+struct DiffRow<'a> {
+    left: Option<(usize, &'a str)>,
+    right: Option<(usize, &'a str)>,
+    changed: bool,
+}
+
+enum DisplayRow<'a> {
+    Line(DiffRow<'a>),
+    Skipped(usize),
+}
+
+impl TextDiff {
+    fn normalize(&self, line: &str) -> String {
+        let line = match self.whitespace {
+            WhitespaceMode::Exact => line.to_string(),
+            WhitespaceMode::IgnoreTrailing => line.trim().to_string(),
+            WhitespaceMode::IgnoreAll => line.chars().filter(|c| !c.is_whitespace()).collect(),
+        };
+        if self.ignore_case { line.to_lowercase() } else { line }
+    }
+
+    /// Diffs normalized lines but returns the original text for display
+    fn rows(&self) -> Vec<DiffRow<'_>> {
+        let left: Vec<&str> = self.left.lines().collect();
+        let right: Vec<&str> = self.right.lines().collect();
+        let left_keys: Vec<String> = left.iter().map(|line| self.normalize(line)).collect();
+        let right_keys: Vec<String> = right.iter().map(|line| self.normalize(line)).collect();
+
+        let mut rows = Vec::new();
+        for op in capture_diff_slices(Algorithm::Myers, &left_keys, &right_keys) {
+            match op {
+                DiffOp::Equal { old_index, new_index, len } => {
+                    for offset in 0..len {
+                        rows.push(DiffRow {
+                            left: Some((old_index + offset, left[old_index + offset])),
+                            right: Some((new_index + offset, right[new_index + offset])),
+                            changed: false,
+                        });
+                    }
+                }
+                DiffOp::Delete { old_index, old_len, .. } => {
+                    for (index, line) in left.iter().enumerate().skip(old_index).take(old_len) {
+                        rows.push(DiffRow { left: Some((index, line)), right: None, changed: true });
+                    }
+                }
+                DiffOp::Insert { new_index, new_len, .. } => {
+                    for (index, line) in right.iter().enumerate().skip(new_index).take(new_len) {
+                        rows.push(DiffRow { left: None, right: Some((index, line)), changed: true });
+                    }
+                }
+                DiffOp::Replace { old_index, old_len, new_index, new_len } => {
+                    // Pair replaced lines up so side-by-side shows them next to each other
+                    for offset in 0..old_len.max(new_len) {
+                        rows.push(DiffRow {
+                            left: (offset < old_len).then(|| (old_index + offset, left[old_index + offset])),
+                            right: (offset < new_len).then(|| (new_index + offset, right[new_index + offset])),
+                            changed: true,
+                        });
+                    }
+                }
+            }
+        }
+        rows
+    }
+}
+
+/// Replaces long runs of unchanged rows with a "n unchanged lines" marker
+fn collapse(rows: Vec<DiffRow<'_>>) -> Vec<DisplayRow<'_>> {
+    let near_change: Vec<bool> = (0..rows.len())
+        .map(|index| {
+            let start = index.saturating_sub(CONTEXT_LINES);
+            let end = (index + CONTEXT_LINES + 1).min(rows.len());
+            rows[start..end].iter().any(|row| row.changed)
+        })
+        .collect();
+
+    let mut display = Vec::new();
+    let mut skipped = 0;
+    for (row, keep) in rows.into_iter().zip(near_change) {
+        if keep {
+            if skipped > 0 {
+                display.push(DisplayRow::Skipped(skipped));
+                skipped = 0;
+            }
+            display.push(DisplayRow::Line(row));
+        } else {
+            skipped += 1;
+        }
+    }
+    if skipped > 0 {
+        display.push(DisplayRow::Skipped(skipped));
+    }
+    display
+}
+
+/// A line number and text on an optional background
+fn diff_line(ui: &mut egui::Ui, line: Option<(usize, &str)>, prefix: &str, background: Option<Color32>, width: f32) {
+    let text = match line {
+        Some((index, text)) => format!("{:>4} {}{}", index + 1, prefix, text),
+        None => String::new(),
+    };
+    let mut label = RichText::new(text).monospace();
+    if let Some(color) = background {
+        label = label.background_color(color);
+    }
+    ui.add_sized([width, 14.0], egui::Label::new(label).truncate(true));
+}
+
+impl DevDashboard {
+    /// Text diff tool page
+    /// Compares two pasted texts line by line
+    pub(crate) fn show_diff_tool(&mut self, ui: &mut egui::Ui) {
+        let diff = &mut self.text_diff;
+
+        ui.heading("Text Diff");
+        ui.add_space(8.0);
+
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut diff.mode, DiffMode::SideBySide, "Side by side");
+            ui.selectable_value(&mut diff.mode, DiffMode::Inline, "Inline");
+            ui.separator();
+            egui::ComboBox::from_id_source("diff_whitespace")
+                .selected_text(match diff.whitespace {
+                    WhitespaceMode::Exact => "Compare whitespace",
+                    WhitespaceMode::IgnoreTrailing => "Ignore leading/trailing whitespace",
+                    WhitespaceMode::IgnoreAll => "Ignore all whitespace",
+                })
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut diff.whitespace, WhitespaceMode::Exact, "Compare whitespace");
+                    ui.selectable_value(&mut diff.whitespace, WhitespaceMode::IgnoreTrailing, "Ignore leading/trailing whitespace");
+                    ui.selectable_value(&mut diff.whitespace, WhitespaceMode::IgnoreAll, "Ignore all whitespace");
+                });
+            ui.checkbox(&mut diff.ignore_case, "Ignore case");
+            ui.checkbox(&mut diff.collapse_unchanged, "Collapse unchanged");
+            if ui.button("⇄ Swap").clicked() {
+                std::mem::swap(&mut diff.left, &mut diff.right);
+            }
+        });
+        ui.add_space(4.0);
+
+        let half = (ui.available_width() - ui.spacing().item_spacing.x) / 2.0;
+        ui.horizontal(|ui| {
+            for (text, hint) in [(&mut diff.left, "Original"), (&mut diff.right, "Changed")] {
+                egui::ScrollArea::vertical()
+                    .id_source(hint)
+                    .max_height(160.0)
+                    .show(ui, |ui| {
+                        ui.add(egui::TextEdit::multiline(text)
+                            .hint_text(hint)
+                            .font(egui::TextStyle::Monospace)
+                            .desired_rows(8)
+                            .desired_width(half - 16.0));
+                    });
+            }
+        });
+        ui.add_space(8.0);
+
+        if diff.left.is_empty() && diff.right.is_empty() {
+            return;
+        }
+
+        let rows = diff.rows();
+        let added = rows.iter().filter(|row| row.changed && row.right.is_some()).count();
+        let removed = rows.iter().filter(|row| row.changed && row.left.is_some()).count();
+        if added == 0 && removed == 0 {
+            ui.colored_label(Color32::from_rgb(22, 163, 74), "No differences");
+            return;
+        }
+        ui.horizontal(|ui| {
+            ui.colored_label(Color32::from_rgb(22, 163, 74), format!("+{}", added));
+            ui.colored_label(Color32::from_rgb(220, 50, 50), format!("−{}", removed));
+        });
+
+        let mode = diff.mode;
+        let display: Vec<DisplayRow> = if diff.collapse_unchanged {
+            collapse(rows)
+        } else {
+            rows.into_iter().map(DisplayRow::Line).collect()
+        };
+
+        egui::ScrollArea::both().id_source("diff_output").auto_shrink([false, false]).show(ui, |ui| {
+            ui.spacing_mut().item_spacing.y = 0.0;
+            let full = ui.available_width();
+            for row in display {
+                let row = match row {
+                    DisplayRow::Line(row) => row,
+                    DisplayRow::Skipped(count) => {
+                        ui.label(RichText::new(format!("     ⋯ {} unchanged lines", count))
+                            .monospace()
+                            .color(Color32::from_rgb(107, 114, 128)));
+                        continue;
+                    }
+                };
+                match mode {
+                    DiffMode::SideBySide => {
+                        ui.horizontal(|ui| {
+                            let changed = |line: Option<(usize, &str)>, color| (row.changed && line.is_some()).then_some(color);
+                            diff_line(ui, row.left, "", changed(row.left, REMOVED), half);
+                            diff_line(ui, row.right, "", changed(row.right, ADDED), half);
+                        });
+                    }
+                    DiffMode::Inline => {
+                        if !row.changed {
+                            diff_line(ui, row.right, "  ", None, full);
+                            continue;
+                        }
+                        if row.left.is_some() {
+                            diff_line(ui, row.left, "- ", Some(REMOVED), full);
+                        }
+                        if row.right.is_some() {
+                            diff_line(ui, row.right, "+ ", Some(ADDED), full);
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,143 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+use chrono::{Local, TimeZone};
+use eframe::egui;
+use egui::Color32;
+use serde::{Serialize, Deserialize};
+use crate::card_export::CardHistory;
+use crate::{storage, DevDashboard};
+
+/// Free-space samples are stored next to settings.json
+const STORAGE_FILE: &str = "storage.json";
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// Samples older than this are dropped
+const RETENTION_SECS: i64 = 30 * 24 * 60 * 60;
+/// The growth rate is fitted over this recent window so old cleanups don't skew it
+const FORECAST_WINDOW_SECS: i64 = 14 * 24 * 60 * 60;
+/// History needed before a forecast is shown
+const MIN_SPAN_SECS: i64 = 24 * 60 * 60;
+
+/// Free bytes on one drive at a Unix timestamp
+#[derive(Serialize, Deserialize, Clone, Copy)]
+struct FreeSpaceSample {
+    time: i64,
+    free: u64,
+}
+
+/// Hourly free space per mount point, persisted across restarts
+#[derive(Default)]
+pub struct StorageHistory {
+    drives: BTreeMap<String, Vec<FreeSpaceSample>>,
+    last_sample: Option<Instant>,
+}
+
+pub enum Forecast {
+    Collecting,                                 // Less than a day of history
+    Steady,                                     // Free space is not shrinking
+    Full { days: f64, bytes_per_day: f64 },
+}
+
+impl Forecast {
+    /// Label and color for the storage card
+    pub fn describe(&self) -> (String, Color32) {
+        match self {
+            Forecast::Collecting => ("Forecast available after a day of history".to_string(), Color32::from_rgb(107, 114, 128)),
+            Forecast::Steady => ("Free space steady".to_string(), Color32::from_rgb(156, 163, 175)),
+            Forecast::Full { days, bytes_per_day } => {
+                let (rate, unit) = DevDashboard::format_bytes(*bytes_per_day as u64);
+                let when = if *days < 1.0 {
+                    "less than a day".to_string()
+                } else if *days < 60.0 {
+                    format!("~{:.0} days", days)
+                } else if *days < 730.0 {
+                    format!("~{:.0} months", days / 30.0)
+                } else {
+                    format!("~{:.0} years", days / 365.0)
+                };
+                let color = if *days < 7.0 {
+                    Color32::from_rgb(220, 50, 50)
+                } else if *days < 30.0 {
+                    Color32::from_rgb(202, 138, 4)
+                } else {
+                    Color32::from_rgb(156, 163, 175)
+                };
+                (format!("Full in {} (-{:.1} {}/day)", when, rate, unit), color)
+            }
+        }
+    }
+}
+
+impl StorageHistory {
+    pub fn load() -> Self {
+        let drives = storage::load_json(STORAGE_FILE);
+        Self { drives, last_sample: None }
+    }
+
+    fn save(&self) {
+        storage::save_json(STORAGE_FILE, &self.drives);
+    }
+
+    /// Projects when the drive runs out of space from a least-squares fit of recent free space
+    pub fn forecast(&self, mount_point: &str) -> Forecast {
+        let Some(samples) = self.drives.get(mount_point) else { return Forecast::Collecting };
+        let now = Local::now().timestamp();
+        let recent: Vec<&FreeSpaceSample> = samples.iter().filter(|sample| now - sample.time <= FORECAST_WINDOW_SECS).collect();
+        let (Some(first), Some(last)) = (recent.first(), recent.last()) else { return Forecast::Collecting };
+        if last.time - first.time < MIN_SPAN_SECS {
+            return Forecast::Collecting;
+        }
+
+        let count = recent.len() as f64;
+        let mean_time = recent.iter().map(|sample| (sample.time - first.time) as f64).sum::<f64>() / count;
+        let mean_free = recent.iter().map(|sample| sample.free as f64).sum::<f64>() / count;
+        let (mut covariance, mut variance) = (0.0, 0.0);
+        for sample in &recent {
+            let time = (sample.time - first.time) as f64 - mean_time;
+            covariance += time * (sample.free as f64 - mean_free);
+            variance += time * time;
+        }
+        if variance == 0.0 {
+            return Forecast::Collecting;
+        }
+        let bytes_per_day = -(covariance / variance) * 86_400.0;
+        if bytes_per_day <= 0.0 {
+            return Forecast::Steady;
+        }
+        Forecast::Full { days: last.free as f64 / bytes_per_day, bytes_per_day }
+    }
+
+    /// Hourly free space of every drive, grouped by drive
+    pub fn export_history(&self) -> CardHistory {
+        let mut history = CardHistory::new(vec!["time", "drive", "free_bytes"]);
+        for (mount_point, samples) in &self.drives {
+            for sample in samples {
+                let time = Local.timestamp_opt(sample.time, 0)
+                    .single()
+                    .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+                    .unwrap_or_default();
+                history.rows.push(vec![time, mount_point.clone(), sample.free.to_string()]);
+            }
+        }
+        history
+    }
+}
+
+impl DevDashboard {
+    /// Records free space per drive once an hour
+    pub(crate) fn update_storage_history(&mut self) {
+        if self.storage_history.last_sample.is_some_and(|last| last.elapsed() < SAMPLE_INTERVAL) {
+            return;
+        }
+        self.storage_history.last_sample = Some(Instant::now());
+
+        let now = Local::now().timestamp();
+        for volume in self.volume_monitor.volumes() {
+            // Stale readings from offline volumes would flatten the trend
+            let Some((_, free)) = volume.space.filter(|_| volume.online) else { continue };
+            let samples = self.storage_history.drives.entry(volume.mount.clone()).or_default();
+            samples.push(FreeSpaceSample { time: now, free });
+            samples.retain(|sample| now - sample.time <= RETENTION_SECS);
+        }
+        self.storage_history.save();
+    }
+}
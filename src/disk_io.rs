@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::time::Duration;
+use eframe::egui;
+use egui::Color32;
+use serde::Deserialize;
+use wmi::{COMLibrary, WMIConnection};
+use log::{debug, warn};
+use crate::sensor_recovery::BackendHealth;
+
+/// How often the sensor thread reads the disk counters
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+/// Average response times above these are shown as slow
+const SLOW_LATENCY_MS: f32 = 20.0;
+const VERY_SLOW_LATENCY_MS: f32 = 50.0;
+
+/// Raw counters; the formatted class rounds latencies to whole seconds, so deltas are computed here
+#[derive(Deserialize, Clone)]
+#[serde(rename = "Win32_PerfRawData_PerfDisk_LogicalDisk")]
+#[serde(rename_all = "PascalCase")]
+struct LogicalDiskCounters {
+    name: String,
+    avg_disk_sec_per_read: u32,
+    avg_disk_sec_per_read_base: u32,
+    avg_disk_sec_per_write: u32,
+    avg_disk_sec_per_write_base: u32,
+    avg_disk_queue_length: u64,
+    current_disk_queue_length: u32,
+    percent_idle_time: u64,
+    #[serde(rename = "Timestamp_Sys100NS")]
+    timestamp_sys_100ns: u64,
+    frequency_perf_time: u64,
+}
+
+/// Activity of one volume over the last sample interval
+#[derive(Clone, Copy)]
+pub struct DiskIoStats {
+    pub read_latency_ms: Option<f32>,   // None when nothing was read
+    pub write_latency_ms: Option<f32>,
+    pub queue_length: f32,              // Average outstanding requests
+    pub current_queue: u32,
+    pub busy_percent: f32,
+}
+
+impl DiskIoStats {
+    fn worst_latency(&self) -> f32 {
+        self.read_latency_ms.unwrap_or(0.0).max(self.write_latency_ms.unwrap_or(0.0))
+    }
+
+    pub fn color(&self) -> Color32 {
+        let latency = self.worst_latency();
+        if latency >= VERY_SLOW_LATENCY_MS {
+            Color32::from_rgb(220, 50, 50)
+        } else if latency >= SLOW_LATENCY_MS {
+            Color32::from_rgb(202, 138, 4)
+        } else {
+            Color32::from_rgb(156, 163, 175)
+        }
+    }
+
+    /// One-line summary for the storage card
+    pub fn summary(&self) -> String {
+        let latency = |value: Option<f32>| match value {
+            Some(ms) => format!("{:.1} ms", ms),
+            None => "idle".to_string(),
+        };
+        format!(
+            "Busy {:.0}% · Read {} · Write {} · Queue {:.2}",
+            self.busy_percent,
+            latency(self.read_latency_ms),
+            latency(self.write_latency_ms),
+            self.queue_length
+        )
+    }
+}
+
+/// Average time per operation between two samples of a PERF_AVERAGE_TIMER counter
+fn average_latency_ms(counter: u32, previous_counter: u32, base: u32, previous_base: u32, frequency: u64) -> Option<f32> {
+    let operations = base.wrapping_sub(previous_base);
+    if operations == 0 || frequency == 0 {
+        return None;
+    }
+    let ticks = counter.wrapping_sub(previous_counter) as f64;
+    Some((ticks / frequency as f64 / operations as f64 * 1000.0) as f32)
+}
+
+fn compute_stats(current: &LogicalDiskCounters, previous: &LogicalDiskCounters) -> Option<DiskIoStats> {
+    let elapsed = current.timestamp_sys_100ns.checked_sub(previous.timestamp_sys_100ns)?;
+    if elapsed == 0 {
+        return None;
+    }
+    let idle = current.percent_idle_time.saturating_sub(previous.percent_idle_time) as f64 / elapsed as f64;
+    Some(DiskIoStats {
+        read_latency_ms: average_latency_ms(
+            current.avg_disk_sec_per_read, previous.avg_disk_sec_per_read,
+            current.avg_disk_sec_per_read_base, previous.avg_disk_sec_per_read_base,
+            current.frequency_perf_time,
+        ),
+        write_latency_ms: average_latency_ms(
+            current.avg_disk_sec_per_write, previous.avg_disk_sec_per_write,
+            current.avg_disk_sec_per_write_base, previous.avg_disk_sec_per_write_base,
+            current.frequency_perf_time,
+        ),
+        queue_length: (current.avg_disk_queue_length.saturating_sub(previous.avg_disk_queue_length) as f64 / elapsed as f64) as f32,
+        current_queue: current.current_disk_queue_length,
+        busy_percent: ((1.0 - idle) * 100.0).clamp(0.0, 100.0) as f32,
+    })
+}
+
+/// Samples the logical disk counters until the receiver is dropped
+fn run_sensor_thread(sender: Sender<HashMap<String, DiskIoStats>>) {
+    let Ok(com) = COMLibrary::new() else {
+        warn!("Could not initialize COM for disk counters");
+        return;
+    };
+    let Ok(wmi) = WMIConnection::new(com) else {
+        warn!("Could not connect to WMI for disk counters");
+        return;
+    };
+
+    let mut previous: HashMap<String, LogicalDiskCounters> = HashMap::new();
+    loop {
+        match wmi.query::<LogicalDiskCounters>() {
+            Ok(samples) => {
+                let mut stats = HashMap::new();
+                for sample in samples {
+                    // Skip _Total and volumes without a drive letter
+                    if !sample.name.ends_with(':') {
+                        continue;
+                    }
+                    if let Some(last) = previous.get(&sample.name) {
+                        if let Some(computed) = compute_stats(&sample, last) {
+                            stats.insert(sample.name.clone(), computed);
+                        }
+                    }
+                    previous.insert(sample.name.clone(), sample);
+                }
+                if sender.send(stats).is_err() {
+                    break;
+                }
+            }
+            Err(e) => debug!("Disk counter query failed: {}", e),
+        }
+        std::thread::sleep(SAMPLE_INTERVAL);
+    }
+}
+
+/// Latency, queue depth and busy time per drive letter
+#[derive(Default)]
+pub struct DiskIoMonitor {
+    receiver: Option<Receiver<HashMap<String, DiskIoStats>>>,
+    stats: HashMap<String, DiskIoStats>,
+    pub health: BackendHealth,
+}
+
+impl DiskIoMonitor {
+    pub fn update(&mut self) {
+        match &self.receiver {
+            Some(receiver) => loop {
+                match receiver.try_recv() {
+                    Ok(stats) => {
+                        self.stats = stats;
+                        self.health.succeeded("Disk counters");
+                    }
+                    Err(TryRecvError::Empty) => break,
+                    // The thread gives up when WMI is unavailable; it is started again after a while
+                    Err(TryRecvError::Disconnected) => {
+                        self.receiver = None;
+                        self.stats.clear();
+                        self.health.failed("Disk counters", "Could not connect to WMI".to_string());
+                        break;
+                    }
+                }
+            },
+            None if !self.health.retry_due() => {}
+            None => {
+                debug!("Starting disk counter thread");
+                let (sender, receiver) = channel();
+                self.receiver = Some(receiver);
+                std::thread::spawn(move || run_sensor_thread(sender));
+            }
+        }
+    }
+
+    /// Stats for a mount point such as `C:\`
+    pub fn get(&self, mount_point: &str) -> Option<&DiskIoStats> {
+        self.stats.get(mount_point.trim_end_matches('\\'))
+    }
+}
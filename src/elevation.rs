@@ -0,0 +1,299 @@
+use std::net::IpAddr;
+use std::os::windows::io::AsRawHandle;
+use std::os::windows::process::CommandExt;
+use std::process::Command;
+use std::sync::Arc;
+use std::time::Duration;
+use serde::{Serialize, Deserialize};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::windows::named_pipe::{ClientOptions, NamedPipeServer, ServerOptions};
+use tokio::sync::Mutex;
+use windows::core::{HSTRING, PCWSTR};
+use windows::Win32::Foundation::{BOOL, HANDLE, HLOCAL, HWND};
+use windows::Win32::Security::Authorization::{ConvertStringSecurityDescriptorToSecurityDescriptorW, SDDL_REVISION_1};
+use windows::Win32::Security::{PSECURITY_DESCRIPTOR, SECURITY_ATTRIBUTES};
+use windows::Win32::System::Memory::LocalFree;
+use windows::Win32::System::Pipes::GetNamedPipeServerProcessId;
+use windows::Win32::UI::Shell::{IsUserAnAdmin, ShellExecuteW};
+use windows::Win32::UI::WindowsAndMessaging::SW_HIDE;
+use log::{debug, info, warn};
+use crate::install_audit::MACHINE_UNINSTALL_KEYS;
+use crate::CREATE_NO_WINDOW;
+
+/// First argument that starts this executable as the elevated helper instead of the dashboard
+pub const HELPER_FLAG: &str = "--elevated-helper";
+/// Followed by the dashboard's process id and a random suffix
+const PIPE_PREFIX: &str = r"\\.\pipe\dev_dashboard_elevation_";
+/// Full access for SYSTEM and elevated administrators only, so unelevated processes can't open the pipe
+const PIPE_SDDL: &str = "D:P(A;;GA;;;SY)(A;;GA;;;BA)";
+/// The UAC prompt waits for the user, so the helper may take a while to connect
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(120);
+/// Services such as Docker can take a while to stop
+const ACTION_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Operations that need administrator rights; the helper runs nothing else
+#[derive(Serialize, Deserialize, Debug)]
+pub enum ElevatedAction {
+    StartService(String),
+    StopService(String),    // Also stops services that depend on it
+    AddWingetSource { name: String, url: String },  // A REST source such as an internal package repository
+    DeleteUninstallEntry(String),   // A leftover key below HKLM's Uninstall keys
+    AddPortProxy { listen_address: String, listen_port: u16, connect_address: String, connect_port: u16 },
+    DeletePortProxy { listen_address: String, listen_port: u16 },
+}
+
+/// Service names are passed to `net`, so nothing that could read as a switch or a path
+fn is_service_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 256
+        && !name.starts_with(['-', '/'])
+        && !name.chars().any(|c| c.is_control() || matches!(c, '/' | '\\' | '"'))
+}
+
+/// Winget source names are single words; only https REST sources are registered
+fn is_winget_source(name: &str, url: &str) -> bool {
+    let name_ok = !name.is_empty()
+        && !name.starts_with('-')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    let url_ok = reqwest::Url::parse(url).is_ok_and(|url| url.scheme() == "https" && url.host_str().is_some());
+    name_ok && url_ok
+}
+
+/// Port proxy addresses are an IP address or a host name
+fn is_proxy_address(address: &str) -> bool {
+    address.parse::<IpAddr>().is_ok()
+        || (!address.is_empty()
+            && !address.starts_with('-')
+            && address.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.')))
+}
+
+impl ElevatedAction {
+    /// Rejects arguments that could make a command do more than the action describes
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            ElevatedAction::StartService(name) | ElevatedAction::StopService(name) if !is_service_name(name) =>
+                Err(format!("Not a service name: {}", name)),
+            ElevatedAction::AddWingetSource { name, url } if !is_winget_source(name, url) =>
+                Err(format!("Not an https winget source: {} at {}", name, url)),
+            ElevatedAction::AddPortProxy { listen_address, connect_address, .. }
+                if !is_proxy_address(listen_address) || !is_proxy_address(connect_address) =>
+                Err(format!("Not a port proxy address: {} or {}", listen_address, connect_address)),
+            ElevatedAction::DeletePortProxy { listen_address, .. } if !is_proxy_address(listen_address) =>
+                Err(format!("Not a port proxy address: {}", listen_address)),
+            _ => Ok(()),
+        }
+    }
+
+    fn execute(&self) -> Result<(), String> {
+        self.validate()?;
+        let hklm_key;
+        let netsh_args: Vec<String>;
+        let (program, args) = match self {
+            ElevatedAction::StartService(name) => ("net", vec!["start", name.as_str()]),
+            // /y stops dependent services instead of waiting for a confirmation
+            ElevatedAction::StopService(name) => ("net", vec!["stop", name.as_str(), "/y"]),
+            ElevatedAction::AddWingetSource { name, url } => ("winget", vec![
+                "source", "add", "--name", name.as_str(), "--arg", url.as_str(), "--type", "Microsoft.Rest",
+                "--accept-source-agreements", "--disable-interactivity",
+            ]),
+            ElevatedAction::DeleteUninstallEntry(key) => {
+                // Only a direct child of an Uninstall key, so the helper can't be asked to delete anything else
+                let is_entry = key.rsplit_once('\\').is_some_and(|(parent, name)| {
+                    !name.is_empty() && MACHINE_UNINSTALL_KEYS.iter().any(|uninstall| parent.eq_ignore_ascii_case(uninstall))
+                });
+                if !is_entry {
+                    return Err(format!("Not an uninstall entry: {}", key));
+                }
+                hklm_key = format!(r"HKLM\{}", key);
+                ("reg", vec!["delete", hklm_key.as_str(), "/f"])
+            }
+            ElevatedAction::AddPortProxy { listen_address, listen_port, connect_address, connect_port } => {
+                netsh_args = vec![
+                    format!("listenaddress={}", listen_address), format!("listenport={}", listen_port),
+                    format!("connectaddress={}", connect_address), format!("connectport={}", connect_port),
+                ];
+                let mut args = vec!["interface", "portproxy", "add", "v4tov4"];
+                args.extend(netsh_args.iter().map(String::as_str));
+                ("netsh", args)
+            }
+            ElevatedAction::DeletePortProxy { listen_address, listen_port } => {
+                netsh_args = vec![format!("listenaddress={}", listen_address), format!("listenport={}", listen_port)];
+                let mut args = vec!["interface", "portproxy", "delete", "v4tov4"];
+                args.extend(netsh_args.iter().map(String::as_str));
+                ("netsh", args)
+            }
+        };
+        let output = Command::new(program)
+            .args(&args)
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+            .map_err(|e| format!("Could not start {}: {}", program, e))?;
+        if output.status.success() {
+            return Ok(());
+        }
+        let text = format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+        let line = text.lines().map(str::trim).find(|line| !line.is_empty()).unwrap_or("No output");
+        Err(format!("Exit code {}: {}", output.status.code().unwrap_or(-1), line))
+    }
+}
+
+pub fn is_elevated() -> bool {
+    unsafe { IsUserAnAdmin().as_bool() }
+}
+
+/// Runs privileged actions, through an elevated copy of this executable when the dashboard itself is not elevated.
+/// The helper is started on first use, which shows one UAC prompt per session, and exits with the dashboard.
+#[derive(Clone, Default)]
+pub struct ElevationBroker {
+    helper: Arc<Mutex<Option<BufReader<NamedPipeServer>>>>,
+}
+
+/// Starts the helper through the "runas" verb and waits for it to connect and present its token
+async fn launch_helper() -> Result<BufReader<NamedPipeServer>, String> {
+    let pipe_name = format!("{}{}_{:016x}", PIPE_PREFIX, std::process::id(), rand::random::<u64>());
+    let token = format!("{:032x}", rand::random::<u128>());
+    let server = create_pipe(&pipe_name).map_err(|e| format!("Could not create the helper pipe: {}", e))?;
+
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let parameters = format!("{} {} {}", HELPER_FLAG, pipe_name, token);
+    info!("Requesting administrator rights for the elevated helper");
+    let launched = tokio::task::spawn_blocking(move || {
+        let result = unsafe {
+            ShellExecuteW(HWND(0), &HSTRING::from("runas"), &HSTRING::from(exe.as_os_str()),
+                &HSTRING::from(parameters), PCWSTR::null(), SW_HIDE)
+        };
+        // Values above 32 mean the process started; declining the prompt returns an error code
+        result.0 > 32
+    }).await.map_err(|e| e.to_string())?;
+    if !launched {
+        return Err("Administrator rights were declined".to_string());
+    }
+
+    tokio::time::timeout(CONNECT_TIMEOUT, server.connect())
+        .await
+        .map_err(|_| "The elevated helper did not connect".to_string())?
+        .map_err(|e| e.to_string())?;
+    let mut pipe = BufReader::new(server);
+    let mut hello = String::new();
+    tokio::time::timeout(CONNECT_TIMEOUT, pipe.read_line(&mut hello))
+        .await
+        .map_err(|_| "The elevated helper did not identify itself".to_string())?
+        .map_err(|e| e.to_string())?;
+    if hello.trim() != token {
+        return Err("The elevated helper presented the wrong token".to_string());
+    }
+    debug!("Elevated helper connected on {}", pipe_name);
+    Ok(pipe)
+}
+
+/// Creates the pipe with an explicit DACL instead of the default one, which lets everyone read.
+/// The first instance flag fails if another process already owns the name.
+fn create_pipe(pipe_name: &str) -> std::io::Result<NamedPipeServer> {
+    let mut descriptor = PSECURITY_DESCRIPTOR::default();
+    let converted = unsafe {
+        ConvertStringSecurityDescriptorToSecurityDescriptorW(&HSTRING::from(PIPE_SDDL), SDDL_REVISION_1, &mut descriptor, None)
+    };
+    if !converted.as_bool() {
+        return Err(std::io::Error::last_os_error());
+    }
+    let mut attributes = SECURITY_ATTRIBUTES {
+        nLength: std::mem::size_of::<SECURITY_ATTRIBUTES>() as u32,
+        lpSecurityDescriptor: descriptor.0,
+        bInheritHandle: BOOL(0),
+    };
+    let server = unsafe {
+        ServerOptions::new()
+            .first_pipe_instance(true)
+            .reject_remote_clients(true)
+            .create_with_security_attributes_raw(pipe_name, &mut attributes as *mut SECURITY_ATTRIBUTES as *mut std::ffi::c_void)
+    };
+    unsafe { LocalFree(HLOCAL(descriptor.0 as isize)).ok() };
+    server
+}
+
+/// Whether the pipe is served by the dashboard whose process id is in its name, not one squatting on the name
+fn is_dashboard_pipe(pipe: &impl AsRawHandle, pipe_name: &str) -> bool {
+    let expected = pipe_name.strip_prefix(PIPE_PREFIX)
+        .and_then(|rest| rest.split('_').next())
+        .and_then(|pid| pid.parse::<u32>().ok());
+    let mut server_pid = 0u32;
+    let known = unsafe { GetNamedPipeServerProcessId(HANDLE(pipe.as_raw_handle() as isize), &mut server_pid) }.as_bool();
+    known && expected == Some(server_pid)
+}
+
+/// Sends one action and waits for its result; an I/O error means the helper is gone
+async fn send(pipe: &mut BufReader<NamedPipeServer>, action: &ElevatedAction) -> std::io::Result<Result<(), String>> {
+    let request = serde_json::to_string(action).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    pipe.write_all(format!("{}\n", request).as_bytes()).await?;
+    pipe.flush().await?;
+    let mut reply = String::new();
+    if pipe.read_line(&mut reply).await? == 0 {
+        return Err(std::io::ErrorKind::UnexpectedEof.into());
+    }
+    serde_json::from_str(&reply).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+impl ElevationBroker {
+    pub async fn run(&self, action: ElevatedAction) -> Result<(), String> {
+        if is_elevated() {
+            return tokio::task::spawn_blocking(move || action.execute()).await.map_err(|e| e.to_string())?;
+        }
+
+        let mut helper = self.helper.lock().await;
+        // A helper that was closed is relaunched once
+        for _ in 0..2 {
+            let pipe = match helper.as_mut() {
+                Some(pipe) => pipe,
+                None => helper.insert(launch_helper().await?),
+            };
+            match tokio::time::timeout(ACTION_TIMEOUT, send(pipe, &action)).await {
+                Ok(Ok(result)) => return result,
+                Ok(Err(e)) => {
+                    warn!("Lost the elevated helper: {}", e);
+                    *helper = None;
+                }
+                Err(_) => {
+                    // The helper is still busy with the action, so its next reply would be out of step
+                    *helper = None;
+                    return Err(format!("Timed out after {}s", ACTION_TIMEOUT.as_secs()));
+                }
+            }
+        }
+        Err("The elevated helper keeps closing".to_string())
+    }
+}
+
+/// Entry point of the elevated helper: connects to the dashboard's pipe and runs actions until it closes
+pub fn run_helper(args: &[String]) {
+    let (Some(pipe_name), Some(token)) = (args.first(), args.get(1)) else { return };
+    let Ok(runtime) = tokio::runtime::Builder::new_current_thread().enable_all().build() else { return };
+    runtime.block_on(async {
+        let Ok(pipe) = ClientOptions::new().open(pipe_name) else { return };
+        if !is_dashboard_pipe(&pipe, pipe_name) {
+            warn!("Elevated helper pipe {} is not served by the dashboard that started it", pipe_name);
+            return;
+        }
+        let mut pipe = BufReader::new(pipe);
+        if pipe.write_all(format!("{}\n", token).as_bytes()).await.is_err() {
+            return;
+        }
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match pipe.read_line(&mut line).await {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {}
+            }
+            let result = match serde_json::from_str::<ElevatedAction>(&line) {
+                Ok(action) => tokio::task::spawn_blocking(move || action.execute())
+                    .await
+                    .unwrap_or_else(|e| Err(e.to_string())),
+                Err(e) => Err(format!("Unknown request: {}", e)),
+            };
+            let Ok(reply) = serde_json::to_string(&result) else { break };
+            if pipe.write_all(format!("{}\n", reply).as_bytes()).await.is_err() {
+                break;
+            }
+        }
+    });
+}
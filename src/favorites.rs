@@ -0,0 +1,248 @@
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+use std::net::Ipv4Addr;
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use windows::Win32::NetworkManagement::IpHelper::{
+    GetBestRoute, IcmpCloseHandle, IcmpCreateFile, IcmpSendEcho, ICMP_ECHO_REPLY, MIB_IPFORWARDROW,
+};
+use log::debug;
+use crate::DevDashboard;
+
+/// The strip is meant for a glance; more than this and the numbers stop being large
+pub const MAX_FAVORITES: usize = 5;
+const PING_INTERVAL: Duration = Duration::from_secs(5);
+const PING_TIMEOUT_MS: u32 = 1000;
+/// Any public address; the route to it goes through the default gateway
+const INTERNET_ADDRESS: Ipv4Addr = Ipv4Addr::new(1, 1, 1, 1);
+
+/// A reading that can be pinned to the top of the dashboard
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub enum FavoriteMetric {
+    CpuUsage,
+    MemoryUsage,
+    GpuUsage,
+    GpuTemperature,
+    DriveFree(String),              // Mount such as "C:\"
+    NetworkDown,
+    NetworkUp,
+    GatewayPing,
+}
+
+impl FavoriteMetric {
+    pub fn label(&self) -> String {
+        match self {
+            FavoriteMetric::CpuUsage => "CPU".to_string(),
+            FavoriteMetric::MemoryUsage => "Memory".to_string(),
+            FavoriteMetric::GpuUsage => "GPU".to_string(),
+            FavoriteMetric::GpuTemperature => "GPU temperature".to_string(),
+            FavoriteMetric::DriveFree(mount) => format!("{} free", mount.trim_end_matches('\\')),
+            FavoriteMetric::NetworkDown => "Network ↓".to_string(),
+            FavoriteMetric::NetworkUp => "Network ↑".to_string(),
+            FavoriteMetric::GatewayPing => "Ping to gateway".to_string(),
+        }
+    }
+}
+
+/// Metrics pinned to the strip, in display order
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct FavoriteSettings {
+    pub metrics: Vec<FavoriteMetric>,   // Empty hides the strip
+}
+
+/// Round trip to the default gateway, measured in the background while it is a favorite
+#[derive(Default)]
+pub struct GatewayPing {
+    result: Option<Result<(Ipv4Addr, u32), String>>,    // Gateway and milliseconds
+    receiver: Option<Receiver<Result<(Ipv4Addr, u32), String>>>,
+    last_ping: Option<Instant>,
+}
+
+/// The next hop towards the internet, which is the default gateway
+pub(crate) fn default_gateway() -> Result<Ipv4Addr, String> {
+    let mut route = MIB_IPFORWARDROW::default();
+    let destination = u32::from_ne_bytes(INTERNET_ADDRESS.octets());
+    let result = unsafe { GetBestRoute(destination, 0, &mut route) };
+    if result != 0 {
+        return Err("No network route".to_string());
+    }
+    let gateway = Ipv4Addr::from(route.dwForwardNextHop.to_ne_bytes());
+    if gateway.is_unspecified() {
+        return Err("No default gateway".to_string());
+    }
+    Ok(gateway)
+}
+
+/// One ICMP echo to the gateway, which needs no administrator rights unlike raw sockets
+fn ping_gateway() -> Result<(Ipv4Addr, u32), String> {
+    let gateway = default_gateway()?;
+    let handle = unsafe { IcmpCreateFile() }.map_err(|e| e.to_string())?;
+    let request = [0u8; 32];
+    // The reply holds the echoed data and room for an ICMP error after the header
+    let mut reply = vec![0u8; std::mem::size_of::<ICMP_ECHO_REPLY>() + request.len() + 8];
+    let replies = unsafe {
+        IcmpSendEcho(handle, u32::from_ne_bytes(gateway.octets()), request.as_ptr() as *const _, request.len() as u16,
+            None, reply.as_mut_ptr() as *mut _, reply.len() as u32, PING_TIMEOUT_MS)
+    };
+    unsafe { IcmpCloseHandle(handle) };
+    if replies == 0 {
+        return Err(format!("{} did not answer", gateway));
+    }
+    let reply = unsafe { &*(reply.as_ptr() as *const ICMP_ECHO_REPLY) };
+    if reply.Status != 0 {
+        return Err(format!("{} did not answer (status {})", gateway, reply.Status));
+    }
+    Ok((gateway, reply.RoundTripTime))
+}
+
+impl DevDashboard {
+    /// Pings the gateway every few seconds while it is on the strip
+    pub(crate) fn update_favorites(&mut self) {
+        let ping = &mut self.gateway_ping;
+        if let Some(receiver) = &ping.receiver {
+            if let Ok(result) = receiver.try_recv() {
+                if let Err(e) = &result {
+                    debug!("Gateway ping failed: {}", e);
+                }
+                ping.result = Some(result);
+                ping.receiver = None;
+            }
+        }
+        if !self.settings.favorites.metrics.contains(&FavoriteMetric::GatewayPing)
+            || ping.receiver.is_some()
+            || ping.last_ping.is_some_and(|time| time.elapsed() < PING_INTERVAL) {
+            return;
+        }
+        ping.last_ping = Some(Instant::now());
+        let (sender, receiver) = channel();
+        ping.receiver = Some(receiver);
+        std::thread::spawn(move || {
+            sender.send(ping_gateway()).ok();
+        });
+    }
+
+    /// Current text of a favorite, and a hover with more detail; None while there is no reading
+    fn favorite_value(&self, metric: &FavoriteMetric) -> Option<(String, String)> {
+        let history = &self.tray.history;
+        let percent = |value: Option<&f32>| value.map(|value| (format!("{:.0}%", value), String::new()));
+        match metric {
+            FavoriteMetric::CpuUsage => percent(history.cpu.back()),
+            FavoriteMetric::MemoryUsage => percent(history.memory.back()),
+            FavoriteMetric::GpuUsage => percent(history.gpu.back()),
+            FavoriteMetric::GpuTemperature => {
+                let gpu = self.gpu_info.as_ref()?;
+                gpu.temperature.map(|temperature| (format!("{}°C", temperature), gpu.name.clone()))
+            }
+            FavoriteMetric::DriveFree(mount) => {
+                let volume = self.volume_monitor.volumes().iter().find(|volume| volume.mount == *mount)?;
+                let (total, free) = volume.space?;
+                let (value, unit) = DevDashboard::format_bytes(free);
+                let (total_value, total_unit) = DevDashboard::format_bytes(total);
+                Some((format!("{:.0} {}", value, unit), format!("of {:.1} {}", total_value, total_unit)))
+            }
+            FavoriteMetric::NetworkDown => history.received.back().map(|speed| (Self::speed_text(*speed), "All interfaces".to_string())),
+            FavoriteMetric::NetworkUp => history.sent.back().map(|speed| (Self::speed_text(*speed), "All interfaces".to_string())),
+            FavoriteMetric::GatewayPing => match self.gateway_ping.result.as_ref()? {
+                Ok((gateway, milliseconds)) => Some((format!("{} ms", milliseconds), gateway.to_string())),
+                Err(e) => Some(("—".to_string(), e.clone())),
+            },
+        }
+    }
+
+    /// Large numbers for the pinned metrics, above the cards
+    pub(crate) fn show_favorites_strip(&self, ui: &mut egui::Ui) {
+        let metrics = &self.settings.favorites.metrics;
+        if metrics.is_empty() {
+            return;
+        }
+        let grey = Color32::from_rgb(156, 163, 175);
+        ui.add_space(16.0);
+        egui::Frame::none()
+            .fill(self.palette().card)
+            .inner_margin(egui::style::Margin::symmetric(16.0, 10.0))
+            .rounding(12.0)
+            .show(ui, |ui| {
+                ui.columns(metrics.len(), |columns| {
+                    for (column, metric) in columns.iter_mut().zip(metrics) {
+                        column.vertical_centered(|ui| {
+                            ui.label(RichText::new(metric.label()).color(grey));
+                            match self.favorite_value(metric) {
+                                Some((value, hover)) => {
+                                    let response = ui.label(RichText::new(value).size(28.0).strong());
+                                    if !hover.is_empty() {
+                                        response.on_hover_text(hover);
+                                    }
+                                }
+                                None => {
+                                    ui.label(RichText::new("—").size(28.0).color(grey));
+                                }
+                            }
+                        });
+                    }
+                });
+            });
+    }
+
+    pub(crate) fn show_favorite_settings(&mut self, ui: &mut egui::Ui) {
+        ui.label("Favorite Metrics:");
+        ui.label(RichText::new(format!("Shown as large numbers above the dashboard cards, up to {}", MAX_FAVORITES))
+            .color(Color32::from_rgb(107, 114, 128)));
+        let mut changed = false;
+        let mut removed = None;
+        let mut moved_up = None;
+        let metrics = &self.settings.favorites.metrics;
+        for (index, metric) in metrics.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(metric.label());
+                if ui.add_enabled(index > 0, egui::Button::new("⏶").small()).on_hover_text("Move left").clicked() {
+                    moved_up = Some(index);
+                }
+                if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                    removed = Some(index);
+                }
+            });
+        }
+
+        let mut candidates = vec![
+            FavoriteMetric::CpuUsage,
+            FavoriteMetric::MemoryUsage,
+            FavoriteMetric::GpuUsage,
+            FavoriteMetric::GpuTemperature,
+        ];
+        candidates.extend(self.volume_monitor.volumes().iter().map(|volume| FavoriteMetric::DriveFree(volume.mount.clone())));
+        candidates.extend([FavoriteMetric::NetworkDown, FavoriteMetric::NetworkUp, FavoriteMetric::GatewayPing]);
+        candidates.retain(|candidate| !metrics.contains(candidate));
+        let mut added = None;
+        ui.add_enabled_ui(metrics.len() < MAX_FAVORITES && !candidates.is_empty(), |ui| {
+            egui::ComboBox::from_id_source("favorite_add")
+                .selected_text("Add a metric")
+                .show_ui(ui, |ui| {
+                    for candidate in candidates {
+                        let label = candidate.label();
+                        if ui.selectable_label(false, label).clicked() {
+                            added = Some(candidate);
+                        }
+                    }
+                });
+        });
+
+        let metrics = &mut self.settings.favorites.metrics;
+        if let Some(index) = removed {
+            metrics.remove(index);
+            changed = true;
+        }
+        if let Some(index) = moved_up {
+            metrics.swap(index - 1, index);
+            changed = true;
+        }
+        if let Some(metric) = added {
+            metrics.push(metric);
+            changed = true;
+        }
+        if changed {
+            self.save_settings();
+        }
+    }
+}
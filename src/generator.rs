@@ -0,0 +1,242 @@
+use chrono::Utc;
+use eframe::egui;
+use egui::{Color32, RichText};
+use rand::rngs::OsRng;
+use rand::{Rng, RngCore};
+use crate::DevDashboard;
+
+/// Crockford's base32 alphabet used by ULIDs
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &str = "0123456789";
+const SYMBOLS: &str = "!#$%&()*+,-./:;<=>?@[]^_{|}~";
+/// Characters that are easy to mix up when read aloud or typed from a screen
+const AMBIGUOUS: &str = "0O1lI|";
+
+#[derive(Clone, Copy, PartialEq)]
+enum IdKind {
+    UuidV4,
+    UuidV7,
+    Ulid,
+    Secret,
+}
+
+/// Options and output of the Generator tool
+pub struct Generator {
+    kind: IdKind,
+    count: usize,
+    uppercase_uuid: bool,
+    length: usize,
+    lowercase: bool,
+    uppercase: bool,
+    digits: bool,
+    symbols: bool,
+    exclude_ambiguous: bool,
+    values: Vec<String>,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Self {
+            kind: IdKind::UuidV4,
+            count: 1,
+            uppercase_uuid: false,
+            length: 32,
+            lowercase: true,
+            uppercase: true,
+            digits: true,
+            symbols: false,
+            exclude_ambiguous: false,
+            values: Vec::new(),
+        }
+    }
+}
+
+fn format_uuid(bytes: [u8; 16]) -> String {
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32])
+}
+
+/// Random UUID (RFC 9562 version 4)
+fn uuid_v4() -> String {
+    let mut bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+    bytes[6] = (bytes[6] & 0x0F) | 0x40;
+    bytes[8] = (bytes[8] & 0x3F) | 0x80;
+    format_uuid(bytes)
+}
+
+/// Time-ordered UUID (RFC 9562 version 7): 48-bit Unix milliseconds followed by random bits
+fn uuid_v7() -> String {
+    let mut bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+    let millis = Utc::now().timestamp_millis() as u64;
+    bytes[..6].copy_from_slice(&millis.to_be_bytes()[2..]);
+    bytes[6] = (bytes[6] & 0x0F) | 0x70;
+    bytes[8] = (bytes[8] & 0x3F) | 0x80;
+    format_uuid(bytes)
+}
+
+/// 48-bit Unix milliseconds and 80 random bits in 26 base32 characters
+fn ulid() -> String {
+    let millis = Utc::now().timestamp_millis() as u128 & ((1 << 48) - 1);
+    let value = (millis << 80) | (OsRng.gen::<u128>() & ((1 << 80) - 1));
+    (0..26)
+        .rev()
+        .map(|index| CROCKFORD[((value >> (index * 5)) & 0x1F) as usize] as char)
+        .collect()
+}
+
+impl Generator {
+    fn charset(&self) -> Vec<char> {
+        let mut charset = String::new();
+        for (enabled, characters) in [
+            (self.lowercase, LOWERCASE),
+            (self.uppercase, UPPERCASE),
+            (self.digits, DIGITS),
+            (self.symbols, SYMBOLS),
+        ] {
+            if enabled {
+                charset.push_str(characters);
+            }
+        }
+        charset.chars().filter(|c| !self.exclude_ambiguous || !AMBIGUOUS.contains(*c)).collect()
+    }
+
+    fn secret(&self, charset: &[char]) -> String {
+        (0..self.length).map(|_| charset[OsRng.gen_range(0..charset.len())]).collect()
+    }
+
+    fn generate(&mut self) {
+        let charset = self.charset();
+        self.values = (0..self.count)
+            .map(|_| match self.kind {
+                IdKind::UuidV4 | IdKind::UuidV7 => {
+                    let uuid = if self.kind == IdKind::UuidV4 { uuid_v4() } else { uuid_v7() };
+                    if self.uppercase_uuid { uuid.to_uppercase() } else { uuid }
+                }
+                IdKind::Ulid => ulid(),
+                IdKind::Secret => self.secret(&charset),
+            })
+            .collect();
+    }
+}
+
+impl DevDashboard {
+    /// Generator tool page
+    /// UUIDs, ULIDs and random secrets from the OS random number generator
+    pub(crate) fn show_generator_tool(&mut self, ui: &mut egui::Ui) {
+        let generator = &mut self.generator;
+
+        ui.heading("Generator");
+        ui.add_space(8.0);
+
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut generator.kind, IdKind::UuidV4, "UUID v4");
+            ui.selectable_value(&mut generator.kind, IdKind::UuidV7, "UUID v7")
+                .on_hover_text("Time-ordered, sorts by creation time");
+            ui.selectable_value(&mut generator.kind, IdKind::Ulid, "ULID");
+            ui.selectable_value(&mut generator.kind, IdKind::Secret, "Random string");
+        });
+
+        let mut options_valid = true;
+        ui.horizontal(|ui| {
+            ui.label("Count:");
+            ui.add(egui::DragValue::new(&mut generator.count).clamp_range(1..=50));
+            match generator.kind {
+                IdKind::UuidV4 | IdKind::UuidV7 => {
+                    ui.checkbox(&mut generator.uppercase_uuid, "Uppercase");
+                }
+                IdKind::Secret => {
+                    ui.label("Length:");
+                    ui.add(egui::DragValue::new(&mut generator.length).clamp_range(4..=256));
+                }
+                IdKind::Ulid => {}
+            }
+        });
+        if generator.kind == IdKind::Secret {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut generator.lowercase, "a-z");
+                ui.checkbox(&mut generator.uppercase, "A-Z");
+                ui.checkbox(&mut generator.digits, "0-9");
+                ui.checkbox(&mut generator.symbols, "Symbols");
+                ui.checkbox(&mut generator.exclude_ambiguous, "Exclude ambiguous")
+                    .on_hover_text(format!("Leaves out {}", AMBIGUOUS));
+            });
+            let charset = generator.charset();
+            if charset.is_empty() {
+                options_valid = false;
+                ui.colored_label(Color32::from_rgb(220, 50, 50), "Select at least one character set");
+            } else {
+                let bits = generator.length as f64 * (charset.len() as f64).log2();
+                ui.label(RichText::new(format!("{:.0} bits of entropy", bits))
+                    .color(Color32::from_rgb(156, 163, 175)));
+            }
+        }
+        ui.add_space(4.0);
+
+        ui.horizontal(|ui| {
+            if ui.add_enabled(options_valid, egui::Button::new("Generate")).clicked() {
+                generator.generate();
+            }
+            if generator.values.len() > 1 && ui.button("📋 Copy all").clicked() {
+                let all = generator.values.join("\n");
+                ui.output_mut(|output| output.copied_text = all);
+            }
+        });
+        ui.add_space(8.0);
+
+        egui::ScrollArea::vertical().auto_shrink([false, true]).show(ui, |ui| {
+            for value in &generator.values {
+                ui.horizontal(|ui| {
+                    if ui.small_button("📋").on_hover_text("Copy").clicked() {
+                        ui.output_mut(|output| output.copied_text = value.clone());
+                    }
+                    ui.monospace(value);
+                });
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uuid_v7_has_version_variant_and_time() {
+        let before = Utc::now().timestamp_millis() as u64;
+        let uuid = uuid_v7();
+        let after = Utc::now().timestamp_millis() as u64;
+
+        assert_eq!(uuid.len(), 36);
+        assert_eq!(uuid.matches('-').count(), 4);
+        assert_eq!(&uuid[14..15], "7");
+        assert!(matches!(&uuid[19..20], "8" | "9" | "a" | "b"));
+        let millis = u64::from_str_radix(&uuid.replace('-', "")[..12], 16).unwrap();
+        assert!(before <= millis && millis <= after);
+    }
+
+    #[test]
+    fn ulid_is_crockford_base32_with_time() {
+        let before = Utc::now().timestamp_millis() as u64;
+        let id = ulid();
+        let after = Utc::now().timestamp_millis() as u64;
+
+        assert_eq!(id.len(), 26);
+        let digits: Vec<u64> = id.bytes()
+            .map(|c| CROCKFORD.iter().position(|d| *d == c).expect("not a Crockford digit") as u64)
+            .collect();
+        // 128 bits in 130: the first character only carries 3 bits
+        assert!(digits[0] < 8);
+        let millis = digits[..10].iter().fold(0u64, |value, digit| value << 5 | digit);
+        assert!(before <= millis && millis <= after);
+    }
+
+    #[test]
+    fn ids_are_unique() {
+        assert_ne!(uuid_v7(), uuid_v7());
+        assert_ne!(ulid(), ulid());
+    }
+}
@@ -0,0 +1,164 @@
+use eframe::egui;
+use egui::{Color32, RichText};
+#[cfg(feature = "nvml")]
+use nvml_wrapper::Device;
+#[cfg(feature = "nvml")]
+use nvml_wrapper::enum_wrappers::device::{ComputeMode, DriverModel, EccCounter, MemoryError, RetirementCause};
+use crate::DevDashboard;
+
+/// ECC, compute mode and memory health of workstation and data center cards; None where the card doesn't report it
+#[derive(Clone, Copy, Default, PartialEq)]
+pub struct GpuCompute {
+    pub ecc_enabled: Option<bool>,
+    pub ecc_pending: Option<bool>,          // Mode after the next reboot
+    pub compute_mode: Option<&'static str>,
+    pub driver_model: Option<&'static str>, // WDDM for display, TCC for compute only
+    pub driver_model_pending: Option<&'static str>,
+    pub corrected_errors: Option<u64>,      // Single bit ECC errors over the card's lifetime
+    pub uncorrected_errors: Option<u64>,    // Double bit ECC errors over the card's lifetime
+    pub retired_single_bit: Option<usize>,  // Pages retired after repeated single bit errors
+    pub retired_double_bit: Option<usize>,  // Pages retired after a double bit error
+    pub retirement_pending: Option<bool>,   // Pages waiting for a reboot to be retired
+}
+
+impl GpuCompute {
+    /// Consumer cards report no ECC, so the section is only shown on cards that do
+    pub fn is_supported(&self) -> bool {
+        self.ecc_enabled.is_some()
+    }
+
+    pub fn retired_pages(&self) -> Option<usize> {
+        match (self.retired_single_bit, self.retired_double_bit) {
+            (None, None) => None,
+            (single, double) => Some(single.unwrap_or(0) + double.unwrap_or(0)),
+        }
+    }
+
+    /// Uncorrectable errors, retired pages or a pending retirement mean the memory is wearing out
+    pub fn needs_attention(&self) -> bool {
+        self.uncorrected_errors.unwrap_or(0) > 0
+            || self.retired_pages().unwrap_or(0) > 0
+            || self.retirement_pending == Some(true)
+    }
+
+    /// One-line summary for the GPU card
+    pub fn summary(&self) -> Option<String> {
+        let ecc = self.ecc_enabled?;
+        let mut parts = vec![if ecc { "ECC on".to_string() } else { "ECC off".to_string() }];
+        if self.ecc_pending.is_some_and(|pending| pending != ecc) {
+            parts[0].push_str(" (changes on reboot)");
+        }
+        if let Some(mode) = self.compute_mode {
+            parts.push(format!("{} compute", mode));
+        }
+        if let Some(pages) = self.retired_pages() {
+            parts.push(match pages {
+                1 => "1 retired page".to_string(),
+                pages => format!("{} retired pages", pages),
+            });
+        }
+        Some(parts.join(" · "))
+    }
+}
+
+#[cfg(feature = "nvml")]
+fn driver_model_name(model: DriverModel) -> &'static str {
+    match model {
+        DriverModel::WDDM => "WDDM",
+        DriverModel::WDM => "TCC",
+    }
+}
+
+#[cfg(feature = "nvml")]
+pub fn read_compute(device: &Device) -> GpuCompute {
+    let ecc = device.is_ecc_enabled().ok();
+    let driver_model = device.driver_model().ok();
+    // Cards without ECC answer NotSupported to the error and retirement queries too, so skip asking
+    let ecc_supported = ecc.is_some();
+    let retired = |cause| if ecc_supported { device.retired_pages(cause).ok().map(|pages| pages.len()) } else { None };
+    let errors = |kind| if ecc_supported { device.total_ecc_errors(kind, EccCounter::Aggregate).ok() } else { None };
+    GpuCompute {
+        ecc_enabled: ecc.as_ref().map(|state| state.currently_enabled),
+        ecc_pending: ecc.as_ref().map(|state| state.pending_enabled),
+        compute_mode: device.compute_mode().ok().map(|mode| match mode {
+            ComputeMode::Default => "Shared",
+            ComputeMode::ExclusiveThread => "Exclusive thread",
+            ComputeMode::Prohibited => "Prohibited",
+            ComputeMode::ExclusiveProcess => "Exclusive process",
+        }),
+        driver_model: driver_model.as_ref().map(|state| driver_model_name(state.current.clone())),
+        driver_model_pending: driver_model.as_ref().map(|state| driver_model_name(state.pending.clone())),
+        corrected_errors: errors(MemoryError::Corrected),
+        uncorrected_errors: errors(MemoryError::Uncorrected),
+        retired_single_bit: retired(RetirementCause::MultipleSingleBitEccErrors),
+        retired_double_bit: retired(RetirementCause::DoubleBitEccError),
+        retirement_pending: if ecc_supported { device.are_pages_pending_retired().ok() } else { None },
+    }
+}
+
+impl DevDashboard {
+    /// ECC and compute section of the GPU window; only on cards that report ECC
+    pub(crate) fn show_gpu_compute(&self, ui: &mut egui::Ui) {
+        let Some(gpu) = &self.gpu_info else { return };
+        let compute = gpu.compute;
+        if !compute.is_supported() {
+            return;
+        }
+        let grey = Color32::from_rgb(156, 163, 175);
+        let red = Color32::from_rgb(220, 50, 50);
+        let count = |value: Option<u64>| value.map(|count| count.to_string()).unwrap_or_else(|| "-".to_string());
+        let on_off = |value: Option<bool>| match value {
+            Some(true) => "On",
+            Some(false) => "Off",
+            None => "-",
+        };
+
+        ui.add_space(8.0);
+        ui.label(RichText::new("Compute").strong());
+        egui::Grid::new("gpu_compute").num_columns(2).spacing([16.0, 2.0]).show(ui, |ui| {
+            ui.label(RichText::new("ECC").color(grey));
+            if compute.ecc_pending != compute.ecc_enabled {
+                ui.label(format!("{} ({} after reboot)", on_off(compute.ecc_enabled), on_off(compute.ecc_pending)));
+            } else {
+                ui.label(on_off(compute.ecc_enabled));
+            }
+            ui.end_row();
+            ui.label(RichText::new("Compute mode").color(grey));
+            ui.label(compute.compute_mode.unwrap_or("-"))
+                .on_hover_text("Shared lets several processes use the GPU; exclusive allows one at a time");
+            ui.end_row();
+            if let Some(model) = compute.driver_model {
+                ui.label(RichText::new("Driver model").color(grey));
+                match compute.driver_model_pending.filter(|pending| *pending != model) {
+                    Some(pending) => ui.label(format!("{} ({} after reboot)", model, pending)),
+                    None => ui.label(model),
+                };
+                ui.end_row();
+            }
+            ui.label(RichText::new("Corrected ECC errors").color(grey));
+            ui.label(count(compute.corrected_errors)).on_hover_text("Single bit errors over the card's lifetime");
+            ui.end_row();
+            ui.label(RichText::new("Uncorrected ECC errors").color(grey));
+            let uncorrected = count(compute.uncorrected_errors);
+            if compute.uncorrected_errors.unwrap_or(0) > 0 {
+                ui.colored_label(red, uncorrected);
+            } else {
+                ui.label(uncorrected);
+            }
+            ui.end_row();
+            ui.label(RichText::new("Retired pages").color(grey));
+            let retired = format!("{} single bit · {} double bit",
+                count(compute.retired_single_bit.map(|pages| pages as u64)),
+                count(compute.retired_double_bit.map(|pages| pages as u64)));
+            if compute.retired_pages().unwrap_or(0) > 0 {
+                ui.colored_label(red, retired);
+            } else {
+                ui.label(retired);
+            }
+            ui.end_row();
+        });
+        if compute.retirement_pending == Some(true) {
+            ui.colored_label(Color32::from_rgb(202, 138, 4), "Pages are waiting to be retired; restart to take them out of use");
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+use eframe::egui;
+use egui::{Color32, RichText};
+use reqwest::Client;
+use serde::{Serialize, Deserialize};
+use winreg::enums::HKEY_LOCAL_MACHINE;
+use winreg::RegKey;
+use log::{debug, info, warn};
+use crate::notifications::NotificationSource;
+use crate::DevDashboard;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+const FETCH_TIMEOUT: Duration = Duration::from_secs(20);
+/// Latest WHQL DCH GeForce driver for Windows 10/11 64-bit; every current GeForce card shares it
+const NVIDIA_LOOKUP_URL: &str = "https://gfwsl.geforce.com/services_toolkit/services/com/nvidia/services/AjaxDriverService.php?func=DriverManualLookup&psid=120&pfid=929&osID=57&languageCode=1033&isWHQL=1&dch=1&sort1=0&numberOfResults=1";
+/// Arc and Iris Xe graphics driver download page, which lists the current version
+const INTEL_DOWNLOAD_URL: &str = "https://www.intel.com/content/www/us/en/download/785597/intel-arc-iris-xe-graphics-windows.html";
+/// Radeon driver page, which names the current Adrenalin Edition release
+const AMD_DOWNLOAD_URL: &str = "https://www.amd.com/en/support/download/drivers.html";
+/// Browser user agent; the vendor pages turn away unknown clients
+const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64)";
+
+/// Persisted driver update check configuration
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct GpuDriverSettings {
+    pub check_updates: bool,
+}
+
+impl Default for GpuDriverSettings {
+    fn default() -> Self {
+        Self { check_updates: true }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum GpuVendor {
+    Nvidia,
+    Amd,
+    Intel,
+}
+
+impl GpuVendor {
+    fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        if name.contains("nvidia") || name.contains("geforce") || name.contains("quadro") {
+            Some(GpuVendor::Nvidia)
+        } else if name.contains("amd") || name.contains("radeon") {
+            Some(GpuVendor::Amd)
+        } else if name.contains("intel") {
+            Some(GpuVendor::Intel)
+        } else {
+            None
+        }
+    }
+}
+
+/// The vendor's current driver and where to get it
+#[derive(Clone)]
+pub struct DriverRelease {
+    pub version: String,
+    pub url: String,
+}
+
+/// Result of the last comparison against the vendor's release
+#[derive(Default)]
+pub struct DriverUpdateCheck {
+    latest: Option<Result<DriverRelease, String>>,
+    last_check: Option<Instant>,
+    receiver: Option<Receiver<Result<DriverRelease, String>>>,
+}
+
+/// Numeric groups of a version such as "546.33" or "31.0.101.5186"
+fn version_parts(version: &str) -> Vec<u64> {
+    version.split('.').map(|part| part.trim().parse().unwrap_or(0)).collect()
+}
+
+/// Runs of digits and dots in a page, such as version numbers
+fn version_tokens(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !c.is_ascii_digit() && c != '.')
+        .map(|token| token.trim_matches('.'))
+        .filter(|token| token.contains('.'))
+}
+
+/// The version the vendor's release notes use for the installed driver
+fn installed_version(vendor: GpuVendor, driver_version: Option<&str>) -> Option<String> {
+    match vendor {
+        // NVML reports "546.33"; Windows reports the same driver as "31.0.15.4633"
+        GpuVendor::Nvidia => {
+            let driver = driver_version?;
+            let parts: Vec<&str> = driver.split('.').collect();
+            if parts.len() < 4 {
+                return Some(driver.to_string());
+            }
+            let digits = format!("{}{}", parts[2], parts[3]);
+            let digits = &digits[digits.len().saturating_sub(5)..];
+            Some(format!("{}.{}", &digits[..digits.len().saturating_sub(2)], &digits[digits.len().saturating_sub(2)..]))
+        }
+        GpuVendor::Intel => driver_version.map(str::to_string),
+        // Windows only knows the internal version; Adrenalin records its release number itself
+        GpuVendor::Amd => RegKey::predef(HKEY_LOCAL_MACHINE)
+            .open_subkey(r"SOFTWARE\AMD\CN")
+            .and_then(|key| key.get_value::<String, _>("RadeonSoftwareVersion"))
+            .ok(),
+    }
+}
+
+async fn fetch_page(client: &Client, url: &str) -> Result<String, String> {
+    client.get(url)
+        .header("User-Agent", USER_AGENT)
+        .send().await.map_err(|e| e.to_string())?
+        .error_for_status().map_err(|e| e.to_string())?
+        .text().await.map_err(|e| e.to_string())
+}
+
+async fn latest_release(vendor: GpuVendor) -> Result<DriverRelease, String> {
+    let client = Client::builder().timeout(FETCH_TIMEOUT).build().map_err(|e| e.to_string())?;
+    match vendor {
+        GpuVendor::Nvidia => {
+            let json: serde_json::Value = serde_json::from_str(&fetch_page(&client, NVIDIA_LOOKUP_URL).await?)
+                .map_err(|e| e.to_string())?;
+            let info = &json["IDS"][0]["downloadInfo"];
+            let version = info["Version"].as_str().ok_or("No driver in the NVIDIA response")?;
+            let url = info["DetailsURL"].as_str()
+                .or_else(|| info["DownloadURL"].as_str())
+                .unwrap_or("https://www.nvidia.com/Download/index.aspx");
+            Ok(DriverRelease { version: version.to_string(), url: url.to_string() })
+        }
+        GpuVendor::Intel => {
+            let page = fetch_page(&client, INTEL_DOWNLOAD_URL).await?;
+            // Intel graphics drivers are numbered 31.0.101.NNNN
+            let version = version_tokens(&page)
+                .filter(|token| token.split('.').count() == 4 && token.split('.').nth(2) == Some("101"))
+                .max_by_key(|token| version_parts(token))
+                .ok_or("No version on the Intel download page")?;
+            Ok(DriverRelease { version: version.to_string(), url: INTEL_DOWNLOAD_URL.to_string() })
+        }
+        GpuVendor::Amd => {
+            let page = fetch_page(&client, AMD_DOWNLOAD_URL).await?;
+            let version = page.split("Adrenalin Edition")
+                .skip(1)
+                .filter_map(|rest| version_tokens(rest.get(..24).unwrap_or(rest)).next())
+                .max_by_key(|token| version_parts(token))
+                .ok_or("No Adrenalin release on the AMD download page")?;
+            Ok(DriverRelease { version: version.to_string(), url: AMD_DOWNLOAD_URL.to_string() })
+        }
+    }
+}
+
+impl DevDashboard {
+    /// Looks up the vendor's current driver once a day
+    pub(crate) fn update_gpu_driver_check(&mut self) {
+        if let Some(receiver) = &self.gpu_driver_check.receiver {
+            let Ok(result) = receiver.try_recv() else { return };
+            let check = &mut self.gpu_driver_check;
+            check.receiver = None;
+            match &result {
+                Ok(release) => debug!("Latest GPU driver: {}", release.version),
+                Err(e) => warn!("Could not check for a GPU driver update: {}", e),
+            }
+            check.latest = Some(result);
+            if let Some((installed, release)) = self.newer_gpu_driver() {
+                let body = format!("Installed {}; download it from {}", installed, release.url);
+                self.notifications.push_once(NotificationSource::Update, &format!("GPU driver {} available", release.version), &body);
+            }
+        }
+
+        if !self.settings.gpu_driver.check_updates {
+            return;
+        }
+        let Some(vendor) = self.gpu_info.as_ref().and_then(|gpu| GpuVendor::from_name(&gpu.name)) else { return };
+        let due = match self.gpu_driver_check.last_check {
+            Some(last) => last.elapsed() >= CHECK_INTERVAL,
+            None => true,
+        };
+        if !due {
+            return;
+        }
+        let check = &mut self.gpu_driver_check;
+        check.last_check = Some(Instant::now());
+        let (sender, receiver) = channel();
+        check.receiver = Some(receiver);
+        self.runtime().spawn(async move {
+            sender.send(latest_release(vendor).await).ok();
+        });
+    }
+
+    /// The installed driver version and the vendor's newer release, when there is one
+    fn newer_gpu_driver(&self) -> Option<(String, DriverRelease)> {
+        let gpu = self.gpu_info.as_ref()?;
+        let vendor = GpuVendor::from_name(&gpu.name)?;
+        let Some(Ok(release)) = &self.gpu_driver_check.latest else { return None };
+        let installed = installed_version(vendor, gpu.driver_version.as_deref())?;
+        (version_parts(&release.version) > version_parts(&installed)).then(|| (installed, release.clone()))
+    }
+
+    /// Update notice for the GPU card, shown when the vendor has a newer driver
+    pub(crate) fn show_gpu_driver_notice(&self, ui: &mut egui::Ui) {
+        if !self.settings.gpu_driver.check_updates {
+            return;
+        }
+        let Some(gpu) = &self.gpu_info else { return };
+        let Some(vendor) = GpuVendor::from_name(&gpu.name) else { return };
+        let Some(Ok(release)) = &self.gpu_driver_check.latest else { return };
+        let Some(installed) = installed_version(vendor, gpu.driver_version.as_deref()) else { return };
+
+        if version_parts(&release.version) > version_parts(&installed) {
+            ui.horizontal(|ui| {
+                ui.colored_label(Color32::from_rgb(202, 138, 4), format!("⬆ Driver {} available", release.version))
+                    .on_hover_text(format!("Installed: {}", installed));
+                ui.hyperlink_to("Download", &release.url);
+            });
+        } else {
+            ui.label(RichText::new("✔ Latest driver").color(Color32::from_rgb(107, 114, 128)))
+                .on_hover_text(format!("Installed {}, latest {}", installed, release.version));
+        }
+    }
+
+    /// Settings section for the driver update check
+    pub(crate) fn show_gpu_driver_settings(&mut self, ui: &mut egui::Ui) {
+        let response = ui.checkbox(&mut self.settings.gpu_driver.check_updates, "Check for GPU driver updates daily")
+            .on_hover_text("Compares the installed driver with the latest release on the NVIDIA, AMD or Intel site");
+        if response.changed() {
+            info!("GPU driver update check {}", if self.settings.gpu_driver.check_updates { "enabled" } else { "disabled" });
+            self.gpu_driver_check = DriverUpdateCheck::default();
+            self.save_settings();
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::VecDeque;
+use chrono::{DateTime, Local};
+use eframe::egui;
+use egui::{Color32, RichText};
+use crate::card_export::CardHistory;
+use crate::DevDashboard;
+
+/// Samples kept per GPU; one per update tick, so about ten minutes
+const HISTORY_LEN: usize = 600;
+const CHART_HEIGHT: f32 = 90.0;
+
+/// One reading of every GPU metric; missing values were not reported by the driver
+#[derive(Clone, Copy)]
+pub struct GpuSample {
+    pub time: DateTime<Local>,
+    pub utilization: Option<f32>,       // Percent
+    pub memory_used: Option<u64>,       // Bytes
+    pub temperature: Option<u32>,       // Celsius
+    pub graphics_clock: Option<u32>,    // MHz
+    pub memory_clock: Option<u32>,      // MHz
+}
+
+/// Recent readings of one GPU, oldest first
+#[derive(Default)]
+pub struct GpuHistory {
+    samples: VecDeque<GpuSample>,
+}
+
+impl GpuHistory {
+    pub fn push(&mut self, sample: GpuSample) {
+        if self.samples.len() == HISTORY_LEN {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// Every kept sample; metrics the driver did not report are left empty
+    pub fn export_history(&self) -> CardHistory {
+        let mut history = CardHistory::new(vec![
+            "time", "utilization_percent", "memory_used_bytes", "temperature_c", "graphics_clock_mhz", "memory_clock_mhz",
+        ]);
+        let text = |value: Option<String>| value.unwrap_or_default();
+        for sample in &self.samples {
+            history.rows.push(vec![
+                sample.time.format("%Y-%m-%d %H:%M:%S").to_string(),
+                text(sample.utilization.map(|value| format!("{:.1}", value))),
+                text(sample.memory_used.map(|value| value.to_string())),
+                text(sample.temperature.map(|value| value.to_string())),
+                text(sample.graphics_clock.map(|value| value.to_string())),
+                text(sample.memory_clock.map(|value| value.to_string())),
+            ]);
+        }
+        history
+    }
+}
+
+/// Line chart of one metric with a crosshair and exact value under the pointer
+fn history_chart(
+    ui: &mut egui::Ui,
+    title: &str,
+    samples: &VecDeque<GpuSample>,
+    value: impl Fn(&GpuSample) -> Option<f64>,
+    range: Option<(f64, f64)>,
+    format: impl Fn(f64) -> String,
+    color: Color32,
+) {
+    let values: Vec<Option<f64>> = samples.iter().map(&value).collect();
+    ui.label(RichText::new(title).strong());
+    if values.iter().all(Option::is_none) {
+        ui.label(RichText::new("Not reported by this GPU").color(Color32::from_rgb(107, 114, 128)));
+        ui.add_space(8.0);
+        return;
+    }
+
+    let (min, max) = range.unwrap_or_else(|| {
+        let present = values.iter().flatten();
+        let min = present.clone().fold(f64::INFINITY, |a, b| a.min(*b));
+        let max = present.fold(f64::NEG_INFINITY, |a, b| a.max(*b));
+        // Pad auto ranges so a flat line sits in the middle
+        let padding = ((max - min) * 0.1).max(1.0);
+        ((min - padding).max(0.0), max + padding)
+    });
+    let span = (max - min).max(f64::EPSILON);
+
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), CHART_HEIGHT), egui::Sense::hover());
+    let painter = ui.painter();
+    painter.rect_filled(rect, 4.0, Color32::from_rgb(17, 24, 39));
+    let step = rect.width() / (HISTORY_LEN - 1) as f32;
+    // Newest sample on the right edge, like Task Manager
+    let offset = HISTORY_LEN - values.len();
+    let point = |index: usize, value: f64| {
+        egui::pos2(
+            rect.left() + (offset + index) as f32 * step,
+            rect.bottom() - ((value - min) / span) as f32 * rect.height(),
+        )
+    };
+
+    let mut line = Vec::new();
+    for (index, value) in values.iter().enumerate() {
+        match value {
+            Some(value) => line.push(point(index, *value)),
+            None => {
+                // Gaps break the line instead of connecting across missing readings
+                if line.len() > 1 {
+                    painter.add(egui::Shape::line(std::mem::take(&mut line), egui::Stroke::new(1.5, color)));
+                }
+                line.clear();
+            }
+        }
+    }
+    if line.len() > 1 {
+        painter.add(egui::Shape::line(line, egui::Stroke::new(1.5, color)));
+    }
+
+    let grey = Color32::from_rgb(156, 163, 175);
+    let font = egui::FontId::proportional(11.0);
+    painter.text(rect.right_top() + egui::vec2(-4.0, 2.0), egui::Align2::RIGHT_TOP, format(max), font.clone(), grey);
+    painter.text(rect.right_bottom() + egui::vec2(-4.0, -2.0), egui::Align2::RIGHT_BOTTOM, format(min), font.clone(), grey);
+
+    if let Some(pos) = response.hover_pos() {
+        let index = ((pos.x - rect.left()) / step).round() as isize - offset as isize;
+        if let Some(sample) = usize::try_from(index).ok().and_then(|index| samples.get(index)) {
+            let x = rect.left() + (offset as isize + index) as f32 * step;
+            painter.line_segment([egui::pos2(x, rect.top()), egui::pos2(x, rect.bottom())], egui::Stroke::new(1.0, grey));
+            let readout = match value(sample) {
+                Some(reading) => {
+                    painter.circle_filled(point(index as usize, reading), 3.0, color);
+                    format!("{}  {}", sample.time.format("%H:%M:%S"), format(reading))
+                }
+                None => format!("{}  no reading", sample.time.format("%H:%M:%S")),
+            };
+            painter.text(rect.left_top() + egui::vec2(4.0, 2.0), egui::Align2::LEFT_TOP, readout, font, Color32::WHITE);
+        }
+    }
+    ui.add_space(8.0);
+}
+
+impl DevDashboard {
+    /// Appends the current GPU readings to its history
+    pub(crate) fn record_gpu_sample(&mut self) {
+        let Some(gpu) = &mut self.gpu_info else { return };
+        let sample = GpuSample {
+            time: Local::now(),
+            utilization: gpu.utilization,
+            memory_used: gpu.memory_used,
+            temperature: gpu.temperature,
+            graphics_clock: gpu.graphics_clock,
+            memory_clock: gpu.memory_clock,
+        };
+        gpu.history.push(sample);
+    }
+
+    /// GPU detail window with limits, ECC and compute mode, and utilization, VRAM, temperature and clock history
+    pub(crate) fn show_gpu_detail_window(&mut self, ctx: &egui::Context) {
+        if !self.gpu_detail_open {
+            return;
+        }
+        if self.gpu_info.is_none() {
+            self.gpu_detail_open = false;
+            return;
+        }
+
+        let mut open = true;
+        egui::Window::new("GPU Details")
+            .open(&mut open)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                if let Some(gpu) = &self.gpu_info {
+                    ui.label(RichText::new(&gpu.name).strong());
+                }
+                self.show_gpu_limits(ui);
+                self.show_gpu_compute(ui);
+                let Some(gpu) = &self.gpu_info else { return };
+                ui.label(RichText::new("Hover a chart for exact values").color(Color32::from_rgb(107, 114, 128)));
+                ui.add_space(8.0);
+                let samples = &gpu.history.samples;
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    history_chart(ui, "Utilization", samples, |sample| sample.utilization.map(f64::from),
+                        Some((0.0, 100.0)), |value| format!("{:.0}%", value), Color32::from_rgb(220, 38, 38));
+                    let gib = 1024.0 * 1024.0 * 1024.0;
+                    history_chart(ui, "VRAM", samples, |sample| sample.memory_used.map(|bytes| bytes as f64 / gib),
+                        gpu.memory_total.map(|total| (0.0, total as f64 / gib)), |value| format!("{:.1} GB", value),
+                        Color32::from_rgb(147, 51, 234));
+                    history_chart(ui, "Temperature", samples, |sample| sample.temperature.map(f64::from),
+                        None, |value| format!("{:.0}°C", value), Color32::from_rgb(202, 138, 4));
+                    history_chart(ui, "Graphics clock", samples, |sample| sample.graphics_clock.map(f64::from),
+                        None, |value| format!("{:.0} MHz", value), Color32::from_rgb(88, 165, 237));
+                    history_chart(ui, "Memory clock", samples, |sample| sample.memory_clock.map(f64::from),
+                        None, |value| format!("{:.0} MHz", value), Color32::from_rgb(22, 163, 74));
+                });
+            });
+        if !open {
+            self.gpu_detail_open = false;
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+#[cfg(feature = "nvml")]
+use std::sync::OnceLock;
+#[cfg(feature = "nvml")]
+use nvml_wrapper::Device;
+#[cfg(feature = "nvml")]
+use nvml_wrapper::enum_wrappers::device::TemperatureThreshold;
+use log::{info, warn};
+use crate::DevDashboard;
+
+/// Power limit control is off unless the user opts in
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct GpuPowerSettings {
+    pub allow_power_limit_control: bool,
+}
+
+/// Power, temperature and clock limits reported by the driver; None when not supported
+#[derive(Clone, Copy, Default)]
+pub struct GpuLimits {
+    pub power_limit: Option<f32>,           // Watts, as configured
+    pub enforced_power_limit: Option<f32>,  // Watts, lowest of all active limits
+    pub default_power_limit: Option<f32>,
+    pub power_limit_range: Option<(f32, f32)>,
+    pub slowdown_temperature: Option<u32>,  // Celsius, hardware throttling starts here
+    pub max_temperature: Option<u32>,       // Celsius, clocks may drop below base from here
+    pub graphics_offset: Option<i32>,       // MHz, set by tools such as MSI Afterburner
+    pub memory_offset: Option<i32>,
+}
+
+impl GpuLimits {
+    pub fn is_empty(&self) -> bool {
+        self.power_limit.is_none() && self.slowdown_temperature.is_none() && self.graphics_offset.is_none()
+    }
+
+    /// One-line summary for the GPU card
+    pub fn summary(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if let Some(limit) = self.power_limit {
+            parts.push(format!("Power limit {:.0} W", limit));
+        }
+        if let Some(temperature) = self.slowdown_temperature {
+            parts.push(format!("throttles at {}°C", temperature));
+        }
+        (!parts.is_empty()).then(|| parts.join(" · "))
+    }
+}
+
+#[cfg(feature = "nvml")]
+type ClockOffsetFn = unsafe extern "C" fn(*mut std::ffi::c_void, *mut std::ffi::c_int) -> u32;
+
+/// nvml.dll opened a second time for the clock offset calls, which nvml-wrapper does not expose.
+/// Windows hands back the module NVML already loaded.
+#[cfg(feature = "nvml")]
+fn nvml_library() -> Option<&'static libloading::Library> {
+    static LIBRARY: OnceLock<Option<libloading::Library>> = OnceLock::new();
+    LIBRARY.get_or_init(|| unsafe { libloading::Library::new("nvml.dll").ok() }).as_ref()
+}
+
+/// Reads a VF curve offset; drivers before R510 lack these functions
+#[cfg(feature = "nvml")]
+fn clock_offset(device: &Device, symbol: &[u8]) -> Option<i32> {
+    let library = nvml_library()?;
+    unsafe {
+        let function = library.get::<ClockOffsetFn>(symbol).ok()?;
+        let mut offset: std::ffi::c_int = 0;
+        (function(device.handle() as *mut std::ffi::c_void, &mut offset) == 0).then_some(offset)
+    }
+}
+
+#[cfg(feature = "nvml")]
+pub fn read_limits(device: &Device) -> GpuLimits {
+    let watts = |milliwatts: u32| milliwatts as f32 / 1000.0;
+    GpuLimits {
+        power_limit: device.power_management_limit().ok().map(watts),
+        enforced_power_limit: device.enforced_power_limit().ok().map(watts),
+        default_power_limit: device.power_management_limit_default().ok().map(watts),
+        power_limit_range: device
+            .power_management_limit_constraints()
+            .ok()
+            .map(|constraints| (watts(constraints.min_limit), watts(constraints.max_limit))),
+        slowdown_temperature: device.temperature_threshold(TemperatureThreshold::Slowdown).ok(),
+        max_temperature: device.temperature_threshold(TemperatureThreshold::GpuMax).ok(),
+        graphics_offset: clock_offset(device, b"nvmlDeviceGetGpcClkVfOffset\0"),
+        memory_offset: clock_offset(device, b"nvmlDeviceGetMemClkVfOffset\0"),
+    }
+}
+
+/// State of the power limit slider in the GPU window
+#[derive(Default)]
+pub struct GpuLimitControl {
+    target: Option<f32>,                    // Watts chosen on the slider, None until it is moved
+    result: Option<Result<String, String>>,
+}
+
+impl DevDashboard {
+    /// Sets the board power limit; needs administrator rights
+    fn apply_gpu_power_limit(&mut self, watts: f32) -> Result<(), String> {
+        #[cfg(feature = "nvml")]
+        {
+            let nvml = self.nvml.as_ref().ok_or("NVML is not available")?;
+            let index = self.gpu_info.as_ref().and_then(|gpu| gpu.nvml_index).ok_or("The GPU card is not showing an NVIDIA GPU")?;
+            let mut device = nvml.device_by_index(index).map_err(|e| e.to_string())?;
+            device
+                .set_power_management_limit((watts * 1000.0).round() as u32)
+                .map_err(|e| match e {
+                    nvml_wrapper::error::NvmlError::NoPermission => "Run the dashboard as administrator to change the power limit".to_string(),
+                    other => other.to_string(),
+                })?;
+            if let Some(gpu) = &mut self.gpu_info {
+                gpu.limits = read_limits(&device);
+            }
+            Ok(())
+        }
+        #[cfg(not(feature = "nvml"))]
+        {
+            let _ = watts;
+            Err("This build has no NVIDIA support".to_string())
+        }
+    }
+
+    /// Limits section of the GPU window, with the opt-in power limit slider
+    pub(crate) fn show_gpu_limits(&mut self, ui: &mut egui::Ui) {
+        let Some(gpu) = &self.gpu_info else { return };
+        let limits = gpu.limits;
+        if limits.is_empty() {
+            return;
+        }
+        let grey = Color32::from_rgb(156, 163, 175);
+        let watts = |value: Option<f32>| value.map(|limit| format!("{:.0} W", limit)).unwrap_or_else(|| "-".to_string());
+        let celsius = |value: Option<u32>| value.map(|limit| format!("{}°C", limit)).unwrap_or_else(|| "-".to_string());
+        let offset = |value: Option<i32>| value.map(|mhz| format!("{:+} MHz", mhz)).unwrap_or_else(|| "-".to_string());
+
+        ui.label(RichText::new("Limits").strong());
+        egui::Grid::new("gpu_limits").num_columns(2).spacing([16.0, 2.0]).show(ui, |ui| {
+            ui.label(RichText::new("Power limit").color(grey));
+            ui.label(format!("{} (default {})", watts(limits.power_limit), watts(limits.default_power_limit)));
+            ui.end_row();
+            ui.label(RichText::new("Enforced limit").color(grey));
+            ui.label(watts(limits.enforced_power_limit))
+                .on_hover_text("Lowest of the configured, thermal and platform limits");
+            ui.end_row();
+            ui.label(RichText::new("Slowdown temperature").color(grey));
+            ui.label(celsius(limits.slowdown_temperature));
+            ui.end_row();
+            ui.label(RichText::new("Max operating temperature").color(grey));
+            ui.label(celsius(limits.max_temperature));
+            ui.end_row();
+            ui.label(RichText::new("Clock offsets").color(grey));
+            ui.label(format!("Core {} · Memory {}", offset(limits.graphics_offset), offset(limits.memory_offset)));
+            ui.end_row();
+        });
+
+        let mut apply = None;
+        if let (true, Some((min, max)), Some(current)) =
+            (self.settings.gpu_power.allow_power_limit_control, limits.power_limit_range, limits.power_limit) {
+            let control = &mut self.gpu_limit_control;
+            let target = control.target.get_or_insert(current);
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.add(egui::Slider::new(target, min..=max).suffix(" W").fixed_decimals(0));
+                if ui.button("Apply").clicked() {
+                    apply = Some(*target);
+                }
+                if let Some(default) = limits.default_power_limit {
+                    if ui.button("Reset to default").clicked() {
+                        apply = Some(default);
+                    }
+                }
+            });
+            ui.label(RichText::new("Lower limits keep a laptop cooler at the cost of speed; the driver resets them on reboot")
+                .color(Color32::from_rgb(202, 138, 4)));
+            match &control.result {
+                Some(Ok(message)) => { ui.colored_label(Color32::from_rgb(22, 163, 74), message); }
+                Some(Err(error)) => { ui.colored_label(Color32::from_rgb(220, 50, 50), error); }
+                None => {}
+            }
+        }
+        ui.add_space(8.0);
+
+        if let Some(watts) = apply {
+            let result = match self.apply_gpu_power_limit(watts) {
+                Ok(()) => {
+                    info!("Set GPU power limit to {:.0} W", watts);
+                    Ok(format!("Power limit set to {:.0} W", watts))
+                }
+                Err(e) => {
+                    warn!("Could not set GPU power limit: {}", e);
+                    Err(e)
+                }
+            };
+            self.gpu_limit_control.target = Some(watts);
+            self.gpu_limit_control.result = Some(result);
+        }
+    }
+
+    /// Settings section for the GPU power limit opt-in
+    pub(crate) fn show_gpu_power_settings(&mut self, ui: &mut egui::Ui) {
+        ui.label("GPU Power Limit:");
+        if ui.checkbox(&mut self.settings.gpu_power.allow_power_limit_control,
+            "Allow changing the NVIDIA power limit from the GPU window").changed() {
+            self.gpu_limit_control = GpuLimitControl::default();
+            self.save_settings();
+        }
+        if self.settings.gpu_power.allow_power_limit_control {
+            ui.label(RichText::new("⚠ Limits outside what the vendor tested can cause instability; needs administrator rights")
+                .color(Color32::from_rgb(202, 138, 4)));
+        }
+    }
+}
@@ -0,0 +1,445 @@
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+use eframe::egui;
+use pbkdf2::pbkdf2_hmac;
+use rand::RngCore;
+use serde::{Serialize, Deserialize};
+use sha2::{Digest, Sha256};
+use windows::core::HSTRING;
+use windows::Security::Credentials::UI::{UserConsentVerificationResult, UserConsentVerifier};
+use log::{info, warn};
+use crate::containers::{ContainerAction, ProjectAction};
+use crate::install_audit::OrphanedEntry;
+use crate::package_sources::WingetSource;
+use crate::services::ServiceAction;
+use crate::watchdog::WatchdogAction;
+use crate::wsl::WslAction;
+use crate::DevDashboard;
+
+/// Failed PIN attempts allowed before the prompt is temporarily locked
+const MAX_PIN_ATTEMPTS: u32 = 5;
+/// How long the prompt stays locked after too many failed attempts
+const PIN_LOCKOUT: Duration = Duration::from_secs(30);
+/// PBKDF2-HMAC-SHA256 rounds for new PIN hashes; a short numeric PIN needs a slow hash to resist guessing
+const PIN_ITERATIONS: u32 = 600_000;
+
+/// Actions that can be locked behind a PIN or Windows Hello confirmation
+#[derive(Clone, PartialEq, Debug)]
+pub enum SensitiveAction {
+    InstallApps,
+    ChangeLockSettings,
+    KillProcess(ProcessKill),
+    ControlService(ServiceControl),
+    ElevatedAction(ElevatedRequest),
+    Uninstall(OrphanedEntry),
+}
+
+/// Ways the dashboard can end a process
+#[derive(Clone, PartialEq, Debug)]
+pub enum ProcessKill {
+    TopProcess { pid: u32, name: String },
+    PortOwner { pid: u32, name: String, port: u16 },
+    WatchdogRule { rule: usize, process: String, action: WatchdogAction },  // Arms a watchdog rule to kill or restart
+}
+
+/// Starting, stopping or restarting something that keeps running in the background
+#[derive(Clone, PartialEq, Debug)]
+pub enum ServiceControl {
+    Service(String, ServiceAction),
+    Stack(usize, String),               // Index and name of a restart stack
+    Container(String, ContainerAction), // Container id
+    Compose(String, ProjectAction),     // Compose project name
+    Wsl(WslAction),
+}
+
+/// Changes to the system made through the elevated helper
+#[derive(Clone, PartialEq, Debug)]
+pub enum ElevatedRequest {
+    AddWingetSource(WingetSource),
+    PortProxy { tunnel: usize, name: String, add: bool },
+}
+
+impl SensitiveAction {
+    fn description(&self) -> String {
+        match self {
+            SensitiveAction::InstallApps => "Install the selected applications".to_string(),
+            SensitiveAction::ChangeLockSettings => "Change the sensitive action lock settings".to_string(),
+            SensitiveAction::KillProcess(ProcessKill::TopProcess { pid, name }) => format!("End {} (PID {})", name, pid),
+            SensitiveAction::KillProcess(ProcessKill::PortOwner { pid, name, port }) =>
+                format!("End {} (PID {}) to free port {}", name, pid, port),
+            SensitiveAction::KillProcess(ProcessKill::WatchdogRule { process, action, .. }) =>
+                format!("Let the watchdog {} {} when it exceeds its limits", action.label().to_lowercase(), process),
+            SensitiveAction::ControlService(ServiceControl::Service(name, action)) => format!("{} the {} service", action.label(), name),
+            SensitiveAction::ControlService(ServiceControl::Stack(_, name)) => format!("Restart the {} stack", name),
+            SensitiveAction::ControlService(ServiceControl::Container(id, action)) =>
+                format!("{} container {}", action.label(), &id[..id.len().min(12)]),
+            SensitiveAction::ControlService(ServiceControl::Compose(name, action)) =>
+                format!("Compose {} {}", action.label().to_lowercase(), name),
+            SensitiveAction::ControlService(ServiceControl::Wsl(action)) => action.description(),
+            SensitiveAction::ElevatedAction(ElevatedRequest::AddWingetSource(source)) =>
+                format!("Register the winget source {} at {}", source.name, source.url),
+            SensitiveAction::ElevatedAction(ElevatedRequest::PortProxy { name, add, .. }) =>
+                format!("{} the {} port proxy", if *add { "Add" } else { "Remove" }, name),
+            SensitiveAction::Uninstall(entry) => format!("Remove the uninstall entry of {}", entry.name),
+        }
+    }
+}
+
+/// Salted PBKDF2 hash of the confirmation PIN
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PinHash {
+    salt: String,
+    hash: String,
+    #[serde(default)]
+    iterations: u32,                    // 0 for hashes saved before PBKDF2, a single salted SHA-256
+}
+
+impl PinHash {
+    fn new(pin: &str) -> Self {
+        let mut salt = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut salt);
+        let salt = to_hex(&salt);
+        let hash = Self::hash(&salt, pin, PIN_ITERATIONS);
+        Self { salt, hash, iterations: PIN_ITERATIONS }
+    }
+
+    fn hash(salt: &str, pin: &str, iterations: u32) -> String {
+        if iterations == 0 {
+            let mut hasher = Sha256::new();
+            hasher.update(salt.as_bytes());
+            hasher.update(pin.as_bytes());
+            return to_hex(&hasher.finalize());
+        }
+        let mut key = [0u8; 32];
+        pbkdf2_hmac::<Sha256>(pin.as_bytes(), salt.as_bytes(), iterations, &mut key);
+        to_hex(&key)
+    }
+
+    fn matches(&self, pin: &str) -> bool {
+        let hash = Self::hash(&self.salt, pin, self.iterations);
+        // Compare every byte so the time taken doesn't hint at how much matched
+        hash.len() == self.hash.len()
+            && hash.bytes().zip(self.hash.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+    }
+
+    /// Hashes from older versions are replaced after the next correct PIN
+    fn is_outdated(&self) -> bool {
+        self.iterations < PIN_ITERATIONS
+    }
+}
+
+/// Outcome of checking the entered PIN on the worker thread
+enum PinCheck {
+    Correct(Option<PinHash>),           // Replacement hash when the stored one is outdated
+    Incorrect,
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Persisted configuration for the sensitive action lock
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ActionLockSettings {
+    pub enabled: bool,
+    pub pin: Option<PinHash>,
+    pub allow_windows_hello: bool,
+}
+
+impl ActionLockSettings {
+    /// The lock only applies once there is a way to unlock it
+    fn is_active(&self) -> bool {
+        self.enabled && (self.pin.is_some() || self.allow_windows_hello)
+    }
+}
+
+/// Runtime state of the confirmation prompt
+#[derive(Default)]
+pub struct ActionGuard {
+    pending: Option<SensitiveAction>,    // Action waiting for confirmation
+    pin_input: String,
+    error: Option<String>,
+    failed_attempts: u32,
+    locked_until: Option<Instant>,
+    hello_receiver: Option<Receiver<bool>>,
+    pin_receiver: Option<Receiver<PinCheck>>,       // PIN check in flight
+    new_pin: String,                     // PIN being entered in Settings
+    new_pin_receiver: Option<Receiver<PinHash>>,    // New PIN being hashed
+    pub settings_unlocked: bool,         // Lock settings may be edited until Settings is closed
+}
+
+impl ActionGuard {
+    /// Whether an action is waiting for the PIN or Windows Hello
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    fn reset_prompt(&mut self) {
+        self.pending = None;
+        self.pin_input.clear();
+        self.error = None;
+        self.hello_receiver = None;
+        self.pin_receiver = None;
+    }
+
+    fn is_locked_out(&self) -> bool {
+        self.locked_until.map(|until| Instant::now() < until).unwrap_or(false)
+    }
+
+    /// Checks the entered PIN on a background thread, the slow hash would freeze the window
+    fn start_pin_check(&mut self, stored: PinHash) {
+        let (sender, receiver) = channel();
+        self.pin_receiver = Some(receiver);
+        let pin = self.pin_input.clone();
+
+        std::thread::spawn(move || {
+            let check = if stored.matches(&pin) {
+                PinCheck::Correct(stored.is_outdated().then(|| PinHash::new(&pin)))
+            } else {
+                PinCheck::Incorrect
+            };
+            sender.send(check).ok();
+        });
+    }
+
+    /// Hashes a new PIN from Settings on a background thread
+    fn start_new_pin_hash(&mut self) {
+        let (sender, receiver) = channel();
+        self.new_pin_receiver = Some(receiver);
+        let pin = std::mem::take(&mut self.new_pin);
+
+        std::thread::spawn(move || {
+            sender.send(PinHash::new(&pin)).ok();
+        });
+    }
+
+    /// Counts a wrong PIN and locks the prompt after too many
+    fn record_failed_attempt(&mut self) {
+        self.failed_attempts += 1;
+        self.pin_input.clear();
+        warn!("Incorrect PIN entered ({} attempts)", self.failed_attempts);
+        if self.failed_attempts >= MAX_PIN_ATTEMPTS {
+            self.failed_attempts = 0;
+            self.locked_until = Some(Instant::now() + PIN_LOCKOUT);
+            self.error = Some(format!("Too many attempts. Try again in {} seconds.", PIN_LOCKOUT.as_secs()));
+        } else {
+            self.error = Some("Incorrect PIN".to_string());
+        }
+    }
+
+    /// Asks Windows Hello to verify the user on a background thread
+    fn start_windows_hello(&mut self, message: &str) {
+        let (sender, receiver) = channel();
+        self.hello_receiver = Some(receiver);
+        let message = HSTRING::from(message);
+
+        std::thread::spawn(move || {
+            let verified = UserConsentVerifier::RequestVerificationAsync(&message)
+                .and_then(|operation| operation.get())
+                .map(|result| result == UserConsentVerificationResult::Verified);
+            match verified {
+                Ok(verified) => { sender.send(verified).ok(); }
+                Err(e) => {
+                    warn!("Windows Hello verification failed: {}", e);
+                    sender.send(false).ok();
+                }
+            }
+        });
+    }
+}
+
+impl DevDashboard {
+    /// Runs the action right away, or asks for confirmation first when the lock is enabled
+    pub(crate) fn request_sensitive_action(&mut self, action: SensitiveAction) {
+        if self.settings.action_lock.is_active() {
+            info!("Confirmation required for sensitive action: {:?}", action);
+            self.action_guard.reset_prompt();
+            self.action_guard.pending = Some(action);
+        } else {
+            self.perform_sensitive_action(action);
+        }
+    }
+
+    fn perform_sensitive_action(&mut self, action: SensitiveAction) {
+        info!("Performing sensitive action: {:?}", action);
+        match action {
+            SensitiveAction::InstallApps => self.start_installation(),
+            SensitiveAction::ChangeLockSettings => self.action_guard.settings_unlocked = true,
+            SensitiveAction::KillProcess(ProcessKill::TopProcess { pid, name }) => self.end_top_process(pid, &name),
+            SensitiveAction::KillProcess(ProcessKill::PortOwner { pid, name, port }) => self.kill_port_owner(pid, name, port),
+            SensitiveAction::KillProcess(ProcessKill::WatchdogRule { rule, action, .. }) => self.set_watchdog_action(rule, action),
+            SensitiveAction::ControlService(ServiceControl::Service(name, action)) => self.run_service_action(name, action),
+            SensitiveAction::ControlService(ServiceControl::Stack(index, _)) => self.restart_stack(index),
+            SensitiveAction::ControlService(ServiceControl::Container(id, action)) => self.start_container_action(id, action),
+            SensitiveAction::ControlService(ServiceControl::Compose(name, action)) => self.start_project_action(name, action),
+            SensitiveAction::ControlService(ServiceControl::Wsl(action)) => self.start_wsl_action(action),
+            SensitiveAction::ElevatedAction(ElevatedRequest::AddWingetSource(source)) => self.register_winget_source(source),
+            SensitiveAction::ElevatedAction(ElevatedRequest::PortProxy { tunnel, add: true, .. }) => self.start_tunnel(tunnel),
+            SensitiveAction::ElevatedAction(ElevatedRequest::PortProxy { tunnel, add: false, .. }) => self.stop_tunnel(tunnel),
+            SensitiveAction::Uninstall(entry) => self.remove_orphaned_entry(entry),
+        }
+    }
+
+    /// Shows the PIN / Windows Hello prompt while an action is waiting for confirmation
+    pub(crate) fn show_action_guard_window(&mut self, ctx: &egui::Context) {
+        let Some(action) = self.action_guard.pending.clone() else {
+            return;
+        };
+
+        // Collect the Windows Hello result if a verification is in flight
+        if let Some(receiver) = &self.action_guard.hello_receiver {
+            if let Ok(verified) = receiver.try_recv() {
+                self.action_guard.hello_receiver = None;
+                if verified {
+                    self.action_guard.reset_prompt();
+                    self.action_guard.failed_attempts = 0;
+                    self.perform_sensitive_action(action);
+                    return;
+                }
+                self.action_guard.error = Some("Windows Hello verification was not completed".to_string());
+            }
+        }
+
+        // Collect the PIN check result if one is in flight
+        if let Some(receiver) = &self.action_guard.pin_receiver {
+            if let Ok(check) = receiver.try_recv() {
+                self.action_guard.pin_receiver = None;
+                match check {
+                    PinCheck::Correct(rehash) => {
+                        if let Some(pin) = rehash {
+                            info!("Upgrading the stored PIN hash");
+                            self.settings.action_lock.pin = Some(pin);
+                            self.save_settings();
+                        }
+                        self.action_guard.reset_prompt();
+                        self.action_guard.failed_attempts = 0;
+                        self.perform_sensitive_action(action);
+                        return;
+                    }
+                    PinCheck::Incorrect => self.action_guard.record_failed_attempt(),
+                }
+            }
+        }
+
+        let mut cancelled = false;
+        let lock = &self.settings.action_lock;
+        let guard = &mut self.action_guard;
+
+        egui::Window::new("Confirm Action")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(action.description());
+                ui.add_space(8.0);
+
+                let checking = guard.pin_receiver.is_some();
+                let enabled = !guard.is_locked_out() && !checking;
+                if let Some(pin) = &lock.pin {
+                    ui.label("PIN:");
+                    let response = ui.add_enabled(enabled,
+                        egui::TextEdit::singleline(&mut guard.pin_input).password(true));
+                    let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+                    let clicked = ui.horizontal(|ui| {
+                        let clicked = ui.add_enabled(enabled, egui::Button::new("Confirm")).clicked();
+                        if checking {
+                            ui.spinner();
+                            ui.label("Checking PIN...");
+                        }
+                        clicked
+                    }).inner;
+                    if (submitted || clicked) && enabled && !guard.pin_input.is_empty() {
+                        guard.error = None;
+                        guard.start_pin_check(pin.clone());
+                    }
+                }
+
+                if lock.allow_windows_hello {
+                    ui.add_space(4.0);
+                    if guard.hello_receiver.is_some() {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label("Waiting for Windows Hello...");
+                        });
+                    } else if ui.button("Use Windows Hello").clicked() {
+                        guard.start_windows_hello(&action.description());
+                    }
+                }
+
+                if let Some(error) = &guard.error {
+                    ui.add_space(4.0);
+                    ui.colored_label(egui::Color32::from_rgb(220, 50, 50), error);
+                }
+
+                ui.add_space(8.0);
+                if ui.button("Cancel").clicked() {
+                    cancelled = true;
+                }
+            });
+
+        if cancelled {
+            info!("Sensitive action cancelled: {:?}", action);
+            self.action_guard.reset_prompt();
+        }
+    }
+
+    /// Settings section for configuring the sensitive action lock
+    pub(crate) fn show_action_lock_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+
+        // Store the new PIN once its hash is ready
+        if let Some(receiver) = &self.action_guard.new_pin_receiver {
+            if let Ok(pin) = receiver.try_recv() {
+                self.action_guard.new_pin_receiver = None;
+                self.settings.action_lock.pin = Some(pin);
+                changed = true;
+            }
+        }
+
+        ui.label("Protect sensitive actions:");
+
+        // Don't let the lock be switched off without passing it first
+        if self.settings.action_lock.is_active() && !self.action_guard.settings_unlocked {
+            if ui.button("🔒 Unlock to change").clicked() {
+                self.request_sensitive_action(SensitiveAction::ChangeLockSettings);
+            }
+            if changed {
+                self.save_settings();
+            }
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            let hashing = self.action_guard.new_pin_receiver.is_some();
+            ui.label(if self.settings.action_lock.pin.is_some() { "Change PIN:" } else { "Set PIN:" });
+            ui.add_enabled(!hashing, egui::TextEdit::singleline(&mut self.action_guard.new_pin)
+                .password(true)
+                .desired_width(80.0));
+            let valid = self.action_guard.new_pin.len() >= 4
+                && self.action_guard.new_pin.chars().all(|c| c.is_ascii_digit());
+            if hashing {
+                ui.spinner();
+            } else if ui.add_enabled(valid, egui::Button::new("Save")).on_disabled_hover_text("PIN must be at least 4 digits").clicked() {
+                self.action_guard.start_new_pin_hash();
+            }
+            if self.settings.action_lock.pin.is_some() && !hashing && ui.button("Remove").clicked() {
+                self.settings.action_lock.pin = None;
+                changed = true;
+            }
+        });
+
+        changed |= ui.checkbox(&mut self.settings.action_lock.allow_windows_hello, "Allow Windows Hello").changed();
+
+        let can_enable = self.settings.action_lock.pin.is_some() || self.settings.action_lock.allow_windows_hello;
+        changed |= ui.add_enabled(can_enable,
+            egui::Checkbox::new(&mut self.settings.action_lock.enabled, "Require confirmation for installs, ending processes, services and other sensitive actions"))
+            .on_disabled_hover_text("Set a PIN or allow Windows Hello first")
+            .changed();
+
+        if changed {
+            self.save_settings();
+        }
+    }
+}
@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+use chrono::{Duration as ChronoDuration, Local, NaiveTime};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use sysinfo::SystemExt;
+use tokio::process::Command as TokioCommand;
+use log::{debug, warn};
+use crate::{DevDashboard, CREATE_NO_WINDOW};
+
+/// winget contacts its sources, so pending updates are counted rarely
+const UPDATE_CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+const UPDATE_CHECK_TIMEOUT: Duration = Duration::from_secs(120);
+const AVATAR_SIZE: f32 = 32.0;
+
+/// Persisted top panel configuration
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct HeaderSettings {
+    pub show_greeting: bool,
+    pub display_names: HashMap<String, String>,    // Windows account name to the name in the greeting
+    pub show_avatar: bool,
+    pub avatar_path: String,        // PNG file; initials are drawn when empty or unreadable
+    pub nickname: String,           // Shown instead of the host name
+    pub show_summary: bool,         // Date, uptime, overnight alerts and pending updates under the greeting
+}
+
+impl Default for HeaderSettings {
+    fn default() -> Self {
+        Self {
+            show_greeting: true,
+            display_names: HashMap::new(),
+            show_avatar: true,
+            avatar_path: String::new(),
+            nickname: String::new(),
+            show_summary: true,
+        }
+    }
+}
+
+/// Avatar texture and the pending update count for the top panel
+#[derive(Default)]
+pub struct Header {
+    avatar: Option<(String, Option<egui::TextureHandle>)>,  // Loaded path and its texture, None if unreadable
+    pending_updates: Option<usize>,
+    receiver: Option<Receiver<Option<usize>>>,
+    last_update_check: Option<Instant>,
+}
+
+/// Counts rows in the `winget upgrade` table; the header and the summary line are localized, the column padding is not
+async fn count_pending_updates() -> Option<usize> {
+    let child = TokioCommand::new("winget")
+        .args(["upgrade", "--accept-source-agreements", "--disable-interactivity"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .ok()?;
+    let output = tokio::time::timeout(UPDATE_CHECK_TIMEOUT, child.wait_with_output()).await.ok()?.ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // winget draws progress with carriage returns; only the last segment of each line is real output
+    let lines: Vec<&str> = stdout.lines().map(|line| line.rsplit('\r').next().unwrap_or(line)).collect();
+    let separator = lines.iter().position(|line| line.trim().len() > 10 && line.trim().chars().all(|c| c == '-'));
+    let Some(separator) = separator else {
+        // No table means nothing to upgrade, or winget is missing
+        return output.status.success().then_some(0);
+    };
+    Some(lines[separator + 1..].iter().take_while(|line| line.contains("  ")).count())
+}
+
+/// Upper-cases the first character; it may expand to several, as 'ß' becomes "SS"
+pub fn capitalize_first(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Initials for the drawn avatar
+fn initials(name: &str) -> String {
+    name.split(|c: char| c.is_whitespace() || c == '.' || c == '_' || c == '-')
+        .filter_map(|part| part.chars().next())
+        .take(2)
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+fn load_avatar(ctx: &egui::Context, path: &str) -> Option<egui::TextureHandle> {
+    let image = match image::open(path) {
+        Ok(image) => image.thumbnail(64, 64).to_rgba8(),
+        Err(e) => {
+            warn!("Could not load avatar {}: {}", path, e);
+            return None;
+        }
+    };
+    let size = [image.width() as usize, image.height() as usize];
+    let pixels = egui::ColorImage::from_rgba_unmultiplied(size, image.as_raw());
+    Some(ctx.load_texture("header_avatar", pixels, egui::TextureOptions::LINEAR))
+}
+
+impl DevDashboard {
+    /// Refreshes the pending update count in the background
+    pub(crate) fn update_header(&mut self) {
+        if !self.settings.header.show_summary {
+            return;
+        }
+        if let Some(receiver) = &self.header.receiver {
+            if let Ok(count) = receiver.try_recv() {
+                debug!("Pending app updates: {:?}", count);
+                self.header.pending_updates = count;
+                self.header.receiver = None;
+            }
+            return;
+        }
+        let due = match self.header.last_update_check {
+            Some(last) => last.elapsed() >= UPDATE_CHECK_INTERVAL,
+            None => true,
+        };
+        if due {
+            let (sender, receiver) = channel();
+            self.header.receiver = Some(receiver);
+            self.header.last_update_check = Some(Instant::now());
+            self.runtime().spawn(async move {
+                sender.send(count_pending_updates().await).ok();
+            });
+        }
+    }
+
+    /// Greeting name for the signed-in Windows account, falling back to the account name
+    pub(crate) fn display_name(&self) -> String {
+        let account = whoami::username();
+        match self.settings.header.display_names.get(&account).map(|name| name.trim()) {
+            Some(name) if !name.is_empty() => name.to_string(),
+            _ => account,
+        }
+    }
+
+    /// One line with the date, machine, uptime, overnight alerts and pending updates
+    fn daily_summary(&self) -> (String, String) {
+        let now = Local::now();
+        let machine = if self.settings.header.nickname.trim().is_empty() {
+            self.sys.host_name().unwrap_or_default()
+        } else {
+            self.settings.header.nickname.trim().to_string()
+        };
+        let uptime = self.sys.uptime();
+        let mut parts = vec![
+            now.format("%A, %-d %B").to_string(),
+            machine,
+            format!("Up {}h {}m", uptime / 3600, (uptime % 3600) / 60),
+        ];
+
+        // Overnight means since six in the evening before today
+        let evening = now.date_naive().and_time(NaiveTime::from_hms_opt(18, 0, 0).unwrap_or_default()) - ChronoDuration::days(1);
+        let cutoff = evening.and_local_timezone(Local).earliest().unwrap_or(now);
+        let watchdog = self.watchdog.alerts_since(cutoff);
+        let shutdowns = self.uptime_history.unexpected_since(cutoff);
+        let shares = self.share_monitor.unhealthy_count();
+        let alerts = watchdog + shutdowns + shares;
+        parts.push(match alerts {
+            0 => "No alerts overnight".to_string(),
+            1 => "1 alert overnight".to_string(),
+            count => format!("{} alerts overnight", count),
+        });
+        match self.header.pending_updates {
+            Some(0) => parts.push("Apps up to date".to_string()),
+            Some(1) => parts.push("1 app update pending".to_string()),
+            Some(count) => parts.push(format!("{} app updates pending", count)),
+            None => {}
+        }
+
+        let details = format!(
+            "Since {}:\n{} watchdog alerts\n{} unexpected shutdowns\n{} network shares slow or offline now\n\nApp updates are counted with winget every 6 hours",
+            cutoff.format("%a %H:%M"),
+            watchdog,
+            shutdowns,
+            shares
+        );
+        (parts.join(" · "), details)
+    }
+
+    /// Avatar next to the greeting: the configured picture, or initials in a circle
+    pub(crate) fn show_header_avatar(&mut self, ui: &mut egui::Ui, name: &str) {
+        if !self.settings.header.show_avatar {
+            return;
+        }
+        let path = self.settings.header.avatar_path.trim().to_string();
+        if !path.is_empty() && self.header.avatar.as_ref().map(|(loaded, _)| loaded != &path).unwrap_or(true) {
+            let texture = load_avatar(ui.ctx(), &path);
+            self.header.avatar = Some((path.clone(), texture));
+        }
+
+        let size = egui::vec2(AVATAR_SIZE, AVATAR_SIZE);
+        match self.header.avatar.as_ref().filter(|_| !path.is_empty()).and_then(|(_, texture)| texture.as_ref()) {
+            Some(texture) => {
+                ui.add(egui::Image::new(texture).fit_to_exact_size(size).rounding(AVATAR_SIZE / 2.0));
+            }
+            None => {
+                let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+                let painter = ui.painter();
+                painter.circle_filled(rect.center(), AVATAR_SIZE / 2.0, Color32::from_rgb(37, 99, 235));
+                painter.text(rect.center(), egui::Align2::CENTER_CENTER, initials(name),
+                    egui::FontId::proportional(14.0), Color32::WHITE);
+            }
+        }
+    }
+
+    /// Summary line under the greeting
+    pub(crate) fn show_header_summary(&self, ui: &mut egui::Ui) {
+        if !self.settings.header.show_summary {
+            return;
+        }
+        let (summary, details) = self.daily_summary();
+        ui.label(RichText::new(summary).color(Color32::from_rgb(156, 163, 175))).on_hover_text(details);
+    }
+
+    /// Settings section for the top panel
+    pub(crate) fn show_header_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        let header = &mut self.settings.header;
+
+        ui.label("Top Panel:");
+        changed |= ui.checkbox(&mut header.show_greeting, "Show greeting").changed();
+        if header.show_greeting {
+            // settings.json can be shared between accounts, so each keeps its own name
+            let account = whoami::username();
+            let mut name = header.display_names.get(&account).cloned().unwrap_or_default();
+            ui.horizontal(|ui| {
+                ui.label(format!("Display name for {}:", account));
+                if ui.add(egui::TextEdit::singleline(&mut name)
+                    .hint_text(account.as_str())
+                    .desired_width(160.0)).changed() {
+                    if name.is_empty() {
+                        header.display_names.remove(&account);
+                    } else {
+                        header.display_names.insert(account.clone(), name);
+                    }
+                    changed = true;
+                }
+            });
+
+            let mut others: Vec<(String, String)> = header.display_names
+                .iter()
+                .filter(|(other, _)| **other != account)
+                .map(|(other, name)| (other.clone(), name.clone()))
+                .collect();
+            others.sort();
+            for (other, name) in others {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(format!("{}: {}", other, name)).color(Color32::from_rgb(156, 163, 175)));
+                    if ui.small_button("✖").on_hover_text("Forget this account's name").clicked() {
+                        header.display_names.remove(&other);
+                        changed = true;
+                    }
+                });
+            }
+        }
+        changed |= ui.checkbox(&mut header.show_avatar, "Show avatar").changed();
+        if header.show_avatar {
+            ui.horizontal(|ui| {
+                ui.label("Picture (PNG):");
+                changed |= ui.add(egui::TextEdit::singleline(&mut header.avatar_path)
+                    .hint_text("Initials when empty")
+                    .desired_width(220.0)).lost_focus();
+            });
+        }
+        ui.horizontal(|ui| {
+            ui.label("Machine nickname:");
+            changed |= ui.add(egui::TextEdit::singleline(&mut header.nickname)
+                .hint_text("Host name when empty")
+                .desired_width(160.0)).changed();
+        });
+        changed |= ui.checkbox(&mut header.show_summary, "Show date, uptime, overnight alerts and pending updates").changed();
+
+        if changed {
+            self.save_settings();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capitalize_first_handles_non_ascii() {
+        assert_eq!(capitalize_first("élodie"), "Élodie");
+        assert_eq!(capitalize_first("ßen"), "SSen");
+        assert_eq!(capitalize_first("ß"), "SS");
+    }
+
+    #[test]
+    fn capitalize_first_handles_empty() {
+        assert_eq!(capitalize_first(""), "");
+    }
+}
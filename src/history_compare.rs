@@ -0,0 +1,353 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use chrono::{Local, TimeZone};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use log::warn;
+use crate::{storage, DevDashboard};
+
+/// Minute averages are stored next to settings.json
+const ARCHIVE_FILE: &str = "metric_archive.json";
+/// A week back plus the longest range shown, so "last week" has a full day to compare
+const RETENTION_SECS: i64 = 8 * 24 * 60 * 60;
+const SAVE_INTERVAL: Duration = Duration::from_secs(10 * 60);
+/// Ranges the comparison window can show, in hours
+const RANGES: [i64; 3] = [1, 6, 24];
+const CHART_HEIGHT: f32 = 140.0;
+/// Minutes further apart than this were paused or the dashboard was closed; the line breaks between them
+const MAX_GAP_SECS: i64 = 3 * 60;
+
+/// Readings kept as minute averages for comparing against earlier days
+#[derive(Clone, Copy, PartialEq)]
+pub enum ArchiveMetric {
+    CpuUsage,
+    MemoryUsage,
+    GpuUsage,
+    Network,                        // Download and upload together, bytes per second
+    CpuTemperature,
+    GpuTemperature,
+}
+
+impl ArchiveMetric {
+    const ALL: [ArchiveMetric; 6] = [
+        ArchiveMetric::CpuUsage,
+        ArchiveMetric::MemoryUsage,
+        ArchiveMetric::GpuUsage,
+        ArchiveMetric::Network,
+        ArchiveMetric::CpuTemperature,
+        ArchiveMetric::GpuTemperature,
+    ];
+
+    /// Position in a sample's values
+    fn index(&self) -> usize {
+        *self as usize
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            ArchiveMetric::CpuUsage => "CPU",
+            ArchiveMetric::MemoryUsage => "Memory",
+            ArchiveMetric::GpuUsage => "GPU",
+            ArchiveMetric::Network => "Network",
+            ArchiveMetric::CpuTemperature => "CPU temperature",
+            ArchiveMetric::GpuTemperature => "GPU temperature",
+        }
+    }
+
+    fn color(&self) -> Color32 {
+        match self {
+            ArchiveMetric::CpuUsage => Color32::from_rgb(37, 99, 235),
+            ArchiveMetric::MemoryUsage => Color32::from_rgb(22, 163, 74),
+            ArchiveMetric::GpuUsage => Color32::from_rgb(220, 38, 38),
+            ArchiveMetric::Network => Color32::from_rgb(14, 165, 233),
+            ArchiveMetric::CpuTemperature | ArchiveMetric::GpuTemperature => Color32::from_rgb(202, 138, 4),
+        }
+    }
+
+    /// Fixed scale for percentages; the others scale to what is shown
+    fn range(&self) -> Option<(f32, f32)> {
+        match self {
+            ArchiveMetric::CpuUsage | ArchiveMetric::MemoryUsage | ArchiveMetric::GpuUsage => Some((0.0, 100.0)),
+            _ => None,
+        }
+    }
+
+    fn format(&self, value: f32) -> String {
+        match self {
+            ArchiveMetric::CpuUsage | ArchiveMetric::MemoryUsage | ArchiveMetric::GpuUsage => format!("{:.0}%", value),
+            ArchiveMetric::CpuTemperature | ArchiveMetric::GpuTemperature => format!("{:.0}°C", value),
+            ArchiveMetric::Network => {
+                let (value, unit) = DevDashboard::format_bytes(value as u64);
+                format!("{:.1} {}/s", value, unit)
+            }
+        }
+    }
+}
+
+/// The earlier curve drawn over the current one
+#[derive(Clone, Copy, PartialEq)]
+enum Overlay {
+    None,
+    Yesterday,
+    LastWeek,
+}
+
+impl Overlay {
+    fn label(&self) -> &'static str {
+        match self {
+            Overlay::None => "None",
+            Overlay::Yesterday => "Yesterday",
+            Overlay::LastWeek => "Last week",
+        }
+    }
+
+    fn shift_secs(&self) -> Option<i64> {
+        match self {
+            Overlay::None => None,
+            Overlay::Yesterday => Some(24 * 60 * 60),
+            Overlay::LastWeek => Some(7 * 24 * 60 * 60),
+        }
+    }
+}
+
+/// Averages over one minute, starting at a Unix timestamp; values follow ArchiveMetric::ALL
+#[derive(Serialize, Deserialize, Clone, Copy)]
+struct MinuteSample {
+    time: i64,
+    values: [Option<f32>; 6],
+}
+
+/// Readings of the minute in progress
+#[derive(Default)]
+struct MinuteTotals {
+    minute: i64,
+    sums: [f64; 6],
+    counts: [u32; 6],
+}
+
+/// A week of minute averages persisted across restarts, and the comparison window's choices
+pub struct MetricArchive {
+    samples: VecDeque<MinuteSample>,    // Oldest first
+    current: MinuteTotals,
+    last_save: Instant,
+    open: bool,
+    metric: ArchiveMetric,
+    hours: i64,
+    overlay: Overlay,
+}
+
+impl MetricArchive {
+    pub fn load() -> Self {
+        let samples = storage::load_json(ARCHIVE_FILE);
+        Self {
+            samples,
+            current: MinuteTotals::default(),
+            last_save: Instant::now(),
+            open: false,
+            metric: ArchiveMetric::CpuUsage,
+            hours: RANGES[2],
+            overlay: Overlay::Yesterday,
+        }
+    }
+
+    fn save(&self) {
+        // A week of minutes; compact, since nobody reads this file by hand
+        if let Ok(json) = serde_json::to_string(&self.samples) {
+            if let Err(e) = storage::write_atomic(ARCHIVE_FILE, json.as_bytes()) {
+                warn!("Could not save {}: {}", ARCHIVE_FILE, e);
+            }
+        }
+    }
+
+    /// Adds a second's readings, closing the previous minute once a new one starts
+    fn record(&mut self, now: i64, readings: [Option<f32>; 6]) {
+        let minute = now - now.rem_euclid(60);
+        if minute != self.current.minute {
+            let totals = std::mem::replace(&mut self.current, MinuteTotals { minute, ..Default::default() });
+            if totals.counts.iter().any(|count| *count > 0) {
+                let mut values = [None; 6];
+                for (index, value) in values.iter_mut().enumerate() {
+                    if totals.counts[index] > 0 {
+                        *value = Some((totals.sums[index] / totals.counts[index] as f64) as f32);
+                    }
+                }
+                self.samples.push_back(MinuteSample { time: totals.minute, values });
+            }
+            while self.samples.front().is_some_and(|sample| now - sample.time > RETENTION_SECS) {
+                self.samples.pop_front();
+            }
+        }
+        for (index, reading) in readings.iter().enumerate() {
+            if let Some(value) = reading {
+                self.current.sums[index] += *value as f64;
+                self.current.counts[index] += 1;
+            }
+        }
+        if self.last_save.elapsed() >= SAVE_INTERVAL {
+            self.last_save = Instant::now();
+            self.save();
+        }
+    }
+
+    /// Minute values of the metric between two timestamps, shifted forward by `shift` seconds
+    fn series(&self, metric: ArchiveMetric, from: i64, to: i64, shift: i64) -> Vec<(i64, f32)> {
+        self.samples.iter()
+            .filter(|sample| sample.time >= from && sample.time <= to)
+            .filter_map(|sample| sample.values[metric.index()].map(|value| (sample.time + shift, value)))
+            .collect()
+    }
+}
+
+/// Asks for the comparison window from a card, which only borrows the dashboard
+pub fn request_compare(ui: &egui::Ui, metric: ArchiveMetric) {
+    ui.data_mut(|data| data.insert_temp(egui::Id::new("history_compare_request"), Some(metric)));
+}
+
+fn average(series: &[(i64, f32)]) -> Option<f32> {
+    (!series.is_empty()).then(|| series.iter().map(|(_, value)| value).sum::<f32>() / series.len() as f32)
+}
+
+/// Draws a series as a line broken at gaps
+fn draw_series(painter: &egui::Painter, series: &[(i64, f32)], point: impl Fn(i64, f32) -> egui::Pos2, stroke: egui::Stroke) {
+    let mut line: Vec<egui::Pos2> = Vec::new();
+    let mut previous: Option<i64> = None;
+    for (time, value) in series {
+        if previous.is_some_and(|previous| time - previous > MAX_GAP_SECS) {
+            if line.len() > 1 {
+                painter.add(egui::Shape::line(std::mem::take(&mut line), stroke));
+            }
+            line.clear();
+        }
+        line.push(point(*time, *value));
+        previous = Some(*time);
+    }
+    if line.len() > 1 {
+        painter.add(egui::Shape::line(line, stroke));
+    }
+}
+
+/// The reading closest to a time, if one is within a gap of it
+fn nearest(series: &[(i64, f32)], time: i64) -> Option<(i64, f32)> {
+    series.iter()
+        .min_by_key(|(sample, _)| (sample - time).abs())
+        .filter(|(sample, _)| (sample - time).abs() <= MAX_GAP_SECS)
+        .copied()
+}
+
+impl DevDashboard {
+    /// Adds the current readings to the minute averages; called once a second
+    pub(crate) fn update_metric_archive(&mut self) {
+        let network = self.network_stats.values()
+            .map(|stats| (stats.received_speed + stats.sent_speed) as f32)
+            .sum::<f32>();
+        let gpu = self.gpu_info.as_ref();
+        let readings = [
+            Some(self.current_cpu_usage.target),
+            Some(self.memory_usage.target * 100.0),
+            gpu.and_then(|gpu| gpu.utilization),
+            Some(network),
+            self.throttle_monitor.status.and_then(|status| status.temperature),
+            gpu.and_then(|gpu| gpu.temperature).map(|temperature| temperature as f32),
+        ];
+        self.metric_archive.record(Local::now().timestamp(), readings);
+    }
+
+    /// Today's curve with yesterday's or last week's drawn over it at the same times of day
+    pub(crate) fn show_history_compare_window(&mut self, ctx: &egui::Context) {
+        let request_id = egui::Id::new("history_compare_request");
+        if let Some(metric) = ctx.data_mut(|data| data.get_temp::<Option<ArchiveMetric>>(request_id)).flatten() {
+            ctx.data_mut(|data| data.remove::<Option<ArchiveMetric>>(request_id));
+            self.metric_archive.metric = metric;
+            self.metric_archive.open = true;
+        }
+        if !self.metric_archive.open {
+            return;
+        }
+
+        let grey = Color32::from_rgb(156, 163, 175);
+        let mut open = true;
+        let archive = &mut self.metric_archive;
+        egui::Window::new("History Comparison")
+            .open(&mut open)
+            .default_width(620.0)
+            .show(ctx, |ui| {
+                ui.horizontal_wrapped(|ui| {
+                    for metric in ArchiveMetric::ALL {
+                        ui.selectable_value(&mut archive.metric, metric, metric.label());
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Range:");
+                    for hours in RANGES {
+                        ui.selectable_value(&mut archive.hours, hours, format!("{}h", hours));
+                    }
+                    ui.add_space(12.0);
+                    ui.label("Compare with:");
+                    for overlay in [Overlay::None, Overlay::Yesterday, Overlay::LastWeek] {
+                        ui.selectable_value(&mut archive.overlay, overlay, overlay.label());
+                    }
+                });
+                ui.add_space(4.0);
+
+                let metric = archive.metric;
+                let now = Local::now().timestamp();
+                let span = archive.hours * 60 * 60;
+                let current = archive.series(metric, now - span, now, 0);
+                let earlier = archive.overlay.shift_secs()
+                    .map(|shift| archive.series(metric, now - span - shift, now - shift, shift))
+                    .unwrap_or_default();
+                if current.is_empty() && earlier.is_empty() {
+                    ui.label(RichText::new("No readings in this range yet; one is kept per minute while the dashboard runs")
+                        .color(Color32::from_rgb(107, 114, 128)));
+                    return;
+                }
+
+                let (min, max) = metric.range().unwrap_or_else(|| {
+                    let max = current.iter().chain(&earlier).map(|(_, value)| *value).fold(0.0, f32::max);
+                    (0.0, (max * 1.1).max(1.0))
+                });
+                let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), CHART_HEIGHT), egui::Sense::hover());
+                let painter = ui.painter_at(rect);
+                painter.rect_filled(rect, 4.0, Color32::from_rgb(17, 24, 39));
+                let point = |time: i64, value: f32| egui::pos2(
+                    rect.right() - (now - time) as f32 / span as f32 * rect.width(),
+                    rect.bottom() - ((value - min) / (max - min)).clamp(0.0, 1.0) * rect.height(),
+                );
+                // The earlier day goes underneath, faded, so today's curve stays readable
+                draw_series(&painter, &earlier, point, egui::Stroke::new(1.5, grey.gamma_multiply(0.6)));
+                draw_series(&painter, &current, point, egui::Stroke::new(1.5, metric.color()));
+
+                let font = egui::FontId::proportional(11.0);
+                painter.text(rect.right_top() + egui::vec2(-4.0, 2.0), egui::Align2::RIGHT_TOP, metric.format(max), font.clone(), grey);
+                if let Some(pos) = response.hover_pos() {
+                    let time = now - ((rect.right() - pos.x) / rect.width() * span as f32) as i64;
+                    painter.line_segment([egui::pos2(pos.x, rect.top()), egui::pos2(pos.x, rect.bottom())], egui::Stroke::new(1.0, grey));
+                    let clock = Local.timestamp_opt(time, 0).single().map(|time| time.format("%H:%M").to_string()).unwrap_or_default();
+                    let mut readout = clock;
+                    if let Some((at, value)) = nearest(&current, time) {
+                        painter.circle_filled(point(at, value), 3.0, metric.color());
+                        readout.push_str(&format!("  now {}", metric.format(value)));
+                    }
+                    if let Some((at, value)) = nearest(&earlier, time) {
+                        painter.circle_filled(point(at, value), 3.0, grey);
+                        readout.push_str(&format!("  {} {}", archive.overlay.label().to_lowercase(), metric.format(value)));
+                    }
+                    painter.text(rect.left_top() + egui::vec2(4.0, 2.0), egui::Align2::LEFT_TOP, readout, font, Color32::WHITE);
+                }
+
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    if let Some(value) = average(&current) {
+                        ui.label(RichText::new(format!("Average {}", metric.format(value))).color(metric.color()));
+                    }
+                    if let Some(value) = average(&earlier) {
+                        ui.label(RichText::new(format!("{} {}", archive.overlay.label(), metric.format(value))).color(grey));
+                    }
+                });
+            });
+        if !open {
+            self.metric_archive.open = false;
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use std::time::{Duration, Instant};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use windows::Win32::Graphics::Dxgi::{CreateDXGIFactory1, IDXGIFactory1, DXGI_ADAPTER_DESC1, DXGI_ADAPTER_FLAG_SOFTWARE};
+use log::{debug, info};
+use crate::DevDashboard;
+
+/// Displays can move between GPUs when a monitor is plugged in or the laptop docks
+const RESCAN_INTERVAL: Duration = Duration::from_secs(10);
+/// Integrated GPUs report little or no dedicated memory, carved out of system RAM
+const INTEGRATED_MEMORY: u64 = 512 * 1024 * 1024;
+#[cfg(feature = "nvml")]
+const NVIDIA_VENDOR: u32 = 0x10de;
+
+/// Which GPU the GPU card shows
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct GpuSelectionSettings {
+    pub pinned: String,             // GpuAdapter::key, empty to pick automatically
+}
+
+/// A hardware graphics adapter as DXGI lists it
+#[derive(Clone, PartialEq)]
+pub struct GpuAdapter {
+    pub name: String,
+    pub vendor_id: u32,
+    pub device_id: u32,
+    pub luid: (i32, u32),           // High and low parts, as performance counters name the adapter
+    pub dedicated_memory: u64,
+    pub outputs: usize,             // Displays connected to it
+}
+
+impl GpuAdapter {
+    /// Vendor and device ID, stable across restarts and driver updates
+    pub fn key(&self) -> String {
+        format!("{:04x}:{:04x}", self.vendor_id, self.device_id)
+    }
+
+    #[cfg(feature = "nvml")]
+    pub fn is_nvidia(&self) -> bool {
+        self.vendor_id == NVIDIA_VENDOR
+    }
+
+    pub fn is_integrated(&self) -> bool {
+        self.dedicated_memory < INTEGRATED_MEMORY
+    }
+
+    /// Whether a PNP device ID such as `PCI\VEN_10DE&DEV_1F99&...` is this adapter
+    pub fn matches_pnp_id(&self, pnp_id: &str) -> bool {
+        pnp_id.to_uppercase().contains(&format!("VEN_{:04X}&DEV_{:04X}", self.vendor_id, self.device_id))
+    }
+
+    /// Part of the GPU performance counter instance names, like `luid_0x00000000_0x0000d1b6`
+    pub fn luid_tag(&self) -> String {
+        format!("luid_0x{:08x}_0x{:08x}", self.luid.0, self.luid.1)
+    }
+}
+
+/// Hardware adapters, leaving out the Basic Render Driver and other software adapters
+pub fn adapters() -> Vec<GpuAdapter> {
+    let mut adapters = Vec::new();
+    let Ok(factory) = (unsafe { CreateDXGIFactory1::<IDXGIFactory1>() }) else { return adapters };
+    let mut index = 0;
+    while let Ok(adapter) = unsafe { factory.EnumAdapters1(index) } {
+        index += 1;
+        let mut desc = DXGI_ADAPTER_DESC1::default();
+        if unsafe { adapter.GetDesc1(&mut desc) }.is_err() || desc.Flags & DXGI_ADAPTER_FLAG_SOFTWARE.0 != 0 {
+            continue;
+        }
+        let mut outputs = 0;
+        while unsafe { adapter.EnumOutputs(outputs) }.is_ok() {
+            outputs += 1;
+        }
+        let length = desc.Description.iter().position(|&c| c == 0).unwrap_or(desc.Description.len());
+        adapters.push(GpuAdapter {
+            name: String::from_utf16_lossy(&desc.Description[..length]),
+            vendor_id: desc.VendorId,
+            device_id: desc.DeviceId,
+            luid: (desc.AdapterLuid.HighPart, desc.AdapterLuid.LowPart),
+            dedicated_memory: desc.DedicatedVideoMemory as u64,
+            outputs: outputs as usize,
+        });
+    }
+    adapters
+}
+
+/// The adapters found at the last scan
+#[derive(Default)]
+pub struct GpuAdapters {
+    pub list: Vec<GpuAdapter>,
+    last_scan: Option<Instant>,
+}
+
+impl DevDashboard {
+    /// Re-lists the adapters now and then, to follow displays moving between GPUs
+    pub(crate) fn update_gpu_adapters(&mut self) {
+        if self.gpu_adapters.last_scan.is_some_and(|time| time.elapsed() < RESCAN_INTERVAL) {
+            return;
+        }
+        self.gpu_adapters.last_scan = Some(Instant::now());
+        let list = adapters();
+        if list != self.gpu_adapters.list {
+            debug!("GPU adapters: {}", list.iter().map(|adapter| adapter.name.as_str()).collect::<Vec<_>>().join(", "));
+            self.gpu_adapters.list = list;
+        }
+    }
+
+    /// Pins the GPU card to an adapter, or back to automatic with None
+    pub(crate) fn pin_gpu(&mut self, adapter: Option<&GpuAdapter>) {
+        self.settings.gpu_selection.pinned = adapter.map(GpuAdapter::key).unwrap_or_default();
+        info!("GPU card pinned to {}", adapter.map_or("automatic", |adapter| adapter.name.as_str()));
+        self.save_settings();
+        self.gpu_info = Self::initialize_gpu(&self.settings.gpu_selection);
+    }
+
+    /// GPU card section on hybrid systems: each adapter, which one drives the display, and the pin.
+    /// Returns the new pin when one was clicked, with None for automatic.
+    pub(crate) fn show_gpu_adapters(&self, ui: &mut egui::Ui) -> Option<Option<GpuAdapter>> {
+        if self.gpu_adapters.list.len() < 2 {
+            return None;
+        }
+        let grey = Color32::from_rgb(156, 163, 175);
+        let pinned = &self.settings.gpu_selection.pinned;
+        let mut pin = None;
+        ui.add_space(8.0);
+        ui.label("Adapters:");
+        for adapter in &self.gpu_adapters.list {
+            ui.horizontal(|ui| {
+                let key = adapter.key();
+                let is_pinned = *pinned == key;
+                let hover = if is_pinned { "Unpin to pick the GPU automatically" } else { "Show this GPU on the card" };
+                if ui.selectable_label(is_pinned, "📌").on_hover_text(hover).clicked() {
+                    pin = Some(if is_pinned { None } else { Some(adapter.clone()) });
+                }
+                ui.label(&adapter.name);
+                ui.label(RichText::new(if adapter.is_integrated() { "integrated" } else { "discrete" }).color(grey));
+                if adapter.outputs > 0 {
+                    ui.label(RichText::new("drives the display").color(Color32::from_rgb(22, 163, 74)))
+                        .on_hover_text(format!("{} display{} connected", adapter.outputs, if adapter.outputs == 1 { "" } else { "s" }));
+                }
+            });
+        }
+        if pinned.is_empty() {
+            ui.label(RichText::new("Not pinned; the discrete GPU is shown").small().color(grey));
+        }
+        pin
+    }
+}
@@ -0,0 +1,138 @@
+use std::time::{Duration, Instant};
+use eframe::egui;
+use serde::{Serialize, Deserialize};
+use windows::Win32::System::SystemInformation::GetTickCount;
+use windows::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+use windows::Win32::UI::Shell::{
+    SHQueryUserNotificationState, QUNS_BUSY, QUNS_PRESENTATION_MODE, QUNS_RUNNING_D3D_FULL_SCREEN,
+};
+use log::info;
+use crate::DevDashboard;
+
+/// How often the shell is asked whether a fullscreen app is running
+const FULLSCREEN_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+/// Collection interval used while throttled (away or a fullscreen app is running)
+pub const IDLE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Persisted idle detection configuration
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct IdleSettings {
+    pub away_minutes: u32,          // No input for this long counts as away
+    pub pause_when_away: bool,      // Slow down collection and repaints while away
+    pub pause_in_fullscreen: bool,  // Same while a game or other fullscreen app is in front
+}
+
+impl Default for IdleSettings {
+    fn default() -> Self {
+        Self {
+            away_minutes: 5,
+            pause_when_away: false,
+            pause_in_fullscreen: true,
+        }
+    }
+}
+
+/// Tracks whether the user is currently away from the machine
+#[derive(Default)]
+pub struct IdleMonitor {
+    away_since: Option<Instant>,
+    fullscreen: bool,               // Another app is running fullscreen or in presentation mode
+    last_fullscreen_check: Option<Instant>,
+}
+
+impl IdleMonitor {
+    pub fn is_away(&self) -> bool {
+        self.away_since.is_some()
+    }
+
+    /// Overlays and popups should stay hidden while this is set
+    pub fn is_fullscreen(&self) -> bool {
+        self.fullscreen
+    }
+}
+
+/// Whether a fullscreen game, video, or presentation other than the dashboard itself is in front
+fn fullscreen_app_active() -> bool {
+    let state = unsafe { SHQueryUserNotificationState() };
+    let fullscreen = matches!(state, Ok(state)
+        if state == QUNS_BUSY || state == QUNS_RUNNING_D3D_FULL_SCREEN || state == QUNS_PRESENTATION_MODE);
+    fullscreen && crate::usage::foreground_pid() != Some(std::process::id())
+}
+
+/// Time since the last keyboard or mouse input anywhere in the session
+pub fn idle_time() -> Duration {
+    let mut info = LASTINPUTINFO {
+        cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+        dwTime: 0,
+    };
+    unsafe {
+        if !GetLastInputInfo(&mut info).as_bool() {
+            return Duration::ZERO;
+        }
+        // Both counters wrap after ~49 days
+        Duration::from_millis(GetTickCount().wrapping_sub(info.dwTime) as u64)
+    }
+}
+
+impl DevDashboard {
+    /// Updates away status from the system idle time and checks for fullscreen apps.
+    /// Runs every frame so returning input is noticed right away.
+    pub(crate) fn update_idle(&mut self) {
+        let away = idle_time() >= Duration::from_secs(self.settings.idle.away_minutes.max(1) as u64 * 60);
+        match (away, self.idle.away_since) {
+            (true, None) => {
+                info!("User away, idle for {} minutes", self.settings.idle.away_minutes);
+                self.idle.away_since = Some(Instant::now());
+                self.usage.pause();
+            }
+            (false, Some(since)) => {
+                info!("User back after {} minutes away", since.elapsed().as_secs() / 60);
+                self.idle.away_since = None;
+            }
+            _ => {}
+        }
+
+        if matches!(self.idle.last_fullscreen_check, Some(last) if last.elapsed() < FULLSCREEN_CHECK_INTERVAL) {
+            return;
+        }
+        self.idle.last_fullscreen_check = Some(Instant::now());
+        let fullscreen = fullscreen_app_active();
+        if fullscreen != self.idle.fullscreen {
+            info!("Fullscreen app {}", if fullscreen { "started, throttling collection" } else { "closed" });
+            self.idle.fullscreen = fullscreen;
+        }
+    }
+
+    /// Whether high-frequency collection and animations should be throttled
+    pub(crate) fn is_throttled(&self) -> bool {
+        // The wall display is watched without touching the input, and is itself fullscreen
+        if self.wall.open {
+            return false;
+        }
+        // The compact overlay is meant to be watched over a fullscreen game or build
+        (self.settings.idle.pause_when_away && self.idle.is_away())
+            || (self.settings.idle.pause_in_fullscreen && self.idle.is_fullscreen() && !self.settings.overlay.open)
+    }
+
+    /// Settings section for idle detection
+    pub(crate) fn show_idle_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            ui.label("Away after (minutes idle):");
+            changed |= ui.add(egui::DragValue::new(&mut self.settings.idle.away_minutes)
+                .clamp_range(1..=120)).changed();
+        });
+        changed |= ui.checkbox(&mut self.settings.idle.pause_when_away, "Pause collection and animations while away")
+            .on_hover_text("Refreshes every 10 seconds instead of continuously until input resumes")
+            .changed();
+        changed |= ui.checkbox(&mut self.settings.idle.pause_in_fullscreen, "Throttle while a game or fullscreen app is running")
+            .on_hover_text("Also skips installed-app scans so the dashboard doesn't cost frames")
+            .changed();
+
+        if changed {
+            self.save_settings();
+        }
+    }
+}
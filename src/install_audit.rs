@@ -0,0 +1,225 @@
+use std::path::Path;
+use std::sync::mpsc::{channel, Receiver};
+use eframe::egui;
+use egui::{Color32, RichText};
+use winreg::enums::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE, KEY_ALL_ACCESS, KEY_READ};
+use winreg::RegKey;
+use log::{debug, info, warn};
+use crate::elevation::ElevatedAction;
+use crate::guard::SensitiveAction;
+use crate::known_folders;
+use crate::DevDashboard;
+
+/// Uninstall entries of per-machine installs, for 64-bit and 32-bit programs
+pub const MACHINE_UNINSTALL_KEYS: &[&str] = &[
+    r"SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall",
+    r"SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\Uninstall",
+];
+/// Uninstall entries of per-user installs
+const USER_UNINSTALL_KEY: &str = r"SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall";
+
+/// Where an app was found by its registry keys and files
+#[derive(Clone, Copy, Default, PartialEq)]
+pub struct InstallScopes {
+    pub machine: bool,
+    pub user: bool,
+}
+
+impl InstallScopes {
+    /// Installed both for everyone and for the current user, usually two copies that fight over updates
+    pub fn is_duplicate(&self) -> bool {
+        self.machine && self.user
+    }
+}
+
+/// Whether a detection path is inside a user profile rather than Program Files
+pub fn is_per_user(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    let resolved = known_folders::resolve(path).to_lowercase();
+    let in_profile = known_folders::profile().is_some_and(|profile| resolved.starts_with(&profile.to_lowercase()));
+    lower.contains("%username%") || in_profile || lower.contains(r"\appdata\")
+}
+
+/// Whether a detection registry key is an uninstall entry, the only keys that say which scope an install has
+pub fn is_uninstall_key(key: &str) -> bool {
+    key.to_lowercase().contains(r"\currentversion\uninstall\")
+}
+
+/// An uninstall entry whose InstallLocation no longer exists, left behind by a manual delete or a failed uninstall
+#[derive(Clone, PartialEq, Debug)]
+pub struct OrphanedEntry {
+    pub name: String,
+    pub location: String,
+    pub key: String,                // Full path below its hive
+    pub per_user: bool,             // In HKCU, which needs no elevation to remove
+}
+
+fn scan_key(root: &RegKey, key_path: &str, per_user: bool, orphans: &mut Vec<OrphanedEntry>) {
+    let Ok(uninstall) = root.open_subkey_with_flags(key_path, KEY_READ) else { return };
+    for subkey in uninstall.enum_keys().flatten() {
+        let Ok(app_key) = uninstall.open_subkey(&subkey) else { continue };
+        let (Ok(name), Ok(location)) = (app_key.get_value::<String, _>("DisplayName"), app_key.get_value::<String, _>("InstallLocation")) else {
+            continue;
+        };
+        // Some installers write the location quoted or with a trailing backslash
+        let location = location.trim().trim_matches('"').to_string();
+        if location.is_empty() || Path::new(&location).is_dir() {
+            continue;
+        }
+        debug!("Orphaned uninstall entry {}: {} is missing", name, location);
+        orphans.push(OrphanedEntry { name, location, key: format!(r"{}\{}", key_path, subkey), per_user });
+    }
+}
+
+/// Every uninstall entry, per-machine and per-user, whose install folder is gone
+fn scan() -> Vec<OrphanedEntry> {
+    let mut orphans = Vec::new();
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+    for key_path in MACHINE_UNINSTALL_KEYS {
+        scan_key(&hklm, key_path, false, &mut orphans);
+    }
+    scan_key(&RegKey::predef(HKEY_CURRENT_USER), USER_UNINSTALL_KEY, true, &mut orphans);
+    orphans.sort_by_key(|entry| entry.name.to_lowercase());
+    orphans
+}
+
+/// Leftover scan results and the entry waiting for a confirmed removal
+#[derive(Default)]
+pub struct InstallAudit {
+    orphans: Option<Vec<OrphanedEntry>>,
+    receiver: Option<Receiver<Vec<OrphanedEntry>>>,
+    confirm: Option<String>,                            // Key of the entry whose Remove was clicked once
+    removal: Option<Receiver<(String, Result<(), String>)>>,
+    status: Option<(String, bool)>,                     // Message and whether it failed
+}
+
+impl DevDashboard {
+    pub(crate) fn remove_orphaned_entry(&mut self, entry: OrphanedEntry) {
+        info!("Removing orphaned uninstall entry {} ({})", entry.name, entry.key);
+        let (sender, receiver) = channel();
+        self.install_audit.removal = Some(receiver);
+        if entry.per_user {
+            let result = RegKey::predef(HKEY_CURRENT_USER)
+                .open_subkey_with_flags(USER_UNINSTALL_KEY, KEY_ALL_ACCESS)
+                .and_then(|uninstall| uninstall.delete_subkey_all(entry.key.rsplit('\\').next().unwrap_or_default()))
+                .map_err(|e| e.to_string());
+            sender.send((entry.key, result)).ok();
+            return;
+        }
+        let broker = self.elevation.clone();
+        self.runtime().spawn(async move {
+            let result = broker.run(ElevatedAction::DeleteUninstallEntry(entry.key.clone())).await;
+            sender.send((entry.key, result)).ok();
+        });
+    }
+
+    /// Installer page section listing duplicate installs and leftover uninstall entries
+    pub(crate) fn show_install_cleanup(&mut self, ui: &mut egui::Ui) {
+        let audit = &mut self.install_audit;
+        if let Some(receiver) = &audit.receiver {
+            if let Ok(orphans) = receiver.try_recv() {
+                info!("Found {} orphaned uninstall entries", orphans.len());
+                audit.orphans = Some(orphans);
+                audit.receiver = None;
+            }
+        }
+        if let Some(receiver) = &audit.removal {
+            if let Ok((key, result)) = receiver.try_recv() {
+                audit.status = Some(match result {
+                    Ok(()) => {
+                        if let Some(orphans) = &mut audit.orphans {
+                            orphans.retain(|entry| entry.key != key);
+                        }
+                        ("Removed the leftover entry".to_string(), false)
+                    }
+                    Err(e) => {
+                        warn!("Could not remove {}: {}", key, e);
+                        (format!("Could not remove the entry: {}", e), true)
+                    }
+                });
+                audit.removal = None;
+            }
+        }
+
+        let duplicates: Vec<String> = self.ninite_apps.iter()
+            .filter(|app| app.installed && app.scopes.is_duplicate())
+            .map(|app| app.name.clone())
+            .collect();
+        let orphan_count = self.install_audit.orphans.as_ref().map_or(0, Vec::len);
+        let header = match duplicates.len() + orphan_count {
+            0 => "Install Cleanup".to_string(),
+            count => format!("Install Cleanup ({})", count),
+        };
+        let grey = Color32::from_rgb(107, 114, 128);
+        let mut remove = None;
+
+        egui::CollapsingHeader::new(header).id_source("install_cleanup").show(ui, |ui| {
+            if duplicates.is_empty() {
+                ui.label(RichText::new("No apps are installed both per-user and per-machine").color(grey));
+            }
+            for name in &duplicates {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(format!("{} is installed per-user and per-machine", name)).color(Color32::from_rgb(202, 138, 4)));
+                    if ui.button("Installed apps")
+                        .on_hover_text("Uninstall the copy you don't use; the per-user one is usually the stray")
+                        .clicked() {
+                        let _ = std::process::Command::new("explorer").arg("ms-settings:appsfeatures").spawn();
+                    }
+                });
+            }
+
+            ui.add_space(8.0);
+            let audit = &mut self.install_audit;
+            ui.horizontal(|ui| {
+                let scanning = audit.receiver.is_some();
+                if ui.add_enabled(!scanning, egui::Button::new(if scanning { "Scanning..." } else { "Find leftover entries" }))
+                    .on_hover_text("Uninstall entries whose install folder no longer exists")
+                    .clicked() {
+                    let (sender, receiver) = channel();
+                    audit.receiver = Some(receiver);
+                    std::thread::spawn(move || {
+                        sender.send(scan()).ok();
+                    });
+                }
+                if let Some((message, failed)) = &audit.status {
+                    ui.label(RichText::new(message).color(if *failed { Color32::from_rgb(220, 50, 50) } else { grey }));
+                }
+            });
+            match &audit.orphans {
+                Some(orphans) if orphans.is_empty() => {
+                    ui.label(RichText::new("Every uninstall entry points at an existing folder").color(grey));
+                }
+                Some(orphans) => {
+                    let removing = audit.removal.is_some();
+                    for entry in orphans {
+                        ui.horizontal(|ui| {
+                            ui.label(&entry.name);
+                            ui.label(RichText::new(format!("{} is missing", entry.location)).color(grey))
+                                .on_hover_text(format!("{}\\{}", if entry.per_user { "HKCU" } else { "HKLM" }, entry.key));
+                            let confirming = audit.confirm.as_ref() == Some(&entry.key);
+                            let text = if confirming { "Confirm remove" } else { "Remove entry" };
+                            let hover = if entry.per_user {
+                                "Deletes the entry so it no longer shows in Installed apps"
+                            } else {
+                                "Deletes the entry so it no longer shows in Installed apps; needs administrator rights"
+                            };
+                            if ui.add_enabled(!removing, egui::Button::new(text)).on_hover_text(hover).clicked() {
+                                if confirming {
+                                    remove = Some(entry.clone());
+                                } else {
+                                    audit.confirm = Some(entry.key.clone());
+                                }
+                            }
+                        });
+                    }
+                }
+                None => {}
+            }
+        });
+
+        if let Some(entry) = remove {
+            self.install_audit.confirm = None;
+            self.request_sensitive_action(SensitiveAction::Uninstall(entry));
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use std::path::{Component, Path};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use crate::{DevDashboard, NiniteApp};
+
+/// Approximate download and installed size of one app, in megabytes
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct InstallSize {
+    pub download_mb: u64,
+    pub installed_mb: u64,
+}
+
+/// Built-in estimates by Ninite ID for current 64-bit releases; catalog entries can override them
+const ESTIMATES: &[(&str, u64, u64)] = &[
+    ("chrome", 110, 450),
+    ("firefox", 60, 230),
+    ("edge", 170, 600),
+    ("zoom", 90, 300),
+    ("discord", 100, 400),
+    ("vlc", 40, 170),
+    ("audacity", 15, 70),
+    ("blender", 300, 1100),
+    ("paintdotnet", 70, 200),
+    ("gimp", 300, 1000),
+    ("libreoffice", 340, 1300),
+    ("python", 27, 120),
+    ("filezilla", 12, 40),
+    ("notepadplusplus", 5, 15),
+    ("winscp", 11, 40),
+    ("putty", 4, 10),
+    ("vscode", 95, 380),
+    ("evernote", 180, 500),
+    ("googleearth", 70, 250),
+    ("7zip", 2, 6),
+    ("winrar", 4, 10),
+    ("qbittorrent", 33, 100),
+];
+
+pub fn estimate(ninite_id: &str) -> Option<InstallSize> {
+    ESTIMATES.iter()
+        .find(|(id, _, _)| *id == ninite_id)
+        .map(|&(_, download_mb, installed_mb)| InstallSize { download_mb, installed_mb })
+}
+
+/// Summed estimates for a group of apps
+#[derive(Default)]
+struct SizeTotal {
+    download_mb: u64,
+    installed_mb: u64,
+    count: usize,
+    unknown: usize,         // Apps without an estimate
+}
+
+impl SizeTotal {
+    fn of<'a>(apps: impl Iterator<Item = &'a NiniteApp>) -> Self {
+        let mut total = Self::default();
+        for app in apps {
+            total.count += 1;
+            match app.size {
+                Some(size) => {
+                    total.download_mb += size.download_mb;
+                    total.installed_mb += size.installed_mb;
+                }
+                None => total.unknown += 1,
+            }
+        }
+        total
+    }
+}
+
+fn mb_text(mb: u64) -> String {
+    let (value, unit) = DevDashboard::format_bytes(mb * 1024 * 1024);
+    format!("{:.1} {}", value, unit)
+}
+
+/// "C:\" for any path on that drive
+fn drive_root(path: &Path) -> Option<String> {
+    match path.components().next()? {
+        Component::Prefix(prefix) => Some(format!("{}\\", prefix.as_os_str().to_string_lossy())),
+        _ => None,
+    }
+}
+
+impl DevDashboard {
+    fn selected_ninite_apps(&self) -> impl Iterator<Item = &NiniteApp> {
+        self.ninite_apps.iter().filter(|app| !app.installed && self.selected_apps.contains(&app.name))
+    }
+
+    /// Header for a category in the installer list, with the estimate for its selected apps
+    pub(crate) fn category_header(&self, category: &str) -> String {
+        let total = SizeTotal::of(self.selected_ninite_apps().filter(|app| app.category == category));
+        if total.count == 0 {
+            category.to_string()
+        } else {
+            format!("{} ({} selected, ~{} download)", category, total.count, mb_text(total.download_mb))
+        }
+    }
+
+    /// Estimated sizes for the selection and a warning when a drive it needs is short of space
+    pub(crate) fn show_install_estimate(&self, ui: &mut egui::Ui) {
+        let total = SizeTotal::of(self.selected_ninite_apps());
+        if total.count == 0 {
+            return;
+        }
+        let mut summary = format!("Estimated: ~{} download, ~{} installed", mb_text(total.download_mb), mb_text(total.installed_mb));
+        if total.unknown > 0 {
+            summary.push_str(&format!(" ({} app{} without an estimate)", total.unknown, if total.unknown == 1 { "" } else { "s" }));
+        }
+        ui.label(RichText::new(summary).color(Color32::from_rgb(156, 163, 175)));
+
+        // Ninite downloads into the temp folder and installs under Program Files
+        let program_files = std::env::var("ProgramFiles").unwrap_or_else(|_| r"C:\Program Files".to_string());
+        let install_drive = drive_root(Path::new(&program_files)).unwrap_or_else(|| r"C:\".to_string());
+        let download_drive = drive_root(&std::env::temp_dir()).unwrap_or_else(|| install_drive.clone());
+        let mut needs = vec![(install_drive, total.installed_mb)];
+        if needs[0].0.eq_ignore_ascii_case(&download_drive) {
+            needs[0].1 += total.download_mb;
+        } else {
+            needs.push((download_drive, total.download_mb));
+        }
+
+        for (drive, needed_mb) in needs {
+            let Some((_, free)) = Self::get_disk_space(&drive) else { continue };
+            if free < needed_mb * 1024 * 1024 {
+                ui.colored_label(Color32::from_rgb(220, 50, 50), format!("⚠ Not enough space on {}: needs about {}, {} free",
+                    drive, mb_text(needed_mb), mb_text(free / (1024 * 1024))));
+            }
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use std::io::Write;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use eframe::egui;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::net::windows::named_pipe::{NamedPipeServer, ServerOptions};
+use tokio::sync::oneshot;
+use windows::Win32::UI::WindowsAndMessaging::{AllowSetForegroundWindow, ASFW_ANY};
+use log::{debug, info, warn};
+use crate::cli::StartupOptions;
+use crate::DevDashboard;
+
+/// ERROR_ACCESS_DENIED, returned when the first-instance pipe already exists
+const ACCESS_DENIED: i32 = 5;
+/// ERROR_PIPE_BUSY, while the running dashboard is between two forwarded starts
+const PIPE_BUSY: i32 = 231;
+
+/// Stops the listener and reports once its pipe is closed
+static RELEASE: Mutex<Option<(oneshot::Sender<()>, Receiver<()>)>> = Mutex::new(None);
+
+/// One dashboard per account and profile; each profile has its own settings, so they can run side by side
+fn pipe_name(profile: Option<&str>) -> String {
+    match profile {
+        Some(profile) => format!(r"\\.\pipe\dev_dashboard_instance_{}_{}", whoami::username(), profile),
+        None => format!(r"\\.\pipe\dev_dashboard_instance_{}", whoami::username()),
+    }
+}
+
+/// Arguments forwarded by later starts, and the context to wake the window with when one arrives
+#[derive(Default)]
+pub struct InstanceListener {
+    receiver: Option<Receiver<Vec<String>>>,
+    repaint: Arc<OnceLock<egui::Context>>,
+}
+
+/// Accepts one start after another, reading each one's arguments as a JSON line
+async fn listen(mut server: NamedPipeServer, name: String, sender: Sender<Vec<String>>, repaint: Arc<OnceLock<egui::Context>>) {
+    loop {
+        if let Err(e) = server.connect().await {
+            warn!("Single-instance pipe stopped: {}", e);
+            return;
+        }
+        // The next start can connect while this one is being read
+        let next = match ServerOptions::new().reject_remote_clients(true).create(&name) {
+            Ok(next) => next,
+            Err(e) => {
+                warn!("Single-instance pipe stopped: {}", e);
+                return;
+            }
+        };
+        let mut line = String::new();
+        if BufReader::new(server).read_line(&mut line).await.is_ok() {
+            match serde_json::from_str::<Vec<String>>(&line) {
+                Ok(args) => {
+                    if sender.send(args).is_err() {
+                        return;
+                    }
+                    if let Some(ctx) = repaint.get() {
+                        ctx.request_repaint();
+                    }
+                }
+                Err(e) => debug!("Ignored a malformed forwarded start: {}", e),
+            }
+        }
+        server = next;
+    }
+}
+
+/// Sends the arguments to the running dashboard; false when there is none to take them
+fn forward(name: &str, args: &[String]) -> bool {
+    let Ok(line) = serde_json::to_string(args) else { return false };
+    for _ in 0..10 {
+        match std::fs::OpenOptions::new().write(true).open(name) {
+            Ok(mut pipe) => {
+                // The running dashboard may only come to the front when this start allows it
+                unsafe { AllowSetForegroundWindow(ASFW_ANY) };
+                return pipe.write_all(format!("{}\n", line).as_bytes()).is_ok();
+            }
+            Err(e) if e.raw_os_error() == Some(PIPE_BUSY) => std::thread::sleep(Duration::from_millis(100)),
+            Err(_) => return false,
+        }
+    }
+    false
+}
+
+/// Becomes the running dashboard, or hands the arguments to the one already running.
+/// Returns None when they were forwarded and this process should exit.
+pub fn claim(profile: Option<&str>, args: &[String]) -> Option<InstanceListener> {
+    let name = pipe_name(profile);
+    let (ready_sender, ready) = channel();
+    let (sender, receiver) = channel();
+    let repaint = Arc::new(OnceLock::new());
+    let thread_repaint = repaint.clone();
+    let thread_name = name.clone();
+    let (stop, stop_receiver) = oneshot::channel();
+    let (stopped_sender, stopped) = channel();
+    std::thread::spawn(move || {
+        let Ok(runtime) = tokio::runtime::Builder::new_current_thread().enable_all().build() else {
+            ready_sender.send(Err(None)).ok();
+            return;
+        };
+        runtime.block_on(async move {
+            // The first instance flag fails if another dashboard already owns the name
+            match ServerOptions::new().first_pipe_instance(true).reject_remote_clients(true).create(&thread_name) {
+                Ok(server) => {
+                    ready_sender.send(Ok(())).ok();
+                    tokio::select! {
+                        _ = listen(server, thread_name, sender, thread_repaint) => {}
+                        _ = stop_receiver => {}
+                    }
+                }
+                Err(e) => {
+                    ready_sender.send(Err(Some(e))).ok();
+                }
+            }
+        });
+        stopped_sender.send(()).ok();
+    });
+
+    match ready.recv() {
+        Ok(Ok(())) => {
+            if let Ok(mut release) = RELEASE.lock() {
+                *release = Some((stop, stopped));
+            }
+            Some(InstanceListener { receiver: Some(receiver), repaint })
+        }
+        Ok(Err(Some(e))) if e.raw_os_error() == Some(ACCESS_DENIED) => {
+            if forward(&name, args) {
+                info!("Dev Dashboard is already running, forwarded {:?} to it", args);
+                return None;
+            }
+            warn!("Dev Dashboard seems to be running but did not take the start, starting another");
+            Some(InstanceListener::default())
+        }
+        Ok(Err(e)) => {
+            warn!("Could not check for a running dashboard: {}", e.map(|e| e.to_string()).unwrap_or_default());
+            Some(InstanceListener::default())
+        }
+        Err(_) => Some(InstanceListener::default()),
+    }
+}
+
+/// Gives up the single-instance name, so a replacement started by the crash handler doesn't hand its start back here
+pub fn release() {
+    let Some((stop, stopped)) = RELEASE.lock().ok().and_then(|mut release| release.take()) else { return };
+    if stop.send(()).is_ok() {
+        stopped.recv_timeout(Duration::from_secs(2)).ok();
+    }
+}
+
+impl DevDashboard {
+    /// Brings the window forward and acts on the flags of a start that was forwarded here
+    pub(crate) fn update_instance(&mut self, ctx: &egui::Context) {
+        self.instance.repaint.get_or_init(|| ctx.clone());
+        let Some(receiver) = &self.instance.receiver else { return };
+        let Ok(args) = receiver.try_recv() else { return };
+        let (options, problems) = StartupOptions::parse(&args);
+        for problem in &problems {
+            warn!("Forwarded command line: {}", problem);
+        }
+        info!("Activated by another start with {:?}", args);
+        self.restore_from_tray();
+        // The profile already matched; the other flags apply as if the dashboard had just started
+        self.startup.queue(options);
+    }
+}
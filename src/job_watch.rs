@@ -0,0 +1,369 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Local};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use windows::Win32::Foundation::{BOOL, HWND, LPARAM};
+use windows::Win32::UI::WindowsAndMessaging::{EnumWindows, GetWindowTextW, IsWindowVisible};
+use log::{info, warn};
+use crate::notifications::NotificationSource;
+use crate::{toast, DevDashboard};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(2);
+/// New log output read per check, so a log that grows very fast cannot stall the UI
+const MAX_READ: u64 = 1024 * 1024;
+const MAX_EVENTS: usize = 50;
+/// A failing build can log many error lines; one notification per burst is enough
+const LOG_COOLDOWN: Duration = Duration::from_secs(30);
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum JobSource {
+    LogFile,        // Lines appended to a file
+    WindowTitle,    // Titles of visible top-level windows
+}
+
+impl JobSource {
+    fn label(&self) -> &'static str {
+        match self {
+            JobSource::LogFile => "Log file",
+            JobSource::WindowTitle => "Window title",
+        }
+    }
+}
+
+/// Patterns to look for in one log file or in window titles
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct JobWatcher {
+    pub enabled: bool,
+    pub name: String,
+    pub source: JobSource,
+    pub target: String,             // The log file path, or text the window title must contain
+    pub success_pattern: String,    // Case-insensitive; empty to not look for it
+    pub failure_pattern: String,
+}
+
+impl Default for JobWatcher {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            name: String::new(),
+            source: JobSource::LogFile,
+            target: String::new(),
+            success_pattern: "Build succeeded".to_string(),
+            failure_pattern: "error".to_string(),
+        }
+    }
+}
+
+impl JobWatcher {
+    fn is_active(&self) -> bool {
+        self.enabled
+            && !(self.source == JobSource::LogFile && self.target.trim().is_empty())
+            && !(self.success_pattern.trim().is_empty() && self.failure_pattern.trim().is_empty())
+    }
+
+    fn title(&self) -> String {
+        match (self.name.trim(), self.target.trim()) {
+            ("", "") => "New watcher".to_string(),
+            ("", target) => target.to_string(),
+            (name, _) => name.to_string(),
+        }
+    }
+
+    /// Whether the text matches the failure or success pattern; failure wins when both match
+    fn outcome(&self, text: &str) -> Option<bool> {
+        let text = text.to_lowercase();
+        let matches = |pattern: &str| !pattern.trim().is_empty() && text.contains(&pattern.trim().to_lowercase());
+        if matches(&self.failure_pattern) {
+            Some(false)
+        } else if matches(&self.success_pattern) {
+            Some(true)
+        } else {
+            None
+        }
+    }
+}
+
+/// Persisted log and window title watchers
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct JobWatchSettings {
+    pub watchers: Vec<JobWatcher>,
+}
+
+impl JobWatchSettings {
+    pub fn is_active(&self) -> bool {
+        self.watchers.iter().any(JobWatcher::is_active)
+    }
+}
+
+/// Read position in a watched log
+#[derive(Default)]
+struct LogTail {
+    path: String,
+    offset: Option<u64>,    // None until the first check, which starts at the end of the file
+    partial: String,        // An unfinished last line
+}
+
+/// Per-watcher state; indices follow the settings list
+#[derive(Default)]
+struct WatcherState {
+    tail: LogTail,
+    matched_title: Option<String>,  // The window title that last matched, so it is reported once
+    notified: Option<Instant>,      // Last log match that raised a notification
+}
+
+struct JobEvent {
+    time: DateTime<Local>,
+    message: String,
+    succeeded: bool,
+}
+
+/// Log tails, title matches and the notifications they raised
+#[derive(Default)]
+pub struct JobWatch {
+    states: Vec<WatcherState>,
+    last_check: Option<Instant>,
+    events: VecDeque<JobEvent>,     // Newest first
+}
+
+/// Lines appended since the last read; a file that shrank was replaced and is read from the start
+fn read_new_lines(tail: &mut LogTail) -> std::io::Result<Vec<String>> {
+    let mut file = File::open(&tail.path)?;
+    let length = file.metadata()?.len();
+    let offset = match tail.offset {
+        None => {
+            tail.offset = Some(length);
+            return Ok(Vec::new());
+        }
+        Some(offset) if offset > length => {
+            tail.partial.clear();
+            0
+        }
+        Some(offset) => offset,
+    };
+    file.seek(SeekFrom::Start(offset))?;
+    let mut bytes = Vec::new();
+    file.take(MAX_READ).read_to_end(&mut bytes)?;
+    tail.offset = Some(offset + bytes.len() as u64);
+
+    tail.partial.push_str(&String::from_utf8_lossy(&bytes));
+    let Some(end) = tail.partial.rfind('\n') else { return Ok(Vec::new()) };
+    let complete: String = tail.partial.drain(..=end).collect();
+    Ok(complete.lines().map(str::to_string).collect())
+}
+
+unsafe extern "system" fn collect_title(hwnd: HWND, titles: LPARAM) -> BOOL {
+    let titles = &mut *(titles.0 as *mut Vec<String>);
+    if IsWindowVisible(hwnd).as_bool() {
+        let mut buffer = [0u16; 512];
+        let length = GetWindowTextW(hwnd, &mut buffer);
+        if length > 0 {
+            titles.push(String::from_utf16_lossy(&buffer[..length as usize]));
+        }
+    }
+    BOOL(1)
+}
+
+fn window_titles() -> Vec<String> {
+    let mut titles: Vec<String> = Vec::new();
+    unsafe { EnumWindows(Some(collect_title), LPARAM(&mut titles as *mut _ as isize)) };
+    titles
+}
+
+impl JobWatch {
+    fn push_event(&mut self, message: String, succeeded: bool) {
+        info!("{}", message);
+        if self.events.len() == MAX_EVENTS {
+            self.events.pop_back();
+        }
+        self.events.push_front(JobEvent { time: Local::now(), message, succeeded });
+    }
+
+    /// Recent watcher notifications for the Processes tab
+    pub fn show_events(&self, ui: &mut egui::Ui, active: bool) {
+        if !active && self.events.is_empty() {
+            return;
+        }
+        egui::CollapsingHeader::new(format!("Job watchers ({} events)", self.events.len()))
+            .id_source("job_watch_events")
+            .show(ui, |ui| {
+                if self.events.is_empty() {
+                    ui.label(RichText::new("Nothing matched yet").color(Color32::from_rgb(107, 114, 128)));
+                }
+                for event in &self.events {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(event.time.format("%H:%M:%S").to_string())
+                            .color(Color32::from_rgb(156, 163, 175)));
+                        let color = if event.succeeded { Color32::from_rgb(22, 163, 74) } else { Color32::from_rgb(220, 50, 50) };
+                        ui.colored_label(color, &event.message);
+                    });
+                }
+            });
+        ui.add_space(8.0);
+    }
+}
+
+impl DevDashboard {
+    /// Checks watched logs and window titles every few seconds
+    pub(crate) fn update_job_watch(&mut self) {
+        let watchers = &self.settings.job_watch.watchers;
+        let job_watch = &mut self.job_watch;
+        job_watch.states.resize_with(watchers.len(), WatcherState::default);
+        if !self.settings.job_watch.is_active() {
+            return;
+        }
+        let due = match job_watch.last_check {
+            Some(last) => last.elapsed() >= CHECK_INTERVAL,
+            None => true,
+        };
+        if !due {
+            return;
+        }
+        job_watch.last_check = Some(Instant::now());
+
+        let titles = if watchers.iter().any(|watcher| watcher.is_active() && watcher.source == JobSource::WindowTitle) {
+            window_titles()
+        } else {
+            Vec::new()
+        };
+        let mut found = Vec::new();
+
+        for (watcher, state) in watchers.iter().zip(job_watch.states.iter_mut()) {
+            if !watcher.is_active() {
+                continue;
+            }
+            match watcher.source {
+                JobSource::LogFile => {
+                    let path = watcher.target.trim().trim_matches('"');
+                    if state.tail.path != path {
+                        state.tail = LogTail { path: path.to_string(), ..Default::default() };
+                    }
+                    let lines = match read_new_lines(&mut state.tail) {
+                        Ok(lines) => lines,
+                        // The log may not exist until the job starts writing it
+                        Err(_) => continue,
+                    };
+                    let quiet = match state.notified {
+                        Some(notified) => notified.elapsed() >= LOG_COOLDOWN,
+                        None => true,
+                    };
+                    if !quiet {
+                        continue;
+                    }
+                    if let Some((line, succeeded)) = lines.iter().find_map(|line| watcher.outcome(line).map(|outcome| (line, outcome))) {
+                        state.notified = Some(Instant::now());
+                        found.push((watcher.title(), line.trim().to_string(), succeeded));
+                    }
+                }
+                JobSource::WindowTitle => {
+                    let filter = watcher.target.trim().to_lowercase();
+                    let matched = titles
+                        .iter()
+                        .filter(|title| title.to_lowercase().contains(&filter))
+                        .find_map(|title| watcher.outcome(title).map(|outcome| (title.clone(), outcome)));
+                    let title = matched.as_ref().map(|(title, _)| title.clone());
+                    if let Some((title, succeeded)) = matched {
+                        if state.matched_title.as_ref() != Some(&title) {
+                            found.push((watcher.title(), title, succeeded));
+                        }
+                    }
+                    state.matched_title = title;
+                }
+            }
+        }
+
+        for (name, text, succeeded) in found {
+            let headline = if succeeded { format!("{} succeeded", name) } else { format!("{} failed", name) };
+            if !succeeded {
+                warn!("Job watcher {}: {}", name, text);
+            }
+            toast::show_toast(&headline, &text);
+            self.notifications.push(NotificationSource::Watcher, &headline, &text);
+            job_watch.push_event(format!("{}: {}", headline, text), succeeded);
+        }
+    }
+
+    /// Settings section for log file and window title watchers
+    pub(crate) fn show_job_watch_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        let mut removed = None;
+
+        ui.label("Job Watchers:");
+        for (index, watcher) in self.settings.job_watch.watchers.iter_mut().enumerate() {
+            egui::CollapsingHeader::new(watcher.title())
+                .id_source(("job_watcher", index))
+                .show(ui, |ui| {
+                    egui::Grid::new(("job_watcher_grid", index)).num_columns(2).show(ui, |ui| {
+                        ui.label("Enabled:");
+                        changed |= ui.checkbox(&mut watcher.enabled, "").changed();
+                        ui.end_row();
+                        ui.label("Name:");
+                        changed |= ui.add(egui::TextEdit::singleline(&mut watcher.name).hint_text("Release build")).changed();
+                        ui.end_row();
+
+                        ui.label("Watch:");
+                        egui::ComboBox::from_id_source(("job_watcher_source", index))
+                            .selected_text(watcher.source.label())
+                            .show_ui(ui, |ui| {
+                                for source in [JobSource::LogFile, JobSource::WindowTitle] {
+                                    changed |= ui.selectable_value(&mut watcher.source, source, source.label()).changed();
+                                }
+                            });
+                        ui.end_row();
+
+                        match watcher.source {
+                            JobSource::LogFile => {
+                                ui.label("Log file:");
+                                changed |= ui.add(egui::TextEdit::singleline(&mut watcher.target)
+                                    .hint_text(r"C:\src\app\build.log")
+                                    .desired_width(280.0)).changed();
+                            }
+                            JobSource::WindowTitle => {
+                                ui.label("Title contains:");
+                                changed |= ui.add(egui::TextEdit::singleline(&mut watcher.target)
+                                    .hint_text("Any window")
+                                    .desired_width(280.0)).changed();
+                            }
+                        }
+                        ui.end_row();
+
+                        ui.label("Success text:");
+                        changed |= ui.add(egui::TextEdit::singleline(&mut watcher.success_pattern)).changed();
+                        ui.end_row();
+                        ui.label("Failure text:");
+                        changed |= ui.add(egui::TextEdit::singleline(&mut watcher.failure_pattern)).changed();
+                        ui.end_row();
+                    });
+                    if ui.button("✖ Remove").clicked() {
+                        removed = Some(index);
+                    }
+                });
+        }
+        if let Some(index) = removed {
+            self.settings.job_watch.watchers.remove(index);
+            // State is kept by index, so drop the removed watcher's tail
+            if index < self.job_watch.states.len() {
+                self.job_watch.states.remove(index);
+            }
+            changed = true;
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("Add job watcher").clicked() {
+                self.settings.job_watch.watchers.push(JobWatcher::default());
+                changed = true;
+            }
+            ui.label(RichText::new("Matching is case-insensitive; a log is only read from the point it was first checked")
+                .color(Color32::from_rgb(107, 114, 128)));
+        });
+
+        if changed {
+            self.save_settings();
+        }
+    }
+}
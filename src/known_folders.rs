@@ -0,0 +1,128 @@
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use windows::core::GUID;
+use windows::Win32::Foundation::HANDLE;
+use windows::Win32::System::Com::CoTaskMemFree;
+use windows::Win32::UI::Shell::{
+    SHGetKnownFolderPath, FOLDERID_Documents, FOLDERID_LocalAppData, FOLDERID_Profile, FOLDERID_ProgramData,
+    FOLDERID_ProgramFiles, FOLDERID_ProgramFilesX86, FOLDERID_RoamingAppData, KF_FLAG_DEFAULT,
+};
+use log::debug;
+
+/// How catalogs write the standard folders, longest first so the AppData folders win over the profile.
+/// The real locations can differ: another system drive, a profile folder not named after the account,
+/// or folders moved by policy.
+const WRITTEN_PREFIXES: [(&str, GUID); 6] = [
+    (r"C:\Users\%USERNAME%\AppData\Local", FOLDERID_LocalAppData),
+    (r"C:\Users\%USERNAME%\AppData\Roaming", FOLDERID_RoamingAppData),
+    (r"C:\Users\%USERNAME%", FOLDERID_Profile),
+    (r"C:\Program Files (x86)", FOLDERID_ProgramFilesX86),
+    (r"C:\Program Files", FOLDERID_ProgramFiles),
+    (r"C:\ProgramData", FOLDERID_ProgramData),
+];
+
+static FOLDERS: OnceLock<Vec<(&'static str, String)>> = OnceLock::new();
+
+fn known_folder(id: &GUID) -> Option<String> {
+    unsafe {
+        let path = SHGetKnownFolderPath(id, KF_FLAG_DEFAULT, HANDLE::default()).ok()?;
+        let text = path.to_string().ok();
+        CoTaskMemFree(Some(path.0 as *const _));
+        text
+    }
+}
+
+/// Where each written prefix really is on this machine; looked up once
+fn folders() -> &'static [(&'static str, String)] {
+    FOLDERS.get_or_init(|| {
+        WRITTEN_PREFIXES.iter()
+            .filter_map(|(written, id)| {
+                let actual = known_folder(id)?;
+                debug!("Known folder {} is {}", written, actual);
+                Some((*written, actual))
+            })
+            .collect()
+    })
+}
+
+/// Expands %NAME% environment variables, leaving unknown ones as written
+fn expand_variables(path: &str) -> String {
+    let mut expanded = String::new();
+    let mut rest = path;
+    while let Some(start) = rest.find('%') {
+        let Some(length) = rest[start + 1..].find('%') else { break };
+        let name = &rest[start + 1..start + 1 + length];
+        expanded.push_str(&rest[..start]);
+        let value = if name.eq_ignore_ascii_case("USERNAME") { Some(whoami::username()) } else { std::env::var(name).ok() };
+        match value {
+            Some(value) if !name.is_empty() => expanded.push_str(&value),
+            _ => expanded.push_str(&rest[start..start + length + 2]),
+        }
+        rest = &rest[start + length + 2..];
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// Turns a detection path as written in a catalog, e.g. `C:\Program Files (x86)\App\app.exe` or
+/// `C:\Users\%USERNAME%\AppData\Local\Programs\App\app.exe`, into where it is on this machine
+pub fn resolve(path: &str) -> String {
+    for (written, actual) in folders() {
+        let Some(head) = path.get(..written.len()) else { continue };
+        let rest = &path[written.len()..];
+        if head.eq_ignore_ascii_case(written) && (rest.is_empty() || rest.starts_with('\\')) {
+            return format!("{}{}", actual, expand_variables(rest));
+        }
+    }
+    expand_variables(path)
+}
+
+/// The user's profile folder, which isn't always C:\Users\<account name>
+pub fn profile() -> Option<String> {
+    known_folder(&FOLDERID_Profile).or_else(|| std::env::var("USERPROFILE").ok())
+}
+
+/// The Documents folder, wherever it was redirected, e.g. into OneDrive
+pub fn documents() -> PathBuf {
+    known_folder(&FOLDERID_Documents)
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("USERPROFILE").map(|home| PathBuf::from(home).join("Documents")))
+        .unwrap_or_else(std::env::temp_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_variables_replaces_known_names() {
+        std::env::set_var("DEV_DASHBOARD_TEST_TOOLS", r"D:\Tools");
+        assert_eq!(expand_variables(r"%DEV_DASHBOARD_TEST_TOOLS%\app.exe"), r"D:\Tools\app.exe");
+        assert_eq!(expand_variables(r"C:\Users\%username%\app.exe"), format!(r"C:\Users\{}\app.exe", whoami::username()));
+    }
+
+    #[test]
+    fn expand_variables_keeps_unknown_and_unclosed_names() {
+        assert_eq!(expand_variables(r"%DEV_DASHBOARD_TEST_UNSET%\app.exe"), r"%DEV_DASHBOARD_TEST_UNSET%\app.exe");
+        assert_eq!(expand_variables(r"C:\100%%\app.exe"), r"C:\100%%\app.exe");
+        assert_eq!(expand_variables(r"C:\50%\app.exe"), r"C:\50%\app.exe");
+    }
+
+    #[test]
+    fn resolve_maps_written_prefixes_to_known_folders() {
+        let local = known_folder(&FOLDERID_LocalAppData).unwrap();
+        assert_eq!(
+            resolve(r"C:\Users\%USERNAME%\AppData\Local\Programs\App\app.exe"),
+            format!(r"{}\Programs\App\app.exe", local),
+        );
+        let program_files = known_folder(&FOLDERID_ProgramFiles).unwrap();
+        assert_eq!(resolve(r"c:\program files\App\app.exe"), format!(r"{}\App\app.exe", program_files));
+        assert_eq!(resolve(r"C:\Program Files"), program_files);
+    }
+
+    #[test]
+    fn resolve_only_matches_whole_folder_names() {
+        assert_eq!(resolve(r"C:\Program FilesX\app.exe"), r"C:\Program FilesX\app.exe");
+        assert_eq!(resolve(r"D:\Tools\app.exe"), r"D:\Tools\app.exe");
+    }
+}
@@ -0,0 +1,635 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use eframe::egui;
+use egui::{Color32, RichText};
+use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
+use serde::{Serialize, Deserialize};
+use log::{info, warn};
+use crate::DevDashboard;
+
+/// mDNS service type announced by every dashboard with file drop enabled
+const SERVICE_TYPE: &str = "_devdash-drop._tcp.local.";
+/// How long a sender waits for the receiving user to accept
+const OFFER_TIMEOUT: Duration = Duration::from_secs(60);
+const IO_TIMEOUT: Duration = Duration::from_secs(30);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_HEADER_BYTES: u64 = 4096;
+const CHUNK_SIZE: usize = 64 * 1024;
+/// Progress is reported to the UI at most once per this many bytes
+const PROGRESS_STEP: u64 = 1024 * 1024;
+/// Incoming connections handled at once; more are closed right away so a flood can't exhaust threads
+const MAX_CONNECTIONS: usize = 8;
+/// Offers waiting for the user; more are declined without asking
+const MAX_PENDING_OFFERS: usize = 3;
+
+/// Persisted LAN file drop configuration
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct LanDropSettings {
+    pub enabled: bool,
+    pub device_name: String,        // Empty uses the computer name
+    pub download_folder: String,    // Empty uses the Downloads folder
+}
+
+impl LanDropSettings {
+    fn device_name(&self) -> String {
+        match self.device_name.trim() {
+            "" => whoami::devicename(),
+            name => name.to_string(),
+        }
+    }
+
+    fn download_folder(&self) -> PathBuf {
+        match self.download_folder.trim() {
+            "" => std::env::var_os("USERPROFILE")
+                .map(|home| PathBuf::from(home).join("Downloads"))
+                .unwrap_or_else(std::env::temp_dir),
+            folder => PathBuf::from(folder),
+        }
+    }
+}
+
+/// First line of every transfer, sent as JSON
+#[derive(Serialize, Deserialize)]
+struct Header {
+    name: String,
+    size: u64,
+    from: String,
+}
+
+#[derive(Clone)]
+struct Peer {
+    name: String,
+    address: SocketAddr,
+}
+
+/// A file another dashboard wants to send, waiting for the user
+struct Offer {
+    id: u64,
+    from: String,
+    name: String,
+    size: u64,
+    received: Instant,
+    reply: Sender<bool>,
+}
+
+enum DropEvent {
+    PeerFound(String, Peer),        // mDNS full name, peer
+    PeerLost(String),
+    Offer(Offer),
+    Progress(u64, u64),             // Transfer id, bytes so far
+    Finished(u64, Result<String, String>),
+}
+
+#[derive(PartialEq)]
+enum Direction {
+    Sending,
+    Receiving,
+}
+
+struct Transfer {
+    id: u64,
+    direction: Direction,
+    name: String,
+    peer: String,
+    size: u64,
+    transferred: u64,
+    result: Option<Result<String, String>>,
+}
+
+/// Announcement and listener threads; dropping it stops both
+struct Service {
+    daemon: ServiceDaemon,
+    fullname: String,
+    stop: Arc<AtomicBool>,
+}
+
+impl Drop for Service {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        let _ = self.daemon.unregister(&self.fullname);
+        let _ = self.daemon.shutdown();
+    }
+}
+
+#[derive(Default)]
+pub struct LanDrop {
+    service: Option<Service>,
+    events: Option<(Sender<DropEvent>, Receiver<DropEvent>)>,
+    error: Option<String>,
+    peers: BTreeMap<String, Peer>,
+    target: Option<String>,         // Full name of the selected peer
+    offers: Vec<Offer>,
+    transfers: Vec<Transfer>,
+    next_id: Arc<AtomicU64>,
+}
+
+/// Keeps only the file name and replaces characters Windows does not allow
+fn sanitize_file_name(name: &str) -> String {
+    let name = Path::new(name).file_name().and_then(|name| name.to_str()).unwrap_or("file");
+    let cleaned: String = name
+        .chars()
+        .map(|c| if c.is_control() || "<>:\"/\\|?*".contains(c) { '_' } else { c })
+        .collect();
+    match cleaned.trim_matches(['.', ' ']) {
+        "" => "file".to_string(),
+        name => name.to_string(),
+    }
+}
+
+/// `name`, or `name (2)` etc. if a file with that name already exists
+fn unique_path(folder: &Path, name: &str) -> PathBuf {
+    let path = folder.join(name);
+    if !path.exists() {
+        return path;
+    }
+    let stem = Path::new(name).file_stem().and_then(|stem| stem.to_str()).unwrap_or(name);
+    let extension = Path::new(name).extension().and_then(|extension| extension.to_str());
+    (2..)
+        .map(|index| match extension {
+            Some(extension) => folder.join(format!("{} ({}).{}", stem, index, extension)),
+            None => folder.join(format!("{} ({})", stem, index)),
+        })
+        .find(|candidate| !candidate.exists())
+        .unwrap_or(path)
+}
+
+/// Copies exactly `size` bytes, reporting progress as it goes
+fn copy_with_progress(reader: &mut impl Read, writer: &mut impl Write, size: u64, id: u64, events: &Sender<DropEvent>) -> Result<(), String> {
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+    let mut copied = 0u64;
+    let mut reported = 0u64;
+    while copied < size {
+        let wanted = (size - copied).min(CHUNK_SIZE as u64) as usize;
+        let read = reader.read(&mut buffer[..wanted]).map_err(|e| e.to_string())?;
+        if read == 0 {
+            return Err(format!("Connection closed after {} of {} bytes", copied, size));
+        }
+        writer.write_all(&buffer[..read]).map_err(|e| e.to_string())?;
+        copied += read as u64;
+        if copied - reported >= PROGRESS_STEP || copied == size {
+            reported = copied;
+            let _ = events.send(DropEvent::Progress(id, copied));
+        }
+    }
+    writer.flush().map_err(|e| e.to_string())
+}
+
+fn read_reply(reader: &mut impl BufRead) -> Result<String, String> {
+    let mut line = String::new();
+    reader.take(64).read_line(&mut line).map_err(|e| e.to_string())?;
+    Ok(line.trim().to_string())
+}
+
+/// Handles one incoming connection: asks the user, then writes the file to the download folder
+fn receive_file(stream: TcpStream, id: u64, folder: &Path, events: &Sender<DropEvent>) -> Result<String, String> {
+    stream.set_nonblocking(false).map_err(|e| e.to_string())?;
+    stream.set_read_timeout(Some(IO_TIMEOUT)).map_err(|e| e.to_string())?;
+    let mut writer = stream.try_clone().map_err(|e| e.to_string())?;
+    let mut reader = BufReader::new(stream);
+
+    let mut line = String::new();
+    (&mut reader).take(MAX_HEADER_BYTES).read_line(&mut line).map_err(|e| e.to_string())?;
+    let header: Header = serde_json::from_str(&line).map_err(|e| format!("Invalid header: {}", e))?;
+
+    let (reply, decision) = channel();
+    events.send(DropEvent::Offer(Offer {
+        id,
+        from: header.from.clone(),
+        name: sanitize_file_name(&header.name),
+        size: header.size,
+        received: Instant::now(),
+        reply,
+    })).map_err(|e| e.to_string())?;
+    // A dropped reply sender (feature turned off) counts as declining
+    let accepted = decision.recv_timeout(OFFER_TIMEOUT).unwrap_or(false);
+    writer.write_all(if accepted { b"ACCEPT\n" } else { b"REJECT\n" }).map_err(|e| e.to_string())?;
+    if !accepted {
+        return Err("Declined".to_string());
+    }
+
+    std::fs::create_dir_all(folder).map_err(|e| e.to_string())?;
+    let path = unique_path(folder, &sanitize_file_name(&header.name));
+    let mut file = File::create(&path).map_err(|e| format!("Could not create {}: {}", path.display(), e))?;
+    if let Err(e) = copy_with_progress(&mut reader, &mut file, header.size, id, events) {
+        drop(file);
+        let _ = std::fs::remove_file(&path);
+        return Err(e);
+    }
+    writer.write_all(b"OK\n").map_err(|e| e.to_string())?;
+    info!("Received {} from {}", path.display(), header.from);
+    Ok(path.display().to_string())
+}
+
+fn send_file(address: SocketAddr, path: &Path, from: String, id: u64, events: &Sender<DropEvent>) -> Result<String, String> {
+    let mut file = File::open(path).map_err(|e| e.to_string())?;
+    let size = file.metadata().map_err(|e| e.to_string())?.len();
+    let header = Header {
+        name: path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default(),
+        size,
+        from,
+    };
+
+    let stream = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT).map_err(|e| format!("Could not connect: {}", e))?;
+    let mut writer = stream.try_clone().map_err(|e| e.to_string())?;
+    let mut reader = BufReader::new(stream);
+    let json = serde_json::to_string(&header).map_err(|e| e.to_string())?;
+    writer.write_all(format!("{}\n", json).as_bytes()).map_err(|e| e.to_string())?;
+
+    // The other side waits for its user, so allow for the whole offer timeout
+    reader.get_ref().set_read_timeout(Some(OFFER_TIMEOUT + IO_TIMEOUT)).map_err(|e| e.to_string())?;
+    if read_reply(&mut reader)? != "ACCEPT" {
+        return Err("Declined".to_string());
+    }
+    reader.get_ref().set_read_timeout(Some(IO_TIMEOUT)).map_err(|e| e.to_string())?;
+
+    copy_with_progress(&mut file, &mut writer, size, id, events)?;
+    if read_reply(&mut reader)? != "OK" {
+        return Err("Receiver did not confirm the transfer".to_string());
+    }
+    Ok("Sent".to_string())
+}
+
+/// Announces this dashboard over mDNS, browses for others, and listens for transfers
+fn start_service(settings: &LanDropSettings, events: Sender<DropEvent>, ids: Arc<AtomicU64>) -> Result<Service, String> {
+    let listener = TcpListener::bind("0.0.0.0:0").map_err(|e| format!("Could not listen: {}", e))?;
+    listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+    let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+
+    let daemon = ServiceDaemon::new().map_err(|e| format!("Could not start mDNS: {}", e))?;
+    let name = settings.device_name();
+    let host: String = whoami::devicename()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let info = ServiceInfo::new(SERVICE_TYPE, &name, &format!("{}.local.", host), "", port, &[("name", name.as_str())][..])
+        .map_err(|e| e.to_string())?
+        .enable_addr_auto();
+    let fullname = info.get_fullname().to_string();
+    daemon.register(info).map_err(|e| format!("Could not announce: {}", e))?;
+    let browse = daemon.browse(SERVICE_TYPE).map_err(|e| format!("Could not browse: {}", e))?;
+    info!("LAN file drop listening on port {} as {}", port, fullname);
+
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let own_name = fullname.clone();
+    let browse_events = events.clone();
+    let browse_stop = stop.clone();
+    std::thread::spawn(move || {
+        while !browse_stop.load(Ordering::Relaxed) {
+            let event = match browse.recv_timeout(Duration::from_secs(1)) {
+                Ok(event) => event,
+                Err(_) if browse.is_disconnected() => break,
+                Err(_) => continue,
+            };
+            let sent = match event {
+                ServiceEvent::ServiceResolved(service) if service.get_fullname() != own_name => {
+                    let Some(ip) = service.get_addresses_v4().into_iter().next() else { continue };
+                    let name = service.get_property_val_str("name").unwrap_or(service.get_fullname()).to_string();
+                    let address = SocketAddr::new((*ip).into(), service.get_port());
+                    browse_events.send(DropEvent::PeerFound(service.get_fullname().to_string(), Peer { name, address }))
+                }
+                ServiceEvent::ServiceRemoved(_, fullname) => browse_events.send(DropEvent::PeerLost(fullname)),
+                _ => Ok(()),
+            };
+            if sent.is_err() {
+                break;
+            }
+        }
+    });
+
+    let folder = settings.download_folder();
+    let listen_stop = stop.clone();
+    let connections = Arc::new(AtomicUsize::new(0));
+    std::thread::spawn(move || {
+        while !listen_stop.load(Ordering::Relaxed) {
+            match listener.accept() {
+                Ok((stream, address)) => {
+                    if connections.load(Ordering::Relaxed) >= MAX_CONNECTIONS {
+                        warn!("Closing connection from {}: {} transfers are already running", address, MAX_CONNECTIONS);
+                        drop(stream);
+                        continue;
+                    }
+                    connections.fetch_add(1, Ordering::Relaxed);
+                    let id = ids.fetch_add(1, Ordering::Relaxed);
+                    let events = events.clone();
+                    let folder = folder.clone();
+                    let connections = connections.clone();
+                    std::thread::spawn(move || {
+                        let result = receive_file(stream, id, &folder, &events);
+                        connections.fetch_sub(1, Ordering::Relaxed);
+                        if let Err(e) = &result {
+                            warn!("Incoming transfer from {} failed: {}", address, e);
+                        }
+                        let _ = events.send(DropEvent::Finished(id, result));
+                    });
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => std::thread::sleep(Duration::from_millis(200)),
+                Err(e) => {
+                    warn!("LAN file drop accept failed: {}", e);
+                    std::thread::sleep(Duration::from_secs(1));
+                }
+            }
+        }
+    });
+
+    Ok(Service { daemon, fullname, stop })
+}
+
+impl LanDrop {
+    /// Stops announcing and forgets peers; running transfers finish on their own
+    fn stop(&mut self) {
+        self.service = None;
+        self.peers.clear();
+        self.offers.clear();
+    }
+
+    fn send(&mut self, path: PathBuf, from: String) {
+        let Some(peer) = self.target.as_ref().and_then(|target| self.peers.get(target)).cloned() else { return };
+        let Some((events, _)) = &self.events else { return };
+        let Ok(size) = std::fs::metadata(&path).map(|metadata| metadata.len()) else { return };
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.transfers.push(Transfer {
+            id,
+            direction: Direction::Sending,
+            name: path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default(),
+            peer: peer.name.clone(),
+            size,
+            transferred: 0,
+            result: None,
+        });
+
+        let events = events.clone();
+        std::thread::spawn(move || {
+            let result = send_file(peer.address, &path, from, id, &events);
+            match &result {
+                Ok(_) => info!("Sent {} to {}", path.display(), peer.name),
+                Err(e) => warn!("Sending {} to {} failed: {}", path.display(), peer.name, e),
+            }
+            let _ = events.send(DropEvent::Finished(id, result));
+        });
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    let (value, unit) = DevDashboard::format_bytes(bytes);
+    format!("{:.1} {}", value, unit)
+}
+
+impl DevDashboard {
+    /// Starts or stops the service to match settings and applies events from the background threads
+    pub(crate) fn update_lan_drop(&mut self) {
+        let settings = &self.settings.lan_drop;
+        let lan = &mut self.lan_drop;
+        if !settings.enabled {
+            lan.stop();
+            lan.error = None;
+        } else if lan.service.is_none() && lan.error.is_none() {
+            let (sender, receiver) = match lan.events.take() {
+                Some(events) => events,
+                None => channel(),
+            };
+            match start_service(settings, sender.clone(), lan.next_id.clone()) {
+                Ok(service) => lan.service = Some(service),
+                Err(e) => {
+                    warn!("LAN file drop failed to start: {}", e);
+                    lan.error = Some(e);
+                }
+            }
+            lan.events = Some((sender, receiver));
+        }
+
+        let Some((_, receiver)) = &lan.events else { return };
+        while let Ok(event) = receiver.try_recv() {
+            match event {
+                DropEvent::PeerFound(fullname, peer) => {
+                    lan.peers.insert(fullname, peer);
+                }
+                DropEvent::PeerLost(fullname) => {
+                    lan.peers.remove(&fullname);
+                }
+                DropEvent::Offer(offer) if lan.offers.len() >= MAX_PENDING_OFFERS => {
+                    info!("Declining {} from {}: too many offers waiting", offer.name, offer.from);
+                    let _ = offer.reply.send(false);
+                }
+                DropEvent::Offer(offer) => lan.offers.push(offer),
+                DropEvent::Progress(id, bytes) => {
+                    if let Some(transfer) = lan.transfers.iter_mut().find(|transfer| transfer.id == id) {
+                        transfer.transferred = bytes;
+                    }
+                }
+                DropEvent::Finished(id, result) => {
+                    lan.offers.retain(|offer| offer.id != id);
+                    if let Some(transfer) = lan.transfers.iter_mut().find(|transfer| transfer.id == id) {
+                        transfer.result = Some(result);
+                    }
+                }
+            }
+        }
+        lan.offers.retain(|offer| offer.received.elapsed() < OFFER_TIMEOUT);
+        if lan.target.as_ref().is_some_and(|target| !lan.peers.contains_key(target)) {
+            lan.target = None;
+        }
+        if lan.target.is_none() && lan.peers.len() == 1 {
+            lan.target = lan.peers.keys().next().cloned();
+        }
+    }
+
+    /// Prompt for incoming files, shown over every tab
+    pub(crate) fn show_lan_drop_offers(&mut self, ctx: &egui::Context) {
+        let Some(offer) = self.lan_drop.offers.first() else { return };
+        let mut decision = None;
+
+        egui::Window::new("Incoming File")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(format!("{} wants to send you:", offer.from));
+                ui.label(RichText::new(format!("{} ({})", offer.name, format_size(offer.size))).strong());
+                ui.label(RichText::new(format!("Saved to {}", self.settings.lan_drop.download_folder().display()))
+                    .color(Color32::from_rgb(156, 163, 175)));
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Accept").clicked() {
+                        decision = Some(true);
+                    }
+                    if ui.button("Decline").clicked() {
+                        decision = Some(false);
+                    }
+                });
+            });
+
+        if let Some(accepted) = decision {
+            let offer = self.lan_drop.offers.remove(0);
+            let _ = offer.reply.send(accepted);
+            if accepted {
+                self.lan_drop.transfers.push(Transfer {
+                    id: offer.id,
+                    direction: Direction::Receiving,
+                    name: offer.name,
+                    peer: offer.from,
+                    size: offer.size,
+                    transferred: 0,
+                    result: None,
+                });
+            }
+        }
+    }
+
+    /// File Drop tool page
+    /// Lists dashboards found on the LAN and sends files dropped onto the window
+    pub(crate) fn show_lan_drop_tool(&mut self, ui: &mut egui::Ui) {
+        ui.heading("File Drop");
+        ui.add_space(8.0);
+
+        if !self.settings.lan_drop.enabled {
+            ui.label("Send files to other computers on this network running Dev Dashboard.");
+            ui.add_space(4.0);
+            if ui.button("Enable file drop").clicked() {
+                self.settings.lan_drop.enabled = true;
+                self.save_settings();
+            }
+            return;
+        }
+        if let Some(error) = &self.lan_drop.error {
+            ui.colored_label(Color32::from_rgb(220, 50, 50), error);
+            if ui.button("Retry").clicked() {
+                self.lan_drop.error = None;
+            }
+            return;
+        }
+
+        ui.label(RichText::new(format!("Visible as \"{}\"", self.settings.lan_drop.device_name()))
+            .color(Color32::from_rgb(156, 163, 175)));
+        ui.add_space(8.0);
+
+        let lan = &mut self.lan_drop;
+        ui.label(RichText::new("Send to").strong());
+        if lan.peers.is_empty() {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label("Looking for other dashboards on this network...");
+            });
+        }
+        for (fullname, peer) in &lan.peers {
+            let selected = lan.target.as_ref() == Some(fullname);
+            if ui.selectable_label(selected, format!("🖥 {}  ({})", peer.name, peer.address.ip())).clicked() {
+                lan.target = Some(fullname.clone());
+            }
+        }
+        ui.add_space(8.0);
+
+        let target_name = lan.target.as_ref().and_then(|target| lan.peers.get(target)).map(|peer| peer.name.clone());
+        let hovering = ui.ctx().input(|i| !i.raw.hovered_files.is_empty());
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width(), 80.0), egui::Sense::hover());
+        let stroke_color = if hovering && target_name.is_some() {
+            Color32::from_rgb(88, 165, 237)
+        } else {
+            Color32::from_rgb(55, 65, 81)
+        };
+        ui.painter().rect_stroke(rect, 8.0, egui::Stroke::new(2.0, stroke_color));
+        ui.painter().text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            match &target_name {
+                Some(name) => format!("Drop files here to send them to {}", name),
+                None => "Select a computer to send to".to_string(),
+            },
+            egui::FontId::proportional(16.0),
+            Color32::from_rgb(156, 163, 175),
+        );
+
+        let dropped: Vec<PathBuf> = ui.ctx().input(|i| i.raw.dropped_files.iter().filter_map(|file| file.path.clone()).collect());
+        if target_name.is_some() {
+            let from = self.settings.lan_drop.device_name();
+            for path in dropped.into_iter().filter(|path| path.is_file()) {
+                lan.send(path, from.clone());
+            }
+        }
+
+        if lan.transfers.is_empty() {
+            return;
+        }
+        ui.add_space(12.0);
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Transfers").strong());
+            if ui.small_button("Clear finished").clicked() {
+                lan.transfers.retain(|transfer| transfer.result.is_none());
+            }
+        });
+        egui::ScrollArea::vertical().auto_shrink([false, true]).show(ui, |ui| {
+            for transfer in lan.transfers.iter().rev() {
+                ui.horizontal(|ui| {
+                    let arrow = if transfer.direction == Direction::Sending { "⬆" } else { "⬇" };
+                    ui.label(format!("{} {}", arrow, transfer.name));
+                    ui.label(RichText::new(&transfer.peer).color(Color32::from_rgb(156, 163, 175)));
+                    match &transfer.result {
+                        None => {
+                            let fraction = if transfer.size == 0 { 0.0 } else { transfer.transferred as f32 / transfer.size as f32 };
+                            ui.add(egui::ProgressBar::new(fraction)
+                                .desired_width(160.0)
+                                .text(format!("{} / {}", format_size(transfer.transferred), format_size(transfer.size))));
+                        }
+                        Some(Ok(message)) => {
+                            if transfer.direction == Direction::Receiving {
+                                if ui.small_button("Show in folder").clicked() {
+                                    let _ = std::process::Command::new("explorer")
+                                        .arg(format!("/select,{}", message))
+                                        .spawn();
+                                }
+                            } else {
+                                ui.colored_label(Color32::from_rgb(22, 163, 74), message);
+                            }
+                        }
+                        Some(Err(error)) => {
+                            ui.colored_label(Color32::from_rgb(220, 50, 50), error);
+                        }
+                    }
+                });
+            }
+        });
+    }
+
+    /// Settings section for LAN file drop
+    pub(crate) fn show_lan_drop_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        let mut restart = false;
+
+        changed |= ui.checkbox(&mut self.settings.lan_drop.enabled, "Enable LAN file drop")
+            .on_hover_text("Announces this dashboard on the local network so others can send it files")
+            .changed();
+        if self.settings.lan_drop.enabled {
+            ui.horizontal(|ui| {
+                ui.label("Device name:");
+                let response = ui.add(egui::TextEdit::singleline(&mut self.settings.lan_drop.device_name)
+                    .hint_text(whoami::devicename()));
+                changed |= response.changed();
+                restart |= response.lost_focus();
+            });
+            ui.horizontal(|ui| {
+                ui.label("Save to:");
+                let response = ui.add(egui::TextEdit::singleline(&mut self.settings.lan_drop.download_folder)
+                    .hint_text("Downloads"));
+                changed |= response.changed();
+                restart |= response.lost_focus();
+            });
+        }
+
+        if restart {
+            // Announce the new name and save to the new folder
+            self.lan_drop.stop();
+        }
+        if changed {
+            self.save_settings();
+        }
+    }
+}
@@ -0,0 +1,433 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use eframe::egui;
+use egui::{Color32, RichText};
+use reqwest::Client;
+use windows::Win32::Foundation::{ERROR_BUFFER_OVERFLOW, NO_ERROR};
+use windows::Win32::NetworkManagement::IpHelper::{
+    GetAdaptersAddresses, IcmpCloseHandle, IcmpCreateFile, IcmpSendEcho, SendARP, GAA_FLAG_SKIP_ANYCAST,
+    GAA_FLAG_SKIP_DNS_SERVER, GAA_FLAG_SKIP_MULTICAST, ICMP_ECHO_REPLY, IP_ADAPTER_ADDRESSES_LH,
+};
+use windows::Win32::Networking::WinSock::{
+    GetNameInfoW, WSAStartup, AF_INET, NI_MAXHOST, NI_NAMEREQD, SOCKADDR, SOCKADDR_IN, WSADATA, socklen_t,
+};
+use log::{info, warn};
+use crate::{favorites, qr, storage, DevDashboard};
+
+/// IEEE's registry of MAC address prefixes, downloaded once and kept next to settings.json
+const OUI_URL: &str = "https://standards-oui.ieee.org/oui/oui.txt";
+const OUI_FILE: &str = "oui.txt";
+const OUI_TIMEOUT: Duration = Duration::from_secs(60);
+/// Probes run side by side, since an address nobody answers for takes a few seconds
+const WORKERS: usize = 64;
+const PING_TIMEOUT_MS: u32 = 700;
+/// Networks larger than this are only scanned around this PC, i.e. its /24
+const WIDEST_PREFIX: u8 = 22;
+
+/// A device that answered ARP or ping
+#[derive(Clone)]
+pub struct LanDevice {
+    pub address: Ipv4Addr,
+    pub mac: Option<[u8; 6]>,
+    pub hostname: Option<String>,
+    pub ping_ms: Option<u32>,           // None when it answered ARP but not ping
+}
+
+impl LanDevice {
+    fn mac_text(&self) -> String {
+        self.mac
+            .map(|mac| mac.iter().map(|byte| format!("{:02X}", byte)).collect::<Vec<_>>().join(":"))
+            .unwrap_or_default()
+    }
+
+    /// Phones and laptops use random addresses per network, with this bit set; they have no vendor
+    fn is_private_mac(&self) -> bool {
+        self.mac.is_some_and(|mac| mac[0] & 0x02 != 0)
+    }
+}
+
+/// This PC's address, prefix length and MAC on the interface used for the LAN
+struct Subnet {
+    address: Ipv4Addr,
+    prefix: u8,
+    mac: Option<[u8; 6]>,
+}
+
+impl Subnet {
+    /// Every host address in the subnet, or in this PC's /24 when the subnet is larger
+    fn hosts(&self) -> Vec<Ipv4Addr> {
+        let prefix = if self.prefix < WIDEST_PREFIX { 24 } else { self.prefix.min(30) };
+        let mask = u32::MAX << (32 - prefix);
+        let network = u32::from(self.address) & mask;
+        let broadcast = network | !mask;
+        (network + 1..broadcast).map(Ipv4Addr::from).collect()
+    }
+
+    fn describe(&self) -> String {
+        let hosts = self.hosts();
+        match (hosts.first(), hosts.last()) {
+            (Some(first), Some(last)) => format!("{} – {} ({} addresses)", first, last, hosts.len()),
+            _ => self.address.to_string(),
+        }
+    }
+}
+
+/// Finds the adapter holding the LAN address and reads its prefix length and MAC
+fn local_subnet() -> Option<Subnet> {
+    let IpAddr::V4(address) = qr::lan_address()? else { return None };
+    let flags = GAA_FLAG_SKIP_ANYCAST | GAA_FLAG_SKIP_MULTICAST | GAA_FLAG_SKIP_DNS_SERVER;
+    let mut size = 16 * 1024u32;
+    // u64s keep the linked structures correctly aligned
+    let mut buffer = Vec::new();
+    for _ in 0..4 {
+        buffer = vec![0u64; size as usize / 8 + 1];
+        let result = unsafe {
+            GetAdaptersAddresses(AF_INET.0 as u32, flags, None, Some(buffer.as_mut_ptr() as *mut IP_ADAPTER_ADDRESSES_LH), &mut size)
+        };
+        if result == NO_ERROR.0 {
+            break;
+        }
+        if result != ERROR_BUFFER_OVERFLOW.0 {
+            warn!("Failed to read network adapters: error {}", result);
+            return None;
+        }
+    }
+
+    let mut adapter = buffer.as_ptr() as *const IP_ADAPTER_ADDRESSES_LH;
+    while !adapter.is_null() {
+        let current = unsafe { &*adapter };
+        adapter = current.Next;
+        let mut unicast = current.FirstUnicastAddress;
+        while !unicast.is_null() {
+            let entry = unsafe { &*unicast };
+            unicast = entry.Next;
+            let socket = entry.Address.lpSockaddr;
+            if socket.is_null() || unsafe { (*socket).sa_family } != AF_INET {
+                continue;
+            }
+            let socket = unsafe { &*(socket as *const SOCKADDR_IN) };
+            if Ipv4Addr::from(unsafe { socket.sin_addr.S_un.S_addr }.to_ne_bytes()) != address {
+                continue;
+            }
+            let mac = (current.PhysicalAddressLength == 6).then(|| {
+                let mut mac = [0u8; 6];
+                mac.copy_from_slice(&current.PhysicalAddress[..6]);
+                mac
+            });
+            return Some(Subnet { address, prefix: entry.OnLinkPrefixLength, mac });
+        }
+    }
+    None
+}
+
+/// Asks for the MAC address; answered by anything on the subnet even when it drops ping
+fn arp(address: Ipv4Addr, source: Ipv4Addr) -> Option<[u8; 6]> {
+    let mut mac = [0u8; 8];
+    let mut length = mac.len() as u32;
+    let result = unsafe {
+        SendARP(u32::from_ne_bytes(address.octets()), u32::from_ne_bytes(source.octets()), mac.as_mut_ptr() as *mut _, &mut length)
+    };
+    (result == NO_ERROR.0 && length == 6).then(|| [mac[0], mac[1], mac[2], mac[3], mac[4], mac[5]])
+}
+
+/// One ICMP echo, which needs no administrator rights unlike raw sockets
+fn ping(address: Ipv4Addr) -> Option<u32> {
+    let handle = unsafe { IcmpCreateFile() }.ok()?;
+    let request = [0u8; 32];
+    let mut reply = vec![0u8; std::mem::size_of::<ICMP_ECHO_REPLY>() + request.len() + 8];
+    let replies = unsafe {
+        IcmpSendEcho(handle, u32::from_ne_bytes(address.octets()), request.as_ptr() as *const _, request.len() as u16,
+            None, reply.as_mut_ptr() as *mut _, reply.len() as u32, PING_TIMEOUT_MS)
+    };
+    unsafe { IcmpCloseHandle(handle) };
+    if replies == 0 {
+        return None;
+    }
+    let reply = unsafe { &*(reply.as_ptr() as *const ICMP_ECHO_REPLY) };
+    (reply.Status == 0).then_some(reply.RoundTripTime)
+}
+
+/// Reverse lookup through DNS, then NetBIOS and mDNS, which is how most home devices announce a name
+fn hostname(address: Ipv4Addr) -> Option<String> {
+    let mut socket = SOCKADDR_IN { sin_family: AF_INET, ..Default::default() };
+    socket.sin_addr.S_un.S_addr = u32::from_ne_bytes(address.octets());
+    let mut name = vec![0u16; NI_MAXHOST as usize];
+    let result = unsafe {
+        GetNameInfoW(&socket as *const SOCKADDR_IN as *const SOCKADDR, socklen_t(std::mem::size_of::<SOCKADDR_IN>() as i32),
+            Some(&mut name), None, NI_NAMEREQD as i32)
+    };
+    if result != 0 {
+        return None;
+    }
+    let end = name.iter().position(|unit| *unit == 0).unwrap_or(name.len());
+    Some(String::from_utf16_lossy(&name[..end])).filter(|name| !name.is_empty())
+}
+
+/// Reads "B8-27-EB   (hex)		Raspberry Pi Foundation" lines into prefix → vendor
+fn parse_oui(text: &str) -> HashMap<[u8; 3], String> {
+    text.lines()
+        .filter_map(|line| {
+            let (prefix, vendor) = line.split_once("(hex)")?;
+            let bytes: Vec<u8> = prefix.trim().split('-').filter_map(|byte| u8::from_str_radix(byte, 16).ok()).collect();
+            let prefix: [u8; 3] = bytes.try_into().ok()?;
+            Some((prefix, vendor.trim().to_string()))
+        })
+        .collect()
+}
+
+/// The vendor list from disk, downloading it first when it isn't there yet
+async fn load_oui() -> Result<HashMap<[u8; 3], String>, String> {
+    let mut text = fs::read_to_string(OUI_FILE).unwrap_or_default();
+    if text.is_empty() {
+        info!("Downloading the MAC vendor list");
+        // The IEEE site turns away requests without a user agent
+        let client = Client::builder()
+            .timeout(OUI_TIMEOUT)
+            .user_agent(concat!("dev-dashboard/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .map_err(|e| e.to_string())?;
+        text = client.get(OUI_URL).send().await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| e.to_string())?
+            .text().await
+            .map_err(|e| e.to_string())?;
+        if let Err(e) = storage::write_atomic(OUI_FILE, text.as_bytes()) {
+            warn!("Could not save {}: {}", OUI_FILE, e);
+        }
+    }
+    let vendors = parse_oui(&text);
+    if vendors.is_empty() {
+        return Err("The MAC vendor list is empty".to_string());
+    }
+    Ok(vendors)
+}
+
+/// Probes every address with a pool of threads; sends each result, None for addresses nobody answered for
+fn start_scan(subnet: &Subnet, sender: Sender<Option<LanDevice>>, cancel: Arc<AtomicBool>) {
+    unsafe {
+        let mut data = WSADATA::default();
+        WSAStartup(0x202, &mut data);
+    }
+    let source = subnet.address;
+    let own_mac = subnet.mac;
+    let queue = Arc::new(Mutex::new(subnet.hosts().into_iter().collect::<VecDeque<_>>()));
+    for _ in 0..WORKERS {
+        let queue = queue.clone();
+        let sender = sender.clone();
+        let cancel = cancel.clone();
+        std::thread::spawn(move || loop {
+            let Some(address) = queue.lock().ok().and_then(|mut queue| queue.pop_front()) else { break };
+            if cancel.load(Ordering::Relaxed) {
+                break;
+            }
+            // SendARP doesn't answer for this PC's own address
+            let mac = if address == source { own_mac } else { arp(address, source) };
+            let ping_ms = ping(address);
+            let device = (mac.is_some() || ping_ms.is_some()).then(|| LanDevice {
+                address,
+                mac,
+                hostname: hostname(address),
+                ping_ms,
+            });
+            if sender.send(device).is_err() {
+                break;
+            }
+        });
+    }
+}
+
+/// State of the LAN Scan tool
+#[derive(Default)]
+pub struct LanScan {
+    devices: Vec<LanDevice>,            // Sorted by address
+    previous: HashSet<Ipv4Addr>,        // Devices found by the scan before, to point out new ones
+    scans: usize,
+    subnet: Option<(Ipv4Addr, String)>, // This PC's address and the scanned range
+    gateway: Option<Ipv4Addr>,
+    total: usize,                       // Addresses in this scan
+    probed: usize,
+    receiver: Option<Receiver<Option<LanDevice>>>,
+    cancel: Arc<AtomicBool>,
+    vendors: Option<Result<HashMap<[u8; 3], String>, String>>,
+    vendor_receiver: Option<Receiver<Result<HashMap<[u8; 3], String>, String>>>,
+    error: Option<String>,
+}
+
+impl LanScan {
+    fn is_scanning(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    fn poll(&mut self) {
+        if let Some(vendors) = self.vendor_receiver.as_ref().and_then(|receiver| receiver.try_recv().ok()) {
+            if let Err(e) = &vendors {
+                warn!("Could not load the MAC vendor list: {}", e);
+            }
+            self.vendors = Some(vendors);
+            self.vendor_receiver = None;
+        }
+
+        let Some(receiver) = &self.receiver else { return };
+        let mut finished = false;
+        loop {
+            match receiver.try_recv() {
+                Ok(device) => {
+                    self.probed += 1;
+                    if let Some(device) = device {
+                        let index = self.devices.partition_point(|existing| existing.address < device.address);
+                        self.devices.insert(index, device);
+                    }
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => break,
+                // Every worker is done, or stopped
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    finished = true;
+                    break;
+                }
+            }
+        }
+        if finished {
+            info!("LAN scan found {} devices", self.devices.len());
+            self.receiver = None;
+            self.scans += 1;
+        }
+    }
+
+    fn vendor(&self, device: &LanDevice) -> Option<&str> {
+        let mac = device.mac?;
+        let vendors = self.vendors.as_ref()?.as_ref().ok()?;
+        vendors.get(&[mac[0], mac[1], mac[2]]).map(String::as_str)
+    }
+
+    /// What else is known about an address: this PC, the router, or a device the last scan didn't see
+    fn note(&self, device: &LanDevice) -> Option<(&'static str, Color32)> {
+        if self.subnet.as_ref().is_some_and(|(address, _)| *address == device.address) {
+            Some(("This PC", Color32::from_rgb(107, 114, 128)))
+        } else if self.gateway == Some(device.address) {
+            Some(("Gateway", Color32::from_rgb(107, 114, 128)))
+        } else if self.scans > 0 && !self.previous.contains(&device.address) {
+            Some(("New", Color32::from_rgb(202, 138, 4)))
+        } else {
+            None
+        }
+    }
+}
+
+impl DevDashboard {
+    fn start_lan_scan(&mut self) {
+        let Some(subnet) = local_subnet() else {
+            self.lan_scan.error = Some("No LAN connection found".to_string());
+            return;
+        };
+        let scan = &mut self.lan_scan;
+        info!("Scanning {}", subnet.describe());
+        if scan.scans > 0 {
+            scan.previous = scan.devices.iter().map(|device| device.address).collect();
+        }
+        scan.devices.clear();
+        scan.error = None;
+        scan.total = subnet.hosts().len();
+        scan.probed = 0;
+        scan.gateway = favorites::default_gateway().ok();
+        scan.subnet = Some((subnet.address, subnet.describe()));
+        scan.cancel = Arc::new(AtomicBool::new(false));
+        let (sender, receiver) = channel();
+        scan.receiver = Some(receiver);
+        start_scan(&subnet, sender, scan.cancel.clone());
+
+        if scan.vendors.is_none() && scan.vendor_receiver.is_none() {
+            let (sender, receiver) = channel();
+            scan.vendor_receiver = Some(receiver);
+            self.runtime().spawn(async move {
+                sender.send(load_oui().await).ok();
+            });
+        }
+    }
+
+    /// LAN Scan page of the Tools tab: devices on the local subnet with MAC, vendor, name and ping
+    pub(crate) fn show_lan_scan_tool(&mut self, ui: &mut egui::Ui) {
+        self.lan_scan.poll();
+        let grey = Color32::from_rgb(156, 163, 175);
+        let mut start = false;
+
+        ui.heading("LAN Scan");
+        ui.add_space(8.0);
+        let scan = &mut self.lan_scan;
+        ui.horizontal(|ui| {
+            if scan.is_scanning() {
+                if ui.button("Stop").clicked() {
+                    scan.cancel.store(true, Ordering::Relaxed);
+                }
+                ui.add(egui::ProgressBar::new(scan.probed as f32 / scan.total.max(1) as f32)
+                    .desired_width(200.0)
+                    .text(format!("{} / {}", scan.probed, scan.total)));
+            } else if ui.button(if scan.scans == 0 { "Scan" } else { "Scan Again" }).clicked() {
+                start = true;
+            }
+            if let Some((_, range)) = &scan.subnet {
+                ui.label(RichText::new(range).color(grey));
+            }
+        });
+        ui.label(RichText::new("Devices are found by ARP, so ones that drop ping are listed too. Names come from DNS, NetBIOS or mDNS.")
+            .small()
+            .color(grey));
+        if let Some(error) = &scan.error {
+            ui.colored_label(Color32::from_rgb(220, 50, 50), error);
+        }
+        if let Some(Err(e)) = &scan.vendors {
+            ui.label(RichText::new(format!("Vendors unavailable: {}", e)).small().color(grey));
+        }
+        ui.add_space(12.0);
+
+        if !scan.devices.is_empty() {
+            ui.label(format!("{} devices", scan.devices.len()));
+            ui.add_space(4.0);
+            egui::Grid::new("lan_scan_grid").striped(true).spacing([16.0, 4.0]).show(ui, |ui| {
+                ui.label(RichText::new("Address").strong());
+                ui.label(RichText::new("MAC").strong());
+                ui.label(RichText::new("Vendor").strong());
+                ui.label(RichText::new("Name").strong());
+                ui.label(RichText::new("Ping").strong());
+                ui.label("");
+                ui.end_row();
+
+                for device in &scan.devices {
+                    let mac = device.mac_text();
+                    ui.label(RichText::new(device.address.to_string()).monospace());
+                    if ui.add(egui::Label::new(RichText::new(&mac).monospace()).sense(egui::Sense::click()))
+                        .on_hover_text("Click to copy")
+                        .clicked() {
+                        ui.output_mut(|output| output.copied_text = mac.clone());
+                    }
+                    match scan.vendor(device) {
+                        Some(vendor) => ui.label(vendor),
+                        None if device.is_private_mac() => ui.label(RichText::new("Private address").color(grey))
+                            .on_hover_text("Phones and laptops use a random MAC address per network"),
+                        None => ui.label(""),
+                    };
+                    ui.label(device.hostname.as_deref().unwrap_or(""));
+                    match device.ping_ms {
+                        Some(ms) => ui.label(format!("{} ms", ms)),
+                        None => ui.label(RichText::new("no reply").color(grey)),
+                    };
+                    match scan.note(device) {
+                        Some((note, color)) => ui.colored_label(color, note),
+                        None => ui.label(""),
+                    };
+                    ui.end_row();
+                }
+            });
+        } else if scan.scans > 0 && !scan.is_scanning() {
+            ui.label("No devices answered.");
+        }
+
+        if start {
+            self.start_lan_scan();
+        }
+    }
+}
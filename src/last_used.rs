@@ -0,0 +1,176 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Local, TimeZone};
+use eframe::egui;
+use egui::{Color32, RichText};
+use winreg::enums::HKEY_CURRENT_USER;
+use winreg::RegKey;
+use log::debug;
+use crate::{DevDashboard, NiniteApp};
+
+/// Explorer's launch counters; each subkey holds a `Count` key of ROT13-encoded paths
+const USER_ASSIST_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Explorer\UserAssist";
+/// Windows writes a prefetch file per executable it launches; listing the folder needs administrator rights
+const PREFETCH_DIR: &str = r"C:\Windows\Prefetch";
+/// Launches are only re-read occasionally; running apps are noticed in between
+const SCAN_INTERVAL: Duration = Duration::from_secs(30 * 60);
+/// Apps not launched for this long are flagged as cleanup candidates
+const STALE_DAYS: i64 = 90;
+/// Windows FILETIME ticks (100 ns since 1601) at the Unix epoch
+const FILETIME_UNIX_EPOCH: u64 = 116_444_736_000_000_000;
+
+fn rot13(text: &str) -> String {
+    text.chars().map(|c| match c {
+        'a'..='z' => (((c as u8 - b'a') + 13) % 26 + b'a') as char,
+        'A'..='Z' => (((c as u8 - b'A') + 13) % 26 + b'A') as char,
+        _ => c,
+    }).collect()
+}
+
+fn from_filetime(ticks: u64) -> Option<DateTime<Local>> {
+    let ticks = ticks.checked_sub(FILETIME_UNIX_EPOCH).filter(|ticks| *ticks > 0)?;
+    Local.timestamp_opt((ticks / 10_000_000) as i64, 0).single()
+}
+
+/// Lower-cased file name of a launched path, the key both sources are matched on
+fn file_key(path: &str) -> Option<String> {
+    let name = path.rsplit(['\\', '/']).next()?.trim();
+    (!name.is_empty()).then(|| name.to_lowercase())
+}
+
+fn keep_latest(times: &mut HashMap<String, DateTime<Local>>, key: String, time: DateTime<Local>) {
+    let entry = times.entry(key).or_insert(time);
+    if time > *entry {
+        *entry = time;
+    }
+}
+
+/// Last launch per executable from UserAssist, which records programs started from Explorer and the Start menu.
+/// Each value is 72 bytes with the last launch as a FILETIME at offset 60.
+fn read_user_assist(times: &mut HashMap<String, DateTime<Local>>) {
+    let Ok(root) = RegKey::predef(HKEY_CURRENT_USER).open_subkey(USER_ASSIST_KEY) else { return };
+    for guid in root.enum_keys().flatten() {
+        let Ok(count) = root.open_subkey(format!(r"{}\Count", guid)) else { continue };
+        for (name, value) in count.enum_values().flatten() {
+            let bytes = &value.bytes;
+            if bytes.len() < 68 {
+                continue;
+            }
+            let ticks = u64::from_le_bytes(bytes[60..68].try_into().unwrap_or_default());
+            if let (Some(key), Some(time)) = (file_key(&rot13(&name)), from_filetime(ticks)) {
+                keep_latest(times, key, time);
+            }
+        }
+    }
+}
+
+/// Last launch per executable from prefetch files named like `CHROME.EXE-1A2B3C4D.pf`
+fn read_prefetch(times: &mut HashMap<String, DateTime<Local>>) {
+    let Ok(entries) = std::fs::read_dir(PREFETCH_DIR) else {
+        debug!("Prefetch folder is not readable without administrator rights");
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Some((exe, _)) = name.rsplit_once('-') else { continue };
+        let Ok(modified) = entry.metadata().and_then(|metadata| metadata.modified()) else { continue };
+        keep_latest(times, exe.to_lowercase(), DateTime::<Local>::from(modified));
+    }
+}
+
+fn scan() -> HashMap<String, DateTime<Local>> {
+    let mut times = HashMap::new();
+    read_user_assist(&mut times);
+    read_prefetch(&mut times);
+    debug!("Read last launch times for {} executables", times.len());
+    times
+}
+
+/// Executables an app is detected by, lower-cased
+fn exe_names(app: &NiniteApp) -> Vec<String> {
+    let mut names: Vec<String> = app.file_paths.iter()
+        .filter_map(|path| file_key(path))
+        .filter(|name| name.ends_with(".exe") && !name.contains('*'))
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// When installed apps were last launched, for deciding what to uninstall
+#[derive(Default)]
+pub struct LastUsed {
+    times: HashMap<String, DateTime<Local>>,    // By lower-cased executable name
+    running: HashSet<String>,                   // Executables running at the last status check
+    receiver: Option<Receiver<HashMap<String, DateTime<Local>>>>,
+    last_scan: Option<Instant>,
+}
+
+impl LastUsed {
+    /// Records which executables are running, called with the installer's periodic process list
+    pub fn set_running(&mut self, names: impl Iterator<Item = String>) {
+        let now = Local::now();
+        self.running = names.map(|name| name.to_lowercase()).collect();
+        for name in &self.running {
+            self.times.insert(name.clone(), now);
+        }
+    }
+
+    fn last_used(&self, app: &NiniteApp) -> Option<DateTime<Local>> {
+        exe_names(app).iter().filter_map(|name| self.times.get(name)).max().copied()
+    }
+
+    fn is_running(&self, app: &NiniteApp) -> bool {
+        exe_names(app).iter().any(|name| self.running.contains(name))
+    }
+
+    /// "Running now", "last used 3 days ago", or a flag for apps unused for months
+    pub fn show(&self, ui: &mut egui::Ui, app: &NiniteApp) {
+        let grey = Color32::from_rgb(107, 114, 128);
+        let hover = "From Explorer's launch history and, when running as administrator, Windows prefetch files";
+        if self.is_running(app) {
+            ui.label(RichText::new("· running now").color(Color32::from_rgb(22, 163, 74)));
+            return;
+        }
+        let Some(time) = self.last_used(app) else {
+            if self.last_scan.is_some() && self.receiver.is_none() {
+                ui.label(RichText::new("· no recorded launches").color(grey)).on_hover_text(hover);
+            }
+            return;
+        };
+        let days = (Local::now() - time).num_days();
+        let text = match days {
+            0 => "· last used today".to_string(),
+            1 => "· last used yesterday".to_string(),
+            _ => format!("· last used {} days ago", days),
+        };
+        let color = if days >= STALE_DAYS { Color32::from_rgb(202, 138, 4) } else { grey };
+        ui.label(RichText::new(text).color(color))
+            .on_hover_text(format!("{}\n{}", time.format("%Y-%m-%d %H:%M"), hover));
+    }
+}
+
+impl DevDashboard {
+    /// Re-reads launch history in the background every half hour while the installer page is open
+    pub(crate) fn update_last_used(&mut self) {
+        let last_used = &mut self.last_used;
+        if let Some(receiver) = &last_used.receiver {
+            if let Ok(times) = receiver.try_recv() {
+                for (name, time) in times {
+                    keep_latest(&mut last_used.times, name, time);
+                }
+                last_used.receiver = None;
+            }
+        }
+        if last_used.receiver.is_some() || last_used.last_scan.is_some_and(|time| time.elapsed() < SCAN_INTERVAL) {
+            return;
+        }
+        last_used.last_scan = Some(Instant::now());
+        let (sender, receiver) = channel();
+        last_used.receiver = Some(receiver);
+        std::thread::spawn(move || {
+            sender.send(scan()).ok();
+        });
+    }
+}
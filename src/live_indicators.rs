@@ -0,0 +1,104 @@
+use std::collections::VecDeque;
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use crate::DevDashboard;
+
+/// Title eframe opens the main window with
+const APP_TITLE: &str = "Dev Dashboard";
+const SPARK_SIZE: egui::Vec2 = egui::vec2(44.0, 14.0);
+
+/// Where CPU and memory are shown while the Dashboard tab is not
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct LiveIndicatorSettings {
+    pub tab_bar: bool,              // Tiny graphs at the right of the tab bar
+    pub window_title: bool,         // Percentages in the title, visible on the taskbar and in Alt+Tab
+}
+
+impl Default for LiveIndicatorSettings {
+    fn default() -> Self {
+        Self {
+            tab_bar: true,
+            window_title: false,
+        }
+    }
+}
+
+/// The title last given to the main window, so it is only replaced when it changes
+#[derive(Default)]
+pub struct LiveIndicators {
+    title: String,
+}
+
+/// Percentage line without a caption, scaled to 100
+fn tiny_sparkline(ui: &mut egui::Ui, values: &VecDeque<f32>, color: Color32) {
+    let (rect, _) = ui.allocate_exact_size(SPARK_SIZE, egui::Sense::hover());
+    let painter = ui.painter();
+    painter.rect_filled(rect, 2.0, Color32::from_rgb(31, 41, 55));
+    if values.len() < 2 {
+        return;
+    }
+    let step = rect.width() / (values.len() - 1) as f32;
+    let points: Vec<egui::Pos2> = values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| egui::pos2(
+            rect.left() + index as f32 * step,
+            rect.bottom() - (value / 100.0).clamp(0.0, 1.0) * (rect.height() - 2.0),
+        ))
+        .collect();
+    painter.add(egui::Shape::line(points, egui::Stroke::new(1.0, color)));
+}
+
+impl DevDashboard {
+    /// CPU and memory graphs with their current value, laid out right-to-left at the end of the tab bar
+    pub(crate) fn show_tab_indicators(&self, ui: &mut egui::Ui) {
+        if !self.settings.live_indicators.tab_bar {
+            return;
+        }
+        let history = &self.tray.history;
+        let (Some(cpu), Some(memory)) = (history.cpu.back(), history.memory.back()) else { return };
+        let grey = Color32::from_rgb(156, 163, 175);
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            let hover = "The last minute; the full graphs are on the Dashboard tab";
+            ui.label(RichText::new(format!("RAM {:.0}%", memory)).small().color(grey)).on_hover_text(hover);
+            tiny_sparkline(ui, &history.memory, Color32::from_rgb(22, 163, 74));
+            ui.add_space(8.0);
+            ui.label(RichText::new(format!("CPU {:.0}%", cpu)).small().color(grey)).on_hover_text(hover);
+            tiny_sparkline(ui, &history.cpu, Color32::from_rgb(37, 99, 235));
+            if self.monitoring_paused() {
+                ui.label(RichText::new("paused").small().color(Color32::from_rgb(202, 138, 4)));
+            }
+        });
+    }
+
+    /// Puts CPU and memory in the window title when enabled, and the plain title back when not
+    pub(crate) fn update_window_title(&mut self, ctx: &egui::Context) {
+        let history = &self.tray.history;
+        let title = match (history.cpu.back(), history.memory.back()) {
+            (Some(cpu), Some(memory)) if self.settings.live_indicators.window_title && !self.monitoring_paused() => {
+                format!("{} · CPU {:.0}% · RAM {:.0}%", APP_TITLE, cpu, memory)
+            }
+            _ => APP_TITLE.to_string(),
+        };
+        // Nothing to restore until a live title was set
+        if title == self.live_indicators.title || (self.live_indicators.title.is_empty() && title == APP_TITLE) {
+            return;
+        }
+        ctx.send_viewport_cmd_to(egui::ViewportId::ROOT, egui::ViewportCommand::Title(title.clone()));
+        self.live_indicators.title = title;
+    }
+
+    pub(crate) fn show_live_indicator_settings(&mut self, ui: &mut egui::Ui) {
+        ui.label("Live Indicators:");
+        let settings = &mut self.settings.live_indicators;
+        let mut changed = ui.checkbox(&mut settings.tab_bar, "Show CPU and memory graphs in the tab bar").changed();
+        changed |= ui.checkbox(&mut settings.window_title, "Show CPU and memory in the window title")
+            .on_hover_text("Visible on the taskbar button and in Alt+Tab while the dashboard is in the background")
+            .changed();
+        if changed {
+            self.save_settings();
+        }
+    }
+}
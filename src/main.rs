@@ -7,15 +7,20 @@ use std::collections::HashMap;
 use windows::Win32::Storage::FileSystem::GetDiskFreeSpaceExA;
 use windows::core::PCSTR;
 use wmi::{COMLibrary, WMIConnection};
+#[cfg(feature = "nvml")]
 use nvml_wrapper::Nvml;
+#[cfg(feature = "nvml")]
+use nvml_wrapper::enum_wrappers::device::{Clock, TemperatureSensor};
 use log::{error, info, warn, debug};
 use simplelog::{WriteLogger, LevelFilter, Config};
-use std::fs::{File, OpenOptions};
-use std::io::{Read, Write};
+use std::fs::OpenOptions;
+use std::io::Write;
 use whoami::username;
 use serde::{Serialize, Deserialize};
 use futures::StreamExt;
 use std::sync::mpsc::{channel, Sender, Receiver};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::error::Error as StdError;
 use winreg::enums::*;
 use winreg::RegKey;
@@ -23,12 +28,198 @@ use std::ffi::CString;
 use reqwest::Client;
 use glob::glob;
 use tokio::process::Command as TokioCommand;
+use tokio::sync::Notify;
 use egui::RichText;
 
+mod alerts;
+mod api;
+mod app_info;
+mod app_names;
+mod arch;
+mod backup;
+mod battery;
+mod benchmark;
+mod calendar;
+mod card_export;
+mod caffeine;
+mod catalog;
+mod cli;
+mod certificates;
+mod cloud;
+mod color_picker;
+mod command_card;
+mod containers;
+mod converters;
+mod cpu_throttle;
+mod crash;
+mod credentials;
+mod daily_summary;
+mod d3dkmt;
+mod diff;
+mod disk_forecast;
+mod disk_io;
+mod elevation;
+mod favorites;
+mod generator;
+mod gpu_compute;
+mod gpu_driver;
+mod gpu_history;
+mod gpu_limits;
+mod guard;
+mod header;
+mod history_compare;
+mod hybrid_gpu;
+mod idle;
+mod install_audit;
+mod install_size;
+mod instance;
+mod job_watch;
+mod known_folders;
+mod lan_drop;
+mod lan_scan;
+mod last_used;
+mod live_indicators;
+mod metric_graphs;
+mod network_events;
+mod network_shares;
+mod network_totals;
+mod notifications;
+mod overlay;
+mod package_sources;
+mod plugins;
+mod popout;
+mod port_usage;
+mod ports;
+mod power;
+mod presets;
+mod process_network;
+mod process_search;
+mod process_watch;
+mod processes;
+mod provision;
+mod qr;
+mod recorder;
+mod repos;
+mod screenshot;
+mod scripting;
+mod security;
+mod sensor_recovery;
+mod services;
+mod share;
+mod signature;
+mod snapshot;
+mod stacks;
+mod storage;
+mod temperature_trend;
+mod theme;
+mod ticker;
+mod toast;
+mod todo;
+mod top_processes;
+mod tour;
+mod tray;
+mod tunnels;
+mod uptime;
+mod usage;
+mod user_catalog;
+mod volumes;
+mod wall;
+mod watchdog;
+mod wmi_card;
+mod wsl;
+
+use alerts::{Alerts, AlertSettings};
+use api::{ApiServer, ApiSettings};
+use app_info::{AppInfo, AppInfoCache};
+use arch::{Arch, Support};
+use backup::{BackupMonitor, BackupSettings};
+use battery::BatteryHealth;
+use benchmark::Benchmark;
+use caffeine::{Caffeine, CaffeineSettings};
+use calendar::{Calendar, CalendarSettings};
+use catalog::{AppCatalog, CatalogSettings};
+use certificates::{CertificateMonitor, CertificateSettings};
+use cli::{Startup, StartupOptions};
+use cloud::{CloudSettings, CloudStorage};
+use color_picker::ColorPicker;
+use command_card::{CommandCardSettings, CommandCards};
+use containers::{ContainerMonitor, ContainerSettings};
+use converters::Converters;
+use crash::{CrashReporter, CrashSettings};
+use cpu_throttle::ThrottleMonitor;
+use daily_summary::DailySummaries;
+use diff::TextDiff;
+use disk_forecast::StorageHistory;
+use disk_io::DiskIoMonitor;
+use elevation::ElevationBroker;
+use favorites::{FavoriteSettings, GatewayPing};
+use generator::Generator;
+use gpu_driver::{DriverUpdateCheck, GpuDriverSettings};
+use gpu_history::GpuHistory;
+use gpu_compute::GpuCompute;
+use gpu_limits::{GpuLimitControl, GpuLimits, GpuPowerSettings};
+use guard::{ActionGuard, ActionLockSettings, SensitiveAction};
+use header::{capitalize_first, Header, HeaderSettings};
+use history_compare::{ArchiveMetric, MetricArchive};
+use hybrid_gpu::{GpuAdapter, GpuAdapters, GpuSelectionSettings};
+use idle::{IdleMonitor, IdleSettings, IDLE_INTERVAL};
+use install_audit::{InstallAudit, InstallScopes};
+use install_size::InstallSize;
+use instance::InstanceListener;
+use job_watch::{JobWatch, JobWatchSettings};
+use lan_drop::{LanDrop, LanDropSettings};
+use lan_scan::LanScan;
+use last_used::LastUsed;
+use live_indicators::{LiveIndicators, LiveIndicatorSettings};
+use metric_graphs::{GraphMetric, GraphSettings, MetricGraphs};
+use network_events::NetworkEvents;
+use notifications::{NotificationCenter, NotificationSource};
+use network_shares::{NetworkShareSettings, ShareMonitor};
+use network_totals::{NetworkCardSettings, NetworkTotals, Traffic};
+use overlay::{Overlay, OverlaySettings};
+use package_sources::{InstallBackend, PackageSourceSettings, SourceRegistry};
+use plugins::{PluginHost, PluginSettings};
+use popout::{PopoutSettings, Popouts};
+use port_usage::PortUsage;
+use power::{PowerMonitor, PowerSettings};
+use presets::{PresetEditor, PresetSettings};
+use processes::ProcessView;
+use process_network::{ProcessNetworkMonitor, ProcessNetworkSettings};
+use process_watch::{ProcessWatcher, ProcessWatchSettings};
+use provision::ProvisionOptions;
+use qr::QrTool;
+use recorder::{MetricsRecorder, RecorderSettings};
+use repos::{RepoMonitor, RepoSettings};
+use screenshot::ScreenshotTool;
+use scripting::{ScriptHost, ScriptSettings};
+use security::SecurityAudit;
+use sensor_recovery::SensorBackends;
+use services::ServiceList;
+use share::{ReportShare, ShareSettings};
+use snapshot::Snapshots;
+use stacks::{StackRunner, StackSettings};
+use temperature_trend::{TemperatureAlertSettings, TemperatureTrends};
+use theme::{ThemeSettings, ThemeState};
+use ticker::{Ticker, TickerSettings};
+use todo::TodoList;
+use top_processes::{TopProcesses, TopProcessSettings};
+use tour::{Tour, TourSettings, TourTarget};
+use tray::{Tray, TraySettings};
+use tunnels::{TunnelManager, TunnelSettings};
+use uptime::{UptimeHistory, UptimeSettings};
+use usage::{UsageSettings, UsageTracker};
+use user_catalog::UserCatalog;
+use volumes::{DiskRefreshSettings, VolumeMonitor};
+use wall::{Wall, WallSettings};
+use watchdog::{Watchdog, WatchdogSettings};
+use wmi_card::{WmiCardSettings, WmiCards};
+use wsl::{WslMonitor, WslSettings};
+
 #[derive(Debug)]
 enum InstallerError {
     NoAppsSelected,
     DownloadFailed(String),
+    InstallFailed(String),
     ChannelError(String),
 }
 
@@ -40,6 +231,7 @@ impl std::fmt::Display for InstallerError {
         match self {
             InstallerError::NoAppsSelected => write!(f, "No apps selected"),
             InstallerError::DownloadFailed(msg) => write!(f, "Download failed: {}", msg),
+            InstallerError::InstallFailed(msg) => write!(f, "Not installed: {}", msg),
             InstallerError::ChannelError(msg) => write!(f, "Communication error: {}", msg),
         }
     }
@@ -111,21 +303,194 @@ impl From<std::io::Error> for IoErrorWrapper {
 }
 
 #[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
 struct Settings {
-    custom_username: Option<String>,
+    #[serde(skip_serializing)]
+    custom_username: Option<String>,    // Replaced by per-profile names in the header settings; read once to migrate
+    action_lock: ActionLockSettings,
+    certificates: CertificateSettings,
+    ticker: TickerSettings,
+    calendar: CalendarSettings,
+    show_todo_card: bool,
+    usage: UsageSettings,
+    idle: IdleSettings,
+    backup: BackupSettings,
+    cloud: CloudSettings,
+    power: PowerSettings,
+    lan_drop: LanDropSettings,
+    caffeine: CaffeineSettings,
+    share: ShareSettings,
+    plugins: PluginSettings,
+    scripts: ScriptSettings,
+    wmi_cards: WmiCardSettings,
+    command_cards: CommandCardSettings,
+    uptime: UptimeSettings,
+    top_processes: TopProcessSettings,
+    process_network: ProcessNetworkSettings,
+    wsl: WslSettings,
+    containers: ContainerSettings,
+    network_shares: NetworkShareSettings,
+    repos: RepoSettings,
+    watchdog: WatchdogSettings,
+    stacks: StackSettings,
+    tunnels: TunnelSettings,
+    gpu_power: GpuPowerSettings,
+    gpu_driver: GpuDriverSettings,
+    gpu_selection: GpuSelectionSettings,
+    tray: TraySettings,
+    header: HeaderSettings,
+    theme: ThemeSettings,
+    graphs: GraphSettings,
+    tour: TourSettings,
+    popouts: PopoutSettings,
+    disk_refresh: DiskRefreshSettings,
+    catalog: CatalogSettings,
+    package_sources: PackageSourceSettings,
+    crash: CrashSettings,
+    process_watch: ProcessWatchSettings,
+    job_watch: JobWatchSettings,
+    wall: WallSettings,
+    overlay: OverlaySettings,
+    network_card: NetworkCardSettings,
+    live_indicators: LiveIndicatorSettings,
+    favorites: FavoriteSettings,
+    temperature_alerts: TemperatureAlertSettings,
+    api: ApiSettings,
+    recorder: RecorderSettings,
+    presets: PresetSettings,
+    alerts: AlertSettings,
+}
+
+/// Cards that can appear on the Dashboard tab, in display order
+#[derive(Clone, Copy, PartialEq)]
+enum DashboardCard {
+    System,
+    Cpu,
+    Memory,
+    Storage,
+    Network,
+    Gpu,
+    Certificates,
+    Ticker,
+    Calendar,
+    Todo,
+    Usage,
+    Backup,
+    Cloud,
+    Power,
+    Battery,
+    Uptime,
+    NetworkShares,
+    Repos,
+    Stacks,
+    Tunnels,
+    TopProcesses,
+    ProcessNetwork,
+    Wsl,
+    Plugin(usize),                   // Index into the loaded plugins
+    Script(usize),                   // Index into the user scripts
+    Wmi(usize),                      // Index into the custom WMI cards
+    Command(usize),                  // Index into the command output cards
 }
 
 #[derive(PartialEq)]
 enum Tab {
     Dashboard,
     Tools,
+    Processes,
+    Security,
+    Containers,
+    Services,
+}
+
+/// Pages of the Tools tab
+#[derive(Clone, Copy, PartialEq)]
+enum ToolPage {
+    Installer,
+    Benchmark,
+    Screenshot,
+    ColorPicker,
+    Converters,
+    Generator,
+    Qr,
+    Diff,
+    FileDrop,
+    LanScan,
+    Ports,
+    Snapshots,
+}
+
+/// Where the Ninite installer is downloaded to, relative to the working directory
+const INSTALLER_PATH: &str = "ninite.exe";
+
+/// Process creation flag that keeps console programs run in the background from flashing a window
+pub(crate) const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// Bytes received so far and the recent transfer rate
+#[derive(Clone, Default)]
+struct DownloadProgress {
+    downloaded: u64,
+    total: u64,                      // 0 when the server sends no length
+    bytes_per_sec: f64,
+}
+
+impl DownloadProgress {
+    fn fraction(&self) -> f32 {
+        if self.total > 0 {
+            self.downloaded as f32 / self.total as f32
+        } else {
+            0.0
+        }
+    }
+
+    /// "12.3 MB of 45.0 MB · 3.2 MB/s · about 10 s left"
+    fn describe(&self) -> String {
+        let size = |bytes: u64| {
+            let (value, unit) = DevDashboard::format_bytes(bytes);
+            format!("{:.1} {}", value, unit)
+        };
+        let mut text = size(self.downloaded);
+        if self.total > 0 {
+            text.push_str(&format!(" of {}", size(self.total)));
+        }
+        if self.bytes_per_sec > 0.0 {
+            text.push_str(&format!(" · {}/s", size(self.bytes_per_sec as u64)));
+            if self.total > self.downloaded {
+                let secs = ((self.total - self.downloaded) as f64 / self.bytes_per_sec).ceil() as u64;
+                if secs >= 60 {
+                    text.push_str(&format!(" · about {} min {} s left", secs / 60, secs % 60));
+                } else {
+                    text.push_str(&format!(" · about {} s left", secs));
+                }
+            }
+        }
+        text
+    }
+}
+
+/// Pause and cancel requests from the UI, checked by the download between chunks
+#[derive(Default)]
+struct InstallerControl {
+    cancel: AtomicBool,
+    pause: AtomicBool,
+    cancelled: Notify,               // Wakes a download waiting on a stalled connection
+}
+
+impl InstallerControl {
+    fn request_cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+        // Stores a permit when the download isn't waiting yet, so the next wait ends at once
+        self.cancelled.notify_one();
+    }
 }
 
 #[derive(Clone)]
 enum InstallerMessage {
-    UpdateProgress(f32),
+    UpdateProgress(DownloadProgress),
     SetState(InstallerState),
     Error(String),
+    Paused(bool),                    // The download stopped or carried on at a pause request
+    Cancelled,                       // The download stopped and its partial file is gone
 }
 
 #[derive(PartialEq, Clone)]
@@ -147,13 +512,15 @@ fn lerp(start: f32, end: f32, t: f32) -> f32 {
 /// Structure to track network interface statistics
 /// Handles both cumulative and real-time network usage data
 struct NetworkStats {
-    total_received: u64,      // Total bytes received since start
-    total_sent: u64,          // Total bytes sent since start
-    last_received: u64,       // Last recorded received bytes
-    last_sent: u64,           // Last recorded sent bytes
+    total_received: u64,      // Total bytes received since the interface came up, usually boot
+    total_sent: u64,          // Total bytes sent since the interface came up, usually boot
+    last_received: u64,       // Interface receive counter at the previous sample
+    last_sent: u64,           // Interface send counter at the previous sample
     received_speed: f64,      // Current receive speed in bytes/second
     sent_speed: f64,          // Current send speed in bytes/second
     last_update: Instant,     // Timestamp of last update
+    session: Traffic,         // Bytes since the app started or the counters were reset
+    session_start: chrono::DateTime<chrono::Local>,
 }
 
 /// Structure for smooth value transitions with animation
@@ -193,10 +560,20 @@ struct GpuInfo {
     memory_used: Option<u64>,        // Used VRAM in bytes
     utilization: Option<f32>,        // GPU utilization percentage
     temperature: Option<u32>,        // GPU temperature in Celsius
+    power_draw: Option<f32>,         // Board power draw in watts (NVIDIA only)
+    graphics_clock: Option<u32>,     // Core clock in MHz
+    memory_clock: Option<u32>,       // Memory clock in MHz
     memory_usage: AnimatedValue,     // Animated VRAM usage percentage
     gpu_usage: AnimatedValue,        // Animated GPU utilization percentage
     pci_bus_id: Option<String>,      // PCI bus ID for hardware identification
     driver_version: Option<String>,  // GPU driver version
+    history: GpuHistory,             // Recent readings for the detail window
+    limits: GpuLimits,               // Power and temperature limits and clock offsets (NVIDIA only)
+    compute: GpuCompute,             // ECC, compute mode and retired pages (NVIDIA workstation cards)
+    #[cfg(feature = "nvml")]
+    nvml_index: Option<u32>,         // NVML device index; None when the card shows a GPU through WMI
+    luid: Option<String>,            // Performance counter tag of a pinned adapter, so other GPUs are left out
+    sensor_luid: Option<(i32, u32)>, // DXGI LUID for the graphics kernel's temperature and clocks on non-NVIDIA GPUs
 }
 
 impl GpuInfo {
@@ -208,10 +585,20 @@ impl GpuInfo {
             memory_used: None,
             utilization: None,
             temperature: None,
+            power_draw: None,
+            graphics_clock: None,
+            memory_clock: None,
             memory_usage: AnimatedValue::new(0.0),
             gpu_usage: AnimatedValue::new(0.0),
             pci_bus_id: None,
             driver_version: None,
+            history: GpuHistory::default(),
+            limits: GpuLimits::default(),
+            compute: GpuCompute::default(),
+            #[cfg(feature = "nvml")]
+            nvml_index: None,
+            luid: None,
+            sensor_luid: None,
         }
     }
 }
@@ -224,6 +611,11 @@ struct NiniteApp {
     registry_keys: Vec<String>,  // Registry keys to check for installation
     file_paths: Vec<String>,     // Common installation file paths to check
     installed: bool,
+    size: Option<InstallSize>,   // Estimated download and installed size
+    builds: Vec<Arch>,           // Native builds Ninite offers; empty when unknown
+    info: AppInfo,               // Description, homepage and license for the info popover
+    #[serde(skip)]
+    scopes: InstallScopes,       // Whether it was found per-machine, per-user or both
 }
 
 impl NiniteApp {
@@ -235,6 +627,10 @@ impl NiniteApp {
             registry_keys: registry_keys.iter().map(|&s| s.to_string()).collect(),
             file_paths: file_paths.iter().map(|&s| s.to_string()).collect(),
             installed: false,
+            size: install_size::estimate(ninite_id),
+            builds: arch::builds(ninite_id),
+            info: app_info::builtin(ninite_id),
+            scopes: InstallScopes::default(),
         }
     }
 
@@ -246,17 +642,21 @@ impl NiniteApp {
         // Check both 64-bit and 32-bit registry views
         let views = [KEY_READ | KEY_WOW64_64KEY, KEY_READ | KEY_WOW64_32KEY];
         
-        // Check registry keys
-        let registry_installed = self.registry_keys.iter().any(|key_path| {
+        // Check registry keys; uninstall entries also tell per-machine and per-user installs apart
+        let mut scopes = InstallScopes::default();
+        let registry_installed = self.registry_keys.iter().filter(|key_path| {
+            let uninstall_entry = install_audit::is_uninstall_key(key_path);
             let result = views.iter().any(|view| {
                 // Try HKLM first
                 if hklm.open_subkey_with_flags(key_path, *view).is_ok() {
                     info!("Found {} in HKLM registry: {}", self.name, key_path);
+                    scopes.machine |= uninstall_entry;
                     return true;
                 }
                 // Then try HKCU
                 if hkcu.open_subkey_with_flags(key_path, *view).is_ok() {
                     info!("Found {} in HKCU registry: {}", self.name, key_path);
+                    scopes.user |= uninstall_entry;
                     return true;
                 }
                 debug!("Registry key not found for {}: {} (view: {})", 
@@ -265,16 +665,14 @@ impl NiniteApp {
                 false
             });
             result
-        });
+        }).count() > 0;
 
-        // Get current username for path expansion
-        let username = whoami::username();
-        debug!("Checking file paths for {} with username {}", self.name, username);
+        debug!("Checking file paths for {}", self.name);
 
         // Check file paths with environment variable expansion and glob support
-        let file_installed = self.file_paths.iter().any(|path| {
-            // Replace %USERNAME% with actual username
-            let expanded_path = path.replace("%USERNAME%", &username);
+        let found_paths: Vec<&String> = self.file_paths.iter().filter(|path| {
+            // Catalogs write English default locations; map them to this machine's known folders
+            let expanded_path = known_folders::resolve(path);
             debug!("Checking path for {}: {}", self.name, expanded_path);
             
             // Use glob for wildcard pattern matching
@@ -313,7 +711,15 @@ impl NiniteApp {
                 }
                 exists
             }
-        });
+        }).collect();
+        let file_installed = !found_paths.is_empty();
+        for path in &found_paths {
+            if install_audit::is_per_user(path) {
+                scopes.user = true;
+            } else {
+                scopes.machine = true;
+            }
+        }
 
         // Additional registry checks for uninstall entries
         let uninstall_installed = {
@@ -326,9 +732,7 @@ impl NiniteApp {
                         for subkey in subkeys {
                             if let Ok(app_key) = uninstall.open_subkey(&subkey) {
                                 if let Ok(display_name) = app_key.get_value::<String, _>("DisplayName") {
-                                    let display_name_lower = display_name.to_lowercase();
-                                    let app_name_lower = self.name.to_lowercase();
-                                    if display_name_lower.contains(&app_name_lower) {
+                                    if app_names::matches(&display_name, &self.name) {
                                         // Also check InstallLocation if available
                                         if let Ok(install_location) = app_key.get_value::<String, _>("InstallLocation") {
                                             let location_path = std::path::Path::new(&install_location);
@@ -352,6 +756,7 @@ impl NiniteApp {
         };
 
         let was_installed = self.installed;
+        self.scopes = scopes;
         
         // Mark as installed if the executable is found, regardless of registry keys
         self.installed = file_installed;
@@ -375,21 +780,110 @@ pub struct DevDashboard {
     last_frame_time: Instant,        // Last UI frame timestamp
     last_check: Instant,             // Last Ninite check timestamp
     current_cpu_usage: AnimatedValue, // Animated CPU usage percentage
+    core_usage: Vec<AnimatedValue>,  // Animated usage percentage per logical core
     memory_usage: AnimatedValue,     // Animated memory usage percentage
     disk_usage: HashMap<String, AnimatedValue>, // Disk usage per drive
     network_stats: HashMap<String, NetworkStats>, // Network stats per interface
     gpu_info: Option<GpuInfo>,       // GPU information if available
+    #[cfg(feature = "nvml")]
     nvml: Option<Nvml>,              // NVIDIA Management Library instance
     settings: Settings,              // Application settings
     show_settings: bool,             // Whether to show settings window
     current_tab: Tab,                // Current selected tab
+    tool_page: ToolPage,             // Selected page of the Tools tab
     ninite_apps: Vec<NiniteApp>,     // List of available Ninite apps
     selected_apps: Vec<String>,      // Selected apps for installation
-    download_progress: f32,          // Download progress (0.0 to 1.0)
+    download_progress: DownloadProgress, // Bytes, rate and total of the installer download
+    installer_control: Arc<InstallerControl>, // Pause and cancel requests for the running download
+    installer_paused: bool,          // The download is waiting on Resume
     installer_state: InstallerState, // Current state of the installer
     runtime: Option<tokio::runtime::Runtime>, // Tokio runtime for async operations
     message_receiver: Option<Receiver<InstallerMessage>>,
     ninite_running: bool,
+    security_audit: SecurityAudit,   // Open-port audit state for the Security tab
+    action_guard: ActionGuard,       // PIN / Windows Hello prompt state
+    certificate_monitor: CertificateMonitor, // TLS certificate expiry checks
+    process_view: ProcessView,       // Processes tab state
+    ticker: Ticker,                  // Ticker card quotes
+    calendar: Calendar,              // Agenda loaded from the .ics feed
+    todos: TodoList,                 // Todo card items, persisted to todos.json
+    usage: UsageTracker,             // Foreground time per app, persisted to usage.json
+    idle: IdleMonitor,               // Away status from system idle time
+    backup_monitor: BackupMonitor,   // File History and folder backup checks
+    cloud_storage: CloudStorage,     // Sync clients and quotas for the cloud card
+    power_monitor: PowerMonitor,     // Power draw estimate and daily energy, persisted to energy.json
+    battery_health: BatteryHealth,   // Parsed powercfg battery report
+    benchmark: Benchmark,            // Benchmark tool state and saved results
+    screenshot: ScreenshotTool,      // Current capture and its annotations
+    color_picker: ColorPicker,       // Eyedropper state and recent colors
+    converters: Converters,          // Inputs of the converter tool
+    generator: Generator,            // Options and last generated IDs
+    qr_tool: QrTool,                 // Text and code of the QR tool
+    text_diff: TextDiff,             // Texts and options of the diff tool
+    lan_drop: LanDrop,               // LAN discovery, offers and transfers
+    lan_scan: LanScan,               // Devices found by the LAN scan tool
+    port_usage: PortUsage,           // Listening ports and filter of the Ports tool
+    report_share: ReportShare,       // Share Report window and upload result
+    plugins: PluginHost,             // Plugins found at startup and their latest card content
+    scripts: ScriptHost,             // Rhai engine and the scripts in the scripts folder
+    wmi_cards: WmiCards,             // Latest results of the custom WMI queries
+    command_cards: CommandCards,     // Latest output of the command cards
+    network_totals: NetworkTotals,   // Daily and all-time traffic per interface, persisted to network.json
+    uptime_history: UptimeHistory,   // Boot sessions from the event log and own observations, persisted to boots.json
+    gpu_detail_open: bool,           // GPU history window is showing
+    gpu_adapters: GpuAdapters,       // Integrated and discrete GPUs on hybrid systems
+    sensor_backends: SensorBackends, // NVML and GPU detection failures, retried with backoff
+    disk_io: DiskIoMonitor,          // Per-drive latency, queue length and busy time
+    share_monitor: ShareMonitor,     // Latency and availability of registered network shares
+    repo_monitor: RepoMonitor,       // Branch and working tree state of registered git repositories
+    watchdog: Watchdog,              // Processes over their watchdog limits and recent actions
+    stack_runner: StackRunner,       // Progress of stack restarts
+    tunnel_manager: TunnelManager,   // SSH tunnel processes and port proxy rules with their status
+    caffeine: Caffeine,              // Sleep blocker session and what ends it
+    gpu_limit_control: GpuLimitControl, // Power limit slider in the GPU window
+    storage_history: StorageHistory, // Hourly free space per drive, persisted to storage.json
+    metric_archive: MetricArchive,   // A week of minute averages for the history comparison window
+    throttle_monitor: ThrottleMonitor, // CPU performance limit, thermal zone and firmware limit indicators
+    temperature_trends: TemperatureTrends, // Recent CPU and GPU temperatures, for warnings before they overheat
+    alerts: Alerts,                  // How long each alert rule has held and which are firing
+    daily_summaries: DailySummaries, // Peaks and events per day, persisted to summaries.json
+    metrics_recorder: MetricsRecorder, // File the periodic metric snapshots are appended to
+    tray: Tray,                      // Tray icon, its flyout and the graphs it shows
+    header: Header,                  // Avatar texture and pending update count for the top panel
+    popouts: Popouts,                // Measured sizes of popped-out card windows
+    volume_monitor: VolumeMonitor,   // Free space per drive letter, read at each volume's own pace
+    catalog: AppCatalog,             // Version of the signed remote app catalog in use
+    elevation: ElevationBroker,      // Elevated helper for service control and other admin-only actions
+    crash_reporter: CrashReporter,   // Uploads of reports left by earlier crashes
+    snapshots: Snapshots,            // Saved system snapshots for before/after comparisons
+    process_watcher: ProcessWatcher, // Runs of executables watched for start and exit notifications
+    job_watch: JobWatch,             // Log tails and window title matches for build watchers
+    wall: Wall,                      // Fullscreen wall display window and its current page
+    network_events: NetworkEvents,   // Link, address, DHCP and Wi-Fi changes, persisted to network_events.json
+    notifications: NotificationCenter, // Alerts, installs, updates and watcher events, persisted to notifications.json
+    gpu_driver_check: DriverUpdateCheck, // Latest driver release from the GPU vendor
+    theme_state: ThemeState,
+    tour: Tour,                      // Onboarding tour step and the targets drawn this frame
+    metric_graphs: MetricGraphs,     // Last hour of CPU, memory and GPU usage for the card graphs
+    app_info: AppInfoCache,          // Release details from winget for the installer's info popovers
+    top_process_view: TopProcesses,  // Row the top processes card menu was opened on
+    process_network: ProcessNetworkMonitor, // Connections and bandwidth per process for its card
+    containers: ContainerMonitor,    // Docker/Podman containers for the Containers tab
+    service_list: ServiceList,       // Windows services, search and running actions for the Services tab
+    wsl: WslMonitor,                 // WSL distros and the virtual machine's memory for the WSL card
+    provision: ProvisionOptions,     // What the exported setup script covers
+    source_registry: SourceRegistry, // winget sources being registered from Settings
+    last_used: LastUsed,             // When installed apps were last launched
+    app_base: Vec<NiniteApp>,        // Built-in and signed catalog apps, before the user's apps.json
+    user_catalog: UserCatalog,       // The user's own apps from apps.json
+    install_audit: InstallAudit,     // Leftover uninstall entries found on the installer page
+    overlay: Overlay,                // Compact always-on-top overlay size and state
+    live_indicators: LiveIndicators, // Window title last set with live CPU and memory
+    gateway_ping: GatewayPing,       // Round trip to the default gateway for the favorites strip
+    api_server: ApiServer,           // JSON endpoints for scripts and other devices, off unless enabled
+    preset_editor: PresetEditor,     // Name for saving the selection as a preset
+    startup: Startup,                // Command-line flags to act on in the first frame
+    instance: InstanceListener,      // Starts forwarded by a second launch of the executable
 }
 
 impl Default for DevDashboard {
@@ -403,13 +897,15 @@ impl Default for DevDashboard {
         for (name, data) in sys.networks() {
             if DevDashboard::is_physical_interface(name) {
                 network_stats.insert(name.to_string(), NetworkStats {
-                    total_received: data.received(),
-                    total_sent: data.transmitted(),
-                    last_received: data.received(),
-                    last_sent: data.transmitted(),
+                    total_received: data.total_received(),
+                    total_sent: data.total_transmitted(),
+                    last_received: data.total_received(),
+                    last_sent: data.total_transmitted(),
                     received_speed: 0.0,
                     sent_speed: 0.0,
                     last_update: Instant::now(),
+                    session: Traffic::default(),
+                    session_start: chrono::Local::now(),
                 });
             }
         }
@@ -433,14 +929,17 @@ impl Default for DevDashboard {
             }
         }
 
-        let gpu_info = Self::initialize_gpu();
-        let nvml = Nvml::init().ok();
-
         // Load settings from file
         let settings = Self::load_settings();
 
+        let gpu_info = Self::initialize_gpu(&settings.gpu_selection);
+        #[cfg(feature = "nvml")]
+        let nvml = Nvml::init().ok();
+        let plugins = PluginHost::discover(&settings.plugins);
+        let tour = Tour::new(!settings.tour.seen);
+
         // Initialize Ninite apps with registry keys and file paths
-        let ninite_apps = vec![
+        let mut ninite_apps = vec![
             NiniteApp::new("Chrome", "Web Browsers", "chrome", vec![
                 "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\App Paths\\chrome.exe",
                 "SOFTWARE\\Google\\Chrome"
@@ -606,6 +1105,12 @@ impl Default for DevDashboard {
                 "C:\\Users\\%USERNAME%\\AppData\\Local\\Programs\\qBittorrent\\qbittorrent.exe"
             ]),
         ];
+        // Detection fixes from the last verified catalog update override the built-in entries
+        let catalog = AppCatalog::load_cached(&mut ninite_apps);
+        // The user's own apps.json goes last so its entries win
+        let app_base = ninite_apps.clone();
+        let user_catalog = UserCatalog::load();
+        user_catalog::apply(&mut ninite_apps, &user_catalog.apps);
 
         Self {
             sys,
@@ -614,51 +1119,134 @@ impl Default for DevDashboard {
             last_frame_time: Instant::now(),
             last_check: Instant::now(),
             current_cpu_usage: AnimatedValue::new(0.0),
+            core_usage: Vec::new(),
             memory_usage: AnimatedValue::new(0.0),
             disk_usage,
             network_stats,
             gpu_info,
+            #[cfg(feature = "nvml")]
             nvml,
             settings,
             show_settings: false,
             current_tab: Tab::Dashboard,
+            tool_page: ToolPage::Installer,
             ninite_apps,
             selected_apps: Vec::new(),
-            download_progress: 0.0,
+            download_progress: DownloadProgress::default(),
+            installer_control: Arc::default(),
+            installer_paused: false,
             installer_state: InstallerState::Idle,
             runtime: None,
             message_receiver: None,
             ninite_running: false,
+            security_audit: SecurityAudit::default(),
+            action_guard: ActionGuard::default(),
+            certificate_monitor: CertificateMonitor::default(),
+            process_view: ProcessView::default(),
+            ticker: Ticker::default(),
+            calendar: Calendar::default(),
+            todos: TodoList::load(),
+            usage: UsageTracker::load(),
+            idle: IdleMonitor::default(),
+            backup_monitor: BackupMonitor::default(),
+            cloud_storage: CloudStorage::default(),
+            power_monitor: PowerMonitor::load(),
+            battery_health: BatteryHealth::default(),
+            benchmark: Benchmark::load(),
+            screenshot: ScreenshotTool::default(),
+            color_picker: ColorPicker::default(),
+            converters: Converters::default(),
+            generator: Generator::default(),
+            qr_tool: QrTool::default(),
+            text_diff: TextDiff::default(),
+            lan_drop: LanDrop::default(),
+            lan_scan: LanScan::default(),
+            port_usage: PortUsage::default(),
+            report_share: ReportShare::default(),
+            plugins,
+            scripts: ScriptHost::default(),
+            wmi_cards: WmiCards::default(),
+            command_cards: CommandCards::default(),
+            network_totals: NetworkTotals::load(),
+            uptime_history: UptimeHistory::load(),
+            gpu_detail_open: false,
+            gpu_adapters: GpuAdapters::default(),
+            sensor_backends: SensorBackends::default(),
+            disk_io: DiskIoMonitor::default(),
+            share_monitor: ShareMonitor::default(),
+            repo_monitor: RepoMonitor::default(),
+            watchdog: Watchdog::default(),
+            stack_runner: StackRunner::default(),
+            tunnel_manager: TunnelManager::default(),
+            caffeine: Caffeine::default(),
+            gpu_limit_control: GpuLimitControl::default(),
+            storage_history: StorageHistory::load(),
+            metric_archive: MetricArchive::load(),
+            throttle_monitor: ThrottleMonitor::default(),
+            temperature_trends: TemperatureTrends::default(),
+            alerts: Alerts::default(),
+            daily_summaries: DailySummaries::load(),
+            metrics_recorder: MetricsRecorder::default(),
+            tray: Tray::default(),
+            header: Header::default(),
+            popouts: Popouts::default(),
+            volume_monitor: VolumeMonitor::default(),
+            catalog,
+            elevation: ElevationBroker::default(),
+            crash_reporter: CrashReporter::default(),
+            snapshots: Snapshots::load(),
+            process_watcher: ProcessWatcher::load(),
+            job_watch: JobWatch::default(),
+            wall: Wall::default(),
+            network_events: NetworkEvents::load(),
+            notifications: NotificationCenter::load(),
+            gpu_driver_check: DriverUpdateCheck::default(),
+            theme_state: ThemeState::default(),
+            tour,
+            metric_graphs: MetricGraphs::default(),
+            app_info: AppInfoCache::default(),
+            top_process_view: TopProcesses::default(),
+            process_network: ProcessNetworkMonitor::default(),
+            containers: ContainerMonitor::default(),
+            service_list: ServiceList::default(),
+            wsl: WslMonitor::default(),
+            provision: ProvisionOptions::default(),
+            source_registry: SourceRegistry::default(),
+            last_used: LastUsed::default(),
+            app_base,
+            user_catalog,
+            install_audit: InstallAudit::default(),
+            overlay: Overlay::default(),
+            live_indicators: LiveIndicators::default(),
+            gateway_ping: GatewayPing::default(),
+            api_server: ApiServer::default(),
+            preset_editor: PresetEditor::default(),
+            startup: Startup::default(),
+            instance: InstanceListener::default(),
         }
     }
 }
 
 impl DevDashboard {
     fn load_settings() -> Settings {
-        match File::open("settings.json") {
-            Ok(mut file) => {
-                let mut contents = String::new();
-                if file.read_to_string(&mut contents).is_ok() {
-                    if let Ok(settings) = serde_json::from_str(&contents) {
-                        return settings;
-                    }
-                }
-                Settings::default()
-            }
-            Err(_) => Settings::default(),
+        let mut settings: Settings = storage::load_json("settings.json");
+        if let Some(name) = settings.custom_username.take() {
+            settings.header.display_names.entry(username()).or_insert(name);
+        }
+        // Tokens saved by older versions move to Credential Manager and out of the file
+        if settings.cloud.load_tokens() | settings.share.load_token() {
+            storage::save_json("settings.json", &settings);
         }
+        settings
+    }
+
+    /// Returns the shared Tokio runtime, creating it on first use
+    fn runtime(&mut self) -> &tokio::runtime::Runtime {
+        self.runtime.get_or_insert_with(|| tokio::runtime::Runtime::new().unwrap())
     }
 
     fn save_settings(&self) {
-        if let Ok(json) = serde_json::to_string_pretty(&self.settings) {
-            if let Ok(mut file) = OpenOptions::new()
-                .write(true)
-                .create(true)
-                .truncate(true)
-                .open("settings.json") {
-                let _ = file.write_all(json.as_bytes());
-            }
-        }
+        storage::save_json("settings.json", &self.settings);
     }
 
     fn show_settings_window(&mut self, ctx: &egui::Context) {
@@ -670,28 +1258,206 @@ impl DevDashboard {
                     ui.vertical(|ui| {
                         ui.heading("Customize Your Dashboard");
                         ui.add_space(8.0);
-                        
-                        ui.label("Display Name:");
-                        let mut username = self.settings.custom_username.clone().unwrap_or_default();
-                        if ui.text_edit_singleline(&mut username).changed() {
-                            self.settings.custom_username = if username.is_empty() {
-                                None
-                            } else {
-                                Some(username)
-                            };
-                            self.save_settings();
-                        }
-                        
+
+                        egui::ScrollArea::vertical()
+                            .id_source("settings_scroll")
+                            .max_height(ctx.screen_rect().height() * 0.7)
+                            .show(ui, |ui| self.show_settings_sections(ui));
+
                         ui.add_space(16.0);
                         
                         if ui.button("Close").clicked() {
                             self.show_settings = false;
+                            self.action_guard.settings_unlocked = false;
                         }
                     });
                 });
         }
     }
 
+    /// Scrollable body of the Settings window, one section per feature
+    fn show_settings_sections(&mut self, ui: &mut egui::Ui) {
+        self.show_header_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_theme_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_tour_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_graph_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_tray_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_live_indicator_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_favorite_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_sensor_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_temperature_alert_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_alert_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_api_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_recorder_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_action_lock_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_certificate_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_ticker_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_calendar_settings(ui);
+
+        ui.add_space(16.0);
+
+        if ui.checkbox(&mut self.settings.show_todo_card, "Show todo card").changed() {
+            self.save_settings();
+        }
+
+        ui.add_space(16.0);
+
+        self.show_usage_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_idle_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_backup_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_cloud_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_power_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_gpu_power_settings(ui);
+        self.show_gpu_driver_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_uptime_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_top_process_settings(ui);
+        self.show_process_network_settings(ui);
+        self.show_wsl_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_container_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_disk_refresh_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_wall_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_network_share_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_repo_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_lan_drop_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_share_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_plugin_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_script_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_wmi_card_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_command_card_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_watchdog_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_process_watch_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_job_watch_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_stack_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_tunnel_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_catalog_settings(ui);
+        self.show_user_catalog_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_package_source_settings(ui);
+
+        ui.add_space(16.0);
+
+        self.show_crash_settings(ui);
+    }
+
     fn show_tools_tab(&mut self, ui: &mut egui::Ui) {
         // Refresh installed apps status periodically
         static mut LAST_CHECK: Option<Instant> = None;
@@ -726,22 +1492,46 @@ impl DevDashboard {
                     }
                     InstallerMessage::SetState(new_state) => {
                         let should_refresh = new_state == InstallerState::Idle;
+                        let was_installing = self.installer_state == InstallerState::Installing;
                         self.installer_state = new_state;
                         if should_refresh {
                             info!("Installation completed, refreshing program status...");
+                            let mut installed = Vec::new();
                             // Update installation status
                             for app in &mut self.ninite_apps {
                                 app.check_installation();
                                 if app.installed {
+                                    if self.selected_apps.contains(&app.name) {
+                                        self.daily_summaries.record_install(&app.name);
+                                        installed.push(app.name.clone());
+                                    }
                                     self.selected_apps.retain(|name| name != &app.name);
                                 }
                             }
+                            if was_installing && !installed.is_empty() {
+                                let title = match installed.len() {
+                                    1 => "Installed 1 app".to_string(),
+                                    count => format!("Installed {} apps", count),
+                                };
+                                self.notifications.push(NotificationSource::Installer, &title, &installed.join(", "));
+                            }
                         }
                     }
                     InstallerMessage::Error(error) => {
                         error!("Installer error: {}", error);
+                        self.notifications.push(NotificationSource::Installer, "Installation failed", &error);
                         self.installer_state = InstallerState::Error(error);
                     }
+                    InstallerMessage::Paused(paused) => {
+                        info!("Installer download {}", if paused { "paused" } else { "resumed" });
+                        self.installer_paused = paused;
+                    }
+                    InstallerMessage::Cancelled => {
+                        info!("Installer download cancelled");
+                        self.installer_state = InstallerState::Idle;
+                        self.installer_paused = false;
+                        self.download_progress = DownloadProgress::default();
+                    }
                 }
             }
         }
@@ -750,151 +1540,39 @@ impl DevDashboard {
         egui::Frame::none()
             .inner_margin(egui::style::Margin::same(10.0))
             .show(ui, |ui| {
-                ui.set_enabled(self.installer_state == InstallerState::Idle);
-
-                ui.heading("Essential Tools Installation");
-                ui.add_space(8.0);
-
-                // Create a stable ordering of categories
-                let categories = [
-                    "Web Browsers",
-                    "Messaging",
-                    "Media",
-                    "Imaging",
-                    "Documents",
-                    "Developer Tools",
-                    "Other",
-                    "Compression",
-                    "File Sharing",
-                ];
-
-                // Show apps grouped by category with stable ordering
-                egui::ScrollArea::vertical().show(ui, |ui| {
-                    for &category in &categories {
-                        let apps: Vec<&NiniteApp> = self.ninite_apps.iter()
-                            .filter(|app| app.category == category)
-                            .collect();
-
-                        if !apps.is_empty() {
-                            ui.collapsing(category, |ui| {
-                                for app in apps {
-                                    let mut is_selected = self.selected_apps.contains(&app.name);
-                                    
-                                    ui.horizontal(|ui| {
-                                        if app.installed {
-                                            ui.add_enabled(false, egui::Checkbox::new(&mut false, &app.name));
-                                            ui.label(" (Installed)");
-                                        } else {
-                                            if ui.checkbox(&mut is_selected, &app.name).changed() {
-                                                if is_selected {
-                                                    debug!("Selected app for installation: {}", app.name);
-                                                    self.selected_apps.push(app.name.clone());
-                                                } else {
-                                                    debug!("Deselected app: {}", app.name);
-                                                    self.selected_apps.retain(|x| x != &app.name);
-                                                }
-                                            }
-                                        }
-                                    });
-                                }
-                            });
-                        }
-                    }
-
-                    ui.add_space(16.0);
-
-                    match &self.installer_state {
-                        InstallerState::Downloading => {
-                            ui.add_space(8.0);
-                            ui.vertical_centered(|ui| {
-                                ui.heading("Downloading Ninite Installer...");
-                                ui.add_space(4.0);
-                                ui.add(egui::ProgressBar::new(self.download_progress)
-                                    .text(format!("{:.0}%", self.download_progress * 100.0)));
-                            });
-                        }
-                        InstallerState::Installing => {
-                            ui.add_space(8.0);
-                            ui.vertical_centered(|ui| {
-                                ui.heading("Installing Selected Applications...");
-                                ui.add_space(4.0);
-                                ui.label("This may take a few minutes. Please wait for the Ninite installer to complete.");
-                                ui.add_space(8.0);
-                                // Add an animated spinner
-                                let time = ui.input(|i| i.time);
-                                let angle = time * std::f64::consts::PI;
-                                let (sin, cos) = angle.sin_cos();
-                                let points = (0..8).map(|i| {
-                                    let angle = i as f64 * std::f64::consts::PI / 4.0;
-                                    let (s, c) = angle.sin_cos();
-                                    let distance = 20.0 * (1.0 + 0.3 * (sin * c + cos * s)) as f32;
-                                    egui::pos2(
-                                        ui.available_width() / 2.0 + distance * c as f32,
-                                        50.0 + distance * s as f32,
-                                    )
-                                }).collect::<Vec<_>>();
-                                let painter = ui.painter();
-                                for (i, point) in points.iter().enumerate() {
-                                    let alpha = 1.0 - (i as f32 / points.len() as f32);
-                                    painter.circle_filled(
-                                        *point,
-                                        4.0,
-                                        egui::Color32::from_white_alpha((alpha * 255.0) as u8),
-                                    );
-                                }
-                            });
-                        }
-                        InstallerState::Error(error) => {
-                            let error_msg = error.clone();
-                            ui.add_space(8.0);
-                            ui.vertical_centered(|ui| {
-                                ui.colored_label(egui::Color32::from_rgb(220, 50, 50), format!("Error: {}", error_msg));
-                                ui.add_space(8.0);
-                                let retry = ui.button("Retry").clicked();
-                                if retry {
-                                    info!("Retrying installation...");
-                                    self.installer_state = InstallerState::Idle;
-                                }
-                            });
-                        }
-                        InstallerState::Idle => {
-                            if !self.selected_apps.is_empty() {
-                                ui.vertical_centered(|ui| {
-                                    if ui.button("Install Selected Apps").clicked() {
-                                        info!("Starting installation of selected apps: {:?}", self.selected_apps);
-                                        // Initialize runtime if not already done
-                                        if self.runtime.is_none() {
-                                            self.runtime = Some(tokio::runtime::Runtime::new().unwrap());
-                                        }
-
-                                        // Create a channel for communication
-                                        let (sender, receiver) = channel();
-                                        self.message_receiver = Some(receiver);
-
-                                        // Clone the necessary data for the async task
-                                        let selected_apps = self.selected_apps.clone();
-                                        let ninite_apps = self.ninite_apps.clone();
-
-                                        // Start the download process
-                                        if let Some(runtime) = &self.runtime {
-                                            runtime.spawn(async move {
-                                                if let Err(e) = Self::download_ninite_installer(
-                                                    selected_apps,
-                                                    ninite_apps,
-                                                    sender.clone()
-                                                ).await {
-                                                    error!("Download failed: {}", e);
-                                                    sender.send(InstallerMessage::Error(e.to_string())).ok();
-                                                }
-                                            });
-                                        }
-                                    }
-                                });
-                            }
-                        }
-                    }
+                ui.add_enabled_ui(self.installer_state == InstallerState::Idle, |ui| {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.selectable_value(&mut self.tool_page, ToolPage::Installer, "Installer");
+                        ui.selectable_value(&mut self.tool_page, ToolPage::Benchmark, "Benchmark");
+                        ui.selectable_value(&mut self.tool_page, ToolPage::Screenshot, "Screenshot");
+                        ui.selectable_value(&mut self.tool_page, ToolPage::ColorPicker, "Color Picker");
+                        ui.selectable_value(&mut self.tool_page, ToolPage::Converters, "Converters");
+                        ui.selectable_value(&mut self.tool_page, ToolPage::Generator, "Generator");
+                        ui.selectable_value(&mut self.tool_page, ToolPage::Qr, "QR Code");
+                        ui.selectable_value(&mut self.tool_page, ToolPage::Diff, "Text Diff");
+                        ui.selectable_value(&mut self.tool_page, ToolPage::FileDrop, "File Drop");
+                        ui.selectable_value(&mut self.tool_page, ToolPage::LanScan, "LAN Scan");
+                        ui.selectable_value(&mut self.tool_page, ToolPage::Ports, "Ports");
+                        ui.selectable_value(&mut self.tool_page, ToolPage::Snapshots, "Snapshots");
+                    });
                 });
-        });
+                ui.separator();
+
+                match self.tool_page {
+                    ToolPage::Installer => self.show_installer_page(ui),
+                    ToolPage::Benchmark => self.show_benchmark_tool(ui),
+                    ToolPage::Screenshot => self.show_screenshot_tool(ui),
+                    ToolPage::ColorPicker => self.show_color_picker_tool(ui),
+                    ToolPage::Converters => self.show_converters_tool(ui),
+                    ToolPage::Generator => self.show_generator_tool(ui),
+                    ToolPage::Qr => self.show_qr_tool(ui),
+                    ToolPage::Diff => self.show_diff_tool(ui),
+                    ToolPage::FileDrop => self.show_lan_drop_tool(ui),
+                    ToolPage::LanScan => self.show_lan_scan_tool(ui),
+                    ToolPage::Ports => self.show_ports_tool(ui),
+                    ToolPage::Snapshots => self.show_snapshot_tool(ui),
+                }
+            });
 
         // Show overlay message when installer is running
         if self.installer_state == InstallerState::Installing {
@@ -928,6 +1606,266 @@ impl DevDashboard {
         }
     }
 
+    /// Ninite installer page of the Tools tab
+    fn show_installer_page(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Essential Tools Installation");
+        ui.add_space(8.0);
+
+        // Create a stable ordering of categories
+        let categories = [
+            "Web Browsers",
+            "Messaging",
+            "Media",
+            "Imaging",
+            "Documents",
+            "Developer Tools",
+            "Other",
+            "Compression",
+            "File Sharing",
+        ];
+        // Categories introduced by a catalog update go after the known ones
+        let mut categories: Vec<String> = categories.iter().map(|category| category.to_string()).collect();
+        for app in &self.ninite_apps {
+            if !categories.contains(&app.category) {
+                categories.push(app.category.clone());
+            }
+        }
+        // Nothing but Pause and Cancel may change things while a download or install runs
+        let busy = matches!(self.installer_state, InstallerState::Downloading | InstallerState::Installing);
+        ui.add_enabled_ui(!busy, |ui| self.show_catalog_status(ui));
+        self.update_app_info();
+        self.update_last_used();
+
+        // Show apps grouped by category with stable ordering
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            // The selection also stays locked after an error until Retry
+            let idle = self.installer_state == InstallerState::Idle;
+            ui.add_enabled_ui(!busy, |ui| {
+                for category in &categories {
+                    let apps: Vec<&NiniteApp> = self.ninite_apps.iter()
+                        .filter(|app| &app.category == category)
+                        .collect();
+
+                    if !apps.is_empty() {
+                        egui::CollapsingHeader::new(self.category_header(category)).id_source(category).show(ui, |ui| {
+                            for app in apps {
+                                let mut is_selected = self.selected_apps.contains(&app.name);
+                            
+                                ui.horizontal(|ui| {
+                                    let support = app.support();
+                                    if app.installed {
+                                        ui.add_enabled(false, egui::Checkbox::new(&mut false, &app.name));
+                                        ui.label(" (Installed)");
+                                        if app.scopes.is_duplicate() {
+                                            ui.label(RichText::new("· per-user and per-machine").color(egui::Color32::from_rgb(202, 138, 4)))
+                                                .on_hover_text("Two copies compete for updates and file associations; see Install Cleanup below");
+                                        }
+                                        self.last_used.show(ui, app);
+                                    } else if app.ninite_id.is_empty() && self.settings.package_sources.backend == InstallBackend::Ninite {
+                                        ui.add_enabled(false, egui::Checkbox::new(&mut false, &app.name));
+                                        ui.label(RichText::new(" (No Ninite package)").color(egui::Color32::from_rgb(107, 114, 128)))
+                                            .on_hover_text("Choose winget as the install source in Settings to install it from its winget ID");
+                                    } else if support == Support::Unsupported {
+                                        ui.add_enabled(false, egui::Checkbox::new(&mut false, &app.name));
+                                        ui.label(RichText::new(format!(" (No {} build)", arch::os_arch().label()))
+                                            .color(egui::Color32::from_rgb(107, 114, 128)));
+                                    } else {
+                                        if ui.add_enabled(idle, egui::Checkbox::new(&mut is_selected, &app.name)).changed() {
+                                            if is_selected {
+                                                debug!("Selected app for installation: {}", app.name);
+                                                self.selected_apps.push(app.name.clone());
+                                            } else {
+                                                debug!("Deselected app: {}", app.name);
+                                                self.selected_apps.retain(|x| x != &app.name);
+                                            }
+                                        }
+                                        let note = match support {
+                                            Support::Bits32 => Some("32-bit only".to_string()),
+                                            Support::Emulated(build) => Some(format!("{} build, runs emulated", build.label())),
+                                            Support::Native | Support::Unsupported => None,
+                                        };
+                                        if let Some(note) = note {
+                                            ui.label(RichText::new(format!(" ({})", note)).color(egui::Color32::from_rgb(202, 138, 4)));
+                                        }
+                                    }
+                                    self.app_info.show_button(ui, app);
+                                });
+                            }
+                        });
+                    }
+                }
+
+                self.show_install_cleanup(ui);
+            });
+
+            ui.add_space(16.0);
+
+            match &self.installer_state {
+                InstallerState::Downloading => {
+                    ui.add_space(8.0);
+                    ui.vertical_centered(|ui| {
+                        let control = &self.installer_control;
+                        let cancelling = control.cancel.load(Ordering::Relaxed);
+                        ui.heading(if cancelling {
+                            "Cancelling..."
+                        } else if self.installer_paused {
+                            "Download Paused"
+                        } else {
+                            "Downloading Ninite Installer..."
+                        });
+                        ui.add_space(4.0);
+                        ui.add(egui::ProgressBar::new(self.download_progress.fraction())
+                            .text(format!("{:.0}%", self.download_progress.fraction() * 100.0)));
+                        if !self.installer_paused {
+                            ui.label(RichText::new(self.download_progress.describe()).color(egui::Color32::from_rgb(156, 163, 175)));
+                        }
+                        ui.add_space(4.0);
+                        ui.add_enabled_ui(!cancelling, |ui| {
+                            ui.horizontal(|ui| {
+                                let pause_requested = control.pause.load(Ordering::Relaxed);
+                                if ui.button(if pause_requested { "Resume" } else { "Pause" })
+                                    .on_hover_text("Pausing also holds the install until you resume")
+                                    .clicked() {
+                                    control.pause.store(!pause_requested, Ordering::Relaxed);
+                                }
+                                if ui.button("Cancel").clicked() {
+                                    info!("Cancelling installer download");
+                                    control.request_cancel();
+                                }
+                            });
+                        });
+                    });
+                }
+                InstallerState::Installing => {
+                    ui.add_space(8.0);
+                    ui.vertical_centered(|ui| {
+                        ui.heading("Installing Selected Applications...");
+                        ui.add_space(4.0);
+                        ui.label(match self.settings.package_sources.backend {
+                            InstallBackend::Ninite => "This may take a few minutes. Please wait for the Ninite installer to complete.",
+                            InstallBackend::Winget => "This may take a few minutes. winget installs the apps one at a time.",
+                            InstallBackend::Chocolatey => "This may take a few minutes. Please wait for Chocolatey to complete.",
+                        });
+                        ui.add_space(8.0);
+                        // Add an animated spinner
+                        let time = ui.input(|i| i.time);
+                        let angle = time * std::f64::consts::PI;
+                        let (sin, cos) = angle.sin_cos();
+                        let points = (0..8).map(|i| {
+                            let angle = i as f64 * std::f64::consts::PI / 4.0;
+                            let (s, c) = angle.sin_cos();
+                            let distance = 20.0 * (1.0 + 0.3 * (sin * c + cos * s)) as f32;
+                            egui::pos2(
+                                ui.available_width() / 2.0 + distance * c as f32,
+                                50.0 + distance * s as f32,
+                            )
+                        }).collect::<Vec<_>>();
+                        let painter = ui.painter();
+                        for (i, point) in points.iter().enumerate() {
+                            let alpha = 1.0 - (i as f32 / points.len() as f32);
+                            painter.circle_filled(
+                                *point,
+                                4.0,
+                                egui::Color32::from_white_alpha((alpha * 255.0) as u8),
+                            );
+                        }
+                    });
+                }
+                InstallerState::Error(error) => {
+                    let error_msg = error.clone();
+                    ui.add_space(8.0);
+                    ui.vertical_centered(|ui| {
+                        ui.colored_label(egui::Color32::from_rgb(220, 50, 50), format!("Error: {}", error_msg));
+                        ui.add_space(8.0);
+                        let retry = ui.button("Retry").clicked();
+                        if retry {
+                            info!("Retrying installation...");
+                            self.installer_state = InstallerState::Idle;
+                        }
+                    });
+                }
+                InstallerState::Idle => {
+                    if !self.selected_apps.is_empty() {
+                        ui.vertical_centered(|ui| {
+                            self.show_install_estimate(ui);
+                            ui.add_space(4.0);
+                            if ui.button("Install Selected Apps").clicked() {
+                                self.request_sensitive_action(SensitiveAction::InstallApps);
+                            }
+                        });
+                    }
+                }
+            }
+
+            if idle {
+                ui.add_space(16.0);
+                self.show_presets(ui);
+                ui.add_space(8.0);
+                self.show_provisioning_export(ui);
+            }
+        });
+    }
+
+    /// Downloads and launches the Ninite installer for the selected apps, or installs them from the configured package sources
+    fn start_installation(&mut self) {
+        info!("Starting installation of selected apps: {:?}", self.selected_apps);
+
+        // Create a channel for communication
+        let (sender, receiver) = channel();
+        self.message_receiver = Some(receiver);
+
+        // Clone the necessary data for the async task, leaving out apps with no build for this PC
+        let selected_apps: Vec<String> = self.selected_apps.iter()
+            .filter(|name| self.ninite_apps.iter().any(|app| &app.name == *name && app.support() != Support::Unsupported))
+            .cloned()
+            .collect();
+        let ninite_apps = self.ninite_apps.clone();
+
+        // Start the download process
+        self.download_progress = DownloadProgress::default();
+        self.installer_paused = false;
+        self.installer_control = Arc::default();
+        let control = self.installer_control.clone();
+        if self.settings.package_sources.backend != InstallBackend::Ninite {
+            let apps: Vec<NiniteApp> = ninite_apps.into_iter().filter(|app| selected_apps.contains(&app.name)).collect();
+            let sources = self.settings.package_sources.clone();
+            self.runtime().spawn(async move {
+                if let Err(e) = Self::install_from_sources(apps, sources, sender.clone(), control).await {
+                    error!("Install failed: {}", e);
+                    sender.send(InstallerMessage::Error(e.to_string())).ok();
+                }
+            });
+            return;
+        }
+        // Ninite cannot install the user's own tools that have no Ninite ID
+        let selected_apps: Vec<String> = selected_apps.into_iter()
+            .filter(|name| ninite_apps.iter().any(|app| &app.name == name && !app.ninite_id.is_empty()))
+            .collect();
+        self.runtime().spawn(async move {
+            if let Err(e) = Self::download_ninite_installer(
+                selected_apps,
+                ninite_apps,
+                sender.clone(),
+                control
+            ).await {
+                error!("Download failed: {}", e);
+                sender.send(InstallerMessage::Error(e.to_string())).ok();
+            }
+        });
+    }
+
+    /// Holds the download while a pause is requested, telling the UI when it stops and carries on
+    async fn wait_while_paused(control: &InstallerControl, sender: &Sender<InstallerMessage>) -> InstallerResult<()> {
+        if !control.pause.load(Ordering::Relaxed) || control.cancel.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        Self::send_message(sender, InstallerMessage::Paused(true))?;
+        while control.pause.load(Ordering::Relaxed) && !control.cancel.load(Ordering::Relaxed) {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+        Self::send_message(sender, InstallerMessage::Paused(false))
+    }
+
     fn get_disk_space(path: &str) -> Option<(u64, u64)> {
         let path_cstr = CString::new(path).ok()?;
         let mut total_bytes = 0u64;
@@ -952,7 +1890,8 @@ impl DevDashboard {
     async fn download_ninite_installer(
         selected_apps: Vec<String>,
         ninite_apps: Vec<NiniteApp>,
-        sender: Sender<InstallerMessage>
+        sender: Sender<InstallerMessage>,
+        control: Arc<InstallerControl>
     ) -> InstallerResult<()> {
         if selected_apps.is_empty() {
             return Err(Box::new(InstallerError::NoAppsSelected));
@@ -986,7 +1925,7 @@ impl DevDashboard {
         let mut downloaded = 0u64;
 
         // Clean up any existing installer file
-        let installer_path = "ninite.exe";
+        let installer_path = INSTALLER_PATH;
         if std::path::Path::new(installer_path).exists() {
             match std::fs::remove_file(installer_path) {
                 Ok(_) => info!("Removed existing installer file"),
@@ -1015,16 +1954,49 @@ impl DevDashboard {
             };
 
         let mut stream = response.bytes_stream();
-
-        while let Some(chunk) = stream.next().await {
+        // Rate over half-second windows, smoothed so the ETA does not jump with every chunk
+        let mut bytes_per_sec = 0.0;
+        let mut window_start = Instant::now();
+        let mut window_bytes = 0u64;
+
+        loop {
+            // Racing the cancel request lets a stalled connection be cancelled without waiting for its next chunk
+            let chunk = tokio::select! {
+                chunk = stream.next() => chunk,
+                _ = control.cancelled.notified() => None,
+            };
+            let Some(chunk) = chunk else { break };
+            // Not reading while paused lets the connection throttle the sender
+            if control.pause.load(Ordering::Relaxed) {
+                Self::wait_while_paused(&control, &sender).await?;
+                window_start = Instant::now();
+                window_bytes = 0;
+            }
+            if control.cancel.load(Ordering::Relaxed) {
+                drop(file);
+                match std::fs::remove_file(installer_path) {
+                    Ok(_) => info!("Removed partial installer file"),
+                    Err(e) => warn!("Could not remove partial installer file: {}", e),
+                }
+                return Self::send_message(&sender, InstallerMessage::Cancelled);
+            }
             let chunk = chunk.map_err(ReqwestErrorWrapper)?;
             match file.write_all(&chunk) {
                 Ok(_) => {
                     downloaded += chunk.len() as u64;
-                    if total_size > 0 {
-                        let progress = downloaded as f32 / total_size as f32;
-                        Self::send_message(&sender, InstallerMessage::UpdateProgress(progress))?;
+                    window_bytes += chunk.len() as u64;
+                    let elapsed = window_start.elapsed().as_secs_f64();
+                    if elapsed >= 0.5 {
+                        let rate = window_bytes as f64 / elapsed;
+                        bytes_per_sec = if bytes_per_sec > 0.0 { bytes_per_sec * 0.7 + rate * 0.3 } else { rate };
+                        window_start = Instant::now();
+                        window_bytes = 0;
                     }
+                    Self::send_message(&sender, InstallerMessage::UpdateProgress(DownloadProgress {
+                        downloaded,
+                        total: total_size,
+                        bytes_per_sec,
+                    }))?;
                 },
                 Err(e) => {
                     error!("Failed to write installer chunk: {}", e);
@@ -1041,6 +2013,13 @@ impl DevDashboard {
         // Explicitly close the file before launching
         drop(file);
 
+        // A pause at the end of the download also holds back the install
+        Self::wait_while_paused(&control, &sender).await?;
+        if control.cancel.load(Ordering::Relaxed) {
+            let _ = std::fs::remove_file(installer_path);
+            return Self::send_message(&sender, InstallerMessage::Cancelled);
+        }
+
         Self::send_message(&sender, InstallerMessage::SetState(InstallerState::Installing))?;
 
         // Launch the installer and wait for it to complete
@@ -1089,13 +2068,23 @@ impl DevDashboard {
 }
 
 impl eframe::App for DevDashboard {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
         let now = Instant::now();
         let delta_time = now.duration_since(self.last_frame_time).as_secs_f32();
         self.last_frame_time = now;
 
+        self.update_instance(ctx);
+        self.update_startup(ctx);
+        self.update_idle();
+        // While away or behind a fullscreen app everything collects at a slow, shared interval
+        let throttled = self.is_throttled();
+        let slow_interval = |interval: Duration| if throttled { IDLE_INTERVAL } else { interval };
+
+        // Pausing from the tray menu stops every reading until resumed
+        let paused = self.monitoring_paused();
+
         // Only check Ninite and app status every 2 seconds
-        if now.duration_since(self.last_check) >= Duration::from_secs(2) {
+        if !paused && now.duration_since(self.last_check) >= slow_interval(Duration::from_secs(2)) {
             let mut sys = System::new_all();
             sys.refresh_processes();
             
@@ -1103,9 +2092,10 @@ impl eframe::App for DevDashboard {
                 let name = process.name().to_lowercase();
                 name.contains("ninite") && name.ends_with(".exe")
             });
+            self.last_used.set_running(sys.processes().values().map(|process| process.name().to_string()));
 
-            // Only update app states if Ninite is not running
-            if !self.ninite_running {
+            // Only update app states if Ninite is not running, and defer the scan while throttled
+            if !self.ninite_running && !throttled {
                 for app in &mut self.ninite_apps {
                     app.check_installation();
                 }
@@ -1117,9 +2107,13 @@ impl eframe::App for DevDashboard {
         // Prevent tab switching during installation
         if self.installer_state != InstallerState::Idle {
             self.current_tab = Tab::Tools;
+            self.tool_page = ToolPage::Installer;
         }
 
         self.current_cpu_usage.update(delta_time);
+        for usage in &mut self.core_usage {
+            usage.update(delta_time);
+        }
         self.memory_usage.update(delta_time);
         for usage in self.disk_usage.values_mut() {
             usage.update(delta_time);
@@ -1129,7 +2123,7 @@ impl eframe::App for DevDashboard {
             gpu_info.gpu_usage.update(delta_time);
         }
 
-        if self.last_network_update.elapsed() >= Duration::from_millis(100) {
+        if !paused && self.last_network_update.elapsed() >= slow_interval(Duration::from_millis(100)) {
             self.sys.refresh_networks();
             let networks = self.sys.networks();
             
@@ -1137,8 +2131,8 @@ impl eframe::App for DevDashboard {
                 if let Some((_, data)) = networks
                     .iter()
                     .find(|(n, _)| *n == name) {
-                    let current_received = data.received();
-                    let current_sent = data.transmitted();
+                    let current_received = data.total_received();
+                    let current_sent = data.total_transmitted();
                     let elapsed = stats.last_update.elapsed().as_secs_f64();
                     
                     if elapsed > 0.0 {
@@ -1157,8 +2151,10 @@ impl eframe::App for DevDashboard {
                         stats.received_speed = received_diff as f64 / elapsed;
                         stats.sent_speed = sent_diff as f64 / elapsed;
                         
-                        stats.total_received = stats.total_received.saturating_add(received_diff);
-                        stats.total_sent = stats.total_sent.saturating_add(sent_diff);
+                        stats.total_received = current_received;
+                        stats.total_sent = current_sent;
+                        stats.session.add(received_diff, sent_diff);
+                        self.network_totals.record(name, received_diff, sent_diff);
                     }
                     
                     stats.last_received = current_received;
@@ -1170,10 +2166,19 @@ impl eframe::App for DevDashboard {
             self.last_network_update = Instant::now();
         }
 
-        if self.last_update.elapsed() >= Duration::from_secs(1) {
+        if !paused && self.last_update.elapsed() >= slow_interval(Duration::from_secs(1)) {
             self.sys.refresh_cpu();
             self.sys.refresh_memory();
-            self.sys.refresh_disks();
+            // Free space is read per volume so sleeping and disconnecting drives are left alone
+            self.volume_monitor.update(&self.settings.disk_refresh);
+            // Watchdog rules, watched executables, the process cards, the WSL card, a sleep blocker waiting for a process and the REST API need live processes even when the Processes tab is hidden
+            if self.current_tab == Tab::Processes || self.settings.watchdog.is_active() || self.settings.process_watch.is_active()
+                || self.settings.top_processes.show_card || self.settings.process_network.show_card || self.settings.wsl.show_card
+                || self.caffeine.watches_process() || self.api_server.is_running() {
+                self.sys.refresh_processes();
+                self.update_watchdog();
+                self.update_process_watch();
+            }
             
             let total_usage: f32 = match self.sys.cpus().len() {
                 0 => {
@@ -1185,6 +2190,10 @@ impl eframe::App for DevDashboard {
                 }
             };
             self.current_cpu_usage.set_target(total_usage);
+            self.core_usage.resize_with(self.sys.cpus().len(), || AnimatedValue::new(0.0));
+            for (usage, cpu) in self.core_usage.iter_mut().zip(self.sys.cpus()) {
+                usage.set_target(cpu.cpu_usage().min(100.0));
+            }
 
             let total_memory = self.sys.total_memory() as f64;
             if total_memory > 0.0 {
@@ -1192,17 +2201,13 @@ impl eframe::App for DevDashboard {
                 self.memory_usage.set_target((used_memory as f32).min(1.0));
             }
 
-            for disk in self.sys.disks() {
-                let mount_point = disk.mount_point().to_string_lossy().to_string();
-                if mount_point.len() == 2 && mount_point.ends_with(":") {
-                    let total_space = disk.total_space();
-                    if total_space > 0 {
-                        let usage = (total_space - disk.available_space()) as f64 / total_space as f64;
-                        self.disk_usage
-                            .entry(mount_point.clone())
-                            .or_insert_with(|| AnimatedValue::new(usage as f32))
-                            .set_target((usage as f32).min(1.0));
-                    }
+            for volume in self.volume_monitor.volumes() {
+                if let Some((total_space, available_space)) = volume.space {
+                    let usage = (total_space - available_space) as f64 / total_space as f64;
+                    self.disk_usage
+                        .entry(volume.mount.clone())
+                        .or_insert_with(|| AnimatedValue::new(usage as f32))
+                        .set_target((usage as f32).min(1.0));
                 }
             }
 
@@ -1219,236 +2224,158 @@ impl eframe::App for DevDashboard {
                 if !self.network_stats.contains_key(name as &str) {
                     if let Some((_, data)) = networks.iter().find(|(n, _)| *n == name) {
                         self.network_stats.insert(name.clone(), NetworkStats {
-                            total_received: data.received(),
-                            total_sent: data.transmitted(),
-                            last_received: data.received(),
-                            last_sent: data.transmitted(),
+                            total_received: data.total_received(),
+                            total_sent: data.total_transmitted(),
+                            last_received: data.total_received(),
+                            last_sent: data.total_transmitted(),
                             received_speed: 0.0,
                             sent_speed: 0.0,
                             last_update: Instant::now(),
+                            session: Traffic::default(),
+                            session_start: chrono::Local::now(),
                         });
                     }
                 }
             }
 
+            self.update_gpu_adapters();
+            self.update_sensor_recovery();
             self.update_gpu_info();
+            self.record_gpu_sample();
+            self.record_tray_sample();
+            self.update_favorites();
+            self.update_api_snapshot();
+            self.record_metric_graphs();
+            self.update_metric_archive();
+            self.disk_io.update();
+            self.process_network.update(&self.settings.process_network);
+            self.update_wsl();
+            self.update_storage_history();
+            self.throttle_monitor.update();
+            self.update_temperature_trends();
+            self.update_alerts();
+            self.update_daily_summary();
+            self.update_metrics_recorder();
+            self.certificate_monitor.update(&self.settings.certificates);
+            self.update_ticker();
+            self.update_calendar();
+            self.update_usage();
+            self.update_backups();
+            self.update_cloud_storage();
+            self.update_power();
+            self.battery_health.update();
+            self.update_uptime_history();
+            self.update_network_shares();
+            self.repo_monitor.update(&self.settings.repos);
+            self.update_stacks();
+            self.tunnel_manager.update(&self.settings.tunnels);
+            self.update_caffeine();
+            self.update_header();
+            self.plugins.update();
+            self.update_scripts();
+            self.update_wmi_cards();
+            self.update_command_cards();
+            self.network_totals.save_if_due();
+            self.update_catalog();
+            self.update_crash_reports();
+            self.update_job_watch();
+            self.network_events.update();
+            self.update_gpu_driver_check();
 
             self.last_update = Instant::now();
         }
 
-        ctx.request_repaint_after(Duration::from_secs_f32(1.0 / 60.0));
+        self.update_lan_drop();
+        self.update_api_server();
+        self.update_tray(ctx, frame);
+        self.update_window_title(ctx);
 
-        let mut visuals = egui::Visuals::dark();
-        visuals.panel_fill = egui::Color32::from_rgb(17, 24, 39);
-        visuals.window_fill = egui::Color32::from_rgb(17, 24, 39);
-        visuals.widgets.noninteractive.bg_fill = egui::Color32::from_rgb(31, 41, 55);
-        visuals.widgets.noninteractive.fg_stroke.color = egui::Color32::from_rgb(243, 244, 246);
-        visuals.window_rounding = 12.0.into();
-        visuals.window_shadow.extrusion = 2.0;
-        ctx.set_visuals(visuals);
+        if throttled || paused {
+            // Repaint often enough to notice returning input within a second
+            ctx.request_repaint_after(Duration::from_secs(1));
+        } else {
+            ctx.request_repaint_after(Duration::from_secs_f32(1.0 / 60.0));
+        }
+
+        self.apply_theme(ctx);
 
         // Add top panel with welcome message
-        egui::TopBottomPanel::top("top_panel")
+        let header = egui::TopBottomPanel::top("top_panel")
             .frame(egui::Frame::none()
-                .fill(egui::Color32::from_rgb(31, 41, 55))
+                .fill(self.palette().card)
                 .inner_margin(egui::style::Margin::symmetric(10.0, 8.0)))
             .show(ctx, |ui| {
                 ui.horizontal(|ui| {
-                    let system_username = username();
-                    let display_name = self.settings.custom_username.as_deref()
-                        .unwrap_or_else(|| system_username.as_str());
-                    let capitalized_name = display_name.chars().next()
-                        .map(|c| c.to_uppercase().collect::<String>())
-                        .unwrap_or_default() + &display_name[1..];
-                    ui.heading(format!("Welcome back, {}!", capitalized_name));
+                    let display_name = self.display_name();
+                    self.show_header_avatar(ui, &display_name);
+                    ui.vertical(|ui| {
+                        if self.settings.header.show_greeting {
+                            ui.heading(format!("Welcome back, {}!", capitalize_first(&display_name)));
+                        }
+                        self.show_header_summary(ui);
+                    });
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        if ui.button("⚙").on_hover_text("Settings").clicked() {
+                        let settings_button = ui.button("⚙").on_hover_text("Settings");
+                        self.tour.mark(TourTarget::Settings, &settings_button);
+                        if settings_button.clicked() {
                             self.show_settings = true;
                         }
+                        self.show_notification_button(ui);
+                        self.show_caffeine_button(ui);
+                        self.show_share_button(ui);
+                        if ui.button("🖵").on_hover_text("Wall display").clicked() {
+                            self.open_wall_display();
+                        }
+                        self.show_overlay_button(ui);
+                        self.show_monitoring_paused(ui);
                         ui.label(format!("v0.2.1-beta.4"));
+                        self.show_next_meeting(ui);
                     });
                 });
             });
+        self.tour.mark(TourTarget::Header, &header.response);
 
         // Show settings window if enabled
         self.show_settings_window(ctx);
+        self.show_user_catalog_editor(ctx);
+        self.show_action_guard_window(ctx);
+        self.show_lan_drop_offers(ctx);
+        self.show_share_window(ctx);
+        self.show_gpu_detail_window(ctx);
+        self.show_network_events_window(ctx);
+        self.show_history_compare_window(ctx);
+        self.show_notification_center(ctx);
+        self.show_tray_flyout(ctx);
+        self.show_popout_windows(ctx);
+        self.show_wall_display(ctx);
+        self.show_overlay(ctx);
+        self.show_daily_summary_window(ctx);
 
         // Add tabs panel
         if !self.ninite_running {
             egui::TopBottomPanel::top("tabs")
                 .frame(egui::Frame::none()
-                    .fill(egui::Color32::from_rgb(31, 41, 55))
+                    .fill(self.palette().card)
                     .inner_margin(egui::style::Margin::symmetric(8.0, 4.0)))
                 .show(ctx, |ui| {
                     ui.horizontal(|ui| {
-                        ui.selectable_value(&mut self.current_tab, Tab::Dashboard, "Dashboard");
-                        ui.selectable_value(&mut self.current_tab, Tab::Tools, "Tools");
-                    });
-                });
-        }
-
-        egui::CentralPanel::default().show(ctx, |ui| {
-            let available_width = ui.available_width();
-            
-            let min_card_width = 280.0;
-            let spacing = 16.0;
-            
-            let max_columns = 3;
-            let columns = if available_width >= (min_card_width + spacing) * 3.2 {
-                max_columns
-            } else if available_width >= (min_card_width + spacing) * 2.2 {
-                2
-            } else {
-                1
-            };
-            
-            // Show content based on selected tab
-            if self.installer_state == InstallerState::Idle {
-                match self.current_tab {
-                    Tab::Dashboard => {
-                        egui::ScrollArea::vertical().show(ui, |ui| {
-                            let base_frame = egui::Frame::none()
-                                .fill(egui::Color32::from_rgb(31, 41, 55))
-                                .inner_margin(egui::style::Margin::same(16.0))
-                                .rounding(12.0)
-                                .shadow(egui::epaint::Shadow {
-                                    extrusion: 2.0,
-                                    color: egui::Color32::from_black_alpha(60),
-                                });
-
-                            ui.columns(columns, |columns| {
-                                for i in 0..columns.len() {
-                                    columns[i].add_space(spacing);
-                                    match i {
-                                        0 => base_frame.show(&mut columns[i], |ui| {
-                                            ui.set_min_width(min_card_width);
-                                            ui.set_min_height(180.0);
-                                            self.show_system_card(ui);
-                                        }),
-                                        1 => base_frame.show(&mut columns[i], |ui| {
-                                            ui.set_min_width(min_card_width);
-                                            ui.set_min_height(180.0);
-                                            self.show_cpu_card(ui);
-                                        }),
-                                        2 => base_frame.show(&mut columns[i], |ui| {
-                                            ui.set_min_width(min_card_width);
-                                            ui.set_min_height(180.0);
-                                            self.show_memory_card(ui);
-                                        }),
-                                        _ => unreachable!(),
-                                    };
-                                }
-
-                                for column in columns.iter_mut() {
-                                    column.add_space(spacing);
-                                }
-
-                                for i in 0..columns.len() {
-                                    match i {
-                                        0 => base_frame.show(&mut columns[i], |ui| {
-                                            ui.set_min_width(min_card_width);
-                                            ui.set_min_height(180.0);
-                                            self.show_storage_card(ui);
-                                        }),
-                                        1 => base_frame.show(&mut columns[i], |ui| {
-                                            ui.set_min_width(min_card_width);
-                                            ui.set_min_height(180.0);
-                                            self.show_network_card(ui);
-                                        }),
-                                        2 => base_frame.show(&mut columns[i], |ui| {
-                                            ui.set_min_width(min_card_width);
-                                            ui.set_min_height(180.0);
-                                            self.show_gpu_card(ui);
-                                        }),
-                                        _ => unreachable!(),
-                                    };
-                                }
-
-                                if columns.len() == 1 {
-                                    columns[0].add_space(spacing);
-                                    base_frame.show(&mut columns[0], |ui| {
-                                        ui.set_min_width(min_card_width);
-                                        ui.set_min_height(180.0);
-                                        self.show_cpu_card(ui);
-                                    });
-                                    columns[0].add_space(spacing);
-                                    base_frame.show(&mut columns[0], |ui| {
-                                        ui.set_min_width(min_card_width);
-                                        ui.set_min_height(180.0);
-                                        self.show_memory_card(ui);
-                                    });
-                                    columns[0].add_space(spacing);
-                                    base_frame.show(&mut columns[0], |ui| {
-                                        ui.set_min_width(min_card_width);
-                                        ui.set_min_height(180.0);
-                                        self.show_network_card(ui);
-                                    });
-                                    columns[0].add_space(spacing);
-                                    base_frame.show(&mut columns[0], |ui| {
-                                        ui.set_min_width(min_card_width);
-                                        ui.set_min_height(180.0);
-                                        self.show_gpu_card(ui);
-                                    });
-                                } else if columns.len() == 2 {
-                                    columns[0].add_space(spacing);
-                                    base_frame.show(&mut columns[0], |ui| {
-                                        ui.set_min_width(min_card_width);
-                                        ui.set_min_height(180.0);
-                                        self.show_memory_card(ui);
-                                    });
-                                    
-                                    columns[1].add_space(spacing);
-                                    base_frame.show(&mut columns[1], |ui| {
-                                        ui.set_min_width(min_card_width);
-                                        ui.set_min_height(180.0);
-                                        self.show_gpu_card(ui);
-                                    });
-                                }
-                            });
-                        });
-                    },
-                    Tab::Tools => {
-                        self.show_tools_tab(ui);
-                    },
-                }
-            }
-        });
-
-        // Show overlay message when installer is running
-        if self.ninite_running {
-            let screen_rect = ctx.screen_rect();
-            let window_size = egui::Vec2::new(400.0, 150.0);
-            let pos = screen_rect.center() - (window_size * 0.5);
-            
-            egui::Window::new("installer_overlay")
-                .fixed_pos(pos)
-                .fixed_size(window_size)
-                .title_bar(false)
-                .frame(egui::Frame::none()
-                    .fill(egui::Color32::from_black_alpha(240))
-                    .rounding(8.0)
-                    .inner_margin(egui::style::Margin::same(20.0))
-                    .shadow(egui::epaint::Shadow {
-                        extrusion: 8.0,
-                        color: egui::Color32::from_black_alpha(100),
-                    }))
-                .show(ctx, |ui| {
-                    ui.vertical_centered(|ui| {
-                        ui.heading("Installation in Progress");
-                        ui.add_space(8.0);
-                        ui.label("The Ninite installer is running.");
-                        ui.label("Please complete the installation before making any changes.");
-                        ui.add_space(8.0);
-                        ui.label("This window will be enabled once the installation is complete.");
+                        let tabs = [
+                            (Tab::Dashboard, "Dashboard", TourTarget::DashboardTab),
+                            (Tab::Tools, "Tools", TourTarget::ToolsTab),
+                            (Tab::Processes, "Processes", TourTarget::ProcessesTab),
+                            (Tab::Security, "Security", TourTarget::SecurityTab),
+                            (Tab::Containers, "Containers", TourTarget::ContainersTab),
+                            (Tab::Services, "Services", TourTarget::ServicesTab),
+                        ];
+                        for (tab, label, target) in tabs {
+                            let response = ui.selectable_value(&mut self.current_tab, tab, label);
+                            self.tour.mark(target, &response);
+                        }
+                        self.show_tab_indicators(ui);
                     });
                 });
         }
 
-        // Prevent tab switching during installation
-        if self.ninite_running {
-            self.current_tab = Tab::Tools;
-        }
-
         egui::CentralPanel::default().show(ctx, |ui| {
             let available_width = ui.available_width();
             
@@ -1471,7 +2398,7 @@ impl eframe::App for DevDashboard {
                         let scroll_area = egui::ScrollArea::vertical().id_source("dashboard_scroll");
                         scroll_area.show(ui, |ui| {
                             let base_frame = egui::Frame::none()
-                                .fill(egui::Color32::from_rgb(31, 41, 55))
+                                .fill(self.palette().card)
                                 .inner_margin(egui::style::Margin::same(16.0))
                                 .rounding(12.0)
                                 .shadow(egui::epaint::Shadow {
@@ -1479,93 +2406,26 @@ impl eframe::App for DevDashboard {
                                     color: egui::Color32::from_black_alpha(60),
                                 });
 
+                            // Popped-out cards live in their own windows instead
+                            let cards: Vec<DashboardCard> = self.dashboard_cards()
+                                .into_iter()
+                                .filter(|card| !self.settings.popouts.is_popped(*card))
+                                .collect();
+                            self.show_favorites_strip(ui);
                             ui.columns(columns, |columns| {
-                                for i in 0..columns.len() {
-                                    columns[i].add_space(spacing);
-                                    match i {
-                                        0 => base_frame.show(&mut columns[i], |ui| {
-                                            ui.set_min_width(min_card_width);
-                                            ui.set_min_height(180.0);
-                                            self.show_system_card(ui);
-                                        }),
-                                        1 => base_frame.show(&mut columns[i], |ui| {
-                                            ui.set_min_width(min_card_width);
-                                            ui.set_min_height(180.0);
-                                            self.show_cpu_card(ui);
-                                        }),
-                                        2 => base_frame.show(&mut columns[i], |ui| {
-                                            ui.set_min_width(min_card_width);
-                                            ui.set_min_height(180.0);
-                                            self.show_memory_card(ui);
-                                        }),
-                                        _ => unreachable!(),
-                                    };
-                                }
-
-                                for column in columns.iter_mut() {
+                                let column_count = columns.len();
+                                for (index, card) in cards.into_iter().enumerate() {
+                                    let column = &mut columns[index % column_count];
                                     column.add_space(spacing);
-                                }
-
-                                for i in 0..columns.len() {
-                                    match i {
-                                        0 => base_frame.show(&mut columns[i], |ui| {
-                                            ui.set_min_width(min_card_width);
-                                            ui.set_min_height(180.0);
-                                            self.show_storage_card(ui);
-                                        }),
-                                        1 => base_frame.show(&mut columns[i], |ui| {
-                                            ui.set_min_width(min_card_width);
-                                            ui.set_min_height(180.0);
-                                            self.show_network_card(ui);
-                                        }),
-                                        2 => base_frame.show(&mut columns[i], |ui| {
-                                            ui.set_min_width(min_card_width);
-                                            ui.set_min_height(180.0);
-                                            self.show_gpu_card(ui);
-                                        }),
-                                        _ => unreachable!(),
-                                    };
-                                }
-
-                                if columns.len() == 1 {
-                                    columns[0].add_space(spacing);
-                                    base_frame.show(&mut columns[0], |ui| {
+                                    let frame = base_frame.show(column, |ui| {
                                         ui.set_min_width(min_card_width);
                                         ui.set_min_height(180.0);
-                                        self.show_cpu_card(ui);
-                                    });
-                                    columns[0].add_space(spacing);
-                                    base_frame.show(&mut columns[0], |ui| {
-                                        ui.set_min_width(min_card_width);
-                                        ui.set_min_height(180.0);
-                                        self.show_memory_card(ui);
-                                    });
-                                    columns[0].add_space(spacing);
-                                    base_frame.show(&mut columns[0], |ui| {
-                                        ui.set_min_width(min_card_width);
-                                        ui.set_min_height(180.0);
-                                        self.show_network_card(ui);
-                                    });
-                                    columns[0].add_space(spacing);
-                                    base_frame.show(&mut columns[0], |ui| {
-                                        ui.set_min_width(min_card_width);
-                                        ui.set_min_height(180.0);
-                                        self.show_gpu_card(ui);
-                                    });
-                                } else if columns.len() == 2 {
-                                    columns[0].add_space(spacing);
-                                    base_frame.show(&mut columns[0], |ui| {
-                                        ui.set_min_width(min_card_width);
-                                        ui.set_min_height(180.0);
-                                        self.show_memory_card(ui);
-                                    });
-                                    
-                                    columns[1].add_space(spacing);
-                                    base_frame.show(&mut columns[1], |ui| {
-                                        ui.set_min_width(min_card_width);
-                                        ui.set_min_height(180.0);
-                                        self.show_gpu_card(ui);
+                                        self.show_dashboard_card(ui, card);
                                     });
+                                    self.tour.mark(TourTarget::Card(card), &frame.response);
+                                    self.show_card_menu(frame.response.interact(egui::Sense::click()), card);
+                                    self.show_popout_button(column, frame.response.rect, card);
+                                    self.show_alert_badge(column, frame.response.rect, card);
                                 }
                             });
                         });
@@ -1576,10 +2436,26 @@ impl eframe::App for DevDashboard {
                             self.show_tools_tab(ui);
                         });
                     },
+                    Tab::Processes => {
+                        self.show_processes_tab(ui);
+                    },
+                    Tab::Security => {
+                        self.show_security_tab(ui);
+                    },
+                    Tab::Containers => {
+                        self.show_containers_tab(ui);
+                    },
+                    Tab::Services => {
+                        self.show_services_tab(ui);
+                    },
                 }
             }
         });
 
+        if !self.ninite_running {
+            self.show_tour(ctx);
+        }
+
         // Show overlay message when installer is running
         if self.ninite_running {
             let screen_rect = ctx.screen_rect();
@@ -1615,9 +2491,118 @@ impl eframe::App for DevDashboard {
             self.current_tab = Tab::Tools;
         }
     }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        // Keep the traffic and last-seen time since the last periodic save
+        self.network_totals.save();
+        self.uptime_history.save();
+        self.daily_summaries.save();
+    }
 }
 
 impl DevDashboard {
+    /// Returns the cards to show on the dashboard, in display order
+    /// Optional cards are only included once they have something to show
+    fn dashboard_cards(&self) -> Vec<DashboardCard> {
+        let mut cards = vec![
+            DashboardCard::System,
+            DashboardCard::Cpu,
+            DashboardCard::Memory,
+            DashboardCard::Storage,
+            DashboardCard::Network,
+            DashboardCard::Gpu,
+        ];
+        if !self.settings.certificates.domains.is_empty() {
+            cards.push(DashboardCard::Certificates);
+        }
+        if self.settings.ticker.enabled {
+            cards.push(DashboardCard::Ticker);
+        }
+        if !self.settings.calendar.ics_source.trim().is_empty() {
+            cards.push(DashboardCard::Calendar);
+        }
+        if self.settings.show_todo_card {
+            cards.push(DashboardCard::Todo);
+        }
+        if self.settings.usage.enabled {
+            cards.push(DashboardCard::Usage);
+        }
+        if self.settings.backup.is_configured() {
+            cards.push(DashboardCard::Backup);
+        }
+        if self.settings.cloud.enabled {
+            cards.push(DashboardCard::Cloud);
+        }
+        if self.settings.power.enabled {
+            cards.push(DashboardCard::Power);
+        }
+        if self.battery_health.has_battery() {
+            cards.push(DashboardCard::Battery);
+        }
+        if self.settings.uptime.show_card {
+            cards.push(DashboardCard::Uptime);
+        }
+        if !self.settings.network_shares.shares.is_empty() {
+            cards.push(DashboardCard::NetworkShares);
+        }
+        if !self.settings.repos.repos.is_empty() {
+            cards.push(DashboardCard::Repos);
+        }
+        if !self.settings.stacks.stacks.is_empty() {
+            cards.push(DashboardCard::Stacks);
+        }
+        if !self.settings.tunnels.tunnels.is_empty() {
+            cards.push(DashboardCard::Tunnels);
+        }
+        if self.settings.top_processes.show_card {
+            cards.push(DashboardCard::TopProcesses);
+        }
+        if self.settings.process_network.show_card {
+            cards.push(DashboardCard::ProcessNetwork);
+        }
+        if self.settings.wsl.show_card {
+            cards.push(DashboardCard::Wsl);
+        }
+        cards.extend(self.plugins.card_indices().into_iter().map(DashboardCard::Plugin));
+        cards.extend(self.scripts.card_indices().into_iter().map(DashboardCard::Script));
+        cards.extend((0..self.settings.wmi_cards.cards.len()).map(DashboardCard::Wmi));
+        cards.extend((0..self.settings.command_cards.cards.len()).map(DashboardCard::Command));
+        cards
+    }
+
+    /// Renders a single dashboard card
+    fn show_dashboard_card(&mut self, ui: &mut egui::Ui, card: DashboardCard) {
+        match card {
+            DashboardCard::System => self.show_system_card(ui),
+            DashboardCard::Cpu => self.show_cpu_card(ui),
+            DashboardCard::Memory => self.show_memory_card(ui),
+            DashboardCard::Storage => self.show_storage_card(ui),
+            DashboardCard::Network => self.show_network_card(ui),
+            DashboardCard::Gpu => self.show_gpu_card(ui),
+            DashboardCard::Certificates => self.show_certificates_card(ui),
+            DashboardCard::Ticker => self.show_ticker_card(ui),
+            DashboardCard::Calendar => self.show_calendar_card(ui),
+            DashboardCard::Todo => self.show_todo_card(ui),
+            DashboardCard::Usage => self.show_usage_card(ui),
+            DashboardCard::Backup => self.show_backup_card(ui),
+            DashboardCard::Cloud => self.show_cloud_card(ui),
+            DashboardCard::Power => self.show_power_card(ui),
+            DashboardCard::Battery => self.show_battery_card(ui),
+            DashboardCard::Uptime => self.show_uptime_card(ui),
+            DashboardCard::NetworkShares => self.show_network_shares_card(ui),
+            DashboardCard::Repos => self.show_repos_card(ui),
+            DashboardCard::Stacks => self.show_stacks_card(ui),
+            DashboardCard::Tunnels => self.show_tunnels_card(ui),
+            DashboardCard::TopProcesses => self.show_top_processes_card(ui),
+            DashboardCard::ProcessNetwork => self.show_process_network_card(ui),
+            DashboardCard::Wsl => self.show_wsl_card(ui),
+            DashboardCard::Plugin(index) => self.show_plugin_card(ui, index),
+            DashboardCard::Script(index) => self.show_script_card(ui, index),
+            DashboardCard::Wmi(index) => self.show_wmi_card(ui, index),
+            DashboardCard::Command(index) => self.show_command_card(ui, index),
+        }
+    }
+
     /// Helper function to display a card in the UI with consistent styling
     /// title: Card title
     /// add_contents: Function to add card contents
@@ -1641,6 +2626,7 @@ impl DevDashboard {
                 self.sys.name().unwrap_or_default(),
                 self.sys.os_version().unwrap_or_default()
             ));
+            ui.label(format!("Architecture: {}", arch::os_arch().label()));
             ui.label(format!("Hostname: {}", self.sys.host_name().unwrap_or_default()));
             ui.label(format!("Uptime: {} hours, {} minutes", 
                 uptime_hours,
@@ -1658,6 +2644,8 @@ impl DevDashboard {
                 ui.label(format!("Physical Cores: {}", self.sys.physical_core_count().unwrap_or(0)));
                 ui.label(format!("Threads: {}", self.sys.cpus().len()));
                 ui.label(format!("Speed: {:.1} GHz", cpu.frequency() as f64 / 1000.0));
+                self.show_cpu_throttle_status(ui);
+                temperature_trend::show_trend_warning(ui, &self.temperature_trends.cpu);
                 
                 ui.add_space(4.0);
                 ui.label("Usage:");
@@ -1669,10 +2657,58 @@ impl DevDashboard {
                 visuals.widgets.inactive.bg_fill = egui::Color32::from_rgb(55, 65, 81);
                 ui.add(egui::ProgressBar::new(self.current_cpu_usage.current / 100.0)
                     .fill(egui::Color32::from_rgb(37, 99, 235)));
+                self.show_metric_graph(ui, GraphMetric::Cpu, egui::Color32::from_rgb(37, 99, 235));
+                self.show_core_heatmap(ui);
             }
         });
     }
 
+    /// Collapsible grid with one cell per logical core, shaded by its usage
+    fn show_core_heatmap(&self, ui: &mut egui::Ui) {
+        if self.core_usage.is_empty() {
+            return;
+        }
+        egui::CollapsingHeader::new(format!("Per-core usage ({} threads)", self.core_usage.len()))
+            .id_source("cpu_core_heatmap")
+            .show(ui, |ui| {
+                const CELL: f32 = 30.0;
+                const GAP: f32 = 3.0;
+                let per_row = (((ui.available_width() + GAP) / (CELL + GAP)).floor() as usize).max(1);
+                let rows = (self.core_usage.len() - 1) / per_row + 1;
+                let size = egui::vec2(ui.available_width(), rows as f32 * (CELL + GAP) - GAP);
+                let (rect, response) = ui.allocate_exact_size(size, egui::Sense::hover());
+                let painter = ui.painter_at(rect);
+
+                let idle = egui::Color32::from_rgb(55, 65, 81);
+                let busy = egui::Color32::from_rgb(37, 99, 235);
+                let hot = egui::Color32::from_rgb(220, 50, 50);
+                let mut hovered = None;
+                for (index, usage) in self.core_usage.iter().enumerate() {
+                    let min = rect.min + egui::vec2((index % per_row) as f32 * (CELL + GAP), (index / per_row) as f32 * (CELL + GAP));
+                    let cell = egui::Rect::from_min_size(min, egui::vec2(CELL, CELL));
+                    let load = (usage.current / 100.0).clamp(0.0, 1.0);
+                    // Blue deepens with load, turning red for a core that is nearly pegged
+                    let color = if load < 0.85 {
+                        egui::Color32::from_rgb(
+                            lerp(idle.r() as f32, busy.r() as f32, load / 0.85) as u8,
+                            lerp(idle.g() as f32, busy.g() as f32, load / 0.85) as u8,
+                            lerp(idle.b() as f32, busy.b() as f32, load / 0.85) as u8)
+                    } else {
+                        hot
+                    };
+                    painter.rect_filled(cell, 3.0, color);
+                    painter.text(cell.center(), egui::Align2::CENTER_CENTER, format!("{:.0}", usage.current),
+                        egui::FontId::proportional(10.0), egui::Color32::from_rgb(243, 244, 246));
+                    if response.hovered() && response.hover_pos().is_some_and(|pos| cell.contains(pos)) {
+                        hovered = Some((index, usage.current));
+                    }
+                }
+                if let Some((index, usage)) = hovered {
+                    response.on_hover_text(format!("Thread {}: {:.1}%", index, usage));
+                }
+            });
+    }
+
     /// Displays memory information card
     /// Shows total memory, used memory, free memory, and usage percentage
     fn show_memory_card(&self, ui: &mut egui::Ui) {
@@ -1695,6 +2731,7 @@ impl DevDashboard {
             visuals.widgets.inactive.bg_fill = egui::Color32::from_rgb(55, 65, 81);
             ui.add(egui::ProgressBar::new(self.memory_usage.current)
                 .fill(egui::Color32::from_rgb(22, 163, 74)));
+            self.show_metric_graph(ui, GraphMetric::Memory, egui::Color32::from_rgb(22, 163, 74));
         });
     }
 
@@ -1702,19 +2739,11 @@ impl DevDashboard {
     /// Shows disk usage for each drive with detailed statistics
     fn show_storage_card(&mut self, ui: &mut egui::Ui) {
         self.show_card(ui, "Storage", |ui| {
-            for disk in self.sys.disks() {
-                let mount_point = disk.mount_point().to_string_lossy();
+            for volume in self.volume_monitor.volumes() {
+                let mount_point = volume.mount.as_str();
                 
-                // Use Windows API to get accurate disk space information
-                if let Some((total_bytes, free_bytes)) = Self::get_disk_space(&mount_point) {
-                    if total_bytes == 0 {
-                        warn!("Drive {} reported zero total space", mount_point);
-                        ui.label(RichText::new(format!("{} (Unable to read size)", mount_point)).strong());
-                        ui.label("Could not read disk information. Try running as administrator.");
-                        ui.add_space(12.0);
-                        continue;
-                    }
-                    
+                // Space is read in the background at each volume's refresh interval
+                if let Some((total_bytes, free_bytes)) = volume.space {
                     let available_bytes = free_bytes;
                     let used_bytes = total_bytes - available_bytes;
                     
@@ -1722,13 +2751,18 @@ impl DevDashboard {
                     let (_used, _) = DevDashboard::format_bytes(used_bytes);
                     let free_gb = available_bytes as f64 / (1024.0 * 1024.0 * 1024.0);
                     
-                    let usage_percentage = if let Some(usage) = self.disk_usage.get(&mount_point.to_string()) {
+                    let usage_percentage = if let Some(usage) = self.disk_usage.get(mount_point) {
                         usage.current * 100.0
                     } else {
                         (used_bytes as f32 / total_bytes as f32) * 100.0
                     };
                     
                     ui.label(RichText::new(format!("{} ({:.1} {})", mount_point, total, total_unit)).strong());
+                    if !volume.online {
+                        let age = volume.checked.map(|checked| checked.elapsed().as_secs() / 60).unwrap_or_default();
+                        ui.label(RichText::new(format!("⚠ Offline, last read {} min ago", age))
+                            .color(egui::Color32::from_rgb(202, 138, 4)));
+                    }
                     ui.horizontal(|ui| {
                         ui.label(format!("Used ({:.1}%)", usage_percentage));
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
@@ -1736,7 +2770,7 @@ impl DevDashboard {
                         });
                     });
                     
-                    let progress = if let Some(usage) = self.disk_usage.get(&mount_point.to_string()) {
+                    let progress = if let Some(usage) = self.disk_usage.get(mount_point) {
                         usage.current
                     } else {
                         used_bytes as f32 / total_bytes as f32
@@ -1754,11 +2788,28 @@ impl DevDashboard {
                     } else {
                         format!("Free: {:.1} GB", free_space / (1024.0 * 1024.0 * 1024.0))
                     };
-                    ui.label(free_space_str);
+                    ui.horizontal(|ui| {
+                        ui.label(free_space_str);
+                        let (forecast, color) = self.storage_history.forecast(mount_point).describe();
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            ui.label(RichText::new(forecast).color(color))
+                                .on_hover_text("Projected from the free-space trend over the last two weeks");
+                        });
+                    });
+
+                    // Full and busy are different problems, so show how the drive is coping with its load
+                    if let Some(io) = self.disk_io.get(mount_point) {
+                        ui.label(RichText::new(io.summary()).color(io.color()))
+                            .on_hover_text(format!(
+                                "Average response time per read and write, average outstanding requests ({} right now), and the share of time the drive was busy",
+                                io.current_queue
+                            ));
+                    }
+                } else if volume.checked.is_none() {
+                    ui.label(RichText::new(format!("{} (Reading...)", mount_point)).strong());
                 } else {
-                    error!("Failed to get disk space for {}", mount_point);
-                    ui.label(RichText::new(format!("{} (Error)", mount_point)).strong());
-                    ui.label("Failed to read disk information. Try running as administrator.");
+                    // Never readable, like an empty card reader
+                    continue;
                 }
                 
                 ui.add_space(12.0);
@@ -1789,90 +2840,180 @@ impl DevDashboard {
         }
     }
 
+    /// Received and sent columns: bytes since boot and the current speed
+    fn show_network_traffic(ui: &mut egui::Ui, total_received: u64, total_sent: u64, received_speed: f64, sent_speed: f64) {
+        let total_text = |bytes: u64| {
+            let bytes = bytes as f64;
+            if bytes >= 1024.0 * 1024.0 * 1024.0 {
+                format!("{:.1} GB", bytes / (1024.0 * 1024.0 * 1024.0))
+            } else if bytes >= 1024.0 * 1024.0 {
+                format!("{:.1} MB", bytes / (1024.0 * 1024.0))
+            } else {
+                format!("{:.1} KB", bytes / 1024.0)
+            }
+        };
+        ui.horizontal(|ui| {
+            ui.vertical(|ui| {
+                ui.label("Received");
+                ui.label(RichText::new(total_text(total_received))
+                    .size(16.0)
+                    .strong());
+                ui.label(RichText::new(format!("{:.1} kb/s", received_speed / 1024.0))
+                    .color(egui::Color32::from_rgb(88, 165, 237)));
+            });
+            ui.add_space(32.0); // Add fixed margin between received and sent data
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.vertical(|ui| {
+                    ui.label("Sent");
+                    ui.label(RichText::new(total_text(total_sent))
+                        .size(16.0)
+                        .strong());
+                    ui.label(RichText::new(format!("{:.1} kb/s", sent_speed / 1024.0))
+                        .color(egui::Color32::from_rgb(67, 208, 118)));
+                });
+            });
+        });
+        ui.label(RichText::new("Totals above are since boot")
+            .color(egui::Color32::from_rgb(107, 114, 128)));
+    }
+
+    /// Session, today and all-time rows; returns whether the session reset was clicked
+    fn show_network_totals(ui: &mut egui::Ui, id: impl std::hash::Hash, rows: [(&str, Traffic); 3],
+                           session_start: chrono::DateTime<chrono::Local>) -> bool {
+        let size = |bytes: u64| {
+            let (value, unit) = DevDashboard::format_bytes(bytes);
+            format!("{:.1} {}", value, unit)
+        };
+        let grey = egui::Color32::from_rgb(156, 163, 175);
+        let mut reset = false;
+        egui::Grid::new(id)
+            .num_columns(4)
+            .spacing([12.0, 2.0])
+            .show(ui, |ui| {
+                for (label, traffic) in rows {
+                    let response = ui.label(RichText::new(label).color(grey));
+                    if label == "Session" {
+                        response.on_hover_text(format!("Since {}", session_start.format("%Y-%m-%d %H:%M:%S")));
+                    }
+                    ui.label(format!("↓ {}", size(traffic.received)));
+                    ui.label(format!("↑ {}", size(traffic.sent)));
+                    if label == "Session" {
+                        reset = ui.small_button("⟲").on_hover_text("Reset session counters").clicked();
+                    } else {
+                        ui.label("");
+                    }
+                    ui.end_row();
+                }
+            });
+        reset
+    }
+
     /// Displays network information card
-    /// Shows network interface statistics including speed and total usage
-    fn show_network_card(&self, ui: &mut egui::Ui) {
+    /// Shows network interface statistics including speed and total usage,
+    /// per interface or combined across all physical interfaces
+    fn show_network_card(&mut self, ui: &mut egui::Ui) {
+        let mut reset = Vec::new();
+        let mut open_events = false;
+        let mut aggregate = self.settings.network_card.aggregate;
         self.show_card(ui, "Network", |ui| {
-            for (name, _data) in self.sys.networks() {
-                let name_lower = name.to_string().to_lowercase();
-                
-                // Filter for physical network interfaces
-                let is_physical = name_lower.contains("ethernet") ||
-                                name_lower.starts_with("eth") ||
-                                name_lower.starts_with("en") ||
-                                name_lower.starts_with("wlan") ||
-                                name_lower.starts_with("wi-fi") ||
-                                name_lower.starts_with("wireless") ||
-                                name_lower.contains("wireless");
-                
-                if !is_physical {
-                    continue;
-                }
-                
-                // Display interface type
-                if name_lower.contains("wireless") || 
-                   name_lower.contains("wi-fi") || 
-                   name_lower.starts_with("wlan") {
-                    ui.label("Wi-Fi");
-                } else {
-                    ui.label("Ethernet");
-                }
-                
+            let interfaces: Vec<(&String, &NetworkStats)> = self.sys.networks()
+                .into_iter()
+                .filter(|(name, _)| Self::is_physical_interface(name))
+                .filter_map(|(name, _)| self.network_stats.get_key_value(name))
+                .collect();
+            let is_wifi = |name: &str| {
+                let name_lower = name.to_lowercase();
+                name_lower.contains("wireless") || name_lower.contains("wi-fi") || name_lower.starts_with("wlan")
+            };
+
+            if aggregate && !interfaces.is_empty() {
+                ui.label(format!("All interfaces ({})", interfaces.len()));
                 ui.add_space(4.0);
-                
-                // Display network statistics if available
-                if let Some(stats) = self.network_stats.get(name) {
-                    ui.horizontal(|ui| {
-                        ui.vertical(|ui| {
-                            ui.label("Received");
-                            let total_received = stats.total_received as f64;
-                            let total_text = if total_received >= 1024.0 * 1024.0 * 1024.0 {
-                                format!("{:.1} GB", total_received / (1024.0 * 1024.0 * 1024.0))
-                            } else if total_received >= 1024.0 * 1024.0 {
-                                format!("{:.1} MB", total_received / (1024.0 * 1024.0))
-                            } else {
-                                format!("{:.1} KB", total_received / 1024.0)
-                            };
-                            ui.label(RichText::new(total_text)
-                                .size(16.0)
-                                .strong());
-                            ui.label(RichText::new(format!("{:.1} kb/s", stats.received_speed / 1024.0))
-                                .color(egui::Color32::from_rgb(88, 165, 237)));
-                        });
-                        ui.add_space(32.0); // Add fixed margin between received and sent data
-                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            ui.vertical(|ui| {
-                                ui.label("Sent");
-                                let total_sent = stats.total_sent as f64;
-                                let total_text = if total_sent >= 1024.0 * 1024.0 * 1024.0 {
-                                    format!("{:.1} GB", total_sent / (1024.0 * 1024.0 * 1024.0))
-                                } else if total_sent >= 1024.0 * 1024.0 {
-                                    format!("{:.1} MB", total_sent / (1024.0 * 1024.0))
-                                } else {
-                                    format!("{:.1} KB", total_sent / 1024.0)
-                                };
-                                ui.label(RichText::new(total_text)
-                                    .size(16.0)
-                                    .strong());
-                                ui.label(RichText::new(format!("{:.1} kb/s", stats.sent_speed / 1024.0))
+                Self::show_network_traffic(ui,
+                    interfaces.iter().map(|(_, stats)| stats.total_received).sum(),
+                    interfaces.iter().map(|(_, stats)| stats.total_sent).sum(),
+                    interfaces.iter().map(|(_, stats)| stats.received_speed).sum(),
+                    interfaces.iter().map(|(_, stats)| stats.sent_speed).sum());
+                let rows = [
+                    ("Session", interfaces.iter().map(|(_, stats)| stats.session).sum()),
+                    ("Today", interfaces.iter().map(|(name, _)| self.network_totals.today(name)).sum()),
+                    ("All time", interfaces.iter().map(|(name, _)| self.network_totals.all_time(name)).sum()),
+                ];
+                let session_start = interfaces.iter().map(|(_, stats)| stats.session_start).min().unwrap_or_else(chrono::Local::now);
+                if Self::show_network_totals(ui, "network_totals_combined", rows, session_start) {
+                    reset = interfaces.iter().map(|(name, _)| (*name).clone()).collect();
+                }
+                egui::CollapsingHeader::new("Breakdown").id_source("network_breakdown").show(ui, |ui| {
+                    egui::Grid::new("network_breakdown_grid")
+                        .num_columns(3)
+                        .spacing([12.0, 2.0])
+                        .show(ui, |ui| {
+                            for (name, stats) in &interfaces {
+                                ui.label(if is_wifi(name) { "Wi-Fi" } else { "Ethernet" }).on_hover_text(name.as_str());
+                                ui.label(RichText::new(format!("↓ {:.1} kb/s", stats.received_speed / 1024.0))
+                                    .color(egui::Color32::from_rgb(88, 165, 237)));
+                                ui.label(RichText::new(format!("↑ {:.1} kb/s", stats.sent_speed / 1024.0))
                                     .color(egui::Color32::from_rgb(67, 208, 118)));
-                            });
+                                ui.end_row();
+                            }
                         });
-                    });
+                });
+            } else {
+                for (name, stats) in &interfaces {
+                    // Display interface type
+                    ui.label(if is_wifi(name) { "Wi-Fi" } else { "Ethernet" });
+                    ui.add_space(4.0);
+                    Self::show_network_traffic(ui, stats.total_received, stats.total_sent, stats.received_speed, stats.sent_speed);
+                    let rows = [
+                        ("Session", stats.session),
+                        ("Today", self.network_totals.today(name)),
+                        ("All time", self.network_totals.all_time(name)),
+                    ];
+                    if Self::show_network_totals(ui, ("network_totals", name.as_str()), rows, stats.session_start) {
+                        reset = vec![(*name).clone()];
+                    }
                 }
             }
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                open_events = ui.button("🕑 Events")
+                    .on_hover_text("Timeline of disconnects, address changes, DHCP renewals and Wi-Fi roams")
+                    .clicked();
+                if ui.button("⇆ Compare").on_hover_text("Throughput today against yesterday or last week").clicked() {
+                    history_compare::request_compare(ui, ArchiveMetric::Network);
+                }
+                ui.checkbox(&mut aggregate, "Combine interfaces")
+                    .on_hover_text("One throughput figure for Ethernet and Wi-Fi together, with a breakdown below it");
+            });
         });
+        if open_events {
+            self.network_events.open = true;
+        }
+        if aggregate != self.settings.network_card.aggregate {
+            self.settings.network_card.aggregate = aggregate;
+            self.save_settings();
+        }
+        for name in reset {
+            if let Some(stats) = self.network_stats.get_mut(&name) {
+                info!("Reset session network counters for {}", name);
+                stats.session = Traffic::default();
+                stats.session_start = chrono::Local::now();
+            }
+        }
     }
 
     /// Displays GPU information card
     /// Shows GPU model, driver version, usage, temperature, and memory usage
     fn show_gpu_card(&mut self, ui: &mut egui::Ui) {
         if let Some(gpu_info) = &self.gpu_info {
+            let mut open_details = false;
+            let mut pin = None;
             self.show_card(ui, "GPU", |ui| {
                 ui.label(RichText::new(&gpu_info.name).strong());
                 if let Some(driver) = &gpu_info.driver_version {
                     ui.label(format!("Driver: {}", driver));
                 }
+                self.show_gpu_driver_notice(ui);
                 if let Some(pci_id) = &gpu_info.pci_bus_id {
                     ui.label(format!("Bus ID: {}", pci_id));
                 }
@@ -1887,10 +3028,29 @@ impl DevDashboard {
                 visuals.widgets.inactive.bg_fill = egui::Color32::from_rgb(55, 65, 81);
                 ui.add(egui::ProgressBar::new(gpu_info.gpu_usage.current)
                     .fill(egui::Color32::from_rgb(220, 38, 38)));
+                self.show_metric_graph(ui, GraphMetric::Gpu, egui::Color32::from_rgb(220, 38, 38));
 
                 if let Some(temp) = gpu_info.temperature {
                     ui.label(format!("Temperature: {}°C", temp));
                 }
+                temperature_trend::show_trend_warning(ui, &self.temperature_trends.gpu);
+                if let Some(graphics) = gpu_info.graphics_clock {
+                    match gpu_info.memory_clock {
+                        Some(memory) => ui.label(format!("Clocks: {} MHz core · {} MHz memory", graphics, memory)),
+                        None => ui.label(format!("Clock: {} MHz", graphics)),
+                    };
+                }
+                if let Some(limits) = gpu_info.limits.summary() {
+                    ui.label(RichText::new(limits).color(egui::Color32::from_rgb(156, 163, 175)));
+                }
+                if let Some(compute) = gpu_info.compute.summary() {
+                    let color = if gpu_info.compute.needs_attention() {
+                        egui::Color32::from_rgb(202, 138, 4)
+                    } else {
+                        egui::Color32::from_rgb(156, 163, 175)
+                    };
+                    ui.label(RichText::new(compute).color(color));
+                }
 
                 if let (Some(total), Some(used)) = (gpu_info.memory_total, gpu_info.memory_used) {
                     ui.add_space(8.0);
@@ -1907,11 +3067,24 @@ impl DevDashboard {
                     ui.add(egui::ProgressBar::new(gpu_info.memory_usage.current)
                         .fill(egui::Color32::from_rgb(147, 51, 234)));
                 }
+
+                pin = self.show_gpu_adapters(ui);
+
+                ui.add_space(8.0);
+                open_details = ui.button("📈 History").on_hover_text("Utilization, VRAM, temperature and clock graphs").clicked();
             });
+            if let Some(adapter) = pin {
+                self.pin_gpu(adapter.as_ref());
+            }
+            if open_details {
+                self.gpu_detail_open = true;
+            }
+            self.show_gpu_recovery(ui);
         } else {
             self.show_card(ui, "GPU", |ui| {
                 ui.label("No GPU detected");
             });
+            self.show_gpu_recovery(ui);
         }
     }
 
@@ -1930,41 +3103,59 @@ impl DevDashboard {
 
     /// Initializes GPU information using either NVML (for NVIDIA GPUs) or WMI (for other GPUs)
     /// Returns None if no suitable GPU is found
-    fn initialize_gpu() -> Option<GpuInfo> {
+    fn initialize_gpu(selection: &GpuSelectionSettings) -> Option<GpuInfo> {
+        // On hybrid laptops the pinned adapter wins; otherwise the discrete GPU is preferred
+        let adapters = hybrid_gpu::adapters();
+        let pinned = adapters.iter().find(|adapter| adapter.key() == selection.pinned);
+        let preferred = pinned.or_else(|| adapters.iter().find(|adapter| !adapter.is_integrated()));
+
         // Try NVIDIA GPU first using NVML
-        match Nvml::init() {
-            Ok(nvml) => {
-                match nvml.device_by_index(0) {
-                    Ok(device) => {
-                        let name = device.name().unwrap_or_else(|_| "Unknown GPU".to_string());
-                        let mut gpu_info = GpuInfo::new(name);
+        #[cfg(feature = "nvml")]
+        if pinned.map(GpuAdapter::is_nvidia).unwrap_or(true) {
+            match Nvml::init() {
+                Ok(nvml) => {
+                    // NVML reports the PCI IDs as device << 16 | vendor
+                    let index = pinned
+                        .and_then(|adapter| {
+                            let pci_id = adapter.device_id << 16 | adapter.vendor_id;
+                            (0..nvml.device_count().unwrap_or(0)).find(|&index| {
+                                nvml.device_by_index(index).and_then(|device| device.pci_info()).is_ok_and(|pci| pci.pci_device_id == pci_id)
+                            })
+                        })
+                        .unwrap_or(0);
+                    match nvml.device_by_index(index) {
+                        Ok(device) => {
+                            let name = device.name().unwrap_or_else(|_| "Unknown GPU".to_string());
+                            let mut gpu_info = GpuInfo::new(name);
+                            gpu_info.nvml_index = Some(index);
                         
-                        if let Ok(pci_info) = device.pci_info() {
-                            gpu_info.pci_bus_id = Some(format!("{:04x}:{:02x}:{:02x}.0", 
-                                pci_info.domain, 
-                                pci_info.bus, 
-                                pci_info.device
-                            ));
-                        }
+                            if let Ok(pci_info) = device.pci_info() {
+                                gpu_info.pci_bus_id = Some(format!("{:04x}:{:02x}:{:02x}.0", 
+                                    pci_info.domain, 
+                                    pci_info.bus, 
+                                    pci_info.device
+                                ));
+                            }
                         
-                        if let Ok(version) = nvml.sys_driver_version() {
-                            gpu_info.driver_version = Some(version);
-                        }
+                            if let Ok(version) = nvml.sys_driver_version() {
+                                gpu_info.driver_version = Some(version);
+                            }
                         
-                        info!("Successfully initialized NVIDIA GPU: {} (Driver: {})", 
-                            gpu_info.name,
-                            gpu_info.driver_version.as_deref().unwrap_or("Unknown")
-                        );
+                            info!("Successfully initialized NVIDIA GPU: {} (Driver: {})", 
+                                gpu_info.name,
+                                gpu_info.driver_version.as_deref().unwrap_or("Unknown")
+                            );
                         
-                        return Some(gpu_info);
-                    }
-                    Err(e) => {
-                        warn!("Failed to get NVIDIA device: {}", e);
+                            return Some(gpu_info);
+                        }
+                        Err(e) => {
+                            warn!("Failed to get NVIDIA device: {}", e);
+                        }
                     }
                 }
-            }
-            Err(e) => {
-                warn!("Failed to initialize NVML: {}", e);
+                Err(e) => {
+                    warn!("Failed to initialize NVML: {}", e);
+                }
             }
         }
 
@@ -1984,12 +3175,23 @@ impl DevDashboard {
                     device_id: Option<String>,
                 }
 
-                if let Ok(results) = wmi_con.query::<Win32VideoController>() {
+                if let Ok(mut results) = wmi_con.query::<Win32VideoController>() {
+                    // Put the pinned or discrete adapter first
+                    if let Some(adapter) = preferred {
+                        results.sort_by_key(|gpu| !gpu.device_id.as_deref().is_some_and(|id| adapter.matches_pnp_id(id)));
+                    }
                     for gpu in results {
                         if !gpu.name.to_lowercase().contains("microsoft basic display") {
+                            let adapter = gpu.device_id.as_deref()
+                                .and_then(|id| adapters.iter().find(|adapter| adapter.matches_pnp_id(id)));
                             let mut gpu_info = GpuInfo::new(gpu.name);
-                            gpu_info.memory_total = gpu.adapter_ram;
+                            // AdapterRAM stops at 4 GB, so DXGI's figure is used when there is one
+                            gpu_info.memory_total = adapter.map(|adapter| adapter.dedicated_memory).filter(|&memory| memory > 0).or(gpu.adapter_ram);
                             gpu_info.driver_version = gpu.driver_version;
+                            if adapters.len() > 1 {
+                                gpu_info.luid = adapter.map(GpuAdapter::luid_tag);
+                            }
+                            gpu_info.sensor_luid = adapter.map(|adapter| adapter.luid);
                             
                             if let Some(device_id) = gpu.device_id {
                                 if device_id.starts_with("PCI\\") {
@@ -2022,42 +3224,113 @@ impl DevDashboard {
     /// Updates GPU information including usage, temperature, and memory usage
     /// Uses either NVML or WMI depending on GPU type
     fn update_gpu_info(&mut self) {
+        #[cfg(feature = "nvml")]
+        let mut nvml_error = None;
         if let Some(gpu_info) = &mut self.gpu_info {
-            if let Some(nvml) = &self.nvml {
-                if let Ok(device) = nvml.device_by_index(0) {
-                    if let Ok(memory) = device.memory_info() {
-                        gpu_info.memory_total = Some(memory.total);
-                        gpu_info.memory_used = Some(memory.used);
-                        gpu_info.memory_usage.set_target((memory.used as f32 / memory.total as f32).min(1.0));
-                    }
+            #[cfg(feature = "nvml")]
+            if let (Some(nvml), Some(index)) = (&self.nvml, gpu_info.nvml_index) {
+                match nvml.device_by_index(index) {
+                    Ok(device) => {
+                        if let Ok(memory) = device.memory_info() {
+                            gpu_info.memory_total = Some(memory.total);
+                            gpu_info.memory_used = Some(memory.used);
+                            gpu_info.memory_usage.set_target((memory.used as f32 / memory.total as f32).min(1.0));
+                        }
 
-                    if let Ok(utilization) = device.utilization_rates() {
-                        gpu_info.utilization = Some(utilization.gpu as f32);
-                        gpu_info.gpu_usage.set_target((utilization.gpu as f32 / 100.0).min(1.0));
+                        if let Ok(utilization) = device.utilization_rates() {
+                            gpu_info.utilization = Some(utilization.gpu as f32);
+                            gpu_info.gpu_usage.set_target((utilization.gpu as f32 / 100.0).min(1.0));
+                        }
+
+                        gpu_info.power_draw = device.power_usage().ok().map(|milliwatts| milliwatts as f32 / 1000.0);
+                        gpu_info.temperature = device.temperature(TemperatureSensor::Gpu).ok().or(gpu_info.temperature);
+                        gpu_info.graphics_clock = device.clock_info(Clock::Graphics).ok();
+                        gpu_info.memory_clock = device.clock_info(Clock::Memory).ok();
+                        gpu_info.limits = gpu_limits::read_limits(&device);
+                        let compute = gpu_compute::read_compute(&device);
+                        if compute.retired_pages() > gpu_info.compute.retired_pages() && gpu_info.compute.is_supported() {
+                            warn!("{} retired memory pages: {:?}", gpu_info.name, compute.retired_pages());
+                        }
+                        gpu_info.compute = compute;
+                        return;
                     }
+                    // A driver update leaves the handle dead; WMI stands in until NVML is initialized again
+                    Err(e) => nvml_error = Some(e.to_string()),
                 }
-            } else {
-                // Fallback to WMI for non-NVIDIA GPUs
-                if let Ok(com_con) = COMLibrary::new() {
-                    if let Ok(wmi_con) = WMIConnection::new(com_con) {
-                        #[derive(serde::Deserialize)]
-                        #[serde(rename = "Win32_PerfFormattedData_GPUPerformanceCounters_GPUEngine")]
-                        struct GpuPerformance {
-                            #[serde(rename = "UtilizationPercentage")]
-                            utilization: Option<u32>,
+            }
+
+            // Fallback to WMI for non-NVIDIA GPUs
+            if let Ok(com_con) = COMLibrary::new() {
+                if let Ok(wmi_con) = WMIConnection::new(com_con) {
+                    #[derive(serde::Deserialize)]
+                    #[serde(rename = "Win32_PerfFormattedData_GPUPerformanceCounters_GPUEngine")]
+                    struct GpuPerformance {
+                        #[serde(rename = "Name")]
+                        name: String,
+                        #[serde(rename = "UtilizationPercentage")]
+                        utilization: Option<u32>,
+                    }
+
+                    #[derive(serde::Deserialize)]
+                    #[serde(rename = "Win32_PerfFormattedData_GPUPerformanceCounters_GPUAdapterMemory")]
+                    struct GpuAdapterMemory {
+                        #[serde(rename = "Name")]
+                        name: String,
+                        #[serde(rename = "DedicatedUsage")]
+                        dedicated_usage: Option<u64>,
+                        #[serde(rename = "SharedUsage")]
+                        shared_usage: Option<u64>,
+                    }
+
+                    // Instance names carry the adapter's LUID; on hybrid systems only the shown GPU counts
+                    let luid = gpu_info.luid.clone();
+                    let on_adapter = |name: &str| match &luid {
+                        Some(luid) => name.to_lowercase().contains(luid.as_str()),
+                        None => true,
+                    };
+
+                    // There is one instance per process and engine; the busiest 3D engine matches Task Manager
+                    if let Ok(results) = wmi_con.query::<GpuPerformance>() {
+                        let util = results.iter()
+                            .filter(|perf| perf.name.ends_with("engtype_3D") && on_adapter(&perf.name))
+                            .filter_map(|perf| perf.utilization)
+                            .max();
+                        if let Some(util) = util {
+                            gpu_info.utilization = Some(util as f32);
+                            gpu_info.gpu_usage.set_target((util as f32 / 100.0).min(1.0));
                         }
+                    }
 
-                        if let Ok(results) = wmi_con.query::<GpuPerformance>() {
-                            if let Some(perf) = results.into_iter().next() {
-                                if let Some(util) = perf.utilization {
-                                    gpu_info.utilization = Some(util as f32);
-                                    gpu_info.gpu_usage.set_target((util as f32 / 100.0).min(1.0));
-                                }
+                    // Integrated GPUs, such as those on ARM64 devices, only have shared memory
+                    if let Ok(results) = wmi_con.query::<GpuAdapterMemory>() {
+                        let used = results.iter()
+                            .filter(|memory| on_adapter(&memory.name))
+                            .map(|memory| match memory.dedicated_usage {
+                                Some(dedicated) if dedicated > 0 => dedicated,
+                                _ => memory.shared_usage.unwrap_or(0),
+                            })
+                            .max();
+                        if let (Some(used), Some(total)) = (used, gpu_info.memory_total) {
+                            if total > 0 {
+                                gpu_info.memory_used = Some(used);
+                                gpu_info.memory_usage.set_target((used as f32 / total as f32).min(1.0));
                             }
                         }
                     }
                 }
             }
+
+            // WMI has no temperature or clocks; AMD and Intel drivers report them to the graphics kernel
+            if let Some(sensors) = gpu_info.sensor_luid.and_then(d3dkmt::read) {
+                gpu_info.temperature = sensors.temperature;
+                gpu_info.graphics_clock = sensors.graphics_clock;
+                gpu_info.memory_clock = sensors.memory_clock;
+            }
+        }
+        #[cfg(feature = "nvml")]
+        if let Some(error) = nvml_error {
+            self.nvml = None;
+            self.sensor_backends.nvml.failed("NVML", error);
         }
     }
 }
@@ -2065,6 +3338,25 @@ impl DevDashboard {
 /// Main entry point of the application
 /// Sets up logging and initializes the GUI
 fn main() -> Result<(), eframe::Error> {
+    // The elevated helper shares this executable; it must not touch the dashboard's log or window
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some(elevation::HELPER_FLAG) {
+        elevation::run_helper(&args[2..]);
+        return Ok(());
+    }
+
+    // A profile keeps its own settings, history and log, so it is switched to before anything is read
+    let (startup, problems) = StartupOptions::parse(&args[1..]);
+    let profile_error = match startup.profile_folder() {
+        Some(folder) => std::fs::create_dir_all(&folder).and_then(|_| std::env::set_current_dir(&folder)).err().map(|e| e.to_string()),
+        None if startup.profile.is_some() => Some("not a valid folder name".to_string()),
+        None => None,
+    };
+    // A second start hands its flags to the running dashboard instead of fighting it over settings and the installer file
+    let Some(instance) = instance::claim(startup.profile_folder().and(startup.profile.as_deref()), &args[1..]) else {
+        return Ok(());
+    };
+
     // Initialize logging to file
     let log_file = OpenOptions::new()
         .create(true)
@@ -2078,8 +3370,18 @@ fn main() -> Result<(), eframe::Error> {
         Config::default(),
         log_file,
     ).expect("Failed to initialize logger");
+    crash::install();
 
     info!("Starting Dev Dashboard");
+    for problem in &problems {
+        warn!("Command line: {}", problem);
+    }
+    if let Some(profile) = &startup.profile {
+        match &profile_error {
+            Some(e) => warn!("Could not use profile {:?}: {}", profile, e),
+            None => info!("Using profile {:?}", profile),
+        }
+    }
 
     // Configure window options
     let options = eframe::NativeOptions {
@@ -2094,7 +3396,12 @@ fn main() -> Result<(), eframe::Error> {
     let result = eframe::run_native(
         "Dev Dashboard",
         options,
-        Box::new(|_cc| Box::new(DevDashboard::default())),
+        Box::new(|_cc| {
+            let mut app = DevDashboard::default();
+            app.startup = Startup::new(startup);
+            app.instance = instance;
+            Box::new(app)
+        }),
     );
 
     if let Err(ref e) = result {
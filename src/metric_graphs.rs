@@ -0,0 +1,174 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use chrono::Local;
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use crate::history_compare::{self, ArchiveMetric};
+use crate::DevDashboard;
+
+/// The longest range a card graph can show
+const HISTORY_SPAN: Duration = Duration::from_secs(60 * 60);
+/// Graph ranges a card can switch between, in minutes
+const RANGES: [u64; 3] = [5, 15, 60];
+const GRAPH_HEIGHT: f32 = 56.0;
+/// Samples further apart than this were throttled or paused; the line breaks between them
+const MAX_GAP: Duration = Duration::from_secs(15);
+
+/// Persisted card graph preference
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct GraphSettings {
+    pub show_graphs: bool,
+}
+
+impl Default for GraphSettings {
+    fn default() -> Self {
+        Self { show_graphs: true }
+    }
+}
+
+/// Metrics with a history graph on their card
+#[derive(Clone, Copy)]
+pub enum GraphMetric {
+    Cpu,
+    Memory,
+    Gpu,
+}
+
+impl GraphMetric {
+    /// The same reading in the week-long minute averages
+    fn archive_metric(&self) -> ArchiveMetric {
+        match self {
+            GraphMetric::Cpu => ArchiveMetric::CpuUsage,
+            GraphMetric::Memory => ArchiveMetric::MemoryUsage,
+            GraphMetric::Gpu => ArchiveMetric::GpuUsage,
+        }
+    }
+}
+
+/// Timestamped readings for the last hour, oldest first
+#[derive(Default)]
+struct Series {
+    samples: VecDeque<(Instant, f32)>,
+}
+
+impl Series {
+    fn push(&mut self, now: Instant, value: f32) {
+        while self.samples.front().is_some_and(|(time, _)| now.duration_since(*time) > HISTORY_SPAN) {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((now, value));
+    }
+}
+
+/// Last hour of CPU, memory and GPU usage for the card graphs
+#[derive(Default)]
+pub struct MetricGraphs {
+    cpu: Series,                     // Percent
+    memory: Series,                  // Percent
+    gpu: Series,                     // Percent; empty without a GPU reading
+}
+
+impl DevDashboard {
+    /// Appends the current readings, once per update tick
+    pub(crate) fn record_metric_graphs(&mut self) {
+        let now = Instant::now();
+        let graphs = &mut self.metric_graphs;
+        graphs.cpu.push(now, self.current_cpu_usage.target);
+        graphs.memory.push(now, self.memory_usage.target * 100.0);
+        if let Some(utilization) = self.gpu_info.as_ref().and_then(|gpu| gpu.utilization) {
+            graphs.gpu.push(now, utilization);
+        }
+    }
+
+    /// Usage graph for a card with a 5/15/60 minute range switch
+    pub(crate) fn show_metric_graph(&self, ui: &mut egui::Ui, metric: GraphMetric, color: Color32) {
+        if !self.settings.graphs.show_graphs {
+            return;
+        }
+        let (series, key) = match metric {
+            GraphMetric::Cpu => (&self.metric_graphs.cpu, "cpu"),
+            GraphMetric::Memory => (&self.metric_graphs.memory, "memory"),
+            GraphMetric::Gpu => (&self.metric_graphs.gpu, "gpu"),
+        };
+        if series.samples.len() < 2 {
+            return;
+        }
+
+        // The range is per card and kept in egui's memory, since cards only borrow the dashboard
+        let id = egui::Id::new(("metric_graph_range", key));
+        let mut minutes = ui.data_mut(|data| *data.get_temp_mut_or(id, RANGES[0]));
+        ui.add_space(4.0);
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("History").color(Color32::from_rgb(156, 163, 175)));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.small_button("⇆").on_hover_text("Compare with yesterday or last week").clicked() {
+                    history_compare::request_compare(ui, metric.archive_metric());
+                }
+                for range in RANGES.iter().rev() {
+                    ui.selectable_value(&mut minutes, *range, format!("{}m", range));
+                }
+            });
+        });
+        ui.data_mut(|data| data.insert_temp(id, minutes));
+
+        let span = Duration::from_secs(minutes * 60);
+        let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), GRAPH_HEIGHT), egui::Sense::hover());
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 4.0, Color32::from_rgb(17, 24, 39));
+
+        // Time runs left to right with now at the right edge; usage is always 0-100%
+        let now = Instant::now();
+        let point = |time: Instant, value: f32| egui::pos2(
+            rect.right() - now.duration_since(time).as_secs_f32() / span.as_secs_f32() * rect.width(),
+            rect.bottom() - (value / 100.0).clamp(0.0, 1.0) * rect.height(),
+        );
+        let visible: Vec<&(Instant, f32)> = series.samples.iter()
+            .filter(|(time, _)| now.duration_since(*time) <= span + MAX_GAP)
+            .collect();
+        let mut line: Vec<egui::Pos2> = Vec::new();
+        let mut previous: Option<Instant> = None;
+        for (time, value) in &visible {
+            if previous.is_some_and(|previous| time.duration_since(previous) > MAX_GAP) {
+                if line.len() > 1 {
+                    painter.add(egui::Shape::line(std::mem::take(&mut line), egui::Stroke::new(1.5, color)));
+                }
+                line.clear();
+            }
+            line.push(point(*time, *value));
+            previous = Some(*time);
+        }
+        if line.len() > 1 {
+            painter.add(egui::Shape::line(line, egui::Stroke::new(1.5, color)));
+        }
+
+        let grey = Color32::from_rgb(156, 163, 175);
+        let font = egui::FontId::proportional(10.0);
+        let peak = visible.iter().map(|(_, value)| *value).fold(0.0, f32::max);
+        painter.text(rect.right_top() + egui::vec2(-4.0, 2.0), egui::Align2::RIGHT_TOP, format!("peak {:.0}%", peak), font.clone(), grey);
+
+        if let Some(pos) = response.hover_pos() {
+            let ago = Duration::from_secs_f32(((rect.right() - pos.x) / rect.width()).max(0.0) * span.as_secs_f32());
+            let nearest = visible.iter().min_by_key(|(time, _)| {
+                let age = now.duration_since(*time);
+                age.saturating_sub(ago) + ago.saturating_sub(age)
+            });
+            if let Some((time, value)) = nearest {
+                let at = point(*time, *value);
+                painter.line_segment([egui::pos2(at.x, rect.top()), egui::pos2(at.x, rect.bottom())], egui::Stroke::new(1.0, grey));
+                painter.circle_filled(at, 3.0, color);
+                let clock = Local::now() - chrono::Duration::from_std(now.duration_since(*time)).unwrap_or_else(|_| chrono::Duration::zero());
+                painter.text(rect.left_top() + egui::vec2(4.0, 2.0), egui::Align2::LEFT_TOP,
+                    format!("{}  {:.1}%", clock.format("%H:%M:%S"), value), font, Color32::from_rgb(243, 244, 246));
+            }
+        }
+    }
+
+    /// Settings section for the card graphs
+    pub(crate) fn show_graph_settings(&mut self, ui: &mut egui::Ui) {
+        if ui.checkbox(&mut self.settings.graphs.show_graphs, "Show usage history graphs on the CPU, memory and GPU cards").changed() {
+            self.save_settings();
+        }
+    }
+}
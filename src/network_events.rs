@@ -0,0 +1,440 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::net::Ipv4Addr;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Local};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use windows::Win32::Foundation::{ERROR_BUFFER_OVERFLOW, HANDLE, NO_ERROR};
+use windows::Win32::NetworkManagement::IpHelper::{
+    GetAdaptersAddresses, GetAdaptersInfo, GAA_FLAG_SKIP_ANYCAST, GAA_FLAG_SKIP_DNS_SERVER, GAA_FLAG_SKIP_MULTICAST,
+    IF_TYPE_ETHERNET_CSMACD, IF_TYPE_IEEE80211, IP_ADAPTER_ADDRESSES_LH, IP_ADAPTER_INFO,
+};
+use windows::Win32::NetworkManagement::Ndis::IfOperStatusUp;
+use windows::Win32::NetworkManagement::WiFi::{
+    wlan_interface_state_connected, wlan_intf_opcode_current_connection, WlanCloseHandle, WlanEnumInterfaces,
+    WlanFreeMemory, WlanOpenHandle, WlanQueryInterface, WLAN_CONNECTION_ATTRIBUTES, WLAN_INTERFACE_INFO_LIST,
+};
+use windows::Win32::Networking::WinSock::{AF_INET, SOCKADDR_IN};
+use log::{info, warn};
+use crate::{storage, DevDashboard};
+
+/// Connection events are stored next to settings.json
+const EVENTS_FILE: &str = "network_events.json";
+const MAX_EVENTS: usize = 1000;
+const CHECK_INTERVAL: Duration = Duration::from_secs(2);
+const TIMELINE_HOURS: i64 = 24;
+/// Client version for WlanOpenHandle on Vista and later
+const WLAN_CLIENT_VERSION: u32 = 2;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum NetworkEventKind {
+    Up,
+    Down,
+    AddressChanged,
+    DhcpRenewed,
+    WifiConnected,
+    WifiDisconnected,
+    WifiRoamed,
+}
+
+impl NetworkEventKind {
+    fn label(&self) -> &'static str {
+        match self {
+            NetworkEventKind::Up => "Connected",
+            NetworkEventKind::Down => "Disconnected",
+            NetworkEventKind::AddressChanged => "Address changed",
+            NetworkEventKind::DhcpRenewed => "DHCP renewed",
+            NetworkEventKind::WifiConnected => "Joined Wi-Fi",
+            NetworkEventKind::WifiDisconnected => "Left Wi-Fi",
+            NetworkEventKind::WifiRoamed => "Wi-Fi roam",
+        }
+    }
+
+    fn color(&self) -> Color32 {
+        match self {
+            NetworkEventKind::Up | NetworkEventKind::WifiConnected => Color32::from_rgb(22, 163, 74),
+            NetworkEventKind::Down | NetworkEventKind::WifiDisconnected => Color32::from_rgb(220, 50, 50),
+            NetworkEventKind::AddressChanged | NetworkEventKind::WifiRoamed => Color32::from_rgb(202, 138, 4),
+            NetworkEventKind::DhcpRenewed => Color32::from_rgb(88, 165, 237),
+        }
+    }
+}
+
+/// Something that happened to a connection, for lining up with dropped calls
+#[derive(Serialize, Deserialize, Clone)]
+pub struct NetworkEvent {
+    pub time: DateTime<Local>,
+    pub kind: NetworkEventKind,
+    pub interface: String,
+    pub detail: String,
+}
+
+/// One Ethernet or Wi-Fi adapter at a point in time
+#[derive(Clone, PartialEq)]
+struct AdapterState {
+    name: String,                   // Friendly name such as "Wi-Fi" or "Ethernet 2"
+    up: bool,
+    addresses: Vec<String>,         // IPv4, sorted
+    lease_obtained: Option<i64>,    // Unix time of the current DHCP lease
+    dhcp_server: Option<String>,
+}
+
+#[derive(Clone, PartialEq)]
+struct WifiState {
+    ssid: String,
+    bssid: [u8; 6],
+    signal: u32,                    // Percent
+}
+
+/// Adapters and Wi-Fi associations keyed by interface GUID, e.g. "{4D36E972-...}"
+#[derive(Default)]
+struct NetworkSnapshot {
+    adapters: BTreeMap<String, AdapterState>,
+    wifi: BTreeMap<String, WifiState>,
+}
+
+/// Connection event history and the state it is compared against
+#[derive(Default)]
+pub struct NetworkEvents {
+    events: VecDeque<NetworkEvent>,     // Newest first
+    last: Option<NetworkSnapshot>,
+    receiver: Option<Receiver<NetworkSnapshot>>,
+    last_check: Option<Instant>,
+    pub open: bool,
+}
+
+fn c_string(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|byte| *byte == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).to_string()
+}
+
+fn format_bssid(bssid: &[u8; 6]) -> String {
+    bssid.iter().map(|byte| format!("{:02x}", byte)).collect::<Vec<_>>().join(":")
+}
+
+/// Status and IPv4 addresses of every Ethernet and Wi-Fi adapter
+fn read_adapters() -> BTreeMap<String, AdapterState> {
+    let mut adapters = BTreeMap::new();
+    let flags = GAA_FLAG_SKIP_ANYCAST | GAA_FLAG_SKIP_MULTICAST | GAA_FLAG_SKIP_DNS_SERVER;
+    let mut size = 16 * 1024u32;
+    // u64s keep the linked structures correctly aligned
+    let mut buffer = Vec::new();
+    for _ in 0..4 {
+        buffer = vec![0u64; size as usize / 8 + 1];
+        let result = unsafe {
+            GetAdaptersAddresses(AF_INET.0 as u32, flags, None, Some(buffer.as_mut_ptr() as *mut IP_ADAPTER_ADDRESSES_LH), &mut size)
+        };
+        if result == NO_ERROR.0 {
+            break;
+        }
+        if result != ERROR_BUFFER_OVERFLOW.0 {
+            warn!("Failed to read network adapters: error {}", result);
+            return adapters;
+        }
+    }
+
+    let mut adapter = buffer.as_ptr() as *const IP_ADAPTER_ADDRESSES_LH;
+    while !adapter.is_null() {
+        let current = unsafe { &*adapter };
+        adapter = current.Next;
+        if current.IfType != IF_TYPE_ETHERNET_CSMACD && current.IfType != IF_TYPE_IEEE80211 {
+            continue;
+        }
+        let Ok(guid) = (unsafe { current.AdapterName.to_string() }) else { continue };
+        let name = unsafe { current.FriendlyName.to_string() }.unwrap_or_else(|_| guid.clone());
+
+        let mut addresses = Vec::new();
+        let mut unicast = current.FirstUnicastAddress;
+        while !unicast.is_null() {
+            let address = unsafe { &*unicast };
+            unicast = address.Next;
+            let socket = address.Address.lpSockaddr;
+            if socket.is_null() || unsafe { (*socket).sa_family } != AF_INET {
+                continue;
+            }
+            let socket = unsafe { &*(socket as *const SOCKADDR_IN) };
+            addresses.push(Ipv4Addr::from(unsafe { socket.sin_addr.S_un.S_addr }.to_ne_bytes()).to_string());
+        }
+        addresses.sort();
+
+        adapters.insert(guid, AdapterState {
+            name,
+            up: current.OperStatus == IfOperStatusUp,
+            addresses,
+            lease_obtained: None,
+            dhcp_server: None,
+        });
+    }
+
+    // The lease start time is only reported by the older adapter API
+    let mut size = 0u32;
+    unsafe { GetAdaptersInfo(None, &mut size) };
+    if size > 0 {
+        let mut buffer = vec![0u64; size as usize / 8 + 1];
+        let info = buffer.as_mut_ptr() as *mut IP_ADAPTER_INFO;
+        if unsafe { GetAdaptersInfo(Some(info), &mut size) } == NO_ERROR.0 {
+            let mut info = info as *const IP_ADAPTER_INFO;
+            while !info.is_null() {
+                let current = unsafe { &*info };
+                info = current.Next;
+                if current.DhcpEnabled == 0 {
+                    continue;
+                }
+                if let Some(state) = adapters.get_mut(&c_string(&current.AdapterName)) {
+                    state.lease_obtained = Some(current.LeaseObtained).filter(|obtained| *obtained > 0);
+                    state.dhcp_server = Some(c_string(&current.DhcpServer.IpAddress.String)).filter(|server| !server.is_empty());
+                }
+            }
+        }
+    }
+    adapters
+}
+
+/// Network name, access point and signal of every connected Wi-Fi interface
+fn read_wifi() -> BTreeMap<String, WifiState> {
+    let mut wifi = BTreeMap::new();
+    let mut version = 0u32;
+    let mut handle = HANDLE::default();
+    // Fails when the WLAN service isn't running, as on most desktops
+    if unsafe { WlanOpenHandle(WLAN_CLIENT_VERSION, None, &mut version, &mut handle) } != NO_ERROR.0 {
+        return wifi;
+    }
+
+    let mut list: *mut WLAN_INTERFACE_INFO_LIST = std::ptr::null_mut();
+    if unsafe { WlanEnumInterfaces(handle, None, &mut list) } == NO_ERROR.0 && !list.is_null() {
+        let interfaces = unsafe {
+            std::slice::from_raw_parts((*list).InterfaceInfo.as_ptr(), (*list).dwNumberOfItems as usize)
+        };
+        for interface in interfaces {
+            if interface.isState != wlan_interface_state_connected {
+                continue;
+            }
+            let mut size = 0u32;
+            let mut data: *mut core::ffi::c_void = std::ptr::null_mut();
+            let result = unsafe {
+                WlanQueryInterface(handle, &interface.InterfaceGuid, wlan_intf_opcode_current_connection, None, &mut size, &mut data, None)
+            };
+            if result != NO_ERROR.0 || data.is_null() {
+                continue;
+            }
+            let association = unsafe { (*(data as *const WLAN_CONNECTION_ATTRIBUTES)).wlanAssociationAttributes };
+            let length = (association.dot11Ssid.uSSIDLength as usize).min(association.dot11Ssid.ucSSID.len());
+            wifi.insert(format!("{{{:?}}}", interface.InterfaceGuid), WifiState {
+                ssid: String::from_utf8_lossy(&association.dot11Ssid.ucSSID[..length]).to_string(),
+                bssid: association.dot11Bssid,
+                signal: association.wlanSignalQuality,
+            });
+            unsafe { WlanFreeMemory(data) };
+        }
+        unsafe { WlanFreeMemory(list as *const _) };
+    }
+    unsafe { WlanCloseHandle(handle, None) };
+    wifi
+}
+
+/// Events that explain how the network got from one snapshot to the next
+fn compare(previous: &NetworkSnapshot, current: &NetworkSnapshot) -> Vec<NetworkEvent> {
+    let mut events = Vec::new();
+    let mut push = |kind, interface: &str, detail: String| {
+        events.push(NetworkEvent { time: Local::now(), kind, interface: interface.to_string(), detail });
+    };
+
+    for (guid, now) in &current.adapters {
+        let addresses = if now.addresses.is_empty() { "no address".to_string() } else { now.addresses.join(", ") };
+        match previous.adapters.get(guid) {
+            None if now.up => push(NetworkEventKind::Up, &now.name, format!("Adapter appeared, {}", addresses)),
+            None => {}
+            Some(before) if before.up != now.up => {
+                if now.up {
+                    push(NetworkEventKind::Up, &now.name, addresses);
+                } else {
+                    push(NetworkEventKind::Down, &now.name, "Link lost".to_string());
+                }
+            }
+            Some(before) if now.up && before.addresses != now.addresses => {
+                let was = if before.addresses.is_empty() { "none".to_string() } else { before.addresses.join(", ") };
+                push(NetworkEventKind::AddressChanged, &now.name, format!("{} → {}", was, addresses));
+            }
+            Some(before) if now.up && before.lease_obtained != now.lease_obtained && now.lease_obtained.is_some() => {
+                let server = now.dhcp_server.as_deref().map(|server| format!(" from {}", server)).unwrap_or_default();
+                push(NetworkEventKind::DhcpRenewed, &now.name, format!("Lease for {}{}", addresses, server));
+            }
+            Some(_) => {}
+        }
+    }
+    for (guid, before) in &previous.adapters {
+        if before.up && !current.adapters.contains_key(guid) {
+            push(NetworkEventKind::Down, &before.name, "Adapter removed".to_string());
+        }
+    }
+
+    let adapter_name = |guid: &str| {
+        current.adapters.iter()
+            .chain(previous.adapters.iter())
+            .find(|(key, _)| key.eq_ignore_ascii_case(guid))
+            .map(|(_, adapter)| adapter.name.clone())
+            .unwrap_or_else(|| "Wi-Fi".to_string())
+    };
+    for (guid, now) in &current.wifi {
+        let name = adapter_name(guid);
+        match previous.wifi.get(guid) {
+            Some(before) if before.ssid == now.ssid && before.bssid != now.bssid => push(
+                NetworkEventKind::WifiRoamed,
+                &name,
+                format!("{}: {} ({}%) → {} ({}%)", now.ssid, format_bssid(&before.bssid), before.signal, format_bssid(&now.bssid), now.signal),
+            ),
+            Some(before) if before.ssid == now.ssid => {}
+            _ => push(
+                NetworkEventKind::WifiConnected,
+                &name,
+                format!("{} via {} ({}%)", now.ssid, format_bssid(&now.bssid), now.signal),
+            ),
+        }
+    }
+    for (guid, before) in &previous.wifi {
+        if !current.wifi.contains_key(guid) {
+            push(NetworkEventKind::WifiDisconnected, &adapter_name(guid), format!("{} (last signal {}%)", before.ssid, before.signal));
+        }
+    }
+    events
+}
+
+impl NetworkEvents {
+    pub fn load() -> Self {
+        let events = storage::load_json(EVENTS_FILE);
+        Self { events, ..Default::default() }
+    }
+
+    fn save(&self) {
+        storage::save_json(EVENTS_FILE, &self.events);
+    }
+
+    /// Reads adapters in the background and records what changed since the last reading
+    pub fn update(&mut self) {
+        if let Some(receiver) = &self.receiver {
+            let Ok(snapshot) = receiver.try_recv() else { return };
+            self.receiver = None;
+            // The first reading is only the starting point
+            if let Some(previous) = &self.last {
+                let events = compare(previous, &snapshot);
+                for event in &events {
+                    info!("Network: {} {} ({})", event.interface, event.kind.label(), event.detail);
+                }
+                if !events.is_empty() {
+                    for event in events {
+                        if self.events.len() == MAX_EVENTS {
+                            self.events.pop_back();
+                        }
+                        self.events.push_front(event);
+                    }
+                    self.save();
+                }
+            }
+            self.last = Some(snapshot);
+        }
+
+        let due = match self.last_check {
+            Some(last) => last.elapsed() >= CHECK_INTERVAL,
+            None => true,
+        };
+        if !due || self.receiver.is_some() {
+            return;
+        }
+        self.last_check = Some(Instant::now());
+        let (sender, receiver) = channel();
+        self.receiver = Some(receiver);
+        std::thread::spawn(move || {
+            let _ = sender.send(NetworkSnapshot { adapters: read_adapters(), wifi: read_wifi() });
+        });
+    }
+
+    /// Events of the last day as markers on a time axis
+    fn show_timeline(&self, ui: &mut egui::Ui) {
+        let now = Local::now();
+        let start = now - chrono::Duration::hours(TIMELINE_HOURS);
+        let span = (now - start).num_seconds() as f32;
+        let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), 36.0), egui::Sense::hover());
+        let painter = ui.painter();
+        painter.rect_filled(rect, 4.0, Color32::from_rgb(17, 24, 39));
+        let x = |time: DateTime<Local>| rect.left() + (time - start).num_seconds().max(0) as f32 / span * rect.width();
+
+        for hours in (0..TIMELINE_HOURS).step_by(6) {
+            let tick = x(start + chrono::Duration::hours(hours));
+            painter.line_segment([egui::pos2(tick, rect.bottom() - 4.0), egui::pos2(tick, rect.bottom())],
+                egui::Stroke::new(1.0, Color32::from_rgb(75, 85, 99)));
+        }
+        let mut hovered = Vec::new();
+        for event in self.events.iter().filter(|event| event.time >= start) {
+            let position = x(event.time);
+            painter.line_segment([egui::pos2(position, rect.top() + 6.0), egui::pos2(position, rect.bottom() - 6.0)],
+                egui::Stroke::new(2.0, event.kind.color()));
+            if response.hover_pos().is_some_and(|pointer| (pointer.x - position).abs() <= 3.0) {
+                hovered.push(event);
+            }
+        }
+        if !hovered.is_empty() {
+            let text: Vec<String> = hovered
+                .iter()
+                .map(|event| format!("{}  {} {}: {}", event.time.format("%H:%M:%S"), event.interface, event.kind.label(), event.detail))
+                .collect();
+            response.on_hover_text(text.join("\n"));
+        }
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(start.format("%H:%M").to_string()).color(Color32::from_rgb(107, 114, 128)));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.label(RichText::new("now").color(Color32::from_rgb(107, 114, 128)));
+            });
+        });
+    }
+}
+
+impl DevDashboard {
+    /// Window with the connection event timeline and list
+    pub(crate) fn show_network_events_window(&mut self, ctx: &egui::Context) {
+        if !self.network_events.open {
+            return;
+        }
+        let mut open = true;
+        let mut cleared = false;
+        let events = &self.network_events;
+        egui::Window::new("Network Events")
+            .open(&mut open)
+            .default_width(620.0)
+            .show(ctx, |ui| {
+                ui.label(RichText::new(format!("Last {} hours; hover a marker for details", TIMELINE_HOURS))
+                    .color(Color32::from_rgb(107, 114, 128)));
+                events.show_timeline(ui);
+                ui.add_space(8.0);
+
+                if events.events.is_empty() {
+                    ui.label(RichText::new("No connection changes recorded yet").color(Color32::from_rgb(107, 114, 128)));
+                    return;
+                }
+                egui::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                    egui::Grid::new("network_events_grid").striped(true).spacing([12.0, 4.0]).show(ui, |ui| {
+                        for event in events.events.iter().take(200) {
+                            ui.label(RichText::new(event.time.format("%m-%d %H:%M:%S").to_string())
+                                .color(Color32::from_rgb(156, 163, 175)));
+                            ui.label(&event.interface);
+                            ui.colored_label(event.kind.color(), event.kind.label());
+                            ui.label(&event.detail);
+                            ui.end_row();
+                        }
+                    });
+                });
+                ui.add_space(4.0);
+                if ui.small_button("Clear history").clicked() {
+                    cleared = true;
+                }
+            });
+        if cleared {
+            self.network_events.events.clear();
+            self.network_events.save();
+        }
+        if !open {
+            self.network_events.open = false;
+        }
+    }
+}
@@ -0,0 +1,397 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Local};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use log::{info, warn};
+use crate::notifications::NotificationSource;
+use crate::{toast, DevDashboard};
+
+/// A share that has not answered within this time is reported offline
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+/// Probes kept per share for the availability figure
+const HISTORY_LEN: usize = 60;
+
+/// Persisted list of UNC paths to watch
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct NetworkShareSettings {
+    pub shares: Vec<String>,    // \\server\share or a path inside it
+    pub interval_secs: u64,
+    pub slow_ms: u32,           // Warn when listing the share takes longer than this
+}
+
+impl Default for NetworkShareSettings {
+    fn default() -> Self {
+        Self {
+            shares: Vec::new(),
+            interval_secs: 60,
+            slow_ms: 250,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ShareHealth {
+    Ok,
+    Slow,
+    Offline,
+}
+
+/// Latest probe and recent history for one share
+pub struct ShareStatus {
+    latency_ms: Option<f32>,            // None while offline
+    error: Option<String>,
+    checked: DateTime<Local>,
+    history: VecDeque<Option<f32>>,     // Oldest first; None for failed probes
+    health: ShareHealth,
+}
+
+impl ShareStatus {
+    /// Percentage of recent probes that succeeded
+    fn availability(&self) -> f32 {
+        let up = self.history.iter().filter(|probe| probe.is_some()).count();
+        up as f32 / self.history.len().max(1) as f32 * 100.0
+    }
+
+    fn average_ms(&self) -> Option<f32> {
+        let latencies: Vec<f32> = self.history.iter().flatten().copied().collect();
+        if latencies.is_empty() {
+            return None;
+        }
+        Some(latencies.iter().sum::<f32>() / latencies.len() as f32)
+    }
+}
+
+/// Background share probes
+#[derive(Default)]
+pub struct ShareMonitor {
+    results: HashMap<String, ShareStatus>,
+    last_check: Option<Instant>,
+    sender: Option<Sender<(String, Result<f32, String>)>>,
+    receiver: Option<Receiver<(String, Result<f32, String>)>>,
+    pending: HashMap<String, Instant>,  // Probes in flight and when they started
+    new_share: String,                  // Text field in Settings
+}
+
+/// Lists the share, which needs a round trip to the server unlike a cached metadata lookup
+fn probe_share(path: &str) -> Result<f32, String> {
+    let start = Instant::now();
+    let mut entries = std::fs::read_dir(path).map_err(|e| e.to_string())?;
+    entries.next().transpose().map_err(|e| e.to_string())?;
+    Ok(start.elapsed().as_secs_f32() * 1000.0)
+}
+
+impl ShareMonitor {
+    pub fn is_checking(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Shares whose latest probe failed or was slow
+    pub fn unhealthy_count(&self) -> usize {
+        self.results.values().filter(|status| status.health != ShareHealth::Ok).count()
+    }
+
+    /// Probes every share that does not already have a probe in flight
+    pub fn start_check(&mut self, shares: &[String]) {
+        let sender = match &self.sender {
+            Some(sender) => sender.clone(),
+            None => {
+                let (sender, receiver) = channel();
+                self.sender = Some(sender.clone());
+                self.receiver = Some(receiver);
+                sender
+            }
+        };
+        self.last_check = Some(Instant::now());
+
+        for share in shares {
+            if self.pending.contains_key(share) {
+                continue;
+            }
+            self.pending.insert(share.clone(), Instant::now());
+            let sender = sender.clone();
+            let share = share.clone();
+            // One thread per share so an unreachable server cannot hold up the others
+            std::thread::spawn(move || {
+                let result = probe_share(&share);
+                sender.send((share, result)).ok();
+            });
+        }
+    }
+
+    /// Stores a probe result; returns a title and message when the share's health changed
+    fn record(&mut self, share: String, result: Result<f32, String>, slow_ms: u32) -> Option<(String, String)> {
+        let (latency_ms, error) = match result {
+            Ok(latency) => (Some(latency), None),
+            Err(error) => (None, Some(error)),
+        };
+        let health = match latency_ms {
+            Some(latency) if latency > slow_ms as f32 => ShareHealth::Slow,
+            Some(_) => ShareHealth::Ok,
+            None => ShareHealth::Offline,
+        };
+
+        let status = self.results.entry(share.clone()).or_insert_with(|| ShareStatus {
+            latency_ms: None,
+            error: None,
+            checked: Local::now(),
+            history: VecDeque::new(),
+            health: ShareHealth::Ok,
+        });
+        // Only report changes so a share that stays down does not repeat the alert every check
+        let mut change = None;
+        if health != status.health || status.history.is_empty() {
+            change = match (health, &error) {
+                (ShareHealth::Offline, Some(error)) => {
+                    warn!("Network share {} is offline: {}", share, error);
+                    Some((format!("Network share offline: {}", share), error.clone()))
+                }
+                (ShareHealth::Slow, _) => {
+                    let latency = latency_ms.unwrap_or_default();
+                    warn!("Network share {} is slow: {:.0} ms", share, latency);
+                    Some((format!("Network share slow: {}", share), format!("Answered in {:.0} ms (limit {} ms)", latency, slow_ms)))
+                }
+                (ShareHealth::Ok, _) if !status.history.is_empty() => {
+                    info!("Network share {} is responding again", share);
+                    Some((format!("Network share back: {}", share), "Responding normally again".to_string()))
+                }
+                _ => None,
+            };
+        }
+
+        if status.history.len() == HISTORY_LEN {
+            status.history.pop_front();
+        }
+        status.history.push_back(latency_ms);
+        status.latency_ms = latency_ms;
+        status.error = error;
+        status.checked = Local::now();
+        status.health = health;
+        change
+    }
+
+    /// Collects finished probes, times out hung ones and schedules the next run.
+    /// Returns a title and message for each share whose health changed.
+    pub fn update(&mut self, settings: &NetworkShareSettings) -> Vec<(String, String)> {
+        let mut changes = Vec::new();
+        let mut finished = Vec::new();
+        if let Some(receiver) = &self.receiver {
+            while let Ok(result) = receiver.try_recv() {
+                finished.push(result);
+            }
+        }
+        for (share, result) in finished {
+            // A probe that already timed out is dropped; its share has been marked offline
+            if self.pending.remove(&share).is_some() {
+                changes.extend(self.record(share, result, settings.slow_ms));
+            }
+        }
+
+        let timed_out: Vec<String> = self.pending
+            .iter()
+            .filter(|(_, started)| started.elapsed() >= PROBE_TIMEOUT)
+            .map(|(share, _)| share.clone())
+            .collect();
+        for share in timed_out {
+            self.pending.remove(&share);
+            let error = format!("No response after {}s", PROBE_TIMEOUT.as_secs());
+            changes.extend(self.record(share, Err(error), settings.slow_ms));
+        }
+
+        let due = match self.last_check {
+            Some(last) => last.elapsed() >= Duration::from_secs(settings.interval_secs.max(10)),
+            None => true,
+        };
+        if due && !settings.shares.is_empty() {
+            self.start_check(&settings.shares);
+        }
+        changes
+    }
+}
+
+impl DevDashboard {
+    /// Probes the shares and raises a toast and a notification when one goes slow, offline or comes back
+    pub(crate) fn update_network_shares(&mut self) {
+        for (title, body) in self.share_monitor.update(&self.settings.network_shares) {
+            toast::show_toast(&title, &body);
+            self.notifications.push(NotificationSource::Alert, &title, &body);
+        }
+    }
+
+    /// Displays the network share card
+    /// Shows latency and recent availability for each registered share
+    pub(crate) fn show_network_shares_card(&mut self, ui: &mut egui::Ui) {
+        let shares = self.settings.network_shares.shares.clone();
+        let monitor = &mut self.share_monitor;
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Network Shares").strong().heading());
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if monitor.is_checking() {
+                    ui.spinner();
+                } else if ui.small_button("⟳").on_hover_text("Check now").clicked() {
+                    monitor.start_check(&shares);
+                }
+            });
+        });
+        ui.add_space(8.0);
+
+        for share in &shares {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(share).monospace());
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let Some(status) = monitor.results.get(share) else {
+                        ui.label("Checking...");
+                        return;
+                    };
+                    let (text, color) = match (status.health, status.latency_ms) {
+                        (ShareHealth::Offline, _) | (_, None) => ("Offline".to_string(), Color32::from_rgb(220, 50, 50)),
+                        (ShareHealth::Slow, Some(latency)) => (format!("⚠ {:.0} ms", latency), Color32::from_rgb(202, 138, 4)),
+                        (ShareHealth::Ok, Some(latency)) => (format!("{:.0} ms", latency), Color32::from_rgb(22, 163, 74)),
+                    };
+                    let mut details = format!(
+                        "Checked {}\nAvailable {:.0}% of the last {} checks",
+                        status.checked.format("%H:%M:%S"),
+                        status.availability(),
+                        status.history.len()
+                    );
+                    if let Some(average) = status.average_ms() {
+                        details.push_str(&format!("\nAverage {:.0} ms", average));
+                    }
+                    if let Some(error) = &status.error {
+                        details.push_str(&format!("\n{}", error));
+                    }
+                    ui.colored_label(color, text).on_hover_text(details);
+                });
+            });
+        }
+    }
+
+    /// Latency and availability per share, for copying the card
+    pub(crate) fn network_shares_card_values(&self) -> Vec<(String, String)> {
+        self.settings.network_shares.shares
+            .iter()
+            .map(|share| {
+                let value = match self.share_monitor.results.get(share) {
+                    Some(status) => {
+                        let mut value = match (status.health, status.latency_ms) {
+                            (ShareHealth::Offline, _) | (_, None) => "Offline".to_string(),
+                            (ShareHealth::Slow, Some(latency)) => format!("{:.0} ms (slow)", latency),
+                            (ShareHealth::Ok, Some(latency)) => format!("{:.0} ms", latency),
+                        };
+                        value.push_str(&format!(", available {:.0}% of the last {} checks", status.availability(), status.history.len()));
+                        if let Some(error) = &status.error {
+                            value.push_str(&format!(", {}", error));
+                        }
+                        value
+                    }
+                    None => "Checking".to_string(),
+                };
+                (share.clone(), value)
+            })
+            .collect()
+    }
+
+    /// Settings section for managing monitored network shares
+    pub(crate) fn show_network_share_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+
+        ui.label("Network Shares:");
+        let mut removed = None;
+        for (index, share) in self.settings.network_shares.shares.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(share).monospace());
+                if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                    removed = Some(index);
+                }
+            });
+        }
+        if let Some(index) = removed {
+            let share = self.settings.network_shares.shares.remove(index);
+            self.share_monitor.results.remove(&share);
+            changed = true;
+        }
+
+        ui.horizontal(|ui| {
+            let response = ui.add(egui::TextEdit::singleline(&mut self.share_monitor.new_share)
+                .hint_text(r"\\server\builds")
+                .desired_width(180.0));
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if ui.button("Add").clicked() || submitted {
+                let share = self.share_monitor.new_share.trim().trim_end_matches('\\').to_string();
+                if share.starts_with(r"\\") && !self.settings.network_shares.shares.contains(&share) {
+                    info!("Monitoring network share {}", share);
+                    self.settings.network_shares.shares.push(share);
+                    // Probe the new share along with the others right away
+                    self.share_monitor.last_check = None;
+                    changed = true;
+                }
+                self.share_monitor.new_share.clear();
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Check every (s):");
+            changed |= ui.add(egui::DragValue::new(&mut self.settings.network_shares.interval_secs)
+                .clamp_range(10..=3600)).changed();
+            ui.label("Slow above (ms):");
+            changed |= ui.add(egui::DragValue::new(&mut self.settings.network_shares.slow_ms)
+                .clamp_range(10..=10_000)).changed();
+        });
+
+        if changed {
+            self.save_settings();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHARE: &str = r"\\nas\builds";
+
+    fn title(change: Option<(String, String)>) -> Option<String> {
+        change.map(|(title, _)| title)
+    }
+
+    #[test]
+    fn record_reports_only_health_changes() {
+        let mut monitor = ShareMonitor::default();
+        assert_eq!(title(monitor.record(SHARE.to_string(), Ok(12.0), 500)), None);
+        assert_eq!(title(monitor.record(SHARE.to_string(), Ok(15.0), 500)), None);
+        assert_eq!(
+            monitor.record(SHARE.to_string(), Err("The network path was not found".to_string()), 500),
+            Some((format!("Network share offline: {}", SHARE), "The network path was not found".to_string())),
+        );
+        assert_eq!(title(monitor.record(SHARE.to_string(), Err("The network path was not found".to_string()), 500)), None);
+        assert_eq!(monitor.unhealthy_count(), 1);
+        assert_eq!(title(monitor.record(SHARE.to_string(), Ok(900.0), 500)), Some(format!("Network share slow: {}", SHARE)));
+        assert_eq!(title(monitor.record(SHARE.to_string(), Ok(20.0), 500)), Some(format!("Network share back: {}", SHARE)));
+        assert_eq!(monitor.unhealthy_count(), 0);
+    }
+
+    #[test]
+    fn record_reports_a_share_that_is_down_from_the_start() {
+        let mut monitor = ShareMonitor::default();
+        assert_eq!(title(monitor.record(SHARE.to_string(), Err("Access is denied".to_string()), 500)), Some(format!("Network share offline: {}", SHARE)));
+    }
+
+    #[test]
+    fn record_keeps_a_bounded_history() {
+        let mut monitor = ShareMonitor::default();
+        for _ in 0..HISTORY_LEN {
+            monitor.record(SHARE.to_string(), Ok(10.0), 500);
+        }
+        for _ in 0..HISTORY_LEN / 2 {
+            monitor.record(SHARE.to_string(), Err("timed out".to_string()), 500);
+        }
+        let status = &monitor.results[SHARE];
+        assert_eq!(status.history.len(), HISTORY_LEN);
+        assert_eq!(status.availability(), 50.0);
+        assert_eq!(status.average_ms(), Some(10.0));
+    }
+}
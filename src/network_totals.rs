@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+use chrono::Local;
+use serde::{Serialize, Deserialize};
+use crate::storage;
+
+/// Per-interface traffic totals are stored next to settings.json
+const NETWORK_FILE: &str = "network.json";
+const SAVE_INTERVAL: Duration = Duration::from_secs(60);
+/// Daily totals older than this are dropped; the all-time totals are kept
+const RETENTION_DAYS: i64 = 90;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+pub struct Traffic {
+    pub received: u64,
+    pub sent: u64,
+}
+
+impl Traffic {
+    pub fn add(&mut self, received: u64, sent: u64) {
+        self.received = self.received.saturating_add(received);
+        self.sent = self.sent.saturating_add(sent);
+    }
+}
+
+impl std::iter::Sum for Traffic {
+    fn sum<I: Iterator<Item = Traffic>>(iter: I) -> Self {
+        iter.fold(Traffic::default(), |mut total, traffic| {
+            total.add(traffic.received, traffic.sent);
+            total
+        })
+    }
+}
+
+/// How the network card lays out the physical interfaces
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct NetworkCardSettings {
+    pub aggregate: bool,            // One combined figure for all interfaces, with a per-interface breakdown
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct TotalsFile {
+    days: BTreeMap<String, BTreeMap<String, Traffic>>,  // YYYY-MM-DD -> interface -> bytes
+    all_time: BTreeMap<String, Traffic>,
+}
+
+/// Bytes transferred per interface, accumulated across restarts
+#[derive(Default)]
+pub struct NetworkTotals {
+    totals: TotalsFile,
+    last_save: Option<Instant>,
+    dirty: bool,
+}
+
+fn today() -> String {
+    Local::now().format("%Y-%m-%d").to_string()
+}
+
+impl NetworkTotals {
+    pub fn load() -> Self {
+        let totals = storage::load_json(NETWORK_FILE);
+        Self { totals, ..Default::default() }
+    }
+
+    /// Adds bytes transferred since the previous sample
+    pub fn record(&mut self, interface: &str, received: u64, sent: u64) {
+        if received == 0 && sent == 0 {
+            return;
+        }
+        self.totals.days.entry(today()).or_default().entry(interface.to_string()).or_default().add(received, sent);
+        self.totals.all_time.entry(interface.to_string()).or_default().add(received, sent);
+        self.dirty = true;
+    }
+
+    pub fn today(&self, interface: &str) -> Traffic {
+        self.totals.days.get(&today()).and_then(|day| day.get(interface)).copied().unwrap_or_default()
+    }
+
+    /// All interfaces together on a day given as YYYY-MM-DD
+    pub fn day_total(&self, date: &str) -> Traffic {
+        self.totals.days.get(date).map(|day| day.values().copied().sum()).unwrap_or_default()
+    }
+
+    pub fn all_time(&self, interface: &str) -> Traffic {
+        self.totals.all_time.get(interface).copied().unwrap_or_default()
+    }
+
+    /// Drops old days and writes the totals to disk
+    pub fn save(&mut self) {
+        let cutoff = (Local::now().date_naive() - chrono::Duration::days(RETENTION_DAYS))
+            .format("%Y-%m-%d")
+            .to_string();
+        self.totals.days.retain(|day, _| *day >= cutoff);
+
+        storage::save_json(NETWORK_FILE, &self.totals);
+        self.dirty = false;
+        self.last_save = Some(Instant::now());
+    }
+
+    pub fn save_if_due(&mut self) {
+        let save_due = match self.last_save {
+            Some(last) => last.elapsed() >= SAVE_INTERVAL,
+            None => true,
+        };
+        if self.dirty && save_due {
+            self.save();
+        }
+    }
+}
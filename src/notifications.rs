@@ -0,0 +1,196 @@
+use std::collections::VecDeque;
+use chrono::{DateTime, Local};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use crate::{storage, DevDashboard};
+
+const NOTIFICATIONS_FILE: &str = "notifications.json";
+const MAX_NOTIFICATIONS: usize = 200;
+
+/// What raised a notification, for filtering the list
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum NotificationSource {
+    Alert,                          // Threshold rules and temperature trends
+    Installer,
+    Update,
+    Watcher,                        // Job watchers, watched processes and the watchdog
+    Script,
+}
+
+impl NotificationSource {
+    const ALL: [NotificationSource; 5] = [
+        NotificationSource::Alert,
+        NotificationSource::Installer,
+        NotificationSource::Update,
+        NotificationSource::Watcher,
+        NotificationSource::Script,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            NotificationSource::Alert => "Alerts",
+            NotificationSource::Installer => "Installer",
+            NotificationSource::Update => "Updates",
+            NotificationSource::Watcher => "Watchers",
+            NotificationSource::Script => "Scripts",
+        }
+    }
+
+    fn color(&self) -> Color32 {
+        match self {
+            NotificationSource::Alert => Color32::from_rgb(220, 50, 50),
+            NotificationSource::Installer => Color32::from_rgb(22, 163, 74),
+            NotificationSource::Update => Color32::from_rgb(202, 138, 4),
+            NotificationSource::Watcher => Color32::from_rgb(59, 130, 246),
+            NotificationSource::Script => Color32::from_rgb(147, 51, 234),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Notification {
+    pub time: DateTime<Local>,
+    pub source: NotificationSource,
+    pub title: String,
+    pub body: String,
+    pub read: bool,
+}
+
+/// Everything the dashboard reported, kept in the app regardless of whether a Windows notification was shown
+#[derive(Default)]
+pub struct NotificationCenter {
+    notifications: VecDeque<Notification>,  // Newest first
+    pub open: bool,
+    filter: Option<NotificationSource>,
+}
+
+impl NotificationCenter {
+    pub fn load() -> Self {
+        let notifications = storage::load_json(NOTIFICATIONS_FILE);
+        Self { notifications, ..Default::default() }
+    }
+
+    fn save(&self) {
+        storage::save_json(NOTIFICATIONS_FILE, &self.notifications);
+    }
+
+    /// Adds an unread notification; the oldest are dropped past the limit
+    pub fn push(&mut self, source: NotificationSource, title: &str, body: &str) {
+        if self.notifications.len() == MAX_NOTIFICATIONS {
+            self.notifications.pop_back();
+        }
+        self.notifications.push_front(Notification {
+            time: Local::now(),
+            source,
+            title: title.to_string(),
+            body: body.to_string(),
+            read: false,
+        });
+        self.save();
+    }
+
+    /// Like push, but skipped when the same notice is already listed, e.g. an update found again on the next start
+    pub fn push_once(&mut self, source: NotificationSource, title: &str, body: &str) {
+        if !self.notifications.iter().any(|notification| notification.source == source && notification.title == title) {
+            self.push(source, title, body);
+        }
+    }
+
+    pub fn unread(&self) -> usize {
+        self.notifications.iter().filter(|notification| !notification.read).count()
+    }
+}
+
+impl DevDashboard {
+    /// Bell in the header with the unread count; opens the notification center
+    pub(crate) fn show_notification_button(&mut self, ui: &mut egui::Ui) {
+        let unread = self.notifications.unread();
+        let text = if unread > 0 {
+            RichText::new(format!("🔔 {}", unread)).color(Color32::from_rgb(220, 50, 50))
+        } else {
+            RichText::new("🔔")
+        };
+        let hover = match unread {
+            0 => "Notifications".to_string(),
+            1 => "1 unread notification".to_string(),
+            count => format!("{} unread notifications", count),
+        };
+        if ui.button(text).on_hover_text(hover).clicked() {
+            self.notifications.open = !self.notifications.open;
+        }
+    }
+
+    pub(crate) fn show_notification_center(&mut self, ctx: &egui::Context) {
+        if !self.notifications.open {
+            return;
+        }
+        let grey = Color32::from_rgb(107, 114, 128);
+        let mut open = true;
+        let mut changed = false;
+        let center = &mut self.notifications;
+        egui::Window::new("Notifications")
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut center.filter, None, "All");
+                    for source in NotificationSource::ALL {
+                        ui.selectable_value(&mut center.filter, Some(source), source.label());
+                    }
+                });
+                ui.horizontal(|ui| {
+                    if ui.small_button("Mark All Read").clicked() {
+                        for notification in &mut center.notifications {
+                            changed |= !notification.read;
+                            notification.read = true;
+                        }
+                    }
+                    if ui.small_button("Clear").clicked() {
+                        let filter = center.filter;
+                        center.notifications.retain(|notification| filter.is_some_and(|source| source != notification.source));
+                        changed = true;
+                    }
+                });
+                ui.separator();
+
+                let filter = center.filter;
+                let mut shown = 0;
+                egui::ScrollArea::vertical().max_height(420.0).show(ui, |ui| {
+                    for notification in center.notifications.iter_mut()
+                        .filter(|notification| !filter.is_some_and(|source| source != notification.source)) {
+                        shown += 1;
+                        let response = ui.horizontal(|ui| {
+                            let dot = if notification.read { Color32::TRANSPARENT } else { notification.source.color() };
+                            ui.label(RichText::new("●").color(dot));
+                            ui.vertical(|ui| {
+                                ui.horizontal(|ui| {
+                                    let title = RichText::new(&notification.title).color(notification.source.color());
+                                    ui.label(if notification.read { title } else { title.strong() });
+                                    ui.label(RichText::new(notification.time.format("%m-%d %H:%M").to_string()).color(grey));
+                                });
+                                if !notification.body.is_empty() {
+                                    ui.label(&notification.body);
+                                }
+                            });
+                        }).response;
+                        // Clicking anywhere on an entry marks it read
+                        if !notification.read && ui.interact(response.rect, response.id.with("read"), egui::Sense::click()).clicked() {
+                            notification.read = true;
+                            changed = true;
+                        }
+                        ui.add_space(4.0);
+                    }
+                });
+                if shown == 0 {
+                    ui.label(RichText::new("Nothing here yet").color(grey));
+                }
+            });
+        if changed {
+            self.notifications.save();
+        }
+        if !open {
+            self.notifications.open = false;
+        }
+    }
+}
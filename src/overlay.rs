@@ -0,0 +1,126 @@
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use log::info;
+use crate::DevDashboard;
+
+const OVERLAY_WIDTH: f32 = 220.0;
+/// Strip above the graphs used to drag the frameless window
+const HANDLE_HEIGHT: f32 = 18.0;
+
+/// Persisted compact overlay state
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct OverlaySettings {
+    pub open: bool,                     // Reopened in compact mode on the next start
+    pub position: Option<[f32; 2]>,     // Top-left corner in points, None until first placed
+}
+
+/// Content height measured last frame, and whether the main window was collapsed for it
+#[derive(Default)]
+pub struct Overlay {
+    height: Option<f32>,
+    collapsed: bool,
+}
+
+impl DevDashboard {
+    /// Switches between the full dashboard and the compact overlay
+    pub(crate) fn toggle_overlay(&mut self, ctx: &egui::Context) {
+        self.settings.overlay.open = !self.settings.overlay.open;
+        info!("{} the compact overlay", if self.settings.overlay.open { "Opened" } else { "Closed" });
+        if !self.settings.overlay.open && self.overlay.collapsed {
+            self.overlay.collapsed = false;
+            ctx.send_viewport_cmd_to(egui::ViewportId::ROOT, egui::ViewportCommand::Minimized(false));
+            ctx.send_viewport_cmd_to(egui::ViewportId::ROOT, egui::ViewportCommand::Focus);
+        }
+        self.save_settings();
+    }
+
+    /// Header button that collapses the dashboard into the overlay
+    pub(crate) fn show_overlay_button(&mut self, ui: &mut egui::Ui) {
+        if ui.button("▭").on_hover_text("Compact overlay: CPU, memory, GPU and network graphs in a small always-on-top window").clicked() {
+            self.toggle_overlay(ui.ctx());
+        }
+    }
+
+    /// Small frameless always-on-top window of sparklines, shown while the main window is minimized
+    pub(crate) fn show_overlay(&mut self, ctx: &egui::Context) {
+        if !self.settings.overlay.open {
+            return;
+        }
+        if !self.overlay.collapsed {
+            self.overlay.collapsed = true;
+            ctx.send_viewport_cmd_to(egui::ViewportId::ROOT, egui::ViewportCommand::Minimized(true));
+        }
+        let height = self.overlay.height.unwrap_or(250.0);
+        let mut builder = egui::ViewportBuilder::default()
+            .with_title("Dev Dashboard - Overlay")
+            .with_decorations(false)
+            .with_resizable(false)
+            .with_always_on_top()
+            .with_inner_size([OVERLAY_WIDTH, height]);
+        if let Some([x, y]) = self.settings.overlay.position {
+            builder = builder.with_position([x, y]);
+        }
+
+        let id = egui::ViewportId::from_hash_of("compact_overlay");
+        let mut expand = false;
+        let mut moved = None;
+        ctx.show_viewport_immediate(id, builder, |ctx, _class| {
+            egui::CentralPanel::default()
+                .frame(egui::Frame::none()
+                    .fill(self.palette().panel)
+                    .inner_margin(egui::style::Margin::symmetric(10.0, 6.0)))
+                .show(ctx, |ui| {
+                    let (handle, _) = ui.allocate_exact_size(egui::vec2(ui.available_width(), HANDLE_HEIGHT), egui::Sense::hover());
+                    let drag = ui.interact(handle, id.0.with("handle"), egui::Sense::click_and_drag());
+                    if drag.drag_started() {
+                        ctx.send_viewport_cmd(egui::ViewportCommand::StartDrag);
+                    }
+                    if drag.double_clicked() {
+                        expand = true;
+                    }
+                    ui.painter().text(handle.left_center(), egui::Align2::LEFT_CENTER, "⠿ Dev Dashboard",
+                        egui::FontId::proportional(12.0), Color32::from_rgb(107, 114, 128));
+                    let back = egui::Rect::from_min_size(handle.right_top() - egui::vec2(HANDLE_HEIGHT, 0.0), egui::vec2(HANDLE_HEIGHT, HANDLE_HEIGHT));
+                    if ui.put(back, egui::Button::new("⤢").frame(false)).on_hover_text("Back to the full dashboard").clicked() {
+                        expand = true;
+                    }
+
+                    let top = ui.cursor().top();
+                    self.show_metric_sparklines(ui);
+                    if self.monitoring_paused() {
+                        ui.label(RichText::new("Monitoring paused").small().color(Color32::from_rgb(202, 138, 4)));
+                    }
+                    let content = ui.cursor().top() - top + HANDLE_HEIGHT + 16.0;
+                    if !self.overlay.height.is_some_and(|height| (content - height).abs() <= 2.0) {
+                        self.overlay.height = Some(content.max(60.0));
+                    }
+                });
+
+            // Remember where the window was dragged once the button is released
+            let (outer, dragging) = ctx.input(|i| (i.viewport().outer_rect, i.pointer.any_down()));
+            if let Some(outer) = outer {
+                let position = [outer.min.x, outer.min.y];
+                let changed = match self.settings.overlay.position {
+                    Some([x, y]) => (x - position[0]).abs() > 1.0 || (y - position[1]).abs() > 1.0,
+                    None => true,
+                };
+                if changed && !dragging {
+                    moved = Some(position);
+                }
+            }
+            if ctx.input(|i| i.viewport().close_requested()) {
+                expand = true;
+            }
+        });
+
+        if let Some(position) = moved {
+            self.settings.overlay.position = Some(position);
+            self.save_settings();
+        }
+        if expand {
+            self.toggle_overlay(ctx);
+        }
+    }
+}
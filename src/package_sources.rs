@@ -0,0 +1,333 @@
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+use std::time::Duration;
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use tokio::process::Command as TokioCommand;
+use log::{info, warn};
+use crate::elevation::ElevatedAction;
+use crate::guard::{ElevatedRequest, SensitiveAction};
+use crate::provision::ps_quote;
+use crate::{DevDashboard, InstallerControl, InstallerError, InstallerMessage, InstallerResult, InstallerState, NiniteApp, CREATE_NO_WINDOW};
+
+/// winget exit codes for a package that is already installed and current
+pub const WINGET_ALREADY_INSTALLED: &[i32] = &[-1978335189, -1978335135];
+/// Chocolatey exit codes for a finished install that wants a restart
+const CHOCO_REBOOT_REQUIRED: &[i32] = &[1641, 3010];
+/// Large suites such as LibreOffice take a while to install
+const INSTALL_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Where Install Selected Apps gets the apps from
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum InstallBackend {
+    Ninite,
+    Winget,         // Limited to the chosen winget source when one is set
+    Chocolatey,     // From the configured Chocolatey repository
+}
+
+impl InstallBackend {
+    fn label(self) -> &'static str {
+        match self {
+            InstallBackend::Ninite => "Ninite (public)",
+            InstallBackend::Winget => "winget",
+            InstallBackend::Chocolatey => "Chocolatey",
+        }
+    }
+}
+
+/// A winget REST source, such as an internal repository of vetted packages
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Debug)]
+#[serde(default)]
+pub struct WingetSource {
+    pub name: String,
+    pub url: String,
+}
+
+/// Persisted package sources for the Tools tab
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct PackageSourceSettings {
+    pub backend: InstallBackend,
+    pub winget_sources: Vec<WingetSource>,  // Registered with winget from Settings
+    pub winget_source: String,      // Source every winget call is limited to; empty for all of winget's sources
+    pub choco_source: String,       // Chocolatey repository URL; empty for the community feed
+}
+
+impl Default for PackageSourceSettings {
+    fn default() -> Self {
+        Self {
+            backend: InstallBackend::Ninite,
+            winget_sources: Vec::new(),
+            winget_source: String::new(),
+            choco_source: String::new(),
+        }
+    }
+}
+
+impl PackageSourceSettings {
+    /// `--source` arguments for winget, when it is limited to one source
+    pub fn winget_source_args(&self) -> Vec<String> {
+        let source = self.winget_source.trim();
+        if source.is_empty() {
+            Vec::new()
+        } else {
+            vec!["--source".to_string(), source.to_string()]
+        }
+    }
+
+    /// `--source` arguments for Chocolatey, when a repository is configured
+    pub fn choco_source_args(&self) -> Vec<String> {
+        let source = self.choco_source.trim();
+        if source.is_empty() {
+            Vec::new()
+        } else {
+            vec!["--source".to_string(), source.to_string()]
+        }
+    }
+}
+
+/// How a set of apps is installed with a backend
+pub struct InstallPlan<'a> {
+    pub winget: Vec<&'a NiniteApp>,
+    pub choco: Vec<&'a NiniteApp>,
+    pub ninite: Vec<&'a NiniteApp>,
+    pub missing: Vec<&'a NiniteApp>,    // No package for the chosen backend
+}
+
+/// Splits apps by installer. Ninite's public build falls back to winget's packages in scripts,
+/// while the private backends never reach for a public feed.
+pub fn plan<'a>(apps: &[&'a NiniteApp], backend: InstallBackend) -> InstallPlan<'a> {
+    let mut plan = InstallPlan { winget: Vec::new(), choco: Vec::new(), ninite: Vec::new(), missing: Vec::new() };
+    for app in apps {
+        match backend {
+            InstallBackend::Ninite if !app.info.winget_id.is_empty() => plan.winget.push(app),
+            InstallBackend::Ninite if !app.ninite_id.is_empty() => plan.ninite.push(app),
+            InstallBackend::Ninite => plan.missing.push(app),
+            InstallBackend::Winget if !app.info.winget_id.is_empty() => plan.winget.push(app),
+            InstallBackend::Chocolatey if !app.info.choco_id.is_empty() => plan.choco.push(app),
+            InstallBackend::Winget | InstallBackend::Chocolatey => plan.missing.push(app),
+        }
+    }
+    plan
+}
+
+async fn winget_install(app: &NiniteApp, source_args: &[String]) -> Result<(), String> {
+    let child = TokioCommand::new("winget")
+        .args(["install", "--id", &app.info.winget_id, "--exact", "--silent",
+            "--accept-package-agreements", "--accept-source-agreements", "--disable-interactivity"])
+        .args(source_args)
+        .creation_flags(CREATE_NO_WINDOW)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Could not run winget: {}", e))?;
+    let status = tokio::time::timeout(INSTALL_TIMEOUT, child.wait_with_output()).await
+        .map_err(|_| "winget did not finish in time".to_string())?
+        .map_err(|e| e.to_string())?
+        .status;
+    let code = status.code().unwrap_or(-1);
+    if status.success() || WINGET_ALREADY_INSTALLED.contains(&code) {
+        Ok(())
+    } else {
+        Err(format!("winget exit code {}", code))
+    }
+}
+
+/// Chocolatey needs administrator rights, so it is started through the "runas" verb and waited for
+async fn choco_install(ids: &[String], source_args: &[String]) -> Result<(), String> {
+    let mut args = vec!["install".to_string()];
+    args.extend(ids.iter().cloned());
+    args.extend(["-y".to_string(), "--no-progress".to_string()]);
+    args.extend(source_args.iter().cloned());
+    let list = args.iter().map(|arg| ps_quote(arg)).collect::<Vec<_>>().join(",");
+    let script = format!("$p = Start-Process -FilePath choco -ArgumentList {} -Verb RunAs -Wait -PassThru -WindowStyle Hidden; exit $p.ExitCode", list);
+    let child = TokioCommand::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .creation_flags(CREATE_NO_WINDOW)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Could not run PowerShell: {}", e))?;
+    let status = tokio::time::timeout(INSTALL_TIMEOUT, child.wait_with_output()).await
+        .map_err(|_| "Chocolatey did not finish in time".to_string())?
+        .map_err(|e| e.to_string())?
+        .status;
+    let code = status.code().unwrap_or(-1);
+    if status.success() || CHOCO_REBOOT_REQUIRED.contains(&code) {
+        Ok(())
+    } else {
+        Err(format!("Chocolatey exit code {}; is it installed, and were administrator rights granted?", code))
+    }
+}
+
+/// Where winget sources are being registered, and how it went
+#[derive(Default)]
+pub struct SourceRegistry {
+    receiver: Option<Receiver<(String, Result<(), String>)>>,
+    results: HashMap<String, Result<(), String>>,   // By source name
+}
+
+impl DevDashboard {
+    /// Installs the apps with winget or Chocolatey from the configured sources, one winget package at a time
+    pub(crate) async fn install_from_sources(
+        apps: Vec<NiniteApp>,
+        sources: PackageSourceSettings,
+        sender: Sender<InstallerMessage>,
+        control: Arc<InstallerControl>
+    ) -> InstallerResult<()> {
+        if apps.is_empty() {
+            return Err(Box::new(InstallerError::NoAppsSelected));
+        }
+        let apps: Vec<&NiniteApp> = apps.iter().collect();
+        let plan = plan(&apps, sources.backend);
+        Self::send_message(&sender, InstallerMessage::SetState(InstallerState::Installing))?;
+
+        let mut failed: Vec<String> = plan.missing.iter()
+            .map(|app| format!("{} (no {} package)", app.name, sources.backend.label()))
+            .collect();
+        let source_args = sources.winget_source_args();
+        for app in &plan.winget {
+            if control.cancel.load(Ordering::Relaxed) {
+                return Self::send_message(&sender, InstallerMessage::Cancelled);
+            }
+            info!("Installing {} with winget", app.name);
+            if let Err(e) = winget_install(app, &source_args).await {
+                warn!("Could not install {} with winget: {}", app.name, e);
+                failed.push(format!("{} ({})", app.name, e));
+            }
+        }
+        if !plan.choco.is_empty() {
+            let ids: Vec<String> = plan.choco.iter().map(|app| app.info.choco_id.clone()).collect();
+            info!("Installing {:?} with Chocolatey", ids);
+            if let Err(e) = choco_install(&ids, &sources.choco_source_args()).await {
+                warn!("Chocolatey install failed: {}", e);
+                failed.push(format!("{} ({})", plan.choco.iter().map(|app| app.name.as_str()).collect::<Vec<_>>().join(", "), e));
+            }
+        }
+
+        if !failed.is_empty() {
+            return Err(Box::new(InstallerError::InstallFailed(failed.join("; "))));
+        }
+        Self::send_message(&sender, InstallerMessage::SetState(InstallerState::Idle))
+    }
+
+    pub(crate) fn register_winget_source(&mut self, source: WingetSource) {
+        info!("Registering winget source {} at {}", source.name, source.url);
+        let (sender, receiver) = channel();
+        self.source_registry.receiver = Some(receiver);
+        self.source_registry.results.remove(&source.name);
+        let broker = self.elevation.clone();
+        self.runtime().spawn(async move {
+            let result = broker.run(ElevatedAction::AddWingetSource { name: source.name.clone(), url: source.url }).await;
+            sender.send((source.name, result)).ok();
+        });
+    }
+
+    /// Settings section for winget sources and the Chocolatey repository
+    pub(crate) fn show_package_source_settings(&mut self, ui: &mut egui::Ui) {
+        if let Some(receiver) = &self.source_registry.receiver {
+            if let Ok((name, result)) = receiver.try_recv() {
+                match &result {
+                    Ok(()) => info!("Registered winget source {}", name),
+                    Err(e) => warn!("Could not register winget source {}: {}", name, e),
+                }
+                self.source_registry.results.insert(name, result);
+                self.source_registry.receiver = None;
+            }
+        }
+        let registering = self.source_registry.receiver.is_some();
+        let mut changed = false;
+        let mut source_changed = false;
+        let mut register = None;
+        let mut removed = None;
+
+        ui.label("Package Sources:");
+        let settings = &mut self.settings.package_sources;
+        ui.horizontal(|ui| {
+            ui.label("Install apps with:");
+            egui::ComboBox::from_id_source("install_backend")
+                .selected_text(settings.backend.label())
+                .show_ui(ui, |ui| {
+                    for backend in [InstallBackend::Ninite, InstallBackend::Winget, InstallBackend::Chocolatey] {
+                        changed |= ui.selectable_value(&mut settings.backend, backend, backend.label()).changed();
+                    }
+                });
+        });
+
+        ui.add_space(4.0);
+        ui.label(RichText::new("winget sources").color(Color32::from_rgb(156, 163, 175)));
+        for (index, source) in settings.winget_sources.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                changed |= ui.add(egui::TextEdit::singleline(&mut source.name).hint_text("internal").desired_width(90.0)).changed();
+                changed |= ui.add(egui::TextEdit::singleline(&mut source.url).hint_text("https://packages.example.com/api/").desired_width(220.0)).changed();
+                let ready = !source.name.trim().is_empty() && !source.url.trim().is_empty() && !registering;
+                if ui.add_enabled(ready, egui::Button::new("Register"))
+                    .on_hover_text("Adds the source to winget; this needs administrator rights")
+                    .clicked() {
+                    register = Some(WingetSource { name: source.name.trim().to_string(), url: source.url.trim().to_string() });
+                }
+                if ui.small_button("✖").clicked() {
+                    removed = Some(index);
+                }
+                match self.source_registry.results.get(source.name.trim()) {
+                    Some(Ok(())) => { ui.colored_label(Color32::from_rgb(22, 163, 74), "Registered"); }
+                    Some(Err(e)) => { ui.colored_label(Color32::from_rgb(220, 50, 50), "Failed").on_hover_text(e); }
+                    None => {}
+                }
+            });
+        }
+        if ui.button("Add winget source").clicked() {
+            settings.winget_sources.push(WingetSource::default());
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Use winget source:");
+            let selected = if settings.winget_source.is_empty() { "All sources" } else { settings.winget_source.as_str() };
+            egui::ComboBox::from_id_source("winget_source")
+                .selected_text(selected.to_string())
+                .show_ui(ui, |ui| {
+                    let names = ["winget".to_string(), "msstore".to_string()].into_iter()
+                        .chain(settings.winget_sources.iter().map(|source| source.name.trim().to_string()))
+                        .filter(|name| !name.is_empty());
+                    source_changed |= ui.selectable_value(&mut settings.winget_source, String::new(), "All sources").changed();
+                    for name in names {
+                        source_changed |= ui.selectable_value(&mut settings.winget_source, name.clone(), name).changed();
+                    }
+                });
+        }).response.on_hover_text("Installs, release notes and exported scripts only use this source");
+
+        ui.horizontal(|ui| {
+            ui.label("Chocolatey repository:");
+            changed |= ui.add(egui::TextEdit::singleline(&mut settings.choco_source)
+                .hint_text("Community feed")
+                .desired_width(260.0))
+                .changed();
+        });
+        if settings.backend != InstallBackend::Ninite {
+            ui.label(RichText::new("Apps without a package in the chosen source are skipped, never installed from a public feed")
+                .color(Color32::from_rgb(107, 114, 128)));
+        }
+
+        if let Some(index) = removed {
+            settings.winget_sources.remove(index);
+            changed = true;
+        }
+        if source_changed {
+            // Release details came from the previous source
+            self.app_info = Default::default();
+        }
+        if changed || source_changed {
+            self.save_settings();
+        }
+        if let Some(source) = register {
+            self.request_sensitive_action(SensitiveAction::ElevatedAction(ElevatedRequest::AddWingetSource(source)));
+        }
+    }
+}
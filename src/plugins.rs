@@ -0,0 +1,355 @@
+use std::ffi::{c_char, CStr};
+use std::fs::File;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::Duration;
+use eframe::egui;
+use egui::{Color32, RichText};
+use libloading::{Library, Symbol};
+use serde::{Serialize, Deserialize};
+use log::{info, warn};
+use crate::signature::{self, SignatureInfo};
+use crate::DevDashboard;
+
+/// Each plugin lives in its own subfolder with a plugin.json manifest
+const PLUGINS_DIR: &str = "plugins";
+const MANIFEST_FILE: &str = "plugin.json";
+/// Version of the C interface below; plugins built for another version are not loaded
+pub const PLUGIN_API_VERSION: u32 = 1;
+const MIN_INTERVAL_SECS: u64 = 1;
+
+/// `extern "C" fn devdash_plugin_api_version() -> u32`
+const SYMBOL_API_VERSION: &[u8] = b"devdash_plugin_api_version\0";
+/// `extern "C" fn devdash_plugin_collect() -> *mut c_char`, returning card content as UTF-8 JSON
+const SYMBOL_COLLECT: &[u8] = b"devdash_plugin_collect\0";
+/// `extern "C" fn devdash_plugin_free(*mut c_char)`, releasing a string returned by collect
+const SYMBOL_FREE: &[u8] = b"devdash_plugin_free\0";
+
+type ApiVersionFn = unsafe extern "C" fn() -> u32;
+type CollectFn = unsafe extern "C" fn() -> *mut c_char;
+type FreeFn = unsafe extern "C" fn(*mut c_char);
+
+/// Persisted plugin configuration
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct PluginSettings {
+    pub enabled: bool,              // Loading third-party libraries is opt-in
+    pub disabled: Vec<String>,      // Folder names of plugins that are not loaded
+}
+
+/// Contents of plugin.json
+#[derive(Deserialize, Clone)]
+pub struct PluginManifest {
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    pub library: String,            // DLL file name, relative to the plugin folder
+    pub api_version: u32,
+    #[serde(default = "default_interval")]
+    pub interval_secs: u64,         // How often collect is called
+}
+
+fn default_interval() -> u64 {
+    5
+}
+
+/// What a custom card displays: a list of labelled values, optionally with a bar
+#[derive(Deserialize, Clone, Default)]
+pub struct CardContent {
+    #[serde(default)]
+    pub rows: Vec<CardRow>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl CardContent {
+    pub fn error(message: impl Into<String>) -> Self {
+        Self { rows: Vec::new(), error: Some(message.into()) }
+    }
+
+    /// Label and value of each row, then the error if there is one
+    pub fn values(&self) -> Vec<(String, String)> {
+        let mut values: Vec<(String, String)> = self.rows.iter().map(|row| (row.label.clone(), row.value.clone())).collect();
+        if let Some(error) = &self.error {
+            values.push(("Error".to_string(), error.clone()));
+        }
+        values
+    }
+}
+
+#[derive(Deserialize, Clone, Default)]
+pub struct CardRow {
+    pub label: String,
+    #[serde(default)]
+    pub value: String,
+    #[serde(default)]
+    pub progress: Option<f32>,      // 0.0 to 1.0
+    #[serde(default)]
+    pub color: Option<String>,      // "#RRGGBB" or green/amber/red/blue/grey
+}
+
+/// Named colors match the rest of the dashboard
+fn parse_color(color: &str) -> Option<Color32> {
+    match color.trim().to_lowercase().as_str() {
+        "green" => Some(Color32::from_rgb(22, 163, 74)),
+        "amber" | "yellow" => Some(Color32::from_rgb(202, 138, 4)),
+        "red" => Some(Color32::from_rgb(220, 50, 50)),
+        "blue" => Some(Color32::from_rgb(88, 165, 237)),
+        "grey" | "gray" => Some(Color32::from_rgb(156, 163, 175)),
+        hex => {
+            let hex = hex.strip_prefix('#')?;
+            if hex.len() != 6 {
+                return None;
+            }
+            let value = u32::from_str_radix(hex, 16).ok()?;
+            Some(Color32::from_rgb((value >> 16) as u8, (value >> 8) as u8, value as u8))
+        }
+    }
+}
+
+/// Draws custom card rows; shared by every card whose content comes from outside the core
+pub(crate) fn show_card_rows(ui: &mut egui::Ui, content: &CardContent) {
+    if let Some(error) = &content.error {
+        ui.colored_label(Color32::from_rgb(220, 50, 50), error);
+    }
+    for row in &content.rows {
+        let color = row.color.as_deref().and_then(parse_color);
+        ui.horizontal(|ui| {
+            ui.label(&row.label);
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                match color {
+                    Some(color) => ui.colored_label(color, &row.value),
+                    None => ui.label(&row.value),
+                };
+            });
+        });
+        if let Some(progress) = row.progress {
+            let visuals = ui.visuals_mut();
+            visuals.widgets.inactive.bg_fill = Color32::from_rgb(55, 65, 81);
+            ui.add(egui::ProgressBar::new(progress.clamp(0.0, 1.0))
+                .fill(color.unwrap_or(Color32::from_rgb(37, 99, 235))));
+        }
+    }
+}
+
+pub enum PluginState {
+    Disabled,
+    Failed(String),
+    Running,
+}
+
+pub struct Plugin {
+    pub folder_name: String,
+    pub manifest: PluginManifest,
+    pub signature: SignatureInfo,
+    pub state: PluginState,
+    pub content: Option<CardContent>,
+    receiver: Option<Receiver<CardContent>>,
+}
+
+#[derive(Default)]
+pub struct PluginHost {
+    pub plugins: Vec<Plugin>,
+    restart_needed: bool,       // Plugins were switched on or off since they were loaded
+}
+
+fn read_manifest(folder: &Path) -> Result<PluginManifest, String> {
+    let mut contents = String::new();
+    File::open(folder.join(MANIFEST_FILE))
+        .and_then(|mut file| file.read_to_string(&mut contents))
+        .map_err(|e| e.to_string())?;
+    serde_json::from_str(&contents).map_err(|e| e.to_string())
+}
+
+/// The manifest's library must name a file inside the plugin's own folder, not an absolute path or one through ..
+fn library_path(folder: &Path, library: &str) -> Result<PathBuf, String> {
+    let relative = Path::new(library);
+    if library.is_empty() || !relative.components().all(|component| matches!(component, Component::Normal(_))) {
+        return Err(format!("Library {} is outside the plugin folder", library));
+    }
+    Ok(folder.join(relative))
+}
+
+/// Loads the library and checks that it exports the expected interface
+fn load_library(path: &Path) -> Result<Library, String> {
+    // Safety: loading runs the library's initialization code; plugins are opt-in and the user chooses what goes in the folder
+    let library = unsafe { Library::new(path) }.map_err(|e| format!("Could not load {}: {}", path.display(), e))?;
+    unsafe {
+        let api_version: Symbol<ApiVersionFn> = library.get(SYMBOL_API_VERSION).map_err(|e| e.to_string())?;
+        let version = api_version();
+        if version != PLUGIN_API_VERSION {
+            return Err(format!("Built for plugin API {}, this dashboard supports {}", version, PLUGIN_API_VERSION));
+        }
+        library.get::<CollectFn>(SYMBOL_COLLECT).map_err(|e| e.to_string())?;
+        library.get::<FreeFn>(SYMBOL_FREE).map_err(|e| e.to_string())?;
+    }
+    Ok(library)
+}
+
+/// Calls collect on an interval until the receiver is dropped
+fn run_plugin(library: Library, interval: Duration, sender: Sender<CardContent>) {
+    // Safety: both symbols were checked in load_library and the library outlives them
+    let (collect, free) = unsafe {
+        match (library.get::<CollectFn>(SYMBOL_COLLECT), library.get::<FreeFn>(SYMBOL_FREE)) {
+            (Ok(collect), Ok(free)) => (collect, free),
+            _ => return,
+        }
+    };
+    loop {
+        let content = unsafe {
+            let pointer = collect();
+            if pointer.is_null() {
+                CardContent::error("Plugin returned no data")
+            } else {
+                let json = CStr::from_ptr(pointer).to_string_lossy().into_owned();
+                free(pointer);
+                serde_json::from_str(&json).unwrap_or_else(|e| CardContent::error(format!("Invalid plugin output: {}", e)))
+            }
+        };
+        if sender.send(content).is_err() {
+            break;
+        }
+        std::thread::sleep(interval);
+    }
+}
+
+impl PluginHost {
+    /// Reads every manifest in the plugins folder and starts the enabled plugins
+    pub fn discover(settings: &PluginSettings) -> Self {
+        let Ok(entries) = std::fs::read_dir(PLUGINS_DIR) else { return Self::default() };
+        let mut folders: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).filter(|path| path.is_dir()).collect();
+        folders.sort();
+
+        let mut plugins = Vec::new();
+        for folder in folders {
+            let folder_name = folder.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+            let manifest = match read_manifest(&folder) {
+                Ok(manifest) => manifest,
+                Err(e) => {
+                    warn!("Skipping plugin folder {}: {}", folder_name, e);
+                    continue;
+                }
+            };
+            let library_path = match library_path(&folder, &manifest.library) {
+                Ok(path) => path,
+                Err(e) => {
+                    warn!("Skipping plugin folder {}: {}", folder_name, e);
+                    continue;
+                }
+            };
+            let signature = signature::verify(&library_path);
+
+            let mut plugin = Plugin {
+                folder_name,
+                manifest,
+                signature,
+                state: PluginState::Disabled,
+                content: None,
+                receiver: None,
+            };
+            if settings.enabled && !settings.disabled.contains(&plugin.folder_name) {
+                let loaded = if plugin.manifest.api_version == PLUGIN_API_VERSION {
+                    load_library(&library_path)
+                } else {
+                    Err(format!("Manifest targets plugin API {}, this dashboard supports {}", plugin.manifest.api_version, PLUGIN_API_VERSION))
+                };
+                plugin.state = match loaded {
+                    Ok(library) => {
+                        info!("Loaded plugin {} {} ({})", plugin.manifest.name, plugin.manifest.version, plugin.signature.status.label());
+                        let (sender, receiver) = channel();
+                        let interval = Duration::from_secs(plugin.manifest.interval_secs.max(MIN_INTERVAL_SECS));
+                        std::thread::spawn(move || run_plugin(library, interval, sender));
+                        plugin.receiver = Some(receiver);
+                        PluginState::Running
+                    }
+                    Err(e) => {
+                        warn!("Plugin {} failed to load: {}", plugin.manifest.name, e);
+                        PluginState::Failed(e)
+                    }
+                };
+            }
+            plugins.push(plugin);
+        }
+        Self { plugins, restart_needed: false }
+    }
+
+    /// Takes the latest output of each running plugin
+    pub fn update(&mut self) {
+        for plugin in &mut self.plugins {
+            if let Some(receiver) = &plugin.receiver {
+                while let Ok(content) = receiver.try_recv() {
+                    plugin.content = Some(content);
+                }
+            }
+        }
+    }
+
+    /// Indices of plugins that provide a dashboard card
+    pub fn card_indices(&self) -> Vec<usize> {
+        self.plugins
+            .iter()
+            .enumerate()
+            .filter(|(_, plugin)| matches!(plugin.state, PluginState::Running))
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+impl DevDashboard {
+    /// Displays the card of a running plugin
+    pub(crate) fn show_plugin_card(&self, ui: &mut egui::Ui, index: usize) {
+        let Some(plugin) = self.plugins.plugins.get(index) else { return };
+        self.show_card(ui, &plugin.manifest.name, |ui| match &plugin.content {
+            Some(content) => show_card_rows(ui, content),
+            None => {
+                ui.spinner();
+            }
+        });
+    }
+
+    /// Settings section listing discovered plugins
+    pub(crate) fn show_plugin_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        let settings = &mut self.settings.plugins;
+
+        changed |= ui.checkbox(&mut settings.enabled, "Load plugins")
+            .on_hover_text(format!("Plugins are libraries in the {} folder and run with the dashboard's permissions", PLUGINS_DIR))
+            .changed();
+        if self.plugins.plugins.is_empty() {
+            ui.label(RichText::new(format!("No plugins found in {}\\", PLUGINS_DIR)).color(Color32::from_rgb(156, 163, 175)));
+        }
+        for plugin in &self.plugins.plugins {
+            ui.horizontal(|ui| {
+                let mut enabled = !settings.disabled.contains(&plugin.folder_name);
+                if ui.add_enabled(settings.enabled, egui::Checkbox::new(&mut enabled, &plugin.manifest.name)).changed() {
+                    if enabled {
+                        settings.disabled.retain(|name| name != &plugin.folder_name);
+                    } else {
+                        settings.disabled.push(plugin.folder_name.clone());
+                    }
+                    changed = true;
+                }
+                if !plugin.manifest.version.is_empty() {
+                    ui.label(RichText::new(&plugin.manifest.version).color(Color32::from_rgb(156, 163, 175)));
+                }
+                ui.colored_label(plugin.signature.status.color(), plugin.signature.status.label())
+                    .on_hover_text(plugin.signature.signer.as_deref().unwrap_or("No signer"));
+                if let PluginState::Failed(error) = &plugin.state {
+                    ui.colored_label(Color32::from_rgb(220, 50, 50), "Failed").on_hover_text(error);
+                }
+            })
+            .response
+            .on_hover_text(&plugin.manifest.description);
+        }
+        if changed {
+            self.plugins.restart_needed = true;
+            self.save_settings();
+        }
+        if self.plugins.restart_needed {
+            ui.label(RichText::new("Plugin changes take effect after a restart").color(Color32::from_rgb(202, 138, 4)));
+        }
+    }
+}
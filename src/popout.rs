@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use log::info;
+use crate::{DashboardCard, DevDashboard};
+
+const DEFAULT_WIDTH: f32 = 320.0;
+/// Strip above the card used to drag the frameless window
+const HANDLE_HEIGHT: f32 = 18.0;
+
+/// A card shown in its own always-on-top window instead of on the Dashboard tab
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct PoppedCard {
+    pub card: String,                   // DashboardCard::key
+    pub position: Option<[f32; 2]>,     // Top-left corner in points, None until first placed
+    pub width: f32,
+}
+
+impl Default for PoppedCard {
+    fn default() -> Self {
+        Self {
+            card: String::new(),
+            position: None,
+            width: DEFAULT_WIDTH,
+        }
+    }
+}
+
+/// Persisted pop-out windows
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct PopoutSettings {
+    pub cards: Vec<PoppedCard>,
+}
+
+impl PopoutSettings {
+    pub(crate) fn is_popped(&self, card: DashboardCard) -> bool {
+        let key = card.key();
+        self.cards.iter().any(|popped| popped.card == key)
+    }
+}
+
+/// Content heights measured last frame, so each window fits its card
+#[derive(Default)]
+pub struct Popouts {
+    heights: HashMap<String, f32>,
+}
+
+impl DashboardCard {
+    /// Stable name for settings; indexed cards include their position in their own list
+    pub(crate) fn key(&self) -> String {
+        match self {
+            DashboardCard::System => "system".to_string(),
+            DashboardCard::Cpu => "cpu".to_string(),
+            DashboardCard::Memory => "memory".to_string(),
+            DashboardCard::Storage => "storage".to_string(),
+            DashboardCard::Network => "network".to_string(),
+            DashboardCard::Gpu => "gpu".to_string(),
+            DashboardCard::Certificates => "certificates".to_string(),
+            DashboardCard::Ticker => "ticker".to_string(),
+            DashboardCard::Calendar => "calendar".to_string(),
+            DashboardCard::Todo => "todo".to_string(),
+            DashboardCard::Usage => "usage".to_string(),
+            DashboardCard::Backup => "backup".to_string(),
+            DashboardCard::Cloud => "cloud".to_string(),
+            DashboardCard::Power => "power".to_string(),
+            DashboardCard::Battery => "battery".to_string(),
+            DashboardCard::Uptime => "uptime".to_string(),
+            DashboardCard::NetworkShares => "network_shares".to_string(),
+            DashboardCard::Repos => "repos".to_string(),
+            DashboardCard::Stacks => "stacks".to_string(),
+            DashboardCard::Tunnels => "tunnels".to_string(),
+            DashboardCard::TopProcesses => "top_processes".to_string(),
+            DashboardCard::ProcessNetwork => "process_network".to_string(),
+            DashboardCard::Wsl => "wsl".to_string(),
+            DashboardCard::Plugin(index) => format!("plugin:{}", index),
+            DashboardCard::Script(index) => format!("script:{}", index),
+            DashboardCard::Wmi(index) => format!("wmi:{}", index),
+            DashboardCard::Command(index) => format!("command:{}", index),
+        }
+    }
+}
+
+impl DevDashboard {
+    /// Small button in the corner of a dashboard card that moves it to its own window
+    pub(crate) fn show_popout_button(&mut self, ui: &mut egui::Ui, card_rect: egui::Rect, card: DashboardCard) {
+        let rect = egui::Rect::from_min_size(card_rect.right_top() + egui::vec2(-17.0, 1.0), egui::vec2(16.0, 16.0));
+        let button = egui::Button::new(RichText::new("⧉").size(11.0)).frame(false);
+        if ui.put(rect, button).on_hover_text("Pop out as an always-on-top widget").clicked() {
+            info!("Popped out the {} card", card.key());
+            self.settings.popouts.cards.push(PoppedCard { card: card.key(), ..Default::default() });
+            self.save_settings();
+        }
+    }
+
+    /// Frameless always-on-top windows for popped-out cards
+    pub(crate) fn show_popout_windows(&mut self, ctx: &egui::Context) {
+        if self.settings.popouts.cards.is_empty() {
+            return;
+        }
+        let available = self.dashboard_cards();
+        let mut docked = None;
+        let mut moved = None;
+
+        for (index, popped) in self.settings.popouts.cards.clone().into_iter().enumerate() {
+            // Cards that are switched off keep their window settings until they come back
+            let Some(card) = available.iter().copied().find(|card| card.key() == popped.card) else { continue };
+            let height = self.popouts.heights.get(&popped.card).copied().unwrap_or(240.0);
+            let mut builder = egui::ViewportBuilder::default()
+                .with_title(format!("Dev Dashboard - {}", popped.card))
+                .with_decorations(false)
+                .with_resizable(false)
+                .with_always_on_top()
+                .with_inner_size([popped.width, height]);
+            if let Some([x, y]) = popped.position {
+                builder = builder.with_position([x, y]);
+            }
+
+            let id = egui::ViewportId::from_hash_of(("popout", &popped.card));
+            ctx.show_viewport_immediate(id, builder, |ctx, _class| {
+                egui::CentralPanel::default()
+                    .frame(egui::Frame::none()
+                        .fill(self.palette().card)
+                        .inner_margin(egui::style::Margin::symmetric(12.0, 6.0)))
+                    .show(ctx, |ui| {
+                        let (handle, _) = ui.allocate_exact_size(egui::vec2(ui.available_width(), HANDLE_HEIGHT), egui::Sense::hover());
+                        let drag = ui.interact(handle, id.0.with("handle"), egui::Sense::drag());
+                        if drag.drag_started() {
+                            ctx.send_viewport_cmd(egui::ViewportCommand::StartDrag);
+                        }
+                        ui.painter().text(handle.left_center(), egui::Align2::LEFT_CENTER, "⠿",
+                            egui::FontId::proportional(12.0), Color32::from_rgb(107, 114, 128));
+                        let close = egui::Rect::from_min_size(handle.right_top() - egui::vec2(HANDLE_HEIGHT, 0.0), egui::vec2(HANDLE_HEIGHT, HANDLE_HEIGHT));
+                        if ui.put(close, egui::Button::new("✖").frame(false)).on_hover_text("Back to the dashboard").clicked() {
+                            docked = Some(index);
+                        }
+
+                        let top = ui.cursor().top();
+                        self.show_dashboard_card(ui, card);
+                        let menu = ui.interact(ui.min_rect(), id.0.with("menu"), egui::Sense::click());
+                        self.show_card_menu(menu, card);
+                        let content = ui.cursor().top() - top + HANDLE_HEIGHT + 16.0;
+                        if (content - height).abs() > 2.0 {
+                            self.popouts.heights.insert(popped.card.clone(), content.max(60.0));
+                        }
+                    });
+
+                // Remember where the window was dragged once the button is released
+                let (outer, dragging) = ctx.input(|i| (i.viewport().outer_rect, i.pointer.any_down()));
+                if let Some(outer) = outer {
+                    let position = [outer.min.x, outer.min.y];
+                    let changed = match popped.position {
+                        Some([x, y]) => (x - position[0]).abs() > 1.0 || (y - position[1]).abs() > 1.0,
+                        None => true,
+                    };
+                    if changed && !dragging {
+                        moved = Some((index, position));
+                    }
+                }
+                if ctx.input(|i| i.viewport().close_requested()) {
+                    docked = Some(index);
+                }
+            });
+        }
+
+        if let Some((index, position)) = moved {
+            if let Some(popped) = self.settings.popouts.cards.get_mut(index) {
+                popped.position = Some(position);
+                self.save_settings();
+            }
+        }
+        if let Some(index) = docked {
+            let popped = self.settings.popouts.cards.remove(index);
+            info!("Docked the {} card", popped.card);
+            self.popouts.heights.remove(&popped.card);
+            self.save_settings();
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+use eframe::egui;
+use egui::{Color32, RichText};
+use sysinfo::{Pid, PidExt, ProcessExt, SystemExt};
+use log::{info, warn};
+use crate::guard::{ProcessKill, SensitiveAction};
+use crate::ports::{self, SocketProtocol};
+use crate::DevDashboard;
+
+/// The list is read again this often while the page is open
+const REFRESH_INTERVAL: Duration = Duration::from_secs(3);
+
+/// A listening TCP or bound UDP socket and the process that owns it
+struct PortRow {
+    protocol: SocketProtocol,
+    address: IpAddr,
+    port: u16,
+    pid: u32,
+    process: String,
+}
+
+impl PortRow {
+    /// The idle process and the kernel can't be ended
+    fn can_kill(&self) -> bool {
+        self.pid > 4
+    }
+}
+
+/// State of the Ports tool
+#[derive(Default)]
+pub struct PortUsage {
+    rows: Vec<PortRow>,
+    last_refresh: Option<Instant>,
+    filter: String,                     // Port number or part of a process name
+    hide_udp: bool,
+    confirm: Option<u32>,               // PID whose Kill was clicked once
+    status: Option<(String, bool)>,     // Message and whether it failed
+}
+
+impl PortUsage {
+    fn matches(&self, row: &PortRow) -> bool {
+        if self.hide_udp && row.protocol == SocketProtocol::Udp {
+            return false;
+        }
+        let filter = self.filter.trim();
+        if filter.is_empty() {
+            return true;
+        }
+        match filter.parse::<u16>() {
+            Ok(port) => row.port == port,
+            Err(_) => row.process.to_lowercase().contains(&filter.to_lowercase()),
+        }
+    }
+}
+
+impl DevDashboard {
+    fn refresh_port_usage(&mut self) {
+        self.sys.refresh_processes();
+        let mut rows: Vec<PortRow> = ports::listening_sockets()
+            .into_iter()
+            .map(|socket| PortRow {
+                protocol: socket.protocol,
+                address: socket.local_addr,
+                port: socket.local_port,
+                pid: socket.pid,
+                process: match (socket.pid, self.sys.process(Pid::from_u32(socket.pid))) {
+                    (0, _) => "System Idle Process".to_string(),
+                    (4, _) => "System".to_string(),
+                    (_, Some(process)) => process.name().to_string(),
+                    (_, None) => "Unknown".to_string(),
+                },
+            })
+            .collect();
+        rows.sort_by(|a, b| a.port.cmp(&b.port)
+            .then(a.protocol.label().cmp(b.protocol.label()))
+            .then(a.address.cmp(&b.address)));
+        self.port_usage.rows = rows;
+        self.port_usage.last_refresh = Some(Instant::now());
+    }
+
+    /// Ends the process holding a port; services and elevated processes need the dashboard elevated
+    pub(crate) fn kill_port_owner(&mut self, pid: u32, name: String, port: u16) {
+        let subject = format!("{} (PID {})", name, pid);
+        let status = match self.sys.process(Pid::from_u32(pid)) {
+            Some(process) if process.kill() => {
+                info!("Ended {} to free port {}", subject, port);
+                (format!("Ended {}", subject), false)
+            }
+            Some(_) => {
+                warn!("Could not end {}", subject);
+                (format!("Could not end {}; it may need administrator rights", subject), true)
+            }
+            None => (format!("{} has already exited", subject), false),
+        };
+        self.port_usage.status = Some(status);
+        // Give the process a moment to exit before the list is read again
+        self.port_usage.last_refresh = Instant::now().checked_sub(REFRESH_INTERVAL - Duration::from_millis(500));
+    }
+
+    /// Ports page of the Tools tab
+    /// Lists listening TCP and bound UDP ports with the owning process, for finding what holds a port
+    pub(crate) fn show_ports_tool(&mut self, ui: &mut egui::Ui) {
+        let due = match self.port_usage.last_refresh {
+            Some(last) => last.elapsed() >= REFRESH_INTERVAL,
+            None => true,
+        };
+        if due {
+            self.refresh_port_usage();
+        }
+        ui.ctx().request_repaint_after(REFRESH_INTERVAL);
+
+        let grey = Color32::from_rgb(156, 163, 175);
+        let mut refresh = false;
+        let mut kill = None;
+        let usage = &mut self.port_usage;
+
+        ui.heading("Ports");
+        ui.add_space(8.0);
+        ui.horizontal(|ui| {
+            ui.label("Filter:");
+            if ui.add(egui::TextEdit::singleline(&mut usage.filter)
+                .hint_text("3000 or node")
+                .desired_width(140.0)).changed() {
+                usage.confirm = None;
+            }
+            ui.checkbox(&mut usage.hide_udp, "TCP only");
+            if ui.button("⟳ Refresh").clicked() {
+                refresh = true;
+            }
+        });
+        if let Some((message, failed)) = &usage.status {
+            ui.label(RichText::new(message).color(if *failed { Color32::from_rgb(220, 50, 50) } else { grey }));
+        }
+        ui.add_space(8.0);
+
+        let shown: Vec<&PortRow> = usage.rows.iter().filter(|row| usage.matches(row)).collect();
+        if shown.is_empty() {
+            let filter = usage.filter.trim();
+            match filter.parse::<u16>() {
+                Ok(port) => ui.label(format!("Nothing is listening on port {}", port)),
+                Err(_) if !filter.is_empty() => ui.label("No matching ports"),
+                Err(_) => ui.label("No listening ports"),
+            };
+        } else {
+            egui::ScrollArea::vertical().auto_shrink([false, true]).show(ui, |ui| {
+                egui::Grid::new("ports_grid").striped(true).spacing([16.0, 4.0]).show(ui, |ui| {
+                    ui.label(RichText::new("Protocol").strong());
+                    ui.label(RichText::new("Address").strong());
+                    ui.label(RichText::new("Port").strong());
+                    ui.label(RichText::new("PID").strong());
+                    ui.label(RichText::new("Process").strong());
+                    ui.label("");
+                    ui.end_row();
+
+                    for row in shown {
+                        ui.label(row.protocol.label());
+                        let address = if row.address.is_unspecified() {
+                            RichText::new(format!("{} (all)", row.address))
+                        } else if row.address.is_loopback() {
+                            RichText::new(row.address.to_string()).color(grey)
+                        } else {
+                            RichText::new(row.address.to_string())
+                        };
+                        ui.label(address.monospace());
+                        ui.label(RichText::new(row.port.to_string()).monospace());
+                        ui.label(RichText::new(row.pid.to_string()).monospace());
+                        ui.label(&row.process);
+                        let confirming = usage.confirm == Some(row.pid);
+                        let text = if confirming { "Confirm kill" } else { "Kill" };
+                        if ui.add_enabled(row.can_kill(), egui::Button::new(text).small())
+                            .on_hover_text("End the process that owns this port")
+                            .clicked() {
+                            if confirming {
+                                kill = Some((row.pid, row.process.clone(), row.port));
+                            } else {
+                                usage.confirm = Some(row.pid);
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+        }
+
+        if let Some((pid, name, port)) = kill {
+            self.port_usage.confirm = None;
+            self.request_sensitive_action(SensitiveAction::KillProcess(ProcessKill::PortOwner { pid, name, port }));
+        }
+        if refresh {
+            self.refresh_port_usage();
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use windows::Win32::Foundation::{ERROR_INSUFFICIENT_BUFFER, NO_ERROR};
+use windows::Win32::NetworkManagement::IpHelper::{
+    GetExtendedTcpTable, GetExtendedUdpTable, MIB_TCP6TABLE_OWNER_PID, MIB_TCPTABLE_OWNER_PID,
+    MIB_UDP6TABLE_OWNER_PID, MIB_UDPTABLE_OWNER_PID, TCP_TABLE_CLASS, TCP_TABLE_OWNER_PID_ALL,
+    TCP_TABLE_OWNER_PID_LISTENER, UDP_TABLE_OWNER_PID,
+};
+use log::warn;
+
+pub(crate) const AF_INET: u32 = 2;
+pub(crate) const AF_INET6: u32 = 23;
+/// dwState of a listening socket in the TCP tables
+const MIB_TCP_STATE_LISTEN: u32 = 2;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum SocketProtocol {
+    Tcp,
+    Udp,
+}
+
+impl SocketProtocol {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SocketProtocol::Tcp => "TCP",
+            SocketProtocol::Udp => "UDP",
+        }
+    }
+}
+
+/// A single row of the system socket tables with its owning process
+#[derive(Clone, Debug)]
+pub struct SocketEntry {
+    pub protocol: SocketProtocol,
+    pub local_addr: IpAddr,
+    pub local_port: u16,
+    pub remote: Option<(IpAddr, u16)>,  // Peer of a connected TCP socket; None for listeners and UDP
+    pub pid: u32,
+}
+
+impl SocketEntry {
+    /// Whether the socket is bound to a loopback address only
+    pub fn is_loopback(&self) -> bool {
+        self.local_addr.is_loopback()
+    }
+}
+
+/// Ports are stored in network byte order in the low 16 bits
+fn port_from_raw(raw: u32) -> u16 {
+    u16::from_be(raw as u16)
+}
+
+/// IPv4 addresses are stored in network byte order
+fn ipv4_from_raw(raw: u32) -> IpAddr {
+    IpAddr::V4(Ipv4Addr::from(raw.to_ne_bytes()))
+}
+
+/// Calls one of the GetExtended*Table functions, growing the buffer until the table fits.
+/// The buffer is backed by u32s so the table rows are correctly aligned.
+pub(crate) fn read_table(mut query: impl FnMut(Option<*mut core::ffi::c_void>, *mut u32) -> u32) -> Option<Vec<u32>> {
+    let mut size = 0u32;
+    let result = query(None, &mut size);
+    if result != ERROR_INSUFFICIENT_BUFFER.0 && result != NO_ERROR.0 {
+        warn!("Failed to query socket table size: error {}", result);
+        return None;
+    }
+
+    // The table can grow between calls, so retry a few times
+    for _ in 0..4 {
+        let mut buffer = vec![0u32; (size as usize / 4) + 1];
+        let result = query(Some(buffer.as_mut_ptr() as *mut _), &mut size);
+        if result == NO_ERROR.0 {
+            return Some(buffer);
+        }
+        if result != ERROR_INSUFFICIENT_BUFFER.0 {
+            warn!("Failed to read socket table: error {}", result);
+            return None;
+        }
+    }
+
+    warn!("Socket table kept growing while reading it");
+    None
+}
+
+fn tcp_table(class: TCP_TABLE_CLASS) -> Vec<SocketEntry> {
+    let mut entries = Vec::new();
+
+    if let Some(buffer) = read_table(|table, size| unsafe {
+        GetExtendedTcpTable(table, size, false, AF_INET, class, 0)
+    }) {
+        let table = unsafe { &*(buffer.as_ptr() as *const MIB_TCPTABLE_OWNER_PID) };
+        let rows = unsafe { std::slice::from_raw_parts(table.table.as_ptr(), table.dwNumEntries as usize) };
+        entries.extend(rows.iter().map(|row| SocketEntry {
+            protocol: SocketProtocol::Tcp,
+            local_addr: ipv4_from_raw(row.dwLocalAddr),
+            local_port: port_from_raw(row.dwLocalPort),
+            remote: (row.dwState != MIB_TCP_STATE_LISTEN)
+                .then(|| (ipv4_from_raw(row.dwRemoteAddr), port_from_raw(row.dwRemotePort))),
+            pid: row.dwOwningPid,
+        }));
+    }
+
+    if let Some(buffer) = read_table(|table, size| unsafe {
+        GetExtendedTcpTable(table, size, false, AF_INET6, class, 0)
+    }) {
+        let table = unsafe { &*(buffer.as_ptr() as *const MIB_TCP6TABLE_OWNER_PID) };
+        let rows = unsafe { std::slice::from_raw_parts(table.table.as_ptr(), table.dwNumEntries as usize) };
+        entries.extend(rows.iter().map(|row| SocketEntry {
+            protocol: SocketProtocol::Tcp,
+            local_addr: IpAddr::V6(Ipv6Addr::from(row.ucLocalAddr)),
+            local_port: port_from_raw(row.dwLocalPort),
+            remote: (row.dwState != MIB_TCP_STATE_LISTEN)
+                .then(|| (IpAddr::V6(Ipv6Addr::from(row.ucRemoteAddr)), port_from_raw(row.dwRemotePort))),
+            pid: row.dwOwningPid,
+        }));
+    }
+
+    entries
+}
+
+fn udp_table() -> Vec<SocketEntry> {
+    let mut entries = Vec::new();
+
+    if let Some(buffer) = read_table(|table, size| unsafe {
+        GetExtendedUdpTable(table, size, false, AF_INET, UDP_TABLE_OWNER_PID, 0)
+    }) {
+        let table = unsafe { &*(buffer.as_ptr() as *const MIB_UDPTABLE_OWNER_PID) };
+        let rows = unsafe { std::slice::from_raw_parts(table.table.as_ptr(), table.dwNumEntries as usize) };
+        entries.extend(rows.iter().map(|row| SocketEntry {
+            protocol: SocketProtocol::Udp,
+            local_addr: ipv4_from_raw(row.dwLocalAddr),
+            local_port: port_from_raw(row.dwLocalPort),
+            remote: None,
+            pid: row.dwOwningPid,
+        }));
+    }
+
+    if let Some(buffer) = read_table(|table, size| unsafe {
+        GetExtendedUdpTable(table, size, false, AF_INET6, UDP_TABLE_OWNER_PID, 0)
+    }) {
+        let table = unsafe { &*(buffer.as_ptr() as *const MIB_UDP6TABLE_OWNER_PID) };
+        let rows = unsafe { std::slice::from_raw_parts(table.table.as_ptr(), table.dwNumEntries as usize) };
+        entries.extend(rows.iter().map(|row| SocketEntry {
+            protocol: SocketProtocol::Udp,
+            local_addr: IpAddr::V6(Ipv6Addr::from(row.ucLocalAddr)),
+            local_port: port_from_raw(row.dwLocalPort),
+            remote: None,
+            pid: row.dwOwningPid,
+        }));
+    }
+
+    entries
+}
+
+/// Returns all listening TCP sockets and bound UDP sockets (IPv4 and IPv6)
+pub fn listening_sockets() -> Vec<SocketEntry> {
+    let mut entries = tcp_table(TCP_TABLE_OWNER_PID_LISTENER);
+    entries.extend(udp_table());
+    entries
+}
+
+/// Returns every TCP and UDP socket bound to `port` or connected to it on the remote end
+pub fn sockets_on_port(port: u16) -> Vec<SocketEntry> {
+    let mut entries = tcp_table(TCP_TABLE_OWNER_PID_ALL);
+    entries.extend(udp_table());
+    entries.retain(|entry| entry.local_port == port || entry.remote.map(|(_, remote)| remote == port).unwrap_or(false));
+    entries
+}
@@ -0,0 +1,299 @@
+use std::collections::BTreeMap;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+use chrono::Local;
+use eframe::egui;
+use egui::RichText;
+use serde::{Serialize, Deserialize};
+use wmi::{COMLibrary, WMIConnection};
+use log::{debug, warn};
+use crate::card_export::CardHistory;
+use crate::{storage, DevDashboard};
+
+/// Daily energy totals are stored next to settings.json
+const ENERGY_FILE: &str = "energy.json";
+/// How often the sensor thread polls WMI
+const SENSOR_INTERVAL: Duration = Duration::from_secs(5);
+const SAVE_INTERVAL: Duration = Duration::from_secs(60);
+/// Gaps longer than this (sleep, hibernate) are not counted as consumed energy
+const MAX_SAMPLE_GAP: Duration = Duration::from_secs(30);
+/// Days of history shown in the card
+const HISTORY_DAYS: usize = 7;
+
+/// Persisted power estimation configuration
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct PowerSettings {
+    pub enabled: bool,
+    pub cpu_tdp_watts: f32,     // Used to estimate CPU power when no package sensor is available
+    pub base_watts: f32,        // Rest of the system: board, drives, fans, display
+    pub price_per_kwh: f32,     // 0 hides the cost
+}
+
+impl Default for PowerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cpu_tdp_watts: 65.0,
+            base_watts: 25.0,
+            price_per_kwh: 0.0,
+        }
+    }
+}
+
+/// Values read from Windows power sensors
+#[derive(Clone, Copy, Default)]
+struct SensorReading {
+    cpu_package_watts: Option<f32>,       // RAPL package power from the Energy Meter counters
+    battery_discharge_watts: Option<f32>, // Only while running on battery
+}
+
+/// Current draw estimate shown in the card
+#[derive(Clone, Copy)]
+pub struct PowerEstimate {
+    pub cpu_watts: f32,
+    pub cpu_measured: bool,
+    pub gpu_watts: Option<f32>,
+    pub total_watts: f32,
+    pub from_battery: bool,     // Total is the measured battery discharge rate
+}
+
+#[derive(Default)]
+pub struct PowerMonitor {
+    reading: SensorReading,
+    receiver: Option<Receiver<SensorReading>>,
+    pub estimate: Option<PowerEstimate>,
+    days: BTreeMap<String, f64>,    // YYYY-MM-DD -> watt hours
+    last_sample: Option<Instant>,
+    last_save: Option<Instant>,
+    dirty: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Win32_PerfFormattedData_Counters_EnergyMeter")]
+struct EnergyMeter {
+    #[serde(rename = "Name")]
+    name: String,
+    #[serde(rename = "Power")]
+    power: Option<u64>,         // Milliwatts
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "BatteryStatus")]
+struct BatteryStatus {
+    #[serde(rename = "DischargeRate")]
+    discharge_rate: Option<i32>, // Milliwatts
+    #[serde(rename = "Discharging")]
+    discharging: Option<bool>,
+}
+
+/// Polls the CPU energy meter and battery discharge rate until the receiver is dropped
+fn run_sensor_thread(sender: std::sync::mpsc::Sender<SensorReading>) {
+    let Ok(com) = COMLibrary::new() else {
+        warn!("Could not initialize COM for power sensors");
+        return;
+    };
+    let cimv2 = WMIConnection::new(com).ok();
+    let root_wmi = WMIConnection::with_namespace_path("root\\WMI", com).ok();
+
+    loop {
+        let cpu_package_watts = cimv2.as_ref()
+            .and_then(|wmi| wmi.query::<EnergyMeter>().ok())
+            .map(|meters| meters
+                .iter()
+                .filter(|meter| meter.name.ends_with("_PKG"))
+                .filter_map(|meter| meter.power)
+                .sum::<u64>())
+            .filter(|milliwatts| *milliwatts > 0)
+            .map(|milliwatts| milliwatts as f32 / 1000.0);
+
+        let battery_discharge_watts = root_wmi.as_ref()
+            .and_then(|wmi| wmi.query::<BatteryStatus>().ok())
+            .and_then(|batteries| {
+                let discharging: Vec<&BatteryStatus> = batteries.iter()
+                    .filter(|battery| battery.discharging.unwrap_or(false))
+                    .collect();
+                if discharging.is_empty() {
+                    return None;
+                }
+                Some(discharging.iter().filter_map(|battery| battery.discharge_rate).map(|rate| rate.max(0) as f32 / 1000.0).sum())
+            });
+
+        let reading = SensorReading { cpu_package_watts, battery_discharge_watts };
+        if sender.send(reading).is_err() {
+            break;
+        }
+        std::thread::sleep(SENSOR_INTERVAL);
+    }
+}
+
+impl PowerMonitor {
+    pub fn load() -> Self {
+        let days = storage::load_json(ENERGY_FILE);
+        Self { days, ..Default::default() }
+    }
+
+    fn save(&mut self) {
+        storage::save_json(ENERGY_FILE, &self.days);
+        self.dirty = false;
+        self.last_save = Some(Instant::now());
+    }
+
+    /// Energy used per recorded day
+    pub fn export_history(&self) -> CardHistory {
+        let mut history = CardHistory::new(vec!["date", "watt_hours"]);
+        for (day, wh) in &self.days {
+            history.rows.push(vec![day.clone(), format!("{:.1}", wh)]);
+        }
+        history
+    }
+}
+
+impl DevDashboard {
+    /// Updates the power estimate and adds the energy used since the last tick to today's total
+    pub(crate) fn update_power(&mut self) {
+        let settings = self.settings.power.clone();
+        let monitor = &mut self.power_monitor;
+        if !settings.enabled {
+            // Dropping the receiver stops the sensor thread
+            monitor.receiver = None;
+            monitor.estimate = None;
+            monitor.last_sample = None;
+            return;
+        }
+
+        match &monitor.receiver {
+            Some(receiver) => {
+                while let Ok(reading) = receiver.try_recv() {
+                    monitor.reading = reading;
+                }
+            }
+            None => {
+                debug!("Starting power sensor thread");
+                let (sender, receiver) = channel();
+                monitor.receiver = Some(receiver);
+                std::thread::spawn(move || run_sensor_thread(sender));
+            }
+        }
+
+        let (cpu_watts, cpu_measured) = match monitor.reading.cpu_package_watts {
+            Some(watts) => (watts, true),
+            None => (settings.cpu_tdp_watts * (self.current_cpu_usage.target / 100.0).clamp(0.0, 1.0), false),
+        };
+        let gpu_watts = self.gpu_info.as_ref().and_then(|gpu| gpu.power_draw);
+        let (total_watts, from_battery) = match monitor.reading.battery_discharge_watts {
+            Some(watts) if watts > 0.0 => (watts, true),
+            _ => (settings.base_watts + cpu_watts + gpu_watts.unwrap_or(0.0), false),
+        };
+        monitor.estimate = Some(PowerEstimate { cpu_watts, cpu_measured, gpu_watts, total_watts, from_battery });
+
+        let now = Instant::now();
+        if let Some(last) = monitor.last_sample {
+            let elapsed = now.duration_since(last);
+            if elapsed <= MAX_SAMPLE_GAP {
+                let today = Local::now().format("%Y-%m-%d").to_string();
+                *monitor.days.entry(today).or_default() += total_watts as f64 * elapsed.as_secs_f64() / 3600.0;
+                monitor.dirty = true;
+            }
+        }
+        monitor.last_sample = Some(now);
+
+        let save_due = match monitor.last_save {
+            Some(last) => last.elapsed() >= SAVE_INTERVAL,
+            None => true,
+        };
+        if monitor.dirty && save_due {
+            monitor.save();
+        }
+    }
+
+    /// Displays the power card
+    /// Shows the estimated draw, where it comes from, and energy used per day
+    pub(crate) fn show_power_card(&self, ui: &mut egui::Ui) {
+        self.show_card(ui, "Power", |ui| {
+            let Some(estimate) = self.power_monitor.estimate else {
+                ui.spinner();
+                return;
+            };
+            let price = self.settings.power.price_per_kwh;
+
+            ui.label(RichText::new(format!("{:.0} W", estimate.total_watts)).heading().strong())
+                .on_hover_text(if estimate.from_battery {
+                    "Measured battery discharge rate"
+                } else {
+                    "Estimated from CPU and GPU power plus the configured base load"
+                });
+            ui.label(format!("CPU: {:.1} W{}", estimate.cpu_watts, if estimate.cpu_measured { "" } else { " (estimated)" }));
+            if let Some(gpu) = estimate.gpu_watts {
+                ui.label(format!("GPU: {:.1} W", gpu));
+            }
+            ui.add_space(8.0);
+
+            let days: Vec<(&String, &f64)> = self.power_monitor.days.iter().rev().take(HISTORY_DAYS).collect();
+            let most = days.iter().map(|(_, wh)| **wh).fold(0.0, f64::max).max(1.0);
+            egui::Grid::new("power_history").num_columns(3).spacing([8.0, 4.0]).show(ui, |ui| {
+                for (day, wh) in days {
+                    ui.label(day.get(5..).unwrap_or(day));
+                    let (rect, _) = ui.allocate_exact_size(egui::vec2(100.0, 12.0), egui::Sense::hover());
+                    let mut bar = rect;
+                    bar.set_width(rect.width() * (*wh / most) as f32);
+                    ui.painter().rect_filled(rect, 3.0, egui::Color32::from_rgb(55, 65, 81));
+                    ui.painter().rect_filled(bar, 3.0, egui::Color32::from_rgb(202, 138, 4));
+                    let kwh = *wh / 1000.0;
+                    if price > 0.0 {
+                        ui.label(format!("{:.2} kWh  ({:.2})", kwh, kwh * price as f64));
+                    } else {
+                        ui.label(format!("{:.2} kWh", kwh));
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    /// Current draw and the last week's energy, for copying the card
+    pub(crate) fn power_card_values(&self) -> Vec<(String, String)> {
+        let Some(estimate) = self.power_monitor.estimate else { return Vec::new() };
+        let source = if estimate.from_battery { "measured battery discharge" } else { "estimated" };
+        let mut values = vec![
+            ("Total".to_string(), format!("{:.0} W ({})", estimate.total_watts, source)),
+            ("CPU".to_string(), format!("{:.1} W{}", estimate.cpu_watts, if estimate.cpu_measured { "" } else { " (estimated)" })),
+        ];
+        if let Some(gpu) = estimate.gpu_watts {
+            values.push(("GPU".to_string(), format!("{:.1} W", gpu)));
+        }
+        for (day, wh) in self.power_monitor.days.iter().rev().take(HISTORY_DAYS) {
+            values.push((day.clone(), format!("{:.2} kWh", wh / 1000.0)));
+        }
+        values
+    }
+
+    /// Settings section for power estimation
+    pub(crate) fn show_power_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+
+        changed |= ui.checkbox(&mut self.settings.power.enabled, "Show power card").changed();
+        if self.settings.power.enabled {
+            ui.horizontal(|ui| {
+                ui.label("CPU TDP (W):");
+                changed |= ui.add(egui::DragValue::new(&mut self.settings.power.cpu_tdp_watts)
+                    .clamp_range(5.0..=400.0))
+                    .on_hover_text("Only used when the CPU has no package power sensor")
+                    .changed();
+                ui.label("Base load (W):");
+                changed |= ui.add(egui::DragValue::new(&mut self.settings.power.base_watts)
+                    .clamp_range(0.0..=500.0)).changed();
+            });
+            ui.horizontal(|ui| {
+                ui.label("Price per kWh:");
+                changed |= ui.add(egui::DragValue::new(&mut self.settings.power.price_per_kwh)
+                    .speed(0.01)
+                    .clamp_range(0.0..=10.0)).changed();
+            });
+        }
+
+        if changed {
+            self.save_settings();
+        }
+    }
+}
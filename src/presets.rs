@@ -0,0 +1,106 @@
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use log::{info, warn};
+use crate::DevDashboard;
+
+/// A named app selection, e.g. "Dev setup", applied from the installer page or with --preset
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct AppPreset {
+    pub name: String,
+    pub apps: Vec<String>,      // App names as shown on the installer page
+}
+
+/// Persisted app selection presets
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct PresetSettings {
+    pub presets: Vec<AppPreset>,
+}
+
+impl PresetSettings {
+    /// Case-insensitive, so a shortcut's --preset doesn't have to match the capitalization
+    pub fn find(&self, name: &str) -> Option<&AppPreset> {
+        self.presets.iter().find(|preset| preset.name.trim().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+/// Name typed for saving the current selection
+#[derive(Default)]
+pub struct PresetEditor {
+    name: String,
+}
+
+impl DevDashboard {
+    /// Replaces the selection with the preset's apps that are in the catalog; returns false for an unknown preset
+    pub(crate) fn apply_preset(&mut self, name: &str) -> bool {
+        let Some(preset) = self.settings.presets.find(name) else {
+            warn!("No app preset named {:?}", name);
+            return false;
+        };
+        let apps: Vec<String> = preset.apps.iter()
+            .filter(|name| self.ninite_apps.iter().any(|app| &app.name == *name))
+            .cloned()
+            .collect();
+        if apps.len() < preset.apps.len() {
+            warn!("Preset {:?} lists {} apps that are no longer in the catalog", preset.name, preset.apps.len() - apps.len());
+        }
+        info!("Applied app preset {:?}: {:?}", preset.name, apps);
+        self.selected_apps = apps;
+        true
+    }
+
+    /// Installer page controls for applying and saving selection presets
+    pub(crate) fn show_presets(&mut self, ui: &mut egui::Ui) {
+        let grey = Color32::from_rgb(107, 114, 128);
+        let mut apply = None;
+        let mut remove = None;
+        ui.horizontal(|ui| {
+            ui.label("Presets:");
+            if self.settings.presets.presets.is_empty() {
+                ui.label(RichText::new("none saved yet").color(grey));
+            }
+            for (index, preset) in self.settings.presets.presets.iter().enumerate() {
+                let response = ui.button(&preset.name)
+                    .on_hover_text(format!("Select {}\nRight-click to delete", preset.apps.join(", ")));
+                if response.clicked() {
+                    apply = Some(preset.name.clone());
+                }
+                response.context_menu(|ui| {
+                    if ui.button("Delete preset").clicked() {
+                        remove = Some(index);
+                        ui.close_menu();
+                    }
+                });
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.preset_editor.name).hint_text("Dev setup").desired_width(160.0));
+            let name = self.preset_editor.name.trim().to_string();
+            let enabled = !name.is_empty() && !self.selected_apps.is_empty();
+            if ui.add_enabled(enabled, egui::Button::new("Save Selection as Preset"))
+                .on_hover_text("Also usable from a shortcut with --preset \"name\"")
+                .clicked() {
+                let preset = AppPreset { name: name.clone(), apps: self.selected_apps.clone() };
+                let presets = &mut self.settings.presets.presets;
+                match presets.iter_mut().find(|existing| existing.name.eq_ignore_ascii_case(&name)) {
+                    Some(existing) => *existing = preset,
+                    None => presets.push(preset),
+                }
+                info!("Saved app preset {:?}", name);
+                self.preset_editor.name.clear();
+                self.save_settings();
+            }
+        });
+
+        if let Some(name) = apply {
+            self.apply_preset(&name);
+        }
+        if let Some(index) = remove {
+            let preset = self.settings.presets.presets.remove(index);
+            info!("Deleted app preset {:?}", preset.name);
+            self.save_settings();
+        }
+    }
+}
@@ -0,0 +1,342 @@
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::time::{Duration, Instant};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use sysinfo::{Pid, PidExt, ProcessExt, SystemExt};
+use windows::Win32::Foundation::{BOOLEAN, NO_ERROR};
+use windows::Win32::NetworkManagement::IpHelper::{
+    GetExtendedTcpTable, GetPerTcp6ConnectionEStats, GetPerTcpConnectionEStats, SetPerTcp6ConnectionEStats,
+    SetPerTcpConnectionEStats, MIB_TCP6ROW, MIB_TCP6TABLE_OWNER_PID, MIB_TCPROW_LH, MIB_TCPROW_LH_0,
+    MIB_TCPTABLE_OWNER_PID, MIB_TCP_STATE, TCP_ESTATS_DATA_ROD_v0, TCP_ESTATS_DATA_RW_v0,
+    TCP_TABLE_OWNER_PID_CONNECTIONS, TcpConnectionEstatsData,
+};
+use windows::Win32::Networking::WinSock::{IN6_ADDR, IN6_ADDR_0};
+use log::{debug, warn};
+use crate::ports::{read_table, AF_INET, AF_INET6};
+use crate::{elevation, DevDashboard};
+
+/// How often the sampler thread reads the connection table
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Persisted per-process network card configuration
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ProcessNetworkSettings {
+    pub show_card: bool,
+    pub count: usize,               // Rows shown on the card
+}
+
+impl Default for ProcessNetworkSettings {
+    fn default() -> Self {
+        Self { show_card: false, count: 8 }
+    }
+}
+
+/// TCP activity of one process over the last sample interval
+#[derive(Clone)]
+pub struct ProcessTraffic {
+    pub pid: u32,
+    pub connections: usize,
+    pub received: Option<f64>,      // Bytes per second; None without per-connection statistics
+    pub sent: Option<f64>,
+}
+
+impl ProcessTraffic {
+    fn total(&self) -> f64 {
+        self.received.unwrap_or(0.0) + self.sent.unwrap_or(0.0)
+    }
+}
+
+/// A connection's addresses in the form the statistics functions take
+#[derive(Clone, Copy)]
+enum ConnectionRow {
+    V4(MIB_TCPROW_LH),
+    V6(MIB_TCP6ROW),
+}
+
+/// Identifies a connection between samples; ports keep their raw network byte order
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct ConnectionKey {
+    local: [u8; 16],
+    local_port: u32,
+    remote: [u8; 16],
+    remote_port: u32,
+    pid: u32,
+}
+
+fn v4_bytes(raw: u32) -> [u8; 16] {
+    let mut bytes = [0u8; 16];
+    bytes[..4].copy_from_slice(&raw.to_ne_bytes());
+    bytes
+}
+
+/// Open TCP connections with their owning process, IPv4 and IPv6
+fn connections() -> Vec<(ConnectionKey, ConnectionRow)> {
+    let mut connections = Vec::new();
+
+    if let Some(buffer) = read_table(|table, size| unsafe {
+        GetExtendedTcpTable(table, size, false, AF_INET, TCP_TABLE_OWNER_PID_CONNECTIONS, 0)
+    }) {
+        let table = unsafe { &*(buffer.as_ptr() as *const MIB_TCPTABLE_OWNER_PID) };
+        let rows = unsafe { std::slice::from_raw_parts(table.table.as_ptr(), table.dwNumEntries as usize) };
+        connections.extend(rows.iter().map(|row| (
+            ConnectionKey {
+                local: v4_bytes(row.dwLocalAddr),
+                local_port: row.dwLocalPort,
+                remote: v4_bytes(row.dwRemoteAddr),
+                remote_port: row.dwRemotePort,
+                pid: row.dwOwningPid,
+            },
+            ConnectionRow::V4(MIB_TCPROW_LH {
+                Anonymous: MIB_TCPROW_LH_0 { dwState: row.dwState },
+                dwLocalAddr: row.dwLocalAddr,
+                dwLocalPort: row.dwLocalPort,
+                dwRemoteAddr: row.dwRemoteAddr,
+                dwRemotePort: row.dwRemotePort,
+            }),
+        )));
+    }
+
+    if let Some(buffer) = read_table(|table, size| unsafe {
+        GetExtendedTcpTable(table, size, false, AF_INET6, TCP_TABLE_OWNER_PID_CONNECTIONS, 0)
+    }) {
+        let table = unsafe { &*(buffer.as_ptr() as *const MIB_TCP6TABLE_OWNER_PID) };
+        let rows = unsafe { std::slice::from_raw_parts(table.table.as_ptr(), table.dwNumEntries as usize) };
+        connections.extend(rows.iter().map(|row| (
+            ConnectionKey {
+                local: row.ucLocalAddr,
+                local_port: row.dwLocalPort,
+                remote: row.ucRemoteAddr,
+                remote_port: row.dwRemotePort,
+                pid: row.dwOwningPid,
+            },
+            ConnectionRow::V6(MIB_TCP6ROW {
+                State: MIB_TCP_STATE(row.dwState as i32),
+                LocalAddr: IN6_ADDR { u: IN6_ADDR_0 { Byte: row.ucLocalAddr } },
+                dwLocalScopeId: row.dwLocalScopeId,
+                dwLocalPort: row.dwLocalPort,
+                RemoteAddr: IN6_ADDR { u: IN6_ADDR_0 { Byte: row.ucRemoteAddr } },
+                dwRemoteScopeId: row.dwRemoteScopeId,
+                dwRemotePort: row.dwRemotePort,
+            }),
+        )));
+    }
+
+    connections
+}
+
+/// Views a statistics struct as the byte buffer the EStats functions take
+fn as_bytes<T>(value: &mut T) -> &mut [u8] {
+    unsafe { std::slice::from_raw_parts_mut(value as *mut T as *mut u8, std::mem::size_of::<T>()) }
+}
+
+/// Turns on byte counting for a connection; Windows only allows this for administrators
+fn enable_statistics(row: &ConnectionRow) -> bool {
+    let mut settings = TCP_ESTATS_DATA_RW_v0 { EnableCollection: BOOLEAN(1) };
+    let settings = as_bytes(&mut settings);
+    let result = unsafe {
+        match row {
+            ConnectionRow::V4(row) => SetPerTcpConnectionEStats(row, TcpConnectionEstatsData, settings, 0, 0),
+            ConnectionRow::V6(row) => SetPerTcp6ConnectionEStats(row, TcpConnectionEstatsData, settings, 0, 0),
+        }
+    };
+    result == NO_ERROR.0
+}
+
+/// Bytes received and sent on a connection since counting was enabled
+fn read_statistics(row: &ConnectionRow) -> Option<(u64, u64)> {
+    let mut data = unsafe { std::mem::zeroed::<TCP_ESTATS_DATA_ROD_v0>() };
+    let result = unsafe {
+        match row {
+            ConnectionRow::V4(row) => GetPerTcpConnectionEStats(row, TcpConnectionEstatsData, None, 0, None, 0, Some(as_bytes(&mut data)), 0),
+            ConnectionRow::V6(row) => GetPerTcp6ConnectionEStats(row, TcpConnectionEstatsData, None, 0, None, 0, Some(as_bytes(&mut data)), 0),
+        }
+    };
+    (result == NO_ERROR.0).then_some((data.DataBytesIn, data.DataBytesOut))
+}
+
+/// Samples the connection table until the receiver is dropped. Byte counts come from the
+/// per-connection statistics when elevated; otherwise only connections are counted.
+fn run_sampler(sender: Sender<Vec<ProcessTraffic>>, measure: bool) {
+    let mut previous: HashMap<ConnectionKey, (u64, u64)> = HashMap::new();
+    let mut last_sample = Instant::now();
+    loop {
+        let elapsed = last_sample.elapsed().as_secs_f64().max(0.001);
+        last_sample = Instant::now();
+
+        let mut processes: HashMap<u32, ProcessTraffic> = HashMap::new();
+        let mut counters = HashMap::new();
+        for (key, row) in connections() {
+            let traffic = processes.entry(key.pid).or_insert_with(|| ProcessTraffic {
+                pid: key.pid,
+                connections: 0,
+                received: measure.then_some(0.0),
+                sent: measure.then_some(0.0),
+            });
+            traffic.connections += 1;
+            if !measure {
+                continue;
+            }
+            // New connections start counting now, so their first sample has nothing to compare against
+            let Some(last) = previous.get(&key) else {
+                if enable_statistics(&row) {
+                    counters.insert(key, read_statistics(&row).unwrap_or_default());
+                }
+                continue;
+            };
+            if let Some((received, sent)) = read_statistics(&row) {
+                traffic.received = traffic.received.map(|rate| rate + received.saturating_sub(last.0) as f64 / elapsed);
+                traffic.sent = traffic.sent.map(|rate| rate + sent.saturating_sub(last.1) as f64 / elapsed);
+                counters.insert(key, (received, sent));
+            }
+        }
+        previous = counters;
+
+        if sender.send(processes.into_values().collect()).is_err() {
+            break;
+        }
+        std::thread::sleep(SAMPLE_INTERVAL);
+    }
+    debug!("Per-process network sampler stopped");
+}
+
+/// Latest per-process traffic from the sampler thread, which only runs while the card is shown
+#[derive(Default)]
+pub struct ProcessNetworkMonitor {
+    receiver: Option<Receiver<Vec<ProcessTraffic>>>,
+    latest: Vec<ProcessTraffic>,
+    measured: bool,                 // Whether byte rates are available, i.e. the dashboard is elevated
+}
+
+impl ProcessNetworkMonitor {
+    pub fn update(&mut self, settings: &ProcessNetworkSettings) {
+        if !settings.show_card {
+            // Dropping the receiver ends the sampler thread
+            self.receiver = None;
+            self.latest.clear();
+            return;
+        }
+        let receiver = self.receiver.get_or_insert_with(|| {
+            let (sender, receiver) = channel();
+            self.measured = elevation::is_elevated();
+            let measure = self.measured;
+            if let Err(e) = std::thread::Builder::new()
+                .name("process-network".to_string())
+                .spawn(move || run_sampler(sender, measure)) {
+                warn!("Could not start the per-process network sampler: {}", e);
+            }
+            receiver
+        });
+        loop {
+            match receiver.try_recv() {
+                Ok(latest) => self.latest = latest,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.receiver = None;
+                    break;
+                }
+            }
+        }
+    }
+}
+
+fn rate_text(bytes_per_second: Option<f64>) -> String {
+    match bytes_per_second {
+        Some(rate) => {
+            let (value, unit) = DevDashboard::format_bytes(rate as u64);
+            format!("{:.1} {}/s", value, unit)
+        }
+        None => "-".to_string(),
+    }
+}
+
+impl DevDashboard {
+    /// Busiest processes by bandwidth when measured, otherwise by open connections, with their names
+    fn process_network_rows(&self) -> Vec<(String, ProcessTraffic)> {
+        let mut rows: Vec<ProcessTraffic> = self.process_network.latest.iter()
+            // PID 0 owns connections in TIME_WAIT, which belong to no process anymore
+            .filter(|traffic| traffic.pid != 0)
+            .cloned()
+            .collect();
+        rows.sort_by(|a, b| b.total().total_cmp(&a.total()).then(b.connections.cmp(&a.connections)));
+        rows.truncate(self.settings.process_network.count);
+        rows.into_iter()
+            .map(|traffic| {
+                let name = self.sys.process(Pid::from_u32(traffic.pid))
+                    .map(|process| process.name().to_string())
+                    .unwrap_or_else(|| format!("PID {}", traffic.pid));
+                (name, traffic)
+            })
+            .collect()
+    }
+
+    /// Displays the processes using the network the most
+    pub(crate) fn show_process_network_card(&mut self, ui: &mut egui::Ui) {
+        let rows = self.process_network_rows();
+        let grey = Color32::from_rgb(107, 114, 128);
+        self.show_card(ui, "Network by Process", |ui| {
+            egui::Grid::new("process_network_grid")
+                .striped(true)
+                .spacing([12.0, 4.0])
+                .show(ui, |ui| {
+                    ui.label(RichText::new("Name").strong());
+                    ui.label(RichText::new("Connections").strong());
+                    if self.process_network.measured {
+                        ui.label(RichText::new("↓").strong());
+                        ui.label(RichText::new("↑").strong());
+                    }
+                    ui.end_row();
+
+                    for (name, traffic) in &rows {
+                        ui.label(name).on_hover_text(format!("PID {}", traffic.pid));
+                        ui.label(traffic.connections.to_string());
+                        if self.process_network.measured {
+                            ui.label(rate_text(traffic.received));
+                            ui.label(rate_text(traffic.sent));
+                        }
+                        ui.end_row();
+                    }
+                });
+            if rows.is_empty() {
+                ui.label(RichText::new("Waiting for connection readings...").color(grey));
+            }
+            if !self.process_network.measured {
+                ui.add_space(4.0);
+                ui.label(RichText::new("Run as administrator to see bandwidth per process").color(grey));
+            }
+        });
+    }
+
+    /// Each listed process with its connections and rates, for copying the card
+    pub(crate) fn process_network_card_values(&self) -> Vec<(String, String)> {
+        self.process_network_rows()
+            .into_iter()
+            .map(|(name, traffic)| {
+                let mut value = format!("{} connections", traffic.connections);
+                if self.process_network.measured {
+                    value.push_str(&format!(", ↓ {}, ↑ {}", rate_text(traffic.received), rate_text(traffic.sent)));
+                }
+                (format!("{} ({})", name, traffic.pid), value)
+            })
+            .collect()
+    }
+
+    /// Settings section for the per-process network card
+    pub(crate) fn show_process_network_settings(&mut self, ui: &mut egui::Ui) {
+        let settings = &mut self.settings.process_network;
+        let mut changed = ui.checkbox(&mut settings.show_card, "Show network by process card")
+            .on_hover_text("Bandwidth per process needs the dashboard running as administrator; otherwise connections are counted")
+            .changed();
+        if settings.show_card {
+            ui.horizontal(|ui| {
+                ui.label("Processes listed:");
+                changed |= ui.add(egui::DragValue::new(&mut settings.count).clamp_range(3..=50)).changed();
+            });
+        }
+        if changed {
+            self.save_settings();
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::HashSet;
+use std::os::windows::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use eframe::egui;
+use egui::{Color32, RichText};
+use windows::core::{PCWSTR, PWSTR};
+use windows::Win32::Foundation::{ERROR_MORE_DATA, NO_ERROR};
+use windows::Win32::System::RestartManager::{
+    RmEndSession, RmGetList, RmRegisterResources, RmStartSession, CCH_RM_SESSION_KEY, RM_PROCESS_INFO,
+};
+use log::{debug, info};
+use crate::ports;
+
+/// Files registered when searching a folder; Restart Manager slows down a lot beyond this
+const MAX_FOLDER_FILES: usize = 2000;
+
+/// A process that has the searched file open or uses the searched port
+pub struct SearchHit {
+    pub pid: u32,
+    pub app_name: String,   // Restart Manager's friendly name; empty for port matches
+    pub detail: String,
+}
+
+/// Hits for a query, or why the lookup failed
+type SearchResult = Result<Vec<SearchHit>, String>;
+
+/// Reverse lookup in the Processes tab: which process holds a file or a port
+#[derive(Default)]
+pub struct ProcessSearch {
+    query: String,
+    pub only_matches: bool,     // Limit the process list to the hits
+    receiver: Option<Receiver<(String, SearchResult)>>,
+    results: Option<(String, SearchResult)>,  // Query the results belong to
+}
+
+fn wide(path: &Path) -> Vec<u16> {
+    path.as_os_str().encode_wide().chain(std::iter::once(0)).collect()
+}
+
+fn utf16_until_nul(text: &[u16]) -> String {
+    let end = text.iter().position(|c| *c == 0).unwrap_or(text.len());
+    String::from_utf16_lossy(&text[..end])
+}
+
+/// Files in a folder and its subfolders, up to MAX_FOLDER_FILES
+fn folder_files(folder: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![folder.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else { continue };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            match entry.file_type() {
+                Ok(kind) if kind.is_dir() => pending.push(path),
+                Ok(_) => files.push(path),
+                Err(_) => {}
+            }
+            if files.len() >= MAX_FOLDER_FILES {
+                return files;
+            }
+        }
+    }
+    files
+}
+
+/// Asks Restart Manager which processes have handles to the files, the same check installers use
+fn processes_using_files(files: &[PathBuf]) -> SearchResult {
+    let mut session = 0u32;
+    let mut key = [0u16; CCH_RM_SESSION_KEY as usize + 1];
+    let result = unsafe { RmStartSession(&mut session, 0, PWSTR(key.as_mut_ptr())) };
+    if result != NO_ERROR.0 {
+        return Err(format!("Could not start a Restart Manager session: error {}", result));
+    }
+    let hits = list_session_processes(session, files);
+    unsafe { RmEndSession(session) };
+    hits
+}
+
+fn list_session_processes(session: u32, files: &[PathBuf]) -> SearchResult {
+    let names: Vec<Vec<u16>> = files.iter().map(|file| wide(file)).collect();
+    let pointers: Vec<PCWSTR> = names.iter().map(|name| PCWSTR(name.as_ptr())).collect();
+    let result = unsafe { RmRegisterResources(session, Some(&pointers), None, None) };
+    if result != NO_ERROR.0 {
+        return Err(format!("Could not register the files: error {}", result));
+    }
+
+    let mut needed = 0u32;
+    let mut reasons = 0u32;
+    // Processes can open the file between calls, so retry a few times
+    for _ in 0..4 {
+        let mut infos = vec![RM_PROCESS_INFO::default(); needed as usize];
+        let mut count = needed;
+        let buffer = (!infos.is_empty()).then_some(infos.as_mut_ptr());
+        let result = unsafe { RmGetList(session, &mut needed, &mut count, buffer, &mut reasons) };
+        if result == NO_ERROR.0 {
+            infos.truncate(count as usize);
+            return Ok(infos.iter().map(|info| {
+                let service = utf16_until_nul(&info.strServiceShortName);
+                SearchHit {
+                    pid: info.Process.dwProcessId,
+                    app_name: utf16_until_nul(&info.strAppName),
+                    detail: if service.is_empty() { "Has the file open".to_string() } else { format!("Service {}", service) },
+                }
+            }).collect());
+        }
+        if result != ERROR_MORE_DATA.0 {
+            return Err(format!("Could not list the processes using the files: error {}", result));
+        }
+    }
+    Err("The list of processes kept changing while reading it".to_string())
+}
+
+fn processes_using_port(port: u16) -> Vec<SearchHit> {
+    let mut hits: Vec<SearchHit> = ports::sockets_on_port(port)
+        .into_iter()
+        .map(|socket| {
+            let local = format!("{} {}:{}", socket.protocol.label(), socket.local_addr, socket.local_port);
+            let detail = match socket.remote {
+                Some((addr, remote_port)) => format!("{} → {}:{}", local, addr, remote_port),
+                None if socket.protocol == ports::SocketProtocol::Tcp => format!("{} listening", local),
+                None => local,
+            };
+            SearchHit { pid: socket.pid, app_name: String::new(), detail }
+        })
+        .collect();
+    hits.sort_by_key(|hit| hit.pid);
+    hits
+}
+
+/// A bare number, optionally written ":8080", is a port; anything else is a path
+fn run_search(query: &str) -> SearchResult {
+    if let Ok(port) = query.trim_start_matches(':').parse::<u16>() {
+        return Ok(processes_using_port(port));
+    }
+    let path = PathBuf::from(query.trim_matches('"'));
+    let files = if path.is_dir() {
+        folder_files(&path)
+    } else if path.exists() {
+        vec![path]
+    } else {
+        return Err(format!("{} does not exist", path.display()));
+    };
+    if files.is_empty() {
+        return Ok(Vec::new());
+    }
+    processes_using_files(&files)
+}
+
+impl ProcessSearch {
+    pub fn is_searching(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    /// PIDs to keep in the process list, or None when it should not be limited
+    pub fn pid_filter(&self) -> Option<HashSet<u32>> {
+        match &self.results {
+            Some((_, Ok(hits))) if self.only_matches => Some(hits.iter().map(|hit| hit.pid).collect()),
+            _ => None,
+        }
+    }
+
+    fn start(&mut self) {
+        let query = self.query.trim().to_string();
+        if query.is_empty() || self.is_searching() {
+            return;
+        }
+        debug!("Searching for processes using {}", query);
+        let (sender, receiver) = channel();
+        self.receiver = Some(receiver);
+        std::thread::spawn(move || {
+            let result = run_search(&query);
+            sender.send((query, result)).ok();
+        });
+    }
+
+    fn poll(&mut self) {
+        let Some(receiver) = &self.receiver else { return };
+        if let Ok((query, result)) = receiver.try_recv() {
+            if let Ok(hits) = &result {
+                info!("{} processes are using {}", hits.len(), query);
+            }
+            self.results = Some((query, result));
+            self.receiver = None;
+        }
+    }
+
+    /// Search box and results; `process_name` resolves PIDs against the current process list
+    pub fn show(&mut self, ui: &mut egui::Ui, process_name: impl Fn(u32) -> Option<String>) {
+        self.poll();
+
+        ui.horizontal(|ui| {
+            ui.label("Find by file or port:");
+            let response = ui.add(egui::TextEdit::singleline(&mut self.query)
+                .hint_text(r"C:\path\to\file.dll or 8080")
+                .desired_width(280.0));
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if self.is_searching() {
+                ui.spinner();
+            } else if ui.button("Find").clicked() || submitted {
+                self.start();
+            }
+            if self.results.is_some() {
+                ui.checkbox(&mut self.only_matches, "Only show these");
+                if ui.small_button("✖").on_hover_text("Clear").clicked() {
+                    self.results = None;
+                }
+            }
+        });
+
+        let Some((query, result)) = &self.results else { return };
+        match result {
+            Err(error) => {
+                ui.colored_label(Color32::from_rgb(220, 50, 50), error);
+            }
+            Ok(hits) if hits.is_empty() => {
+                ui.label(RichText::new(format!("No process is using {}", query)).color(Color32::from_rgb(156, 163, 175)));
+            }
+            Ok(hits) => {
+                egui::Grid::new("process_search_grid").spacing([16.0, 4.0]).show(ui, |ui| {
+                    for hit in hits {
+                        let name = process_name(hit.pid).unwrap_or_else(|| hit.app_name.clone());
+                        ui.label(RichText::new(name).strong());
+                        ui.label(hit.pid.to_string());
+                        ui.label(&hit.detail);
+                        ui.end_row();
+                    }
+                });
+            }
+        }
+    }
+}
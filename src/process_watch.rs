@@ -0,0 +1,243 @@
+use std::collections::{HashMap, VecDeque};
+use chrono::{DateTime, Local, TimeZone};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use sysinfo::{PidExt, ProcessExt, SystemExt};
+use log::info;
+use crate::notifications::NotificationSource;
+use crate::{storage, toast, DevDashboard};
+
+/// Finished runs are stored next to settings.json
+const RUNS_FILE: &str = "process_runs.json";
+const MAX_RUNS: usize = 200;
+/// Processes found later than this after starting were already running, e.g. when a rule was added
+const START_GRACE_SECS: i64 = 10;
+
+/// An executable to report on when it starts and exits
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct WatchedProcess {
+    pub enabled: bool,
+    pub process: String,        // Executable name such as msbuild.exe, case-insensitive
+    pub notify_start: bool,
+    pub notify_exit: bool,
+    pub min_secs: u64,          // Runs shorter than this finish without a notification
+}
+
+impl Default for WatchedProcess {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            process: String::new(),
+            notify_start: false,
+            notify_exit: true,
+            min_secs: 10,
+        }
+    }
+}
+
+/// Persisted executables to watch
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ProcessWatchSettings {
+    pub processes: Vec<WatchedProcess>,
+}
+
+impl ProcessWatchSettings {
+    pub fn is_active(&self) -> bool {
+        self.processes.iter().any(|watched| watched.enabled && !watched.process.trim().is_empty())
+    }
+}
+
+/// One finished run of a watched executable
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProcessRun {
+    pub process: String,
+    pub pid: u32,
+    pub started: DateTime<Local>,
+    pub duration_secs: u64,
+}
+
+struct RunningProcess {
+    process: String,
+    started: DateTime<Local>,
+}
+
+/// Watched processes that are running now and the runs that have finished
+#[derive(Default)]
+pub struct ProcessWatcher {
+    running: HashMap<u32, RunningProcess>,
+    runs: VecDeque<ProcessRun>,     // Newest first
+}
+
+/// "1h 05m", "4m 12s" or "37s"
+pub fn format_elapsed(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3600, secs / 60 % 60),
+    }
+}
+
+impl ProcessWatcher {
+    pub fn load() -> Self {
+        let runs = storage::load_json(RUNS_FILE);
+        Self { runs, ..Default::default() }
+    }
+
+    fn save(&self) {
+        storage::save_json(RUNS_FILE, &self.runs);
+    }
+
+    fn push_run(&mut self, run: ProcessRun) {
+        if self.runs.len() == MAX_RUNS {
+            self.runs.pop_back();
+        }
+        self.runs.push_front(run);
+        self.save();
+    }
+
+    /// Running and recent runs for the Processes tab
+    pub fn show_runs(&mut self, ui: &mut egui::Ui, active: bool) {
+        if !active && self.runs.is_empty() {
+            return;
+        }
+        let title = format!("Watched processes ({} running)", self.running.len());
+        let mut cleared = false;
+        egui::CollapsingHeader::new(title)
+            .id_source("process_watch_runs")
+            .show(ui, |ui| {
+                let now = Local::now();
+                let mut running: Vec<(&u32, &RunningProcess)> = self.running.iter().collect();
+                running.sort_by_key(|(_, running)| running.started);
+                for (pid, process) in running {
+                    ui.horizontal(|ui| {
+                        ui.colored_label(Color32::from_rgb(22, 163, 74), "●");
+                        ui.label(RichText::new(&process.process).strong());
+                        ui.label(format!("PID {}", pid));
+                        let elapsed = (now - process.started).num_seconds().max(0) as u64;
+                        ui.label(format!("running for {}", format_elapsed(elapsed)));
+                    });
+                }
+                if self.runs.is_empty() {
+                    ui.label(RichText::new("No finished runs yet").color(Color32::from_rgb(107, 114, 128)));
+                    return;
+                }
+                egui::Grid::new("process_runs_grid").striped(true).spacing([16.0, 4.0]).show(ui, |ui| {
+                    for run in self.runs.iter().take(20) {
+                        ui.label(RichText::new(run.started.format("%m-%d %H:%M").to_string())
+                            .color(Color32::from_rgb(156, 163, 175)));
+                        ui.label(&run.process);
+                        ui.label(format_elapsed(run.duration_secs));
+                        ui.end_row();
+                    }
+                });
+                if ui.small_button("Clear history").clicked() {
+                    cleared = true;
+                }
+            });
+        if cleared {
+            self.runs.clear();
+            self.save();
+        }
+        ui.add_space(8.0);
+    }
+}
+
+impl DevDashboard {
+    /// Notices watched executables starting and exiting; expects processes to be freshly refreshed
+    pub(crate) fn update_process_watch(&mut self) {
+        if !self.settings.process_watch.is_active() {
+            self.process_watcher.running.clear();
+            return;
+        }
+        let watched: Vec<&WatchedProcess> = self.settings.process_watch.processes
+            .iter()
+            .filter(|watched| watched.enabled && !watched.process.trim().is_empty())
+            .collect();
+        let find = |name: &str| watched.iter().find(|watched| watched.process.trim().eq_ignore_ascii_case(name)).copied();
+
+        for (pid, process) in self.sys.processes() {
+            let pid = pid.as_u32();
+            if self.process_watcher.running.contains_key(&pid) {
+                continue;
+            }
+            let Some(rule) = find(process.name()) else { continue };
+            let started = Local.timestamp_opt(process.start_time() as i64, 0).single().unwrap_or_else(Local::now);
+            if (Local::now() - started).num_seconds() < START_GRACE_SECS {
+                info!("Watched process {} started (PID {})", process.name(), pid);
+                if rule.notify_start {
+                    let (title, body) = (format!("{} started", process.name()), format!("PID {}", pid));
+                    toast::show_toast(&title, &body);
+                    self.notifications.push(NotificationSource::Watcher, &title, &body);
+                }
+            }
+            self.process_watcher.running.insert(pid, RunningProcess { process: process.name().to_string(), started });
+        }
+
+        let exited: Vec<u32> = self.process_watcher.running
+            .iter()
+            .filter(|(pid, running)| match self.sys.process(sysinfo::Pid::from_u32(**pid)) {
+                // A reused PID belongs to a different executable
+                Some(process) => !process.name().eq_ignore_ascii_case(&running.process),
+                None => true,
+            })
+            .map(|(pid, _)| *pid)
+            .collect();
+        for pid in exited {
+            let Some(running) = self.process_watcher.running.remove(&pid) else { continue };
+            let duration_secs = (Local::now() - running.started).num_seconds().max(0) as u64;
+            info!("Watched process {} (PID {}) finished after {}", running.process, pid, format_elapsed(duration_secs));
+            if let Some(rule) = find(&running.process) {
+                if rule.notify_exit && duration_secs >= rule.min_secs {
+                    let title = format!("{} finished", running.process);
+                    let body = format!("Ran for {} (PID {})", format_elapsed(duration_secs), pid);
+                    toast::show_toast(&title, &body);
+                    self.notifications.push(NotificationSource::Watcher, &title, &body);
+                }
+            }
+            self.process_watcher.push_run(ProcessRun { process: running.process, pid, started: running.started, duration_secs });
+        }
+    }
+
+    /// Settings section for executables that notify when they start or finish
+    pub(crate) fn show_process_watch_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        let mut removed = None;
+
+        ui.label("Process Notifications:");
+        for (index, watched) in self.settings.process_watch.processes.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                changed |= ui.checkbox(&mut watched.enabled, "").changed();
+                changed |= ui.add(egui::TextEdit::singleline(&mut watched.process)
+                    .hint_text("msbuild.exe")
+                    .desired_width(160.0)).changed();
+                changed |= ui.checkbox(&mut watched.notify_start, "On start").changed();
+                changed |= ui.checkbox(&mut watched.notify_exit, "On finish").changed();
+                ui.label("if it ran at least");
+                changed |= ui.add(egui::DragValue::new(&mut watched.min_secs).clamp_range(0..=86_400).suffix(" s")).changed();
+                if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                    removed = Some(index);
+                }
+            });
+        }
+        if let Some(index) = removed {
+            self.settings.process_watch.processes.remove(index);
+            changed = true;
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("Watch an executable").clicked() {
+                self.settings.process_watch.processes.push(WatchedProcess::default());
+                changed = true;
+            }
+            ui.label(RichText::new("Shows a Windows notification, and records how long each run took")
+                .color(Color32::from_rgb(107, 114, 128)));
+        });
+
+        if changed {
+            self.save_settings();
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use eframe::egui;
+use egui::RichText;
+use sysinfo::{PidExt, ProcessExt, SystemExt};
+use log::{debug, warn};
+use crate::process_search::ProcessSearch;
+use crate::signature::{self, SignatureInfo, SignatureStatus};
+use crate::DevDashboard;
+
+/// State of the Processes tab
+#[derive(Default)]
+pub struct ProcessView {
+    pub filter: String,
+    pub flagged_only: bool,          // Only list unsigned binaries running from temp directories
+    signatures: HashMap<PathBuf, SignatureInfo>, // Verified binaries, kept for the session
+    verifying: HashSet<PathBuf>,     // Binaries queued for verification
+    receiver: Option<Receiver<(PathBuf, SignatureInfo)>>,
+    search: ProcessSearch,           // Which process has a file or port open
+}
+
+impl ProcessView {
+    /// Collects signature results from the background verifier
+    fn poll(&mut self) {
+        if let Some(receiver) = &self.receiver {
+            while let Ok((path, info)) = receiver.try_recv() {
+                if info.status == SignatureStatus::Unsigned && is_in_temp_dir(&path) {
+                    warn!("Unsigned binary running from temp directory: {:?}", path);
+                }
+                self.verifying.remove(&path);
+                self.signatures.insert(path, info);
+            }
+        }
+    }
+
+    /// Queues binaries that haven't been verified yet on a background thread.
+    /// New binaries wait until the current batch has finished.
+    fn verify_missing(&mut self, paths: Vec<PathBuf>) {
+        if !self.verifying.is_empty() {
+            return;
+        }
+        let missing: Vec<PathBuf> = paths
+            .into_iter()
+            .filter(|path| !self.signatures.contains_key(path) && !self.verifying.contains(path))
+            .collect();
+        if missing.is_empty() {
+            return;
+        }
+
+        debug!("Verifying signatures of {} binaries", missing.len());
+        self.verifying.extend(missing.iter().cloned());
+        let (sender, receiver) = channel();
+        self.receiver = Some(receiver);
+
+        std::thread::spawn(move || {
+            for path in missing {
+                let info = signature::verify(&path);
+                if sender.send((path, info)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+}
+
+/// Whether a binary lives under a temporary directory, a common sign of droppers and unpacked installers
+fn is_in_temp_dir(path: &Path) -> bool {
+    let temp_dir = std::env::temp_dir();
+    if path.starts_with(&temp_dir) {
+        return true;
+    }
+    path.components().any(|component| {
+        let part = component.as_os_str().to_string_lossy().to_lowercase();
+        part == "temp" || part == "tmp"
+    })
+}
+
+impl DevDashboard {
+    /// Displays the Processes tab
+    /// Lists running processes with their binary's signature status and signer
+    pub(crate) fn show_processes_tab(&mut self, ui: &mut egui::Ui) {
+        self.process_view.poll();
+
+        let mut processes: Vec<(u32, String, Option<PathBuf>)> = self.sys.processes()
+            .iter()
+            .map(|(pid, process)| {
+                let exe = process.exe();
+                let exe = (!exe.as_os_str().is_empty()).then(|| exe.to_path_buf());
+                (pid.as_u32(), process.name().to_string(), exe)
+            })
+            .collect();
+        processes.sort_by(|a, b| a.1.to_lowercase().cmp(&b.1.to_lowercase()).then(a.0.cmp(&b.0)));
+
+        let unique_paths: HashSet<PathBuf> = processes.iter().filter_map(|(_, _, exe)| exe.clone()).collect();
+        self.process_view.verify_missing(unique_paths.into_iter().collect());
+
+        let view = &mut self.process_view;
+        let watchdog = &self.watchdog;
+        let watchdog_active = self.settings.watchdog.is_active();
+        let process_watcher = &mut self.process_watcher;
+        let process_watch_active = self.settings.process_watch.is_active();
+        let job_watch = &self.job_watch;
+        let job_watch_active = self.settings.job_watch.is_active();
+
+        egui::Frame::none()
+            .inner_margin(egui::style::Margin::same(10.0))
+            .show(ui, |ui| {
+                ui.heading("Processes");
+                ui.add_space(8.0);
+                watchdog.show_events(ui, watchdog_active);
+                process_watcher.show_runs(ui, process_watch_active);
+                job_watch.show_events(ui, job_watch_active);
+
+                ui.horizontal(|ui| {
+                    ui.label("Filter:");
+                    ui.text_edit_singleline(&mut view.filter);
+                    ui.checkbox(&mut view.flagged_only, "Unsigned in temp dirs only");
+                    if !view.verifying.is_empty() {
+                        ui.spinner();
+                        ui.label(format!("Verifying {} binaries...", view.verifying.len()));
+                    }
+                });
+                view.search.show(ui, |pid| processes.iter()
+                    .find(|(process_pid, _, _)| *process_pid == pid)
+                    .map(|(_, name, _)| name.clone()));
+                ui.add_space(8.0);
+
+                let filter = view.filter.to_lowercase();
+                let pid_filter = view.search.pid_filter();
+
+                egui::ScrollArea::vertical().id_source("processes_scroll").show(ui, |ui| {
+                    egui::Grid::new("processes_grid")
+                        .striped(true)
+                        .spacing([16.0, 6.0])
+                        .show(ui, |ui| {
+                            ui.label(RichText::new("Name").strong());
+                            ui.label(RichText::new("PID").strong());
+                            ui.label(RichText::new("Signature").strong());
+                            ui.label(RichText::new("Signer").strong());
+                            ui.label(RichText::new("Path").strong());
+                            ui.end_row();
+
+                            for (pid, name, exe) in &processes {
+                                let signature = exe.as_ref().and_then(|path| view.signatures.get(path));
+                                let flagged = matches!(signature, Some(info) if info.status == SignatureStatus::Unsigned)
+                                    && exe.as_deref().map(is_in_temp_dir).unwrap_or(false);
+
+                                if view.flagged_only && !flagged {
+                                    continue;
+                                }
+                                if pid_filter.as_ref().map(|pids| !pids.contains(pid)).unwrap_or(false) {
+                                    continue;
+                                }
+                                let path_text = exe.as_ref().map(|path| path.display().to_string()).unwrap_or_default();
+                                if !filter.is_empty()
+                                    && !name.to_lowercase().contains(&filter)
+                                    && !path_text.to_lowercase().contains(&filter) {
+                                    continue;
+                                }
+
+                                if flagged {
+                                    ui.colored_label(egui::Color32::from_rgb(220, 50, 50), format!("⚠ {}", name))
+                                        .on_hover_text("Unsigned binary running from a temporary directory");
+                                } else {
+                                    ui.label(name);
+                                }
+                                ui.label(pid.to_string());
+                                match signature {
+                                    Some(info) => {
+                                        ui.colored_label(info.status.color(), info.status.label());
+                                        ui.label(info.signer.as_deref().unwrap_or("-"));
+                                    }
+                                    None if exe.is_some() => {
+                                        ui.label("Checking...");
+                                        ui.label("-");
+                                    }
+                                    None => {
+                                        ui.colored_label(SignatureStatus::Unknown.color(), SignatureStatus::Unknown.label());
+                                        ui.label("-");
+                                    }
+                                }
+                                ui.label(path_text);
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+    }
+}
@@ -0,0 +1,262 @@
+use chrono::Local;
+use eframe::egui;
+use egui::{Color32, RichText};
+use sysinfo::SystemExt;
+use log::{info, warn};
+use crate::known_folders;
+use crate::package_sources::{self, PackageSourceSettings, WINGET_ALREADY_INSTALLED};
+use crate::{toast, DevDashboard, NiniteApp};
+
+/// Settings that hold credentials or private addresses; they are left out of exported scripts
+const SECRET_SETTINGS: &[(&str, &str)] = &[
+    ("cloud", "onedrive_token"),
+    ("cloud", "dropbox_token"),
+    ("share", "github_token"),
+    ("api", "token"),
+    ("calendar", "ics_source"),     // Secret calendar addresses carry their own access key
+    ("action_lock", "pin"),
+];
+
+/// What the exported setup script covers
+pub struct ProvisionOptions {
+    pub include_installed: bool,    // Apps already installed here, not just the selection
+    pub include_settings: bool,     // Restore the dashboard's settings.json
+}
+
+impl Default for ProvisionOptions {
+    fn default() -> Self {
+        Self { include_installed: false, include_settings: true }
+    }
+}
+
+/// A PowerShell single-quoted string, where the only escape is a doubled quote.
+/// PowerShell also ends the string at the typographic quotes U+2018 to U+201B, so those are doubled too.
+pub fn ps_quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('\'');
+    for c in text.chars() {
+        if matches!(c, '\'' | '\u{2018}'..='\u{201B}') {
+            quoted.push(c);
+        }
+        quoted.push(c);
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// Standalone PowerShell script that installs the apps from the configured package sources,
+/// falling back to Ninite for apps without a winget package, and optionally writes the dashboard settings
+fn provisioning_script(apps: &[&NiniteApp], sources: &PackageSourceSettings, settings: Option<&str>, host: &str) -> String {
+    let plan = package_sources::plan(apps, sources.backend);
+    let mut lines = vec![
+        "#Requires -Version 5.1".to_string(),
+        format!("# Dev Dashboard setup script, exported {} from {}", Local::now().format("%Y-%m-%d %H:%M"), host),
+        "# Installs the apps chosen in the dashboard and restores its settings on a machine without the GUI.".to_string(),
+        "# Run it from an elevated PowerShell: powershell -ExecutionPolicy Bypass -File <this script>".to_string(),
+        "param(".to_string(),
+        "    # Folder of dev_dashboard.exe, where its settings.json is read from".to_string(),
+        "    [string]$DashboardFolder = $PSScriptRoot".to_string(),
+        ")".to_string(),
+        String::new(),
+        "$failed = @()".to_string(),
+    ];
+
+    if !plan.missing.is_empty() {
+        let names: Vec<String> = plan.missing.iter().map(|app| ps_quote(&app.name)).collect();
+        lines.extend([
+            String::new(),
+            "# Not in the configured package source, so left out rather than taken from a public feed".to_string(),
+            format!("$failed += {}", names.join(", ")),
+        ]);
+    }
+
+    if !plan.winget.is_empty() {
+        lines.extend([
+            String::new(),
+            "if (-not (Get-Command winget -ErrorAction SilentlyContinue)) {".to_string(),
+            "    Write-Error 'winget was not found. Install App Installer from the Microsoft Store and run this script again.'".to_string(),
+            "    exit 1".to_string(),
+            "}".to_string(),
+            String::new(),
+            "$packages = @(".to_string(),
+        ]);
+        for app in &plan.winget {
+            lines.push(format!("    @{{ Name = {}; Id = {} }}", ps_quote(&app.name), ps_quote(&app.info.winget_id)));
+        }
+        let already = WINGET_ALREADY_INSTALLED.iter().map(|code| code.to_string()).collect::<Vec<_>>().join(", ");
+        let source: String = sources.winget_source_args().iter().map(|arg| format!(" {}", ps_quote(arg))).collect();
+        lines.extend([
+            ")".to_string(),
+            format!("$alreadyInstalled = @({})", already),
+            "foreach ($package in $packages) {".to_string(),
+            "    Write-Host \"Installing $($package.Name)...\"".to_string(),
+            format!("    winget install --id $package.Id --exact --silent --accept-package-agreements --accept-source-agreements --disable-interactivity{}", source),
+            "    if ($LASTEXITCODE -ne 0 -and $alreadyInstalled -notcontains $LASTEXITCODE) {".to_string(),
+            "        Write-Warning \"$($package.Name) failed with exit code $LASTEXITCODE\"".to_string(),
+            "        $failed += $package.Name".to_string(),
+            "    }".to_string(),
+            "}".to_string(),
+        ]);
+    }
+
+    if !plan.choco.is_empty() {
+        let ids: Vec<String> = plan.choco.iter().map(|app| ps_quote(&app.info.choco_id)).collect();
+        let names: Vec<String> = plan.choco.iter().map(|app| ps_quote(&app.name)).collect();
+        let source: String = sources.choco_source_args().iter().map(|arg| format!(" {}", ps_quote(arg))).collect();
+        lines.extend([
+            String::new(),
+            "if (-not (Get-Command choco -ErrorAction SilentlyContinue)) {".to_string(),
+            "    Write-Error 'Chocolatey was not found. Install it from your internal repository and run this script again.'".to_string(),
+            "    exit 1".to_string(),
+            "}".to_string(),
+            format!("choco install {} -y --no-progress{}", ids.join(" "), source),
+            // 1641 and 3010 report a successful install that wants a restart
+            "if ($LASTEXITCODE -ne 0 -and $LASTEXITCODE -ne 1641 -and $LASTEXITCODE -ne 3010) {".to_string(),
+            "    Write-Warning \"Chocolatey failed with exit code $LASTEXITCODE\"".to_string(),
+            format!("    $failed += {}", names.join(", ")),
+            "}".to_string(),
+        ]);
+    }
+
+    if !plan.ninite.is_empty() {
+        let ids: Vec<&str> = plan.ninite.iter().map(|app| app.ninite_id.as_str()).collect();
+        let names: Vec<&str> = plan.ninite.iter().map(|app| app.name.as_str()).collect();
+        lines.extend([
+            String::new(),
+            format!("# No winget package for: {}", names.join(", ")),
+            format!("Write-Host {}", ps_quote(&format!("Installing {} with Ninite...", names.join(", ")))),
+            "$ninite = Join-Path $env:TEMP 'ninite.exe'".to_string(),
+            "try {".to_string(),
+            format!("    Invoke-WebRequest -Uri {} -OutFile $ninite -UseBasicParsing", ps_quote(&format!("https://ninite.com/{}/ninite.exe", ids.join("-")))),
+            "    Start-Process -FilePath $ninite -Wait".to_string(),
+            "} catch {".to_string(),
+            "    Write-Warning \"The Ninite installer failed: $_\"".to_string(),
+            format!("    $failed += {}", names.iter().map(|name| ps_quote(name)).collect::<Vec<_>>().join(", ")),
+            "} finally {".to_string(),
+            "    Remove-Item $ninite -ErrorAction SilentlyContinue".to_string(),
+            "}".to_string(),
+        ]);
+    }
+
+    if let Some(settings) = settings {
+        // A single-quoted here-string keeps the JSON verbatim; its lines never start with '@
+        lines.extend([
+            String::new(),
+            "# Dashboard settings, with access tokens left out".to_string(),
+            "$settings = @'".to_string(),
+            settings.replace("\r\n", "\n").replace('\n', "\r\n"),
+            "'@".to_string(),
+            "$settingsPath = Join-Path $DashboardFolder 'settings.json'".to_string(),
+            "if (Test-Path $settingsPath) {".to_string(),
+            "    Copy-Item $settingsPath \"$settingsPath.bak\" -Force".to_string(),
+            "    Write-Host \"Backed up the existing settings to $settingsPath.bak\"".to_string(),
+            "}".to_string(),
+            "New-Item -ItemType Directory -Path $DashboardFolder -Force | Out-Null".to_string(),
+            "# WriteAllText leaves out the byte order mark that Set-Content adds in Windows PowerShell".to_string(),
+            "[System.IO.File]::WriteAllText($settingsPath, $settings)".to_string(),
+            "Write-Host \"Wrote the dashboard settings to $settingsPath\"".to_string(),
+        ]);
+    }
+
+    lines.extend([
+        String::new(),
+        "if ($failed.Count -gt 0) {".to_string(),
+        "    Write-Warning \"Not installed: $($failed -join ', ')\"".to_string(),
+        "    exit 1".to_string(),
+        "}".to_string(),
+        "Write-Host 'Setup finished.'".to_string(),
+        String::new(),
+    ]);
+    lines.join("\r\n")
+}
+
+impl DevDashboard {
+    fn provisioning_apps(&self) -> Vec<&NiniteApp> {
+        let include_installed = self.provision.include_installed;
+        self.ninite_apps.iter()
+            .filter(|app| self.selected_apps.contains(&app.name) || (include_installed && app.installed))
+            .collect()
+    }
+
+    /// The current settings as JSON without access tokens
+    fn exportable_settings(&self) -> Option<String> {
+        let mut settings = serde_json::to_value(&self.settings).ok()?;
+        // Removed keys fall back to their defaults when the script's settings.json is loaded
+        for (section, key) in SECRET_SETTINGS {
+            if let Some(section) = settings.get_mut(*section).and_then(|section| section.as_object_mut()) {
+                section.remove(*key);
+            }
+        }
+        serde_json::to_string_pretty(&settings).ok()
+    }
+
+    /// Writes the setup script to Documents\Dev Dashboard and shows it in Explorer
+    fn export_provisioning_script(&self) {
+        let apps = self.provisioning_apps();
+        let settings = if self.provision.include_settings { self.exportable_settings() } else { None };
+        let host = self.sys.host_name().unwrap_or_default();
+        // Windows PowerShell reads scripts without a byte order mark as ANSI
+        let script = format!("\u{feff}{}", provisioning_script(&apps, &self.settings.package_sources, settings.as_deref(), &host));
+
+        let folder = known_folders::documents().join("Dev Dashboard");
+        let path = folder.join(format!("dev-dashboard-setup-{}.ps1", Local::now().format("%Y%m%d-%H%M%S")));
+        let result = std::fs::create_dir_all(&folder).and_then(|_| std::fs::write(&path, script));
+        match result {
+            Ok(()) => {
+                info!("Exported a setup script for {} apps to {:?}", apps.len(), path);
+                let _ = std::process::Command::new("explorer").arg(format!("/select,{}", path.display())).spawn();
+            }
+            Err(e) => {
+                warn!("Could not export the setup script: {}", e);
+                toast::show_toast("Could not export the setup script", &e.to_string());
+            }
+        }
+    }
+
+    /// Installer page controls for exporting the selection as a setup script
+    pub(crate) fn show_provisioning_export(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.provision.include_installed, "Include apps installed here");
+            ui.checkbox(&mut self.provision.include_settings, "Include dashboard settings")
+                .on_hover_text("Access tokens for cloud storage and Gists are left out");
+        });
+        let apps = self.provisioning_apps();
+        let plan = package_sources::plan(&apps, self.settings.package_sources.backend);
+        let enabled = !apps.is_empty() || self.provision.include_settings;
+        ui.horizontal(|ui| {
+            if ui.add_enabled(enabled, egui::Button::new("Export Setup Script"))
+                .on_hover_text("A PowerShell script that installs these apps from the configured package sources on another machine")
+                .clicked() {
+                self.export_provisioning_script();
+            }
+            let mut summary = format!("{} app{}", apps.len(), if apps.len() == 1 { "" } else { "s" });
+            if !plan.ninite.is_empty() {
+                summary.push_str(&format!(", {} through Ninite", plan.ninite.len()));
+            }
+            if !plan.missing.is_empty() {
+                summary.push_str(&format!(", {} without a package in the chosen source", plan.missing.len()));
+            }
+            ui.label(RichText::new(summary).color(Color32::from_rgb(107, 114, 128)));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ps_quote_wraps_in_single_quotes() {
+        assert_eq!(ps_quote(""), "''");
+        assert_eq!(ps_quote(r"C:\Program Files\App"), r"'C:\Program Files\App'");
+        assert_eq!(ps_quote("$env:TEMP; `n"), "'$env:TEMP; `n'");
+    }
+
+    #[test]
+    fn ps_quote_doubles_every_kind_of_single_quote() {
+        assert_eq!(ps_quote("it's"), "'it''s'");
+        assert_eq!(ps_quote("it\u{2019}s"), "'it\u{2019}\u{2019}s'");
+        assert_eq!(ps_quote("\u{2018}\u{201A}\u{201B}"), "'\u{2018}\u{2018}\u{201A}\u{201A}\u{201B}\u{201B}'");
+        assert_eq!(ps_quote("\"double\""), "'\"double\"'");
+    }
+}
@@ -0,0 +1,183 @@
+use std::net::{IpAddr, UdpSocket};
+use eframe::egui;
+use egui::{Color32, RichText};
+use qrcode::{Color, EcLevel, QrCode};
+use crate::DevDashboard;
+
+/// Modules of white border required around the code
+const QUIET_ZONE: usize = 4;
+/// Pixels per module when copying the code as an image
+const EXPORT_SCALE: usize = 8;
+
+/// Options and output of the QR Code tool
+pub struct QrTool {
+    text: String,
+    port: u16,
+    level: EcLevel,
+    code: Option<Result<QrCode, String>>,
+    encoded: String,            // Text and level the code was built from
+    status: Option<Result<String, String>>,
+}
+
+impl Default for QrTool {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            port: 8080,
+            level: EcLevel::M,
+            code: None,
+            encoded: String::new(),
+            status: None,
+        }
+    }
+}
+
+/// Address of the interface used for outbound traffic, i.e. the one other LAN devices can reach
+/// Connecting a UDP socket only picks a route; no packets are sent
+pub fn lan_address() -> Option<IpAddr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("192.0.2.1:80").ok()?;
+    let address = socket.local_addr().ok()?.ip();
+    (!address.is_unspecified() && !address.is_loopback()).then_some(address)
+}
+
+fn level_name(level: EcLevel) -> &'static str {
+    match level {
+        EcLevel::L => "Low (7%)",
+        EcLevel::M => "Medium (15%)",
+        EcLevel::Q => "Quartile (25%)",
+        EcLevel::H => "High (30%)",
+    }
+}
+
+/// The code as black-on-white RGBA pixels, including the quiet zone
+fn rasterize(code: &QrCode) -> (usize, Vec<u8>) {
+    let width = code.width();
+    let colors = code.to_colors();
+    let size = (width + QUIET_ZONE * 2) * EXPORT_SCALE;
+    let mut pixels = vec![255u8; size * size * 4];
+    for (index, color) in colors.iter().enumerate() {
+        if *color != Color::Dark {
+            continue;
+        }
+        let (x, y) = (index % width + QUIET_ZONE, index / width + QUIET_ZONE);
+        for row in y * EXPORT_SCALE..(y + 1) * EXPORT_SCALE {
+            let start = (row * size + x * EXPORT_SCALE) * 4;
+            for pixel in pixels[start..start + EXPORT_SCALE * 4].chunks_exact_mut(4) {
+                pixel[..3].fill(0);
+            }
+        }
+    }
+    (size, pixels)
+}
+
+impl QrTool {
+    /// Rebuilds the code when the text or error correction level changes
+    fn refresh(&mut self) {
+        let key = format!("{:?}{}", self.level, self.text);
+        if key == self.encoded {
+            return;
+        }
+        self.encoded = key;
+        self.status = None;
+        self.code = if self.text.is_empty() {
+            None
+        } else {
+            Some(QrCode::with_error_correction_level(self.text.as_bytes(), self.level)
+                .map_err(|e| format!("Cannot encode: {}", e)))
+        };
+    }
+
+    fn copy_image(&mut self) {
+        let Some(Ok(code)) = &self.code else { return };
+        let (size, pixels) = rasterize(code);
+        let result = arboard::Clipboard::new().and_then(|mut clipboard| {
+            clipboard.set_image(arboard::ImageData { width: size, height: size, bytes: pixels.into() })
+        });
+        self.status = Some(match result {
+            Ok(()) => Ok("Copied to clipboard".to_string()),
+            Err(e) => Err(format!("Could not copy: {}", e)),
+        });
+    }
+}
+
+impl DevDashboard {
+    /// QR code tool page
+    /// Encodes text or a LAN URL so it can be opened on a phone
+    pub(crate) fn show_qr_tool(&mut self, ui: &mut egui::Ui) {
+        let tool = &mut self.qr_tool;
+
+        ui.heading("QR Code");
+        ui.add_space(8.0);
+
+        ui.add(egui::TextEdit::multiline(&mut tool.text)
+            .hint_text("Text or URL")
+            .desired_rows(2)
+            .desired_width(f32::INFINITY));
+        ui.horizontal(|ui| {
+            ui.label("Port:");
+            ui.add(egui::DragValue::new(&mut tool.port).clamp_range(1..=65535));
+            if ui.button("Use LAN address").on_hover_text("http://<this PC's address>:<port>").clicked() {
+                match lan_address() {
+                    Some(address) => tool.text = format!("http://{}:{}", address, tool.port),
+                    None => tool.status = Some(Err("No network connection".to_string())),
+                }
+            }
+            ui.separator();
+            egui::ComboBox::from_id_source("qr_level")
+                .selected_text(level_name(tool.level))
+                .show_ui(ui, |ui| {
+                    for level in [EcLevel::L, EcLevel::M, EcLevel::Q, EcLevel::H] {
+                        ui.selectable_value(&mut tool.level, level, level_name(level));
+                    }
+                });
+        });
+        tool.refresh();
+        ui.add_space(8.0);
+
+        let mut copy_clicked = false;
+        match &tool.code {
+            Some(Ok(code)) => {
+                let modules = code.width() + QUIET_ZONE * 2;
+                let side = ui.available_width().min(ui.available_height() - 40.0).clamp(120.0, 360.0);
+                let module = (side / modules as f32).floor().max(1.0);
+                let (rect, _) = ui.allocate_exact_size(egui::vec2(module, module) * modules as f32, egui::Sense::hover());
+                let painter = ui.painter();
+                painter.rect_filled(rect, 0.0, Color32::WHITE);
+                for (index, color) in code.to_colors().iter().enumerate() {
+                    if *color == Color::Dark {
+                        let (x, y) = (index % code.width() + QUIET_ZONE, index / code.width() + QUIET_ZONE);
+                        let cell = egui::Rect::from_min_size(
+                            rect.min + egui::vec2(x as f32, y as f32) * module,
+                            egui::vec2(module, module),
+                        );
+                        painter.rect_filled(cell, 0.0, Color32::BLACK);
+                    }
+                }
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    copy_clicked = ui.button("📋 Copy image").clicked();
+                    ui.label(RichText::new(format!("{} × {} modules", code.width(), code.width()))
+                        .color(Color32::from_rgb(156, 163, 175)));
+                });
+            }
+            Some(Err(error)) => {
+                ui.colored_label(Color32::from_rgb(220, 50, 50), error);
+            }
+            None => {}
+        }
+        if copy_clicked {
+            tool.copy_image();
+        }
+
+        match &tool.status {
+            Some(Ok(message)) => {
+                ui.colored_label(Color32::from_rgb(22, 163, 74), message);
+            }
+            Some(Err(error)) => {
+                ui.colored_label(Color32::from_rgb(220, 50, 50), error);
+            }
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,306 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+use chrono::Local;
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use serde_json::{Map, Value};
+use sysinfo::SystemExt;
+use log::{info, warn};
+use crate::known_folders;
+use crate::DevDashboard;
+
+const FILE_PREFIX: &str = "metrics-";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum RecorderFormat {
+    Csv,
+    Jsonl,                          // One JSON object per line
+}
+
+impl RecorderFormat {
+    fn label(&self) -> &'static str {
+        match self {
+            RecorderFormat::Csv => "CSV",
+            RecorderFormat::Jsonl => "JSON Lines",
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            RecorderFormat::Csv => "csv",
+            RecorderFormat::Jsonl => "jsonl",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct RecorderSettings {
+    pub enabled: bool,
+    pub format: RecorderFormat,
+    pub interval_seconds: u32,
+    pub retention_days: u32,        // Older files in the folder are deleted
+    pub max_file_mb: u32,           // A day's file continues in a new part past this size
+}
+
+impl Default for RecorderSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            format: RecorderFormat::Csv,
+            interval_seconds: 5,
+            retention_days: 7,
+            max_file_mb: 50,
+        }
+    }
+}
+
+/// Documents\Dev Dashboard\Metrics, next to the card exports
+fn metrics_folder() -> PathBuf {
+    known_folders::documents().join("Dev Dashboard").join("Metrics")
+}
+
+/// One row of readings; None is written as an empty CSV field or a JSON null
+struct Snapshot {
+    time: String,
+    values: Vec<(String, Option<f64>)>,
+}
+
+impl Snapshot {
+    fn columns(&self) -> Vec<String> {
+        let mut columns = vec!["time".to_string()];
+        columns.extend(self.values.iter().map(|(name, _)| name.clone()));
+        columns
+    }
+
+    fn line(&self, format: RecorderFormat) -> String {
+        // Readings converted from f32 would otherwise carry digits like 23.399999618530273
+        let rounded = |value: &Option<f64>| value.map(|value| (value * 100.0).round() / 100.0);
+        match format {
+            RecorderFormat::Csv => {
+                let mut fields = vec![self.time.clone()];
+                fields.extend(self.values.iter().map(|(_, value)| rounded(value).map(|value| value.to_string()).unwrap_or_default()));
+                format!("{}\r\n", fields.join(","))
+            }
+            RecorderFormat::Jsonl => {
+                let mut object = Map::new();
+                object.insert("time".to_string(), Value::from(self.time.clone()));
+                for (name, value) in &self.values {
+                    object.insert(name.clone(), rounded(value).map(Value::from).unwrap_or(Value::Null));
+                }
+                format!("{}\n", Value::Object(object))
+            }
+        }
+    }
+}
+
+/// The file being appended to
+struct RecorderFile {
+    path: PathBuf,
+    date: String,
+    format: RecorderFormat,
+    columns: Vec<String>,           // CSV header; a drive coming or going starts a new part
+    size: u64,
+    file: File,
+}
+
+fn first_line(path: &Path) -> Option<String> {
+    let mut line = String::new();
+    BufReader::new(File::open(path).ok()?).read_line(&mut line).ok()?;
+    Some(line.trim_end().to_string())
+}
+
+/// Appends to today's file when its header matches, otherwise starts the next part
+fn open_file(folder: &Path, format: RecorderFormat, columns: &[String], max_size: u64) -> std::io::Result<RecorderFile> {
+    std::fs::create_dir_all(folder)?;
+    let date = Local::now().format("%Y-%m-%d").to_string();
+    let header = columns.join(",");
+    let mut part = 1;
+    loop {
+        let name = match part {
+            1 => format!("{}{}.{}", FILE_PREFIX, date, format.extension()),
+            _ => format!("{}{}-{}.{}", FILE_PREFIX, date, part, format.extension()),
+        };
+        let path = folder.join(name);
+        let size = std::fs::metadata(&path).map(|metadata| metadata.len()).unwrap_or(0);
+        let usable = size == 0
+            || (size < max_size && (format == RecorderFormat::Jsonl || first_line(&path).as_deref() == Some(header.as_str())));
+        if !usable {
+            part += 1;
+            continue;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let mut size = size;
+        if size == 0 && format == RecorderFormat::Csv {
+            let line = format!("{}\r\n", header);
+            file.write_all(line.as_bytes())?;
+            size = line.len() as u64;
+        }
+        info!("Recording metrics to {:?}", path);
+        return Ok(RecorderFile { path, date, format, columns: columns.to_vec(), size, file });
+    }
+}
+
+/// Deletes recordings last written more than the retention ago
+fn remove_old_files(folder: &Path, retention_days: u32) {
+    let Ok(entries) = std::fs::read_dir(folder) else { return };
+    let retention = Duration::from_secs(retention_days as u64 * 24 * 60 * 60);
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !name.starts_with(FILE_PREFIX) || !(name.ends_with(".csv") || name.ends_with(".jsonl")) {
+            continue;
+        }
+        let age = entry.metadata().ok()
+            .and_then(|metadata| metadata.modified().ok())
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok());
+        if age.is_some_and(|age| age > retention) {
+            match std::fs::remove_file(entry.path()) {
+                Ok(()) => info!("Removed old metrics recording {}", name),
+                Err(e) => warn!("Could not remove old metrics recording {}: {}", name, e),
+            }
+        }
+    }
+}
+
+/// Appends a snapshot of the readings to a file every interval
+#[derive(Default)]
+pub struct MetricsRecorder {
+    file: Option<RecorderFile>,
+    last_sample: Option<Instant>,
+    error: Option<String>,
+}
+
+impl MetricsRecorder {
+    fn write(&mut self, snapshot: &Snapshot, settings: &RecorderSettings) -> std::io::Result<()> {
+        let columns = snapshot.columns();
+        let max_size = settings.max_file_mb.max(1) as u64 * 1024 * 1024;
+        let date = Local::now().format("%Y-%m-%d").to_string();
+        let rotate = match &self.file {
+            Some(file) => file.date != date
+                || file.format != settings.format
+                || file.size >= max_size
+                || (file.format == RecorderFormat::Csv && file.columns != columns),
+            None => true,
+        };
+        if rotate {
+            self.file = None;
+            let folder = metrics_folder();
+            self.file = Some(open_file(&folder, settings.format, &columns, max_size)?);
+            remove_old_files(&folder, settings.retention_days.max(1));
+        }
+        let Some(file) = self.file.as_mut() else { return Ok(()) };
+        let line = snapshot.line(settings.format);
+        file.file.write_all(line.as_bytes())?;
+        file.size += line.len() as u64;
+        Ok(())
+    }
+}
+
+impl DevDashboard {
+    /// The readings shown on the cards, under stable column names
+    fn metrics_snapshot(&self) -> Snapshot {
+        let history = &self.tray.history;
+        let total_memory = self.sys.total_memory();
+        let used_memory = total_memory.saturating_sub(self.sys.available_memory());
+        let physical = || self.network_stats.iter().filter(|(name, _)| DevDashboard::is_physical_interface(name));
+        let gpu = self.gpu_info.as_ref();
+        let mut values = vec![
+            ("cpu_percent".to_string(), history.cpu.back().map(|value| *value as f64)),
+            ("cpu_temperature_c".to_string(), self.throttle_monitor.status.and_then(|status| status.temperature).map(f64::from)),
+            ("memory_percent".to_string(), history.memory.back().map(|value| *value as f64)),
+            ("memory_used_bytes".to_string(), Some(used_memory as f64)),
+            ("received_bytes_per_second".to_string(), Some(physical().map(|(_, stats)| stats.received_speed).sum())),
+            ("sent_bytes_per_second".to_string(), Some(physical().map(|(_, stats)| stats.sent_speed).sum())),
+            ("gpu_percent".to_string(), gpu.and_then(|gpu| gpu.utilization).map(f64::from)),
+            ("gpu_temperature_c".to_string(), gpu.and_then(|gpu| gpu.temperature).map(f64::from)),
+            ("gpu_memory_used_bytes".to_string(), gpu.and_then(|gpu| gpu.memory_used).map(|bytes| bytes as f64)),
+        ];
+        for volume in self.volume_monitor.volumes() {
+            let Some((_, free)) = volume.space else { continue };
+            let drive = volume.mount.trim_end_matches(['\\', ':']).to_lowercase();
+            let io = self.disk_io.get(&volume.mount);
+            values.push((format!("disk_{}_free_bytes", drive), Some(free as f64)));
+            values.push((format!("disk_{}_busy_percent", drive), io.map(|io| io.busy_percent as f64)));
+            values.push((format!("disk_{}_read_ms", drive), io.and_then(|io| io.read_latency_ms).map(f64::from)));
+            values.push((format!("disk_{}_write_ms", drive), io.and_then(|io| io.write_latency_ms).map(f64::from)));
+        }
+        Snapshot { time: Local::now().to_rfc3339(), values }
+    }
+
+    /// Writes a snapshot when the interval has passed; called once a second
+    pub(crate) fn update_metrics_recorder(&mut self) {
+        let settings = &self.settings.recorder;
+        if !settings.enabled {
+            self.metrics_recorder.file = None;
+            return;
+        }
+        let interval = Duration::from_secs(settings.interval_seconds.max(1) as u64);
+        // Paused readings would repeat the last values
+        if self.monitoring_paused() || self.metrics_recorder.last_sample.is_some_and(|time| time.elapsed() < interval) {
+            return;
+        }
+        self.metrics_recorder.last_sample = Some(Instant::now());
+        let snapshot = self.metrics_snapshot();
+        let settings = self.settings.recorder.clone();
+        match self.metrics_recorder.write(&snapshot, &settings) {
+            Ok(()) => self.metrics_recorder.error = None,
+            Err(e) => {
+                if self.metrics_recorder.error.is_none() {
+                    warn!("Could not record metrics: {}", e);
+                }
+                self.metrics_recorder.file = None;
+                self.metrics_recorder.error = Some(e.to_string());
+            }
+        }
+    }
+
+    pub(crate) fn show_recorder_settings(&mut self, ui: &mut egui::Ui) {
+        ui.label("Metrics Recording:");
+        let settings = &mut self.settings.recorder;
+        let mut changed = ui.checkbox(&mut settings.enabled, "Record metrics to disk")
+            .on_hover_text("Appends CPU, memory, drive, network and GPU readings to a file, to look into load during long builds later")
+            .changed();
+        ui.add_enabled_ui(settings.enabled, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Format:");
+                egui::ComboBox::from_id_source("recorder_format")
+                    .selected_text(settings.format.label())
+                    .show_ui(ui, |ui| {
+                        for format in [RecorderFormat::Csv, RecorderFormat::Jsonl] {
+                            changed |= ui.selectable_value(&mut settings.format, format, format.label()).changed();
+                        }
+                    });
+                ui.label("every");
+                changed |= ui.add(egui::DragValue::new(&mut settings.interval_seconds).clamp_range(1..=3600).suffix(" s")).changed();
+            });
+            ui.horizontal(|ui| {
+                ui.label("Keep for");
+                changed |= ui.add(egui::DragValue::new(&mut settings.retention_days).clamp_range(1..=365).suffix(" days")).changed();
+                ui.label("New file after");
+                changed |= ui.add(egui::DragValue::new(&mut settings.max_file_mb).clamp_range(1..=1024).suffix(" MB")).changed();
+            });
+        });
+
+        let grey = Color32::from_rgb(107, 114, 128);
+        ui.horizontal(|ui| {
+            match &self.metrics_recorder.file {
+                Some(file) => ui.label(RichText::new(format!("Writing to {}", file.path.display())).color(grey)),
+                None => ui.label(RichText::new(format!("Files go to {}", metrics_folder().display())).color(grey)),
+            };
+            if ui.small_button("📂").on_hover_text("Open the folder").clicked() {
+                let folder = metrics_folder();
+                let _ = std::fs::create_dir_all(&folder);
+                let _ = std::process::Command::new("explorer").arg(&folder).spawn();
+            }
+        });
+        if let Some(error) = &self.metrics_recorder.error {
+            ui.colored_label(Color32::from_rgb(220, 50, 50), error);
+        }
+        if changed {
+            self.save_settings();
+        }
+    }
+}
@@ -0,0 +1,369 @@
+use std::collections::{HashMap, HashSet};
+use std::os::windows::process::CommandExt;
+use std::path::Path;
+use std::process::Command;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Local, TimeZone};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use log::{info, warn};
+use crate::{DevDashboard, CREATE_NO_WINDOW};
+
+/// Registered repositories and how often they are checked
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct RepoSettings {
+    pub repos: Vec<String>,     // Working tree folders
+    pub interval_secs: u64,
+}
+
+impl Default for RepoSettings {
+    fn default() -> Self {
+        Self {
+            repos: Vec::new(),
+            interval_secs: 60,
+        }
+    }
+}
+
+/// What `git status` and the last commit say about one working tree
+#[derive(Clone)]
+pub struct RepoStatus {
+    branch: Option<String>,             // None on a detached HEAD
+    head: String,                       // Abbreviated commit id
+    changed: usize,                     // Staged, modified, renamed and conflicted entries
+    untracked: usize,
+    ahead_behind: Option<(u32, u32)>,   // None without an upstream branch
+    last_commit: Option<(String, DateTime<Local>)>,  // Subject and commit time
+}
+
+impl RepoStatus {
+    fn is_dirty(&self) -> bool {
+        self.changed > 0 || self.untracked > 0
+    }
+
+    fn state_text(&self) -> String {
+        match (self.changed, self.untracked) {
+            (0, 0) => "clean".to_string(),
+            (changed, 0) => format!("{} changed", changed),
+            (0, untracked) => format!("{} untracked", untracked),
+            (changed, untracked) => format!("{} changed, {} untracked", changed, untracked),
+        }
+    }
+
+    fn branch_text(&self) -> String {
+        match &self.branch {
+            Some(branch) => branch.clone(),
+            None => format!("detached at {}", self.head),
+        }
+    }
+
+    fn sync_text(&self) -> Option<String> {
+        match self.ahead_behind? {
+            (0, 0) => None,
+            (ahead, 0) => Some(format!("↑{}", ahead)),
+            (0, behind) => Some(format!("↓{}", behind)),
+            (ahead, behind) => Some(format!("↑{} ↓{}", ahead, behind)),
+        }
+    }
+}
+
+/// Formats how long ago a time was, e.g. "3h ago"
+fn format_age(time: DateTime<Local>) -> String {
+    let minutes = (Local::now() - time).num_minutes().max(0);
+    if minutes >= 48 * 60 {
+        format!("{}d ago", minutes / 60 / 24)
+    } else if minutes >= 60 {
+        format!("{}h ago", minutes / 60)
+    } else {
+        format!("{}m ago", minutes)
+    }
+}
+
+/// Runs git in the repository and returns its output, or its first error line
+fn git(path: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        // Status would otherwise take the index lock and can make a concurrent commit fail
+        .args(["--no-optional-locks", "-C", path])
+        .args(args)
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_err(|e| format!("Could not run git: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.lines().map(str::trim).find(|line| !line.is_empty()).unwrap_or("git failed").to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Reads branch, tracking and changes from `git status --porcelain=v2 --branch`, whose format is stable
+/// across git versions and languages
+fn check_repo(path: &str) -> Result<RepoStatus, String> {
+    let status = git(path, &["status", "--porcelain=v2", "--branch"])?;
+    let mut repo = RepoStatus {
+        branch: None,
+        head: String::new(),
+        changed: 0,
+        untracked: 0,
+        ahead_behind: None,
+        last_commit: None,
+    };
+    for line in status.lines() {
+        if let Some(oid) = line.strip_prefix("# branch.oid ") {
+            repo.head = oid.chars().take(7).collect();
+        } else if let Some(head) = line.strip_prefix("# branch.head ") {
+            repo.branch = (head != "(detached)").then(|| head.to_string());
+        } else if let Some(counts) = line.strip_prefix("# branch.ab ") {
+            let mut counts = counts.split_whitespace().map(|count| count.trim_start_matches(['+', '-']).parse::<u32>().unwrap_or(0));
+            repo.ahead_behind = Some((counts.next().unwrap_or(0), counts.next().unwrap_or(0)));
+        } else if line.starts_with("? ") {
+            repo.untracked += 1;
+        } else if line.starts_with("1 ") || line.starts_with("2 ") || line.starts_with("u ") {
+            repo.changed += 1;
+        }
+    }
+
+    // A repository without commits has no log; that isn't an error
+    if let Ok(log) = git(path, &["log", "-1", "--format=%ct%x09%s"]) {
+        if let Some((time, subject)) = log.trim_end().split_once('\t') {
+            if let Some(time) = time.parse().ok().and_then(|seconds| Local.timestamp_opt(seconds, 0).single()) {
+                repo.last_commit = Some((subject.to_string(), time));
+            }
+        }
+    }
+    Ok(repo)
+}
+
+/// Opens a terminal in the folder: Windows Terminal when installed, otherwise a command prompt
+fn open_terminal(path: &str) {
+    if Command::new("wt").args(["-d", path]).spawn().is_err() {
+        let _ = Command::new("cmd")
+            .args(["/C", "start", "", "cmd", "/K"])
+            .current_dir(path)
+            .creation_flags(CREATE_NO_WINDOW)
+            .spawn();
+    }
+}
+
+/// VS Code's `code` launcher is a batch file, so it goes through cmd
+fn open_vs_code(path: &str) {
+    if let Err(e) = Command::new("cmd").args(["/C", "code", path]).creation_flags(CREATE_NO_WINDOW).spawn() {
+        warn!("Could not start VS Code for {}: {}", path, e);
+    }
+}
+
+/// Background repository checks for the Repos card
+#[derive(Default)]
+pub struct RepoMonitor {
+    results: HashMap<String, (Result<RepoStatus, String>, DateTime<Local>)>,  // By path, with when it was checked
+    last_check: Option<Instant>,
+    sender: Option<Sender<(String, Result<RepoStatus, String>)>>,
+    receiver: Option<Receiver<(String, Result<RepoStatus, String>)>>,
+    pending: HashSet<String>,           // Checks in flight
+    new_repo: String,                   // Text field in Settings
+}
+
+impl RepoMonitor {
+    pub fn is_checking(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Checks every repository that isn't already being checked
+    pub fn start_check(&mut self, repos: &[String]) {
+        let sender = match &self.sender {
+            Some(sender) => sender.clone(),
+            None => {
+                let (sender, receiver) = channel();
+                self.sender = Some(sender.clone());
+                self.receiver = Some(receiver);
+                sender
+            }
+        };
+        self.last_check = Some(Instant::now());
+
+        for repo in repos {
+            if !self.pending.insert(repo.clone()) {
+                continue;
+            }
+            let sender = sender.clone();
+            let repo = repo.clone();
+            // One thread per repository so a large working tree doesn't hold up the others
+            std::thread::spawn(move || {
+                let result = check_repo(&repo);
+                sender.send((repo, result)).ok();
+            });
+        }
+    }
+
+    /// Collects finished checks and starts the next round when it is due
+    pub fn update(&mut self, settings: &RepoSettings) {
+        if let Some(receiver) = &self.receiver {
+            while let Ok((repo, result)) = receiver.try_recv() {
+                self.pending.remove(&repo);
+                if let Err(e) = &result {
+                    // Only log a failure once, not every round
+                    if !matches!(self.results.get(&repo), Some((Err(previous), _)) if previous == e) {
+                        warn!("Could not read repository {}: {}", repo, e);
+                    }
+                }
+                self.results.insert(repo, (result, Local::now()));
+            }
+        }
+
+        let due = match self.last_check {
+            Some(last) => last.elapsed() >= Duration::from_secs(settings.interval_secs.max(10)),
+            None => true,
+        };
+        if due && !settings.repos.is_empty() {
+            self.start_check(&settings.repos);
+        }
+    }
+}
+
+/// Last part of the path, e.g. "dev-dashboard"
+fn repo_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+impl DevDashboard {
+    /// Displays the Repos card
+    /// Shows branch, uncommitted changes, ahead/behind counts and the last commit for each registered repository
+    pub(crate) fn show_repos_card(&mut self, ui: &mut egui::Ui) {
+        let repos = self.settings.repos.repos.clone();
+        let monitor = &mut self.repo_monitor;
+        let grey = Color32::from_rgb(107, 114, 128);
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Repos").strong().heading());
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if monitor.is_checking() {
+                    ui.spinner();
+                } else if ui.small_button("⟳").on_hover_text("Check now").clicked() {
+                    monitor.start_check(&repos);
+                }
+            });
+        });
+        ui.add_space(8.0);
+
+        for path in &repos {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(repo_name(path)).strong()).on_hover_text(path);
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.small_button("VS Code").on_hover_text("Open in VS Code").clicked() {
+                        open_vs_code(path);
+                    }
+                    if ui.small_button(">_").on_hover_text("Open a terminal here").clicked() {
+                        open_terminal(path);
+                    }
+                });
+            });
+            match monitor.results.get(path) {
+                None => {
+                    ui.label(RichText::new("Checking...").color(grey));
+                }
+                Some((Err(error), _)) => {
+                    ui.colored_label(Color32::from_rgb(220, 50, 50), error);
+                }
+                Some((Ok(status), checked)) => {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(status.branch_text()).monospace());
+                        let state_color = if status.is_dirty() { Color32::from_rgb(202, 138, 4) } else { Color32::from_rgb(22, 163, 74) };
+                        ui.colored_label(state_color, status.state_text());
+                        match status.sync_text() {
+                            Some(sync) => {
+                                ui.label(sync).on_hover_text("Commits ahead of and behind the upstream branch, as of the last fetch");
+                            }
+                            None if status.ahead_behind.is_none() && status.branch.is_some() => {
+                                ui.label(RichText::new("no upstream").color(grey));
+                            }
+                            None => {}
+                        }
+                    }).response.on_hover_text(format!("Checked {}", checked.format("%H:%M:%S")));
+                    if let Some((subject, time)) = &status.last_commit {
+                        ui.label(RichText::new(format!("{} · {} · {}", status.head, subject, format_age(*time))).color(grey))
+                            .on_hover_text(time.format("%Y-%m-%d %H:%M").to_string());
+                    }
+                }
+            }
+            ui.add_space(4.0);
+        }
+    }
+
+    /// Branch and state per repository, for copying the card
+    pub(crate) fn repos_card_values(&self) -> Vec<(String, String)> {
+        self.settings.repos.repos
+            .iter()
+            .map(|path| {
+                let value = match self.repo_monitor.results.get(path) {
+                    Some((Ok(status), _)) => {
+                        let mut value = format!("{}, {}", status.branch_text(), status.state_text());
+                        if let Some(sync) = status.sync_text() {
+                            value.push_str(&format!(", {}", sync));
+                        }
+                        if let Some((subject, time)) = &status.last_commit {
+                            value.push_str(&format!(", last commit {} \"{}\" {}", status.head, subject, format_age(*time)));
+                        }
+                        value
+                    }
+                    Some((Err(error), _)) => error.clone(),
+                    None => "Checking".to_string(),
+                };
+                (path.clone(), value)
+            })
+            .collect()
+    }
+
+    /// Settings section for registering repositories
+    pub(crate) fn show_repo_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+
+        ui.label("Git Repositories:");
+        let mut removed = None;
+        for (index, repo) in self.settings.repos.repos.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(repo).monospace());
+                if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                    removed = Some(index);
+                }
+            });
+        }
+        if let Some(index) = removed {
+            let repo = self.settings.repos.repos.remove(index);
+            self.repo_monitor.results.remove(&repo);
+            changed = true;
+        }
+
+        ui.horizontal(|ui| {
+            let response = ui.add(egui::TextEdit::singleline(&mut self.repo_monitor.new_repo)
+                .hint_text(r"C:\src\my-project")
+                .desired_width(180.0));
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if ui.button("Add").clicked() || submitted {
+                let repo = self.repo_monitor.new_repo.trim().trim_matches('"').trim_end_matches('\\').to_string();
+                if Path::new(&repo).is_dir() && !self.settings.repos.repos.contains(&repo) {
+                    info!("Watching repository {}", repo);
+                    self.settings.repos.repos.push(repo);
+                    // Check the new repository along with the others right away
+                    self.repo_monitor.last_check = None;
+                    changed = true;
+                }
+                self.repo_monitor.new_repo.clear();
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Check every (s):");
+            changed |= ui.add(egui::DragValue::new(&mut self.settings.repos.interval_secs)
+                .clamp_range(10..=3600)).changed();
+        });
+
+        if changed {
+            self.save_settings();
+        }
+    }
+}
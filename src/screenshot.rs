@@ -0,0 +1,552 @@
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::Duration;
+use ab_glyph::{Font, FontRef, PxScale, ScaleFont};
+use chrono::Local;
+use eframe::egui;
+use egui::{Color32, Pos2, Rect, RichText};
+use windows::Win32::Foundation::{HWND, RECT};
+use windows::Win32::Graphics::Dwm::{DwmGetWindowAttribute, DWMWA_EXTENDED_FRAME_BOUNDS};
+use windows::Win32::Graphics::Gdi::{
+    BitBlt, CreateCompatibleBitmap, CreateCompatibleDC, DeleteDC, DeleteObject, GetDC, GetDIBits, ReleaseDC,
+    SelectObject, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, CAPTUREBLT, DIB_RGB_COLORS, SRCCOPY,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetForegroundWindow, GetSystemMetrics, GetWindowRect, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN,
+    SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN,
+};
+use log::{info, warn};
+use crate::DevDashboard;
+
+/// Annotation line width and text size, in image pixels
+const STROKE_WIDTH: f32 = 3.0;
+const TEXT_SIZE: f32 = 24.0;
+const ARROW_HEAD: f32 = 16.0;
+const ANNOTATION_COLORS: [Color32; 4] = [
+    Color32::from_rgb(220, 50, 50),
+    Color32::from_rgb(234, 179, 8),
+    Color32::from_rgb(88, 165, 237),
+    Color32::from_rgb(22, 163, 74),
+];
+
+/// An RGBA image grabbed from the screen
+#[derive(Clone)]
+pub struct Capture {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+}
+
+/// Copies a rectangle of the desktop, in physical pixels, using GDI
+pub fn capture_rect(x: i32, y: i32, width: i32, height: i32) -> Result<Capture, String> {
+    if width <= 0 || height <= 0 {
+        return Err("Nothing to capture".to_string());
+    }
+    unsafe {
+        let screen_dc = GetDC(HWND(0));
+        let memory_dc = CreateCompatibleDC(screen_dc);
+        let bitmap = CreateCompatibleBitmap(screen_dc, width, height);
+        let previous = SelectObject(memory_dc, bitmap);
+
+        let copied = BitBlt(memory_dc, 0, 0, width, height, screen_dc, x, y, SRCCOPY | CAPTUREBLT).as_bool();
+
+        let mut info = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: width,
+                biHeight: -height,  // Top-down rows
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: BI_RGB.0 as u32,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut pixels = vec![0u8; width as usize * height as usize * 4];
+        let lines = if copied {
+            GetDIBits(memory_dc, bitmap, 0, height as u32, Some(pixels.as_mut_ptr() as *mut _), &mut info, DIB_RGB_COLORS)
+        } else {
+            0
+        };
+
+        SelectObject(memory_dc, previous);
+        DeleteObject(bitmap);
+        DeleteDC(memory_dc);
+        ReleaseDC(HWND(0), screen_dc);
+
+        if lines == 0 {
+            return Err("Could not read the screen".to_string());
+        }
+
+        // GDI returns BGRA with an undefined alpha channel
+        for pixel in pixels.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+            pixel[3] = 255;
+        }
+        Ok(Capture { width: width as usize, height: height as usize, pixels })
+    }
+}
+
+/// All monitors as one image
+fn capture_full_screen() -> Result<Capture, String> {
+    unsafe {
+        capture_rect(
+            GetSystemMetrics(SM_XVIRTUALSCREEN),
+            GetSystemMetrics(SM_YVIRTUALSCREEN),
+            GetSystemMetrics(SM_CXVIRTUALSCREEN),
+            GetSystemMetrics(SM_CYVIRTUALSCREEN),
+        )
+    }
+}
+
+/// The window in front, without the invisible resize borders
+fn capture_foreground_window() -> Result<Capture, String> {
+    unsafe {
+        let window = GetForegroundWindow();
+        if window.0 == 0 {
+            return Err("No window in front".to_string());
+        }
+        let mut rect = RECT::default();
+        let bounds = DwmGetWindowAttribute(
+            window,
+            DWMWA_EXTENDED_FRAME_BOUNDS,
+            &mut rect as *mut _ as *mut _,
+            std::mem::size_of::<RECT>() as u32,
+        );
+        if bounds.is_err() && !GetWindowRect(window, &mut rect).as_bool() {
+            return Err("Could not read window bounds".to_string());
+        }
+        capture_rect(rect.left, rect.top, rect.right - rect.left, rect.bottom - rect.top)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum CaptureMode {
+    FullScreen,
+    Window,      // Whatever window is in front once the delay has passed
+    Region,      // Full screen, then drag to crop
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum AnnotationTool {
+    Arrow,
+    Box,
+    Text,
+    Crop,
+}
+
+/// Annotations are kept in image coordinates so they survive resizing the editor
+#[derive(Clone)]
+enum Annotation {
+    Arrow { from: Pos2, to: Pos2, color: Color32 },
+    Box { rect: Rect, color: Color32 },
+    Text { pos: Pos2, text: String, color: Color32 },
+}
+
+impl Annotation {
+    fn translate(&mut self, offset: egui::Vec2) {
+        match self {
+            Annotation::Arrow { from, to, .. } => {
+                *from += offset;
+                *to += offset;
+            }
+            Annotation::Box { rect, .. } => *rect = rect.translate(offset),
+            Annotation::Text { pos, .. } => *pos += offset,
+        }
+    }
+}
+
+/// The two barbs of an arrow pointing at `to`
+fn arrow_head(from: Pos2, to: Pos2) -> [Pos2; 2] {
+    let direction = (to - from).normalized();
+    let back = -direction * ARROW_HEAD;
+    let rotate = |v: egui::Vec2, angle: f32| {
+        let (sin, cos) = angle.sin_cos();
+        egui::vec2(v.x * cos - v.y * sin, v.x * sin + v.y * cos)
+    };
+    [to + rotate(back, 0.5), to + rotate(back, -0.5)]
+}
+
+/// State of the Screenshot tool
+pub struct ScreenshotTool {
+    mode: CaptureMode,
+    delay_secs: u32,
+    receiver: Option<Receiver<Result<Capture, String>>>,
+    capture: Option<Capture>,
+    texture: Option<egui::TextureHandle>,
+    annotations: Vec<Annotation>,
+    tool: AnnotationTool,
+    color: Color32,
+    text_input: String,
+    drag_start: Option<Pos2>,
+    status: Option<Result<String, String>>,
+    last_saved: Option<PathBuf>,
+}
+
+impl Default for ScreenshotTool {
+    fn default() -> Self {
+        Self {
+            mode: CaptureMode::FullScreen,
+            delay_secs: 3,
+            receiver: None,
+            capture: None,
+            texture: None,
+            annotations: Vec::new(),
+            tool: AnnotationTool::Arrow,
+            color: ANNOTATION_COLORS[0],
+            text_input: String::new(),
+            drag_start: None,
+            status: None,
+            last_saved: None,
+        }
+    }
+}
+
+/// Draws a thick line by stamping discs along it
+fn draw_line(image: &mut Capture, from: Pos2, to: Pos2, color: Color32) {
+    let radius = STROKE_WIDTH / 2.0;
+    let steps = ((to - from).length() * 2.0).ceil().max(1.0) as usize;
+    for step in 0..=steps {
+        let center = from + (to - from) * (step as f32 / steps as f32);
+        for dy in -radius.ceil() as i32..=radius.ceil() as i32 {
+            for dx in -radius.ceil() as i32..=radius.ceil() as i32 {
+                if (dx * dx + dy * dy) as f32 <= radius * radius {
+                    blend_pixel(image, center.x as i32 + dx, center.y as i32 + dy, color, 1.0);
+                }
+            }
+        }
+    }
+}
+
+fn blend_pixel(image: &mut Capture, x: i32, y: i32, color: Color32, coverage: f32) {
+    if x < 0 || y < 0 || x as usize >= image.width || y as usize >= image.height {
+        return;
+    }
+    let index = (y as usize * image.width + x as usize) * 4;
+    let alpha = coverage.clamp(0.0, 1.0);
+    for (channel, value) in [color.r(), color.g(), color.b()].into_iter().enumerate() {
+        let current = image.pixels[index + channel] as f32;
+        image.pixels[index + channel] = (current + (value as f32 - current) * alpha) as u8;
+    }
+}
+
+/// Rasterizes text with the same font the UI uses
+fn draw_text(image: &mut Capture, pos: Pos2, text: &str, color: Color32) {
+    let fonts = egui::FontDefinitions::default();
+    let Some(data) = fonts.font_data.get("Ubuntu-Light") else { return };
+    let Ok(font) = FontRef::try_from_slice(&data.font) else { return };
+    let scale = PxScale::from(TEXT_SIZE);
+    let scaled = font.as_scaled(scale);
+
+    let mut caret = ab_glyph::point(pos.x, pos.y + scaled.ascent());
+    for character in text.chars() {
+        let glyph_id = font.glyph_id(character);
+        let glyph = glyph_id.with_scale_and_position(scale, caret);
+        caret.x += scaled.h_advance(glyph_id);
+        if let Some(outlined) = font.outline_glyph(glyph) {
+            let bounds = outlined.px_bounds();
+            outlined.draw(|x, y, coverage| {
+                blend_pixel(image, bounds.min.x as i32 + x as i32, bounds.min.y as i32 + y as i32, color, coverage);
+            });
+        }
+    }
+}
+
+impl ScreenshotTool {
+    fn start_capture(&mut self) {
+        let mode = self.mode;
+        let delay = Duration::from_secs(self.delay_secs as u64);
+        let (sender, receiver) = channel();
+        self.receiver = Some(receiver);
+        self.status = None;
+        std::thread::spawn(move || {
+            std::thread::sleep(delay);
+            let capture = match mode {
+                CaptureMode::Window => capture_foreground_window(),
+                CaptureMode::FullScreen | CaptureMode::Region => capture_full_screen(),
+            };
+            sender.send(capture).ok();
+        });
+    }
+
+    fn poll(&mut self) {
+        let Some(receiver) = &self.receiver else { return };
+        if let Ok(result) = receiver.try_recv() {
+            self.receiver = None;
+            match result {
+                Ok(capture) => {
+                    info!("Captured {}x{} screenshot", capture.width, capture.height);
+                    self.capture = Some(capture);
+                    self.texture = None;
+                    self.annotations.clear();
+                    if self.mode == CaptureMode::Region {
+                        self.tool = AnnotationTool::Crop;
+                    }
+                }
+                Err(e) => {
+                    warn!("Screenshot failed: {}", e);
+                    self.status = Some(Err(e));
+                }
+            }
+        }
+    }
+
+    /// Cuts the capture down to `rect` and moves annotations along with it
+    fn crop(&mut self, rect: Rect) {
+        let Some(capture) = &mut self.capture else { return };
+        let rect = rect.intersect(Rect::from_min_size(Pos2::ZERO, egui::vec2(capture.width as f32, capture.height as f32)));
+        let (x0, y0) = (rect.min.x as usize, rect.min.y as usize);
+        let (width, height) = (rect.width() as usize, rect.height() as usize);
+        if width < 2 || height < 2 {
+            return;
+        }
+
+        let mut pixels = Vec::with_capacity(width * height * 4);
+        for row in y0..y0 + height {
+            let start = (row * capture.width + x0) * 4;
+            pixels.extend_from_slice(&capture.pixels[start..start + width * 4]);
+        }
+        *capture = Capture { width, height, pixels };
+        for annotation in &mut self.annotations {
+            annotation.translate(-rect.min.to_vec2());
+        }
+        self.texture = None;
+    }
+
+    /// The capture with annotations burned in
+    fn render(&self) -> Option<Capture> {
+        let mut image = self.capture.clone()?;
+        for annotation in &self.annotations {
+            match annotation {
+                Annotation::Arrow { from, to, color } => {
+                    draw_line(&mut image, *from, *to, *color);
+                    for barb in arrow_head(*from, *to) {
+                        draw_line(&mut image, *to, barb, *color);
+                    }
+                }
+                Annotation::Box { rect, color } => {
+                    for (a, b) in [
+                        (rect.left_top(), rect.right_top()),
+                        (rect.right_top(), rect.right_bottom()),
+                        (rect.right_bottom(), rect.left_bottom()),
+                        (rect.left_bottom(), rect.left_top()),
+                    ] {
+                        draw_line(&mut image, a, b, *color);
+                    }
+                }
+                Annotation::Text { pos, text, color } => draw_text(&mut image, *pos, text, *color),
+            }
+        }
+        Some(image)
+    }
+
+    fn save(&mut self) {
+        let Some(image) = self.render() else { return };
+        let folder = std::env::var_os("USERPROFILE")
+            .map(|home| PathBuf::from(home).join("Pictures").join("Dev Dashboard"))
+            .unwrap_or_else(std::env::temp_dir);
+        let path = folder.join(format!("screenshot-{}.png", Local::now().format("%Y%m%d-%H%M%S")));
+
+        let result = std::fs::create_dir_all(&folder)
+            .map_err(|e| e.to_string())
+            .and_then(|_| image::save_buffer(&path, &image.pixels, image.width as u32, image.height as u32, image::ColorType::Rgba8)
+                .map_err(|e| e.to_string()));
+        self.status = Some(match result {
+            Ok(()) => {
+                info!("Saved screenshot to {:?}", path);
+                self.last_saved = Some(path.clone());
+                Ok(format!("Saved to {}", path.display()))
+            }
+            Err(e) => Err(format!("Could not save: {}", e)),
+        });
+    }
+
+    fn copy(&mut self) {
+        let Some(image) = self.render() else { return };
+        let result = arboard::Clipboard::new().and_then(|mut clipboard| {
+            clipboard.set_image(arboard::ImageData {
+                width: image.width,
+                height: image.height,
+                bytes: image.pixels.into(),
+            })
+        });
+        self.status = Some(match result {
+            Ok(()) => Ok("Copied to clipboard".to_string()),
+            Err(e) => Err(format!("Could not copy: {}", e)),
+        });
+    }
+}
+
+impl DevDashboard {
+    /// Screenshot tool page
+    /// Captures the screen or a window, then annotates, crops, saves or copies it
+    pub(crate) fn show_screenshot_tool(&mut self, ui: &mut egui::Ui) {
+        let tool = &mut self.screenshot;
+        tool.poll();
+
+        ui.heading("Screenshot");
+        ui.add_space(8.0);
+
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut tool.mode, CaptureMode::FullScreen, "Full screen");
+            ui.selectable_value(&mut tool.mode, CaptureMode::Window, "Window")
+                .on_hover_text("Captures the window in front once the delay has passed");
+            ui.selectable_value(&mut tool.mode, CaptureMode::Region, "Region")
+                .on_hover_text("Captures the full screen, then drag to select the region");
+            ui.separator();
+            ui.label("Delay:");
+            ui.add(egui::DragValue::new(&mut tool.delay_secs).clamp_range(0..=10).suffix(" s"));
+            if tool.receiver.is_some() {
+                ui.spinner();
+            } else if ui.button("📷 Capture").clicked() {
+                tool.start_capture();
+            }
+        });
+
+        match &tool.status {
+            Some(Ok(message)) => {
+                ui.horizontal(|ui| {
+                    ui.colored_label(Color32::from_rgb(22, 163, 74), message);
+                    if let Some(path) = &tool.last_saved {
+                        if ui.small_button("Show in folder").clicked() {
+                            let _ = std::process::Command::new("explorer")
+                                .arg(format!("/select,{}", path.display()))
+                                .spawn();
+                        }
+                    }
+                });
+            }
+            Some(Err(error)) => {
+                ui.colored_label(Color32::from_rgb(220, 50, 50), error);
+            }
+            None => {}
+        }
+        ui.add_space(8.0);
+
+        let Some((width, height)) = tool.capture.as_ref().map(|capture| (capture.width, capture.height)) else {
+            ui.label(RichText::new("Switch to the window you want within the delay, then annotate the capture here.")
+                .color(Color32::from_rgb(156, 163, 175)));
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut tool.tool, AnnotationTool::Arrow, "➡ Arrow");
+            ui.selectable_value(&mut tool.tool, AnnotationTool::Box, "☐ Box");
+            ui.selectable_value(&mut tool.tool, AnnotationTool::Text, "T Text");
+            ui.selectable_value(&mut tool.tool, AnnotationTool::Crop, "✂ Crop");
+            if tool.tool == AnnotationTool::Text {
+                ui.add(egui::TextEdit::singleline(&mut tool.text_input)
+                    .hint_text("Text, then click the image")
+                    .desired_width(160.0));
+            }
+            ui.separator();
+            for color in ANNOTATION_COLORS {
+                let (rect, response) = ui.allocate_exact_size(egui::vec2(16.0, 16.0), egui::Sense::click());
+                ui.painter().rect_filled(rect, 3.0, color);
+                if tool.color == color {
+                    ui.painter().rect_stroke(rect, 3.0, egui::Stroke::new(2.0, Color32::WHITE));
+                }
+                if response.clicked() {
+                    tool.color = color;
+                }
+            }
+        });
+        ui.horizontal(|ui| {
+            if ui.add_enabled(!tool.annotations.is_empty(), egui::Button::new("↶ Undo")).clicked() {
+                tool.annotations.pop();
+            }
+            if ui.button("💾 Save").clicked() {
+                tool.save();
+            }
+            if ui.button("📋 Copy").clicked() {
+                tool.copy();
+            }
+            if ui.button("Discard").clicked() {
+                tool.capture = None;
+                tool.texture = None;
+                tool.annotations.clear();
+            }
+            ui.label(format!("{} × {}", width, height));
+        });
+        ui.add_space(4.0);
+
+        let Some(capture) = &tool.capture else { return };
+        let image_size = egui::vec2(capture.width as f32, capture.height as f32);
+        let texture = tool.texture.get_or_insert_with(|| {
+            let image = egui::ColorImage::from_rgba_unmultiplied([capture.width, capture.height], &capture.pixels);
+            ui.ctx().load_texture("screenshot", image, egui::TextureOptions::LINEAR)
+        });
+
+        let available = ui.available_size();
+        let scale = (available.x / image_size.x).min(available.y.max(200.0) / image_size.y).min(1.0);
+        let (rect, response) = ui.allocate_exact_size(image_size * scale, egui::Sense::click_and_drag());
+        let to_screen = |p: Pos2| rect.min + p.to_vec2() * scale;
+        let to_image = |p: Pos2| Pos2::ZERO + (p - rect.min) / scale;
+
+        let painter = ui.painter_at(rect);
+        painter.image(texture.id(), rect, Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)), Color32::WHITE);
+
+        let stroke_for = |color: Color32| egui::Stroke::new((STROKE_WIDTH * scale).max(1.0), color);
+        let paint = |annotation: &Annotation| match annotation {
+            Annotation::Arrow { from, to, color } => {
+                painter.line_segment([to_screen(*from), to_screen(*to)], stroke_for(*color));
+                for barb in arrow_head(*from, *to) {
+                    painter.line_segment([to_screen(*to), to_screen(barb)], stroke_for(*color));
+                }
+            }
+            Annotation::Box { rect, color } => {
+                painter.rect_stroke(Rect::from_min_max(to_screen(rect.min), to_screen(rect.max)), 0.0, stroke_for(*color));
+            }
+            Annotation::Text { pos, text, color } => {
+                painter.text(to_screen(*pos), egui::Align2::LEFT_TOP, text, egui::FontId::proportional(TEXT_SIZE * scale), *color);
+            }
+        };
+        for annotation in &tool.annotations {
+            paint(annotation);
+        }
+
+        let pointer = response.interact_pointer_pos().map(to_image);
+        if response.drag_started() {
+            tool.drag_start = pointer;
+        }
+
+        // Preview of the shape being dragged
+        let dragged = match (tool.drag_start, pointer) {
+            (Some(start), Some(end)) if tool.tool != AnnotationTool::Text => Some((start, end)),
+            _ => None,
+        };
+        if let Some((start, end)) = dragged {
+            match tool.tool {
+                AnnotationTool::Arrow => paint(&Annotation::Arrow { from: start, to: end, color: tool.color }),
+                AnnotationTool::Box => paint(&Annotation::Box { rect: Rect::from_two_pos(start, end), color: tool.color }),
+                AnnotationTool::Crop => {
+                    let selection = Rect::from_min_max(to_screen(start), to_screen(end));
+                    painter.rect_stroke(Rect::from_two_pos(selection.min, selection.max), 0.0,
+                        egui::Stroke::new(1.0, Color32::WHITE));
+                }
+                AnnotationTool::Text => {}
+            }
+        }
+
+        if response.drag_released() {
+            if let Some((start, end)) = dragged {
+                if (end - start).length() > 3.0 {
+                    match tool.tool {
+                        AnnotationTool::Arrow => tool.annotations.push(Annotation::Arrow { from: start, to: end, color: tool.color }),
+                        AnnotationTool::Box => tool.annotations.push(Annotation::Box { rect: Rect::from_two_pos(start, end), color: tool.color }),
+                        AnnotationTool::Crop => tool.crop(Rect::from_two_pos(start, end)),
+                        AnnotationTool::Text => {}
+                    }
+                }
+            }
+            tool.drag_start = None;
+        }
+
+        if response.clicked() && tool.tool == AnnotationTool::Text && !tool.text_input.trim().is_empty() {
+            if let Some(pos) = pointer {
+                tool.annotations.push(Annotation::Text { pos, text: tool.text_input.trim().to_string(), color: tool.color });
+                tool.text_input.clear();
+            }
+        }
+    }
+}
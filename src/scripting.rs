@@ -0,0 +1,328 @@
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::{Duration, Instant, SystemTime};
+use eframe::egui;
+use egui::{Color32, RichText};
+use rhai::{Dynamic, Engine, ImmutableString, Map, Scope, AST};
+use serde::{Serialize, Deserialize};
+use sysinfo::{CpuExt, SystemExt};
+use log::{info, warn};
+use crate::plugins::{self, CardContent, CardRow};
+use crate::notifications::NotificationSource;
+use crate::DevDashboard;
+
+/// Folder scanned for *.rhai files, relative to the working directory
+const SCRIPTS_DIR: &str = "scripts";
+/// Upper bound on work per run so a runaway loop cannot freeze the UI
+const MAX_OPERATIONS: u64 = 200_000;
+/// How often the scripts folder is checked for new and changed files
+const SCAN_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Persisted scripting configuration
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ScriptSettings {
+    pub enabled: bool,
+}
+
+/// What a script produced during its last run
+#[derive(Default)]
+struct ScriptOutput {
+    title: Option<String>,
+    rows: Vec<CardRow>,
+    alerts: Vec<String>,
+}
+
+pub struct Script {
+    pub file_name: String,
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    ast: Result<AST, String>,
+    scope: Scope<'static>,          // Keeps the `state` map between runs
+    pub title: Option<String>,      // Set by card(); scripts without a title have no card
+    pub content: CardContent,
+    alerts: Vec<String>,            // Raised by the previous run
+}
+
+/// Rhai engine shared by all scripts, with the dashboard functions registered
+pub struct ScriptHost {
+    engine: Engine,
+    output: Rc<RefCell<ScriptOutput>>,
+    pub scripts: Vec<Script>,
+    last_scan: Option<Instant>,
+}
+
+/// Accepts both integers and floats from scripts
+fn as_number(value: &Dynamic) -> f64 {
+    value.as_float().or_else(|_| value.as_int().map(|value| value as f64)).unwrap_or(0.0)
+}
+
+fn create_engine(output: &Rc<RefCell<ScriptOutput>>) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(32);
+    engine.set_max_string_size(64 * 1024);
+    engine.set_max_array_size(10_000);
+    engine.set_max_map_size(10_000);
+    engine.on_print(|text| info!("[script] {}", text));
+    engine.on_debug(|text, _, _| info!("[script] {}", text));
+
+    let out = output.clone();
+    engine.register_fn("card", move |title: ImmutableString| {
+        out.borrow_mut().title = Some(title.to_string());
+    });
+    let out = output.clone();
+    engine.register_fn("row", move |label: ImmutableString, value: Dynamic| {
+        out.borrow_mut().rows.push(CardRow { label: label.to_string(), value: value.to_string(), ..Default::default() });
+    });
+    let out = output.clone();
+    engine.register_fn("row", move |label: ImmutableString, value: Dynamic, color: ImmutableString| {
+        out.borrow_mut().rows.push(CardRow {
+            label: label.to_string(),
+            value: value.to_string(),
+            color: Some(color.to_string()),
+            ..Default::default()
+        });
+    });
+    let out = output.clone();
+    engine.register_fn("bar", move |label: ImmutableString, fraction: Dynamic| {
+        let fraction = as_number(&fraction) as f32;
+        out.borrow_mut().rows.push(CardRow {
+            label: label.to_string(),
+            value: format!("{:.0}%", fraction * 100.0),
+            progress: Some(fraction),
+            color: None,
+        });
+    });
+    let out = output.clone();
+    engine.register_fn("bar", move |label: ImmutableString, fraction: Dynamic, color: ImmutableString| {
+        let fraction = as_number(&fraction) as f32;
+        out.borrow_mut().rows.push(CardRow {
+            label: label.to_string(),
+            value: format!("{:.0}%", fraction * 100.0),
+            progress: Some(fraction),
+            color: Some(color.to_string()),
+        });
+    });
+    let out = output.clone();
+    engine.register_fn("alert", move |message: ImmutableString| {
+        out.borrow_mut().alerts.push(message.to_string());
+    });
+    engine
+}
+
+impl Default for ScriptHost {
+    fn default() -> Self {
+        let output = Rc::new(RefCell::new(ScriptOutput::default()));
+        Self {
+            engine: create_engine(&output),
+            output,
+            scripts: Vec::new(),
+            last_scan: None,
+        }
+    }
+}
+
+impl ScriptHost {
+    /// Picks up new, changed and deleted scripts; state is reset when a file changes.
+    /// Runs on the UI thread, so the folder is only read every few seconds.
+    fn scan(&mut self) {
+        if self.last_scan.is_some_and(|last| last.elapsed() < SCAN_INTERVAL) {
+            return;
+        }
+        self.last_scan = Some(Instant::now());
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(SCRIPTS_DIR)
+            .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+            .unwrap_or_default();
+        paths.retain(|path| path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("rhai")));
+        paths.sort();
+
+        self.scripts.retain(|script| paths.contains(&script.path));
+        for path in paths {
+            let modified = std::fs::metadata(&path).and_then(|metadata| metadata.modified()).ok();
+            let position = self.scripts.iter().position(|script| script.path == path);
+            if let Some(index) = position {
+                if self.scripts[index].modified == modified {
+                    continue;
+                }
+            }
+
+            let ast = std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|source| self.engine.compile(source).map_err(|e| e.to_string()));
+            let file_name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+            match &ast {
+                Ok(_) => info!("Loaded script {}", file_name),
+                Err(e) => warn!("Script {} failed to compile: {}", file_name, e),
+            }
+            let mut scope = Scope::new();
+            scope.push("state", Map::new());
+            let script = Script {
+                file_name,
+                path,
+                modified,
+                ast,
+                scope,
+                title: None,
+                content: CardContent::default(),
+                alerts: Vec::new(),
+            };
+            match position {
+                Some(index) => self.scripts[index] = script,
+                None => self.scripts.push(script),
+            }
+        }
+    }
+
+    /// Runs every script once against the given metrics; returns alerts that weren't raised on the previous run,
+    /// as (script file, alert)
+    fn run(&mut self, metrics: &Map) -> Vec<(String, String)> {
+        let mut raised = Vec::new();
+        for script in &mut self.scripts {
+            let ast = match &script.ast {
+                Ok(ast) => ast,
+                Err(e) => {
+                    script.content = CardContent::error(e.clone());
+                    continue;
+                }
+            };
+            *self.output.borrow_mut() = ScriptOutput::default();
+            script.scope.set_value("metrics", metrics.clone());
+            let result = self.engine.run_ast_with_scope(&mut script.scope, ast);
+
+            let output = std::mem::take(&mut *self.output.borrow_mut());
+            // Only log alerts when they first appear, not on every run while they persist
+            for alert in output.alerts.iter().filter(|alert| !script.alerts.contains(alert)) {
+                warn!("[script {}] {}", script.file_name, alert);
+                raised.push((script.file_name.clone(), alert.clone()));
+            }
+            script.alerts = output.alerts.clone();
+            let mut errors = output.alerts;
+            if let Err(e) = &result {
+                errors.push(e.to_string());
+            }
+            // A failing run keeps the card on screen so the error is visible
+            script.title = if result.is_err() { output.title.or(script.title.take()) } else { output.title };
+            script.content = CardContent {
+                rows: output.rows,
+                error: (!errors.is_empty()).then(|| errors.join("\n")),
+            };
+        }
+        raised
+    }
+
+    /// Indices of scripts that draw a dashboard card
+    pub fn card_indices(&self) -> Vec<usize> {
+        self.scripts
+            .iter()
+            .enumerate()
+            .filter(|(_, script)| script.title.is_some())
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+impl DevDashboard {
+    /// Current readings exposed to scripts as the `metrics` map
+    fn script_metrics(&self) -> Map {
+        let mut metrics = Map::new();
+        let total_memory = self.sys.total_memory();
+        metrics.insert("cpu_usage".into(), Dynamic::from(self.current_cpu_usage.target as f64));
+        metrics.insert("cpu_threads".into(), Dynamic::from(self.sys.cpus().len() as i64));
+        metrics.insert("cpu_frequency_mhz".into(), Dynamic::from(self.sys.cpus().first().map(|cpu| cpu.frequency()).unwrap_or(0) as i64));
+        metrics.insert("memory_total".into(), Dynamic::from(total_memory as i64));
+        metrics.insert("memory_used".into(), Dynamic::from(total_memory.saturating_sub(self.sys.available_memory()) as i64));
+        metrics.insert("memory_usage".into(), Dynamic::from(self.memory_usage.target as f64 * 100.0));
+        metrics.insert("uptime_secs".into(), Dynamic::from(self.sys.uptime() as i64));
+        metrics.insert("away".into(), Dynamic::from(self.idle.is_away()));
+
+        let (received, sent) = self.network_stats.values()
+            .fold((0.0, 0.0), |(received, sent), stats| (received + stats.received_speed, sent + stats.sent_speed));
+        metrics.insert("net_received_per_sec".into(), Dynamic::from(received));
+        metrics.insert("net_sent_per_sec".into(), Dynamic::from(sent));
+
+        let disks: rhai::Array = self.volume_monitor.volumes().iter().filter_map(|volume| {
+            let (total, free) = volume.space?;
+            let mut entry = Map::new();
+            entry.insert("mount".into(), Dynamic::from(volume.mount.clone()));
+            entry.insert("total".into(), Dynamic::from(total as i64));
+            entry.insert("free".into(), Dynamic::from(free as i64));
+            Some(Dynamic::from_map(entry))
+        }).collect();
+        metrics.insert("disks".into(), Dynamic::from_array(disks));
+
+        if let Some(gpu) = &self.gpu_info {
+            metrics.insert("gpu_name".into(), Dynamic::from(gpu.name.clone()));
+            metrics.insert("gpu_usage".into(), Dynamic::from(gpu.utilization.unwrap_or(0.0) as f64));
+            if let Some(temperature) = gpu.temperature {
+                metrics.insert("gpu_temperature".into(), Dynamic::from(temperature as i64));
+            }
+            if let (Some(used), Some(total)) = (gpu.memory_used, gpu.memory_total) {
+                metrics.insert("gpu_memory_used".into(), Dynamic::from(used as i64));
+                metrics.insert("gpu_memory_total".into(), Dynamic::from(total as i64));
+            }
+        }
+        metrics
+    }
+
+    /// Reloads changed scripts and runs them with fresh metrics
+    pub(crate) fn update_scripts(&mut self) {
+        if !self.settings.scripts.enabled {
+            return;
+        }
+        self.scripts.scan();
+        let metrics = self.script_metrics();
+        for (file_name, alert) in self.scripts.run(&metrics) {
+            self.notifications.push(NotificationSource::Script, &file_name, &alert);
+        }
+    }
+
+    /// Displays the card drawn by a script
+    pub(crate) fn show_script_card(&self, ui: &mut egui::Ui, index: usize) {
+        let Some(script) = self.scripts.scripts.get(index) else { return };
+        self.show_card(ui, script.title.as_deref().unwrap_or_default(), |ui| {
+            plugins::show_card_rows(ui, &script.content);
+        });
+    }
+
+    /// Settings section for user scripts
+    pub(crate) fn show_script_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            changed |= ui.checkbox(&mut self.settings.scripts.enabled, "Run scripts")
+                .on_hover_text(format!("Runs every .rhai file in the {} folder once a second", SCRIPTS_DIR))
+                .changed();
+            if ui.button("Open folder").clicked() {
+                let _ = std::fs::create_dir_all(SCRIPTS_DIR);
+                let _ = std::process::Command::new("explorer").arg(SCRIPTS_DIR).spawn();
+            }
+        });
+        if self.settings.scripts.enabled {
+            if self.scripts.scripts.is_empty() {
+                ui.label(RichText::new(format!("No scripts found in {}\\", SCRIPTS_DIR)).color(Color32::from_rgb(156, 163, 175)));
+            }
+            for script in &self.scripts.scripts {
+                ui.horizontal(|ui| {
+                    ui.label(&script.file_name);
+                    match (&script.ast, &script.content.error) {
+                        (Err(error), _) => {
+                            ui.colored_label(Color32::from_rgb(220, 50, 50), "Compile error").on_hover_text(error);
+                        }
+                        (Ok(_), Some(error)) => {
+                            ui.colored_label(Color32::from_rgb(202, 138, 4), "Error or alert").on_hover_text(error);
+                        }
+                        (Ok(_), None) => {
+                            ui.colored_label(Color32::from_rgb(22, 163, 74), "OK");
+                        }
+                    }
+                });
+            }
+        }
+
+        if changed {
+            self.save_settings();
+        }
+    }
+}
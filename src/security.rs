@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+use eframe::egui;
+use egui::RichText;
+use sysinfo::{Pid, PidExt, ProcessExt, System, SystemExt};
+use log::{info, warn};
+use crate::ports::{self, SocketProtocol};
+use crate::signature::{self, SignatureInfo, SignatureStatus};
+use crate::DevDashboard;
+
+/// How often the audit re-scans while the Security tab is visible
+const AUDIT_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// A listening socket reachable from other machines, with the process that owns it
+#[derive(Clone)]
+pub struct ExposedPort {
+    pub protocol: SocketProtocol,
+    pub address: IpAddr,             // Local bind address (0.0.0.0 / :: means all interfaces)
+    pub port: u16,
+    pub pid: u32,
+    pub process_name: String,
+    pub exe_path: Option<PathBuf>,   // Binary path, if the process could be opened
+    pub signature: SignatureInfo,
+}
+
+impl ExposedPort {
+    /// Unsigned binaries are the interesting ones; unreadable ones are reported but not flagged
+    pub fn is_flagged(&self) -> bool {
+        self.signature.status == SignatureStatus::Unsigned
+    }
+}
+
+/// State of the open-port security audit shown in the Security tab
+#[derive(Default)]
+pub struct SecurityAudit {
+    pub findings: Vec<ExposedPort>,
+    pub last_scan: Option<Instant>,
+    pub scanning: bool,
+    pub flagged_only: bool,          // Only list ports owned by unsigned binaries
+    receiver: Option<Receiver<Vec<ExposedPort>>>,
+}
+
+impl SecurityAudit {
+    /// Starts a background scan unless one is already running
+    pub fn start_scan(&mut self) {
+        if self.scanning {
+            return;
+        }
+
+        info!("Starting open-port security audit");
+        let (sender, receiver) = channel();
+        self.receiver = Some(receiver);
+        self.scanning = true;
+
+        std::thread::spawn(move || {
+            let findings = Self::scan();
+            sender.send(findings).ok();
+        });
+    }
+
+    /// Collects results from a finished background scan
+    pub fn poll(&mut self) {
+        if let Some(receiver) = &self.receiver {
+            if let Ok(findings) = receiver.try_recv() {
+                info!(
+                    "Security audit found {} exposed ports ({} unsigned)",
+                    findings.len(),
+                    findings.iter().filter(|f| f.is_flagged()).count()
+                );
+                self.findings = findings;
+                self.last_scan = Some(Instant::now());
+                self.scanning = false;
+                self.receiver = None;
+            }
+        }
+    }
+
+    fn needs_refresh(&self) -> bool {
+        match self.last_scan {
+            Some(last) => last.elapsed() >= AUDIT_REFRESH_INTERVAL,
+            None => true,
+        }
+    }
+
+    /// Enumerates non-loopback listening sockets and resolves their owning binaries
+    fn scan() -> Vec<ExposedPort> {
+        let mut sys = System::new();
+        sys.refresh_processes();
+
+        // Many sockets share a binary, so only verify each path once
+        let mut signature_cache: HashMap<PathBuf, SignatureInfo> = HashMap::new();
+
+        let mut findings: Vec<ExposedPort> = ports::listening_sockets()
+            .into_iter()
+            .filter(|socket| !socket.is_loopback())
+            .map(|socket| {
+                let process = sys.process(Pid::from_u32(socket.pid));
+                let process_name = match (socket.pid, process) {
+                    (0, _) => "System Idle Process".to_string(),
+                    (4, _) => "System".to_string(),
+                    (_, Some(process)) => process.name().to_string(),
+                    (_, None) => "Unknown".to_string(),
+                };
+                let exe_path = process
+                    .map(|process| process.exe().to_path_buf())
+                    .filter(|path| !path.as_os_str().is_empty());
+
+                let signature = match &exe_path {
+                    Some(path) => signature_cache
+                        .entry(path.clone())
+                        .or_insert_with(|| signature::verify(path))
+                        .clone(),
+                    None => SignatureInfo { status: SignatureStatus::Unknown, signer: None },
+                };
+
+                if signature.status == SignatureStatus::Unsigned {
+                    warn!("Unsigned binary listening on {} {}:{}: {:?}",
+                        socket.protocol.label(), socket.local_addr, socket.local_port, exe_path);
+                }
+
+                ExposedPort {
+                    protocol: socket.protocol,
+                    address: socket.local_addr,
+                    port: socket.local_port,
+                    pid: socket.pid,
+                    process_name,
+                    exe_path,
+                    signature,
+                }
+            })
+            .collect();
+
+        // Flagged entries first, then by port
+        findings.sort_by(|a, b| {
+            b.is_flagged().cmp(&a.is_flagged())
+                .then(a.port.cmp(&b.port))
+                .then(a.protocol.label().cmp(b.protocol.label()))
+        });
+        findings
+    }
+}
+
+impl DevDashboard {
+    /// Displays the Security tab
+    /// Lists ports exposed beyond loopback and highlights unsigned binaries behind them
+    pub(crate) fn show_security_tab(&mut self, ui: &mut egui::Ui) {
+        self.security_audit.poll();
+        if self.security_audit.needs_refresh() {
+            self.security_audit.start_scan();
+        }
+
+        let audit = &mut self.security_audit;
+
+        egui::Frame::none()
+            .inner_margin(egui::style::Margin::same(10.0))
+            .show(ui, |ui| {
+                ui.heading("Open Port Audit");
+                ui.add_space(4.0);
+                ui.label("Sockets listening on non-loopback interfaces are reachable from your network.");
+                ui.add_space(8.0);
+
+                let flagged = audit.findings.iter().filter(|f| f.is_flagged()).count();
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} exposed ports", audit.findings.len()));
+                    if flagged > 0 {
+                        ui.colored_label(egui::Color32::from_rgb(220, 50, 50),
+                            format!("{} owned by unsigned binaries", flagged));
+                    }
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if audit.scanning {
+                            ui.spinner();
+                        } else if ui.button("Rescan").clicked() {
+                            audit.start_scan();
+                        }
+                        ui.checkbox(&mut audit.flagged_only, "Unsigned only");
+                    });
+                });
+                ui.add_space(8.0);
+
+                egui::ScrollArea::vertical().id_source("security_audit_scroll").show(ui, |ui| {
+                    egui::Grid::new("security_audit_grid")
+                        .striped(true)
+                        .spacing([16.0, 6.0])
+                        .show(ui, |ui| {
+                            ui.label(RichText::new("Protocol").strong());
+                            ui.label(RichText::new("Address").strong());
+                            ui.label(RichText::new("Port").strong());
+                            ui.label(RichText::new("PID").strong());
+                            ui.label(RichText::new("Process").strong());
+                            ui.label(RichText::new("Signature").strong());
+                            ui.end_row();
+
+                            for finding in audit.findings.iter().filter(|f| !audit.flagged_only || f.is_flagged()) {
+                                let address = if finding.address.is_unspecified() {
+                                    format!("{} (all)", finding.address)
+                                } else {
+                                    finding.address.to_string()
+                                };
+
+                                ui.label(finding.protocol.label());
+                                ui.label(address);
+                                ui.label(finding.port.to_string());
+                                ui.label(finding.pid.to_string());
+                                let process_label = ui.label(&finding.process_name);
+                                if let Some(path) = &finding.exe_path {
+                                    process_label.on_hover_text(path.display().to_string());
+                                }
+                                let signature_label = ui.colored_label(
+                                    finding.signature.status.color(), finding.signature.status.label());
+                                if let Some(signer) = &finding.signature.signer {
+                                    signature_label.on_hover_text(signer);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+    }
+}
@@ -0,0 +1,148 @@
+use std::time::{Duration, Instant};
+use eframe::egui;
+use egui::{Color32, RichText};
+#[cfg(feature = "nvml")]
+use nvml_wrapper::Nvml;
+use log::{info, warn};
+#[cfg(feature = "nvml")]
+use crate::hybrid_gpu::GpuAdapter;
+use crate::DevDashboard;
+
+/// Wait before the first retry; doubled after each failure up to the maximum
+const FIRST_RETRY: Duration = Duration::from_secs(30);
+const MAX_RETRY: Duration = Duration::from_secs(10 * 60);
+
+/// Whether a sensor backend is working, and when it is tried again if not
+#[derive(Default)]
+pub struct BackendHealth {
+    error: Option<String>,
+    failures: u32,
+    retry_at: Option<Instant>,
+}
+
+impl BackendHealth {
+    pub fn succeeded(&mut self, backend: &str) {
+        if self.error.take().is_some() {
+            info!("{} recovered after {} failed attempts", backend, self.failures);
+        }
+        self.failures = 0;
+        self.retry_at = None;
+    }
+
+    /// Records a failure and schedules the next attempt, backing off while it keeps failing
+    pub fn failed(&mut self, backend: &str, error: String) {
+        if self.error.as_ref() != Some(&error) {
+            warn!("{} failed: {}", backend, error);
+        }
+        let delay = FIRST_RETRY.saturating_mul(1 << self.failures.min(5)).min(MAX_RETRY);
+        self.failures += 1;
+        self.retry_at = Some(Instant::now() + delay);
+        self.error = Some(error);
+    }
+
+    pub fn retry_due(&self) -> bool {
+        !self.retry_at.is_some_and(|time| Instant::now() < time)
+    }
+
+    pub fn retry_now(&mut self) {
+        self.retry_at = None;
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Colored status line with a Retry button while failed; returns true when it was clicked
+    pub fn show(&self, ui: &mut egui::Ui, backend: &str) -> bool {
+        let mut retry = false;
+        ui.horizontal(|ui| {
+            match &self.error {
+                None => {
+                    ui.label(RichText::new("●").color(Color32::from_rgb(22, 163, 74)));
+                    ui.label(backend);
+                }
+                Some(error) => {
+                    ui.label(RichText::new("●").color(Color32::from_rgb(220, 50, 50)));
+                    ui.label(backend).on_hover_text(error);
+                    let wait = self.retry_at.map(|time| time.saturating_duration_since(Instant::now()).as_secs()).unwrap_or(0);
+                    let next = match wait {
+                        0 => "retrying now".to_string(),
+                        seconds if seconds < 60 => format!("retrying in {} s", seconds),
+                        seconds => format!("retrying in {} min", seconds.div_ceil(60)),
+                    };
+                    ui.label(RichText::new(format!("failed, {}", next)).color(Color32::from_rgb(107, 114, 128)));
+                    retry = ui.small_button("Retry").clicked();
+                }
+            }
+        });
+        retry
+    }
+}
+
+/// Health of the GPU backends; the disk and processor counter threads keep their own
+#[derive(Default)]
+pub struct SensorBackends {
+    #[cfg(feature = "nvml")]
+    pub nvml: BackendHealth,
+    pub gpu: BackendHealth,         // Finding a GPU through NVML or WMI
+}
+
+impl DevDashboard {
+    /// Retries GPU backends that failed, e.g. while a driver update had the GPU unavailable at startup
+    pub(crate) fn update_sensor_recovery(&mut self) {
+        #[cfg(feature = "nvml")]
+        {
+            let has_nvidia = self.gpu_adapters.list.iter().any(GpuAdapter::is_nvidia);
+            if self.nvml.is_none() && has_nvidia && self.sensor_backends.nvml.retry_due() {
+                match Nvml::init() {
+                    Ok(nvml) => {
+                        self.sensor_backends.nvml.succeeded("NVML");
+                        self.nvml = Some(nvml);
+                        // The card may have fallen back to WMI, which has no power draw or limits
+                        self.gpu_info = Self::initialize_gpu(&self.settings.gpu_selection);
+                    }
+                    Err(e) => self.sensor_backends.nvml.failed("NVML", e.to_string()),
+                }
+            }
+        }
+
+        if self.gpu_info.is_some() {
+            self.sensor_backends.gpu.succeeded("GPU detection");
+        } else if self.sensor_backends.gpu.retry_due() {
+            self.gpu_info = Self::initialize_gpu(&self.settings.gpu_selection);
+            match self.gpu_info {
+                Some(_) => self.sensor_backends.gpu.succeeded("GPU detection"),
+                None => self.sensor_backends.gpu.failed("GPU detection", "No GPU found through NVML or WMI".to_string()),
+            }
+        }
+    }
+
+    /// Status and Retry on the GPU card while one of its backends is failing
+    pub(crate) fn show_gpu_recovery(&mut self, ui: &mut egui::Ui) {
+        #[cfg(feature = "nvml")]
+        if self.sensor_backends.nvml.error().is_some() && self.sensor_backends.nvml.show(ui, "NVIDIA driver (NVML)") {
+            self.sensor_backends.nvml.retry_now();
+        }
+        if self.sensor_backends.gpu.error().is_some() && self.sensor_backends.gpu.show(ui, "GPU detection") {
+            self.sensor_backends.gpu.retry_now();
+        }
+    }
+
+    /// Status of each sensor backend, with Retry for those that failed
+    pub(crate) fn show_sensor_settings(&mut self, ui: &mut egui::Ui) {
+        ui.label("Sensors:");
+        #[cfg(feature = "nvml")]
+        if self.gpu_adapters.list.iter().any(GpuAdapter::is_nvidia) && self.sensor_backends.nvml.show(ui, "NVIDIA driver (NVML)") {
+            self.sensor_backends.nvml.retry_now();
+        }
+        if self.sensor_backends.gpu.show(ui, "GPU detection") {
+            self.sensor_backends.gpu.retry_now();
+        }
+        if self.disk_io.health.show(ui, "Disk counters (WMI)") {
+            self.disk_io.health.retry_now();
+        }
+        if self.throttle_monitor.health.show(ui, "Processor counters (WMI)") {
+            self.throttle_monitor.health.retry_now();
+        }
+    }
+}
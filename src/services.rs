@@ -0,0 +1,362 @@
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::{Duration, Instant};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::Deserialize;
+use wmi::{COMLibrary, WMIConnection};
+use log::{info, warn};
+use crate::elevation::{ElevatedAction, ElevationBroker};
+use crate::guard::{SensitiveAction, ServiceControl};
+use crate::DevDashboard;
+
+/// The list is read again this often while the tab is open
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Deserialize, Clone)]
+#[serde(rename = "Win32_Service")]
+#[serde(rename_all = "PascalCase")]
+struct Win32Service {
+    name: String,
+    display_name: Option<String>,
+    description: Option<String>,
+    state: Option<String>,              // Running, Stopped, Start Pending, ...; not localized
+    start_mode: Option<String>,         // Auto, Manual, Disabled, Boot or System
+    delayed_auto_start: Option<bool>,
+    process_id: Option<u32>,
+    accept_stop: Option<bool>,
+}
+
+impl Win32Service {
+    fn display_name(&self) -> &str {
+        self.display_name.as_deref().filter(|name| !name.is_empty()).unwrap_or(&self.name)
+    }
+
+    fn state(&self) -> &str {
+        self.state.as_deref().unwrap_or("Unknown")
+    }
+
+    fn is_running(&self) -> bool {
+        self.state() == "Running"
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.state() == "Stopped"
+    }
+
+    /// Startup type as services.msc words it
+    fn startup_type(&self) -> &'static str {
+        match (self.start_mode.as_deref(), self.delayed_auto_start) {
+            (Some("Auto"), Some(true)) => "Automatic (Delayed)",
+            (Some("Auto"), _) => "Automatic",
+            (Some("Manual"), _) => "Manual",
+            (Some("Disabled"), _) => "Disabled",
+            (Some("Boot" | "System"), _) => "Driver",
+            _ => "Unknown",
+        }
+    }
+
+    fn state_color(&self) -> Color32 {
+        match self.state() {
+            "Running" => Color32::from_rgb(22, 163, 74),
+            "Stopped" => Color32::from_rgb(156, 163, 175),
+            _ => Color32::from_rgb(202, 138, 4),
+        }
+    }
+}
+
+/// Reads every service; runs on its own thread since COM is initialized per thread
+fn read_services() -> Result<Vec<Win32Service>, String> {
+    let com = COMLibrary::new().map_err(|e| format!("Could not initialize COM: {}", e))?;
+    let wmi = WMIConnection::new(com).map_err(|e| format!("Could not connect to WMI: {}", e))?;
+    let mut services: Vec<Win32Service> = wmi.query().map_err(|e| format!("Could not list services: {}", e))?;
+    services.sort_by_key(|service| service.display_name().to_lowercase());
+    Ok(services)
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum ServiceAction {
+    Start,
+    Stop,
+    Restart,
+}
+
+impl ServiceAction {
+    pub(crate) fn label(&self) -> &'static str {
+        match self {
+            ServiceAction::Start => "Start",
+            ServiceAction::Stop => "Stop",
+            ServiceAction::Restart => "Restart",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Win32_Service")]
+#[serde(rename_all = "PascalCase")]
+struct DependentService {
+    name: String,
+    state: Option<String>,
+}
+
+/// Running services that depend on this one, directly or through others, in an order they can be started in
+fn running_dependents(name: &str) -> Result<Vec<String>, String> {
+    let com = COMLibrary::new().map_err(|e| format!("Could not initialize COM: {}", e))?;
+    let wmi = WMIConnection::new(com).map_err(|e| format!("Could not connect to WMI: {}", e))?;
+    let mut dependents: Vec<String> = Vec::new();
+    let mut queue = vec![name.to_string()];
+    while !queue.is_empty() {
+        let service = queue.remove(0);
+        let query = format!(
+            "ASSOCIATORS OF {{Win32_Service.Name='{}'}} WHERE AssocClass=Win32_DependentService Role=Antecedent",
+            service.replace('\\', "\\\\").replace('\'', "\\'"),
+        );
+        let found: Vec<DependentService> = wmi.raw_query(query).map_err(|e| format!("Could not list dependents of {}: {}", service, e))?;
+        for dependent in found {
+            if dependent.state.as_deref() != Some("Running") {
+                continue;
+            }
+            // A service reached through two paths is started after the later one
+            dependents.retain(|name| !name.eq_ignore_ascii_case(&dependent.name));
+            dependents.push(dependent.name.clone());
+            queue.push(dependent.name);
+        }
+    }
+    Ok(dependents)
+}
+
+/// Starts or stops the service through the elevated helper.
+/// A restart is a stop followed by a start; `net stop /y` also stops the running dependents, so they are started again after.
+async fn run_action(name: String, action: ServiceAction, broker: ElevationBroker) -> Result<(), String> {
+    let mut dependents = Vec::new();
+    if action == ServiceAction::Restart {
+        let service = name.clone();
+        dependents = tokio::task::spawn_blocking(move || running_dependents(&service))
+            .await
+            .map_err(|e| e.to_string())?
+            .unwrap_or_else(|e| {
+                warn!("{}", e);
+                Vec::new()
+            });
+    }
+    if matches!(action, ServiceAction::Stop | ServiceAction::Restart) {
+        broker.run(ElevatedAction::StopService(name.clone())).await?;
+    }
+    if matches!(action, ServiceAction::Start | ServiceAction::Restart) {
+        broker.run(ElevatedAction::StartService(name)).await?;
+    }
+    let mut failed = Vec::new();
+    for dependent in dependents {
+        info!("Starting dependent service {}", dependent);
+        if let Err(e) = broker.run(ElevatedAction::StartService(dependent.clone())).await {
+            warn!("Could not start dependent service {}: {}", dependent, e);
+            failed.push(dependent);
+        }
+    }
+    if !failed.is_empty() {
+        return Err(format!("Restarted, but could not start {} again", failed.join(", ")));
+    }
+    Ok(())
+}
+
+/// Services for the Services tab and the actions running on them
+#[derive(Default)]
+pub struct ServiceList {
+    services: Vec<Win32Service>,
+    error: Option<String>,
+    last_scan: Option<Instant>,
+    receiver: Option<Receiver<Result<Vec<Win32Service>, String>>>,
+    search: String,                     // Part of the name or display name
+    running_only: bool,
+    action_sender: Option<Sender<(String, Result<(), String>)>>,
+    action_receiver: Option<Receiver<(String, Result<(), String>)>>,
+    actions: HashMap<String, ServiceAction>,    // Actions in flight by service name
+    results: HashMap<String, String>,   // Errors of the last action by service name
+}
+
+impl ServiceList {
+    fn is_scanning(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    fn start_scan(&mut self) {
+        let (sender, receiver) = channel();
+        self.receiver = Some(receiver);
+        self.last_scan = Some(Instant::now());
+        std::thread::spawn(move || {
+            sender.send(read_services()).ok();
+        });
+    }
+
+    fn poll(&mut self) {
+        if let Some(result) = self.receiver.as_ref().and_then(|receiver| receiver.try_recv().ok()) {
+            self.receiver = None;
+            match result {
+                Ok(services) => {
+                    self.services = services;
+                    self.error = None;
+                }
+                Err(e) => {
+                    warn!("{}", e);
+                    self.error = Some(e);
+                }
+            }
+        }
+
+        let mut finished = false;
+        if let Some(receiver) = &self.action_receiver {
+            while let Ok((name, result)) = receiver.try_recv() {
+                self.actions.remove(&name);
+                match result {
+                    Ok(()) => {
+                        self.results.remove(&name);
+                    }
+                    Err(e) => {
+                        warn!("Service {}: {}", name, e);
+                        self.results.insert(name, e);
+                    }
+                }
+                finished = true;
+            }
+        }
+        let due = match self.last_scan {
+            Some(last) => last.elapsed() >= REFRESH_INTERVAL,
+            None => true,
+        };
+        if (due || finished) && !self.is_scanning() {
+            self.start_scan();
+        }
+    }
+
+    fn matches(&self, service: &Win32Service) -> bool {
+        if self.running_only && !service.is_running() {
+            return false;
+        }
+        let search = self.search.trim().to_lowercase();
+        search.is_empty()
+            || service.name.to_lowercase().contains(&search)
+            || service.display_name().to_lowercase().contains(&search)
+    }
+}
+
+impl DevDashboard {
+    pub(crate) fn run_service_action(&mut self, name: String, action: ServiceAction) {
+        info!("{} service {}", action.label(), name);
+        let list = &mut self.service_list;
+        let sender = match &list.action_sender {
+            Some(sender) => sender.clone(),
+            None => {
+                let (sender, receiver) = channel();
+                list.action_sender = Some(sender.clone());
+                list.action_receiver = Some(receiver);
+                sender
+            }
+        };
+        list.actions.insert(name.clone(), action);
+        list.results.remove(&name);
+        let broker = self.elevation.clone();
+        self.runtime().spawn(async move {
+            let result = run_action(name.clone(), action, broker).await;
+            sender.send((name, result)).ok();
+        });
+    }
+
+    /// Services tab
+    /// Lists Windows services with their state and startup type, with search and start/stop/restart buttons
+    pub(crate) fn show_services_tab(&mut self, ui: &mut egui::Ui) {
+        self.service_list.poll();
+        ui.ctx().request_repaint_after(REFRESH_INTERVAL);
+
+        let grey = Color32::from_rgb(107, 114, 128);
+        let mut requested = None;
+        let list = &mut self.service_list;
+
+        egui::Frame::none()
+            .inner_margin(egui::style::Margin::same(10.0))
+            .show(ui, |ui| {
+                ui.heading("Services");
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    ui.label("Search:");
+                    ui.add(egui::TextEdit::singleline(&mut list.search)
+                        .hint_text("sql, docker")
+                        .desired_width(180.0));
+                    ui.checkbox(&mut list.running_only, "Running only");
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if list.is_scanning() {
+                            ui.spinner();
+                        } else if ui.button("⟳ Refresh").clicked() {
+                            list.start_scan();
+                        }
+                    });
+                });
+                ui.label(RichText::new("Starting and stopping services asks for administrator rights once per session")
+                    .small()
+                    .color(grey));
+                if let Some(error) = &list.error {
+                    ui.colored_label(Color32::from_rgb(220, 50, 50), error);
+                }
+                ui.add_space(8.0);
+
+                let shown: Vec<&Win32Service> = list.services.iter().filter(|service| list.matches(service)).collect();
+                ui.label(format!("{} of {} services", shown.len(), list.services.len()));
+                ui.add_space(4.0);
+
+                egui::ScrollArea::vertical().id_source("services_scroll").show(ui, |ui| {
+                    egui::Grid::new("services_grid")
+                        .striped(true)
+                        .spacing([16.0, 6.0])
+                        .show(ui, |ui| {
+                            ui.label(RichText::new("Name").strong());
+                            ui.label(RichText::new("State").strong());
+                            ui.label(RichText::new("Startup").strong());
+                            ui.label(RichText::new("PID").strong());
+                            ui.label("");
+                            ui.end_row();
+
+                            for service in shown {
+                                let response = ui.vertical(|ui| {
+                                    ui.label(service.display_name());
+                                    ui.label(RichText::new(&service.name).small().color(grey));
+                                }).response;
+                                if let Some(description) = service.description.as_deref().filter(|text| !text.is_empty()) {
+                                    response.on_hover_text(description);
+                                }
+                                ui.colored_label(service.state_color(), service.state());
+                                ui.label(service.startup_type());
+                                match service.process_id.filter(|pid| *pid != 0) {
+                                    Some(pid) => ui.label(RichText::new(pid.to_string()).monospace()),
+                                    None => ui.label(""),
+                                };
+                                ui.horizontal(|ui| {
+                                    if let Some(action) = list.actions.get(&service.name) {
+                                        ui.spinner();
+                                        ui.label(RichText::new(format!("{}...", action.label())).color(grey));
+                                        return;
+                                    }
+                                    let disabled = service.start_mode.as_deref() == Some("Disabled");
+                                    let can_stop = service.is_running() && service.accept_stop != Some(false);
+                                    if ui.add_enabled(service.is_stopped() && !disabled, egui::Button::new("Start").small()).clicked() {
+                                        requested = Some((service.name.clone(), ServiceAction::Start));
+                                    }
+                                    if ui.add_enabled(can_stop, egui::Button::new("Stop").small()).clicked() {
+                                        requested = Some((service.name.clone(), ServiceAction::Stop));
+                                    }
+                                    if ui.add_enabled(can_stop, egui::Button::new("Restart").small()).clicked() {
+                                        requested = Some((service.name.clone(), ServiceAction::Restart));
+                                    }
+                                    if let Some(error) = list.results.get(&service.name) {
+                                        ui.colored_label(Color32::from_rgb(220, 50, 50), "✖").on_hover_text(error);
+                                    }
+                                });
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+
+        if let Some((name, action)) = requested {
+            self.request_sensitive_action(SensitiveAction::ControlService(ServiceControl::Service(name, action)));
+        }
+    }
+}
@@ -0,0 +1,333 @@
+use std::fmt::Write;
+use std::sync::mpsc::{channel, Receiver};
+use chrono::Local;
+use eframe::egui;
+use egui::{Color32, RichText};
+use reqwest::Client;
+use serde::{Serialize, Deserialize};
+use sysinfo::{CpuExt, DiskExt, SystemExt};
+use log::{info, warn};
+use crate::{credentials, DevDashboard};
+
+const GIST_API: &str = "https://api.github.com/gists";
+const REPORT_FILE_NAME: &str = "system-report.md";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ShareTarget {
+    Gist,
+    Paste,
+}
+
+/// Persisted report sharing configuration
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ShareSettings {
+    pub target: ShareTarget,
+    #[serde(skip_serializing)]
+    pub github_token: String,       // Needs the "gist" scope; kept in Credential Manager
+    pub public_gist: bool,
+    pub paste_url: String,          // Receives the report as a text/plain POST body
+    pub include_identity: bool,     // Host and user name in the report
+}
+
+impl Default for ShareSettings {
+    fn default() -> Self {
+        Self {
+            target: ShareTarget::Gist,
+            github_token: String::new(),
+            public_gist: false,
+            paste_url: String::new(),
+            include_identity: false,
+        }
+    }
+}
+
+impl ShareSettings {
+    /// Reads the token from Credential Manager; returns true when a token from settings.json was moved there
+    pub fn load_token(&mut self) -> bool {
+        credentials::migrate("github_token", &mut self.github_token)
+    }
+}
+
+/// State of the Share Report window
+#[derive(Default)]
+pub struct ReportShare {
+    pub open: bool,
+    report: String,
+    receiver: Option<Receiver<Result<String, String>>>,
+    result: Option<Result<String, String>>,
+}
+
+async fn upload_gist(client: &Client, token: &str, public: bool, report: String) -> Result<String, String> {
+    let body = serde_json::json!({
+        "description": format!("Dev Dashboard system report {}", Local::now().format("%Y-%m-%d %H:%M")),
+        "public": public,
+        "files": { REPORT_FILE_NAME: { "content": report } },
+    });
+    let json: serde_json::Value = client.post(GIST_API)
+        .bearer_auth(token)
+        .header("User-Agent", "dev-dashboard")
+        .header("Accept", "application/vnd.github+json")
+        .json(&body)
+        .send().await.map_err(|e| e.to_string())?
+        .error_for_status().map_err(|e| e.to_string())?
+        .json().await.map_err(|e| e.to_string())?;
+    json["html_url"].as_str().map(str::to_string).ok_or_else(|| "Missing gist URL in response".to_string())
+}
+
+/// Posts the report as plain text; the response is either the URL itself or JSON with a `url` or `link` field
+async fn upload_paste(client: &Client, endpoint: &str, report: String) -> Result<String, String> {
+    let text = client.post(endpoint)
+        .header("Content-Type", "text/plain; charset=utf-8")
+        .body(report)
+        .send().await.map_err(|e| e.to_string())?
+        .error_for_status().map_err(|e| e.to_string())?
+        .text().await.map_err(|e| e.to_string())?;
+    if let Ok(json) = serde_json::from_str::<serde_json::Value>(&text) {
+        if let Some(url) = json["url"].as_str().or_else(|| json["link"].as_str()) {
+            return Ok(url.to_string());
+        }
+    }
+    let url = text.trim();
+    if url.starts_with("http://") || url.starts_with("https://") {
+        Ok(url.to_string())
+    } else {
+        Err("Paste service did not return a URL".to_string())
+    }
+}
+
+impl DevDashboard {
+    /// Markdown summary of the hardware and current state, for support requests
+    pub(crate) fn system_report(&self) -> String {
+        let mut report = String::new();
+        let _ = writeln!(report, "# System Report\n");
+        let _ = writeln!(report, "Generated {} by Dev Dashboard {}\n", Local::now().format("%Y-%m-%d %H:%M:%S %:z"), env!("CARGO_PKG_VERSION"));
+
+        let _ = writeln!(report, "## System\n");
+        let _ = writeln!(report, "- OS: {} {}", self.sys.name().unwrap_or_default(), self.sys.long_os_version().or(self.sys.os_version()).unwrap_or_default());
+        let _ = writeln!(report, "- Kernel: {}", self.sys.kernel_version().unwrap_or_default());
+        if self.settings.share.include_identity {
+            let _ = writeln!(report, "- Hostname: {}", self.sys.host_name().unwrap_or_default());
+            let _ = writeln!(report, "- User: {}", whoami::username());
+        }
+        let uptime = self.sys.uptime();
+        let _ = writeln!(report, "- Uptime: {}h {}m\n", uptime / 3600, (uptime % 3600) / 60);
+
+        let _ = writeln!(report, "## CPU\n");
+        if let Some(cpu) = self.sys.cpus().first() {
+            let _ = writeln!(report, "- Model: {}", cpu.brand().trim());
+            let _ = writeln!(report, "- Cores / threads: {} / {}", self.sys.physical_core_count().unwrap_or(0), self.sys.cpus().len());
+            let _ = writeln!(report, "- Speed: {:.1} GHz", cpu.frequency() as f64 / 1000.0);
+        }
+        let _ = writeln!(report, "- Usage: {:.1}%\n", self.current_cpu_usage.target);
+
+        let _ = writeln!(report, "## Memory\n");
+        let gib = 1024.0 * 1024.0 * 1024.0;
+        let total = self.sys.total_memory() as f64;
+        let _ = writeln!(report, "- Total: {:.1} GB", total / gib);
+        let _ = writeln!(report, "- Used: {:.1} GB ({:.0}%)\n", (total - self.sys.available_memory() as f64) / gib, self.memory_usage.target * 100.0);
+
+        let _ = writeln!(report, "## Storage\n");
+        let _ = writeln!(report, "| Drive | File system | Total | Free |");
+        let _ = writeln!(report, "|---|---|---|---|");
+        for volume in self.volume_monitor.volumes() {
+            let Some((total_bytes, free_bytes)) = volume.space else { continue };
+            // The file system comes from the startup drive list, which leaves out network drives
+            let file_system = self.sys.disks()
+                .iter()
+                .find(|disk| disk.mount_point().to_string_lossy() == volume.mount)
+                .map(|disk| String::from_utf8_lossy(disk.file_system()).to_string())
+                .unwrap_or_else(|| volume.kind.label().to_string());
+            let (total, total_unit) = DevDashboard::format_bytes(total_bytes);
+            let (free, free_unit) = DevDashboard::format_bytes(free_bytes);
+            let _ = writeln!(report, "| {} | {} | {:.1} {} | {:.1} {} |",
+                volume.mount, file_system, total, total_unit, free, free_unit);
+        }
+        let _ = writeln!(report);
+
+        if let Some(gpu) = &self.gpu_info {
+            let _ = writeln!(report, "## GPU\n");
+            let _ = writeln!(report, "- Name: {}", gpu.name);
+            if let Some(driver) = &gpu.driver_version {
+                let _ = writeln!(report, "- Driver: {}", driver);
+            }
+            if let Some(total) = gpu.memory_total {
+                let (value, unit) = DevDashboard::format_bytes(total);
+                let _ = writeln!(report, "- Memory: {:.1} {}", value, unit);
+            }
+            if let Some(temperature) = gpu.temperature {
+                let _ = writeln!(report, "- Temperature: {}°C", temperature);
+            }
+            let _ = writeln!(report);
+        }
+
+        let _ = writeln!(report, "## Network\n");
+        let mut interfaces: Vec<_> = self.network_stats.iter().collect();
+        interfaces.sort_by_key(|(name, _)| name.as_str());
+        for (name, stats) in interfaces {
+            let (received, received_unit) = DevDashboard::format_bytes(stats.total_received);
+            let (sent, sent_unit) = DevDashboard::format_bytes(stats.total_sent);
+            let _ = writeln!(report, "- {}: {:.1} {} received, {:.1} {} sent", name, received, received_unit, sent, sent_unit);
+        }
+
+        report
+    }
+
+    fn open_share_report(&mut self) {
+        self.report_share.report = self.system_report();
+        self.report_share.result = None;
+        self.report_share.open = true;
+    }
+
+    fn start_share_upload(&mut self) {
+        let settings = self.settings.share.clone();
+        let report = self.report_share.report.clone();
+        let (sender, receiver) = channel();
+        self.report_share.receiver = Some(receiver);
+        self.report_share.result = None;
+
+        self.runtime().spawn(async move {
+            let client = Client::new();
+            let result = match settings.target {
+                ShareTarget::Gist => upload_gist(&client, settings.github_token.trim(), settings.public_gist, report).await,
+                ShareTarget::Paste => upload_paste(&client, settings.paste_url.trim(), report).await,
+            };
+            match &result {
+                Ok(url) => info!("Shared system report at {}", url),
+                Err(e) => warn!("Sharing system report failed: {}", e),
+            }
+            sender.send(result).ok();
+        });
+    }
+
+    /// Top panel button that opens the Share Report window
+    pub(crate) fn show_share_button(&mut self, ui: &mut egui::Ui) {
+        if ui.button("📤").on_hover_text("Share system report").clicked() {
+            self.open_share_report();
+        }
+    }
+
+    /// Preview of the report with copy and upload actions
+    pub(crate) fn show_share_window(&mut self, ctx: &egui::Context) {
+        if !self.report_share.open {
+            return;
+        }
+
+        if let Some(receiver) = &self.report_share.receiver {
+            if let Ok(result) = receiver.try_recv() {
+                if let Ok(url) = &result {
+                    ctx.output_mut(|output| output.copied_text = url.clone());
+                }
+                self.report_share.result = Some(result);
+                self.report_share.receiver = None;
+            }
+        }
+
+        let settings = &self.settings.share;
+        let configured = match settings.target {
+            ShareTarget::Gist => !settings.github_token.trim().is_empty(),
+            ShareTarget::Paste => !settings.paste_url.trim().is_empty(),
+        };
+        let mut open = true;
+        let mut upload = false;
+        let mut regenerate = false;
+
+        egui::Window::new("Share System Report")
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    ui.add(egui::TextEdit::multiline(&mut self.report_share.report.as_str())
+                        .font(egui::TextStyle::Monospace)
+                        .desired_width(f32::INFINITY));
+                });
+                ui.add_space(8.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button("📋 Copy text").clicked() {
+                        ui.output_mut(|output| output.copied_text = self.report_share.report.clone());
+                    }
+                    if ui.button("⟳ Regenerate").clicked() {
+                        regenerate = true;
+                    }
+                    let label = match settings.target {
+                        ShareTarget::Gist => "Upload to Gist",
+                        ShareTarget::Paste => "Upload to paste service",
+                    };
+                    if self.report_share.receiver.is_some() {
+                        ui.spinner();
+                    } else if ui.add_enabled(configured, egui::Button::new(label))
+                        .on_disabled_hover_text("Set up sharing in Settings")
+                        .clicked() {
+                        upload = true;
+                    }
+                });
+
+                match &self.report_share.result {
+                    Some(Ok(url)) => {
+                        ui.horizontal(|ui| {
+                            ui.colored_label(Color32::from_rgb(22, 163, 74), "Link copied:");
+                            ui.hyperlink(url);
+                        });
+                    }
+                    Some(Err(error)) => {
+                        ui.colored_label(Color32::from_rgb(220, 50, 50), error);
+                    }
+                    None => {}
+                }
+                if !settings.include_identity {
+                    ui.label(RichText::new("Host and user names are left out; this can be changed in Settings")
+                        .color(Color32::from_rgb(107, 114, 128)));
+                }
+            });
+
+        if regenerate {
+            self.report_share.report = self.system_report();
+        }
+        if upload {
+            self.start_share_upload();
+        }
+        if !open {
+            self.report_share.open = false;
+        }
+    }
+
+    /// Settings section for report sharing
+    pub(crate) fn show_share_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        let settings = &mut self.settings.share;
+
+        ui.label("Share system report to:");
+        ui.horizontal(|ui| {
+            changed |= ui.radio_value(&mut settings.target, ShareTarget::Gist, "GitHub Gist").changed();
+            changed |= ui.radio_value(&mut settings.target, ShareTarget::Paste, "Paste endpoint").changed();
+        });
+        match settings.target {
+            ShareTarget::Gist => {
+                ui.horizontal(|ui| {
+                    ui.label("GitHub token:");
+                    changed |= ui.add(egui::TextEdit::singleline(&mut settings.github_token)
+                        .password(true)
+                        .hint_text("gist scope")).changed();
+                });
+                changed |= ui.checkbox(&mut settings.public_gist, "Public gist")
+                    .on_hover_text("Secret gists are unlisted but anyone with the link can read them")
+                    .changed();
+            }
+            ShareTarget::Paste => {
+                ui.horizontal(|ui| {
+                    ui.label("Endpoint URL:");
+                    changed |= ui.add(egui::TextEdit::singleline(&mut settings.paste_url)
+                        .hint_text("https://paste.example.com/")).changed();
+                });
+            }
+        }
+        changed |= ui.checkbox(&mut settings.include_identity, "Include host and user name").changed();
+
+        if changed {
+            credentials::write("github_token", settings.github_token.trim());
+            self.save_settings();
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use std::os::windows::ffi::OsStrExt;
+use std::os::windows::io::AsRawHandle;
+use std::path::Path;
+use eframe::egui;
+use windows::core::{GUID, PCWSTR};
+use windows::Win32::Foundation::{HANDLE, HWND};
+use windows::Win32::Security::Cryptography::{CertGetNameStringW, CERT_NAME_SIMPLE_DISPLAY_TYPE};
+use windows::Win32::Security::Cryptography::Catalog::{
+    CryptCATAdminAcquireContext, CryptCATAdminCalcHashFromFileHandle, CryptCATAdminEnumCatalogFromHash,
+    CryptCATAdminReleaseCatalogContext, CryptCATAdminReleaseContext, CryptCATCatalogInfoFromContext, CATALOG_INFO,
+};
+use windows::Win32::Security::WinTrust::{
+    WTHelperGetProvSignerFromChain, WTHelperProvDataFromStateData, WinVerifyTrust,
+    WINTRUST_ACTION_GENERIC_VERIFY_V2, WINTRUST_CATALOG_INFO, WINTRUST_DATA, WINTRUST_DATA_0,
+    WINTRUST_DATA_UNION_CHOICE, WINTRUST_FILE_INFO, WTD_CACHE_ONLY_URL_RETRIEVAL, WTD_CHOICE_CATALOG,
+    WTD_CHOICE_FILE, WTD_REVOKE_NONE, WTD_STATEACTION_CLOSE, WTD_STATEACTION_VERIFY, WTD_UI_NONE,
+};
+use log::debug;
+
+/// Result of an Authenticode check on an executable
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SignatureStatus {
+    Signed,      // Valid embedded or catalog signature
+    Unsigned,    // No signature, or the signature did not verify
+    Unknown,     // The file could not be read
+}
+
+impl SignatureStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SignatureStatus::Signed => "Signed",
+            SignatureStatus::Unsigned => "Unsigned",
+            SignatureStatus::Unknown => "Unknown",
+        }
+    }
+
+    pub fn color(&self) -> egui::Color32 {
+        match self {
+            SignatureStatus::Signed => egui::Color32::from_rgb(22, 163, 74),
+            SignatureStatus::Unsigned => egui::Color32::from_rgb(220, 50, 50),
+            SignatureStatus::Unknown => egui::Color32::from_rgb(156, 163, 175),
+        }
+    }
+}
+
+/// Signature status plus the signing certificate's display name
+#[derive(Clone, PartialEq, Debug)]
+pub struct SignatureInfo {
+    pub status: SignatureStatus,
+    pub signer: Option<String>,   // Subject of the leaf signing certificate
+}
+
+impl SignatureInfo {
+    fn unsigned() -> Self {
+        Self { status: SignatureStatus::Unsigned, signer: None }
+    }
+}
+
+fn to_wide(value: &std::ffi::OsStr) -> Vec<u16> {
+    value.encode_wide().chain(std::iter::once(0)).collect()
+}
+
+/// Reads the display name of the primary signer from WinVerifyTrust state data
+unsafe fn signer_name(state: HANDLE) -> Option<String> {
+    let provider = WTHelperProvDataFromStateData(state);
+    if provider.is_null() {
+        return None;
+    }
+    let signer = WTHelperGetProvSignerFromChain(provider, 0, false, 0);
+    if signer.is_null() || (*signer).csCertChain == 0 || (*signer).pasCertChain.is_null() {
+        return None;
+    }
+    let certificate = (*(*signer).pasCertChain).pCert;
+    if certificate.is_null() {
+        return None;
+    }
+
+    let mut name = [0u16; 256];
+    let len = CertGetNameStringW(certificate, CERT_NAME_SIMPLE_DISPLAY_TYPE, 0, None, Some(&mut name));
+    if len <= 1 {
+        return None;
+    }
+    Some(String::from_utf16_lossy(&name[..len as usize - 1]))
+}
+
+/// Runs WinVerifyTrust with the given union choice and releases the state data afterwards.
+/// Returns None if verification failed, otherwise the signer name if it could be read.
+unsafe fn run_win_verify_trust(union_choice: WINTRUST_DATA_UNION_CHOICE, data: WINTRUST_DATA_0) -> Option<Option<String>> {
+    let mut trust_data = WINTRUST_DATA {
+        cbStruct: std::mem::size_of::<WINTRUST_DATA>() as u32,
+        dwUIChoice: WTD_UI_NONE,
+        fdwRevocationChecks: WTD_REVOKE_NONE,
+        dwUnionChoice: union_choice,
+        Anonymous: data,
+        dwStateAction: WTD_STATEACTION_VERIFY,
+        dwProvFlags: WTD_CACHE_ONLY_URL_RETRIEVAL,
+        ..Default::default()
+    };
+    let mut action: GUID = WINTRUST_ACTION_GENERIC_VERIFY_V2;
+
+    let result = WinVerifyTrust(HWND(0), &mut action, &mut trust_data as *mut _ as *mut _);
+    let signer = if result == 0 { Some(signer_name(trust_data.hWVTStateData)) } else { None };
+
+    trust_data.dwStateAction = WTD_STATEACTION_CLOSE;
+    WinVerifyTrust(HWND(0), &mut action, &mut trust_data as *mut _ as *mut _);
+
+    signer
+}
+
+/// Checks for an Authenticode signature embedded in the file itself
+fn embedded_signature(path: &Path) -> Option<Option<String>> {
+    let wide_path = to_wide(path.as_os_str());
+    let mut file_info = WINTRUST_FILE_INFO {
+        cbStruct: std::mem::size_of::<WINTRUST_FILE_INFO>() as u32,
+        pcwszFilePath: PCWSTR(wide_path.as_ptr()),
+        ..Default::default()
+    };
+
+    unsafe { run_win_verify_trust(WTD_CHOICE_FILE, WINTRUST_DATA_0 { pFile: &mut file_info }) }
+}
+
+/// Checks whether the file's hash is listed in a signed system catalog.
+/// Most in-box Windows binaries are catalog signed rather than embedding a signature.
+fn catalog_signature(path: &Path, file: &std::fs::File) -> Option<Option<String>> {
+    unsafe {
+        let mut cat_admin = 0isize;
+        if !CryptCATAdminAcquireContext(&mut cat_admin, None, 0).as_bool() {
+            return None;
+        }
+
+        let handle = HANDLE(file.as_raw_handle() as isize);
+        let mut hash_len = 0u32;
+        let _ = CryptCATAdminCalcHashFromFileHandle(handle, &mut hash_len, None, 0);
+        let mut hash = vec![0u8; hash_len as usize];
+        let mut verified = None;
+
+        if hash_len > 0 && CryptCATAdminCalcHashFromFileHandle(handle, &mut hash_len, Some(hash.as_mut_ptr()), 0).as_bool() {
+            let cat_info = CryptCATAdminEnumCatalogFromHash(cat_admin, &hash, 0, None);
+            if cat_info != 0 {
+                let mut catalog = CATALOG_INFO {
+                    cbStruct: std::mem::size_of::<CATALOG_INFO>() as u32,
+                    wszCatalogFile: [0; 260],
+                };
+
+                if CryptCATCatalogInfoFromContext(cat_info, &mut catalog, 0).as_bool() {
+                    let member_tag: String = hash.iter().map(|b| format!("{:02X}", b)).collect();
+                    let wide_tag = to_wide(std::ffi::OsStr::new(&member_tag));
+                    let wide_path = to_wide(path.as_os_str());
+
+                    let mut catalog_info = WINTRUST_CATALOG_INFO {
+                        cbStruct: std::mem::size_of::<WINTRUST_CATALOG_INFO>() as u32,
+                        pcwszCatalogFilePath: PCWSTR(catalog.wszCatalogFile.as_ptr()),
+                        pcwszMemberTag: PCWSTR(wide_tag.as_ptr()),
+                        pcwszMemberFilePath: PCWSTR(wide_path.as_ptr()),
+                        hMemberFile: handle,
+                        pbCalculatedFileHash: hash.as_mut_ptr(),
+                        cbCalculatedFileHash: hash_len,
+                        hCatAdmin: cat_admin,
+                        ..Default::default()
+                    };
+
+                    verified = run_win_verify_trust(WTD_CHOICE_CATALOG, WINTRUST_DATA_0 { pCatalog: &mut catalog_info });
+                }
+
+                CryptCATAdminReleaseCatalogContext(cat_admin, cat_info, 0);
+            }
+        }
+
+        CryptCATAdminReleaseContext(cat_admin, 0);
+        verified
+    }
+}
+
+/// Verifies the Authenticode signature of an executable, falling back to catalog signatures
+pub fn verify(path: &Path) -> SignatureInfo {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) => {
+            debug!("Could not open {:?} for signature check: {}", path, e);
+            return SignatureInfo { status: SignatureStatus::Unknown, signer: None };
+        }
+    };
+
+    match embedded_signature(path).or_else(|| catalog_signature(path, &file)) {
+        Some(signer) => SignatureInfo { status: SignatureStatus::Signed, signer },
+        None => SignatureInfo::unsigned(),
+    }
+}
@@ -0,0 +1,342 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver};
+use chrono::Local;
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use winreg::enums::*;
+use winreg::RegKey;
+use log::info;
+use crate::{storage, DevDashboard};
+
+/// Saved snapshots are stored next to settings.json
+const SNAPSHOTS_FILE: &str = "snapshots.json";
+const UNINSTALL_KEYS: [&str; 2] = [
+    r"SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall",
+    r"SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\Uninstall",
+];
+const SERVICES_KEY: &str = r"SYSTEM\CurrentControlSet\Services";
+const SYSTEM_ENVIRONMENT_KEY: &str = r"SYSTEM\CurrentControlSet\Control\Session Manager\Environment";
+const RUN_KEYS: [&str; 2] = [
+    r"SOFTWARE\Microsoft\Windows\CurrentVersion\Run",
+    r"SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\Run",
+];
+/// Service Type bits for kernel and file system drivers
+const DRIVER_TYPES: u32 = 0x3;
+
+/// Names mapped to the detail that identifies their state, per area of the machine
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct SystemSnapshot {
+    pub name: String,
+    pub timestamp: String,
+    pub apps: BTreeMap<String, String>,         // Display name to version
+    pub drivers: BTreeMap<String, String>,      // Service name to start type and image path
+    pub services: BTreeMap<String, String>,
+    pub environment: BTreeMap<String, String>,  // e.g. "System Path" or "User TEMP" to the stored value
+    pub startup: BTreeMap<String, String>,      // Run key value or startup folder file to its command
+}
+
+/// One entry that differs between two snapshots
+enum Change<'a> {
+    Added(&'a str, &'a str),
+    Removed(&'a str, &'a str),
+    Changed(&'a str, &'a str, &'a str),
+}
+
+/// State of the Snapshots tool
+#[derive(Default)]
+pub struct Snapshots {
+    snapshots: Vec<SystemSnapshot>,
+    receiver: Option<Receiver<SystemSnapshot>>,
+    name: String,
+    before: usize,
+    after: usize,
+}
+
+fn start_type(start: u32) -> &'static str {
+    match start {
+        0 => "Boot",
+        1 => "System",
+        2 => "Automatic",
+        3 => "Manual",
+        4 => "Disabled",
+        _ => "Unknown",
+    }
+}
+
+fn installed_apps() -> BTreeMap<String, String> {
+    let mut apps = BTreeMap::new();
+    for (root, keys) in [(HKEY_LOCAL_MACHINE, &UNINSTALL_KEYS[..]), (HKEY_CURRENT_USER, &UNINSTALL_KEYS[..1])] {
+        for path in keys {
+            let Ok(uninstall) = RegKey::predef(root).open_subkey_with_flags(path, KEY_READ) else { continue };
+            for subkey in uninstall.enum_keys().flatten() {
+                let Ok(app) = uninstall.open_subkey(&subkey) else { continue };
+                // Entries without a name are updates and components that Programs and Features hides too
+                let Ok(name) = app.get_value::<String, _>("DisplayName") else { continue };
+                let version = app.get_value::<String, _>("DisplayVersion").unwrap_or_default();
+                apps.insert(name, version);
+            }
+        }
+    }
+    apps
+}
+
+/// Drivers and services both live under the Services key and differ by their Type value
+fn drivers_and_services() -> (BTreeMap<String, String>, BTreeMap<String, String>) {
+    let (mut drivers, mut services) = (BTreeMap::new(), BTreeMap::new());
+    let Ok(root) = RegKey::predef(HKEY_LOCAL_MACHINE).open_subkey_with_flags(SERVICES_KEY, KEY_READ) else {
+        return (drivers, services);
+    };
+    for name in root.enum_keys().flatten() {
+        let Ok(key) = root.open_subkey(&name) else { continue };
+        let Ok(kind) = key.get_value::<u32, _>("Type") else { continue };
+        let start = key.get_value::<u32, _>("Start").map(start_type).unwrap_or("Unknown");
+        let image = key.get_value::<String, _>("ImagePath").unwrap_or_default();
+        let detail = if image.is_empty() { start.to_string() } else { format!("{}, {}", start, image) };
+        if kind & DRIVER_TYPES != 0 {
+            drivers.insert(name, detail);
+        } else {
+            services.insert(name, detail);
+        }
+    }
+    (drivers, services)
+}
+
+/// The stored variables rather than this process's copy, which misses changes made since it started
+fn environment() -> BTreeMap<String, String> {
+    let mut variables = BTreeMap::new();
+    let sources = [
+        (HKEY_LOCAL_MACHINE, SYSTEM_ENVIRONMENT_KEY, "System"),
+        (HKEY_CURRENT_USER, "Environment", "User"),
+    ];
+    for (root, path, scope) in sources {
+        let Ok(key) = RegKey::predef(root).open_subkey_with_flags(path, KEY_READ) else { continue };
+        for (name, value) in key.enum_values().flatten() {
+            variables.insert(format!("{} {}", scope, name), value.to_string());
+        }
+    }
+    variables
+}
+
+fn startup_items() -> BTreeMap<String, String> {
+    let mut items = BTreeMap::new();
+    for (root, scope) in [(HKEY_LOCAL_MACHINE, "All users"), (HKEY_CURRENT_USER, "User")] {
+        for path in RUN_KEYS {
+            let Ok(key) = RegKey::predef(root).open_subkey_with_flags(path, KEY_READ) else { continue };
+            for (name, value) in key.enum_values().flatten() {
+                items.insert(format!("{} Run: {}", scope, name), value.to_string());
+            }
+        }
+    }
+    let folders = [
+        (std::env::var_os("APPDATA"), "User"),
+        (std::env::var_os("ProgramData"), "All users"),
+    ];
+    for (base, scope) in folders {
+        let Some(base) = base else { continue };
+        let folder = PathBuf::from(base).join(r"Microsoft\Windows\Start Menu\Programs\Startup");
+        let Ok(entries) = std::fs::read_dir(&folder) else { continue };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.eq_ignore_ascii_case("desktop.ini") {
+                continue;
+            }
+            items.insert(format!("{} Startup folder: {}", scope, name), entry.path().display().to_string());
+        }
+    }
+    items
+}
+
+fn take_snapshot(name: String) -> SystemSnapshot {
+    let (drivers, services) = drivers_and_services();
+    SystemSnapshot {
+        name,
+        timestamp: Local::now().format("%Y-%m-%d %H:%M").to_string(),
+        apps: installed_apps(),
+        drivers,
+        services,
+        environment: environment(),
+        startup: startup_items(),
+    }
+}
+
+fn changes<'a>(before: &'a BTreeMap<String, String>, after: &'a BTreeMap<String, String>) -> Vec<Change<'a>> {
+    let mut changes = Vec::new();
+    for (name, old) in before {
+        match after.get(name) {
+            None => changes.push(Change::Removed(name, old)),
+            Some(new) if new != old => changes.push(Change::Changed(name, old, new)),
+            Some(_) => {}
+        }
+    }
+    for (name, new) in after {
+        if !before.contains_key(name) {
+            changes.push(Change::Added(name, new));
+        }
+    }
+    // Keep an area's changes in name order regardless of their kind
+    changes.sort_by_key(|change| match change {
+        Change::Added(name, _) | Change::Removed(name, _) | Change::Changed(name, _, _) => *name,
+    });
+    changes
+}
+
+impl Snapshots {
+    pub fn load() -> Self {
+        let snapshots: Vec<SystemSnapshot> = storage::load_json(SNAPSHOTS_FILE);
+        let after = snapshots.len().saturating_sub(1);
+        Self { before: after.saturating_sub(1), after, snapshots, ..Default::default() }
+    }
+
+    fn save(&self) {
+        storage::save_json(SNAPSHOTS_FILE, &self.snapshots);
+    }
+
+    fn is_running(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    fn start(&mut self) {
+        let name = match self.name.trim() {
+            "" => format!("Snapshot {}", self.snapshots.len() + 1),
+            name => name.to_string(),
+        };
+        info!("Taking system snapshot {}", name);
+        let (sender, receiver) = channel();
+        self.receiver = Some(receiver);
+        std::thread::spawn(move || {
+            sender.send(take_snapshot(name)).ok();
+        });
+    }
+
+    fn poll(&mut self) {
+        let Some(receiver) = &self.receiver else { return };
+        let Ok(snapshot) = receiver.try_recv() else { return };
+        self.receiver = None;
+        info!("Snapshot {} has {} apps, {} drivers and {} services",
+            snapshot.name, snapshot.apps.len(), snapshot.drivers.len(), snapshot.services.len());
+        self.snapshots.push(snapshot);
+        self.name.clear();
+        // Compare the new snapshot with the one before it
+        self.after = self.snapshots.len() - 1;
+        self.before = self.after.saturating_sub(1);
+        self.save();
+    }
+
+    fn remove(&mut self, index: usize) {
+        self.snapshots.remove(index);
+        let last = self.snapshots.len().saturating_sub(1);
+        self.before = self.before.min(last);
+        self.after = self.after.min(last);
+        self.save();
+    }
+}
+
+fn snapshot_combo(ui: &mut egui::Ui, id: &str, snapshots: &[SystemSnapshot], selected: &mut usize) {
+    let label = |snapshot: &SystemSnapshot| format!("{} ({})", snapshot.name, snapshot.timestamp);
+    egui::ComboBox::from_id_source(id)
+        .selected_text(snapshots.get(*selected).map(label).unwrap_or_default())
+        .show_ui(ui, |ui| {
+            for (index, snapshot) in snapshots.iter().enumerate() {
+                ui.selectable_value(selected, index, label(snapshot));
+            }
+        });
+}
+
+fn show_changes(ui: &mut egui::Ui, title: &str, changes: &[Change]) {
+    let header = RichText::new(format!("{} ({} changed)", title, changes.len()));
+    egui::CollapsingHeader::new(header)
+        .id_source(("snapshot_area", title))
+        .default_open(!changes.is_empty())
+        .show(ui, |ui| {
+            if changes.is_empty() {
+                ui.label(RichText::new("No changes").color(Color32::from_rgb(156, 163, 175)));
+            }
+            for change in changes {
+                match change {
+                    Change::Added(name, value) => {
+                        ui.colored_label(Color32::from_rgb(22, 163, 74), format!("+ {}  {}", name, value));
+                    }
+                    Change::Removed(name, value) => {
+                        ui.colored_label(Color32::from_rgb(220, 50, 50), format!("- {}  {}", name, value));
+                    }
+                    Change::Changed(name, old, new) => {
+                        ui.colored_label(Color32::from_rgb(202, 138, 4), format!("~ {}  {} → {}", name, old, new));
+                    }
+                }
+            }
+        });
+}
+
+impl DevDashboard {
+    pub(crate) fn show_snapshot_tool(&mut self, ui: &mut egui::Ui) {
+        let snapshots = &mut self.snapshots;
+        snapshots.poll();
+
+        ui.heading("System Snapshots");
+        ui.add_space(8.0);
+
+        ui.horizontal(|ui| {
+            ui.label("Name:");
+            ui.add(egui::TextEdit::singleline(&mut snapshots.name)
+                .hint_text("e.g. before Visual Studio update")
+                .desired_width(220.0));
+            if ui.add_enabled(!snapshots.is_running(), egui::Button::new("Take Snapshot")).clicked() {
+                snapshots.start();
+            }
+            if snapshots.is_running() {
+                ui.spinner();
+            }
+        });
+        ui.label(RichText::new("Records installed apps, drivers, services, environment variables, and startup items.")
+            .small()
+            .color(Color32::from_rgb(156, 163, 175)));
+        ui.add_space(12.0);
+
+        if snapshots.snapshots.is_empty() {
+            ui.label("No snapshots yet. Take one before installing or updating something, and another afterwards.");
+            return;
+        }
+
+        let mut removed = None;
+        egui::Grid::new("snapshot_grid").striped(true).spacing([16.0, 4.0]).show(ui, |ui| {
+            for (index, snapshot) in snapshots.snapshots.iter().enumerate() {
+                ui.label(&snapshot.timestamp);
+                ui.label(RichText::new(&snapshot.name).strong());
+                ui.label(format!("{} apps", snapshot.apps.len()));
+                if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                    removed = Some(index);
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(index) = removed {
+            snapshots.remove(index);
+            return;
+        }
+        if snapshots.snapshots.len() < 2 {
+            return;
+        }
+
+        ui.add_space(12.0);
+        ui.horizontal(|ui| {
+            ui.label("Compare");
+            snapshot_combo(ui, "snapshot_before", &snapshots.snapshots, &mut snapshots.before);
+            ui.label("with");
+            snapshot_combo(ui, "snapshot_after", &snapshots.snapshots, &mut snapshots.after);
+        });
+        ui.add_space(8.0);
+
+        let before = &snapshots.snapshots[snapshots.before];
+        let after = &snapshots.snapshots[snapshots.after];
+        egui::ScrollArea::vertical().id_source("snapshot_diff_scroll").show(ui, |ui| {
+            show_changes(ui, "Installed apps", &changes(&before.apps, &after.apps));
+            show_changes(ui, "Drivers", &changes(&before.drivers, &after.drivers));
+            show_changes(ui, "Services", &changes(&before.services, &after.services));
+            show_changes(ui, "Environment variables", &changes(&before.environment, &after.environment));
+            show_changes(ui, "Startup items", &changes(&before.startup, &after.startup));
+        });
+    }
+}
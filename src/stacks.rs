@@ -0,0 +1,436 @@
+use std::path::Path;
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::time::Duration;
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use tokio::process::Command as TokioCommand;
+use log::{info, warn};
+use crate::elevation::{ElevatedAction, ElevationBroker};
+use crate::guard::{SensitiveAction, ServiceControl};
+use crate::{DevDashboard, CREATE_NO_WINDOW};
+
+/// Services such as Docker can take a while to stop
+const STEP_TIMEOUT: Duration = Duration::from_secs(90);
+/// taskkill exit code when no process matched
+const TASKKILL_NOT_FOUND: i32 = 128;
+/// SERVICE_STOPPED and SERVICE_RUNNING as printed by `sc query`
+const SERVICE_STOPPED: u32 = 1;
+const SERVICE_RUNNING: u32 = 4;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum StepKind {
+    Service,    // Windows service name, stopped and started with net through the elevation broker
+    Process,    // Executable path; stopping kills every process with its file name
+    Command,    // Separate start and stop commands run through cmd /C
+}
+
+impl StepKind {
+    fn label(&self) -> &'static str {
+        match self {
+            StepKind::Service => "Service",
+            StepKind::Process => "Process",
+            StepKind::Command => "Command",
+        }
+    }
+
+    fn hint(&self) -> &'static str {
+        match self {
+            StepKind::Service => "com.docker.service",
+            StepKind::Process => r"C:\Program Files\Docker\Docker\Docker Desktop.exe",
+            StepKind::Command => "wsl --exec true",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct StackStep {
+    pub kind: StepKind,
+    pub target: String,     // Service name, executable path, or start command
+    pub stop: String,       // Stop command for Command steps
+}
+
+impl Default for StackStep {
+    fn default() -> Self {
+        Self {
+            kind: StepKind::Service,
+            target: String::new(),
+            stop: String::new(),
+        }
+    }
+}
+
+impl StackStep {
+    fn describe(&self) -> String {
+        match self.kind {
+            StepKind::Process => Path::new(&self.target)
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| self.target.clone()),
+            _ => self.target.clone(),
+        }
+    }
+}
+
+/// Services and processes that are restarted together; stopped in reverse order, started in order
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct StackConfig {
+    pub name: String,
+    pub steps: Vec<StackStep>,
+}
+
+impl Default for StackConfig {
+    fn default() -> Self {
+        Self {
+            name: "Dev stack".to_string(),
+            steps: Vec::new(),
+        }
+    }
+}
+
+/// Persisted restart stacks
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct StackSettings {
+    pub stacks: Vec<StackConfig>,
+}
+
+#[derive(Clone)]
+enum StepStatus {
+    Waiting,
+    Stopping,
+    Stopped,
+    Starting,
+    Running,
+    Failed(String),
+}
+
+impl StepStatus {
+    fn label(&self) -> (String, Color32) {
+        match self {
+            StepStatus::Waiting => ("Waiting".to_string(), Color32::from_rgb(107, 114, 128)),
+            StepStatus::Stopping => ("Stopping...".to_string(), Color32::from_rgb(202, 138, 4)),
+            StepStatus::Stopped => ("Stopped".to_string(), Color32::from_rgb(156, 163, 175)),
+            StepStatus::Starting => ("Starting...".to_string(), Color32::from_rgb(88, 165, 237)),
+            StepStatus::Running => ("✔ Running".to_string(), Color32::from_rgb(22, 163, 74)),
+            StepStatus::Failed(error) => (format!("✖ {}", error), Color32::from_rgb(220, 50, 50)),
+        }
+    }
+}
+
+/// Progress of the latest restart of one stack
+struct StackRun {
+    statuses: Vec<StepStatus>,
+    receiver: Option<Receiver<(usize, StepStatus)>>,    // None once the restart has finished
+}
+
+/// Restart progress per stack, in settings order
+#[derive(Default)]
+pub struct StackRunner {
+    runs: Vec<Option<StackRun>>,
+}
+
+/// Runs a console tool and returns its exit code and combined output
+async fn run_tool(program: &str, args: &[&str]) -> Result<(i32, String), String> {
+    let child = TokioCommand::new(program)
+        .args(args)
+        .creation_flags(CREATE_NO_WINDOW)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Could not start {}: {}", program, e))?;
+    let output = tokio::time::timeout(STEP_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| format!("Timed out after {}s", STEP_TIMEOUT.as_secs()))?
+        .map_err(|e| e.to_string())?;
+    let text = format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+    Ok((output.status.code().unwrap_or(-1), text))
+}
+
+/// First non-empty output line, for step errors
+fn first_line(output: &str) -> String {
+    output.lines().map(str::trim).find(|line| !line.is_empty()).unwrap_or("No output").to_string()
+}
+
+/// Reads the numeric state from `sc query`, which unlike the state name is not localized
+async fn service_state(name: &str) -> Result<u32, String> {
+    let (code, output) = run_tool("sc", &["query", name]).await?;
+    if code != 0 {
+        return Err(first_line(&output));
+    }
+    output
+        .lines()
+        .find(|line| line.trim_start().starts_with("STATE"))
+        .and_then(|line| line.split_once(':'))
+        .and_then(|(_, value)| value.split_whitespace().next())
+        .and_then(|state| state.parse().ok())
+        .ok_or_else(|| "Could not read service state".to_string())
+}
+
+async fn run_checked(program: &str, args: &[&str]) -> Result<(), String> {
+    let (code, output) = run_tool(program, args).await?;
+    if code != 0 {
+        return Err(format!("Exit code {}: {}", code, first_line(&output)));
+    }
+    Ok(())
+}
+
+async fn stop_step(step: &StackStep, broker: &ElevationBroker) -> Result<(), String> {
+    match step.kind {
+        StepKind::Service => {
+            if service_state(&step.target).await? == SERVICE_STOPPED {
+                return Ok(());
+            }
+            broker.run(ElevatedAction::StopService(step.target.clone())).await
+        }
+        StepKind::Process => {
+            let image = step.describe();
+            let (code, output) = run_tool("taskkill", &["/IM", &image, "/T", "/F"]).await?;
+            match code {
+                0 | TASKKILL_NOT_FOUND => Ok(()),
+                _ => Err(format!("Exit code {}: {}", code, first_line(&output))),
+            }
+        }
+        StepKind::Command if step.stop.trim().is_empty() => Ok(()),
+        StepKind::Command => run_checked("cmd", &["/C", &step.stop]).await,
+    }
+}
+
+async fn start_step(step: &StackStep, broker: &ElevationBroker) -> Result<(), String> {
+    match step.kind {
+        StepKind::Service => {
+            if service_state(&step.target).await? == SERVICE_RUNNING {
+                return Ok(());
+            }
+            broker.run(ElevatedAction::StartService(step.target.clone())).await
+        }
+        StepKind::Process => {
+            let path = Path::new(&step.target);
+            let mut command = std::process::Command::new(path);
+            if let Some(directory) = path.parent().filter(|directory| directory.is_dir()) {
+                command.current_dir(directory);
+            }
+            // Left running on its own; the dashboard does not wait for GUI apps to exit
+            command.spawn().map(|_| ()).map_err(|e| format!("Could not start: {}", e))
+        }
+        StepKind::Command if step.target.trim().is_empty() => Ok(()),
+        StepKind::Command => run_checked("cmd", &["/C", &step.target]).await,
+    }
+}
+
+/// Stops every step from last to first, then starts them in order, stopping at the first failure
+async fn restart_stack(name: String, steps: Vec<StackStep>, broker: ElevationBroker, sender: Sender<(usize, StepStatus)>) {
+    for (index, step) in steps.iter().enumerate().rev() {
+        sender.send((index, StepStatus::Stopping)).ok();
+        if let Err(e) = stop_step(step, &broker).await {
+            warn!("Stack \"{}\": could not stop {}: {}", name, step.describe(), e);
+            sender.send((index, StepStatus::Failed(e))).ok();
+            return;
+        }
+        sender.send((index, StepStatus::Stopped)).ok();
+    }
+    for (index, step) in steps.iter().enumerate() {
+        sender.send((index, StepStatus::Starting)).ok();
+        if let Err(e) = start_step(step, &broker).await {
+            warn!("Stack \"{}\": could not start {}: {}", name, step.describe(), e);
+            sender.send((index, StepStatus::Failed(e))).ok();
+            return;
+        }
+        sender.send((index, StepStatus::Running)).ok();
+    }
+    info!("Stack \"{}\" restarted", name);
+}
+
+impl DevDashboard {
+    pub(crate) fn restart_stack(&mut self, index: usize) {
+        let Some(stack) = self.settings.stacks.stacks.get(index).cloned() else { return };
+        info!("Restarting stack \"{}\"", stack.name);
+        let (sender, receiver) = channel();
+        self.stack_runner.runs.resize_with(self.settings.stacks.stacks.len(), || None);
+        self.stack_runner.runs[index] = Some(StackRun {
+            statuses: vec![StepStatus::Waiting; stack.steps.len()],
+            receiver: Some(receiver),
+        });
+        let broker = self.elevation.clone();
+        self.runtime().spawn(restart_stack(stack.name, stack.steps, broker, sender));
+    }
+
+    /// Collects step progress from running restarts
+    pub(crate) fn update_stacks(&mut self) {
+        for run in self.stack_runner.runs.iter_mut().flatten() {
+            let Some(receiver) = &run.receiver else { continue };
+            loop {
+                match receiver.try_recv() {
+                    Ok((step, status)) => {
+                        if let Some(slot) = run.statuses.get_mut(step) {
+                            *slot = status;
+                        }
+                    }
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        run.receiver = None;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Displays the restart stacks card
+    /// Shows one restart button per stack and the status of each step of the latest run
+    pub(crate) fn show_stacks_card(&mut self, ui: &mut egui::Ui) {
+        let mut restart = None;
+        self.show_card(ui, "Stacks", |ui| {
+            for (index, stack) in self.settings.stacks.stacks.iter().enumerate() {
+                let run = self.stack_runner.runs.get(index).and_then(Option::as_ref);
+                let running = run.is_some_and(|run| run.receiver.is_some());
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(&stack.name).strong());
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if running {
+                            ui.spinner();
+                        } else if ui.add_enabled(!stack.steps.is_empty(), egui::Button::new("⟳ Restart")).clicked() {
+                            restart = Some((index, stack.name.clone()));
+                        }
+                    });
+                });
+                for (step_index, step) in stack.steps.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(format!("{}. {}", step_index + 1, step.kind.label()))
+                            .color(Color32::from_rgb(156, 163, 175)));
+                        ui.label(step.describe());
+                        if let Some(status) = run.and_then(|run| run.statuses.get(step_index)) {
+                            let (text, color) = status.label();
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                ui.colored_label(color, text);
+                            });
+                        }
+                    });
+                }
+                ui.add_space(6.0);
+            }
+        });
+        if let Some((index, name)) = restart {
+            self.request_sensitive_action(SensitiveAction::ControlService(ServiceControl::Stack(index, name)));
+        }
+    }
+
+    /// Each step of each stack with its status from the latest restart, for copying the card
+    pub(crate) fn stacks_card_values(&self) -> Vec<(String, String)> {
+        let mut values = Vec::new();
+        for (index, stack) in self.settings.stacks.stacks.iter().enumerate() {
+            let run = self.stack_runner.runs.get(index).and_then(Option::as_ref);
+            for (step_index, step) in stack.steps.iter().enumerate() {
+                let mut value = format!("{} {}", step.kind.label(), step.describe());
+                if let Some(status) = run.and_then(|run| run.statuses.get(step_index)) {
+                    value.push_str(&format!(": {}", status.label().0));
+                }
+                values.push((format!("{} {}", stack.name, step_index + 1), value));
+            }
+        }
+        values
+    }
+
+    /// Settings section for restart stacks
+    pub(crate) fn show_stack_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        let mut removed = None;
+
+        ui.label("Restart Stacks:");
+        for (index, stack) in self.settings.stacks.stacks.iter_mut().enumerate() {
+            egui::CollapsingHeader::new(stack.name.clone())
+                .id_source(("stack_settings", index))
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Name:");
+                        changed |= ui.text_edit_singleline(&mut stack.name).changed();
+                    });
+                    ui.label(RichText::new("Stopped from the bottom up, then started from the top down")
+                        .color(Color32::from_rgb(107, 114, 128)));
+
+                    let step_count = stack.steps.len();
+                    let mut moved = None;
+                    let mut removed_step = None;
+                    for (step_index, step) in stack.steps.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            egui::ComboBox::from_id_source(("stack_step_kind", index, step_index))
+                                .selected_text(step.kind.label())
+                                .width(80.0)
+                                .show_ui(ui, |ui| {
+                                    for kind in [StepKind::Service, StepKind::Process, StepKind::Command] {
+                                        changed |= ui.selectable_value(&mut step.kind, kind, kind.label()).changed();
+                                    }
+                                });
+                            changed |= ui.add(egui::TextEdit::singleline(&mut step.target)
+                                .hint_text(step.kind.hint())
+                                .desired_width(220.0)).changed();
+                            if step.kind == StepKind::Command {
+                                changed |= ui.add(egui::TextEdit::singleline(&mut step.stop)
+                                    .hint_text("wsl --shutdown")
+                                    .desired_width(140.0)).changed();
+                            }
+                            if ui.add_enabled(step_index > 0, egui::Button::new("⏶").small()).clicked() {
+                                moved = Some((step_index, step_index - 1));
+                            }
+                            if ui.add_enabled(step_index + 1 < step_count, egui::Button::new("⏷").small()).clicked() {
+                                moved = Some((step_index, step_index + 1));
+                            }
+                            if ui.small_button("✖").on_hover_text("Remove step").clicked() {
+                                removed_step = Some(step_index);
+                            }
+                        });
+                    }
+                    if let Some((from, to)) = moved {
+                        stack.steps.swap(from, to);
+                        changed = true;
+                    }
+                    if let Some(step_index) = removed_step {
+                        stack.steps.remove(step_index);
+                        changed = true;
+                    }
+
+                    ui.horizontal(|ui| {
+                        if ui.button("Add step").clicked() {
+                            stack.steps.push(StackStep::default());
+                            changed = true;
+                        }
+                        if ui.button("✖ Remove stack").clicked() {
+                            removed = Some(index);
+                        }
+                    });
+                });
+        }
+        if let Some(index) = removed {
+            self.settings.stacks.stacks.remove(index);
+            if index < self.stack_runner.runs.len() {
+                self.stack_runner.runs.remove(index);
+            }
+            changed = true;
+        }
+        if changed {
+            // Step lists may no longer match the last run's statuses
+            for (run, stack) in self.stack_runner.runs.iter_mut().zip(&self.settings.stacks.stacks) {
+                if run.as_ref().is_some_and(|run| run.receiver.is_none() && run.statuses.len() != stack.steps.len()) {
+                    *run = None;
+                }
+            }
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("Add stack").clicked() {
+                self.settings.stacks.stacks.push(StackConfig::default());
+                changed = true;
+            }
+            ui.label(RichText::new("Services usually need the dashboard to run as administrator")
+                .color(Color32::from_rgb(107, 114, 128)));
+        });
+
+        if changed {
+            self.save_settings();
+        }
+    }
+}
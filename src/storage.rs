@@ -0,0 +1,51 @@
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use log::warn;
+
+/// Reads a JSON data file stored next to settings.json.
+/// A missing file gives the default; a damaged one is logged and also gives the default.
+pub fn load_json<T: DeserializeOwned + Default>(path: &str) -> T {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return T::default();
+    };
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        warn!("Could not parse {}: {}", path, e);
+        T::default()
+    })
+}
+
+/// Writes a value as pretty-printed JSON with `write_atomic`, logging failures
+pub fn save_json<T: Serialize>(path: &str, value: &T) {
+    let result = serde_json::to_string_pretty(value)
+        .map_err(io::Error::from)
+        .and_then(|json| write_atomic(path, json.as_bytes()));
+    if let Err(e) = result {
+        warn!("Could not save {}: {}", path, e);
+    }
+}
+
+/// Replaces the file's contents so a crash or power loss mid-write leaves the previous ones
+pub fn write_atomic(path: impl AsRef<Path>, contents: &[u8]) -> io::Result<()> {
+    write_atomic_with(path, |file| file.write_all(contents))
+}
+
+/// Lets `write` fill a temporary file next to `path`, flushes it to disk and renames it over `path`
+pub fn write_atomic_with(path: impl AsRef<Path>, write: impl FnOnce(&mut File) -> io::Result<()>) -> io::Result<()> {
+    let path = path.as_ref();
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    let temp = PathBuf::from(temp);
+    let result = File::create(&temp)
+        .and_then(|mut file| {
+            write(&mut file)?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temp, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
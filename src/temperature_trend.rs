@@ -0,0 +1,196 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use log::warn;
+use crate::notifications::NotificationSource;
+use crate::{toast, DevDashboard};
+
+/// Readings kept per sensor, one per second
+const MAX_SAMPLES: usize = 30 * 60;
+/// Slower rises are normal warm-up after a load starts
+const MIN_RISE_PER_MINUTE: f32 = 0.3;
+/// How well the readings must fit a straight line to count as a sustained rise rather than noise
+const MIN_FIT: f32 = 0.6;
+/// Warn when the threshold is this close at the current rate
+const LEAD_MINUTES: f32 = 10.0;
+/// Between repeated notifications for the same sensor
+const ALERT_COOLDOWN: Duration = Duration::from_secs(30 * 60);
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct TemperatureAlertSettings {
+    pub enabled: bool,
+    pub window_minutes: u32,        // History the trend is fitted over
+    pub cpu_critical: u32,          // Celsius, used when the thermal zone reports no passive point
+    pub gpu_critical: u32,          // Celsius, used when the driver reports no slowdown temperature
+}
+
+impl Default for TemperatureAlertSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window_minutes: 5,
+            cpu_critical: 95,
+            gpu_critical: 90,
+        }
+    }
+}
+
+/// A sensor heading for its threshold
+#[derive(Clone, Copy)]
+pub struct TrendWarning {
+    pub rise_per_minute: f32,
+    pub critical: f32,
+    pub minutes_left: Option<f32>,  // None once the threshold is reached
+}
+
+impl TrendWarning {
+    fn text(&self) -> String {
+        match self.minutes_left {
+            Some(minutes) => format!("Rising {:.1}°C/min, {:.0}°C in about {:.0} min", self.rise_per_minute, self.critical, minutes.max(1.0)),
+            None => format!("At or above {:.0}°C and still rising {:.1}°C/min", self.critical, self.rise_per_minute),
+        }
+    }
+}
+
+/// Recent readings of one sensor and its current warning
+#[derive(Default)]
+pub struct TemperatureTrend {
+    samples: VecDeque<(Instant, f32)>,
+    pub warning: Option<TrendWarning>,
+    last_alert: Option<Instant>,
+}
+
+impl TemperatureTrend {
+    fn push(&mut self, temperature: f32) {
+        if self.samples.len() == MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((Instant::now(), temperature));
+    }
+
+    /// Least-squares slope in °C per minute over the window, and how well a line fits (R²)
+    fn fit(&self, window: Duration) -> Option<(f32, f32)> {
+        let now = Instant::now();
+        let first = self.samples.front()?.0;
+        // A partial window would turn a load spike into a trend
+        if now.duration_since(first) < window.mul_f32(0.9) {
+            return None;
+        }
+        let points: Vec<(f32, f32)> = self.samples.iter()
+            .filter(|(time, _)| now.duration_since(*time) <= window)
+            .map(|(time, value)| (now.duration_since(*time).as_secs_f32() / -60.0, *value))
+            .collect();
+        if points.len() < 10 {
+            return None;
+        }
+        let count = points.len() as f32;
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f32>() / count;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<f32>() / count;
+        let (mut xy, mut xx, mut yy) = (0.0, 0.0, 0.0);
+        for (x, y) in &points {
+            xy += (x - mean_x) * (y - mean_y);
+            xx += (x - mean_x) * (x - mean_x);
+            yy += (y - mean_y) * (y - mean_y);
+        }
+        if xx == 0.0 || yy == 0.0 {
+            return None;
+        }
+        Some((xy / xx, xy * xy / (xx * yy)))
+    }
+
+    /// Records a reading and re-evaluates the warning; returns it when it is new enough to notify about
+    fn update(&mut self, temperature: Option<f32>, critical: f32, window: Duration) -> Option<TrendWarning> {
+        let Some(temperature) = temperature else {
+            self.warning = None;
+            return None;
+        };
+        self.push(temperature);
+        self.warning = self.fit(window)
+            .filter(|(slope, fit)| *slope >= MIN_RISE_PER_MINUTE && *fit >= MIN_FIT)
+            .and_then(|(slope, _)| {
+                let minutes_left = (critical - temperature) / slope;
+                (minutes_left <= LEAD_MINUTES).then_some(TrendWarning {
+                    rise_per_minute: slope,
+                    critical,
+                    minutes_left: (temperature < critical).then_some(minutes_left),
+                })
+            });
+        let warning = self.warning?;
+        if self.last_alert.is_some_and(|time| time.elapsed() < ALERT_COOLDOWN) {
+            return None;
+        }
+        self.last_alert = Some(Instant::now());
+        Some(warning)
+    }
+}
+
+/// Trends of the CPU and GPU temperatures
+#[derive(Default)]
+pub struct TemperatureTrends {
+    pub cpu: TemperatureTrend,
+    pub gpu: TemperatureTrend,
+}
+
+/// Amber line under a card's temperature while its trend is heading for the threshold
+pub fn show_trend_warning(ui: &mut egui::Ui, trend: &TemperatureTrend) {
+    if let Some(warning) = &trend.warning {
+        ui.label(RichText::new(format!("⚠ {}", warning.text())).color(Color32::from_rgb(202, 138, 4)))
+            .on_hover_text("A steady rise like this often means a failing fan or a blocked vent");
+    }
+}
+
+impl DevDashboard {
+    /// Feeds the latest temperatures to the trends and notifies about new warnings
+    pub(crate) fn update_temperature_trends(&mut self) {
+        let settings = &self.settings.temperature_alerts;
+        if !settings.enabled {
+            self.temperature_trends = TemperatureTrends::default();
+            return;
+        }
+        let window = Duration::from_secs(settings.window_minutes.max(1) as u64 * 60);
+
+        let cpu_status = self.throttle_monitor.status;
+        let cpu_critical = cpu_status.and_then(|status| status.passive_temperature).unwrap_or(settings.cpu_critical as f32);
+        if let Some(warning) = self.temperature_trends.cpu.update(cpu_status.and_then(|status| status.temperature), cpu_critical, window) {
+            warn!("CPU temperature trend: {}", warning.text());
+            toast::show_toast("CPU temperature is climbing", &warning.text());
+            self.notifications.push(NotificationSource::Alert, "CPU temperature is climbing", &warning.text());
+            self.daily_summaries.record_alert(&format!("CPU temperature: {}", warning.text()));
+        }
+
+        let gpu = self.gpu_info.as_ref();
+        let gpu_critical = gpu.and_then(|gpu| gpu.limits.slowdown_temperature).unwrap_or(settings.gpu_critical) as f32;
+        let gpu_temperature = gpu.and_then(|gpu| gpu.temperature).map(|temperature| temperature as f32);
+        if let Some(warning) = self.temperature_trends.gpu.update(gpu_temperature, gpu_critical, window) {
+            warn!("GPU temperature trend: {}", warning.text());
+            toast::show_toast("GPU temperature is climbing", &warning.text());
+            self.notifications.push(NotificationSource::Alert, "GPU temperature is climbing", &warning.text());
+            self.daily_summaries.record_alert(&format!("GPU temperature: {}", warning.text()));
+        }
+    }
+
+    pub(crate) fn show_temperature_alert_settings(&mut self, ui: &mut egui::Ui) {
+        ui.label("Temperature Alerts:");
+        let settings = &mut self.settings.temperature_alerts;
+        let mut changed = ui.checkbox(&mut settings.enabled, "Warn when the CPU or GPU temperature is climbing towards its limit")
+            .on_hover_text("Notifies before the limit is reached, from a steady rise in the recent readings")
+            .changed();
+        ui.add_enabled_ui(settings.enabled, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Trend over the last");
+                changed |= ui.add(egui::DragValue::new(&mut settings.window_minutes).clamp_range(2..=30).suffix(" min")).changed();
+            });
+            ui.horizontal(|ui| {
+                ui.label("Limits when not reported:");
+                changed |= ui.add(egui::DragValue::new(&mut settings.cpu_critical).clamp_range(60..=110).prefix("CPU ").suffix("°C")).changed();
+                changed |= ui.add(egui::DragValue::new(&mut settings.gpu_critical).clamp_range(60..=110).prefix("GPU ").suffix("°C")).changed();
+            });
+        });
+        if changed {
+            self.save_settings();
+        }
+    }
+}
@@ -0,0 +1,279 @@
+use std::f64::consts::PI;
+use std::time::{Duration, Instant};
+use chrono::{Datelike, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use winreg::enums::HKEY_CURRENT_USER;
+use winreg::RegKey;
+use log::info;
+use crate::DevDashboard;
+
+/// How often the Windows app theme is re-read while following it
+const SYSTEM_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+const PERSONALIZE_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Themes\Personalize";
+
+const DARK_PANEL: Color32 = Color32::from_rgb(17, 24, 39);
+const DARK_CARD: Color32 = Color32::from_rgb(31, 41, 55);
+const DARK_TEXT: Color32 = Color32::from_rgb(243, 244, 246);
+const LIGHT_PANEL: Color32 = Color32::from_rgb(243, 244, 246);
+const LIGHT_CARD: Color32 = Color32::from_rgb(255, 255, 255);
+const LIGHT_TEXT: Color32 = Color32::from_rgb(17, 24, 39);
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ThemeMode {
+    /// Follow the Windows "app mode" setting
+    System,
+    /// Light during the day, dark from the evening switch time or sundown
+    Schedule,
+    Dark,
+    Light,
+}
+
+impl ThemeMode {
+    fn label(self) -> &'static str {
+        match self {
+            ThemeMode::System => "Follow Windows",
+            ThemeMode::Schedule => "Schedule",
+            ThemeMode::Dark => "Dark",
+            ThemeMode::Light => "Light",
+        }
+    }
+}
+
+/// Persisted theme choice and per-theme card colors
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ThemeSettings {
+    pub mode: ThemeMode,
+    /// Switch at sunrise and sunset for the location below instead of fixed times
+    pub follow_sun: bool,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub light_at: String,            // HH:MM
+    pub dark_at: String,             // HH:MM
+    /// Card background while dark, kept when switching to light and back
+    pub dark_card: Option<[u8; 3]>,
+    /// Card background while light
+    pub light_card: Option<[u8; 3]>,
+}
+
+impl Default for ThemeSettings {
+    fn default() -> Self {
+        Self {
+            mode: ThemeMode::System,
+            follow_sun: false,
+            latitude: 0.0,
+            longitude: 0.0,
+            light_at: "07:00".to_string(),
+            dark_at: "19:00".to_string(),
+            dark_card: None,
+            light_card: None,
+        }
+    }
+}
+
+/// Colors the current theme paints panels and cards with
+#[derive(Clone, Copy)]
+pub struct Palette {
+    pub dark: bool,
+    pub panel: Color32,
+    pub card: Color32,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self { dark: true, panel: DARK_PANEL, card: DARK_CARD }
+    }
+}
+
+/// Last reading of the Windows theme and the palette in use
+#[derive(Default)]
+pub struct ThemeState {
+    palette: Palette,
+    system_light: bool,
+    last_check: Option<Instant>,
+}
+
+/// Whether Windows is set to show apps in light mode
+fn system_uses_light() -> bool {
+    RegKey::predef(HKEY_CURRENT_USER)
+        .open_subkey(PERSONALIZE_KEY)
+        .and_then(|key| key.get_value::<u32, _>("AppsUseLightTheme"))
+        .map(|value| value != 0)
+        .unwrap_or(false)
+}
+
+fn parse_time(text: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(text.trim(), "%H:%M").ok()
+}
+
+/// The sun's course over one day at a location
+enum Daylight {
+    /// Local sunrise and sunset
+    Sun(NaiveTime, NaiveTime),
+    PolarDay,
+    PolarNight,
+}
+
+/// Sunrise and sunset by the NOAA approximation, which is within a few minutes
+fn daylight(date: NaiveDate, latitude: f64, longitude: f64) -> Daylight {
+    let gamma = 2.0 * PI / 365.0 * (date.ordinal0() as f64);
+    let eqtime = 229.18 * (0.000075 + 0.001868 * gamma.cos() - 0.032077 * gamma.sin()
+        - 0.014615 * (2.0 * gamma).cos() - 0.040849 * (2.0 * gamma).sin());
+    let decl = 0.006918 - 0.399912 * gamma.cos() + 0.070257 * gamma.sin()
+        - 0.006758 * (2.0 * gamma).cos() + 0.000907 * (2.0 * gamma).sin()
+        - 0.002697 * (3.0 * gamma).cos() + 0.00148 * (3.0 * gamma).sin();
+    let lat = latitude.to_radians();
+    let cos_ha = 90.833f64.to_radians().cos() / (lat.cos() * decl.cos()) - lat.tan() * decl.tan();
+    if cos_ha < -1.0 {
+        return Daylight::PolarDay;
+    }
+    if cos_ha > 1.0 {
+        return Daylight::PolarNight;
+    }
+    let ha = cos_ha.acos().to_degrees();
+
+    let midnight = Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap_or_default());
+    let local = |minutes: f64| (midnight + chrono::Duration::seconds((minutes * 60.0) as i64))
+        .with_timezone(&Local)
+        .time();
+    Daylight::Sun(local(720.0 - 4.0 * (longitude + ha) - eqtime), local(720.0 - 4.0 * (longitude - ha) - eqtime))
+}
+
+impl ThemeSettings {
+    fn scheduled_dark(&self) -> bool {
+        let (light, dark) = if self.follow_sun {
+            match daylight(Local::now().date_naive(), self.latitude, self.longitude) {
+                Daylight::Sun(sunrise, sunset) => (sunrise, sunset),
+                Daylight::PolarDay => return false,
+                Daylight::PolarNight => return true,
+            }
+        } else {
+            match (parse_time(&self.light_at), parse_time(&self.dark_at)) {
+                (Some(light), Some(dark)) => (light, dark),
+                _ => return true,
+            }
+        };
+        let now = Local::now().time();
+        if light <= dark {
+            now < light || now >= dark
+        } else {
+            now >= dark && now < light
+        }
+    }
+}
+
+impl DevDashboard {
+    /// Picks dark or light for this frame and applies it to egui
+    pub(crate) fn apply_theme(&mut self, ctx: &egui::Context) {
+        let theme = &self.settings.theme;
+        let state = &mut self.theme_state;
+        if theme.mode == ThemeMode::System {
+            let due = match state.last_check {
+                Some(last) => last.elapsed() >= SYSTEM_CHECK_INTERVAL,
+                None => true,
+            };
+            if due {
+                state.last_check = Some(Instant::now());
+                state.system_light = system_uses_light();
+            }
+        }
+        let dark = match theme.mode {
+            ThemeMode::System => !state.system_light,
+            ThemeMode::Schedule => theme.scheduled_dark(),
+            ThemeMode::Dark => true,
+            ThemeMode::Light => false,
+        };
+        if dark != state.palette.dark {
+            info!("Switching to the {} theme", if dark { "dark" } else { "light" });
+        }
+
+        let (card, panel, text) = if dark {
+            (theme.dark_card.map_or(DARK_CARD, |[r, g, b]| Color32::from_rgb(r, g, b)), DARK_PANEL, DARK_TEXT)
+        } else {
+            (theme.light_card.map_or(LIGHT_CARD, |[r, g, b]| Color32::from_rgb(r, g, b)), LIGHT_PANEL, LIGHT_TEXT)
+        };
+        state.palette = Palette { dark, panel, card };
+
+        let mut visuals = if dark { egui::Visuals::dark() } else { egui::Visuals::light() };
+        visuals.panel_fill = panel;
+        visuals.window_fill = panel;
+        visuals.widgets.noninteractive.bg_fill = card;
+        visuals.widgets.noninteractive.fg_stroke.color = text;
+        visuals.window_rounding = 12.0.into();
+        visuals.window_shadow.extrusion = 2.0;
+        ctx.set_visuals(visuals);
+    }
+
+    /// Colors for panels and cards in the current theme
+    pub(crate) fn palette(&self) -> Palette {
+        self.theme_state.palette
+    }
+
+    /// Settings section for the theme and card colors
+    pub(crate) fn show_theme_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        let theme = &mut self.settings.theme;
+
+        ui.label("Theme:");
+        ui.horizontal(|ui| {
+            for mode in [ThemeMode::System, ThemeMode::Schedule, ThemeMode::Dark, ThemeMode::Light] {
+                changed |= ui.selectable_value(&mut theme.mode, mode, mode.label()).changed();
+            }
+        });
+
+        if theme.mode == ThemeMode::Schedule {
+            changed |= ui.checkbox(&mut theme.follow_sun, "Switch at sunrise and sunset").changed();
+            if theme.follow_sun {
+                ui.horizontal(|ui| {
+                    ui.label("Latitude:");
+                    changed |= ui.add(egui::DragValue::new(&mut theme.latitude).clamp_range(-90.0..=90.0).speed(0.1).suffix("°")).changed();
+                    ui.label("Longitude:");
+                    changed |= ui.add(egui::DragValue::new(&mut theme.longitude).clamp_range(-180.0..=180.0).speed(0.1).suffix("°"))
+                        .on_hover_text("East is positive")
+                        .changed();
+                });
+                let today = match daylight(Local::now().date_naive(), theme.latitude, theme.longitude) {
+                    Daylight::Sun(sunrise, sunset) => format!("Today: light from {}, dark from {}",
+                        sunrise.format("%H:%M"), sunset.format("%H:%M")),
+                    Daylight::PolarDay => "The sun does not set here today".to_string(),
+                    Daylight::PolarNight => "The sun does not rise here today".to_string(),
+                };
+                ui.label(RichText::new(today).color(Color32::from_rgb(107, 114, 128)));
+            } else {
+                ui.horizontal(|ui| {
+                    ui.label("Light from:");
+                    changed |= ui.add(egui::TextEdit::singleline(&mut theme.light_at).desired_width(50.0)).changed();
+                    ui.label("Dark from:");
+                    changed |= ui.add(egui::TextEdit::singleline(&mut theme.dark_at).desired_width(50.0)).changed();
+                    if parse_time(&theme.light_at).is_none() || parse_time(&theme.dark_at).is_none() {
+                        ui.colored_label(Color32::from_rgb(220, 50, 50), "Use HH:MM");
+                    }
+                });
+            }
+        }
+
+        ui.horizontal(|ui| {
+            let mut card_color = |ui: &mut egui::Ui, label: &str, color: &mut Option<[u8; 3]>, default: Color32| {
+                ui.label(label);
+                let mut rgb = color.unwrap_or([default.r(), default.g(), default.b()]);
+                if ui.color_edit_button_srgb(&mut rgb).changed() {
+                    *color = Some(rgb);
+                    changed = true;
+                }
+                if color.is_some() && ui.small_button("Reset").clicked() {
+                    *color = None;
+                    changed = true;
+                }
+            };
+            card_color(ui, "Dark cards:", &mut theme.dark_card, DARK_CARD);
+            card_color(ui, "Light cards:", &mut theme.light_card, LIGHT_CARD);
+        });
+
+        if changed {
+            self.theme_state.last_check = None;
+            self.save_settings();
+        }
+    }
+}
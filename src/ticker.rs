@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+use eframe::egui;
+use egui::RichText;
+use futures::future::BoxFuture;
+use reqwest::{Client, Url};
+use serde::{Serialize, Deserialize};
+use log::{debug, warn};
+use crate::DevDashboard;
+
+/// Latest price for a symbol
+#[derive(Clone)]
+pub struct Quote {
+    pub price: f64,
+    pub change_percent: Option<f64>,  // Change since the previous close / last 24h
+    pub currency: Option<String>,
+}
+
+/// A source of quotes for the ticker card
+/// Implement this to add another market data service
+pub trait QuoteProvider: Send + Sync {
+    fn fetch<'a>(&'a self, client: &'a Client, symbol: &'a str) -> BoxFuture<'a, Result<Quote, String>>;
+}
+
+/// Stock, ETF, index, and crypto pair quotes from Yahoo Finance (e.g. MSFT, ^GSPC, BTC-USD)
+struct YahooFinance;
+
+impl QuoteProvider for YahooFinance {
+    fn fetch<'a>(&'a self, client: &'a Client, symbol: &'a str) -> BoxFuture<'a, Result<Quote, String>> {
+        Box::pin(async move {
+            // Symbols such as ^GSPC or BRK/B need encoding to stay a single path segment
+            let mut url = Url::parse("https://query1.finance.yahoo.com/v8/finance/chart").map_err(|e| e.to_string())?;
+            url.path_segments_mut()
+                .map_err(|_| "Invalid quote URL".to_string())?
+                .push(symbol);
+            let json: serde_json::Value = client.get(url)
+                .query(&[("range", "1d"), ("interval", "1d")])
+                .header("User-Agent", "Mozilla/5.0")
+                .send().await.map_err(|e| e.to_string())?
+                .json().await.map_err(|e| e.to_string())?;
+
+            let meta = &json["chart"]["result"][0]["meta"];
+            let price = meta["regularMarketPrice"].as_f64().ok_or("Unknown symbol")?;
+            let change_percent = meta["chartPreviousClose"].as_f64()
+                .filter(|previous| *previous > 0.0)
+                .map(|previous| (price - previous) / previous * 100.0);
+
+            Ok(Quote {
+                price,
+                change_percent,
+                currency: meta["currency"].as_str().map(str::to_string),
+            })
+        })
+    }
+}
+
+/// Crypto quotes in USD from CoinGecko, by coin id (e.g. bitcoin, ethereum)
+struct CoinGecko;
+
+impl QuoteProvider for CoinGecko {
+    fn fetch<'a>(&'a self, client: &'a Client, symbol: &'a str) -> BoxFuture<'a, Result<Quote, String>> {
+        Box::pin(async move {
+            let id = symbol.to_lowercase();
+            let json: serde_json::Value = client.get("https://api.coingecko.com/api/v3/simple/price")
+                .query(&[("ids", id.as_str()), ("vs_currencies", "usd"), ("include_24hr_change", "true")])
+                .send().await.map_err(|e| e.to_string())?
+                .json().await.map_err(|e| e.to_string())?;
+
+            let coin = &json[&id];
+            let price = coin["usd"].as_f64().ok_or("Unknown coin id")?;
+
+            Ok(Quote {
+                price,
+                change_percent: coin["usd_24h_change"].as_f64(),
+                currency: Some("USD".to_string()),
+            })
+        })
+    }
+}
+
+/// Providers selectable in Settings
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum TickerProviderKind {
+    #[default]
+    YahooFinance,
+    CoinGecko,
+}
+
+impl TickerProviderKind {
+    const ALL: [TickerProviderKind; 2] = [TickerProviderKind::YahooFinance, TickerProviderKind::CoinGecko];
+
+    fn label(&self) -> &'static str {
+        match self {
+            TickerProviderKind::YahooFinance => "Yahoo Finance",
+            TickerProviderKind::CoinGecko => "CoinGecko",
+        }
+    }
+
+    fn symbol_hint(&self) -> &'static str {
+        match self {
+            TickerProviderKind::YahooFinance => "MSFT, ^GSPC, BTC-USD",
+            TickerProviderKind::CoinGecko => "bitcoin, ethereum",
+        }
+    }
+
+    fn provider(&self) -> Box<dyn QuoteProvider> {
+        match self {
+            TickerProviderKind::YahooFinance => Box::new(YahooFinance),
+            TickerProviderKind::CoinGecko => Box::new(CoinGecko),
+        }
+    }
+}
+
+/// Persisted ticker configuration; the card is hidden until enabled
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct TickerSettings {
+    pub enabled: bool,
+    pub provider: TickerProviderKind,
+    pub symbols: Vec<String>,
+    pub refresh_minutes: u32,
+}
+
+impl Default for TickerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: TickerProviderKind::default(),
+            symbols: Vec::new(),
+            refresh_minutes: 5,
+        }
+    }
+}
+
+/// Runtime state of the ticker card
+#[derive(Default)]
+pub struct Ticker {
+    quotes: HashMap<String, Result<Quote, String>>,
+    last_fetch: Option<Instant>,
+    receiver: Option<Receiver<(String, Result<Quote, String>)>>,
+    pending: usize,
+    symbols_input: Option<String>,   // Comma separated symbols being edited in Settings
+}
+
+impl DevDashboard {
+    /// Fetches quotes when the refresh interval has passed and collects finished requests
+    pub(crate) fn update_ticker(&mut self) {
+        if let Some(receiver) = &self.ticker.receiver {
+            while let Ok((symbol, quote)) = receiver.try_recv() {
+                if let Err(e) = &quote {
+                    warn!("Failed to fetch quote for {}: {}", symbol, e);
+                }
+                self.ticker.quotes.insert(symbol, quote);
+                self.ticker.pending = self.ticker.pending.saturating_sub(1);
+            }
+            if self.ticker.pending == 0 {
+                self.ticker.receiver = None;
+            }
+        }
+
+        let settings = &self.settings.ticker;
+        if !settings.enabled || settings.symbols.is_empty() || self.ticker.pending > 0 {
+            return;
+        }
+        let interval = Duration::from_secs(settings.refresh_minutes.max(1) as u64 * 60);
+        if let Some(last) = self.ticker.last_fetch {
+            if last.elapsed() < interval {
+                return;
+            }
+        }
+
+        let symbols = settings.symbols.clone();
+        let provider = settings.provider.provider();
+        debug!("Fetching quotes for {:?}", symbols);
+
+        let (sender, receiver) = channel();
+        self.ticker.receiver = Some(receiver);
+        self.ticker.pending = symbols.len();
+        self.ticker.last_fetch = Some(Instant::now());
+
+        self.runtime().spawn(async move {
+            let client = Client::new();
+            for symbol in symbols {
+                let quote = provider.fetch(&client, &symbol).await;
+                if sender.send((symbol, quote)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Displays the ticker card
+    /// Shows the current price and daily change per configured symbol
+    pub(crate) fn show_ticker_card(&self, ui: &mut egui::Ui) {
+        self.show_card(ui, "Ticker", |ui| {
+            if self.settings.ticker.symbols.is_empty() {
+                ui.label("Add symbols in Settings.");
+                return;
+            }
+
+            for symbol in &self.settings.ticker.symbols {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(symbol.to_uppercase()).strong());
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        match self.ticker.quotes.get(symbol) {
+                            Some(Ok(quote)) => {
+                                if let Some(change) = quote.change_percent {
+                                    let color = if change >= 0.0 {
+                                        egui::Color32::from_rgb(22, 163, 74)
+                                    } else {
+                                        egui::Color32::from_rgb(220, 38, 38)
+                                    };
+                                    let arrow = if change >= 0.0 { "▲" } else { "▼" };
+                                    ui.colored_label(color, format!("{} {:.2}%", arrow, change.abs()));
+                                }
+                                ui.label(format!("{:.2} {}", quote.price, quote.currency.as_deref().unwrap_or_default()));
+                            }
+                            Some(Err(error)) => {
+                                ui.colored_label(egui::Color32::from_rgb(220, 50, 50), "Unavailable")
+                                    .on_hover_text(error);
+                            }
+                            None => {
+                                ui.spinner();
+                            }
+                        }
+                    });
+                });
+            }
+        });
+    }
+
+    /// Latest quote per symbol, for copying the card
+    pub(crate) fn ticker_card_values(&self) -> Vec<(String, String)> {
+        self.settings.ticker.symbols
+            .iter()
+            .map(|symbol| {
+                let value = match self.ticker.quotes.get(symbol) {
+                    Some(Ok(quote)) => {
+                        let mut value = format!("{:.2} {}", quote.price, quote.currency.as_deref().unwrap_or_default());
+                        if let Some(change) = quote.change_percent {
+                            value.push_str(&format!(" ({:+.2}%)", change));
+                        }
+                        value
+                    }
+                    Some(Err(error)) => format!("Unavailable: {}", error),
+                    None => "Loading".to_string(),
+                };
+                (symbol.to_uppercase(), value)
+            })
+            .collect()
+    }
+
+    /// Settings section for the ticker card
+    pub(crate) fn show_ticker_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        let mut refetch = false;
+
+        changed |= ui.checkbox(&mut self.settings.ticker.enabled, "Show ticker card").changed();
+
+        if self.settings.ticker.enabled {
+            ui.horizontal(|ui| {
+                ui.label("Provider:");
+                egui::ComboBox::from_id_source("ticker_provider")
+                    .selected_text(self.settings.ticker.provider.label())
+                    .show_ui(ui, |ui| {
+                        for kind in TickerProviderKind::ALL {
+                            if ui.selectable_value(&mut self.settings.ticker.provider, kind, kind.label()).changed() {
+                                changed = true;
+                                refetch = true;
+                            }
+                        }
+                    });
+            });
+
+            let symbols_input = self.ticker.symbols_input
+                .get_or_insert_with(|| self.settings.ticker.symbols.join(", "));
+            ui.horizontal(|ui| {
+                ui.label("Symbols:");
+                let response = ui.add(egui::TextEdit::singleline(symbols_input)
+                    .hint_text(self.settings.ticker.provider.symbol_hint()));
+                if response.lost_focus() {
+                    let symbols: Vec<String> = symbols_input
+                        .split(',')
+                        .map(|symbol| symbol.trim().to_string())
+                        .filter(|symbol| !symbol.is_empty())
+                        .collect();
+                    if symbols != self.settings.ticker.symbols {
+                        self.settings.ticker.symbols = symbols;
+                        changed = true;
+                        refetch = true;
+                    }
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Refresh every (minutes):");
+                changed |= ui.add(egui::DragValue::new(&mut self.settings.ticker.refresh_minutes)
+                    .clamp_range(1..=120)).changed();
+            });
+        }
+
+        if refetch {
+            // Dropping the receiver discards quotes still arriving for the old provider or symbols,
+            // and ends that fetch at its next send
+            self.ticker.receiver = None;
+            self.ticker.pending = 0;
+            self.ticker.quotes.clear();
+            self.ticker.last_fetch = None;
+        }
+        if changed {
+            self.save_settings();
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use std::sync::Once;
+use windows::core::HSTRING;
+use windows::Data::Xml::Dom::XmlDocument;
+use windows::UI::Notifications::{ToastNotification, ToastNotificationManager};
+use windows::Win32::UI::Shell::SetCurrentProcessExplicitAppUserModelID;
+use winreg::enums::*;
+use winreg::RegKey;
+use log::{debug, warn};
+
+/// Identifies the dashboard to the notification platform without an installer-created shortcut
+const APP_ID: &str = "ShilohCoffey.DevDashboard";
+const APP_ID_KEY: &str = r"Software\Classes\AppUserModelId\ShilohCoffey.DevDashboard";
+
+static REGISTER: Once = Once::new();
+
+/// Unpackaged apps need their ID registered under the user's classes before Windows shows their toasts
+fn register_app_id() {
+    REGISTER.call_once(|| {
+        let result = RegKey::predef(HKEY_CURRENT_USER)
+            .create_subkey(APP_ID_KEY)
+            .and_then(|(key, _)| key.set_value("DisplayName", &"Dev Dashboard"));
+        if let Err(e) = result {
+            warn!("Could not register for notifications: {}", e);
+        }
+        if let Err(e) = unsafe { SetCurrentProcessExplicitAppUserModelID(&HSTRING::from(APP_ID)) } {
+            warn!("Could not set the app ID for notifications: {}", e);
+        }
+    });
+}
+
+/// Escapes text for the toast template
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn show(title: &str, body: &str) -> windows::core::Result<()> {
+    let xml = format!(
+        "<toast><visual><binding template=\"ToastGeneric\"><text>{}</text><text>{}</text></binding></visual></toast>",
+        escape(title),
+        escape(body)
+    );
+    let document = XmlDocument::new()?;
+    document.LoadXml(&HSTRING::from(xml))?;
+    let notification = ToastNotification::CreateToastNotification(&document)?;
+    ToastNotificationManager::CreateToastNotifierWithId(&HSTRING::from(APP_ID))?.Show(&notification)
+}
+
+/// Shows a Windows notification; runs on its own thread so the UI never waits on the notification platform
+pub fn show_toast(title: &str, body: &str) {
+    let (title, body) = (title.to_string(), body.to_string());
+    std::thread::spawn(move || {
+        register_app_id();
+        match show(&title, &body) {
+            Ok(()) => debug!("Showed notification: {}", title),
+            Err(e) => warn!("Could not show notification {}: {}", title, e),
+        }
+    });
+}
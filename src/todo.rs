@@ -0,0 +1,165 @@
+use chrono::{Local, NaiveDate};
+use eframe::egui;
+use egui::RichText;
+use serde::{Serialize, Deserialize};
+use crate::{storage, DevDashboard};
+
+/// Todo items are stored next to settings.json
+const TODO_FILE: &str = "todos.json";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TodoItem {
+    pub text: String,
+    pub done: bool,
+    pub due: Option<NaiveDate>,
+}
+
+/// Todo card state; items are kept in the user's order
+#[derive(Default)]
+pub struct TodoList {
+    pub items: Vec<TodoItem>,
+    new_text: String,
+    new_due: String,              // YYYY-MM-DD, optional
+    due_invalid: bool,
+}
+
+impl TodoList {
+    pub fn load() -> Self {
+        let items = storage::load_json(TODO_FILE);
+        Self { items, ..Default::default() }
+    }
+
+    fn save(&self) {
+        storage::save_json(TODO_FILE, &self.items);
+    }
+
+    fn remaining(&self) -> usize {
+        self.items.iter().filter(|item| !item.done).count()
+    }
+}
+
+impl DevDashboard {
+    /// Displays the todo card
+    /// Items can be checked off, reordered, removed and given a due date
+    pub(crate) fn show_todo_card(&mut self, ui: &mut egui::Ui) {
+        let todos = &mut self.todos;
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Todo").strong().heading());
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if todos.items.iter().any(|item| item.done)
+                    && ui.small_button("Clear done").on_hover_text("Remove completed items").clicked() {
+                    todos.items.retain(|item| !item.done);
+                    changed = true;
+                }
+                ui.label(format!("{} left", todos.remaining()));
+            });
+        });
+        ui.add_space(8.0);
+
+        let today = Local::now().date_naive();
+        let count = todos.items.len();
+        let mut moved = None;
+        let mut removed = None;
+
+        for (index, item) in todos.items.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                let text = if item.done {
+                    RichText::new(&item.text).strikethrough().color(egui::Color32::from_rgb(107, 114, 128))
+                } else {
+                    RichText::new(&item.text)
+                };
+                changed |= ui.checkbox(&mut item.done, text).changed();
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                        removed = Some(index);
+                    }
+                    if ui.add_enabled(index + 1 < count, egui::Button::new("⏷").small()).clicked() {
+                        moved = Some((index, index + 1));
+                    }
+                    if ui.add_enabled(index > 0, egui::Button::new("⏶").small()).clicked() {
+                        moved = Some((index, index - 1));
+                    }
+                    if let Some(due) = item.due {
+                        let color = if item.done {
+                            egui::Color32::from_rgb(107, 114, 128)
+                        } else if due < today {
+                            egui::Color32::from_rgb(220, 50, 50)
+                        } else if due == today {
+                            egui::Color32::from_rgb(202, 138, 4)
+                        } else {
+                            egui::Color32::from_rgb(156, 163, 175)
+                        };
+                        let label = if due == today { "Today".to_string() } else { due.format("%b %d").to_string() };
+                        ui.colored_label(color, label);
+                    }
+                });
+            });
+        }
+
+        if let Some((from, to)) = moved {
+            todos.items.swap(from, to);
+            changed = true;
+        }
+        if let Some(index) = removed {
+            todos.items.remove(index);
+            changed = true;
+        }
+
+        ui.add_space(4.0);
+        ui.horizontal(|ui| {
+            let response = ui.add(egui::TextEdit::singleline(&mut todos.new_text)
+                .hint_text("New task")
+                .desired_width(140.0));
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            let due_edit = egui::TextEdit::singleline(&mut todos.new_due)
+                .hint_text("Due YYYY-MM-DD")
+                .desired_width(100.0);
+            let due_edit = if todos.due_invalid { due_edit.text_color(egui::Color32::from_rgb(220, 50, 50)) } else { due_edit };
+            ui.add(due_edit);
+
+            if (ui.button("Add").clicked() || submitted) && !todos.new_text.trim().is_empty() {
+                let due_text = todos.new_due.trim();
+                let due = if due_text.is_empty() {
+                    Ok(None)
+                } else {
+                    NaiveDate::parse_from_str(due_text, "%Y-%m-%d").map(Some)
+                };
+                match due {
+                    Ok(due) => {
+                        todos.items.push(TodoItem {
+                            text: todos.new_text.trim().to_string(),
+                            done: false,
+                            due,
+                        });
+                        todos.new_text.clear();
+                        todos.new_due.clear();
+                        todos.due_invalid = false;
+                        changed = true;
+                    }
+                    Err(_) => todos.due_invalid = true,
+                }
+            }
+        });
+
+        if changed {
+            todos.save();
+        }
+    }
+
+    /// Items in the user's order with their state and due date, for copying the card
+    pub(crate) fn todo_card_values(&self) -> Vec<(String, String)> {
+        self.todos.items
+            .iter()
+            .map(|item| {
+                let mut value = if item.done { "Done".to_string() } else { "Open".to_string() };
+                if let Some(due) = item.due {
+                    value.push_str(&format!(", due {}", due.format("%Y-%m-%d")));
+                }
+                (item.text.clone(), value)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,172 @@
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use sysinfo::{Pid, PidExt, ProcessExt, SystemExt};
+use log::{info, warn};
+use crate::DevDashboard;
+
+/// Column the card is sorted by
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum TopSort {
+    Cpu,
+    Memory,
+}
+
+/// Persisted top processes card configuration
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct TopProcessSettings {
+    pub show_card: bool,
+    pub count: usize,               // Rows shown on the card
+    pub sort: TopSort,
+}
+
+impl Default for TopProcessSettings {
+    fn default() -> Self {
+        Self { show_card: false, count: 10, sort: TopSort::Cpu }
+    }
+}
+
+/// A row of the card
+struct TopProcess {
+    pid: u32,
+    name: String,
+    cpu: f32,                       // Share of the whole machine, like Task Manager
+    memory: u64,                    // Bytes
+}
+
+/// Which row the card menu was opened on, and the outcome of the last kill
+#[derive(Default)]
+pub struct TopProcesses {
+    menu_target: Option<(u32, String)>,
+    status: Option<(String, bool)>,     // Message and whether it failed
+}
+
+fn memory_text(bytes: u64) -> String {
+    let (value, unit) = DevDashboard::format_bytes(bytes);
+    format!("{:.1} {}", value, unit)
+}
+
+impl DevDashboard {
+    /// Busiest processes by the chosen column; expects processes to be freshly refreshed
+    fn top_processes(&self) -> Vec<TopProcess> {
+        let cpu_count = self.sys.cpus().len().max(1) as f32;
+        let mut processes: Vec<TopProcess> = self.sys.processes()
+            .iter()
+            // PID 0 is the idle process, whose "usage" is the unused CPU time
+            .filter(|(pid, _)| pid.as_u32() != 0)
+            .map(|(pid, process)| TopProcess {
+                pid: pid.as_u32(),
+                name: process.name().to_string(),
+                cpu: process.cpu_usage() / cpu_count,
+                memory: process.memory(),
+            })
+            .collect();
+        match self.settings.top_processes.sort {
+            TopSort::Cpu => processes.sort_by(|a, b| b.cpu.total_cmp(&a.cpu).then(b.memory.cmp(&a.memory))),
+            TopSort::Memory => processes.sort_by(|a, b| b.memory.cmp(&a.memory).then(b.cpu.total_cmp(&a.cpu))),
+        }
+        processes.truncate(self.settings.top_processes.count);
+        processes
+    }
+
+    /// Displays the busiest processes; right-clicking a row offers to end it from the card menu
+    pub(crate) fn show_top_processes_card(&mut self, ui: &mut egui::Ui) {
+        let processes = self.top_processes();
+        let mut sort = self.settings.top_processes.sort;
+        let mut hovered = None;
+        self.show_card(ui, "Top Processes", |ui| {
+            egui::Grid::new("top_processes_grid")
+                .striped(true)
+                .spacing([12.0, 4.0])
+                .show(ui, |ui| {
+                    ui.label(RichText::new("Name").strong());
+                    ui.label(RichText::new("PID").strong());
+                    for (column, label) in [(TopSort::Cpu, "CPU"), (TopSort::Memory, "Memory")] {
+                        let text = if sort == column { format!("{} ▼", label) } else { label.to_string() };
+                        if ui.selectable_label(sort == column, RichText::new(text).strong()).clicked() {
+                            sort = column;
+                        }
+                    }
+                    ui.end_row();
+
+                    for process in &processes {
+                        let name = ui.label(&process.name);
+                        ui.label(RichText::new(process.pid.to_string()).color(Color32::from_rgb(156, 163, 175)));
+                        ui.label(format!("{:.1}%", process.cpu));
+                        let memory = ui.label(memory_text(process.memory));
+                        if ui.rect_contains_pointer(name.rect.union(memory.rect)) {
+                            hovered = Some((process.pid, process.name.clone()));
+                        }
+                        ui.end_row();
+                    }
+                });
+            if processes.is_empty() {
+                ui.label(RichText::new("Waiting for process readings...").color(Color32::from_rgb(107, 114, 128)));
+            }
+            if let Some((message, failed)) = &self.top_process_view.status {
+                let color = if *failed { Color32::from_rgb(220, 50, 50) } else { Color32::from_rgb(107, 114, 128) };
+                ui.add_space(4.0);
+                ui.label(RichText::new(message).color(color));
+            }
+        });
+
+        // The card menu opens on this press, so remember the row it belongs to
+        if ui.input(|input| input.pointer.secondary_pressed()) && ui.rect_contains_pointer(ui.min_rect()) {
+            self.top_process_view.menu_target = hovered;
+        }
+        if sort != self.settings.top_processes.sort {
+            self.settings.top_processes.sort = sort;
+            self.save_settings();
+        }
+    }
+
+    /// The process the card menu was opened on, as (PID, name)
+    pub(crate) fn top_process_menu_target(&self) -> Option<(u32, String)> {
+        self.top_process_view.menu_target.clone()
+    }
+
+    /// Ends a process from the card menu; protected and elevated processes need the dashboard elevated
+    pub(crate) fn end_top_process(&mut self, pid: u32, name: &str) {
+        let subject = format!("{} (PID {})", name, pid);
+        let status = match self.sys.process(Pid::from_u32(pid)) {
+            Some(process) if process.kill() => {
+                info!("Ended {} from the top processes card", subject);
+                (format!("Ended {}", subject), false)
+            }
+            Some(_) => {
+                warn!("Could not end {}", subject);
+                (format!("Could not end {}; it may need administrator rights", subject), true)
+            }
+            None => (format!("{} has already exited", subject), false),
+        };
+        self.top_process_view.status = Some(status);
+        self.top_process_view.menu_target = None;
+    }
+
+    /// Each listed process with its CPU and memory use, for copying the card
+    pub(crate) fn top_processes_card_values(&self) -> Vec<(String, String)> {
+        self.top_processes()
+            .into_iter()
+            .map(|process| (
+                format!("{} ({})", process.name, process.pid),
+                format!("CPU {:.1}%, memory {}", process.cpu, memory_text(process.memory)),
+            ))
+            .collect()
+    }
+
+    /// Settings section for the top processes card
+    pub(crate) fn show_top_process_settings(&mut self, ui: &mut egui::Ui) {
+        let settings = &mut self.settings.top_processes;
+        let mut changed = ui.checkbox(&mut settings.show_card, "Show top processes card").changed();
+        if settings.show_card {
+            ui.horizontal(|ui| {
+                ui.label("Processes listed:");
+                changed |= ui.add(egui::DragValue::new(&mut settings.count).clamp_range(3..=50)).changed();
+            });
+        }
+        if changed {
+            self.save_settings();
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use eframe::egui;
+use egui::{Color32, Rect, RichText};
+use serde::{Serialize, Deserialize};
+use log::info;
+use crate::{DashboardCard, DevDashboard, Tab};
+
+/// Persisted tour progress
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct TourSettings {
+    /// Finished or skipped once, so it no longer opens on start
+    pub seen: bool,
+}
+
+/// Parts of the window a tour step can point at
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum TourTarget {
+    Header,
+    Settings,
+    DashboardTab,
+    ToolsTab,
+    ProcessesTab,
+    SecurityTab,
+    ContainersTab,
+    ServicesTab,
+    Card(DashboardCard),
+}
+
+struct TourStep {
+    target: Option<TourTarget>,
+    title: &'static str,
+    text: &'static str,
+}
+
+const STEPS: &[TourStep] = &[
+    TourStep {
+        target: None,
+        title: "Welcome to Dev Dashboard",
+        text: "This short tour points out what each part of the window does. Use Next and Back to move through it, \
+               or Skip to close it; you can take it again from Settings.",
+    },
+    TourStep {
+        target: Some(TourTarget::Header),
+        title: "Top bar",
+        text: "A greeting, today's date and a summary of uptime, overnight alerts and pending app updates. \
+               The buttons on the right open Settings, sharing and the fullscreen wall display.",
+    },
+    TourStep {
+        target: Some(TourTarget::DashboardTab),
+        title: "Dashboard",
+        text: "Live readings for this PC, one card per subsystem. Right-click a card to copy its values or export its history, \
+               or pop it out into its own always-on-top window.",
+    },
+    TourStep {
+        target: Some(TourTarget::Card(DashboardCard::System)),
+        title: "System",
+        text: "The Windows version, computer name and how long it has been running.",
+    },
+    TourStep {
+        target: Some(TourTarget::Card(DashboardCard::Cpu)),
+        title: "CPU",
+        text: "Model, cores and load, plus a warning when the processor is held back by heat or power limits.",
+    },
+    TourStep {
+        target: Some(TourTarget::Card(DashboardCard::Memory)),
+        title: "Memory",
+        text: "How much RAM is in use and how much is left.",
+    },
+    TourStep {
+        target: Some(TourTarget::Card(DashboardCard::Storage)),
+        title: "Storage",
+        text: "Free space per drive, how busy each one is, and an estimate of when it will fill up.",
+    },
+    TourStep {
+        target: Some(TourTarget::Card(DashboardCard::Network)),
+        title: "Network",
+        text: "Traffic per adapter with session and daily totals. The Events button lists connects, drops and Wi-Fi roams.",
+    },
+    TourStep {
+        target: Some(TourTarget::Card(DashboardCard::Gpu)),
+        title: "GPU",
+        text: "Graphics card load, memory, temperature and driver. Details opens graphs of the last ten minutes.",
+    },
+    TourStep {
+        target: Some(TourTarget::ToolsTab),
+        title: "Tools",
+        text: "The Installer downloads a Ninite installer for the apps you tick and runs it when you press Install Selected Apps; \
+               Windows asks for permission first. It only adds apps that are missing: it never uninstalls, updates \
+               or reconfigures anything, and nothing is installed without you starting it. The other pages are local \
+               utilities such as a benchmark, screenshots, a color picker, converters and a text diff.",
+    },
+    TourStep {
+        target: Some(TourTarget::ProcessesTab),
+        title: "Processes",
+        text: "Running programs with their code signatures, flagging unsigned binaries in temp folders, plus the \
+               watchdog, process watcher and job watcher events.",
+    },
+    TourStep {
+        target: Some(TourTarget::SecurityTab),
+        title: "Security",
+        text: "An audit of ports listening on your network, with the program behind each one and whether it is signed. \
+               It only reports; nothing is closed or blocked.",
+    },
+    TourStep {
+        target: Some(TourTarget::ContainersTab),
+        title: "Containers",
+        text: "Containers from a local Docker or Podman engine with their CPU and memory use, and buttons to start, \
+               stop or restart them.",
+    },
+    TourStep {
+        target: Some(TourTarget::ServicesTab),
+        title: "Services",
+        text: "Windows services with their state and startup type. Search for one such as SQL Server and start, stop \
+               or restart it without opening services.msc.",
+    },
+    TourStep {
+        target: Some(TourTarget::Settings),
+        title: "Settings",
+        text: "Turn on optional cards, alerts, the tray icon and the theme, and take this tour again.",
+    },
+];
+
+/// Progress through the tour and where its targets were drawn this frame
+#[derive(Default)]
+pub struct Tour {
+    step: Option<usize>,
+    targets: Vec<(TourTarget, Rect)>,
+    scroll_pending: bool,
+}
+
+impl Tour {
+    /// A tour that opens at the first step
+    pub fn new(open: bool) -> Self {
+        Self { step: open.then_some(0), ..Self::default() }
+    }
+
+    /// Records where a target was drawn, scrolling to it when its step has just started
+    pub(crate) fn mark(&mut self, target: TourTarget, response: &egui::Response) {
+        let Some(step) = self.step else { return };
+        if self.scroll_pending && STEPS[step].target == Some(target) {
+            response.scroll_to_me(None);
+            self.scroll_pending = false;
+        }
+        self.targets.push((target, response.rect));
+    }
+
+    fn go_to(&mut self, step: usize) {
+        self.step = Some(step);
+        self.scroll_pending = true;
+    }
+}
+
+impl DevDashboard {
+    /// Starts the tour from the first step
+    pub(crate) fn start_tour(&mut self) {
+        info!("Starting the dashboard tour");
+        self.current_tab = Tab::Dashboard;
+        self.tour.go_to(0);
+    }
+
+    fn close_tour(&mut self) {
+        self.tour.step = None;
+        if !self.settings.tour.seen {
+            self.settings.tour.seen = true;
+            self.save_settings();
+        }
+    }
+
+    /// Dims the window around the current step's target and explains it
+    pub(crate) fn show_tour(&mut self, ctx: &egui::Context) {
+        let targets = std::mem::take(&mut self.tour.targets);
+        let Some(index) = self.tour.step else { return };
+        let step = &STEPS[index];
+
+        // Cards live on the Dashboard tab; the tab buttons are visible from any tab
+        if matches!(step.target, Some(TourTarget::Card(_))) && self.current_tab != Tab::Dashboard {
+            self.current_tab = Tab::Dashboard;
+        }
+        let highlight = step.target.and_then(|target| targets.iter().find(|(t, _)| *t == target).map(|(_, rect)| *rect));
+
+        let screen = ctx.screen_rect();
+        let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("tour_dim")));
+        let dim = Color32::from_black_alpha(150);
+        match highlight {
+            Some(rect) => {
+                let rect = rect.expand(4.0);
+                painter.rect_filled(Rect::from_min_max(screen.min, egui::pos2(screen.max.x, rect.min.y)), 0.0, dim);
+                painter.rect_filled(Rect::from_min_max(egui::pos2(screen.min.x, rect.max.y), screen.max), 0.0, dim);
+                painter.rect_filled(Rect::from_min_max(egui::pos2(screen.min.x, rect.min.y), egui::pos2(rect.min.x, rect.max.y)), 0.0, dim);
+                painter.rect_filled(Rect::from_min_max(egui::pos2(rect.max.x, rect.min.y), egui::pos2(screen.max.x, rect.max.y)), 0.0, dim);
+                painter.rect_stroke(rect, 8.0, egui::Stroke::new(2.0, Color32::from_rgb(88, 165, 237)));
+            }
+            None => painter.rect_filled(screen, 0.0, dim),
+        }
+
+        // Explanation below the target, or above it when there is no room underneath
+        let width = 340.0;
+        let position = match highlight {
+            Some(rect) if rect.max.y + 200.0 < screen.max.y => egui::pos2(rect.min.x, rect.max.y + 12.0),
+            Some(rect) => egui::pos2(rect.min.x, (rect.min.y - 212.0).max(screen.min.y)),
+            None => screen.center() - egui::vec2(width / 2.0, 80.0),
+        };
+        let position = egui::pos2(position.x.clamp(screen.min.x + 8.0, (screen.max.x - width - 24.0).max(screen.min.x)), position.y);
+
+        let mut next = None;
+        let mut close = ctx.input(|input| input.key_pressed(egui::Key::Escape));
+        egui::Area::new("tour_step")
+            .order(egui::Order::Tooltip)
+            .fixed_pos(position)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).inner_margin(egui::style::Margin::same(12.0)).show(ui, |ui| {
+                    ui.set_width(width);
+                    ui.label(RichText::new(step.title).strong().size(16.0));
+                    ui.add_space(4.0);
+                    ui.label(step.text);
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(format!("{} of {}", index + 1, STEPS.len())).color(Color32::from_rgb(107, 114, 128)));
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if index + 1 < STEPS.len() {
+                                if ui.button("Next").clicked() {
+                                    next = Some(index + 1);
+                                }
+                            } else if ui.button("Finish").clicked() {
+                                close = true;
+                            }
+                            if index > 0 && ui.button("Back").clicked() {
+                                next = Some(index - 1);
+                            }
+                            if index + 1 < STEPS.len() && ui.button("Skip tour").clicked() {
+                                close = true;
+                            }
+                        });
+                    });
+                });
+            });
+
+        if close {
+            self.close_tour();
+        } else if let Some(step) = next {
+            self.tour.go_to(step);
+        }
+    }
+
+    /// Settings section to take the tour again
+    pub(crate) fn show_tour_settings(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button("Take the tour").clicked() {
+                self.show_settings = false;
+                self.start_tour();
+            }
+            ui.label(RichText::new("A walk through the cards and tabs").color(Color32::from_rgb(107, 114, 128)));
+        });
+    }
+}
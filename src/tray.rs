@@ -0,0 +1,389 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicIsize, Ordering};
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::Arc;
+use chrono::{DateTime, Local};
+use eframe::egui;
+use egui::{Color32, RichText};
+use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
+use serde::{Serialize, Deserialize};
+use sysinfo::SystemExt;
+use tray_icon::menu::{CheckMenuItem, Menu, MenuEvent, MenuId, MenuItem, PredefinedMenuItem};
+use tray_icon::{ClickType, Icon, TrayIcon, TrayIconBuilder, TrayIconEvent};
+use windows::Win32::Foundation::{HWND, LPARAM, WPARAM};
+use windows::Win32::System::Com::{CoCreateInstance, CLSCTX_INPROC_SERVER};
+use windows::Win32::UI::Shell::{ITaskbarList, TaskbarList};
+use windows::Win32::UI::WindowsAndMessaging::{IsIconic, PostMessageW, SetForegroundWindow, ShowWindow, SW_RESTORE, WM_CLOSE};
+use log::{info, warn};
+use crate::DevDashboard;
+
+/// Samples kept for the flyout graphs; one per update tick, so one minute
+const HISTORY_LEN: usize = 60;
+const FLYOUT_SIZE: egui::Vec2 = egui::vec2(300.0, 250.0);
+/// Resource ordinal of the icon that build.rs embeds
+const ICON_RESOURCE: u16 = 1;
+
+/// Persisted tray icon configuration
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct TraySettings {
+    pub enabled: bool,
+    pub minimize_to_tray: bool,     // Minimizing removes the taskbar button until the window is restored
+}
+
+/// The last minute of the headline metrics, oldest first
+#[derive(Default)]
+pub struct MetricHistory {
+    pub(crate) cpu: VecDeque<f32>,          // Percent
+    pub(crate) memory: VecDeque<f32>,       // Percent
+    pub(crate) gpu: VecDeque<f32>,          // Percent; empty without a GPU reading
+    pub(crate) received: VecDeque<f32>,     // Bytes per second, all interfaces
+    pub(crate) sent: VecDeque<f32>,
+    pub(crate) times: VecDeque<DateTime<Local>>,    // When each CPU, memory and network sample was taken
+}
+
+fn push(values: &mut VecDeque<f32>, value: f32) {
+    if values.len() == HISTORY_LEN {
+        values.pop_front();
+    }
+    values.push_back(value);
+}
+
+/// Items of the tray icon's right-click menu
+struct TrayMenu {
+    show: MenuId,
+    pause: CheckMenuItem,
+    quit: MenuId,
+}
+
+/// Tray icon, its menu and the quick-look flyout it opens
+#[derive(Default)]
+pub struct Tray {
+    icon: Option<TrayIcon>,
+    events: Option<Receiver<TrayIconEvent>>,
+    menu: Option<TrayMenu>,
+    flyout_open: bool,
+    flyout_position: egui::Pos2,
+    flyout_focused: bool,       // Set once the flyout had focus, so losing it closes the flyout
+    tooltip: String,            // Last text given to the icon, so it is only replaced when it changes
+    window: Arc<AtomicIsize>,   // Main window handle, for the menu and click handlers
+    hidden: Arc<AtomicBool>,    // Minimized with the taskbar button removed
+    paused: Arc<AtomicBool>,    // Monitoring paused from the tray menu
+    pub history: MetricHistory,
+}
+
+/// Brings the main window back from the tray, or from a normal minimize
+fn restore_window(window: &AtomicIsize) {
+    let hwnd = HWND(window.load(Ordering::Relaxed));
+    if hwnd.0 != 0 {
+        unsafe {
+            ShowWindow(hwnd, SW_RESTORE);
+            SetForegroundWindow(hwnd);
+        }
+    }
+}
+
+/// Adds or removes the main window's taskbar button
+fn set_taskbar_button(hwnd: HWND, visible: bool) -> windows::core::Result<()> {
+    unsafe {
+        let taskbar: ITaskbarList = CoCreateInstance(&TaskbarList, None, CLSCTX_INPROC_SERVER)?;
+        taskbar.HrInit()?;
+        if visible { taskbar.AddTab(hwnd) } else { taskbar.DeleteTab(hwnd) }
+    }
+}
+
+/// Compact filled line graph with a caption and current value
+fn sparkline(ui: &mut egui::Ui, label: &str, values: &VecDeque<f32>, max: Option<f32>, value: String, color: Color32) {
+    ui.horizontal(|ui| {
+        ui.label(RichText::new(label).color(Color32::from_rgb(156, 163, 175)));
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            ui.label(RichText::new(value).strong());
+        });
+    });
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width(), 26.0), egui::Sense::hover());
+    let painter = ui.painter();
+    painter.rect_filled(rect, 3.0, Color32::from_rgb(31, 41, 55));
+    if values.len() < 2 {
+        return;
+    }
+    // Network graphs scale to their own peak, percentages to 100
+    let max = max.unwrap_or_else(|| values.iter().copied().fold(1.0, f32::max));
+    let step = rect.width() / (HISTORY_LEN - 1) as f32;
+    let offset = HISTORY_LEN - values.len();
+    let points: Vec<egui::Pos2> = values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| egui::pos2(
+            rect.left() + (offset + index) as f32 * step,
+            rect.bottom() - (value / max).clamp(0.0, 1.0) * (rect.height() - 2.0),
+        ))
+        .collect();
+    let mut fill = points.clone();
+    fill.push(egui::pos2(points[points.len() - 1].x, rect.bottom()));
+    fill.push(egui::pos2(points[0].x, rect.bottom()));
+    painter.add(egui::Shape::convex_polygon(fill, color.gamma_multiply(0.25), egui::Stroke::NONE));
+    painter.add(egui::Shape::line(points, egui::Stroke::new(1.5, color)));
+}
+
+impl DevDashboard {
+    pub(crate) fn speed_text(bytes_per_second: f32) -> String {
+        let (value, unit) = DevDashboard::format_bytes(bytes_per_second as u64);
+        format!("{:.1} {}/s", value, unit)
+    }
+
+    /// Appends the current readings to the flyout graphs
+    pub(crate) fn record_tray_sample(&mut self) {
+        let total_memory = self.sys.total_memory() as f32;
+        let memory = if total_memory > 0.0 {
+            (total_memory - self.sys.available_memory() as f32) / total_memory * 100.0
+        } else {
+            0.0
+        };
+        let received: f64 = self.network_stats.values().map(|stats| stats.received_speed).sum();
+        let sent: f64 = self.network_stats.values().map(|stats| stats.sent_speed).sum();
+
+        let history = &mut self.tray.history;
+        push(&mut history.cpu, self.current_cpu_usage.target);
+        push(&mut history.memory, memory);
+        if let Some(utilization) = self.gpu_info.as_ref().and_then(|gpu| gpu.utilization) {
+            push(&mut history.gpu, utilization);
+        }
+        push(&mut history.received, received as f32);
+        push(&mut history.sent, sent as f32);
+        if history.times.len() == HISTORY_LEN {
+            history.times.pop_front();
+        }
+        history.times.push_back(Local::now());
+    }
+
+    /// Restores and focuses the main window, from the tray or a normal minimize
+    pub(crate) fn restore_from_tray(&self) {
+        restore_window(&self.tray.window);
+    }
+
+    /// Whether monitoring was paused from the tray menu
+    pub(crate) fn monitoring_paused(&self) -> bool {
+        self.tray.paused.load(Ordering::Relaxed)
+    }
+
+    /// Sends a minimized window to the tray, and gives it its taskbar button back once restored
+    fn update_minimize_to_tray(&mut self) {
+        let hwnd = HWND(self.tray.window.load(Ordering::Relaxed));
+        let minimized = hwnd.0 != 0 && unsafe { IsIconic(hwnd).as_bool() };
+        let hidden = self.tray.hidden.load(Ordering::Relaxed);
+        let hide = minimized && self.settings.tray.enabled && self.settings.tray.minimize_to_tray;
+        if hide == hidden {
+            return;
+        }
+        // Not retried every frame when it fails; the window still minimizes and restores normally
+        if let Err(e) = set_taskbar_button(hwnd, !hide) {
+            warn!("Could not change the taskbar button: {}", e);
+        }
+        self.tray.hidden.store(hide, Ordering::Relaxed);
+    }
+
+    /// Live CPU and memory in the icon's hover text
+    fn update_tray_tooltip(&mut self) {
+        let history = &self.tray.history;
+        let text = if self.monitoring_paused() {
+            "Dev Dashboard\nMonitoring paused".to_string()
+        } else if let (Some(cpu), Some(memory)) = (history.cpu.back(), history.memory.back()) {
+            format!("Dev Dashboard\nCPU {:.0}% · RAM {:.0}%", cpu, memory)
+        } else {
+            "Dev Dashboard".to_string()
+        };
+        if text == self.tray.tooltip {
+            return;
+        }
+        if let Some(icon) = &self.tray.icon {
+            if let Err(e) = icon.set_tooltip(Some(&text)) {
+                warn!("Could not update the tray tooltip: {}", e);
+            }
+        }
+        self.tray.tooltip = text;
+    }
+
+    /// Creates or removes the tray icon to match the settings and handles its clicks
+    pub(crate) fn update_tray(&mut self, ctx: &egui::Context, frame: &eframe::Frame) {
+        if let RawWindowHandle::Win32(handle) = frame.raw_window_handle() {
+            self.tray.window.store(handle.hwnd as isize, Ordering::Relaxed);
+        }
+        self.update_minimize_to_tray();
+        if !self.settings.tray.enabled {
+            if self.tray.icon.take().is_some() {
+                info!("Removed tray icon");
+                self.tray.menu = None;
+                // Nothing is left to resume from once the menu is gone
+                self.tray.paused.store(false, Ordering::Relaxed);
+            }
+            self.tray.flyout_open = false;
+            return;
+        }
+
+        if self.tray.icon.is_none() {
+            let (sender, receiver) = channel();
+            let repaint = ctx.clone();
+            let window = self.tray.window.clone();
+            let hidden = self.tray.hidden.clone();
+            // Clicks arrive on the window thread; waking egui makes them show up while the window is idle
+            TrayIconEvent::set_event_handler(Some(move |event: TrayIconEvent| {
+                // A window in the tray comes back on click instead of opening the flyout over nothing
+                if event.click_type == ClickType::Double || (event.click_type == ClickType::Left && hidden.load(Ordering::Relaxed)) {
+                    restore_window(&window);
+                } else {
+                    sender.send(event).ok();
+                }
+                repaint.request_repaint();
+            }));
+
+            let show = MenuItem::new("Show dashboard", true, None);
+            let pause = CheckMenuItem::new("Pause monitoring", true, self.tray.paused.load(Ordering::Relaxed), None);
+            let quit = MenuItem::new("Quit", true, None);
+            let menu = TrayMenu { show: show.id().clone(), pause: pause.clone(), quit: quit.id().clone() };
+            let (show_id, pause_id, quit_id) = (menu.show.clone(), pause.id().clone(), menu.quit.clone());
+            let window = self.tray.window.clone();
+            let paused = self.tray.paused.clone();
+            let repaint = ctx.clone();
+            // Handled here rather than in update, so Show and Quit work while the window is in the tray
+            MenuEvent::set_event_handler(Some(move |event: MenuEvent| {
+                if event.id == show_id {
+                    restore_window(&window);
+                } else if event.id == pause_id {
+                    paused.fetch_xor(true, Ordering::Relaxed);
+                } else if event.id == quit_id {
+                    // Closing through the window lets the dashboard save its state on the way out
+                    unsafe { PostMessageW(HWND(window.load(Ordering::Relaxed)), WM_CLOSE, WPARAM(0), LPARAM(0)) };
+                }
+                repaint.request_repaint();
+            }));
+
+            let mut builder = TrayIconBuilder::new()
+                .with_tooltip("Dev Dashboard")
+                .with_menu_on_left_click(false);
+            match Menu::with_items(&[&show, &pause, &PredefinedMenuItem::separator(), &quit]) {
+                Ok(tray_menu) => builder = builder.with_menu(Box::new(tray_menu)),
+                Err(e) => warn!("Could not create tray menu: {}", e),
+            }
+            match Icon::from_resource(ICON_RESOURCE, Some((32, 32))) {
+                Ok(icon) => builder = builder.with_icon(icon),
+                Err(e) => warn!("Could not load tray icon image: {}", e),
+            }
+            match builder.build() {
+                Ok(icon) => {
+                    info!("Created tray icon");
+                    self.tray.icon = Some(icon);
+                    self.tray.events = Some(receiver);
+                    self.tray.menu = Some(menu);
+                    self.tray.tooltip.clear();
+                }
+                Err(e) => {
+                    warn!("Could not create tray icon: {}", e);
+                    self.settings.tray.enabled = false;
+                    return;
+                }
+            }
+        }
+
+        if let Some(menu) = &self.tray.menu {
+            let paused = self.monitoring_paused();
+            if menu.pause.is_checked() != paused {
+                menu.pause.set_checked(paused);
+            }
+        }
+        self.update_tray_tooltip();
+
+        let Some(events) = &self.tray.events else { return };
+        while let Ok(event) = events.try_recv() {
+            if event.click_type != ClickType::Left {
+                continue;
+            }
+            self.tray.flyout_open = !self.tray.flyout_open;
+            self.tray.flyout_focused = false;
+            // The click is in physical pixels; open the flyout above and left of it, like the clock flyout
+            let scale = ctx.input(|i| i.viewport().native_pixels_per_point).unwrap_or(1.0);
+            self.tray.flyout_position = egui::pos2(
+                (event.x as f32 / scale - FLYOUT_SIZE.x).max(0.0),
+                (event.y as f32 / scale - FLYOUT_SIZE.y - 16.0).max(0.0),
+            );
+        }
+    }
+
+    /// The last minute of CPU, memory, GPU and network use, for the flyout and the compact overlay
+    pub(crate) fn show_metric_sparklines(&self, ui: &mut egui::Ui) {
+        let history = &self.tray.history;
+        sparkline(ui, "CPU", &history.cpu, Some(100.0),
+            format!("{:.0}%", history.cpu.back().copied().unwrap_or_default()), Color32::from_rgb(37, 99, 235));
+        sparkline(ui, "Memory", &history.memory, Some(100.0),
+            format!("{:.0}%", history.memory.back().copied().unwrap_or_default()), Color32::from_rgb(22, 163, 74));
+        if !history.gpu.is_empty() {
+            sparkline(ui, "GPU", &history.gpu, Some(100.0),
+                format!("{:.0}%", history.gpu.back().copied().unwrap_or_default()), Color32::from_rgb(220, 38, 38));
+        }
+        let received = history.received.back().copied().unwrap_or_default();
+        let sent = history.sent.back().copied().unwrap_or_default();
+        sparkline(ui, "Network ↓", &history.received, None, Self::speed_text(received), Color32::from_rgb(88, 165, 237));
+        sparkline(ui, "Network ↑", &history.sent, None, Self::speed_text(sent), Color32::from_rgb(202, 138, 4));
+    }
+
+    /// Quick-look window with CPU, memory, GPU and network graphs
+    pub(crate) fn show_tray_flyout(&mut self, ctx: &egui::Context) {
+        if !self.tray.flyout_open {
+            return;
+        }
+        let builder = egui::ViewportBuilder::default()
+            .with_title("Dev Dashboard")
+            .with_decorations(false)
+            .with_resizable(false)
+            .with_always_on_top()
+            .with_inner_size(FLYOUT_SIZE)
+            .with_position(self.tray.flyout_position);
+
+        let mut close = false;
+        ctx.show_viewport_immediate(egui::ViewportId::from_hash_of("tray_flyout"), builder, |ctx, _class| {
+            egui::CentralPanel::default()
+                .frame(egui::Frame::none()
+                    .fill(self.palette().panel)
+                    .inner_margin(egui::style::Margin::same(10.0)))
+                .show(ctx, |ui| self.show_metric_sparklines(ui));
+
+            let (focused, escape) = ctx.input(|i| (i.focused, i.key_pressed(egui::Key::Escape)));
+            if focused {
+                self.tray.flyout_focused = true;
+            }
+            // Clicking anywhere else dismisses the flyout, like the other tray popups
+            if escape || ctx.input(|i| i.viewport().close_requested()) || (self.tray.flyout_focused && !focused) {
+                close = true;
+            }
+        });
+        if close {
+            self.tray.flyout_open = false;
+        }
+    }
+
+    /// Header button shown while monitoring is paused from the tray menu
+    pub(crate) fn show_monitoring_paused(&mut self, ui: &mut egui::Ui) {
+        if !self.monitoring_paused() {
+            return;
+        }
+        if ui.button(RichText::new("⏸ Paused").color(Color32::from_rgb(202, 138, 4)))
+            .on_hover_text("Readings are paused from the tray menu; click to resume")
+            .clicked() {
+            self.tray.paused.store(false, Ordering::Relaxed);
+        }
+    }
+
+    /// Settings section for the tray icon
+    pub(crate) fn show_tray_settings(&mut self, ui: &mut egui::Ui) {
+        ui.label("System Tray:");
+        let settings = &mut self.settings.tray;
+        let mut changed = ui.checkbox(&mut settings.enabled, "Show a tray icon; click it for CPU, memory, GPU and network graphs")
+            .on_hover_text("Hover it for CPU and memory use; right-click it to show the dashboard, pause monitoring or quit")
+            .changed();
+        ui.add_enabled_ui(settings.enabled, |ui| {
+            changed |= ui.checkbox(&mut settings.minimize_to_tray, "Minimize to the tray instead of the taskbar").changed();
+        });
+        if changed {
+            self.save_settings();
+        }
+    }
+}
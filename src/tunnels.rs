@@ -0,0 +1,587 @@
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader};
+use std::os::windows::process::CommandExt;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use log::{info, warn};
+use crate::elevation::ElevatedAction;
+use crate::guard::{ElevatedRequest, SensitiveAction};
+use crate::ports::{self, SocketProtocol};
+use crate::{DevDashboard, CREATE_NO_WINDOW};
+
+/// How often the port proxy rules are read back from netsh
+const RULE_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+/// Restarts of a dropped tunnel back off up to this delay
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(60);
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum TunnelKind {
+    Ssh,        // ssh -L, running for as long as the dashboard does
+    PortProxy,  // netsh portproxy rule, kept by Windows across reboots
+}
+
+impl TunnelKind {
+    fn label(&self) -> &'static str {
+        match self {
+            TunnelKind::Ssh => "SSH",
+            TunnelKind::PortProxy => "Port proxy",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct TunnelConfig {
+    pub name: String,
+    pub kind: TunnelKind,
+    pub bind_address: String,   // Local address to listen on; 0.0.0.0 makes the port reachable from the LAN
+    pub local_port: u16,
+    pub remote_host: String,    // Where connections go; resolved on the SSH server for SSH tunnels
+    pub remote_port: u16,
+    pub ssh_host: String,       // user@server or a Host alias from ~/.ssh/config
+    pub auto_start: bool,       // Start SSH tunnels with the dashboard
+}
+
+impl Default for TunnelConfig {
+    fn default() -> Self {
+        Self {
+            name: "Tunnel".to_string(),
+            kind: TunnelKind::Ssh,
+            bind_address: "127.0.0.1".to_string(),
+            local_port: 5432,
+            remote_host: "localhost".to_string(),
+            remote_port: 5432,
+            ssh_host: String::new(),
+            auto_start: false,
+        }
+    }
+}
+
+impl TunnelConfig {
+    fn describe(&self) -> String {
+        let route = format!("{}:{} → {}:{}", self.bind_address, self.local_port, self.remote_host, self.remote_port);
+        match self.kind {
+            TunnelKind::Ssh => format!("{} via {}", route, self.ssh_host),
+            TunnelKind::PortProxy => route,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct TunnelSettings {
+    pub tunnels: Vec<TunnelConfig>,
+    pub auto_restart: bool,     // Restart SSH tunnels whose connection dropped
+}
+
+impl Default for TunnelSettings {
+    fn default() -> Self {
+        Self {
+            tunnels: Vec::new(),
+            auto_restart: true,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+enum TunnelStatus {
+    Stopped,
+    Connecting,
+    Up,
+    Dropped(String),                    // Last error; restarted after a delay when auto restart is on
+    NotListening,                       // Port proxy rule exists but nothing listens, e.g. IP Helper is stopped
+    Busy,                               // Waiting for netsh
+    Failed(String),
+}
+
+impl TunnelStatus {
+    fn label(&self) -> (String, Color32) {
+        match self {
+            TunnelStatus::Stopped => ("Stopped".to_string(), Color32::from_rgb(156, 163, 175)),
+            TunnelStatus::Connecting => ("Connecting...".to_string(), Color32::from_rgb(202, 138, 4)),
+            TunnelStatus::Up => ("✔ Up".to_string(), Color32::from_rgb(22, 163, 74)),
+            TunnelStatus::Dropped(error) => (format!("Dropped: {}", error), Color32::from_rgb(220, 50, 50)),
+            TunnelStatus::NotListening => ("Not listening".to_string(), Color32::from_rgb(202, 138, 4)),
+            TunnelStatus::Busy => ("Updating...".to_string(), Color32::from_rgb(88, 165, 237)),
+            TunnelStatus::Failed(error) => (format!("✖ {}", error), Color32::from_rgb(220, 50, 50)),
+        }
+    }
+}
+
+/// A running ssh process and the last line it printed to stderr
+struct SshProcess {
+    child: Child,
+    last_error: Arc<Mutex<String>>,
+}
+
+impl SshProcess {
+    fn start(tunnel: &TunnelConfig) -> Result<Self, String> {
+        let forward = format!("{}:{}:{}:{}", tunnel.bind_address, tunnel.local_port, tunnel.remote_host, tunnel.remote_port);
+        let mut child = Command::new("ssh")
+            .args(["-N", "-L", &forward])
+            // Fail instead of running without the forward, notice dead connections, and never prompt,
+            // since there is no console to type a password into
+            .args(["-o", "ExitOnForwardFailure=yes", "-o", "ServerAliveInterval=15", "-o", "ServerAliveCountMax=3"])
+            .args(["-o", "BatchMode=yes", &tunnel.ssh_host])
+            .creation_flags(CREATE_NO_WINDOW)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Could not start ssh: {}", e))?;
+
+        let last_error = Arc::new(Mutex::new(String::new()));
+        if let Some(stderr) = child.stderr.take() {
+            let last_error = last_error.clone();
+            std::thread::spawn(move || {
+                for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                    let line = line.trim();
+                    if !line.is_empty() {
+                        if let Ok(mut last) = last_error.lock() {
+                            *last = line.to_string();
+                        }
+                    }
+                }
+            });
+        }
+        Ok(Self { child, last_error })
+    }
+
+    /// The exit reason once ssh has exited, None while it runs
+    fn exited(&mut self) -> Option<String> {
+        let status = match self.child.try_wait() {
+            Ok(Some(status)) => status,
+            Ok(None) => return None,
+            Err(e) => return Some(e.to_string()),
+        };
+        let error = self.last_error.lock().map(|last| last.clone()).unwrap_or_default();
+        Some(if error.is_empty() { format!("ssh exited with code {}", status.code().unwrap_or(-1)) } else { error })
+    }
+
+    fn stop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Runtime state of one tunnel
+#[derive(Default)]
+struct TunnelRun {
+    ssh: Option<SshProcess>,
+    status: Option<TunnelStatus>,       // None until the first check
+    wanted: bool,                       // Started by the user or on launch, so a drop is restarted
+    failures: u32,                      // Drops in a row, for the restart delay
+    restart_at: Option<Instant>,
+}
+
+/// A port proxy rule as listed by `netsh interface portproxy show v4tov4`
+#[derive(Clone, PartialEq)]
+struct ProxyRule {
+    listen_address: String,
+    listen_port: u16,
+    connect_address: String,
+    connect_port: u16,
+}
+
+/// SSH processes, port proxy rules and their status, in settings order
+#[derive(Default)]
+pub struct TunnelManager {
+    runs: Vec<TunnelRun>,
+    started: bool,                      // Auto start tunnels have been started
+    rules: Option<Vec<ProxyRule>>,
+    rules_checked: Option<Instant>,
+    rule_receiver: Option<Receiver<Result<Vec<ProxyRule>, String>>>,
+    action_sender: Option<Sender<(usize, Result<(), String>)>>,
+    action_receiver: Option<Receiver<(usize, Result<(), String>)>>,
+    busy: HashSet<usize>,               // Tunnels waiting for an elevated netsh
+    errors: Vec<(usize, String)>,       // Failed netsh actions, shown until the next one
+}
+
+impl Drop for TunnelManager {
+    fn drop(&mut self) {
+        // ssh would otherwise keep the forwards open after the dashboard exits
+        for run in &mut self.runs {
+            if let Some(ssh) = &mut run.ssh {
+                ssh.stop();
+            }
+        }
+    }
+}
+
+/// Reads the IPv4 port proxy rules; data rows are the same in every language, unlike the headings
+fn read_proxy_rules() -> Result<Vec<ProxyRule>, String> {
+    let output = Command::new("netsh")
+        .args(["interface", "portproxy", "show", "v4tov4"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| format!("Could not run netsh: {}", e))?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [listen_address, listen_port, connect_address, connect_port] = fields[..] else { return None };
+            Some(ProxyRule {
+                listen_address: listen_address.to_string(),
+                listen_port: listen_port.parse().ok()?,
+                connect_address: connect_address.to_string(),
+                connect_port: connect_port.parse().ok()?,
+            })
+        })
+        .collect())
+}
+
+impl TunnelManager {
+    fn rule_for(&self, tunnel: &TunnelConfig) -> Option<&ProxyRule> {
+        self.rules.as_ref()?.iter().find(|rule| rule.listen_port == tunnel.local_port && rule.listen_address == tunnel.bind_address)
+    }
+
+    fn start_ssh(run: &mut TunnelRun, tunnel: &TunnelConfig) {
+        run.restart_at = None;
+        match SshProcess::start(tunnel) {
+            Ok(ssh) => {
+                info!("Started tunnel \"{}\": {}", tunnel.name, tunnel.describe());
+                run.ssh = Some(ssh);
+                run.status = Some(TunnelStatus::Connecting);
+            }
+            Err(e) => {
+                warn!("Tunnel \"{}\": {}", tunnel.name, e);
+                run.wanted = false;
+                run.status = Some(TunnelStatus::Failed(e));
+            }
+        }
+    }
+
+    /// Restarts dropped SSH tunnels, reads back port proxy rules and works out each tunnel's status
+    pub fn update(&mut self, settings: &TunnelSettings) {
+        if settings.tunnels.is_empty() {
+            return;
+        }
+        self.runs.resize_with(settings.tunnels.len(), TunnelRun::default);
+        if !self.started {
+            self.started = true;
+            for (run, tunnel) in self.runs.iter_mut().zip(&settings.tunnels) {
+                if tunnel.kind == TunnelKind::Ssh && tunnel.auto_start {
+                    run.wanted = true;
+                    Self::start_ssh(run, tunnel);
+                }
+            }
+        }
+
+        if let Some(receiver) = &self.action_receiver {
+            while let Ok((index, result)) = receiver.try_recv() {
+                self.busy.remove(&index);
+                self.errors.retain(|(tunnel, _)| *tunnel != index);
+                if let Err(e) = result {
+                    self.errors.push((index, e));
+                }
+                // Read the rules back right away rather than waiting for the next check
+                self.rules_checked = None;
+            }
+        }
+        if let Some(rules) = self.rule_receiver.as_ref().and_then(|receiver| receiver.try_recv().ok()) {
+            self.rule_receiver = None;
+            match rules {
+                Ok(rules) => self.rules = Some(rules),
+                Err(e) => warn!("Could not read port proxy rules: {}", e),
+            }
+        }
+        let has_proxy = settings.tunnels.iter().any(|tunnel| tunnel.kind == TunnelKind::PortProxy);
+        let rules_due = match self.rules_checked {
+            Some(checked) => checked.elapsed() >= RULE_CHECK_INTERVAL,
+            None => true,
+        };
+        if has_proxy && rules_due && self.rule_receiver.is_none() {
+            self.rules_checked = Some(Instant::now());
+            let (sender, receiver) = channel();
+            self.rule_receiver = Some(receiver);
+            std::thread::spawn(move || {
+                sender.send(read_proxy_rules()).ok();
+            });
+        }
+
+        let listening: Vec<(u16, u32)> = ports::listening_sockets()
+            .into_iter()
+            .filter(|socket| socket.protocol == SocketProtocol::Tcp)
+            .map(|socket| (socket.local_port, socket.pid))
+            .collect();
+
+        for (index, tunnel) in settings.tunnels.iter().enumerate() {
+            let status = match tunnel.kind {
+                TunnelKind::Ssh => {
+                    let run = &mut self.runs[index];
+                    if let Some(error) = run.ssh.as_mut().and_then(SshProcess::exited) {
+                        run.ssh = None;
+                        if run.wanted {
+                            warn!("Tunnel \"{}\" dropped: {}", tunnel.name, error);
+                            run.status = Some(TunnelStatus::Dropped(error));
+                            if settings.auto_restart {
+                                let delay = Duration::from_secs(5 << run.failures.min(4)).min(MAX_RESTART_DELAY);
+                                run.restart_at = Some(Instant::now() + delay);
+                                run.failures += 1;
+                            }
+                        }
+                    }
+                    if run.restart_at.is_some_and(|at| Instant::now() >= at) {
+                        Self::start_ssh(run, tunnel);
+                    }
+                    match &run.ssh {
+                        Some(ssh) if listening.contains(&(tunnel.local_port, ssh.child.id())) => {
+                            run.failures = 0;
+                            TunnelStatus::Up
+                        }
+                        Some(_) => TunnelStatus::Connecting,
+                        None => match &run.status {
+                            Some(status @ (TunnelStatus::Dropped(_) | TunnelStatus::Failed(_))) => status.clone(),
+                            _ => TunnelStatus::Stopped,
+                        },
+                    }
+                }
+                TunnelKind::PortProxy => {
+                    if self.busy.contains(&index) || self.rules.is_none() {
+                        TunnelStatus::Busy
+                    } else if let Some((_, error)) = self.errors.iter().find(|(tunnel, _)| *tunnel == index) {
+                        TunnelStatus::Failed(error.clone())
+                    } else {
+                        match self.rule_for(tunnel) {
+                            None => TunnelStatus::Stopped,
+                            Some(rule) if rule.connect_address != tunnel.remote_host || rule.connect_port != tunnel.remote_port => {
+                                TunnelStatus::Failed(format!("Rule points to {}:{}", rule.connect_address, rule.connect_port))
+                            }
+                            Some(_) if listening.iter().any(|(port, _)| *port == tunnel.local_port) => TunnelStatus::Up,
+                            Some(_) => TunnelStatus::NotListening,
+                        }
+                    }
+                }
+            };
+            self.runs[index].status = Some(status);
+        }
+    }
+
+    fn is_running(&self, index: usize, tunnel: &TunnelConfig) -> bool {
+        match tunnel.kind {
+            TunnelKind::Ssh => self.runs.get(index).is_some_and(|run| run.ssh.is_some() || run.restart_at.is_some()),
+            TunnelKind::PortProxy => self.rule_for(tunnel).is_some(),
+        }
+    }
+
+    /// Stops and forgets a tunnel removed in Settings
+    fn remove(&mut self, index: usize) {
+        if index < self.runs.len() {
+            let mut run = self.runs.remove(index);
+            if let Some(ssh) = &mut run.ssh {
+                ssh.stop();
+            }
+        }
+        // Pending netsh results refer to tunnels by index
+        self.busy.clear();
+        self.errors.clear();
+    }
+}
+
+impl DevDashboard {
+    pub(crate) fn start_tunnel(&mut self, index: usize) {
+        let Some(tunnel) = self.settings.tunnels.tunnels.get(index).cloned() else { return };
+        let manager = &mut self.tunnel_manager;
+        match tunnel.kind {
+            TunnelKind::Ssh => {
+                let Some(run) = manager.runs.get_mut(index) else { return };
+                run.wanted = true;
+                run.failures = 0;
+                TunnelManager::start_ssh(run, &tunnel);
+            }
+            TunnelKind::PortProxy => {
+                info!("Adding port proxy \"{}\": {}", tunnel.name, tunnel.describe());
+                self.run_proxy_action(index, ElevatedAction::AddPortProxy {
+                    listen_address: tunnel.bind_address,
+                    listen_port: tunnel.local_port,
+                    connect_address: tunnel.remote_host,
+                    connect_port: tunnel.remote_port,
+                });
+            }
+        }
+    }
+
+    pub(crate) fn stop_tunnel(&mut self, index: usize) {
+        let Some(tunnel) = self.settings.tunnels.tunnels.get(index).cloned() else { return };
+        match tunnel.kind {
+            TunnelKind::Ssh => {
+                let Some(run) = self.tunnel_manager.runs.get_mut(index) else { return };
+                info!("Stopping tunnel \"{}\"", tunnel.name);
+                run.wanted = false;
+                run.restart_at = None;
+                run.status = None;
+                if let Some(ssh) = &mut run.ssh {
+                    ssh.stop();
+                }
+                run.ssh = None;
+            }
+            TunnelKind::PortProxy => {
+                info!("Removing port proxy \"{}\"", tunnel.name);
+                self.run_proxy_action(index, ElevatedAction::DeletePortProxy {
+                    listen_address: tunnel.bind_address,
+                    listen_port: tunnel.local_port,
+                });
+            }
+        }
+    }
+
+    /// netsh needs administrator rights to change port proxy rules
+    fn run_proxy_action(&mut self, index: usize, action: ElevatedAction) {
+        let manager = &mut self.tunnel_manager;
+        let sender = match &manager.action_sender {
+            Some(sender) => sender.clone(),
+            None => {
+                let (sender, receiver) = channel();
+                manager.action_sender = Some(sender.clone());
+                manager.action_receiver = Some(receiver);
+                sender
+            }
+        };
+        manager.busy.insert(index);
+        let broker = self.elevation.clone();
+        self.runtime().spawn(async move {
+            sender.send((index, broker.run(action).await)).ok();
+        });
+    }
+
+    /// Displays the Tunnels card
+    /// Shows each SSH tunnel and port proxy rule with its status and a start/stop button
+    pub(crate) fn show_tunnels_card(&mut self, ui: &mut egui::Ui) {
+        let mut toggled = None;
+        self.show_card(ui, "Tunnels", |ui| {
+            for (index, tunnel) in self.settings.tunnels.tunnels.iter().enumerate() {
+                let status = self.tunnel_manager.runs.get(index).and_then(|run| run.status.clone());
+                let running = self.tunnel_manager.is_running(index, tunnel);
+                let (text, color) = status.as_ref().map(TunnelStatus::label)
+                    .unwrap_or_else(|| ("...".to_string(), Color32::from_rgb(156, 163, 175)));
+                ui.horizontal(|ui| {
+                    ui.colored_label(color, "●");
+                    ui.label(RichText::new(&tunnel.name).strong());
+                    ui.label(RichText::new(tunnel.kind.label()).color(Color32::from_rgb(156, 163, 175)));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        let busy = status == Some(TunnelStatus::Busy);
+                        let label = if running { "⏹ Stop" } else { "▶ Start" };
+                        if ui.add_enabled(!busy, egui::Button::new(label).small()).clicked() {
+                            toggled = Some((index, running));
+                        }
+                    });
+                });
+                ui.label(RichText::new(tunnel.describe()).monospace().color(Color32::from_rgb(107, 114, 128)));
+                let restart = self.tunnel_manager.runs.get(index).and_then(|run| run.restart_at);
+                match restart {
+                    Some(at) => ui.colored_label(color, format!("{} · retrying in {}s", text,
+                        at.saturating_duration_since(Instant::now()).as_secs() + 1)),
+                    None => ui.colored_label(color, text),
+                };
+                ui.add_space(6.0);
+            }
+        });
+        let Some((index, running)) = toggled else { return };
+        let Some(tunnel) = self.settings.tunnels.tunnels.get(index) else { return };
+        if matches!(tunnel.kind, TunnelKind::PortProxy) {
+            // Port proxy rules change system-wide networking through the elevated helper
+            let request = ElevatedRequest::PortProxy { tunnel: index, name: tunnel.name.clone(), add: !running };
+            self.request_sensitive_action(SensitiveAction::ElevatedAction(request));
+        } else if running {
+            self.stop_tunnel(index);
+        } else {
+            self.start_tunnel(index);
+        }
+    }
+
+    /// Each tunnel's route and status, for copying the card
+    pub(crate) fn tunnels_card_values(&self) -> Vec<(String, String)> {
+        self.settings.tunnels.tunnels
+            .iter()
+            .enumerate()
+            .map(|(index, tunnel)| {
+                let status = self.tunnel_manager.runs.get(index)
+                    .and_then(|run| run.status.as_ref())
+                    .map(|status| status.label().0)
+                    .unwrap_or_default();
+                (tunnel.name.clone(), format!("{} {}: {}", tunnel.kind.label(), tunnel.describe(), status))
+            })
+            .collect()
+    }
+
+    /// Settings section for SSH tunnels and port proxy rules
+    pub(crate) fn show_tunnel_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        let mut removed = None;
+        let grey = Color32::from_rgb(107, 114, 128);
+
+        ui.label("Tunnels:");
+        for (index, tunnel) in self.settings.tunnels.tunnels.iter_mut().enumerate() {
+            egui::CollapsingHeader::new(tunnel.name.clone())
+                .id_source(("tunnel_settings", index))
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Name:");
+                        changed |= ui.text_edit_singleline(&mut tunnel.name).changed();
+                        egui::ComboBox::from_id_source(("tunnel_kind", index))
+                            .selected_text(tunnel.kind.label())
+                            .width(90.0)
+                            .show_ui(ui, |ui| {
+                                for kind in [TunnelKind::Ssh, TunnelKind::PortProxy] {
+                                    changed |= ui.selectable_value(&mut tunnel.kind, kind, kind.label()).changed();
+                                }
+                            });
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Listen on:");
+                        changed |= ui.add(egui::TextEdit::singleline(&mut tunnel.bind_address)
+                            .hint_text("127.0.0.1")
+                            .desired_width(100.0)).changed();
+                        changed |= ui.add(egui::DragValue::new(&mut tunnel.local_port).clamp_range(1..=65535)).changed();
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Forward to:");
+                        changed |= ui.add(egui::TextEdit::singleline(&mut tunnel.remote_host)
+                            .hint_text("localhost")
+                            .desired_width(140.0)).changed();
+                        changed |= ui.add(egui::DragValue::new(&mut tunnel.remote_port).clamp_range(1..=65535)).changed();
+                    });
+                    if tunnel.kind == TunnelKind::Ssh {
+                        ui.horizontal(|ui| {
+                            ui.label("Through:");
+                            changed |= ui.add(egui::TextEdit::singleline(&mut tunnel.ssh_host)
+                                .hint_text("user@server")
+                                .desired_width(180.0)).changed();
+                        });
+                        changed |= ui.checkbox(&mut tunnel.auto_start, "Start with the dashboard").changed();
+                        ui.label(RichText::new("Uses OpenSSH with key authentication; password prompts can't be answered")
+                            .color(grey));
+                    } else {
+                        ui.label(RichText::new("Kept by Windows across restarts; adding and removing it needs administrator rights")
+                            .color(grey));
+                    }
+                    if ui.button("✖ Remove tunnel").clicked() {
+                        removed = Some(index);
+                    }
+                });
+        }
+        if let Some(index) = removed {
+            self.settings.tunnels.tunnels.remove(index);
+            self.tunnel_manager.remove(index);
+            changed = true;
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("Add tunnel").clicked() {
+                self.settings.tunnels.tunnels.push(TunnelConfig::default());
+                changed = true;
+            }
+            changed |= ui.checkbox(&mut self.settings.tunnels.auto_restart, "Restart dropped SSH tunnels").changed();
+        });
+
+        if changed {
+            self.save_settings();
+        }
+    }
+}
@@ -0,0 +1,440 @@
+use std::os::windows::process::CommandExt;
+use std::process::Command;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Local, TimeZone};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use sysinfo::SystemExt;
+use log::{info, warn};
+use crate::card_export::CardHistory;
+use crate::{storage, DevDashboard, CREATE_NO_WINDOW};
+
+/// Boot sessions are stored next to settings.json
+const BOOTS_FILE: &str = "boots.json";
+/// How often the System event log is re-read for boot and shutdown events
+const EVENT_LOG_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+const SAVE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+const RETENTION_DAYS: i64 = 90;
+/// Days covered by the timeline in the card
+const TIMELINE_DAYS: i64 = 14;
+/// Boot times from the event log and from the kernel differ by up to a few minutes
+const BOOT_TOLERANCE_SECS: i64 = 10 * 60;
+/// Processes that restart the machine on behalf of Windows Update
+const UPDATE_INITIATORS: &[&str] = &["trustedinstaller", "mousocoreworker", "usoclient", "wuauclt", "musnotification"];
+
+/// Persisted uptime card configuration
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct UptimeSettings {
+    pub show_card: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ShutdownKind {
+    Clean,
+    Unexpected,     // Crash, power loss or forced power off
+}
+
+/// One period between a boot and the following shutdown
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BootRecord {
+    pub boot: DateTime<Local>,
+    pub last_seen: Option<DateTime<Local>>,     // Last time the dashboard ran during this boot
+    pub shutdown: Option<DateTime<Local>>,
+    pub kind: Option<ShutdownKind>,
+    pub initiator: Option<String>,              // Process that requested the shutdown or restart
+    pub reason: Option<String>,
+}
+
+impl BootRecord {
+    fn new(boot: DateTime<Local>) -> Self {
+        Self { boot, last_seen: None, shutdown: None, kind: None, initiator: None, reason: None }
+    }
+
+    /// Shutdown time, or the last time the dashboard saw the machine running
+    fn end(&self) -> Option<DateTime<Local>> {
+        self.shutdown.or(self.last_seen)
+    }
+
+    pub fn is_windows_update(&self) -> bool {
+        self.initiator.as_deref().is_some_and(|initiator| {
+            let initiator = initiator.to_lowercase();
+            UPDATE_INITIATORS.iter().any(|name| initiator.contains(name))
+        })
+    }
+
+    fn color(&self) -> Color32 {
+        if self.kind == Some(ShutdownKind::Unexpected) {
+            Color32::from_rgb(220, 50, 50)
+        } else if self.is_windows_update() {
+            Color32::from_rgb(88, 165, 237)
+        } else {
+            Color32::from_rgb(22, 163, 74)
+        }
+    }
+
+    fn describe_end(&self) -> String {
+        let mut text = match (self.kind, self.shutdown) {
+            (Some(ShutdownKind::Unexpected), _) => "Unexpected shutdown".to_string(),
+            (_, Some(time)) => format!("Shut down {}", time.format("%Y-%m-%d %H:%M")),
+            (_, None) => match self.last_seen {
+                Some(time) => format!("Last seen {}", time.format("%Y-%m-%d %H:%M")),
+                None => "Shutdown not recorded".to_string(),
+            },
+        };
+        if self.is_windows_update() {
+            text.push_str(" · Windows Update");
+        } else if let Some(initiator) = &self.initiator {
+            text.push_str(&format!(" · by {}", initiator));
+        }
+        text
+    }
+}
+
+enum LogEvent {
+    Boot,
+    CleanShutdown,
+    UnexpectedShutdown,     // Logged during the boot that follows
+    Initiated { initiator: String, reason: String },
+}
+
+type LogEvents = Vec<(DateTime<Local>, LogEvent)>;
+
+#[derive(Default)]
+pub struct UptimeHistory {
+    records: Vec<BootRecord>,
+    receiver: Option<Receiver<Result<LogEvents, String>>>,
+    last_import: Option<Instant>,
+    last_save: Option<Instant>,
+    dirty: bool,
+}
+
+/// Text between the end of the opening `<tag ...>` and its closing tag
+fn element_text<'a>(xml: &'a str, tag: &str) -> Option<&'a str> {
+    let start = xml.find(&format!("<{}", tag))?;
+    let content = start + xml[start..].find('>')? + 1;
+    let end = content + xml[content..].find(&format!("</{}>", tag))?;
+    Some(xml[content..end].trim())
+}
+
+/// Value of `name='...'` or `name="..."` at its first occurrence
+fn attribute<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let start = xml.find(&format!("{}=", name))? + name.len() + 1;
+    let quote = xml[start..].chars().next()?;
+    let end = start + 1 + xml[start + 1..].find(quote)?;
+    Some(&xml[start + 1..end])
+}
+
+/// Value of `<Data Name='name'>` in the event data
+fn event_data<'a>(event: &'a str, name: &str) -> Option<&'a str> {
+    let marker = format!("Name='{}'>", name);
+    let start = event.find(&marker)? + marker.len();
+    let end = start + event[start..].find("</Data>")?;
+    Some(event[start..end].trim())
+}
+
+fn parse_event(event: &str) -> Option<(DateTime<Local>, LogEvent)> {
+    let provider = attribute(event, "Provider Name")?;
+    let id: u32 = element_text(event, "EventID")?.parse().ok()?;
+    let time = DateTime::parse_from_rfc3339(attribute(event, "SystemTime")?).ok()?.with_timezone(&Local);
+    let parsed = match (id, provider) {
+        (6005, "EventLog") => LogEvent::Boot,
+        (6006, "EventLog") => LogEvent::CleanShutdown,
+        (6008, "EventLog") | (41, "Microsoft-Windows-Kernel-Power") => LogEvent::UnexpectedShutdown,
+        (1074, "User32") => {
+            let process = event_data(event, "param1").unwrap_or_default();
+            // "C:\Windows\servicing\TrustedInstaller.exe (HOST)" -> "TrustedInstaller.exe"
+            let process = process.split(" (").next().unwrap_or(process);
+            let initiator = process.rsplit('\\').next().unwrap_or(process).to_string();
+            LogEvent::Initiated { initiator, reason: event_data(event, "param3").unwrap_or_default().to_string() }
+        }
+        _ => return None,
+    };
+    Some((time, parsed))
+}
+
+/// Reads boot and shutdown events from the System log, oldest first
+fn read_event_log() -> Result<LogEvents, String> {
+    let window_ms = RETENTION_DAYS * 24 * 60 * 60 * 1000;
+    let query = format!(
+        "/q:*[System[(EventID=6005 or EventID=6006 or EventID=6008 or EventID=1074 or EventID=41) and TimeCreated[timediff(@SystemTime) <= {}]]]",
+        window_ms
+    );
+    let output = Command::new("wevtutil")
+        .args(["qe", "System", &query, "/f:xml"])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_err(|e| format!("Could not run wevtutil: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    let xml = String::from_utf8_lossy(&output.stdout);
+    let mut events: Vec<_> = xml.split("</Event>").filter_map(parse_event).collect();
+    events.sort_by_key(|(time, _)| *time);
+    Ok(events)
+}
+
+impl UptimeHistory {
+    pub fn load() -> Self {
+        let records = storage::load_json(BOOTS_FILE);
+        Self { records, ..Default::default() }
+    }
+
+    pub fn save(&mut self) {
+        let cutoff = Local::now() - chrono::Duration::days(RETENTION_DAYS);
+        self.records.retain(|record| record.end().unwrap_or(record.boot) >= cutoff);
+
+        storage::save_json(BOOTS_FILE, &self.records);
+        self.dirty = false;
+        self.last_save = Some(Instant::now());
+    }
+
+    /// Unexpected shutdowns that ended a boot after `cutoff`
+    pub fn unexpected_since(&self, cutoff: DateTime<Local>) -> usize {
+        self.records
+            .iter()
+            .filter(|record| record.kind == Some(ShutdownKind::Unexpected))
+            .filter(|record| record.end().is_some_and(|end| end >= cutoff))
+            .count()
+    }
+
+    /// Index of the record for a boot at `time`, creating it if needed
+    fn record_for_boot(&mut self, time: DateTime<Local>) -> usize {
+        if let Some(index) = self.records.iter().position(|record| (record.boot - time).num_seconds().abs() <= BOOT_TOLERANCE_SECS) {
+            // The kernel boot time is earlier and more precise than the event log entry
+            if time < self.records[index].boot {
+                self.records[index].boot = time;
+            }
+            return index;
+        }
+        self.records.push(BootRecord::new(time));
+        self.records.sort_by_key(|record| record.boot);
+        self.dirty = true;
+        self.records.iter().position(|record| record.boot == time).unwrap_or(0)
+    }
+
+    /// Index of the last boot before `time`
+    fn record_before(&self, time: DateTime<Local>) -> Option<usize> {
+        self.records.iter().rposition(|record| record.boot <= time)
+    }
+
+    fn merge_events(&mut self, events: LogEvents) {
+        for (time, event) in events {
+            match event {
+                LogEvent::Boot => {
+                    self.record_for_boot(time);
+                }
+                LogEvent::CleanShutdown => {
+                    if let Some(index) = self.record_before(time) {
+                        let record = &mut self.records[index];
+                        record.shutdown = Some(time);
+                        record.kind = Some(ShutdownKind::Clean);
+                    }
+                }
+                LogEvent::UnexpectedShutdown => {
+                    let Some(index) = self.record_before(time + chrono::Duration::seconds(BOOT_TOLERANCE_SECS)) else { continue };
+                    if index > 0 && self.records[index - 1].kind != Some(ShutdownKind::Clean) {
+                        self.records[index - 1].kind = Some(ShutdownKind::Unexpected);
+                    }
+                }
+                LogEvent::Initiated { initiator, reason } => {
+                    if let Some(index) = self.record_before(time) {
+                        let record = &mut self.records[index];
+                        record.initiator = Some(initiator);
+                        record.reason = Some(reason).filter(|reason| !reason.is_empty());
+                    }
+                }
+            }
+        }
+        self.dirty = true;
+    }
+
+    /// Records the current boot, merges event log imports, and saves periodically
+    pub fn update(&mut self, boot_time: u64) {
+        let Some(boot) = Local.timestamp_opt(boot_time as i64, 0).single() else { return };
+        let index = self.record_for_boot(boot);
+        self.records[index].last_seen = Some(Local::now());
+        self.dirty = true;
+
+        if let Some(receiver) = &self.receiver {
+            if let Ok(result) = receiver.try_recv() {
+                match result {
+                    Ok(events) => {
+                        info!("Read {} boot/shutdown events from the System log", events.len());
+                        self.merge_events(events);
+                    }
+                    Err(e) => warn!("Could not read boot history from the event log: {}", e),
+                }
+                self.receiver = None;
+            }
+        }
+        let import_due = match self.last_import {
+            Some(last) => last.elapsed() >= EVENT_LOG_INTERVAL,
+            None => true,
+        };
+        if import_due && self.receiver.is_none() {
+            let (sender, receiver) = channel();
+            self.receiver = Some(receiver);
+            self.last_import = Some(Instant::now());
+            std::thread::spawn(move || {
+                sender.send(read_event_log()).ok();
+            });
+        }
+
+        let save_due = match self.last_save {
+            Some(last) => last.elapsed() >= SAVE_INTERVAL,
+            None => true,
+        };
+        if self.dirty && save_due {
+            self.save();
+        }
+    }
+
+    /// One row per boot session, oldest first; the last one is still running
+    pub fn export_history(&self) -> CardHistory {
+        let mut history = CardHistory::new(vec!["boot", "end", "uptime_seconds", "shutdown", "initiator", "reason"]);
+        let now = Local::now();
+        for (index, record) in self.records.iter().enumerate() {
+            let running = index + 1 == self.records.len();
+            let end = if running { Some(now) } else { record.end() };
+            let shutdown = match (running, record.kind) {
+                (true, _) => "running",
+                (_, Some(ShutdownKind::Unexpected)) => "unexpected",
+                (_, Some(ShutdownKind::Clean)) => "clean",
+                (_, None) => "",
+            };
+            history.rows.push(vec![
+                record.boot.format("%Y-%m-%d %H:%M:%S").to_string(),
+                end.map(|end| end.format("%Y-%m-%d %H:%M:%S").to_string()).unwrap_or_default(),
+                end.map(|end| (end - record.boot).num_seconds().to_string()).unwrap_or_default(),
+                shutdown.to_string(),
+                record.initiator.clone().unwrap_or_default(),
+                record.reason.clone().unwrap_or_default(),
+            ]);
+        }
+        history
+    }
+}
+
+fn format_span(duration: chrono::Duration) -> String {
+    let hours = duration.num_hours();
+    if hours >= 24 {
+        format!("{}d {}h", hours / 24, hours % 24)
+    } else {
+        format!("{}h {:02}m", hours, duration.num_minutes() % 60)
+    }
+}
+
+impl DevDashboard {
+    pub(crate) fn update_uptime_history(&mut self) {
+        self.uptime_history.update(self.sys.boot_time());
+    }
+
+    /// Displays the uptime history card
+    /// Shows a timeline of boot sessions and how each one ended
+    pub(crate) fn show_uptime_card(&self, ui: &mut egui::Ui) {
+        self.show_card(ui, "Uptime History", |ui| {
+            let records = &self.uptime_history.records;
+            let now = Local::now();
+            let start = now - chrono::Duration::days(TIMELINE_DAYS);
+            let span = (now - start).num_seconds() as f32;
+
+            let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width().min(320.0), 28.0), egui::Sense::hover());
+            let painter = ui.painter();
+            painter.rect_filled(rect, 4.0, Color32::from_rgb(17, 24, 39));
+            let x = |time: DateTime<Local>| rect.left() + (time - start).num_seconds().max(0) as f32 / span * rect.width();
+
+            let mut hovered = None;
+            for (index, record) in records.iter().enumerate() {
+                let end = if index + 1 == records.len() { now } else { record.end().unwrap_or(record.boot) };
+                if end < start {
+                    continue;
+                }
+                let segment = egui::Rect::from_x_y_ranges(x(record.boot)..=x(end).max(x(record.boot) + 2.0), rect.y_range());
+                painter.rect_filled(segment.shrink2(egui::vec2(0.0, 6.0)), 2.0, Color32::from_rgb(55, 65, 81));
+                if index + 1 < records.len() {
+                    painter.rect_filled(egui::Rect::from_x_y_ranges(segment.right() - 2.0..=segment.right(), rect.y_range()), 0.0, record.color());
+                }
+                if response.hover_pos().is_some_and(|pos| segment.x_range().contains(pos.x)) {
+                    hovered = Some(index);
+                }
+            }
+            if let Some(index) = hovered {
+                let record = &records[index];
+                let running = index + 1 == records.len();
+                let end = if running { now } else { record.end().unwrap_or(record.boot) };
+                let status = if running { "Running".to_string() } else { record.describe_end() };
+                let mut text = format!("Booted {}\nUp {}\n{}", record.boot.format("%Y-%m-%d %H:%M"), format_span(end - record.boot), status);
+                if let Some(reason) = &record.reason {
+                    text.push_str(&format!("\n{}", reason));
+                }
+                response.on_hover_text(text);
+            }
+            ui.label(RichText::new(format!("Last {} days", TIMELINE_DAYS)).color(Color32::from_rgb(107, 114, 128)));
+            ui.add_space(4.0);
+
+            let month_ago = now - chrono::Duration::days(30);
+            let ended: Vec<&BootRecord> = records.iter().rev().skip(1).filter(|record| record.boot >= month_ago).collect();
+            let by_update = ended.iter().filter(|record| record.is_windows_update()).count();
+            let unexpected = ended.iter().filter(|record| record.kind == Some(ShutdownKind::Unexpected)).count();
+            ui.label(format!("{} restarts in 30 days", ended.len()));
+            ui.horizontal(|ui| {
+                ui.colored_label(Color32::from_rgb(88, 165, 237), format!("{} by Windows Update", by_update));
+                ui.colored_label(Color32::from_rgb(220, 50, 50), format!("{} unexpected", unexpected));
+            });
+            if let Some(longest) = records.iter().enumerate()
+                .map(|(index, record)| {
+                    let end = if index + 1 == records.len() { now } else { record.end().unwrap_or(record.boot) };
+                    end - record.boot
+                })
+                .max() {
+                ui.label(format!("Longest uptime: {}", format_span(longest)));
+            }
+            ui.add_space(4.0);
+
+            for record in ended.iter().take(5) {
+                let end = record.end().unwrap_or(record.boot);
+                ui.horizontal(|ui| {
+                    ui.label(record.boot.format("%b %d %H:%M").to_string());
+                    ui.label(RichText::new(format_span(end - record.boot)).color(Color32::from_rgb(156, 163, 175)));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.colored_label(record.color(), record.describe_end())
+                            .on_hover_text(record.reason.as_deref().unwrap_or("No reason recorded"));
+                    });
+                });
+            }
+        });
+    }
+
+    /// Current uptime and the recent restarts, for copying the card
+    pub(crate) fn uptime_card_values(&self) -> Vec<(String, String)> {
+        let records = &self.uptime_history.records;
+        let mut values = Vec::new();
+        if let Some(current) = records.last() {
+            values.push(("Booted".to_string(), current.boot.format("%Y-%m-%d %H:%M").to_string()));
+            values.push(("Up".to_string(), format_span(Local::now() - current.boot)));
+        }
+        let month_ago = Local::now() - chrono::Duration::days(30);
+        let ended: Vec<&BootRecord> = records.iter().rev().skip(1).filter(|record| record.boot >= month_ago).collect();
+        values.push(("Restarts in 30 days".to_string(), ended.len().to_string()));
+        for record in ended.iter().take(5) {
+            let end = record.end().unwrap_or(record.boot);
+            values.push((
+                record.boot.format("%Y-%m-%d %H:%M").to_string(),
+                format!("up {}, {}", format_span(end - record.boot), record.describe_end()),
+            ));
+        }
+        values
+    }
+
+    /// Settings section for the uptime history card
+    pub(crate) fn show_uptime_settings(&mut self, ui: &mut egui::Ui) {
+        if ui.checkbox(&mut self.settings.uptime.show_card, "Show uptime history card")
+            .on_hover_text("Boots and shutdowns come from the System event log and the dashboard's own observations")
+            .changed() {
+            self.save_settings();
+        }
+    }
+}
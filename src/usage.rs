@@ -0,0 +1,251 @@
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+use chrono::Local;
+use eframe::egui;
+use egui::RichText;
+use serde::{Serialize, Deserialize};
+use sysinfo::{Pid, PidExt, ProcessExt, SystemExt};
+use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+use log::info;
+use crate::card_export::CardHistory;
+use crate::idle::IDLE_INTERVAL;
+use crate::{storage, DevDashboard};
+
+/// Per-day focus time is stored next to settings.json
+const USAGE_FILE: &str = "usage.json";
+/// How often accumulated time is written to disk
+const SAVE_INTERVAL: Duration = Duration::from_secs(60);
+/// Gaps longer than this (sleep, a stalled UI) are not attributed to any app.
+/// The tick slows to IDLE_INTERVAL while a fullscreen app has focus, so samples that far apart still count.
+const MAX_SAMPLE_GAP: Duration = Duration::from_secs(IDLE_INTERVAL.as_secs() + 5);
+/// Apps listed in the card before the rest are grouped as "Other"
+const TOP_APPS: usize = 8;
+
+/// Persisted screen-time configuration; tracking is opt-in
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct UsageSettings {
+    pub enabled: bool,
+    pub retention_days: u32,    // Days of history kept in usage.json
+}
+
+impl Default for UsageSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retention_days: 30,
+        }
+    }
+}
+
+/// Foreground time per application, keyed by day
+#[derive(Default)]
+pub struct UsageTracker {
+    days: BTreeMap<String, HashMap<String, u64>>,   // YYYY-MM-DD -> app -> seconds
+    remainders: HashMap<String, Duration>,          // Time per app not yet counted as a whole second
+    last_sample: Option<Instant>,
+    last_save: Option<Instant>,
+    dirty: bool,
+    day_offset: u32,            // Day shown in the card, counted back from today
+}
+
+impl UsageTracker {
+    pub fn load() -> Self {
+        let days = storage::load_json(USAGE_FILE);
+        Self { days, ..Default::default() }
+    }
+
+    /// Drops days older than the retention window and writes the rest to disk
+    fn save(&mut self, retention_days: u32) {
+        let cutoff = (Local::now().date_naive() - chrono::Duration::days(retention_days as i64))
+            .format("%Y-%m-%d")
+            .to_string();
+        self.days.retain(|day, _| *day >= cutoff);
+
+        storage::save_json(USAGE_FILE, &self.days);
+        self.dirty = false;
+        self.last_save = Some(Instant::now());
+    }
+
+    /// Stops attributing time until the next sample, e.g. while the user is away
+    pub fn pause(&mut self) {
+        self.last_sample = None;
+    }
+
+    /// Seconds per app per recorded day
+    pub fn export_history(&self) -> CardHistory {
+        let mut history = CardHistory::new(vec!["date", "app", "seconds"]);
+        for (day, apps) in &self.days {
+            let mut apps: Vec<(&String, &u64)> = apps.iter().collect();
+            apps.sort_by_key(|(_, seconds)| std::cmp::Reverse(**seconds));
+            for (app, seconds) in apps {
+                history.rows.push(vec![day.clone(), app.clone(), seconds.to_string()]);
+            }
+        }
+        history
+    }
+}
+
+/// Process id owning the current foreground window
+pub(crate) fn foreground_pid() -> Option<u32> {
+    unsafe {
+        let window = GetForegroundWindow();
+        if window.0 == 0 {
+            return None;
+        }
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(window, Some(&mut pid));
+        (pid != 0).then_some(pid)
+    }
+}
+
+/// Formats seconds as "1h 05m" or "12m"
+fn format_duration(seconds: u64) -> String {
+    let hours = seconds / 3600;
+    let minutes = (seconds % 3600) / 60;
+    if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+impl DevDashboard {
+    /// Adds the time since the last sample to the foreground application
+    pub(crate) fn update_usage(&mut self) {
+        if !self.settings.usage.enabled || self.idle.is_away() {
+            self.usage.pause();
+            return;
+        }
+
+        let now = Instant::now();
+        let elapsed = self.usage.last_sample.map(|last| now.duration_since(last));
+        self.usage.last_sample = Some(now);
+
+        if let (Some(elapsed), Some(pid)) = (elapsed, foreground_pid()) {
+            if elapsed <= MAX_SAMPLE_GAP {
+                let pid = Pid::from_u32(pid);
+                self.sys.refresh_process(pid);
+                if let Some(process) = self.sys.process(pid) {
+                    let name = process.name().trim_end_matches(".exe").to_string();
+                    let today = Local::now().format("%Y-%m-%d").to_string();
+                    // Whole seconds are stored; the fraction carries over so short samples add up
+                    let remainder = self.usage.remainders.entry(name.clone()).or_default();
+                    *remainder += elapsed;
+                    let seconds = remainder.as_secs();
+                    *remainder -= Duration::from_secs(seconds);
+                    if seconds > 0 {
+                        *self.usage.days.entry(today).or_default().entry(name).or_default() += seconds;
+                        self.usage.dirty = true;
+                    }
+                }
+            }
+        }
+
+        let save_due = match self.usage.last_save {
+            Some(last) => last.elapsed() >= SAVE_INTERVAL,
+            None => true,
+        };
+        if self.usage.dirty && save_due {
+            self.usage.save(self.settings.usage.retention_days);
+        }
+    }
+
+    /// Displays the screen-time card
+    /// Shows a per-application breakdown of foreground time for the selected day
+    pub(crate) fn show_usage_card(&mut self, ui: &mut egui::Ui) {
+        let retention_days = self.settings.usage.retention_days;
+        let usage = &mut self.usage;
+        let day = (Local::now().date_naive() - chrono::Duration::days(usage.day_offset as i64))
+            .format("%Y-%m-%d")
+            .to_string();
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Screen Time").strong().heading());
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.add_enabled(usage.day_offset > 0, egui::Button::new("▶").small()).clicked() {
+                    usage.day_offset -= 1;
+                }
+                ui.label(if usage.day_offset == 0 { "Today".to_string() } else { day.clone() });
+                if ui.add_enabled(usage.day_offset < retention_days, egui::Button::new("◀").small()).clicked() {
+                    usage.day_offset += 1;
+                }
+            });
+        });
+        ui.add_space(8.0);
+
+        let mut apps: Vec<(String, u64)> = usage.days.get(&day)
+            .map(|apps| apps.iter().map(|(name, seconds)| (name.clone(), *seconds)).collect())
+            .unwrap_or_default();
+        if apps.is_empty() {
+            ui.label("No activity recorded");
+            return;
+        }
+        apps.sort_by_key(|(_, seconds)| std::cmp::Reverse(*seconds));
+
+        let total: u64 = apps.iter().map(|(_, seconds)| seconds).sum();
+        if apps.len() > TOP_APPS {
+            let other: u64 = apps.drain(TOP_APPS..).map(|(_, seconds)| seconds).sum();
+            apps.push(("Other".to_string(), other));
+        }
+        let longest = apps.iter().map(|(_, seconds)| *seconds).max().unwrap_or(1).max(1);
+
+        ui.label(format!("Total: {}", format_duration(total)));
+        ui.add_space(4.0);
+
+        egui::Grid::new("usage_grid")
+            .num_columns(3)
+            .spacing([8.0, 4.0])
+            .show(ui, |ui| {
+                for (name, seconds) in &apps {
+                    ui.label(name);
+                    let (rect, _) = ui.allocate_exact_size(egui::vec2(120.0, 12.0), egui::Sense::hover());
+                    let mut bar = rect;
+                    bar.set_width(rect.width() * (*seconds as f32 / longest as f32));
+                    ui.painter().rect_filled(rect, 3.0, egui::Color32::from_rgb(55, 65, 81));
+                    ui.painter().rect_filled(bar, 3.0, egui::Color32::from_rgb(88, 165, 237));
+                    ui.label(format_duration(*seconds));
+                    ui.end_row();
+                }
+            });
+    }
+
+    /// Time per app on the day the card shows, for copying it
+    pub(crate) fn usage_card_values(&self) -> Vec<(String, String)> {
+        let day = (Local::now().date_naive() - chrono::Duration::days(self.usage.day_offset as i64))
+            .format("%Y-%m-%d")
+            .to_string();
+        let mut apps: Vec<(&String, &u64)> = self.usage.days.get(&day).map(|apps| apps.iter().collect()).unwrap_or_default();
+        apps.sort_by_key(|(_, seconds)| std::cmp::Reverse(**seconds));
+        let total: u64 = apps.iter().map(|(_, seconds)| **seconds).sum();
+        let mut values = vec![("Date".to_string(), day), ("Total".to_string(), format_duration(total))];
+        values.extend(apps.into_iter().map(|(app, seconds)| (app.clone(), format_duration(*seconds))));
+        values
+    }
+
+    /// Settings section for screen-time tracking
+    pub(crate) fn show_usage_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+
+        let response = ui.checkbox(&mut self.settings.usage.enabled, "Track application screen time")
+            .on_hover_text(format!("Records which app has focus, stored only in {}", USAGE_FILE));
+        if response.changed() {
+            info!("Screen time tracking {}", if self.settings.usage.enabled { "enabled" } else { "disabled" });
+            changed = true;
+        }
+
+        if self.settings.usage.enabled {
+            ui.horizontal(|ui| {
+                ui.label("Keep history for (days):");
+                changed |= ui.add(egui::DragValue::new(&mut self.settings.usage.retention_days)
+                    .clamp_range(1..=365)).changed();
+            });
+        }
+
+        if changed {
+            self.save_settings();
+        }
+    }
+}
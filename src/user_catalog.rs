@@ -0,0 +1,252 @@
+use std::fs::File;
+use std::io::Read;
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use log::{info, warn};
+use crate::{storage, DevDashboard, NiniteApp};
+
+/// The user's own catalog, next to settings.json
+const USER_CATALOG_FILE: &str = "apps.json";
+
+/// An app the user added, or a replacement for the built-in entry with the same Ninite ID
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct UserApp {
+    pub name: String,
+    pub category: String,
+    pub ninite_id: String,          // Empty for internal tools that are only installed with winget
+    pub winget_id: String,
+    pub registry_keys: Vec<String>,
+    pub file_paths: Vec<String>,    // May contain * wildcards and %USERNAME%
+}
+
+impl UserApp {
+    fn is_same(&self, app: &NiniteApp) -> bool {
+        if self.ninite_id.is_empty() { app.name == self.name } else { app.ninite_id == self.ninite_id }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct UserCatalogFile {
+    apps: Vec<UserApp>,
+}
+
+/// Applies the user's entries over the built-in and signed catalog ones
+pub fn apply(apps: &mut Vec<NiniteApp>, user_apps: &[UserApp]) {
+    for entry in user_apps {
+        let app = match apps.iter_mut().position(|app| entry.is_same(app)) {
+            Some(index) => &mut apps[index],
+            None => {
+                apps.push(NiniteApp::new(&entry.name, &entry.category, &entry.ninite_id, Vec::new(), Vec::new()));
+                apps.last_mut().unwrap()
+            }
+        };
+        if app.name != entry.name || app.category != entry.category
+            || app.registry_keys != entry.registry_keys || app.file_paths != entry.file_paths {
+            app.name = entry.name.clone();
+            app.category = entry.category.clone();
+            app.registry_keys = entry.registry_keys.clone();
+            app.file_paths = entry.file_paths.clone();
+            app.check_installation();
+        }
+        if !entry.winget_id.is_empty() {
+            app.info.winget_id = entry.winget_id.clone();
+        }
+    }
+}
+
+/// An entry being edited, with the lists as one item per line
+#[derive(Clone, Default)]
+struct Draft {
+    app: UserApp,
+    registry_keys: String,
+    file_paths: String,
+}
+
+impl Draft {
+    fn new(app: &UserApp) -> Self {
+        Self { app: app.clone(), registry_keys: app.registry_keys.join("\n"), file_paths: app.file_paths.join("\n") }
+    }
+
+    fn finish(&self) -> UserApp {
+        let lines = |text: &str| text.lines().map(str::trim).filter(|line| !line.is_empty()).map(str::to_string).collect();
+        UserApp {
+            name: self.app.name.trim().to_string(),
+            category: self.app.category.trim().to_string(),
+            ninite_id: self.app.ninite_id.trim().to_string(),
+            winget_id: self.app.winget_id.trim().to_string(),
+            registry_keys: lines(&self.registry_keys),
+            file_paths: lines(&self.file_paths),
+        }
+    }
+}
+
+/// The user's apps.json and its editor
+#[derive(Default)]
+pub struct UserCatalog {
+    pub apps: Vec<UserApp>,
+    pub error: Option<String>,      // Why apps.json could not be read or written
+    drafts: Option<Vec<Draft>>,     // Open editor
+}
+
+impl UserCatalog {
+    /// Reads apps.json; a missing file is an empty catalog
+    pub fn load() -> Self {
+        let mut catalog = Self::default();
+        let mut contents = String::new();
+        match File::open(USER_CATALOG_FILE) {
+            Ok(mut file) => {
+                if let Err(e) = file.read_to_string(&mut contents) {
+                    catalog.error = Some(e.to_string());
+                    return catalog;
+                }
+            }
+            Err(_) => return catalog,
+        }
+        match serde_json::from_str::<UserCatalogFile>(&contents) {
+            Ok(file) => {
+                info!("Loaded {} apps from {}", file.apps.len(), USER_CATALOG_FILE);
+                catalog.apps = file.apps;
+            }
+            Err(e) => {
+                warn!("Could not parse {}: {}", USER_CATALOG_FILE, e);
+                catalog.error = Some(format!("{} is not valid: {}", USER_CATALOG_FILE, e));
+            }
+        }
+        catalog
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&UserCatalogFile { apps: self.apps.clone() }).map_err(|e| e.to_string())?;
+        storage::write_atomic(USER_CATALOG_FILE, json.as_bytes()).map_err(|e| e.to_string())
+    }
+}
+
+impl DevDashboard {
+    /// Rebuilds the installer list from the built-in and signed catalog apps plus the user's entries
+    pub(crate) fn rebuild_app_list(&mut self) {
+        let mut apps = self.app_base.clone();
+        apply(&mut apps, &self.user_catalog.apps);
+        for app in &mut apps {
+            app.check_installation();
+        }
+        self.ninite_apps = apps;
+    }
+
+    fn reload_user_catalog(&mut self) {
+        let drafts = self.user_catalog.drafts.take();
+        self.user_catalog = UserCatalog::load();
+        self.user_catalog.drafts = drafts;
+        self.rebuild_app_list();
+    }
+
+    /// Settings section for the user's own apps
+    pub(crate) fn show_user_catalog_settings(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button("Edit my apps").on_hover_text(format!("Apps you add are kept in {}", USER_CATALOG_FILE)).clicked() {
+                self.user_catalog.drafts = Some(self.user_catalog.apps.iter().map(Draft::new).collect());
+            }
+            if ui.button("Reload apps.json").on_hover_text("Picks up changes made to the file in an editor").clicked() {
+                self.reload_user_catalog();
+            }
+            let count = self.user_catalog.apps.len();
+            ui.label(RichText::new(format!("{} app{} of your own", count, if count == 1 { "" } else { "s" }))
+                .color(Color32::from_rgb(107, 114, 128)));
+        });
+        if let Some(error) = &self.user_catalog.error {
+            ui.colored_label(Color32::from_rgb(220, 50, 50), error);
+        }
+    }
+
+    /// Editor for apps.json: add internal tools or replace the detection of built-in apps
+    pub(crate) fn show_user_catalog_editor(&mut self, ctx: &egui::Context) {
+        let Some(drafts) = &mut self.user_catalog.drafts else { return };
+        let mut open = true;
+        let mut save = false;
+        let mut cancel = false;
+        let mut removed = None;
+
+        egui::Window::new("My Apps")
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                ui.label(RichText::new("An entry with a built-in app's Ninite ID replaces it; apps without a Ninite ID are installed with winget.")
+                    .color(Color32::from_rgb(107, 114, 128)));
+                ui.add_space(8.0);
+                egui::ScrollArea::vertical().max_height(ctx.screen_rect().height() * 0.6).show(ui, |ui| {
+                    for (index, draft) in drafts.iter_mut().enumerate() {
+                        let title = if draft.app.name.trim().is_empty() { "New app".to_string() } else { draft.app.name.clone() };
+                        egui::CollapsingHeader::new(title)
+                            .id_source(("user_app", index))
+                            .default_open(draft.app.name.is_empty())
+                            .show(ui, |ui| {
+                                egui::Grid::new(("user_app_grid", index)).num_columns(2).show(ui, |ui| {
+                                    ui.label("Name:");
+                                    ui.text_edit_singleline(&mut draft.app.name);
+                                    ui.end_row();
+                                    ui.label("Category:");
+                                    ui.add(egui::TextEdit::singleline(&mut draft.app.category).hint_text("Developer Tools"));
+                                    ui.end_row();
+                                    ui.label("Ninite ID:");
+                                    ui.add(egui::TextEdit::singleline(&mut draft.app.ninite_id).hint_text("vscode"));
+                                    ui.end_row();
+                                    ui.label("winget ID:");
+                                    ui.add(egui::TextEdit::singleline(&mut draft.app.winget_id).hint_text("Contoso.BuildTools"));
+                                    ui.end_row();
+                                    ui.label("Registry keys:");
+                                    ui.add(egui::TextEdit::multiline(&mut draft.registry_keys)
+                                        .hint_text(r"SOFTWARE\Contoso\BuildTools")
+                                        .desired_rows(2));
+                                    ui.end_row();
+                                    ui.label("File paths:");
+                                    ui.add(egui::TextEdit::multiline(&mut draft.file_paths)
+                                        .hint_text(r"C:\Program Files\Contoso\*\tool.exe")
+                                        .desired_rows(2));
+                                    ui.end_row();
+                                });
+                                if ui.button("Remove").clicked() {
+                                    removed = Some(index);
+                                }
+                            });
+                    }
+                });
+                if ui.button("Add app").clicked() {
+                    drafts.push(Draft { app: UserApp { category: "Other".to_string(), ..UserApp::default() }, ..Draft::default() });
+                }
+                ui.add_space(8.0);
+                let valid = drafts.iter().all(|draft| !draft.app.name.trim().is_empty() && !draft.app.category.trim().is_empty());
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(valid, egui::Button::new("Save")).on_disabled_hover_text("Every app needs a name and a category").clicked() {
+                        save = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        cancel = true;
+                    }
+                });
+            });
+
+        if let Some(index) = removed {
+            drafts.remove(index);
+        }
+        if save {
+            self.user_catalog.apps = drafts.iter().map(Draft::finish).collect();
+            match self.user_catalog.save() {
+                Ok(()) => {
+                    info!("Saved {} apps to {}", self.user_catalog.apps.len(), USER_CATALOG_FILE);
+                    self.user_catalog.error = None;
+                    self.user_catalog.drafts = None;
+                }
+                Err(e) => {
+                    warn!("Could not save {}: {}", USER_CATALOG_FILE, e);
+                    self.user_catalog.error = Some(format!("Could not save {}: {}", USER_CATALOG_FILE, e));
+                }
+            }
+            self.rebuild_app_list();
+        } else if cancel || !open {
+            self.user_catalog.drafts = None;
+        }
+    }
+}
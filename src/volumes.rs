@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::{Duration, Instant};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use windows::core::HSTRING;
+use windows::Win32::Storage::FileSystem::{GetDriveTypeW, GetLogicalDrives};
+use log::{debug, info, warn};
+use crate::DevDashboard;
+
+/// GetDriveTypeW results
+const DRIVE_REMOVABLE: u32 = 2;
+const DRIVE_REMOTE: u32 = 4;
+const DRIVE_CDROM: u32 = 5;
+/// A volume that has not answered within this time is treated as offline
+const QUERY_TIMEOUT: Duration = Duration::from_secs(10);
+/// How often offline volumes are retried when they are skipped
+const OFFLINE_RETRY: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum VolumeKind {
+    Fixed,
+    Removable,
+    Network,
+}
+
+impl VolumeKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            VolumeKind::Fixed => "fixed",
+            VolumeKind::Removable => "removable",
+            VolumeKind::Network => "network",
+        }
+    }
+}
+
+/// Total and free bytes, or None when the read failed
+type SpaceReading = Option<(u64, u64)>;
+
+/// Per-volume override of the refresh interval for its kind
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum VolumeRefresh {
+    Auto,
+    EverySecs(u64),
+    Paused,     // Only read once, when the volume first appears
+}
+
+/// Persisted free-space refresh policies
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct DiskRefreshSettings {
+    pub fixed_secs: u64,
+    pub removable_secs: u64,            // Reading these wakes sleeping external drives
+    pub network_secs: u64,              // Reading these blocks while a mapped drive disconnects
+    pub skip_offline: bool,             // Retry offline volumes only every few minutes
+    pub volumes: HashMap<String, VolumeRefresh>,    // Keyed by mount point, e.g. "E:\"
+}
+
+impl Default for DiskRefreshSettings {
+    fn default() -> Self {
+        Self {
+            fixed_secs: 1,
+            removable_secs: 60,
+            network_secs: 120,
+            skip_offline: true,
+            volumes: HashMap::new(),
+        }
+    }
+}
+
+impl DiskRefreshSettings {
+    /// Time between reads of a volume, None when it is paused
+    fn interval(&self, volume: &Volume) -> Option<Duration> {
+        let secs = match self.volumes.get(&volume.mount).copied().unwrap_or(VolumeRefresh::Auto) {
+            VolumeRefresh::Paused => return None,
+            VolumeRefresh::EverySecs(secs) => secs,
+            VolumeRefresh::Auto => match volume.kind {
+                VolumeKind::Fixed => self.fixed_secs,
+                VolumeKind::Removable => self.removable_secs,
+                VolumeKind::Network => self.network_secs,
+            },
+        };
+        let interval = Duration::from_secs(secs.max(1));
+        if !volume.online && self.skip_offline {
+            return Some(interval.max(OFFLINE_RETRY));
+        }
+        Some(interval)
+    }
+}
+
+/// A drive letter and the last free-space reading for it
+pub struct Volume {
+    pub mount: String,
+    pub kind: VolumeKind,
+    pub space: Option<(u64, u64)>,      // Total and free bytes from the last successful read
+    pub online: bool,
+    pub checked: Option<Instant>,       // When the last read finished or timed out
+}
+
+/// Reads free space on background threads, each volume at its own pace
+#[derive(Default)]
+pub struct VolumeMonitor {
+    volumes: Vec<Volume>,
+    sender: Option<Sender<(String, SpaceReading)>>,
+    receiver: Option<Receiver<(String, SpaceReading)>>,
+    pending: HashMap<String, Instant>,  // Reads in flight and when they started
+}
+
+fn drive_kind(mount: &str) -> VolumeKind {
+    match unsafe { GetDriveTypeW(&HSTRING::from(mount)) } {
+        DRIVE_REMOVABLE | DRIVE_CDROM => VolumeKind::Removable,
+        DRIVE_REMOTE => VolumeKind::Network,
+        _ => VolumeKind::Fixed,
+    }
+}
+
+impl VolumeMonitor {
+    /// Volumes in drive letter order
+    pub fn volumes(&self) -> &[Volume] {
+        &self.volumes
+    }
+
+    pub fn get(&self, mount: &str) -> Option<&Volume> {
+        self.volumes.iter().find(|volume| volume.mount == mount)
+    }
+
+    /// Follows drive letters appearing and disappearing; this reads no volume
+    fn scan(&mut self) {
+        let drives = unsafe { GetLogicalDrives() };
+        let mounts: Vec<String> = (0..26u8)
+            .filter(|letter| drives & (1 << letter) != 0)
+            .map(|letter| format!("{}:\\", (b'A' + letter) as char))
+            .collect();
+
+        self.volumes.retain(|volume| {
+            let present = mounts.contains(&volume.mount);
+            if !present {
+                info!("Volume {} was removed", volume.mount);
+            }
+            present
+        });
+        for mount in mounts {
+            if self.get(&mount).is_none() {
+                let kind = drive_kind(&mount);
+                debug!("Found {} volume {}", kind.label(), mount);
+                self.volumes.push(Volume { mount, kind, space: None, online: true, checked: None });
+            }
+        }
+        self.volumes.sort_by(|a, b| a.mount.cmp(&b.mount));
+    }
+
+    fn start_read(&mut self, mount: &str) {
+        let sender = match &self.sender {
+            Some(sender) => sender.clone(),
+            None => {
+                let (sender, receiver) = channel();
+                self.sender = Some(sender.clone());
+                self.receiver = Some(receiver);
+                sender
+            }
+        };
+        self.pending.insert(mount.to_string(), Instant::now());
+        let mount = mount.to_string();
+        // One thread per read so a hung network drive cannot hold up the others
+        std::thread::spawn(move || {
+            let space = DevDashboard::get_disk_space(&mount).filter(|(total, _)| *total > 0);
+            sender.send((mount, space)).ok();
+        });
+    }
+
+    fn record(&mut self, mount: &str, space: SpaceReading) {
+        let Some(volume) = self.volumes.iter_mut().find(|volume| volume.mount == mount) else { return };
+        let online = space.is_some();
+        // Only log changes so a drive that stays offline does not repeat the message;
+        // empty card readers fail their first read and are not worth a warning
+        if online != volume.online && volume.checked.is_some() {
+            if online {
+                info!("Volume {} is readable again", mount);
+            } else {
+                warn!("Volume {} is offline", mount);
+            }
+        }
+        volume.online = online;
+        if space.is_some() {
+            volume.space = space;
+        }
+        volume.checked = Some(Instant::now());
+    }
+
+    /// Collects finished reads, times out hung ones and starts those that are due
+    pub fn update(&mut self, settings: &DiskRefreshSettings) {
+        let mut finished = Vec::new();
+        if let Some(receiver) = &self.receiver {
+            while let Ok(result) = receiver.try_recv() {
+                finished.push(result);
+            }
+        }
+        for (mount, space) in finished {
+            self.pending.remove(&mount);
+            self.record(&mount, space);
+        }
+
+        // A hung read stays pending so it is not started again until it returns
+        let timed_out: Vec<String> = self.pending
+            .iter()
+            .filter(|(mount, started)| started.elapsed() >= QUERY_TIMEOUT
+                && self.get(mount).map(|volume| volume.online).unwrap_or(false))
+            .map(|(mount, _)| mount.clone())
+            .collect();
+        for mount in timed_out {
+            self.record(&mount, None);
+        }
+
+        self.scan();
+        let due: Vec<String> = self.volumes
+            .iter()
+            .filter(|volume| !self.pending.contains_key(&volume.mount))
+            .filter(|volume| match (volume.checked, settings.interval(volume)) {
+                (None, _) => true,
+                (Some(checked), Some(interval)) => checked.elapsed() >= interval,
+                (Some(_), None) => false,
+            })
+            .map(|volume| volume.mount.clone())
+            .collect();
+        for mount in due {
+            self.start_read(&mount);
+        }
+    }
+}
+
+impl DevDashboard {
+    /// Settings section for how often each volume's free space is read
+    pub(crate) fn show_disk_refresh_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        let settings = &mut self.settings.disk_refresh;
+
+        ui.label("Disk Refresh:");
+        ui.horizontal(|ui| {
+            ui.label("Fixed (s):");
+            changed |= ui.add(egui::DragValue::new(&mut settings.fixed_secs).clamp_range(1..=3600)).changed();
+            ui.label("Removable (s):");
+            changed |= ui.add(egui::DragValue::new(&mut settings.removable_secs).clamp_range(1..=3600)).changed();
+            ui.label("Network (s):");
+            changed |= ui.add(egui::DragValue::new(&mut settings.network_secs).clamp_range(1..=3600)).changed();
+        });
+        changed |= ui.checkbox(&mut settings.skip_offline, "Retry offline volumes only every 5 minutes").changed();
+
+        for volume in self.volume_monitor.volumes() {
+            let mut policy = settings.volumes.get(&volume.mount).copied().unwrap_or(VolumeRefresh::Auto);
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(&volume.mount).monospace());
+                ui.label(RichText::new(volume.kind.label()).color(Color32::from_rgb(156, 163, 175)));
+                let text = match policy {
+                    VolumeRefresh::Auto => "Default".to_string(),
+                    VolumeRefresh::EverySecs(_) => "Custom".to_string(),
+                    VolumeRefresh::Paused => "Paused".to_string(),
+                };
+                egui::ComboBox::from_id_source(("volume_refresh", &volume.mount))
+                    .selected_text(text)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut policy, VolumeRefresh::Auto, "Default");
+                        if ui.selectable_label(matches!(policy, VolumeRefresh::EverySecs(_)), "Custom").clicked()
+                            && !matches!(policy, VolumeRefresh::EverySecs(_)) {
+                            policy = VolumeRefresh::EverySecs(300);
+                        }
+                        ui.selectable_value(&mut policy, VolumeRefresh::Paused, "Paused");
+                    });
+                if let VolumeRefresh::EverySecs(secs) = &mut policy {
+                    ui.add(egui::DragValue::new(secs).clamp_range(1..=86_400).suffix(" s"));
+                }
+            });
+            let previous = settings.volumes.get(&volume.mount).copied().unwrap_or(VolumeRefresh::Auto);
+            if policy != previous {
+                if policy == VolumeRefresh::Auto {
+                    settings.volumes.remove(&volume.mount);
+                } else {
+                    settings.volumes.insert(volume.mount.clone(), policy);
+                }
+                changed = true;
+            }
+        }
+
+        if changed {
+            self.save_settings();
+        }
+    }
+}
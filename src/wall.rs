@@ -0,0 +1,317 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use eframe::egui;
+use egui::{Color32, FontId, RichText};
+use serde::{Serialize, Deserialize};
+use sysinfo::SystemExt;
+use windows::Win32::Foundation::{BOOL, LPARAM, RECT};
+use windows::Win32::Graphics::Gdi::{EnumDisplayMonitors, GetMonitorInfoW, HDC, HMONITOR, MONITORINFO};
+use log::info;
+use crate::DevDashboard;
+
+/// MONITORINFO flag of the primary display
+const MONITORINFOF_PRIMARY: u32 = 1;
+const PAGE_COUNT: usize = 3;
+
+/// Persisted wall display preferences
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct WallSettings {
+    pub monitor: usize,     // Index into the display list, primary first
+    pub page_secs: u64,     // 0 keeps the current page
+}
+
+impl Default for WallSettings {
+    fn default() -> Self {
+        Self { monitor: 1, page_secs: 15 }
+    }
+}
+
+/// A display and its bounds in physical pixels
+pub struct Monitor {
+    pub left: i32,
+    pub top: i32,
+    pub width: i32,
+    pub height: i32,
+    pub primary: bool,
+}
+
+/// State of the fullscreen wall display window
+#[derive(Default)]
+pub struct Wall {
+    pub open: bool,
+    page: usize,
+    page_started: Option<Instant>,
+    paused: bool,
+}
+
+unsafe extern "system" fn collect_monitor(monitor: HMONITOR, _hdc: HDC, _rect: *mut RECT, monitors: LPARAM) -> BOOL {
+    let monitors = &mut *(monitors.0 as *mut Vec<Monitor>);
+    let mut info = MONITORINFO { cbSize: std::mem::size_of::<MONITORINFO>() as u32, ..Default::default() };
+    if GetMonitorInfoW(monitor, &mut info).as_bool() {
+        let rect = info.rcMonitor;
+        monitors.push(Monitor {
+            left: rect.left,
+            top: rect.top,
+            width: rect.right - rect.left,
+            height: rect.bottom - rect.top,
+            primary: info.dwFlags & MONITORINFOF_PRIMARY != 0,
+        });
+    }
+    BOOL(1)
+}
+
+/// Connected displays, primary first and the rest left to right
+pub fn monitors() -> Vec<Monitor> {
+    let mut monitors: Vec<Monitor> = Vec::new();
+    unsafe { EnumDisplayMonitors(HDC(0), None, Some(collect_monitor), LPARAM(&mut monitors as *mut _ as isize)) };
+    monitors.sort_by_key(|monitor| (!monitor.primary, monitor.left, monitor.top));
+    monitors
+}
+
+/// One large reading with a label above and a detail line below
+fn big_number(ui: &mut egui::Ui, label: &str, value: &str, detail: &str, color: Color32, scale: f32) {
+    ui.vertical_centered(|ui| {
+        ui.label(RichText::new(label).font(FontId::proportional(28.0 * scale)).color(Color32::from_rgb(156, 163, 175)));
+        ui.label(RichText::new(value).font(FontId::proportional(120.0 * scale)).strong().color(color));
+        ui.label(RichText::new(detail).font(FontId::proportional(22.0 * scale)).color(Color32::from_rgb(107, 114, 128)));
+    });
+}
+
+/// A trend filling the given height; percentages scale to 100, throughput to its own peak
+fn big_graph(ui: &mut egui::Ui, values: &VecDeque<f32>, max: Option<f32>, color: Color32, height: f32) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width(), height), egui::Sense::hover());
+    let painter = ui.painter();
+    painter.rect_filled(rect, 6.0, Color32::from_rgb(31, 41, 55));
+    if values.len() < 2 {
+        return;
+    }
+    let max = max.unwrap_or_else(|| values.iter().copied().fold(1.0, f32::max));
+    let step = rect.width() / (values.len() - 1) as f32;
+    let points: Vec<egui::Pos2> = values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| egui::pos2(
+            rect.left() + index as f32 * step,
+            rect.bottom() - (value / max).clamp(0.0, 1.0) * (rect.height() - 4.0),
+        ))
+        .collect();
+    let mut fill = points.clone();
+    fill.push(egui::pos2(points[points.len() - 1].x, rect.bottom()));
+    fill.push(egui::pos2(points[0].x, rect.bottom()));
+    painter.add(egui::Shape::convex_polygon(fill, color.gamma_multiply(0.25), egui::Stroke::NONE));
+    painter.add(egui::Shape::line(points, egui::Stroke::new(3.0, color)));
+}
+
+/// Green, amber or red for a percentage
+fn load_color(percent: f32) -> Color32 {
+    if percent >= 90.0 {
+        Color32::from_rgb(220, 50, 50)
+    } else if percent >= 70.0 {
+        Color32::from_rgb(202, 138, 4)
+    } else {
+        Color32::from_rgb(22, 163, 74)
+    }
+}
+
+impl DevDashboard {
+    pub(crate) fn open_wall_display(&mut self) {
+        info!("Opening the wall display");
+        self.wall.open = true;
+        self.wall.page = 0;
+        self.wall.page_started = Some(Instant::now());
+        self.wall.paused = false;
+    }
+
+    fn show_wall_page(&self, ui: &mut egui::Ui) {
+        // Sized for a 1080p screen and scaled from there
+        let scale = (ui.available_height() / 1000.0).clamp(0.5, 3.0);
+        let history = &self.tray.history;
+        let received: f64 = self.network_stats.values().map(|stats| stats.received_speed).sum();
+        let sent: f64 = self.network_stats.values().map(|stats| stats.sent_speed).sum();
+
+        match self.wall.page {
+            0 => {
+                let cpu = self.current_cpu_usage.current;
+                let total_memory = self.sys.total_memory();
+                let memory = self.memory_usage.current * 100.0;
+                let used_gb = total_memory as f64 * self.memory_usage.current as f64 / (1024.0 * 1024.0 * 1024.0);
+                let gpu = self.gpu_info.as_ref();
+                let columns = if gpu.is_some() { 4 } else { 3 };
+                ui.add_space(ui.available_height() / 4.0);
+                ui.columns(columns, |columns| {
+                    big_number(&mut columns[0], "CPU", &format!("{:.0}%", cpu),
+                        &format!("{} threads", self.sys.cpus().len()), load_color(cpu), scale);
+                    big_number(&mut columns[1], "Memory", &format!("{:.0}%", memory),
+                        &format!("{:.1} GB used", used_gb), load_color(memory), scale);
+                    let mut next = 2;
+                    if let Some(gpu) = gpu {
+                        let utilization = gpu.utilization.unwrap_or_default();
+                        let detail = gpu.temperature.map(|temperature| format!("{}°C", temperature)).unwrap_or_default();
+                        big_number(&mut columns[2], "GPU", &format!("{:.0}%", utilization), &detail, load_color(utilization), scale);
+                        next = 3;
+                    }
+                    big_number(&mut columns[next], "Network ↓", &Self::speed_text(received as f32),
+                        &format!("↑ {}", Self::speed_text(sent as f32)), Color32::from_rgb(88, 165, 237), scale);
+                });
+            }
+            1 => {
+                let graphs = if history.gpu.is_empty() { 3.0 } else { 4.0 };
+                let height = (ui.available_height() / graphs - 60.0 * scale).max(40.0);
+                let latest = |values: &VecDeque<f32>| values.back().copied().unwrap_or_default();
+                let graph = |ui: &mut egui::Ui, label: &str, value: String, values: &VecDeque<f32>, max: Option<f32>, color: Color32| {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(label).font(FontId::proportional(28.0 * scale)).color(Color32::from_rgb(156, 163, 175)));
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            ui.label(RichText::new(value).font(FontId::proportional(36.0 * scale)).strong());
+                        });
+                    });
+                    big_graph(ui, values, max, color, height);
+                };
+                graph(ui, "CPU", format!("{:.0}%", latest(&history.cpu)), &history.cpu, Some(100.0), Color32::from_rgb(37, 99, 235));
+                graph(ui, "Memory", format!("{:.0}%", latest(&history.memory)), &history.memory, Some(100.0), Color32::from_rgb(22, 163, 74));
+                if !history.gpu.is_empty() {
+                    graph(ui, "GPU", format!("{:.0}%", latest(&history.gpu)), &history.gpu, Some(100.0), Color32::from_rgb(220, 38, 38));
+                }
+                graph(ui, "Network ↓", Self::speed_text(latest(&history.received)), &history.received, None, Color32::from_rgb(88, 165, 237));
+            }
+            _ => {
+                ui.label(RichText::new("Storage").font(FontId::proportional(36.0 * scale)).color(Color32::from_rgb(156, 163, 175)));
+                ui.add_space(16.0 * scale);
+                for volume in self.volume_monitor.volumes() {
+                    let Some((total, free)) = volume.space else { continue };
+                    let used = 1.0 - free as f32 / total.max(1) as f32;
+                    let (free_value, free_unit) = Self::format_bytes(free);
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(&volume.mount).font(FontId::monospace(40.0 * scale)).strong());
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            let text = RichText::new(format!("{:.1} {} free", free_value, free_unit)).font(FontId::proportional(32.0 * scale));
+                            if volume.online {
+                                ui.label(text);
+                            } else {
+                                ui.label(text.color(Color32::from_rgb(107, 114, 128)));
+                            }
+                        });
+                    });
+                    ui.add(egui::ProgressBar::new(used)
+                        .desired_height(28.0 * scale)
+                        .fill(load_color(used * 100.0)));
+                    ui.add_space(20.0 * scale);
+                }
+            }
+        }
+    }
+
+    /// Fullscreen window with large readings that cycles through its pages, for a second monitor or a kiosk
+    pub(crate) fn show_wall_display(&mut self, ctx: &egui::Context) {
+        if !self.wall.open {
+            return;
+        }
+        let page_secs = self.settings.wall.page_secs;
+        if let Some(started) = self.wall.page_started {
+            if !self.wall.paused && page_secs > 0 && started.elapsed() >= Duration::from_secs(page_secs) {
+                self.wall.page = (self.wall.page + 1) % PAGE_COUNT;
+                self.wall.page_started = Some(Instant::now());
+            }
+        }
+
+        let mut builder = egui::ViewportBuilder::default()
+            .with_title("Dev Dashboard - Wall Display")
+            .with_decorations(false)
+            .with_fullscreen(true);
+        // Opening on the chosen display is enough; fullscreen then covers the display the window is on
+        let monitors = monitors();
+        if let Some(monitor) = monitors.get(self.settings.wall.monitor).or(monitors.first()) {
+            let scale = ctx.native_pixels_per_point().unwrap_or(1.0);
+            builder = builder
+                .with_position([(monitor.left + 40) as f32 / scale, (monitor.top + 40) as f32 / scale])
+                .with_inner_size([monitor.width as f32 / scale / 2.0, monitor.height as f32 / scale / 2.0]);
+        }
+
+        let mut close = false;
+        ctx.show_viewport_immediate(egui::ViewportId::from_hash_of("wall_display"), builder, |ctx, _class| {
+            egui::CentralPanel::default()
+                .frame(egui::Frame::none()
+                    .fill(Color32::from_rgb(17, 24, 39))
+                    .inner_margin(egui::style::Margin::same(48.0)))
+                .show(ctx, |ui| {
+                    self.show_wall_page(ui);
+
+                    // Page dots are the only chrome, and fade into the background
+                    let rect = ui.max_rect();
+                    let center = egui::pos2(rect.center().x, rect.bottom() + 24.0);
+                    for page in 0..PAGE_COUNT {
+                        let color = if page == self.wall.page { Color32::from_rgb(156, 163, 175) } else { Color32::from_rgb(55, 65, 81) };
+                        let x = center.x + (page as f32 - (PAGE_COUNT - 1) as f32 / 2.0) * 20.0;
+                        ui.painter().circle_filled(egui::pos2(x, center.y), 4.0, color);
+                    }
+                    if self.wall.paused {
+                        ui.painter().text(egui::pos2(rect.right(), rect.bottom() + 24.0), egui::Align2::RIGHT_CENTER,
+                            "Paused", FontId::proportional(14.0), Color32::from_rgb(107, 114, 128));
+                    }
+                });
+
+            let (escape, left, right, space, double_click) = ctx.input(|i| (
+                i.key_pressed(egui::Key::Escape),
+                i.key_pressed(egui::Key::ArrowLeft),
+                i.key_pressed(egui::Key::ArrowRight),
+                i.key_pressed(egui::Key::Space),
+                i.pointer.button_double_clicked(egui::PointerButton::Primary),
+            ));
+            if left || right {
+                self.wall.page = if right { (self.wall.page + 1) % PAGE_COUNT } else { (self.wall.page + PAGE_COUNT - 1) % PAGE_COUNT };
+                self.wall.page_started = Some(Instant::now());
+            }
+            if space {
+                self.wall.paused = !self.wall.paused;
+                self.wall.page_started = Some(Instant::now());
+            }
+            if escape || double_click || ctx.input(|i| i.viewport().close_requested()) {
+                close = true;
+            }
+        });
+        if close {
+            info!("Closed the wall display");
+            self.wall.open = false;
+        }
+    }
+
+    /// Settings section for the wall display
+    pub(crate) fn show_wall_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        let monitors = monitors();
+
+        ui.label("Wall Display:");
+        ui.horizontal(|ui| {
+            ui.label("Display:");
+            let describe = |index: usize, monitor: &Monitor| format!("{}: {}×{}{}",
+                index + 1, monitor.width, monitor.height, if monitor.primary { " (primary)" } else { "" });
+            let selected = match monitors.get(self.settings.wall.monitor) {
+                Some(monitor) => describe(self.settings.wall.monitor, monitor),
+                None => "Primary (chosen display is not connected)".to_string(),
+            };
+            egui::ComboBox::from_id_source("wall_monitor")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    for (index, monitor) in monitors.iter().enumerate() {
+                        changed |= ui.selectable_value(&mut self.settings.wall.monitor, index, describe(index, monitor)).changed();
+                    }
+                });
+            ui.label("Next page every:");
+            changed |= ui.add(egui::DragValue::new(&mut self.settings.wall.page_secs).clamp_range(0..=600).suffix(" s"))
+                .on_hover_text("0 stays on one page")
+                .changed();
+        });
+        ui.horizontal(|ui| {
+            if ui.button("Open wall display").clicked() {
+                self.open_wall_display();
+            }
+            ui.label(RichText::new("Esc or double-click closes it; arrow keys change pages and Space pauses cycling")
+                .color(Color32::from_rgb(107, 114, 128)));
+        });
+
+        if changed {
+            self.save_settings();
+        }
+    }
+}
@@ -0,0 +1,335 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::process::Command;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Local};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use sysinfo::{PidExt, ProcessExt, SystemExt};
+use log::{info, warn};
+use crate::guard::{ProcessKill, SensitiveAction};
+use crate::notifications::NotificationSource;
+use crate::DevDashboard;
+
+/// Watchdog events kept for the Processes tab
+const MAX_EVENTS: usize = 50;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum WatchdogAction {
+    Alert,
+    Kill,
+    Restart,    // Kill, then start the same executable with the same arguments and directory
+}
+
+impl WatchdogAction {
+    pub(crate) fn label(&self) -> &'static str {
+        match self {
+            WatchdogAction::Alert => "Alert only",
+            WatchdogAction::Kill => "Kill",
+            WatchdogAction::Restart => "Restart",
+        }
+    }
+}
+
+/// Limits for every process with a matching name
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct WatchdogRule {
+    pub enabled: bool,
+    pub process: String,                // Executable name such as node.exe, case-insensitive
+    pub max_memory_mb: Option<u64>,
+    pub max_cpu_percent: Option<f32>,   // Share of the whole machine, like Task Manager
+    pub duration_secs: u64,             // How long a limit must be exceeded before acting
+    pub action: WatchdogAction,
+}
+
+impl Default for WatchdogRule {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            process: String::new(),
+            max_memory_mb: Some(2048),
+            max_cpu_percent: None,
+            duration_secs: 30,
+            action: WatchdogAction::Alert,
+        }
+    }
+}
+
+/// Persisted watchdog rules
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct WatchdogSettings {
+    pub rules: Vec<WatchdogRule>,
+}
+
+impl WatchdogSettings {
+    pub fn is_active(&self) -> bool {
+        self.rules.iter().any(|rule| rule.enabled && !rule.process.trim().is_empty())
+    }
+}
+
+struct WatchdogEvent {
+    time: DateTime<Local>,
+    message: String,
+    failed: bool,
+}
+
+/// Breach tracking for the watchdog rules
+#[derive(Default)]
+pub struct Watchdog {
+    over_since: HashMap<(usize, u32), Instant>,     // (rule, pid) -> first sample over a limit
+    triggered: HashSet<(usize, u32)>,               // Breaches already acted on, until the process recovers
+    events: VecDeque<WatchdogEvent>,                // Newest first
+}
+
+impl Watchdog {
+    fn push_event(&mut self, message: String, failed: bool) {
+        if failed {
+            warn!("{}", message);
+        } else {
+            info!("{}", message);
+        }
+        if self.events.len() == MAX_EVENTS {
+            self.events.pop_back();
+        }
+        self.events.push_front(WatchdogEvent { time: Local::now(), message, failed });
+    }
+
+    /// Alerts and failed actions since `cutoff`
+    pub fn alerts_since(&self, cutoff: DateTime<Local>) -> usize {
+        self.events.iter().filter(|event| event.failed && event.time >= cutoff).count()
+    }
+
+    /// Displays recent watchdog actions on the Processes tab
+    pub fn show_events(&self, ui: &mut egui::Ui, active: bool) {
+        if !active && self.events.is_empty() {
+            return;
+        }
+        let watching = self.over_since.len();
+        let title = format!("Watchdog ({} events, {} processes over a limit)", self.events.len(), watching);
+        egui::CollapsingHeader::new(title)
+            .id_source("watchdog_events")
+            .show(ui, |ui| {
+                if self.events.is_empty() {
+                    ui.label(RichText::new("No limits exceeded yet").color(Color32::from_rgb(107, 114, 128)));
+                }
+                for event in &self.events {
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(event.time.format("%H:%M:%S").to_string())
+                            .color(Color32::from_rgb(156, 163, 175)));
+                        if event.failed {
+                            ui.colored_label(Color32::from_rgb(202, 138, 4), &event.message);
+                        } else {
+                            ui.label(&event.message);
+                        }
+                    });
+                }
+            });
+        ui.add_space(8.0);
+    }
+}
+
+/// Describes which limits a process is over, or None when it is within all of them
+fn breach(rule: &WatchdogRule, memory: u64, cpu_percent: f32) -> Option<String> {
+    let mut reasons = Vec::new();
+    if let Some(limit) = rule.max_memory_mb {
+        let memory_mb = memory / (1024 * 1024);
+        if memory_mb > limit {
+            reasons.push(format!("memory {} MB > {} MB", memory_mb, limit));
+        }
+    }
+    if let Some(limit) = rule.max_cpu_percent {
+        if cpu_percent > limit {
+            reasons.push(format!("CPU {:.0}% > {:.0}%", cpu_percent, limit));
+        }
+    }
+    (!reasons.is_empty()).then(|| reasons.join(", "))
+}
+
+impl DevDashboard {
+    /// Checks running processes against the watchdog rules; expects processes to be freshly refreshed
+    pub(crate) fn update_watchdog(&mut self) {
+        if !self.settings.watchdog.is_active() {
+            self.watchdog.over_since.clear();
+            self.watchdog.triggered.clear();
+            return;
+        }
+        let cpu_count = self.sys.cpus().len().max(1) as f32;
+        let mut seen = Vec::new();
+        let mut events = Vec::new();
+
+        for (index, rule) in self.settings.watchdog.rules.iter().enumerate() {
+            if !rule.enabled || rule.process.trim().is_empty() {
+                continue;
+            }
+            let name = rule.process.trim().to_lowercase();
+            for (pid, process) in self.sys.processes() {
+                if process.name().to_lowercase() != name {
+                    continue;
+                }
+                let key = (index, pid.as_u32());
+                seen.push(key);
+                // sysinfo reports CPU per core, so a busy 8-core process can read 800%
+                let Some(reason) = breach(rule, process.memory(), process.cpu_usage() / cpu_count) else {
+                    self.watchdog.over_since.remove(&key);
+                    self.watchdog.triggered.remove(&key);
+                    continue;
+                };
+                let since = *self.watchdog.over_since.entry(key).or_insert_with(Instant::now);
+                if since.elapsed() < Duration::from_secs(rule.duration_secs) || self.watchdog.triggered.contains(&key) {
+                    continue;
+                }
+                self.watchdog.triggered.insert(key);
+
+                let subject = format!("{} (PID {})", process.name(), pid.as_u32());
+                match rule.action {
+                    WatchdogAction::Alert => {
+                        let message = format!("Watchdog: {} over limit for {}s: {}", subject, rule.duration_secs, reason);
+                        events.push((message, true));
+                    }
+                    WatchdogAction::Kill => {
+                        let (message, failed) = if process.kill() {
+                            (format!("Watchdog killed {}: {}", subject, reason), false)
+                        } else {
+                            (format!("Watchdog could not kill {}: {}", subject, reason), true)
+                        };
+                        events.push((message, failed));
+                    }
+                    WatchdogAction::Restart => {
+                        let exe = process.exe().to_path_buf();
+                        let args: Vec<String> = process.cmd().iter().skip(1).cloned().collect();
+                        let cwd = process.cwd().to_path_buf();
+                        if !process.kill() {
+                            events.push((format!("Watchdog could not kill {} for restart: {}", subject, reason), true));
+                            continue;
+                        }
+                        let mut command = Command::new(&exe);
+                        command.args(&args);
+                        if cwd.is_dir() {
+                            command.current_dir(&cwd);
+                        }
+                        let (message, failed) = match command.spawn() {
+                            Ok(child) => (format!("Watchdog restarted {} as PID {}: {}", subject, child.id(), reason), false),
+                            Err(e) => (format!("Watchdog killed {} but could not restart it: {}", subject, e), true),
+                        };
+                        events.push((message, failed));
+                    }
+                }
+            }
+        }
+        for (message, failed) in events {
+            self.notifications.push(NotificationSource::Watcher, "Watchdog", &message);
+            self.watchdog.push_event(message, failed);
+        }
+
+        // Forget processes that have exited or rules that were removed
+        self.watchdog.over_since.retain(|key, _| seen.contains(key));
+        self.watchdog.triggered.retain(|key| seen.contains(key));
+    }
+
+    /// Switches a rule to killing or restarting once the action lock has been passed
+    pub(crate) fn set_watchdog_action(&mut self, index: usize, action: WatchdogAction) {
+        let Some(rule) = self.settings.watchdog.rules.get_mut(index) else { return };
+        rule.action = action;
+        self.save_settings();
+    }
+
+    /// Settings section for process watchdog rules
+    pub(crate) fn show_watchdog_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        let mut removed = None;
+        let mut armed = None;
+
+        ui.label("Process Watchdog:");
+        for (index, rule) in self.settings.watchdog.rules.iter_mut().enumerate() {
+            let title = if rule.process.trim().is_empty() { "New rule".to_string() } else { rule.process.clone() };
+            egui::CollapsingHeader::new(title)
+                .id_source(("watchdog_rule", index))
+                .show(ui, |ui| {
+                    egui::Grid::new(("watchdog_rule_grid", index)).num_columns(2).show(ui, |ui| {
+                        ui.label("Enabled:");
+                        changed |= ui.checkbox(&mut rule.enabled, "").changed();
+                        ui.end_row();
+                        ui.label("Process:");
+                        changed |= ui.add(egui::TextEdit::singleline(&mut rule.process).hint_text("node.exe")).changed();
+                        ui.end_row();
+
+                        ui.label("Memory limit (MB):");
+                        ui.horizontal(|ui| {
+                            let mut limited = rule.max_memory_mb.is_some();
+                            if ui.checkbox(&mut limited, "").changed() {
+                                rule.max_memory_mb = limited.then_some(2048);
+                                changed = true;
+                            }
+                            if let Some(limit) = &mut rule.max_memory_mb {
+                                changed |= ui.add(egui::DragValue::new(limit).clamp_range(16..=1_048_576)).changed();
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("CPU limit (%):");
+                        ui.horizontal(|ui| {
+                            let mut limited = rule.max_cpu_percent.is_some();
+                            if ui.checkbox(&mut limited, "").changed() {
+                                rule.max_cpu_percent = limited.then_some(50.0);
+                                changed = true;
+                            }
+                            if let Some(limit) = &mut rule.max_cpu_percent {
+                                changed |= ui.add(egui::DragValue::new(limit).clamp_range(1.0..=100.0)).changed();
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("For at least (s):");
+                        changed |= ui.add(egui::DragValue::new(&mut rule.duration_secs).clamp_range(1..=3600)).changed();
+                        ui.end_row();
+
+                        ui.label("Then:");
+                        egui::ComboBox::from_id_source(("watchdog_action", index))
+                            .selected_text(rule.action.label())
+                            .show_ui(ui, |ui| {
+                                for action in [WatchdogAction::Alert, WatchdogAction::Kill, WatchdogAction::Restart] {
+                                    if ui.selectable_label(rule.action == action, action.label()).clicked() && rule.action != action {
+                                        if action == WatchdogAction::Alert {
+                                            rule.action = action;
+                                            changed = true;
+                                        } else {
+                                            // Letting the watchdog end processes goes through the action lock
+                                            armed = Some(ProcessKill::WatchdogRule { rule: index, process: rule.process.clone(), action });
+                                        }
+                                    }
+                                }
+                            });
+                        ui.end_row();
+                    });
+                    if ui.button("✖ Remove").clicked() {
+                        removed = Some(index);
+                    }
+                });
+        }
+        if let Some(index) = removed {
+            self.settings.watchdog.rules.remove(index);
+            // Rule indices shift, so start tracking from scratch
+            self.watchdog.over_since.clear();
+            self.watchdog.triggered.clear();
+            changed = true;
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("Add watchdog rule").clicked() {
+                self.settings.watchdog.rules.push(WatchdogRule::default());
+                changed = true;
+            }
+            ui.label(RichText::new("Kill and restart only work on processes you have rights to end")
+                .color(Color32::from_rgb(107, 114, 128)));
+        });
+
+        if changed {
+            self.save_settings();
+        }
+        if let Some(kill) = armed {
+            self.request_sensitive_action(SensitiveAction::KillProcess(kill));
+        }
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use wmi::{COMLibrary, Variant, WMIConnection};
+use log::warn;
+use crate::plugins::{self, CardContent, CardRow};
+use crate::DevDashboard;
+
+const DEFAULT_NAMESPACE: &str = "root\\cimv2";
+/// Rows shown per card; queries over large classes are truncated
+const MAX_ROWS: usize = 25;
+
+/// A user-defined card backed by a WQL query
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct WmiCardConfig {
+    pub title: String,
+    pub namespace: String,
+    pub query: String,
+    pub template: String,           // "{Name} | {LoadPercentage}%", one card row per result
+    pub interval_secs: u64,
+}
+
+impl Default for WmiCardConfig {
+    fn default() -> Self {
+        Self {
+            title: "WMI".to_string(),
+            namespace: DEFAULT_NAMESPACE.to_string(),
+            query: String::new(),
+            template: String::new(),
+            interval_secs: 30,
+        }
+    }
+}
+
+/// Persisted custom WMI cards
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct WmiCardSettings {
+    pub cards: Vec<WmiCardConfig>,
+}
+
+type QueryResult = Result<Vec<HashMap<String, String>>, String>;
+
+#[derive(Default)]
+struct WmiCardState {
+    last_run: Option<Instant>,
+    receiver: Option<Receiver<QueryResult>>,
+    content: Option<CardContent>,
+}
+
+/// Query results for each configured card, in settings order
+#[derive(Default)]
+pub struct WmiCards {
+    states: Vec<WmiCardState>,
+}
+
+fn variant_to_string(value: &Variant) -> String {
+    match value {
+        Variant::String(text) => text.clone(),
+        Variant::I1(number) => number.to_string(),
+        Variant::I2(number) => number.to_string(),
+        Variant::I4(number) => number.to_string(),
+        Variant::I8(number) => number.to_string(),
+        Variant::UI1(number) => number.to_string(),
+        Variant::UI2(number) => number.to_string(),
+        Variant::UI4(number) => number.to_string(),
+        Variant::UI8(number) => number.to_string(),
+        Variant::R4(number) => number.to_string(),
+        Variant::R8(number) => number.to_string(),
+        Variant::Bool(value) => value.to_string(),
+        Variant::Array(values) => values.iter().map(variant_to_string).collect::<Vec<_>>().join(", "),
+        _ => String::new(),
+    }
+}
+
+/// Runs on a worker thread; COM is initialized per thread
+fn run_query(namespace: &str, query: &str) -> QueryResult {
+    let com = COMLibrary::new().map_err(|e| format!("COM initialization failed: {}", e))?;
+    let connection = WMIConnection::with_namespace_path(namespace, com)
+        .map_err(|e| format!("Cannot open namespace {}: {}", namespace, e))?;
+    let rows: Vec<HashMap<String, Variant>> = connection.raw_query(query).map_err(|e| e.to_string())?;
+    Ok(rows
+        .into_iter()
+        .take(MAX_ROWS)
+        .map(|row| row.iter().map(|(name, value)| (name.to_lowercase(), variant_to_string(value))).collect())
+        .collect())
+}
+
+/// Formats one placeholder; `{Size:bytes}` shows byte counts with units
+fn placeholder_value(row: &HashMap<String, String>, placeholder: &str) -> String {
+    let (name, format) = placeholder.split_once(':').unwrap_or((placeholder, ""));
+    let Some(value) = row.get(&name.trim().to_lowercase()) else { return "?".to_string() };
+    match format.trim() {
+        "bytes" => match value.parse::<u64>() {
+            Ok(bytes) => {
+                let (size, unit) = DevDashboard::format_bytes(bytes);
+                format!("{:.1} {}", size, unit)
+            }
+            Err(_) => value.clone(),
+        },
+        _ => value.clone(),
+    }
+}
+
+/// Replaces every {Property} in the template with the row's value
+fn render_template(template: &str, row: &HashMap<String, String>) -> String {
+    let mut output = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+        output.push_str(&placeholder_value(row, &rest[start + 1..start + end]));
+        rest = &rest[start + end + 1..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Without a template every property becomes its own row, which helps when exploring a class
+fn build_content(template: &str, result: QueryResult) -> CardContent {
+    let rows = match result {
+        Ok(rows) => rows,
+        Err(e) => return CardContent::error(e),
+    };
+    if rows.is_empty() {
+        return CardContent::error("Query returned no results");
+    }
+    let mut content = CardContent::default();
+    for row in &rows {
+        if template.trim().is_empty() {
+            let mut properties: Vec<_> = row.iter().collect();
+            properties.sort();
+            content.rows.extend(properties.into_iter().map(|(name, value)| CardRow {
+                label: name.clone(),
+                value: value.clone(),
+                ..Default::default()
+            }));
+            continue;
+        }
+        let line = render_template(template, row);
+        let (label, value) = line.split_once('|').unwrap_or((&line, ""));
+        content.rows.push(CardRow {
+            label: label.trim().to_string(),
+            value: value.trim().to_string(),
+            ..Default::default()
+        });
+    }
+    content
+}
+
+impl DevDashboard {
+    /// Starts queries that are due and collects finished ones
+    pub(crate) fn update_wmi_cards(&mut self) {
+        let configs = &self.settings.wmi_cards.cards;
+        self.wmi_cards.states.resize_with(configs.len(), WmiCardState::default);
+
+        for (config, state) in configs.iter().zip(self.wmi_cards.states.iter_mut()) {
+            if let Some(receiver) = &state.receiver {
+                if let Ok(result) = receiver.try_recv() {
+                    if let Err(e) = &result {
+                        warn!("WMI card \"{}\" query failed: {}", config.title, e);
+                    }
+                    state.content = Some(build_content(&config.template, result));
+                    state.receiver = None;
+                }
+                continue;
+            }
+            let interval = Duration::from_secs(config.interval_secs.max(5));
+            if state.last_run.is_some_and(|last| last.elapsed() < interval) || config.query.trim().is_empty() {
+                continue;
+            }
+
+            let (sender, receiver) = channel();
+            let namespace = config.namespace.trim().to_string();
+            let query = config.query.trim().to_string();
+            std::thread::spawn(move || {
+                sender.send(run_query(&namespace, &query)).ok();
+            });
+            state.receiver = Some(receiver);
+            state.last_run = Some(Instant::now());
+        }
+    }
+
+    /// Displays one custom WMI card
+    pub(crate) fn show_wmi_card(&self, ui: &mut egui::Ui, index: usize) {
+        let Some(config) = self.settings.wmi_cards.cards.get(index) else { return };
+        let content = self.wmi_cards.states.get(index).and_then(|state| state.content.as_ref());
+        self.show_card(ui, &config.title, |ui| match content {
+            Some(content) => plugins::show_card_rows(ui, content),
+            None => {
+                ui.spinner();
+            }
+        });
+    }
+
+    /// Rows of one WMI card, for copying it
+    pub(crate) fn wmi_card_values(&self, index: usize) -> Vec<(String, String)> {
+        self.wmi_cards.states.get(index)
+            .and_then(|state| state.content.as_ref())
+            .map(CardContent::values)
+            .unwrap_or_default()
+    }
+
+    /// Settings section for building custom WMI cards
+    pub(crate) fn show_wmi_card_settings(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        let mut removed = None;
+
+        ui.label("Custom WMI Cards:");
+        for (index, config) in self.settings.wmi_cards.cards.iter_mut().enumerate() {
+            let mut rerun = false;
+            egui::CollapsingHeader::new(config.title.clone())
+                .id_source(("wmi_card", index))
+                .show(ui, |ui| {
+                    egui::Grid::new(("wmi_card_grid", index)).num_columns(2).show(ui, |ui| {
+                        ui.label("Title:");
+                        changed |= ui.text_edit_singleline(&mut config.title).changed();
+                        ui.end_row();
+                        ui.label("Namespace:");
+                        rerun |= ui.text_edit_singleline(&mut config.namespace).lost_focus();
+                        ui.end_row();
+                        ui.label("Query:");
+                        rerun |= ui.add(egui::TextEdit::multiline(&mut config.query)
+                            .hint_text("SELECT Name, LoadPercentage FROM Win32_Processor")
+                            .desired_rows(2)).lost_focus();
+                        ui.end_row();
+                        ui.label("Template:");
+                        rerun |= ui.add(egui::TextEdit::singleline(&mut config.template)
+                            .hint_text("{Name} | {LoadPercentage}%"))
+                            .on_hover_text("One row per result; text after | is right-aligned. {Prop:bytes} formats sizes. Leave empty to list every property.")
+                            .lost_focus();
+                        ui.end_row();
+                        ui.label("Refresh (s):");
+                        changed |= ui.add(egui::DragValue::new(&mut config.interval_secs).clamp_range(5..=3600)).changed();
+                        ui.end_row();
+                    });
+                    ui.horizontal(|ui| {
+                        rerun |= ui.button("⟳ Run now").clicked();
+                        if ui.button("✖ Remove").clicked() {
+                            removed = Some(index);
+                        }
+                    });
+                    if let Some(Some(CardContent { error: Some(error), .. })) = self.wmi_cards.states.get(index).map(|state| &state.content) {
+                        ui.colored_label(Color32::from_rgb(220, 50, 50), error);
+                    }
+                });
+            if rerun {
+                if let Some(state) = self.wmi_cards.states.get_mut(index) {
+                    state.last_run = None;
+                }
+                changed = true;
+            }
+        }
+        if let Some(index) = removed {
+            self.settings.wmi_cards.cards.remove(index);
+            if index < self.wmi_cards.states.len() {
+                self.wmi_cards.states.remove(index);
+            }
+            changed = true;
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("Add WMI card").clicked() {
+                self.settings.wmi_cards.cards.push(WmiCardConfig::default());
+                changed = true;
+            }
+            ui.label(RichText::new("Read-only WQL queries; results refresh in the background")
+                .color(Color32::from_rgb(107, 114, 128)));
+        });
+
+        if changed {
+            self.save_settings();
+        }
+    }
+}
@@ -0,0 +1,321 @@
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+use eframe::egui;
+use egui::{Color32, RichText};
+use serde::{Serialize, Deserialize};
+use sysinfo::{ProcessExt, SystemExt};
+use tokio::process::Command as TokioCommand;
+use winreg::enums::HKEY_CURRENT_USER;
+use winreg::RegKey;
+use log::{info, warn};
+use crate::guard::{SensitiveAction, ServiceControl};
+use crate::{DevDashboard, CREATE_NO_WINDOW};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+/// Starting a distro can include booting the WSL 2 virtual machine
+const WSL_TIMEOUT: Duration = Duration::from_secs(60);
+/// Registered distros, one subkey per distro
+const LXSS_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Lxss";
+/// Process of the WSL 2 virtual machine; WSL from the Store names it vmmemWSL
+const VMMEM_NAMES: [&str; 2] = ["vmmem", "vmmemwsl"];
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct WslSettings {
+    pub show_card: bool,
+}
+
+#[derive(Clone)]
+pub struct Distro {
+    pub name: String,
+    pub version: u32,                // 1 or 2
+    pub default: bool,               // Started by a plain `wsl`
+    pub running: bool,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub(crate) enum WslAction {
+    Start(String),
+    Terminate(String),
+    Shutdown,
+}
+
+impl WslAction {
+    fn args(&self) -> Vec<&str> {
+        match self {
+            // Boots the distro; WSL stops it again a while after its last process exits
+            WslAction::Start(name) => vec!["--distribution", name.as_str(), "--exec", "true"],
+            WslAction::Terminate(name) => vec!["--terminate", name.as_str()],
+            WslAction::Shutdown => vec!["--shutdown"],
+        }
+    }
+
+    pub(crate) fn description(&self) -> String {
+        match self {
+            WslAction::Start(name) => format!("Start {}", name),
+            WslAction::Terminate(name) => format!("Terminate {}", name),
+            WslAction::Shutdown => "Shut down WSL".to_string(),
+        }
+    }
+
+    /// Distro the action is for, to show its spinner; None for the whole of WSL
+    fn distro(&self) -> Option<&str> {
+        match self {
+            WslAction::Start(name) | WslAction::Terminate(name) => Some(name.as_str()),
+            WslAction::Shutdown => None,
+        }
+    }
+}
+
+/// Distros from the registry, which unlike `wsl -l -v` isn't localized. Default distro first.
+fn registered_distros() -> Vec<Distro> {
+    let Ok(lxss) = RegKey::predef(HKEY_CURRENT_USER).open_subkey(LXSS_KEY) else { return Vec::new() };
+    let default: String = lxss.get_value("DefaultDistribution").unwrap_or_default();
+    let mut distros: Vec<Distro> = lxss
+        .enum_keys()
+        .filter_map(Result::ok)
+        .filter_map(|id| {
+            let key = lxss.open_subkey(&id).ok()?;
+            let name: String = key.get_value("DistributionName").ok()?;
+            let version: u32 = key.get_value("Version").unwrap_or(2);
+            Some(Distro { name, version, default: id.eq_ignore_ascii_case(&default), running: false })
+        })
+        .collect();
+    distros.sort_by(|a, b| b.default.cmp(&a.default).then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase())));
+    distros
+}
+
+/// wsl.exe writes UTF-16 unless told otherwise, and older builds ignore WSL_UTF8
+fn decode_output(bytes: &[u8]) -> String {
+    if bytes.contains(&0) {
+        let units: Vec<u16> = bytes.chunks_exact(2).map(|pair| u16::from_le_bytes([pair[0], pair[1]])).collect();
+        String::from_utf16_lossy(&units).trim_start_matches('\u{feff}').to_string()
+    } else {
+        String::from_utf8_lossy(bytes).to_string()
+    }
+}
+
+/// Runs wsl.exe and returns whether it succeeded with its combined output
+async fn run_wsl(args: &[&str]) -> Result<(bool, String), String> {
+    let child = TokioCommand::new("wsl")
+        .args(args)
+        .env("WSL_UTF8", "1")
+        .creation_flags(CREATE_NO_WINDOW)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Could not run wsl: {}", e))?;
+    let output = tokio::time::timeout(WSL_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| format!("wsl did not answer within {}s", WSL_TIMEOUT.as_secs()))?
+        .map_err(|e| e.to_string())?;
+    let text = format!("{}{}", decode_output(&output.stdout), decode_output(&output.stderr));
+    Ok((output.status.success(), text))
+}
+
+/// Registered distros with which ones are running. `--running --quiet` prints only names, so any
+/// message it prints instead, such as "There are no running distributions", matches no distro.
+async fn list_distros() -> Vec<Distro> {
+    let mut distros = registered_distros();
+    if distros.is_empty() {
+        return distros;
+    }
+    if let Ok((_, output)) = run_wsl(&["--list", "--running", "--quiet"]).await {
+        let running: Vec<&str> = output.lines().map(str::trim).collect();
+        for distro in &mut distros {
+            distro.running = running.contains(&distro.name.as_str());
+        }
+    }
+    distros
+}
+
+async fn run_action(action: WslAction) -> Result<(), String> {
+    let (success, output) = run_wsl(&action.args()).await?;
+    if success {
+        Ok(())
+    } else {
+        Err(output.lines().map(str::trim).find(|line| !line.is_empty()).unwrap_or("wsl failed").to_string())
+    }
+}
+
+/// Installed WSL distros and the virtual machine's memory, for the WSL card
+#[derive(Default)]
+pub struct WslMonitor {
+    pub distros: Vec<Distro>,
+    pub vmmem: Option<u64>,          // Bytes the WSL 2 virtual machine holds; None while it isn't running
+    loaded: bool,                    // The first listing has come in
+    last_refresh: Option<Instant>,
+    receiver: Option<Receiver<Vec<Distro>>>,
+    pending: Option<WslAction>,
+    action_receiver: Option<Receiver<Result<(), String>>>,
+    message: Option<(String, bool)>, // Outcome of the last action; true for a failure
+}
+
+impl WslMonitor {
+    fn poll(&mut self) {
+        if let Some(distros) = self.receiver.as_ref().and_then(|receiver| receiver.try_recv().ok()) {
+            self.receiver = None;
+            self.distros = distros;
+            self.loaded = true;
+        }
+        if let Some(result) = self.action_receiver.as_ref().and_then(|receiver| receiver.try_recv().ok()) {
+            self.action_receiver = None;
+            if let Some(action) = self.pending.take() {
+                self.message = Some(match result {
+                    Ok(()) => (format!("{}: done", action.description()), false),
+                    Err(e) => {
+                        warn!("{} failed: {}", action.description(), e);
+                        (format!("{} failed: {}", action.description(), e), true)
+                    }
+                });
+            }
+            // Show the new state right away
+            self.last_refresh = None;
+        }
+    }
+
+    fn needs_refresh(&self) -> bool {
+        self.receiver.is_none() && match self.last_refresh {
+            Some(last) => last.elapsed() >= REFRESH_INTERVAL,
+            None => true,
+        }
+    }
+}
+
+impl DevDashboard {
+    /// Refreshes the distro list when due and reads vmmem's memory; runs once a second while the card is shown
+    pub(crate) fn update_wsl(&mut self) {
+        if !self.settings.wsl.show_card {
+            return;
+        }
+        self.wsl.vmmem = self.sys.processes().values()
+            .filter(|process| VMMEM_NAMES.contains(&process.name().trim_end_matches(".exe").to_lowercase().as_str()))
+            .map(|process| process.memory())
+            .reduce(|a, b| a + b);
+
+        self.wsl.poll();
+        if !self.wsl.needs_refresh() {
+            return;
+        }
+        let (sender, receiver) = channel();
+        self.wsl.receiver = Some(receiver);
+        self.wsl.last_refresh = Some(Instant::now());
+        self.runtime().spawn(async move {
+            sender.send(list_distros().await).ok();
+        });
+    }
+
+    pub(crate) fn start_wsl_action(&mut self, action: WslAction) {
+        info!("WSL: {}", action.description());
+        let (sender, receiver) = channel();
+        self.wsl.pending = Some(action.clone());
+        self.wsl.action_receiver = Some(receiver);
+        self.wsl.message = None;
+        self.runtime().spawn(async move {
+            sender.send(run_action(action).await).ok();
+        });
+    }
+
+    /// Displays WSL distros with their state and the virtual machine's memory
+    pub(crate) fn show_wsl_card(&mut self, ui: &mut egui::Ui) {
+        let grey = Color32::from_rgb(107, 114, 128);
+        let mut requested = None;
+        let wsl = &self.wsl;
+        self.show_card(ui, "WSL", |ui| {
+            match wsl.vmmem {
+                Some(bytes) => {
+                    let (value, unit) = DevDashboard::format_bytes(bytes);
+                    let share = bytes as f64 / self.sys.total_memory().max(1) as f64;
+                    let text = RichText::new(format!("vmmem: {:.1} {}", value, unit)).strong();
+                    // Over a third of RAM is worth a .wslconfig memory limit
+                    let text = if share > 0.33 { text.color(Color32::from_rgb(202, 138, 4)) } else { text };
+                    ui.label(text).on_hover_text(format!("{:.0}% of installed memory, held by the WSL 2 virtual machine", share * 100.0));
+                }
+                None => {
+                    ui.label(RichText::new("WSL 2 virtual machine not running").color(grey));
+                }
+            }
+            ui.add_space(8.0);
+
+            if wsl.loaded && wsl.distros.is_empty() {
+                ui.label(RichText::new("No distros installed").color(grey));
+                return;
+            }
+            let pending = wsl.pending.as_ref();
+            egui::Grid::new("wsl_grid")
+                .striped(true)
+                .spacing([12.0, 4.0])
+                .show(ui, |ui| {
+                    for distro in &wsl.distros {
+                        let name = ui.label(if distro.default { RichText::new(&distro.name).strong() } else { RichText::new(&distro.name) });
+                        if distro.default {
+                            name.on_hover_text("Default distro");
+                        }
+                        if distro.running {
+                            ui.colored_label(Color32::from_rgb(22, 163, 74), "Running");
+                        } else {
+                            ui.label(RichText::new("Stopped").color(grey));
+                        }
+                        ui.label(format!("WSL {}", distro.version));
+                        if pending.is_some_and(|action| action.distro() == Some(distro.name.as_str())) {
+                            ui.spinner();
+                        } else {
+                            let action = if distro.running {
+                                WslAction::Terminate(distro.name.clone())
+                            } else {
+                                WslAction::Start(distro.name.clone())
+                            };
+                            let label = if distro.running { "Terminate" } else { "Start" };
+                            if ui.add_enabled(pending.is_none(), egui::Button::new(label).small()).clicked() {
+                                requested = Some(action);
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if pending == Some(&WslAction::Shutdown) {
+                    ui.spinner();
+                } else if ui.add_enabled(pending.is_none() && (wsl.distros.iter().any(|distro| distro.running) || wsl.vmmem.is_some()),
+                    egui::Button::new("Shut down WSL"))
+                    .on_hover_text("wsl --shutdown: stops every distro and the virtual machine, freeing its memory")
+                    .clicked() {
+                    requested = Some(WslAction::Shutdown);
+                }
+            });
+            if let Some((message, failed)) = &wsl.message {
+                let color = if *failed { Color32::from_rgb(220, 50, 50) } else { grey };
+                ui.colored_label(color, message);
+            }
+        });
+        if let Some(action) = requested {
+            self.request_sensitive_action(SensitiveAction::ControlService(ServiceControl::Wsl(action)));
+        }
+    }
+
+    /// The VM's memory and each distro's state, for copying the card
+    pub(crate) fn wsl_card_values(&self) -> Vec<(String, String)> {
+        let mut values = Vec::new();
+        if let Some(bytes) = self.wsl.vmmem {
+            let (value, unit) = DevDashboard::format_bytes(bytes);
+            values.push(("vmmem".to_string(), format!("{:.1} {}", value, unit)));
+        }
+        for distro in &self.wsl.distros {
+            let state = if distro.running { "Running" } else { "Stopped" };
+            values.push((distro.name.clone(), format!("{}, WSL {}", state, distro.version)));
+        }
+        values
+    }
+
+    pub(crate) fn show_wsl_settings(&mut self, ui: &mut egui::Ui) {
+        if ui.checkbox(&mut self.settings.wsl.show_card, "Show WSL card")
+            .on_hover_text("Installed distros with start/terminate buttons and the WSL 2 virtual machine's memory")
+            .changed() {
+            self.save_settings();
+        }
+    }
+}